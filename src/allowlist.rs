@@ -1,10 +1,17 @@
 //! Allowlist file parsing and layered loading.
 //!
-//! This module implements loading of allowlist entries from three layers:
+//! This module implements loading of allowlist entries from four layers,
+//! listed from highest to lowest precedence:
+//! - Directory: nearest `.dcg/allowlist.toml` between the working directory and
+//!   the repo root (exclusive), for per-directory exceptions inside a repo
 //! - Project: `.dcg/allowlist.toml` at repo root
 //! - User: `~/.config/dcg/allowlist.toml`
 //! - System: `/etc/dcg/allowlist.toml` (optional)
 //!
+//! The first layer with a matching entry wins. Rule selectors are scoped to a
+//! single pack (`core.git:reset-hard`) or to every rule in one pack
+//! (`core.git:*`); `dcg allowlist explain` shows which layer would apply.
+//!
 //! Test override:
 //! - `DCG_ALLOWLIST_SYSTEM_PATH` can override the system allowlist path
 //!   (useful for hermetic E2E tests).
@@ -12,7 +19,7 @@
 //! Design goals:
 //! - Strongly-typed model (`AllowEntry`, `AllowSelector`)
 //! - Robust parsing: invalid TOML or invalid entries must not crash the hook
-//! - Explicit, testable layering precedence (project > user > system)
+//! - Explicit, testable layering precedence (directory > project > user > system)
 
 use std::collections::HashMap;
 use std::fs;
@@ -21,6 +28,7 @@ use std::path::{Path, PathBuf};
 /// Allowlist layer identity (used for precedence and diagnostics).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AllowlistLayer {
+    Directory,
     Project,
     User,
     System,
@@ -30,6 +38,7 @@ impl AllowlistLayer {
     #[must_use]
     pub const fn label(&self) -> &'static str {
         match self {
+            Self::Directory => "directory",
             Self::Project => "project",
             Self::User => "user",
             Self::System => "system",
@@ -165,7 +174,7 @@ pub struct LoadedAllowlistLayer {
     pub file: AllowlistFile,
 }
 
-/// All allowlist layers, ordered by precedence (project > user > system).
+/// All allowlist layers, ordered by precedence (directory > project > user > system).
 #[derive(Debug, Clone, Default)]
 pub struct LayeredAllowlist {
     pub layers: Vec<LoadedAllowlistLayer>,
@@ -181,36 +190,37 @@ impl LayeredAllowlist {
         user: Option<PathBuf>,
         system: Option<PathBuf>,
     ) -> Self {
-        let mut layers: Vec<LoadedAllowlistLayer> = Vec::new();
-
-        if let Some(path) = project {
-            layers.push(LoadedAllowlistLayer {
-                layer: AllowlistLayer::Project,
-                path: path.clone(),
-                file: load_allowlist_file(AllowlistLayer::Project, &path),
-            });
-        }
-
-        if let Some(path) = user {
-            layers.push(LoadedAllowlistLayer {
-                layer: AllowlistLayer::User,
-                path: path.clone(),
-                file: load_allowlist_file(AllowlistLayer::User, &path),
-            });
-        }
+        Self::load_from_layers([
+            (AllowlistLayer::Project, project),
+            (AllowlistLayer::User, user),
+            (AllowlistLayer::System, system),
+        ])
+    }
 
-        if let Some(path) = system {
-            layers.push(LoadedAllowlistLayer {
-                layer: AllowlistLayer::System,
-                path: path.clone(),
-                file: load_allowlist_file(AllowlistLayer::System, &path),
-            });
-        }
+    /// Construct a layered allowlist from explicitly ordered `(layer, path)` pairs.
+    ///
+    /// Layers are consulted in the order given; `None` paths are skipped and any
+    /// missing file is treated as an empty allowlist for that layer.
+    #[must_use]
+    pub fn load_from_layers(
+        layers: impl IntoIterator<Item = (AllowlistLayer, Option<PathBuf>)>,
+    ) -> Self {
+        let layers = layers
+            .into_iter()
+            .filter_map(|(layer, path)| {
+                let path = path?;
+                Some(LoadedAllowlistLayer {
+                    layer,
+                    file: load_allowlist_file(layer, &path),
+                    path,
+                })
+            })
+            .collect();
 
         Self { layers }
     }
 
-    /// Find the first matching rule entry across layers (project > user > system).
+    /// Find the first matching rule entry across layers (directory > project > user > system).
     ///
     /// Note: This performs exact rule ID matching without wildcard expansion.
     /// Use `match_rule` for wildcard-aware matching.
@@ -249,36 +259,35 @@ impl LayeredAllowlist {
         pattern_name: &str,
         cwd: Option<&Path>,
     ) -> Option<AllowlistHit<'_>> {
-        if pack_id == "*" {
-            // Never allow global bypass via wildcard pack id.
-            return None;
-        }
-
-        for layer in &self.layers {
-            for entry in &layer.file.entries {
-                // Skip entries that are invalid or don't match path restrictions
-                if !is_entry_valid_at_path(entry, cwd) {
-                    continue;
-                }
-
-                let AllowSelector::Rule(rule_id) = &entry.selector else {
-                    continue;
-                };
-
-                if rule_id.pack_id != pack_id {
-                    continue;
-                }
-
-                if rule_id.pattern_name == pattern_name || rule_id.pattern_name == "*" {
-                    return Some(AllowlistHit {
-                        layer: layer.layer,
-                        entry,
-                    });
-                }
-            }
-        }
+        self.layers.iter().find_map(|layer| {
+            layer
+                .match_rule_at_path(pack_id, pattern_name, cwd)
+                .map(|entry| AllowlistHit {
+                    layer: layer.layer,
+                    entry,
+                })
+        })
+    }
 
-        None
+    /// Report, for every layer in precedence order, which entry (if any) matches a rule.
+    ///
+    /// The first layer with `entry: Some(..)` is the one `match_rule_at_path` would use;
+    /// lower layers are still reported so shadowed entries are visible.
+    #[must_use]
+    pub fn explain_rule_at_path(
+        &self,
+        pack_id: &str,
+        pattern_name: &str,
+        cwd: Option<&Path>,
+    ) -> Vec<AllowlistLayerMatch<'_>> {
+        self.layers
+            .iter()
+            .map(|layer| AllowlistLayerMatch {
+                layer: layer.layer,
+                path: &layer.path,
+                entry: layer.match_rule_at_path(pack_id, pattern_name, cwd),
+            })
+            .collect()
     }
 
     /// Find the first allowlist entry that matches a rule (backward-compatible, no path filtering).
@@ -387,6 +396,46 @@ impl LayeredAllowlist {
     }
 }
 
+impl LoadedAllowlistLayer {
+    /// Find the first entry in this layer that matches a `(pack_id, pattern_name)` identity.
+    ///
+    /// Same matching rules as [`LayeredAllowlist::match_rule_at_path`], for a single layer.
+    #[must_use]
+    pub fn match_rule_at_path(
+        &self,
+        pack_id: &str,
+        pattern_name: &str,
+        cwd: Option<&Path>,
+    ) -> Option<&AllowEntry> {
+        if pack_id == "*" {
+            // Never allow global bypass via wildcard pack id.
+            return None;
+        }
+
+        self.file.entries.iter().find(|entry| {
+            // Skip entries that are invalid or don't match path restrictions
+            if !is_entry_valid_at_path(entry, cwd) {
+                return false;
+            }
+
+            let AllowSelector::Rule(rule_id) = &entry.selector else {
+                return false;
+            };
+
+            rule_id.pack_id == pack_id
+                && (rule_id.pattern_name == pattern_name || rule_id.pattern_name == "*")
+        })
+    }
+}
+
+/// Per-layer result of [`LayeredAllowlist::explain_rule_at_path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowlistLayerMatch<'a> {
+    pub layer: AllowlistLayer,
+    pub path: &'a Path,
+    pub entry: Option<&'a AllowEntry>,
+}
+
 /// A successful allowlist match (borrowed view).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AllowlistHit<'a> {
@@ -889,9 +938,11 @@ pub fn resolve_path_for_matching(
 /// Invalid TOML is treated as empty for that layer and reported in `errors`.
 #[must_use]
 pub fn load_default_allowlists() -> LayeredAllowlist {
-    let project = std::env::current_dir()
-        .ok()
-        .and_then(|cwd| find_repo_root(&cwd))
+    let cwd = std::env::current_dir().ok();
+    let directory = cwd.as_deref().and_then(find_directory_allowlist);
+    let project = cwd
+        .as_deref()
+        .and_then(find_repo_root)
         .map(|root| root.join(".dcg").join("allowlist.toml"));
 
     // Check XDG-style path first (~/.config/dcg/), then platform-native
@@ -914,7 +965,35 @@ pub fn load_default_allowlists() -> LayeredAllowlist {
         },
     );
 
-    LayeredAllowlist::load_from_paths(project, user, system)
+    LayeredAllowlist::load_from_layers([
+        (AllowlistLayer::Directory, directory),
+        (AllowlistLayer::Project, project),
+        (AllowlistLayer::User, user),
+        (AllowlistLayer::System, system),
+    ])
+}
+
+/// Find the per-directory allowlist for `cwd`.
+///
+/// Walks from `cwd` towards the repo root and returns the nearest existing
+/// `.dcg/allowlist.toml`, excluding the repo root itself (that file is the
+/// project layer). Outside a repo there is no directory layer: in a shared
+/// directory such as `/tmp`, anyone who can write there could plant one.
+#[must_use]
+pub fn find_directory_allowlist(cwd: &Path) -> Option<PathBuf> {
+    let repo_root = find_repo_root(cwd)?;
+    let mut current = cwd.to_path_buf();
+
+    while current.starts_with(&repo_root) && current != repo_root {
+        let candidate = current.join(".dcg").join("allowlist.toml");
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if !current.pop() {
+            break;
+        }
+    }
+    None
 }
 
 fn find_repo_root(start: &Path) -> Option<PathBuf> {
@@ -1337,6 +1416,90 @@ mod tests {
         assert_eq!(entry.reason, "project reason");
     }
 
+    #[test]
+    fn directory_layer_rescues_command_global_layer_does_not() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path().join("repo");
+        let subdir = repo.join("migrations");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(subdir.join(".dcg")).unwrap();
+
+        let directory_path = subdir.join(".dcg").join("allowlist.toml");
+        std::fs::write(
+            &directory_path,
+            r#"
+            [[allow]]
+            rule = "core.git:reset-hard"
+            reason = "migrations dir resets scratch branches"
+        "#,
+        )
+        .unwrap();
+        let user_path = temp.path().join("user-allowlist.toml");
+        std::fs::write(
+            &user_path,
+            r#"
+            [[allow]]
+            rule = "core.git:clean-force"
+            reason = "unrelated global entry"
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_directory_allowlist(&subdir),
+            Some(directory_path.clone())
+        );
+        assert_eq!(find_directory_allowlist(&repo), None);
+
+        let global_only =
+            LayeredAllowlist::load_from_layers([(AllowlistLayer::User, Some(user_path.clone()))]);
+        assert!(global_only.match_rule("core.git", "reset-hard").is_none());
+
+        let allowlists = LayeredAllowlist::load_from_layers([
+            (AllowlistLayer::Directory, find_directory_allowlist(&subdir)),
+            (
+                AllowlistLayer::Project,
+                Some(repo.join(".dcg").join("allowlist.toml")),
+            ),
+            (AllowlistLayer::User, Some(user_path)),
+        ]);
+        let hit = allowlists
+            .match_rule("core.git", "reset-hard")
+            .expect("directory layer must match");
+        assert_eq!(hit.layer, AllowlistLayer::Directory);
+
+        let explained = allowlists.explain_rule_at_path("core.git", "reset-hard", None);
+        let layers: Vec<_> = explained.iter().map(|m| m.layer).collect();
+        assert_eq!(
+            layers,
+            vec![
+                AllowlistLayer::Directory,
+                AllowlistLayer::Project,
+                AllowlistLayer::User
+            ]
+        );
+        assert_eq!(
+            explained[0].entry.map(|e| e.reason.as_str()),
+            Some("migrations dir resets scratch branches")
+        );
+        assert!(explained[1].entry.is_none());
+        assert!(explained[2].entry.is_none());
+    }
+
+    #[test]
+    fn directory_allowlist_requires_a_repo() {
+        let temp = tempfile::tempdir().unwrap();
+        let shared = temp.path().join("shared");
+        std::fs::create_dir_all(shared.join(".dcg")).unwrap();
+        std::fs::write(
+            shared.join(".dcg").join("allowlist.toml"),
+            "[[allow]]\nrule = \"core.git:reset-hard\"\nreason = \"planted\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(find_directory_allowlist(&shared), None);
+    }
+
     #[test]
     fn wildcard_pack_rule_matches_any_pattern_in_pack() {
         let allowlists = LayeredAllowlist {
@@ -358,7 +358,7 @@ const fn script_language_to_ast_lang(lang: ScriptLanguage) -> Option<SupportLang
         ScriptLanguage::Bash => Some(SupportLang::Bash),
         ScriptLanguage::Go => Some(SupportLang::Go),
         ScriptLanguage::Php => Some(SupportLang::Php),
-        ScriptLanguage::Perl | ScriptLanguage::Unknown => None,
+        ScriptLanguage::Perl | ScriptLanguage::Sql | ScriptLanguage::Unknown => None,
     }
 }
 
@@ -378,10 +378,10 @@ static JS_RECURSIVE_TRUE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"(?m)\brecursive\s*:\s*true\b").expect("js recursive:true regex compiles")
 });
 
-static JS_EXEC_SYNC_LITERAL: LazyLock<Regex> = LazyLock::new(|| {
-    // Matches: execSync("...") / execSync('...')
-    Regex::new(r#"(?m)\bexecSync\b\s*\(\s*(?:"(?P<dq>[^"\n]*)"|'(?P<sq>[^'\n]*)')"#)
-        .expect("js execSync literal regex compiles")
+static JS_EXEC_LITERAL: LazyLock<Regex> = LazyLock::new(|| {
+    // Matches: exec("...") / execSync('...')
+    Regex::new(r#"(?m)\bexec(?:Sync)?\b\s*\(\s*(?:"(?P<dq>[^"\n]*)"|'(?P<sq>[^'\n]*)')"#)
+        .expect("js exec/execSync literal regex compiles")
 });
 
 static JS_SPAWN_SYNC_CMD_ARGS: LazyLock<Regex> = LazyLock::new(|| {
@@ -398,11 +398,18 @@ static JS_ARRAY_STRING_LITERALS: LazyLock<Regex> = LazyLock::new(|| {
 });
 
 static JS_FIRST_STRING_ARG: LazyLock<Regex> = LazyLock::new(|| {
-    // Captures the first string literal argument in a call expression.
-    Regex::new(r#"(?m)\(\s*(?:"(?P<dq>[^"\n]*)"|'(?P<sq>[^'\n]*)')"#)
+    // Captures the first string literal argument of a method call, so the
+    // module name in `require('fs').rmSync(...)` is skipped.
+    Regex::new(r#"(?m)\.\w+\s*\(\s*(?:"(?P<dq>[^"\n]*)"|'(?P<sq>[^'\n]*)')"#)
         .expect("js first string arg regex compiles")
 });
 
+static PYTHON_FIRST_STRING_ARG: LazyLock<Regex> = LazyLock::new(|| {
+    // Captures the first string literal argument: os.system("...") / os.popen('...')
+    Regex::new(r#"(?m)\(\s*(?:"(?P<dq>[^"\n]*)"|'(?P<sq>[^'\n]*)')"#)
+        .expect("python first string arg regex compiles")
+});
+
 static RUBY_SYSTEM_EXEC_LITERAL: LazyLock<Regex> = LazyLock::new(|| {
     // Matches:
     // - system("...") / system '...'
@@ -434,6 +441,7 @@ fn refine_match_meta(
         ScriptLanguage::JavaScript => refine_javascript_match(meta, matched_text),
         ScriptLanguage::TypeScript => refine_typescript_match(meta, matched_text),
         ScriptLanguage::Ruby => refine_ruby_match(meta, matched_text),
+        ScriptLanguage::Python => Some(refine_python_match(meta, matched_text)),
         _ => Some(RefinedMatchMeta {
             rule_id: meta.rule_id.clone(),
             reason: meta.reason.clone(),
@@ -443,14 +451,49 @@ fn refine_match_meta(
     }
 }
 
+fn refine_python_match(meta: &CompiledPattern, matched_text: &str) -> RefinedMatchMeta {
+    let rule_id = meta.rule_id.as_str();
+
+    // os.system/os.popen with a literal destructive payload escalate to the
+    // payload's severity; anything else keeps the warn-only default.
+    if matches!(
+        rule_id,
+        "heredoc.python.os_system" | "heredoc.python.os_popen"
+    ) {
+        let hit = PYTHON_FIRST_STRING_ARG
+            .captures(matched_text)
+            .and_then(|caps| string_literal_from_caps(&caps))
+            .and_then(detect_shell_payload);
+
+        if let Some(hit) = hit {
+            return RefinedMatchMeta {
+                rule_id: format!("{rule_id}.{}", hit.rule_suffix),
+                reason: hit.reason.to_string(),
+                severity: hit.severity,
+                suggestion: hit.suggestion.map(str::to_string),
+            };
+        }
+    }
+
+    RefinedMatchMeta {
+        rule_id: meta.rule_id.clone(),
+        reason: meta.reason.clone(),
+        severity: meta.severity,
+        suggestion: meta.suggestion.clone(),
+    }
+}
+
 fn refine_javascript_match(meta: &CompiledPattern, matched_text: &str) -> Option<RefinedMatchMeta> {
     let rule_id = meta.rule_id.as_str();
 
     if matches!(
         rule_id,
-        "heredoc.javascript.execsync" | "heredoc.javascript.require_execsync"
+        "heredoc.javascript.execsync"
+            | "heredoc.javascript.require_execsync"
+            | "heredoc.javascript.exec"
+            | "heredoc.javascript.require_exec"
     ) {
-        let payload = JS_EXEC_SYNC_LITERAL
+        let payload = JS_EXEC_LITERAL
             .captures(matched_text)
             .and_then(|caps| string_literal_from_caps(&caps));
 
@@ -559,7 +602,7 @@ fn refine_typescript_match(meta: &CompiledPattern, matched_text: &str) -> Option
         rule_id,
         "heredoc.typescript.execsync" | "heredoc.typescript.require_execsync"
     ) {
-        let payload = JS_EXEC_SYNC_LITERAL
+        let payload = JS_EXEC_LITERAL
             .captures(matched_text)
             .and_then(|caps| string_literal_from_caps(&caps));
 
@@ -1594,6 +1637,13 @@ fn default_patterns() -> HashMap<ScriptLanguage, Vec<CompiledPattern>> {
                 Severity::Medium, // warn-only unless catastrophic literal target (refined at match time)
                 Some("Verify target path carefully before running".to_string()),
             ),
+            CompiledPattern::new(
+                "require('fs').rmSync($$$)".to_string(),
+                "heredoc.javascript.fs_rmsync".to_string(),
+                "fs.rmSync() deletes files/directories".to_string(),
+                Severity::Medium, // warn-only unless catastrophic literal target (refined at match time)
+                Some("Verify target path carefully before running".to_string()),
+            ),
             CompiledPattern::new(
                 "fs.rmdirSync($$$)".to_string(),
                 "heredoc.javascript.fs_rmdirsync".to_string(),
@@ -1601,6 +1651,13 @@ fn default_patterns() -> HashMap<ScriptLanguage, Vec<CompiledPattern>> {
                 Severity::Medium, // warn-only unless catastrophic literal target (refined at match time)
                 Some("Verify target path carefully before running".to_string()),
             ),
+            CompiledPattern::new(
+                "require('fs').rmdirSync($$$)".to_string(),
+                "heredoc.javascript.fs_rmdirsync".to_string(),
+                "fs.rmdirSync() deletes directories".to_string(),
+                Severity::Medium, // warn-only unless catastrophic literal target (refined at match time)
+                Some("Verify target path carefully before running".to_string()),
+            ),
             CompiledPattern::new(
                 "fs.unlinkSync($$$)".to_string(),
                 "heredoc.javascript.fs_unlinksync".to_string(),
@@ -1622,6 +1679,20 @@ fn default_patterns() -> HashMap<ScriptLanguage, Vec<CompiledPattern>> {
                 Severity::Medium, // refined to block only on destructive literal payloads
                 Some("Validate command arguments carefully".to_string()),
             ),
+            CompiledPattern::new(
+                "child_process.exec($$$)".to_string(),
+                "heredoc.javascript.exec".to_string(),
+                "exec() executes shell commands".to_string(),
+                Severity::Medium, // refined to block only on destructive literal payloads
+                Some("Validate command arguments carefully".to_string()),
+            ),
+            CompiledPattern::new(
+                "require('child_process').exec($$$)".to_string(),
+                "heredoc.javascript.require_exec".to_string(),
+                "exec() executes shell commands".to_string(),
+                Severity::Medium, // refined to block only on destructive literal payloads
+                Some("Validate command arguments carefully".to_string()),
+            ),
             // Spawn variants
             CompiledPattern::new(
                 "child_process.spawnSync($$$)".to_string(),
@@ -2212,6 +2283,36 @@ mod tests {
             assert!(!hit.severity.blocks_by_default());
         }
 
+        #[test]
+        fn require_fs_rmsync_catastrophic_blocks() {
+            let ast_matcher = AstMatcher::new();
+            let code = "require('fs').rmSync('/', { recursive: true });";
+
+            let matches = ast_matcher
+                .find_matches(code, ScriptLanguage::JavaScript)
+                .unwrap();
+            let hit = matches
+                .into_iter()
+                .find(|m| m.rule_id == "heredoc.javascript.fs_rmsync.catastrophic")
+                .expect("require('fs').rmSync('/') should be catastrophic");
+            assert!(hit.severity.blocks_by_default());
+        }
+
+        #[test]
+        fn require_child_process_exec_rm_rf_blocks() {
+            let ast_matcher = AstMatcher::new();
+            let code = "require('child_process').exec('rm -rf /');";
+
+            let matches = ast_matcher
+                .find_matches(code, ScriptLanguage::JavaScript)
+                .unwrap();
+            let hit = matches
+                .into_iter()
+                .find(|m| m.rule_id.starts_with("heredoc.javascript.require_exec."))
+                .expect("exec with destructive payload should be detected");
+            assert!(hit.severity.blocks_by_default());
+        }
+
         #[test]
         fn execsync_git_reset_hard_blocks() {
             let ast_matcher = AstMatcher::new();
@@ -3074,6 +3175,21 @@ mod tests {
             );
         }
 
+        #[test]
+        fn os_system_destructive_payload_blocks() {
+            let ast_matcher = AstMatcher::new();
+            let code = "import os\nos.system('git reset --hard')";
+            let matches = ast_matcher
+                .find_matches(code, ScriptLanguage::Python)
+                .unwrap();
+            assert!(!matches.is_empty(), "os.system must match");
+            assert_eq!(
+                matches[0].rule_id,
+                "heredoc.python.os_system.git_reset_hard"
+            );
+            assert!(matches[0].severity.blocks_by_default());
+        }
+
         #[test]
         fn os_system_warns() {
             // os.system is Medium severity - warns but doesn't block by default
@@ -9,15 +9,16 @@ use clap_complete::generate;
 use inquire::{Select, Text};
 
 use crate::agent::{DetectionMethod, detect_agent_with_details};
-use crate::config::Config;
+use crate::config::{Config, ConfigDiagnostic, ConfigDiagnosticSeverity};
 use crate::evaluator::{
-    DEFAULT_WINDOW_WIDTH, EvaluationDecision, EvaluationResult, MatchSource,
+    DEFAULT_WINDOW_WIDTH, Decision, EvaluationResult, MatchSource,
     evaluate_command_with_pack_order, evaluate_command_with_pack_order_deadline_at_path,
 };
-use crate::exit_codes::EXIT_DENIED;
+use crate::exit_codes::{EXIT_CONFIG_ERROR, EXIT_DENIED, EXIT_SUCCESS, EXIT_WARNING};
 use crate::highlight::{HighlightSpan, format_highlighted_command, should_use_color};
 use crate::history::{
-    ExportOptions, HistoryDb, HistoryStats, Outcome, SuggestionAction, SuggestionAuditEntry,
+    CommandEntry, ExportOptions, HistoryDb, HistoryStats, Outcome, SuggestionAction,
+    SuggestionAuditEntry,
 };
 use crate::interactive::{
     AllowlistScope, InteractiveConfig, InteractiveResult, check_interactive_available,
@@ -30,7 +31,7 @@ use crate::packs::{
 };
 use crate::pending_exceptions::{
     AllowOnceEntry, AllowOnceScopeKind, AllowOnceStore, PendingExceptionRecord,
-    PendingExceptionStore,
+    PendingExceptionStore, confirmation_secret, verify_confirmation_token,
 };
 use crate::suggest::{
     AllowlistSuggestion, CommandEntryInfo, ConfidenceTier, RiskLevel, filter_by_confidence,
@@ -137,6 +138,16 @@ pub struct Cli {
     #[arg(long, global = true, env = "DCG_NO_SUGGESTIONS")]
     pub no_suggestions: bool,
 
+    /// Do not write this evaluation to the history database, even when
+    /// `[history] enabled = true`
+    #[arg(
+        long,
+        global = true,
+        env = "DCG_NO_HISTORY",
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    pub no_history: bool,
+
     /// Enable robot/machine mode for AI agent integration
     ///
     /// When enabled:
@@ -161,6 +172,19 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub robot: bool,
 
+    /// Hook mode: read the JSON input from this file descriptor instead of stdin
+    #[arg(long, value_name = "FD", conflicts_with = "input_path")]
+    pub input_fd: Option<u32>,
+
+    /// Hook mode: read the JSON input from this file or named pipe instead of stdin
+    #[arg(long, value_name = "PATH")]
+    pub input_path: Option<std::path::PathBuf>,
+
+    /// Hook mode: hard limit for evaluating the command, in milliseconds
+    /// (overrides `[evaluation] total_timeout_ms`)
+    #[arg(long, value_name = "MS")]
+    pub timeout: Option<u64>,
+
     /// Subcommand to run (omit to run in hook mode)
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -279,6 +303,14 @@ pub enum Command {
         #[arg(long)]
         enabled: bool,
 
+        /// Show only disabled packs
+        #[arg(long, conflicts_with = "enabled")]
+        disabled: bool,
+
+        /// List every rule (`pack_id:pattern_name`) with severity and reason
+        #[arg(long)]
+        rules: bool,
+
         // NOTE: Removed `verbose: bool` - use global `-v`/`--verbose` instead.
         // The global flag (u8 count) conflicts with local bool flags.
         /// Output format (json for structured output, pretty for human-readable)
@@ -299,6 +331,26 @@ pub enum Command {
         action: PackAction,
     },
 
+    /// Write built-in packs as YAML in the custom pack schema
+    ///
+    /// Each pack is written to `<out>/<pack_id>.yaml` under the ID
+    /// `custom.<pack_id>` so the edited copy can be loaded via `custom_paths`
+    /// alongside (or instead of) the built-in.
+    #[command(name = "export-packs")]
+    ExportPacks {
+        /// Directory to write pack files into (created if missing)
+        #[arg(long, default_value = "packs")]
+        out: std::path::PathBuf,
+
+        /// Only export these pack IDs (repeatable; default: all built-in packs)
+        #[arg(long = "pack", value_name = "PACK_ID")]
+        packs: Vec<String>,
+
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Test a command against enabled packs
     #[command(name = "test")]
     TestCommand {
@@ -350,23 +402,36 @@ pub enum Command {
             value_name = "LANGS"
         )]
         heredoc_languages: Option<Vec<String>>,
+
+        /// Deny commands with unbalanced quotes (overrides config)
+        #[arg(long = "strict-quoting")]
+        strict_quoting: bool,
     },
 
-    /// Generate a sample configuration file
+    /// Scaffold a config file, the custom packs directory, and the hook wiring
     #[command(name = "init")]
     Init {
-        /// Output path (defaults to stdout)
+        /// Config path (defaults to the user config; `-` prints to stdout)
         #[arg(short, long)]
         output: Option<String>,
 
         /// Overwrite existing file
         #[arg(long)]
         force: bool,
+
+        /// Install the hook into Claude Code settings instead of printing the snippet
+        #[arg(long = "write-claude-settings")]
+        write_claude_settings: bool,
     },
 
     /// Show current configuration
     #[command(name = "config")]
-    ShowConfig,
+    ShowConfig {
+        /// Validate the loaded configuration instead of printing it
+        /// (exits 3 on errors)
+        #[arg(long)]
+        check: bool,
+    },
 
     /// Scan files for destructive commands (CI/pre-commit integration)
     ///
@@ -398,7 +463,13 @@ pub enum Command {
     #[command(name = "explain")]
     Explain {
         /// Command to explain
-        command: String,
+        #[arg(required_unless_present = "batch")]
+        command: Option<String>,
+
+        /// Explain every command in FILE (one per line) and print a summary.
+        /// Blank lines and `#` comments are skipped.
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["command", "dump_trace"])]
+        batch: Option<std::path::PathBuf>,
 
         /// Output format
         #[arg(
@@ -413,6 +484,29 @@ pub enum Command {
         /// Additional packs to enable for this evaluation
         #[arg(long, value_delimiter = ',')]
         with_packs: Option<Vec<String>>,
+
+        /// Write a versioned diagnostic bundle (JSON) to this file for bug reports
+        #[arg(long, value_name = "FILE")]
+        dump_trace: Option<std::path::PathBuf>,
+
+        /// Exit with the decision's exit code (1 deny, 2 warn, 0 allow)
+        /// instead of always 0
+        #[arg(long)]
+        exit_code: bool,
+
+        /// Redact the echoed command using `[logging.redaction]` settings
+        /// (default: redact when stdout is not a terminal or in CI)
+        #[arg(long, overrides_with = "no_redact")]
+        redact: bool,
+
+        /// Show the command verbatim, even when output is piped
+        #[arg(long, overrides_with = "redact")]
+        no_redact: bool,
+
+        /// Show the raw command next to the sanitized string patterns match
+        /// against, marking the arguments that were masked
+        #[arg(long, conflicts_with = "batch")]
+        diff_normalization: bool,
     },
 
     /// Run regression corpus tests and output detailed JSON logs
@@ -430,10 +524,24 @@ pub enum Command {
     Stats(StatsCommand),
 
     /// Query command history database
-    #[command(name = "history")]
+    ///
+    /// With `--min-duration` and no subcommand, lists the slowest evaluations.
+    #[command(name = "history", arg_required_else_help = true)]
     History {
         #[command(subcommand)]
-        action: HistoryAction,
+        action: Option<HistoryAction>,
+
+        /// List entries whose evaluation took at least this many microseconds, slowest first
+        #[arg(long, value_name = "MICROSECONDS")]
+        min_duration: Option<u64>,
+
+        /// Maximum number of entries to list with --min-duration
+        #[arg(long, value_name = "N", default_value = "20")]
+        limit: usize,
+
+        /// Output the --min-duration listing as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Suggest allowlist patterns based on command history
@@ -444,6 +552,48 @@ pub enum Command {
     #[command(name = "suggest-allowlist")]
     SuggestAllowlist(SuggestAllowlistCommand),
 
+    /// Re-evaluate past commands from history under the current config
+    ///
+    /// Loads history entries and evaluates them again with the current packs,
+    /// policy, and allowlists, reporting where the decision changed. Use it to
+    /// check config changes against real traffic, e.g. which past denies would
+    /// now be allowed: `dcg replay --all --outcome deny`.
+    #[command(name = "replay")]
+    Replay(ReplayCommand),
+
+    /// Run self-tests against the installed pack set
+    ///
+    /// `--regex` fuzzes every safe and destructive pattern in all packs
+    /// (built-in and custom) with adversarial inputs derived from the
+    /// pattern's syntax tree, and fails if any match exceeds the budget.
+    /// At least one suite flag is required. Exits nonzero on failure.
+    #[command(
+        name = "selftest",
+        group(clap::ArgGroup::new("suite").required(true).multiple(true))
+    )]
+    SelfTest {
+        /// Fuzz pack patterns for catastrophic backtracking (ReDoS)
+        #[arg(long, group = "suite")]
+        regex: bool,
+
+        /// Per-input matching budget in milliseconds
+        ///
+        /// Matches the 500ms pack test budget: generous enough for debug or
+        /// instrumented builds, while catastrophic backtracking takes far longer.
+        #[arg(long, value_name = "MS", default_value_t = 500)]
+        budget_ms: u64,
+
+        /// Output format (json for structured output, pretty for human-readable)
+        #[arg(
+            long,
+            short = 'f',
+            value_enum,
+            default_value = "pretty",
+            env = "DCG_FORMAT"
+        )]
+        format: PacksFormat,
+    },
+
     /// Developer tools for pack development and testing
     #[command(name = "dev")]
     Dev {
@@ -607,6 +757,13 @@ pub struct StatsCommand {
     /// Limit number of rules to display (default: 20)
     #[arg(long, short = 'n', default_value = "20")]
     pub limit: usize,
+
+    /// Summarize command history since a relative time (e.g., "30d", "7d", "24h")
+    ///
+    /// Shows top denied rules, the deny/allow/warn ratio, the busiest working
+    /// directories, and a per-agent breakdown from the history database.
+    #[arg(long, value_name = "DURATION", conflicts_with = "rules")]
+    pub since: Option<String>,
 }
 
 /// Output format for stats command.
@@ -619,6 +776,20 @@ pub enum StatsFormat {
     /// Structured JSON output
     #[value(alias = "sarif")]
     Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+impl StatsFormat {
+    const fn delimited(self) -> Option<crate::output::DelimitedFormat> {
+        match self {
+            Self::Csv => Some(crate::output::DelimitedFormat::Csv),
+            Self::Tsv => Some(crate::output::DelimitedFormat::Tsv),
+            Self::Pretty | Self::Json => None,
+        }
+    }
 }
 
 /// Output format for test command.
@@ -724,6 +895,66 @@ pub struct PacksOutput {
     pub total_count: usize,
 }
 
+/// JSON output structure for `dcg packs --rules`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RulesOutput {
+    /// Every named destructive rule, sorted by rule ID
+    pub rules: Vec<RuleInfo>,
+    /// Count of rules in enabled packs
+    pub enabled_count: usize,
+    /// Total rule count
+    pub total_count: usize,
+}
+
+/// Rule information in the rules catalog
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RuleInfo {
+    /// Rule ID usable in allowlists and overrides (e.g., "core.git:reset-hard")
+    pub rule_id: String,
+    /// Pack ID (e.g., "core.git")
+    pub pack_id: String,
+    /// Pattern name within the pack (e.g., "reset-hard")
+    pub pattern_name: String,
+    /// Severity label ("critical", "high", "medium", "low")
+    pub severity: String,
+    /// Whether the owning pack is enabled
+    pub enabled: bool,
+    /// One-line reason shown when the rule matches
+    pub reason: String,
+}
+
+/// JSON output structure for `dcg selftest --regex`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RegexSelfTestOutput {
+    /// Per-input budget in milliseconds
+    pub budget_ms: u64,
+    /// Number of patterns fuzzed
+    pub patterns_checked: usize,
+    /// Total adversarial inputs evaluated
+    pub inputs_checked: usize,
+    /// Patterns whose slowest input exceeded the budget
+    pub failures: Vec<RegexProbeResult>,
+    /// Whether every pattern stayed within budget
+    pub passed: bool,
+}
+
+/// Fuzzing result for a single pack pattern
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RegexProbeResult {
+    /// Pack ID (e.g., "core.git")
+    pub pack_id: String,
+    /// Pattern name, if the pattern is named
+    pub pattern_name: Option<String>,
+    /// "safe" or "destructive"
+    pub kind: &'static str,
+    /// The regex source
+    pub pattern: String,
+    /// Slowest single match, in milliseconds
+    pub worst_ms: f64,
+    /// Length of the slowest input in bytes
+    pub worst_input_len: usize,
+}
+
 /// Pack information in the packs list
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct PackInfo {
@@ -741,6 +972,51 @@ pub struct PackInfo {
     pub safe_pattern_count: usize,
     /// Number of destructive patterns
     pub destructive_pattern_count: usize,
+    /// Where the pack comes from
+    pub source: PackOrigin,
+    /// Keywords that gate evaluation of the pack
+    pub keywords: Vec<String>,
+    /// Total number of patterns (safe + destructive)
+    pub pattern_count: usize,
+    /// Whether the pack was loaded (false for a custom pack that failed to load)
+    pub loaded: bool,
+    /// Why the pack failed to load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Origin of a pack in the packs list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackOrigin {
+    /// Compiled into dcg
+    Builtin,
+    /// Loaded from `[packs] custom_paths` or a remote bundle
+    Custom,
+}
+
+/// `dcg replay` command arguments.
+#[derive(Args, Debug)]
+pub struct ReplayCommand {
+    /// History entry id to replay (ids are listed by `dcg replay --all`)
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub id: Option<i64>,
+
+    /// Replay every history entry (most recent first)
+    #[arg(long)]
+    pub all: bool,
+
+    /// With --all, only replay entries with this outcome (allow, deny, warn, bypass)
+    #[arg(long, value_name = "OUTCOME", requires = "all")]
+    pub outcome: Option<String>,
+
+    /// With --all, replay at most this many entries
+    #[arg(long, value_name = "N", requires = "all")]
+    pub limit: Option<usize>,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
 }
 
 /// `dcg suggest-allowlist` command arguments.
@@ -835,6 +1111,8 @@ pub enum ExportFormat {
     Jsonl,
     /// Comma-separated values
     Csv,
+    /// Tab-separated values
+    Tsv,
 }
 
 /// History subcommand actions
@@ -1179,6 +1457,16 @@ pub struct ScanCommand {
     #[arg(long, value_enum)]
     fail_on: Option<crate::scan::ScanFailOn>,
 
+    /// Only show findings whose rule severity is at least this level
+    /// (display only; the summary and --fail-on still use every finding)
+    #[arg(long = "severity-at-least", value_enum, value_name = "LEVEL")]
+    severity_at_least: Option<crate::scan::ScanSeverityFilter>,
+
+    /// Section pretty and markdown output by pack, severity, file, or rule
+    /// (json and sarif output stay flat)
+    #[arg(long = "group-by", value_enum, value_name = "KEY")]
+    group_by: Option<crate::scan::ScanGroupBy>,
+
     // === Safety / performance knobs ===
     /// Maximum file size to scan (bytes); larger files are skipped
     #[arg(
@@ -1216,6 +1504,26 @@ pub struct ScanCommand {
     #[arg(long, value_name = "N", default_value = "10")]
     top: usize,
 
+    /// Print only the severity histogram and total (no per-finding output)
+    #[arg(long)]
+    count_only: bool,
+
+    /// Collapse findings with the same rule and command into one entry
+    /// listing every file:line occurrence
+    #[arg(long)]
+    dedupe: bool,
+
+    // === Baseline (adopt incrementally, fail only on new findings) ===
+    /// Ignore findings already recorded in this baseline report; only new
+    /// findings (by rule and normalized command) count toward --fail-on
+    #[arg(long, value_name = "FILE", conflicts_with = "write_baseline")]
+    baseline: Option<std::path::PathBuf>,
+
+    /// Write every current finding to this file as the new baseline
+    /// (exits 0 regardless of --fail-on)
+    #[arg(long = "write-baseline", value_name = "FILE")]
+    write_baseline: Option<std::path::PathBuf>,
+
     /// Optional action subcommand (pre-commit integration helpers)
     #[command(subcommand)]
     action: Option<ScanAction>,
@@ -1409,6 +1717,17 @@ pub enum AllowlistAction {
         #[arg(long)]
         strict: bool,
     },
+
+    /// Show which allowlist layer (if any) would allow a command, and why
+    #[command(name = "explain")]
+    Explain {
+        /// Command to explain
+        command: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "pretty", env = "DCG_FORMAT")]
+        format: AllowlistOutputFormat,
+    },
 }
 
 /// Subcommands for managing allow-once entries.
@@ -1495,6 +1814,11 @@ pub struct AllowOnceCommand {
     /// Select by full hash when multiple match the code (apply-only)
     #[arg(long, value_name = "HASH", conflicts_with = "pick")]
     pub hash: Option<String>,
+
+    /// Confirmation token printed with the denial; required when
+    /// `[exceptions] require_hmac` is set (apply-only)
+    #[arg(long, value_name = "TOKEN")]
+    pub token: Option<String>,
 }
 
 /// Output format for allowlist list command
@@ -1561,6 +1885,32 @@ pub enum PackAction {
         #[arg(long, short = 'f', value_enum, default_value_t = PackValidateFormat::Pretty, env = "DCG_FORMAT")]
         format: PackValidateFormat,
     },
+
+    /// Fetch the `[packs] remote` bundle and update the on-disk cache
+    ///
+    /// Hook mode only reads the cache and starts this in the background when
+    /// the cache is stale. Exits non-zero if the fetch or validation fails.
+    #[command(name = "refresh")]
+    Refresh,
+
+    /// Test a regex against an input using the same engine as pack patterns
+    ///
+    /// Compiles the pattern exactly as dcg does for pack rules (linear-time
+    /// engine, or the backtracking engine for lookarounds/backreferences) and
+    /// reports whether it matches, the matched span, and captured groups.
+    /// Exits non-zero if the pattern does not compile.
+    #[command(name = "regex-test")]
+    RegexTest {
+        /// Regex pattern as it would appear in a pack
+        pattern: String,
+
+        /// Input string to match against
+        input: String,
+
+        /// Output as JSON instead of human-readable text
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Output format for pack validate command
@@ -1708,7 +2058,12 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::Completions { shell }) => {
             write_completions(shell)?;
         }
-        Some(Command::ListPacks { enabled, format }) => {
+        Some(Command::ListPacks {
+            enabled,
+            disabled,
+            rules,
+            format,
+        }) => {
             // Robot mode forces JSON output
             let robot_mode = cli.robot || std::env::var("DCG_ROBOT").is_ok();
             let effective_format = if robot_mode {
@@ -1718,20 +2073,33 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             };
 
             // Load external packs from custom_paths so they appear in the listing
-            let external_paths = config.packs.expand_custom_paths();
-            let _ = load_external_packs(&external_paths);
-
-            list_packs(
-                &config,
-                enabled,
-                verbosity.is_verbose(),
-                effective_format,
-                verbosity.quiet,
-            );
+            let _ =
+                load_external_packs(&config.packs.pack_sources(), &config.packs.enable_patterns);
+
+            // None lists everything; Some(want) keeps packs whose enabled state matches
+            let filter = match (enabled, disabled) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            if rules {
+                list_rules(&config, filter, effective_format, verbosity.quiet);
+            } else {
+                list_packs(
+                    &config,
+                    filter,
+                    verbosity.is_verbose(),
+                    effective_format,
+                    verbosity.quiet,
+                );
+            }
         }
         Some(Command::Pack { action }) => {
             handle_pack_command(&config, action)?;
         }
+        Some(Command::ExportPacks { out, packs, force }) => {
+            export_packs(&out, &packs, force, verbosity.quiet)?;
+        }
         Some(Command::TestCommand {
             command,
             config: config_path,
@@ -1743,6 +2111,7 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             no_heredoc_scan,
             heredoc_timeout_ms,
             heredoc_languages,
+            strict_quoting,
         }) => {
             // Robot mode forces JSON output
             let robot_mode = cli.robot || std::env::var("DCG_ROBOT").is_ok();
@@ -1779,6 +2148,7 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
                     no_heredoc_scan,
                     heredoc_timeout_ms,
                     heredoc_languages,
+                    strict_quoting,
                 );
                 // Exit with code 1 if command would be blocked (for CI/robot mode scripting)
                 if was_blocked {
@@ -1786,16 +2156,31 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
-        Some(Command::Init { output, force }) => {
-            init_config(output, force)?;
+        Some(Command::Init {
+            output,
+            force,
+            write_claude_settings,
+        }) => {
+            init_config(output, force, write_claude_settings)?;
         }
-        Some(Command::ShowConfig) => {
-            if !verbosity.quiet {
+        Some(Command::ShowConfig { check }) => {
+            if check {
+                let diagnostics = config.validate();
+                if !verbosity.quiet {
+                    print_config_diagnostics(&diagnostics);
+                }
+                if diagnostics
+                    .iter()
+                    .any(|d| d.severity == ConfigDiagnosticSeverity::Error)
+                {
+                    std::process::exit(EXIT_CONFIG_ERROR);
+                }
+            } else if !verbosity.quiet {
                 show_config(&config);
             }
         }
         Some(Command::Allowlist { action }) => {
-            handle_allowlist_command(action)?;
+            handle_allowlist_command(&config, action)?;
         }
         Some(Command::Allow {
             rule_id,
@@ -1860,8 +2245,14 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         }
         Some(Command::Explain {
             command,
+            batch,
             format,
             with_packs,
+            dump_trace,
+            exit_code,
+            redact,
+            no_redact,
+            diff_normalization,
         }) => {
             // Robot mode forces JSON output
             let robot_mode = cli.robot || std::env::var("DCG_ROBOT").is_ok();
@@ -1871,8 +2262,58 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
                 format
             };
 
+            if let Some(path) = batch {
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+                let (mut entries, summary) = explain_batch(&config, &contents, with_packs);
+                if explain_should_redact(redact, no_redact) {
+                    for (_, trace) in &mut entries {
+                        trace.redact_commands(&config.logging.redaction);
+                    }
+                }
+                if !verbosity.quiet {
+                    print_explain_batch(&entries, summary, effective_format);
+                }
+                if exit_code {
+                    let code = explain_batch_exit_code(&entries);
+                    if code != EXIT_SUCCESS {
+                        std::process::exit(code);
+                    }
+                }
+                return Ok(());
+            }
+            let command = command.unwrap_or_default();
+            let (mut trace, pack_ids) = build_explain_trace(&config, &command, with_packs);
+            if diff_normalization {
+                trace.sanitized_command = Some(
+                    crate::context::sanitize_for_pattern_matching(&trace.command).into_owned(),
+                );
+            }
+            if explain_should_redact(redact, no_redact) {
+                trace.redact_commands(&config.logging.redaction);
+            }
+            if let Some(path) = dump_trace {
+                crate::trace::TraceBundle::new(&trace, &pack_ids, &config.logging.redaction)
+                    .write_to(&path)?;
+                if !verbosity.quiet {
+                    eprintln!("Trace bundle written to {}", path.display());
+                }
+            }
             if !verbosity.quiet {
-                handle_explain(&config, &command, effective_format, with_packs);
+                print_explain_trace(&trace, effective_format);
+                if diff_normalization && effective_format != ExplainFormat::Json {
+                    if let Some(diff) = trace.format_normalization_diff(
+                        colored::control::SHOULD_COLORIZE.should_colorize(),
+                    ) {
+                        println!("{diff}");
+                    }
+                }
+            }
+            if exit_code {
+                let code = explain_exit_code(&trace);
+                if code != EXIT_SUCCESS {
+                    std::process::exit(code);
+                }
             }
         }
         Some(Command::Corpus(corpus)) => {
@@ -1881,12 +2322,41 @@ pub fn run_command(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::Stats(stats)) => {
             handle_stats_command(&config, &stats, verbosity.quiet)?;
         }
-        Some(Command::History { action }) => {
-            handle_history_command(&config, action)?;
-        }
+        Some(Command::History {
+            action,
+            min_duration,
+            limit,
+            json,
+        }) => match (action, min_duration) {
+            (Some(action), _) => handle_history_command(&config, action)?,
+            (None, Some(min_duration_us)) => {
+                history_slowest(&config, min_duration_us, limit, json)?;
+            }
+            (None, None) => {
+                return Err("history requires a subcommand or --min-duration".into());
+            }
+        },
         Some(Command::SuggestAllowlist(cmd)) => {
             handle_suggest_allowlist_command(&config, &cmd)?;
         }
+        Some(Command::Replay(cmd)) => {
+            handle_replay_command(&config, &cmd)?;
+        }
+        Some(Command::SelfTest {
+            regex,
+            budget_ms,
+            format,
+        }) => {
+            let mut passed = true;
+            if regex {
+                let output = regex_selftest(std::time::Duration::from_millis(budget_ms));
+                print_regex_selftest(&output, format);
+                passed &= output.passed;
+            }
+            if !passed {
+                std::process::exit(1);
+            }
+        }
         Some(Command::Dev { action }) => {
             handle_dev_command(&config, action, verbosity)?;
         }
@@ -2128,15 +2598,15 @@ fn evaluate_batch_line(
     );
 
     match eval_result.decision {
-        EvaluationDecision::Allow => BatchHookOutput {
+        Decision::Allow => BatchHookOutput {
             index,
-            decision: "allow",
+            decision: eval_result.decision.as_str(),
             rule_id: None,
             pack_id: None,
             error: None,
         },
-        EvaluationDecision::Deny => {
-            // Extract pattern info for deny decisions
+        Decision::Warn | Decision::Ask | Decision::Deny => {
+            // Extract pattern info for matched decisions
             let (rule_id, pack_id) =
                 eval_result
                     .pattern_info
@@ -2152,7 +2622,7 @@ fn evaluate_batch_line(
 
             BatchHookOutput {
                 index,
-                decision: "deny",
+                decision: eval_result.decision.as_str(),
                 rule_id,
                 pack_id,
                 error: None,
@@ -2161,10 +2631,90 @@ fn evaluate_batch_line(
     }
 }
 
+/// Collect built-in and custom packs, including custom packs that failed to load.
+///
+/// A file in `custom_paths` is enabled by being listed there, so one that fails
+/// to parse shows up as enabled but not loaded, together with the error.
+fn collect_packs(config: &Config, external: Option<&ExternalPackStore>) -> Vec<PackInfo> {
+    use crate::packs::external::ExternalPackLoader;
+
+    let category_of = |id: &str| id.split('.').next().unwrap_or(id).to_string();
+    let enabled_packs = config.enabled_pack_ids();
+    let mut packs: Vec<PackInfo> = REGISTRY
+        .list_packs(&enabled_packs)
+        .into_iter()
+        .map(|info| PackInfo {
+            category: category_of(&info.id),
+            name: info.name.to_string(),
+            description: info.description.to_string(),
+            enabled: info.enabled,
+            safe_pattern_count: info.safe_pattern_count,
+            destructive_pattern_count: info.destructive_pattern_count,
+            source: PackOrigin::Builtin,
+            keywords: REGISTRY.get(&info.id).map_or_else(Vec::new, |pack| {
+                pack.keywords.iter().map(|kw| (*kw).to_string()).collect()
+            }),
+            pattern_count: info.safe_pattern_count + info.destructive_pattern_count,
+            loaded: true,
+            error: None,
+            id: info.id,
+        })
+        .collect();
+
+    // External packs loaded via custom_paths are always enabled by convention
+    // (if you add a pack to custom_paths, you want it active)
+    let mut custom: Vec<PackInfo> = external
+        .into_iter()
+        .flat_map(ExternalPackStore::iter_packs)
+        .map(|(id, pack)| PackInfo {
+            id: id.clone(),
+            name: pack.name.to_string(),
+            category: category_of(id),
+            description: pack.description.to_string(),
+            enabled: true,
+            safe_pattern_count: pack.safe_patterns.len(),
+            destructive_pattern_count: pack.destructive_patterns.len(),
+            source: PackOrigin::Custom,
+            keywords: pack.keywords.iter().map(|kw| (*kw).to_string()).collect(),
+            pattern_count: pack.safe_patterns.len() + pack.destructive_patterns.len(),
+            loaded: true,
+            error: None,
+        })
+        .collect();
+
+    let failures = ExternalPackLoader::from_paths(&config.packs.expand_custom_paths())
+        .load_all_deduped()
+        .warnings;
+    for failure in failures {
+        let path = failure.path.display().to_string();
+        let id = failure.id.unwrap_or_else(|| path.clone());
+        if custom.iter().any(|pack| pack.id == id) {
+            continue;
+        }
+        custom.push(PackInfo {
+            category: category_of(&id),
+            name: path.clone(),
+            description: format!("Failed to load from {path}"),
+            enabled: true,
+            safe_pattern_count: 0,
+            destructive_pattern_count: 0,
+            source: PackOrigin::Custom,
+            keywords: Vec::new(),
+            pattern_count: 0,
+            loaded: false,
+            error: Some(failure.error.to_string()),
+            id,
+        });
+    }
+    custom.sort_by(|a, b| a.id.cmp(&b.id));
+    packs.extend(custom);
+    packs
+}
+
 /// List all packs and their status
 fn list_packs(
     config: &Config,
-    enabled_only: bool,
+    filter: Option<bool>,
     verbose: bool,
     format: PacksFormat,
     quiet: bool,
@@ -2173,52 +2723,14 @@ fn list_packs(
         return;
     }
 
-    let enabled_packs = config.enabled_pack_ids();
-    let infos = REGISTRY.list_packs(&enabled_packs);
-
-    // Build pack list (filtered if enabled_only)
-    let mut pack_list: Vec<PackInfo> = infos
-        .iter()
-        .filter(|info| !enabled_only || info.enabled)
-        .map(|info| {
-            let category = info.id.split('.').next().unwrap_or(&info.id).to_string();
-            PackInfo {
-                id: info.id.clone(),
-                name: info.name.to_string(),
-                category,
-                description: info.description.to_string(),
-                enabled: info.enabled,
-                safe_pattern_count: info.safe_pattern_count,
-                destructive_pattern_count: info.destructive_pattern_count,
-            }
-        })
+    let all_packs = collect_packs(config, get_external_packs());
+    let total_count = all_packs.len();
+    let pack_list: Vec<PackInfo> = all_packs
+        .into_iter()
+        .filter(|info| filter.is_none_or(|want| info.enabled == want))
         .collect();
 
-    // Include external packs from custom_paths
-    // External packs are auto-enabled when loaded (same behavior as test_command_inner)
-    if let Some(external_store) = get_external_packs() {
-        for (id, pack) in external_store.iter_packs() {
-            // External packs loaded via custom_paths are always enabled by convention
-            // (if you add a pack to custom_paths, you want it active)
-            let is_enabled = true;
-            if enabled_only && !is_enabled {
-                continue;
-            }
-            let category = id.split('.').next().unwrap_or(id).to_string();
-            pack_list.push(PackInfo {
-                id: id.clone(),
-                name: pack.name.to_string(),
-                category,
-                description: pack.description.to_string(),
-                enabled: is_enabled,
-                safe_pattern_count: pack.safe_patterns.len(),
-                destructive_pattern_count: pack.destructive_patterns.len(),
-            });
-        }
-    }
-
     // Handle JSON output
-    let total_count = infos.len() + get_external_packs().map_or(0, ExternalPackStore::len);
     if format == PacksFormat::Json {
         let enabled_count = pack_list.iter().filter(|p| p.enabled).count();
         let output = PacksOutput {
@@ -2233,7 +2745,7 @@ fn list_packs(
     // Rich output when feature enabled
     #[cfg(feature = "rich-output")]
     {
-        list_packs_rich(config, enabled_only, verbose);
+        list_packs_rich(&pack_list, verbose);
     }
 
     // Pretty output (default, non-rich fallback)
@@ -2245,20 +2757,21 @@ fn list_packs(
         // Group by category
         let mut by_category: std::collections::BTreeMap<&str, Vec<_>> =
             std::collections::BTreeMap::new();
-        for info in &infos {
-            let category = info.id.split('.').next().unwrap_or(&info.id);
-            by_category.entry(category).or_default().push(info);
+        for info in &pack_list {
+            by_category.entry(&info.category).or_default().push(info);
         }
 
         for (category, packs) in by_category {
             println!("  {category}:");
             for info in packs {
-                if enabled_only && !info.enabled {
-                    continue;
-                }
-
-                let status = if info.enabled { "✓" } else { "○" };
-                if verbose {
+                let status = match (info.loaded, info.enabled) {
+                    (false, _) => "✗",
+                    (true, true) => "✓",
+                    (true, false) => "○",
+                };
+                if let Some(error) = info.error.as_deref() {
+                    println!("    {} {} - not loaded: {}", status, info.id, error);
+                } else if verbose {
                     println!(
                         "    {} {} - {} ({} safe, {} destructive)",
                         status,
@@ -2274,78 +2787,241 @@ fn list_packs(
             println!();
         }
 
-        println!("Legend: ✓ = enabled, ○ = disabled");
+        println!("Legend: ✓ = enabled, ○ = disabled, ✗ = enabled but failed to load");
         println!();
         println!("Enable packs in ~/.config/dcg/config.toml");
     }
 }
 
-/// Rich terminal packs output using DcgConsole and markup.
-#[cfg(feature = "rich-output")]
-fn list_packs_rich(config: &Config, enabled_only: bool, verbose: bool) {
-    use crate::output::console::console;
-
-    let con = console();
+/// Collect every named destructive rule across built-in and external packs.
+fn collect_rules(config: &Config, filter: Option<bool>) -> Vec<RuleInfo> {
     let enabled_packs = config.enabled_pack_ids();
-    let infos = REGISTRY.list_packs(&enabled_packs);
-
-    // Header
-    con.rule(Some("[bold cyan] Available Packs [/]"));
-    con.print("");
-
-    // Group by category
-    let mut by_category: std::collections::BTreeMap<&str, Vec<_>> =
-        std::collections::BTreeMap::new();
-    for info in &infos {
-        let category = info.id.split('.').next().unwrap_or(&info.id);
-        by_category.entry(category).or_default().push(info);
-    }
+    let builtin = REGISTRY
+        .list_packs(&enabled_packs)
+        .into_iter()
+        .filter_map(|info| REGISTRY.get(&info.id).map(|pack| (pack, info.enabled)));
+    // External packs loaded via custom_paths are always enabled (see list_packs)
+    let external = get_external_packs()
+        .into_iter()
+        .flat_map(|store| store.iter_packs().map(|(_, pack)| (pack, true)));
+
+    let mut rules: Vec<RuleInfo> = builtin
+        .chain(external)
+        .filter(|(_, enabled)| filter.is_none_or(|want| *enabled == want))
+        .flat_map(|(pack, enabled)| {
+            pack.destructive_patterns.iter().filter_map(move |pattern| {
+                let name = pattern.name?;
+                Some(RuleInfo {
+                    rule_id: format!("{}:{name}", pack.id),
+                    pack_id: pack.id.clone(),
+                    pattern_name: name.to_string(),
+                    severity: pattern.severity.label().to_string(),
+                    enabled,
+                    reason: pattern.reason.to_string(),
+                })
+            })
+        })
+        .collect();
+    rules.sort_by(|a, b| a.rule_id.cmp(&b.rule_id));
+    rules
+}
 
-    for (category, packs) in by_category {
-        con.print(&format!("[bold]{category}[/]:"));
-        for info in packs {
-            if enabled_only && !info.enabled {
-                continue;
+/// Fuzz every pack pattern with adversarial inputs (`dcg selftest --regex`).
+///
+/// Each pattern is compiled before timing so lazy compilation is not
+/// counted against the budget.
+fn regex_selftest(budget: std::time::Duration) -> RegexSelfTestOutput {
+    let builtin = REGISTRY.iter();
+    let external = get_external_packs()
+        .into_iter()
+        .flat_map(|store| store.iter_packs().map(|(_, pack)| pack));
+
+    let mut patterns_checked = 0;
+    let mut inputs_checked = 0;
+    let mut failures = Vec::new();
+    for pack in builtin.chain(external) {
+        let safe = pack
+            .safe_patterns
+            .iter()
+            .map(|p| (Some(p.name), "safe", &p.regex));
+        let destructive = pack
+            .destructive_patterns
+            .iter()
+            .map(|p| (p.name, "destructive", &p.regex));
+        for (name, kind, regex) in safe.chain(destructive) {
+            let _ = regex.is_match("");
+            let mut worst = std::time::Duration::ZERO;
+            let mut worst_input_len = 0;
+            for input in crate::packs::regex_engine::adversarial_inputs(regex.as_str()) {
+                let start = std::time::Instant::now();
+                let _ = regex.is_match(&input);
+                let elapsed = start.elapsed();
+                inputs_checked += 1;
+                if elapsed > worst {
+                    worst = elapsed;
+                    worst_input_len = input.len();
+                }
             }
-
-            let (status, color) = if info.enabled {
-                ("●", "green")
-            } else {
-                ("○", "dim")
-            };
-
-            if verbose {
-                con.print(&format!(
-                    "  [{color}]{status}[/] [bold]{id}[/] - {desc} [dim]({safe} safe, {destr} destructive)[/]",
-                    id = info.id,
-                    desc = info.description,
-                    safe = info.safe_pattern_count,
-                    destr = info.destructive_pattern_count
-                ));
-            } else {
-                con.print(&format!(
-                    "  [{color}]{status}[/] [bold]{id}[/] - {name}",
-                    id = info.id,
-                    name = info.name
-                ));
+            patterns_checked += 1;
+            if worst > budget {
+                failures.push(RegexProbeResult {
+                    pack_id: pack.id.clone(),
+                    pattern_name: name.map(str::to_string),
+                    kind,
+                    pattern: regex.as_str().to_string(),
+                    worst_ms: worst.as_secs_f64() * 1000.0,
+                    worst_input_len,
+                });
             }
         }
-        con.print("");
     }
 
-    con.print("[dim]Legend: [green]●[/] = enabled, ○ = disabled[/]");
-    con.print("[dim]Enable packs in ~/.config/dcg/config.toml[/]");
+    RegexSelfTestOutput {
+        budget_ms: u64::try_from(budget.as_millis()).unwrap_or(u64::MAX),
+        patterns_checked,
+        inputs_checked,
+        passed: failures.is_empty(),
+        failures,
+    }
+}
+
+fn print_regex_selftest(output: &RegexSelfTestOutput, format: PacksFormat) {
+    use colored::Colorize;
+
+    if format == PacksFormat::Json {
+        println!("{}", serde_json::to_string_pretty(output).unwrap());
+        return;
+    }
+
+    for failure in &output.failures {
+        println!(
+            "{} {}:{} ({}) took {:.1}ms on a {}-byte input",
+            "✗".red(),
+            failure.pack_id,
+            failure.pattern_name.as_deref().unwrap_or("<unnamed>"),
+            failure.kind,
+            failure.worst_ms,
+            failure.worst_input_len
+        );
+        println!("    {}", failure.pattern.dimmed());
+    }
+    if output.passed {
+        println!(
+            "{} {} patterns stayed within {}ms across {} adversarial inputs",
+            "✓".green(),
+            output.patterns_checked,
+            output.budget_ms,
+            output.inputs_checked
+        );
+    } else {
+        println!(
+            "{} {} of {} patterns exceeded the {}ms budget",
+            "✗".red(),
+            output.failures.len(),
+            output.patterns_checked,
+            output.budget_ms
+        );
+    }
+}
+
+/// List every rule ID with severity and reason (`dcg packs --rules`)
+fn list_rules(config: &Config, filter: Option<bool>, format: PacksFormat, quiet: bool) {
+    if quiet {
+        return;
+    }
+
+    let rules = collect_rules(config, filter);
+
+    if format == PacksFormat::Json {
+        let output = RulesOutput {
+            enabled_count: rules.iter().filter(|r| r.enabled).count(),
+            total_count: rules.len(),
+            rules,
+        };
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
+
+    let width = rules.iter().map(|r| r.rule_id.len()).max().unwrap_or(0);
+    for rule in &rules {
+        let status = if rule.enabled { "✓" } else { "○" };
+        println!(
+            "{status} {:<width$}  {:<8}  {}",
+            rule.rule_id, rule.severity, rule.reason
+        );
+    }
+    println!();
+    println!("Legend: ✓ = enabled, ○ = disabled");
+}
+
+/// Rich terminal packs output using DcgConsole and markup.
+#[cfg(feature = "rich-output")]
+fn list_packs_rich(pack_list: &[PackInfo], verbose: bool) {
+    use crate::output::console::console;
+
+    let con = console();
+
+    // Header
+    con.rule(Some("[bold cyan] Available Packs [/]"));
+    con.print("");
+
+    // Group by category
+    let mut by_category: std::collections::BTreeMap<&str, Vec<_>> =
+        std::collections::BTreeMap::new();
+    for info in pack_list {
+        by_category.entry(&info.category).or_default().push(info);
+    }
+
+    for (category, packs) in by_category {
+        con.print(&format!("[bold]{category}[/]:"));
+        for info in packs {
+            let (status, color) = match (info.loaded, info.enabled) {
+                (false, _) => ("✗", "red"),
+                (true, true) => ("●", "green"),
+                (true, false) => ("○", "dim"),
+            };
+
+            if let Some(error) = info.error.as_deref() {
+                con.print(&format!(
+                    "  [{color}]{status}[/] [bold]{id}[/] - [red]not loaded:[/] {error}",
+                    id = info.id
+                ));
+            } else if verbose {
+                con.print(&format!(
+                    "  [{color}]{status}[/] [bold]{id}[/] - {desc} [dim]({safe} safe, {destr} destructive)[/]",
+                    id = info.id,
+                    desc = info.description,
+                    safe = info.safe_pattern_count,
+                    destr = info.destructive_pattern_count
+                ));
+            } else {
+                con.print(&format!(
+                    "  [{color}]{status}[/] [bold]{id}[/] - {name}",
+                    id = info.id,
+                    name = info.name
+                ));
+            }
+        }
+        con.print("");
+    }
+
+    con.print("[dim]Legend: [green]●[/] = enabled, ○ = disabled, [red]✗[/] = enabled but failed to load[/]");
+    con.print("[dim]Enable packs in ~/.config/dcg/config.toml[/]");
 }
 
 /// Show detailed information about a pack
 fn pack_info(
+    config: &Config,
     pack_id: &str,
     show_patterns: bool,
     json_output: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let pack = REGISTRY
-        .get(pack_id)
-        .ok_or_else(|| format!("Pack not found: {pack_id}"))?;
+    let pack = match REGISTRY.get(pack_id) {
+        Some(pack) => pack,
+        None => load_external_packs(&config.packs.pack_sources(), &config.packs.enable_patterns)
+            .get(pack_id)
+            .ok_or_else(|| format!("Pack not found: {pack_id}"))?,
+    };
 
     if json_output {
         #[derive(serde::Serialize)]
@@ -2372,6 +3048,7 @@ fn pack_info(
             regex: String,
             severity: String,
             reason: String,
+            enabled: bool,
             #[serde(skip_serializing_if = "Option::is_none")]
             explanation: Option<String>,
             #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -2406,6 +3083,7 @@ fn pack_info(
                         regex: p.regex.as_str().to_string(),
                         severity: p.severity.label().to_string(),
                         reason: p.reason.to_string(),
+                        enabled: p.enabled,
                         explanation: p.explanation.map(String::from),
                         suggestions: p
                             .suggestions
@@ -2461,7 +3139,11 @@ fn pack_info(
         for pattern in &pack.destructive_patterns {
             let name = pattern.name.unwrap_or("unnamed");
             let severity_label = pattern.severity.label().to_uppercase();
-            println!("  - {name} [{severity_label}] : {}", pattern.regex.as_str());
+            let state = if pattern.enabled { "" } else { " (disabled)" };
+            println!(
+                "  - {name} [{severity_label}]{state} : {}",
+                pattern.regex.as_str()
+            );
             println!("    Reason: {}", pattern.reason);
             if let Some(explanation) = pattern.explanation {
                 println!("    Explanation: {explanation}");
@@ -2484,7 +3166,7 @@ fn pack_info(
 
 /// Handle all `dcg pack` subcommands
 fn handle_pack_command(
-    _config: &Config,
+    config: &Config,
     action: PackAction,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match action {
@@ -2493,7 +3175,7 @@ fn handle_pack_command(
             no_patterns,
             json,
         } => {
-            pack_info(&pack_id, !no_patterns, json)?;
+            pack_info(config, &pack_id, !no_patterns, json)?;
         }
         PackAction::Validate {
             file_path,
@@ -2502,6 +3184,100 @@ fn handle_pack_command(
         } => {
             pack_validate(&file_path, strict, format)?;
         }
+        PackAction::RegexTest {
+            pattern,
+            input,
+            json,
+        } => {
+            pack_regex_test(&pattern, &input, json)?;
+        }
+        PackAction::Refresh => {
+            let Some(remote) = config.packs.remote_source() else {
+                return Err("no [packs] remote is configured".into());
+            };
+            let count = remote.refresh()?;
+            println!(
+                "Refreshed {count} pack(s) from {}",
+                crate::packs::source::PackSource::describe(&remote)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Result of `dcg pack regex-test`.
+#[derive(Debug, serde::Serialize)]
+struct RegexTestReport {
+    pattern: String,
+    input: String,
+    /// `"linear"` or `"backtracking"`, as selected for pack patterns.
+    engine: &'static str,
+    matched: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matched_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span: Option<(usize, usize)>,
+    /// Capture groups 1..n; `None` for groups that did not participate.
+    groups: Vec<Option<String>>,
+}
+
+/// Compile `pattern` with the pack regex engine and match it against `input`.
+fn regex_test_report(pattern: &str, input: &str) -> Result<RegexTestReport, String> {
+    let regex = crate::packs::regex_engine::CompiledRegex::new(pattern)?;
+    let captures = regex.captures(input);
+    let span = captures
+        .as_ref()
+        .and_then(|caps| caps.first().copied().flatten());
+    let groups = captures
+        .iter()
+        .flatten()
+        .skip(1)
+        .map(|group| group.map(|(start, end)| input[start..end].to_string()))
+        .collect();
+
+    Ok(RegexTestReport {
+        pattern: pattern.to_string(),
+        input: input.to_string(),
+        engine: if regex.uses_backtracking() {
+            "backtracking"
+        } else {
+            "linear"
+        },
+        matched: span.is_some(),
+        matched_text: span.map(|(start, end)| input[start..end].to_string()),
+        span,
+        groups,
+    })
+}
+
+/// Test a regex against an input the way pack patterns are evaluated
+fn pack_regex_test(
+    pattern: &str,
+    input: &str,
+    json_output: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let report = regex_test_report(pattern, input).map_err(|e| format!("Invalid regex: {e}"))?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("Pattern: {}", report.pattern.yellow());
+    println!("Engine:  {}", report.engine);
+    match (report.span, &report.matched_text) {
+        (Some((start, end)), Some(text)) => {
+            println!("{} match at {start}..{end}: {}", "✓".green(), text.bold());
+            for (index, group) in report.groups.iter().enumerate() {
+                match group {
+                    Some(text) => println!("  group {}: {text}", index + 1),
+                    None => println!("  group {}: {}", index + 1, "(unmatched)".dimmed()),
+                }
+            }
+        }
+        _ => println!("{} no match", "○".dimmed()),
     }
     Ok(())
 }
@@ -3103,9 +3879,9 @@ fn resolve_mode_for_cli(
     let pattern = info.pattern_name.as_deref();
 
     let mut mode = match info.source {
-        MatchSource::Pack | MatchSource::HeredocAst => {
-            config.policy().resolve_mode(pack, pattern, info.severity)
-        }
+        MatchSource::Pack | MatchSource::HeredocAst => result
+            .effective_mode
+            .unwrap_or_else(|| config.policy().resolve_mode(pack, pattern, info.severity)),
         MatchSource::ConfigOverride | MatchSource::LegacyPattern => DecisionMode::Deny,
     };
 
@@ -3159,6 +3935,7 @@ fn test_command(
     no_heredoc_scan: bool,
     heredoc_timeout_ms: Option<u64>,
     heredoc_languages: Option<Vec<String>>,
+    strict_quoting: bool,
 ) -> bool {
     use std::time::Instant;
 
@@ -3194,6 +3971,12 @@ fn test_command(
     if let Some(langs) = heredoc_languages {
         effective_config.heredoc.languages = Some(langs);
     }
+    if strict_quoting {
+        effective_config.evaluation.strict_quoting = true;
+    }
+    let shell_command =
+        crate::evaluator::normalize_for_shell(command, &effective_config.evaluation);
+    let command = shell_command.as_ref();
 
     // Get enabled packs and collect keywords for quick rejection
     let mut enabled_packs = effective_config.enabled_pack_ids();
@@ -3207,9 +3990,11 @@ fn test_command(
     // This is a small file read and only affects decisions when a rule matches.
     let allowlists = load_default_allowlists();
 
-    // Load external packs from custom_paths (glob + tilde expansion).
-    let external_paths = effective_config.packs.expand_custom_paths();
-    let external_store = load_external_packs(&external_paths);
+    // Load external packs from custom_paths (glob + tilde expansion) and remote.
+    let external_store = load_external_packs(
+        &effective_config.packs.pack_sources(),
+        &effective_config.packs.enable_patterns,
+    );
 
     // Auto-enable external packs and merge their keywords.
     for id in external_store.pack_ids() {
@@ -3245,20 +4030,50 @@ fn test_command(
         },
     };
 
+    let catalog = effective_config.i18n.load_catalog().unwrap_or_else(|e| {
+        eprintln!("Warning: {e}");
+        None
+    });
+
     // Use shared evaluator for consistent behavior with hook mode
     let start = Instant::now();
-    let result = evaluate_command_with_pack_order_deadline_at_path(
-        command,
-        &enabled_keywords,
-        &ordered_packs,
-        keyword_index.as_ref(),
-        &compiled_overrides,
-        &allowlists,
-        &heredoc_settings,
-        None, // allow_once_audit
-        None, // project_path
-        None, // deadline
-    );
+    let result = crate::evaluator::check_strict_quoting(command, &effective_config.evaluation)
+        .unwrap_or_else(|| {
+            let result = evaluate_command_with_pack_order_deadline_at_path(
+                command,
+                &enabled_keywords,
+                &ordered_packs,
+                keyword_index.as_ref(),
+                &compiled_overrides,
+                &allowlists,
+                &heredoc_settings,
+                None, // allow_once_audit
+                None, // project_path
+                None, // deadline
+            );
+            crate::evaluator::post_process(
+                command,
+                result,
+                &effective_config,
+                &allowlists,
+                catalog.as_deref(),
+                None,
+                |target| {
+                    evaluate_command_with_pack_order_deadline_at_path(
+                        target,
+                        &enabled_keywords,
+                        &ordered_packs,
+                        keyword_index.as_ref(),
+                        &compiled_overrides,
+                        &allowlists,
+                        &heredoc_settings,
+                        None, // allow_once_audit
+                        None, // project_path
+                        None, // deadline
+                    )
+                },
+            )
+        });
 
     // NOTE: External packs from custom_paths are now checked in evaluate_command()
     // alongside built-in packs, so no separate fallback check is needed here.
@@ -3268,7 +4083,7 @@ fn test_command(
     // Handle JSON output
     if format == TestFormat::Json {
         let output = match result.decision {
-            EvaluationDecision::Allow => {
+            Decision::Allow => {
                 let allowlist =
                     result
                         .allowlist_override
@@ -3295,7 +4110,7 @@ fn test_command(
                     agent: Some(agent_info.clone()),
                 }
             }
-            EvaluationDecision::Deny => {
+            Decision::Warn | Decision::Ask | Decision::Deny => {
                 let (
                     pack_id,
                     pattern_name,
@@ -3341,7 +4156,7 @@ fn test_command(
                     dcg_version: env!("CARGO_PKG_VERSION").to_string(),
                     robot_mode,
                     command: command.to_string(),
-                    decision: "deny".to_string(),
+                    decision: result.decision.as_str().to_string(),
                     rule_id,
                     pack_id,
                     pattern_name,
@@ -3356,7 +4171,7 @@ fn test_command(
             }
         };
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
-        return result.decision == EvaluationDecision::Deny;
+        return result.decision != Decision::Allow;
     }
 
     // Pretty output (default)
@@ -3401,7 +4216,7 @@ fn test_command(
     let resolved_mode = resolve_mode_for_cli(&effective_config, command, &result);
 
     match result.decision {
-        EvaluationDecision::Allow => {
+        Decision::Allow => {
             if let Some(override_info) = &result.allowlist_override {
                 println!(
                     "Result: ALLOWED (allowlisted by {})",
@@ -3412,7 +4227,7 @@ fn test_command(
                 println!("Result: ALLOWED");
             }
         }
-        EvaluationDecision::Deny => {
+        Decision::Warn | Decision::Ask | Decision::Deny => {
             let mut result_line = "Result: BLOCKED".to_string();
 
             if let Some(ref info) = result.pattern_info {
@@ -3448,7 +4263,7 @@ fn test_command(
                     DecisionMode::Log => {
                         result_line = "Result: LOG (policy allows)".to_string();
                     }
-                    DecisionMode::Deny => {
+                    DecisionMode::Deny | DecisionMode::Ask => {
                         // For critical/high severity, use security-aware prompt
                         // For medium/low severity, use simpler inquire-based prompt
                         if should_use_secure_prompt(info.severity) {
@@ -3608,37 +4423,162 @@ fn test_command(
         }
     }
 
-    // Return true if the command was blocked (for exit code handling)
-    result.decision == EvaluationDecision::Deny
+    // Return true if a rule matched (for exit code handling); policy may still
+    // block a warn-severity match.
+    result.decision != Decision::Allow
+}
+
+/// Scaffold the config file, the custom packs directory, and the hook wiring.
+///
+/// Re-running is safe: an existing config is kept unless `force`, and the hook
+/// is only added if it is not already installed.
+fn init_config(
+    output: Option<String>,
+    force: bool,
+    write_claude_settings: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if output.as_deref() == Some("-") {
+        println!("{}", Config::generate_sample_config());
+        return Ok(());
+    }
+
+    let path = output.map_or_else(config_path, std::path::PathBuf::from);
+    if scaffold_config(&path, force)? {
+        println!("Configuration written to: {}", path.display());
+    } else {
+        println!(
+            "Configuration already exists: {} (use --force to overwrite)",
+            path.display()
+        );
+    }
+    if let Some(packs_dir) = path.parent().map(|parent| parent.join("packs")) {
+        println!("Custom packs directory: {}", packs_dir.display());
+    }
+
+    let settings_path = claude_settings_path();
+    if write_claude_settings {
+        if install_hook_silent(false)? {
+            println!("Hook installed in: {}", settings_path.display());
+        } else {
+            println!("Hook already installed in: {}", settings_path.display());
+        }
+    } else {
+        let mut snippet = serde_json::json!({});
+        install_dcg_hook_into_settings(&mut snippet, false)?;
+        println!();
+        println!(
+            "Add this to {} (or rerun with --write-claude-settings):",
+            settings_path.display()
+        );
+        println!("{}", serde_json::to_string_pretty(&snippet)?);
+    }
+
+    Ok(())
 }
 
-/// Generate a sample configuration file
-fn init_config(output: Option<String>, force: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let sample = Config::generate_sample_config();
+/// Write built-in packs to `out` as external pack YAML for `dcg export-packs`.
+///
+/// Existing files are skipped unless `force`.
+fn export_packs(
+    out: &std::path::Path,
+    pack_ids: &[String],
+    force: bool,
+    quiet: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::packs::external::{ExternalPack, export_pack_id};
+
+    let ids: Vec<&str> = if pack_ids.is_empty() {
+        REGISTRY.all_pack_ids()
+    } else {
+        pack_ids.iter().map(String::as_str).collect()
+    };
 
-    match output {
-        Some(path) => {
-            let path = std::path::Path::new(&path);
-            if path.exists() && !force {
-                return Err(
-                    format!("File exists: {}. Use --force to overwrite.", path.display()).into(),
+    std::fs::create_dir_all(out)?;
+    let (mut written, mut skipped) = (0usize, 0usize);
+    for id in ids {
+        let Some(pack) = REGISTRY.get(id) else {
+            return Err(format!("Unknown built-in pack: {id}").into());
+        };
+        let path = out.join(format!("{id}.yaml"));
+        if path.exists() && !force {
+            if !quiet {
+                println!(
+                    "Skipped {} (exists; use --force to overwrite)",
+                    path.display()
                 );
             }
+            skipped += 1;
+            continue;
+        }
 
-            // Create parent directories if needed
-            if let Some(parent) = path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
+        let mut external = ExternalPack::from_pack(pack);
+        external.id = export_pack_id(id);
+        let yaml = external.to_yaml()?;
+        std::fs::write(
+            &path,
+            format!(
+                "# Exported from built-in pack '{id}' by dcg {}.\n\
+                 # Add this file to [packs] custom_paths to load it as '{}'.\n{yaml}",
+                env!("CARGO_PKG_VERSION"),
+                external.id
+            ),
+        )?;
+        written += 1;
+    }
 
-            std::fs::write(path, sample)?;
-            println!("Configuration written to: {}", path.display());
+    if !quiet {
+        println!("Exported {written} pack(s) to {}", out.display());
+        if skipped > 0 {
+            println!("Skipped {skipped} existing file(s)");
         }
-        None => {
-            println!("{sample}");
+    }
+    Ok(())
+}
+
+/// Write the sample config to `path` and create the `packs` directory next to it.
+///
+/// Returns `Ok(false)` and leaves an existing config untouched unless `force`.
+fn scaffold_config(
+    path: &std::path::Path,
+    force: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent.join("packs"))?;
+    }
+    if path.exists() && !force {
+        return Ok(false);
+    }
+    std::fs::write(path, Config::generate_sample_config())?;
+    Ok(true)
+}
+
+/// Print the files a config source pulls in via `include`, in merge order.
+fn print_config_includes(path: &std::path::Path) {
+    match crate::config::config_include_chain(path) {
+        Ok(chain) => {
+            for included in chain.iter().take(chain.len().saturating_sub(1)) {
+                println!("      includes: {}", included.display());
+            }
         }
+        Err(e) => println!("      include error: {e} (file skipped)"),
     }
+}
 
-    Ok(())
+/// Print `Config::validate` diagnostics for `dcg config --check`.
+fn print_config_diagnostics(diagnostics: &[ConfigDiagnostic]) {
+    use colored::Colorize;
+
+    if diagnostics.is_empty() {
+        println!("{}", "Configuration OK".green());
+        return;
+    }
+    for d in diagnostics {
+        let label = match d.severity {
+            ConfigDiagnosticSeverity::Error => d.severity.label().red(),
+            ConfigDiagnosticSeverity::Warning => d.severity.label().yellow(),
+        };
+        println!("{label}: {}: {}", d.field.bold(), d.message);
+    }
 }
 
 /// Show the current configuration
@@ -3650,14 +4590,17 @@ fn show_config(config: &Config) {
     let system_cfg = std::path::PathBuf::from("/etc/dcg").join("config.toml");
     if system_cfg.exists() {
         println!("  - system: {}", system_cfg.display());
+        print_config_includes(&system_cfg);
     }
     if user_cfg.exists() {
         println!("  - user: {}", user_cfg.display());
+        print_config_includes(&user_cfg);
     }
     if let Some(repo_root) = find_repo_root_from_cwd() {
         let project_cfg = repo_root.join(".dcg.toml");
         if project_cfg.exists() {
             println!("  - project: {}", project_cfg.display());
+            print_config_includes(&project_cfg);
         }
     }
     if let Ok(value) = std::env::var(crate::config::ENV_CONFIG_PATH) {
@@ -3667,6 +4610,7 @@ fn show_config(config: &Config) {
         ) {
             if path.exists() {
                 println!("  - DCG_CONFIG: {}", path.display());
+                print_config_includes(&path);
             } else {
                 println!("  - DCG_CONFIG: {} (missing)", path.display());
             }
@@ -3680,6 +4624,19 @@ fn show_config(config: &Config) {
     println!("  Verbose: {}", config.general.verbose);
     println!("  Log file: {:?}", config.general.log_file);
     println!();
+    match config.mode.preset {
+        Some(preset) => {
+            println!("Preset: {}", preset.label());
+            println!("  Sets (explicit config keys override these):");
+            for line in preset.settings_toml().lines() {
+                if !line.trim().is_empty() {
+                    println!("    {line}");
+                }
+            }
+        }
+        None => println!("Preset: none"),
+    }
+    println!();
     println!("Enabled packs:");
     for pack in config.enabled_pack_ids() {
         println!("  - {pack}");
@@ -3714,6 +4671,7 @@ fn show_config(config: &Config) {
             crate::heredoc::ScriptLanguage::Perl => "perl",
             crate::heredoc::ScriptLanguage::JavaScript => "javascript",
             crate::heredoc::ScriptLanguage::TypeScript => "typescript",
+            crate::heredoc::ScriptLanguage::Sql => "sql",
             crate::heredoc::ScriptLanguage::Unknown => "unknown",
         }
     };
@@ -4120,6 +5078,8 @@ fn handle_scan_command(
         git_diff,
         format,
         fail_on,
+        severity_at_least,
+        group_by,
         max_file_size,
         max_findings,
         exclude,
@@ -4127,6 +5087,10 @@ fn handle_scan_command(
         redact,
         truncate,
         top,
+        count_only,
+        dedupe,
+        baseline,
+        write_baseline,
         action,
     } = scan;
     let effective_verbose = verbosity.is_verbose();
@@ -4150,7 +5114,7 @@ fn handle_scan_command(
                 }
             }
 
-            let settings = ScanSettingsOverrides {
+            let mut settings = ScanSettingsOverrides {
                 format,
                 fail_on,
                 max_file_size,
@@ -4161,6 +5125,13 @@ fn handle_scan_command(
                 exclude,
             }
             .resolve(hooks.as_ref().map(|h| &h.cfg));
+            let hooks_format = hooks.as_ref().and_then(|h| h.cfg.scan.format);
+            if format.is_none()
+                && hooks_format.is_none()
+                && crate::scan::running_in_github_actions()
+            {
+                settings.format = crate::scan::ScanFormat::Github;
+            }
 
             handle_scan(
                 config,
@@ -4180,6 +5151,12 @@ fn handle_scan_command(
                 debug,
                 trace,
                 top,
+                count_only,
+                dedupe,
+                severity_at_least,
+                group_by,
+                baseline.as_deref(),
+                write_baseline.as_deref(),
             )?;
         }
     }
@@ -4208,6 +5185,12 @@ fn handle_scan(
     debug: bool,
     trace: bool,
     top: usize,
+    count_only: bool,
+    dedupe: bool,
+    severity_at_least: Option<crate::scan::ScanSeverityFilter>,
+    group_by: Option<crate::scan::ScanGroupBy>,
+    baseline: Option<&std::path::Path>,
+    write_baseline: Option<&std::path::Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use crate::output::progress::MaybeProgress;
     use crate::scan::{ScanEvalContext, ScanOptions, scan_paths_with_progress, should_fail};
@@ -4288,7 +5271,7 @@ fn handle_scan(
         }
     };
 
-    let report = scan_paths_with_progress(
+    let mut report = scan_paths_with_progress(
         &scan_paths_list,
         &options,
         config,
@@ -4308,29 +5291,72 @@ fn handle_scan(
         p.finish_and_clear();
     }
 
+    if let Some(path) = write_baseline {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        if !quiet {
+            eprintln!(
+                "Wrote baseline with {} finding(s) to {}",
+                report.findings.len(),
+                path.display()
+            );
+        }
+    }
+    if let Some(path) = baseline {
+        let known = load_scan_baseline(path)?;
+        report.retain_new_since(&known);
+    }
+
+    // Decide the exit status before the display filter hides anything.
+    // Writing a baseline accepts every current finding.
+    let failed = write_baseline.is_none() && should_fail(&report, fail_on);
+    if let Some(min) = severity_at_least {
+        report.retain_severity_at_least(min);
+    }
+    if dedupe {
+        report.dedupe();
+    }
+
     // Output results
-    if !quiet {
+    if !quiet && count_only {
+        let counts = report.counts();
+        match format {
+            crate::scan::ScanFormat::Json | crate::scan::ScanFormat::Sarif => {
+                println!("{}", serde_json::to_string_pretty(&counts)?);
+            }
+            crate::scan::ScanFormat::Pretty
+            | crate::scan::ScanFormat::Markdown
+            | crate::scan::ScanFormat::Github => {
+                println!("error: {}", counts.severities.error);
+                println!("warning: {}", counts.severities.warning);
+                println!("info: {}", counts.severities.info);
+                println!("total: {}", counts.findings_total);
+            }
+        }
+    } else if !quiet {
         match format {
             crate::scan::ScanFormat::Pretty => {
-                print_scan_pretty(&report, verbose, top);
+                print_scan_pretty(&report, verbose, top, group_by);
             }
             crate::scan::ScanFormat::Json => {
                 let json = serde_json::to_string_pretty(&report)?;
                 println!("{json}");
             }
             crate::scan::ScanFormat::Markdown => {
-                print_scan_markdown(&report, top, truncate);
+                print_scan_markdown(&report, top, truncate, group_by);
             }
             crate::scan::ScanFormat::Sarif => {
                 let sarif = crate::sarif::SarifReport::from_scan_report(&report);
                 let json = serde_json::to_string_pretty(&sarif)?;
                 println!("{json}");
             }
+            crate::scan::ScanFormat::Github => {
+                print_scan_github(&report);
+            }
         }
     }
 
     // Exit with appropriate code based on fail-on policy
-    if should_fail(&report, fail_on) {
+    if failed {
         std::process::exit(1);
     }
 
@@ -4454,13 +5480,81 @@ fn parse_git_name_status_z(stdout: &[u8]) -> Vec<std::path::PathBuf> {
     set.into_iter().map(std::path::PathBuf::from).collect()
 }
 
-/// Print scan report in pretty format.
-#[cfg(not(feature = "rich-output"))]
-fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize) {
-    use crate::output::{ScanResultRow, ScanResultsTable, TableStyle, auto_theme};
-    use colored::Colorize;
+/// `file:line` list for a finding collapsed by `--dedupe`.
+fn format_scan_occurrences(occurrences: &[crate::scan::ScanOccurrence]) -> String {
+    occurrences
+        .iter()
+        .map(|o| {
+            o.col.map_or_else(
+                || format!("{}:{}", o.file, o.line),
+                |col| format!("{}:{}:{col}", o.file, o.line),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
-    if report.findings.is_empty() {
+/// Read a report written by `dcg scan --write-baseline`.
+fn load_scan_baseline(
+    path: &std::path::Path,
+) -> Result<crate::scan::ScanReport, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read baseline {}: {e}", path.display()))?;
+    serde_json::from_str(&content).map_err(|e| {
+        format!(
+            "invalid baseline {} (expected `dcg scan --format json` output): {e}",
+            path.display()
+        )
+        .into()
+    })
+}
+
+/// `, N in baseline` suffix for the findings summary line.
+fn format_scan_baseline(report: &crate::scan::ScanReport) -> String {
+    report
+        .summary
+        .baseline_suppressed
+        .map(|suppressed| format!(", {suppressed} in baseline"))
+        .unwrap_or_default()
+}
+
+/// `, N after dedupe` suffix for the findings summary line.
+fn format_scan_deduped(report: &crate::scan::ScanReport) -> String {
+    report
+        .summary
+        .findings_deduped
+        .map(|deduped| format!(", {deduped} after dedupe"))
+        .unwrap_or_default()
+}
+
+/// Findings sections for pretty output: one untitled section when ungrouped.
+fn scan_sections(
+    report: &crate::scan::ScanReport,
+    group_by: Option<crate::scan::ScanGroupBy>,
+) -> Vec<crate::scan::ScanFindingGroup<'_>> {
+    group_by.map_or_else(
+        || {
+            vec![crate::scan::ScanFindingGroup {
+                key: String::new(),
+                findings: report.findings.iter().collect(),
+            }]
+        },
+        |by| report.group_findings(by),
+    )
+}
+
+/// Print scan report in pretty format.
+#[cfg(not(feature = "rich-output"))]
+fn print_scan_pretty(
+    report: &crate::scan::ScanReport,
+    verbose: bool,
+    top: usize,
+    group_by: Option<crate::scan::ScanGroupBy>,
+) {
+    use crate::output::{ScanResultRow, ScanResultsTable, TableStyle, auto_theme};
+    use colored::Colorize;
+
+    if report.findings.is_empty() {
         println!("{}", "No findings.".green());
     } else {
         let total = report.findings.len();
@@ -4468,27 +5562,37 @@ fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize
         println!("{} finding(s):", total.to_string().yellow().bold());
         println!();
 
-        // Render findings as a table
-        let rows: Vec<ScanResultRow> = report
-            .findings
-            .iter()
-            .take(shown)
-            .map(ScanResultRow::from_scan_finding)
-            .collect();
-
+        // Render findings as a table per section
         let theme = auto_theme();
-        let table = ScanResultsTable::new(rows)
-            .with_theme(&theme)
-            .with_style(TableStyle::Ascii)
-            .with_command_preview();
-
-        println!("{}", table.render());
+        let mut displayed = Vec::with_capacity(shown);
+        for section in scan_sections(report, group_by) {
+            let remaining = shown - displayed.len();
+            if remaining == 0 {
+                break;
+            }
+            if !section.key.is_empty() {
+                println!(
+                    "{} ({} finding(s))",
+                    section.key.bold(),
+                    section.findings.len()
+                );
+            }
+            let findings: Vec<_> = section.findings.into_iter().take(remaining).collect();
+            let rows: Vec<ScanResultRow> = findings
+                .iter()
+                .map(|finding| ScanResultRow::from_scan_finding(finding))
+                .collect();
+            let table = ScanResultsTable::new(rows)
+                .with_theme(&theme)
+                .with_style(TableStyle::Ascii)
+                .with_command_preview();
+            println!("{}", table.render());
+            displayed.extend(findings);
+        }
 
         // Show detailed info for findings with reasons/suggestions
-        let findings_with_details: Vec<_> = report
-            .findings
-            .iter()
-            .take(shown)
+        let findings_with_details: Vec<_> = displayed
+            .into_iter()
             .filter(|f| f.reason.is_some() || f.suggestion.is_some())
             .collect();
 
@@ -4501,6 +5605,16 @@ fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize
                     |col| format!("{}:{}:{col}", finding.file, finding.line),
                 );
                 println!("  {}", location.dimmed());
+                if !finding.occurrences.is_empty() {
+                    println!(
+                        "    Occurrences ({}): {}",
+                        finding.occurrences.len(),
+                        format_scan_occurrences(&finding.occurrences)
+                    );
+                }
+                if let Some(context) = finding.context_label() {
+                    println!("    Context: {context}");
+                }
                 if let Some(ref reason) = finding.reason {
                     println!("    Reason: {reason}");
                 }
@@ -4532,8 +5646,10 @@ fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize
     );
     println!("Commands extracted: {}", report.summary.commands_extracted);
     println!(
-        "Findings: {} (allow={}, warn={}, deny={})",
+        "Findings: {}{}{} (allow={}, warn={}, deny={})",
         report.summary.findings_total,
+        format_scan_baseline(report),
+        format_scan_deduped(report),
         report.summary.decisions.allow,
         report.summary.decisions.warn,
         report.summary.decisions.deny
@@ -4563,7 +5679,12 @@ fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize
 
 /// Print scan report in pretty format with rich output.
 #[cfg(feature = "rich-output")]
-fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize) {
+fn print_scan_pretty(
+    report: &crate::scan::ScanReport,
+    verbose: bool,
+    top: usize,
+    group_by: Option<crate::scan::ScanGroupBy>,
+) {
     use crate::output::console::console;
     use crate::output::{ScanResultRow, ScanResultsTable, auto_theme};
 
@@ -4579,26 +5700,36 @@ fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize
         con.print(&format!("[yellow bold]{total}[/] finding(s)"));
         con.print("");
 
-        // Render findings as a table using rich_rust
-        let rows: Vec<ScanResultRow> = report
-            .findings
-            .iter()
-            .take(shown)
-            .map(ScanResultRow::from_scan_finding)
-            .collect();
-
+        // Render findings as a table per section using rich_rust
         let theme = auto_theme();
-        let table = ScanResultsTable::new(rows)
-            .with_theme(&theme)
-            .with_command_preview();
-
-        con.print(&table.render());
+        let mut displayed = Vec::with_capacity(shown);
+        for section in scan_sections(report, group_by) {
+            let remaining = shown - displayed.len();
+            if remaining == 0 {
+                break;
+            }
+            if !section.key.is_empty() {
+                con.print(&format!(
+                    "[bold]{}[/] ({} finding(s))",
+                    section.key,
+                    section.findings.len()
+                ));
+            }
+            let findings: Vec<_> = section.findings.into_iter().take(remaining).collect();
+            let rows: Vec<ScanResultRow> = findings
+                .iter()
+                .map(|finding| ScanResultRow::from_scan_finding(finding))
+                .collect();
+            let table = ScanResultsTable::new(rows)
+                .with_theme(&theme)
+                .with_command_preview();
+            con.print(&table.render());
+            displayed.extend(findings);
+        }
 
         // Show detailed info for findings with reasons/suggestions
-        let findings_with_details: Vec<_> = report
-            .findings
-            .iter()
-            .take(shown)
+        let findings_with_details: Vec<_> = displayed
+            .into_iter()
             .filter(|f| f.reason.is_some() || f.suggestion.is_some())
             .collect();
 
@@ -4611,6 +5742,16 @@ fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize
                     |col| format!("{}:{}:{col}", finding.file, finding.line),
                 );
                 con.print(&format!("  [dim]{location}[/]"));
+                if !finding.occurrences.is_empty() {
+                    con.print(&format!(
+                        "    [cyan]Occurrences ({}):[/] {}",
+                        finding.occurrences.len(),
+                        format_scan_occurrences(&finding.occurrences)
+                    ));
+                }
+                if let Some(context) = finding.context_label() {
+                    con.print(&format!("    [cyan]Context:[/] {context}"));
+                }
                 if let Some(ref reason) = finding.reason {
                     con.print(&format!("    [cyan]Reason:[/] {reason}"));
                 }
@@ -4642,8 +5783,10 @@ fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize
         report.summary.commands_extracted
     ));
     con.print(&format!(
-        "[cyan]Findings:[/] {} ([green]allow={}[/], [yellow]warn={}[/], [red]deny={}[/])",
+        "[cyan]Findings:[/] {}{}{} ([green]allow={}[/], [yellow]warn={}[/], [red]deny={}[/])",
         report.summary.findings_total,
+        format_scan_baseline(report),
+        format_scan_deduped(report),
         report.summary.decisions.allow,
         report.summary.decisions.warn,
         report.summary.decisions.deny
@@ -4672,11 +5815,16 @@ fn print_scan_pretty(report: &crate::scan::ScanReport, verbose: bool, top: usize
 ///
 /// Output structure:
 /// - Summary header with findings counts
-/// - Findings grouped by file, each in a `<details>` block
+/// - Findings grouped by file (or `--group-by`), each group in a `<details>` block
 /// - Severity badges (error/warning/info)
 /// - Truncated command preview for readability
-fn print_scan_markdown(report: &crate::scan::ScanReport, top: usize, truncate: usize) {
-    use std::collections::BTreeMap;
+fn print_scan_markdown(
+    report: &crate::scan::ScanReport,
+    top: usize,
+    truncate: usize,
+    group_by: Option<crate::scan::ScanGroupBy>,
+) {
+    use crate::scan::ScanGroupBy;
 
     // Header
     println!("## DCG Scan Results\n");
@@ -4703,21 +5851,18 @@ fn print_scan_markdown(report: &crate::scan::ScanReport, top: usize, truncate: u
     }
     println!("\n");
 
-    // Group findings by file
-    let mut by_file: BTreeMap<&str, Vec<&crate::scan::ScanFinding>> = BTreeMap::new();
-    for finding in &report.findings {
-        by_file.entry(&finding.file).or_default().push(finding);
-    }
+    let group_by = group_by.unwrap_or(ScanGroupBy::File);
 
     // Limit total findings shown
     let total_findings = report.findings.len();
     let limit = if top == 0 { usize::MAX } else { top };
     let mut shown = 0;
 
-    for (file, findings) in &by_file {
+    for group in report.group_findings(group_by) {
         if shown >= limit {
             break;
         }
+        let (key, findings) = (group.key, group.findings);
 
         let file_errors = findings
             .iter()
@@ -4743,7 +5888,15 @@ fn print_scan_markdown(report: &crate::scan::ScanReport, top: usize, truncate: u
         };
 
         println!("<details>");
-        println!("<summary><code>{file}</code>{summary_suffix}</summary>\n");
+        let title = if group_by == ScanGroupBy::Severity {
+            format!("<strong>{key}</strong>")
+        } else {
+            format!("<code>{key}</code>")
+        };
+        println!(
+            "<summary>{title} ({} finding(s)){summary_suffix}</summary>\n",
+            findings.len()
+        );
 
         for finding in findings {
             if shown >= limit {
@@ -4770,7 +5923,14 @@ fn print_scan_markdown(report: &crate::scan::ScanReport, top: usize, truncate: u
             // Truncate command for readability
             let cmd_preview = truncate_for_markdown(&finding.extracted_command, truncate);
 
-            println!("{severity_badge} **{decision_str}** at line {location}");
+            if group_by == ScanGroupBy::File {
+                println!("{severity_badge} **{decision_str}** at line {location}");
+            } else {
+                println!(
+                    "{severity_badge} **{decision_str}** at `{}` line {location}",
+                    finding.file
+                );
+            }
             println!("```");
             println!("{cmd_preview}");
             println!("```");
@@ -4778,6 +5938,16 @@ fn print_scan_markdown(report: &crate::scan::ScanReport, top: usize, truncate: u
             if let Some(ref rule_id) = finding.rule_id {
                 println!("- **Rule:** `{rule_id}`");
             }
+            if !finding.occurrences.is_empty() {
+                println!(
+                    "- **Occurrences ({}):** {}",
+                    finding.occurrences.len(),
+                    format_scan_occurrences(&finding.occurrences)
+                );
+            }
+            if let Some(context) = finding.context_label() {
+                println!("- **Context:** {context}");
+            }
             if let Some(ref reason) = finding.reason {
                 println!("- **Reason:** {reason}");
             }
@@ -4799,6 +5969,85 @@ fn print_scan_markdown(report: &crate::scan::ScanReport, top: usize, truncate: u
     print_scan_markdown_summary(report);
 }
 
+/// Print scan report as GitHub Actions workflow commands.
+///
+/// Each finding becomes an `::error`/`::warning`/`::notice` annotation so it
+/// shows up inline on the PR diff, followed by a plain-text summary for the
+/// step log.
+fn print_scan_github(report: &crate::scan::ScanReport) {
+    for finding in &report.findings {
+        println!("{}", format_github_annotation(finding));
+    }
+
+    let summary = &report.summary;
+    println!(
+        "dcg scan: {} finding(s) ({} error, {} warning, {} info) in {} file(s) scanned",
+        summary.findings_total,
+        summary.severities.error,
+        summary.severities.warning,
+        summary.severities.info,
+        summary.files_scanned
+    );
+    if summary.max_findings_reached {
+        println!("dcg scan: max findings limit reached, scan stopped early");
+    }
+}
+
+/// Format one finding as a GitHub Actions workflow command.
+///
+/// See <https://docs.github.com/en/actions/reference/workflow-commands-for-github-actions>.
+fn format_github_annotation(finding: &crate::scan::ScanFinding) -> String {
+    use std::fmt::Write as _;
+
+    let command = match finding.severity {
+        crate::scan::ScanSeverity::Error => "error",
+        crate::scan::ScanSeverity::Warning => "warning",
+        crate::scan::ScanSeverity::Info => "notice",
+    };
+
+    let mut properties = vec![
+        format!("file={}", escape_github_property(&finding.file)),
+        format!("line={}", finding.line),
+    ];
+    if let Some(col) = finding.col {
+        properties.push(format!("col={col}"));
+    }
+    let title = finding
+        .rule_id
+        .as_ref()
+        .map_or_else(|| "dcg".to_string(), |rule_id| format!("dcg: {rule_id}"));
+    properties.push(format!("title={}", escape_github_property(&title)));
+
+    let mut message = finding
+        .reason
+        .clone()
+        .unwrap_or_else(|| "Destructive command".to_string());
+    let _ = write!(message, "\n{}", finding.extracted_command);
+    if let Some(ref suggestion) = finding.suggestion {
+        let _ = write!(message, "\nSuggestion: {suggestion}");
+    }
+
+    format!(
+        "::{command} {}::{}",
+        properties.join(","),
+        escape_github_data(&message)
+    )
+}
+
+/// Escape a workflow command message (`%`, CR, LF).
+fn escape_github_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value (message escapes plus `:` and `,`).
+fn escape_github_property(s: &str) -> String {
+    escape_github_data(s)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
 /// Print markdown summary section.
 fn print_scan_markdown_summary(report: &crate::scan::ScanReport) {
     println!("---\n");
@@ -4845,14 +6094,26 @@ fn truncate_for_markdown(s: &str, max_len: usize) -> String {
 ///
 /// Shows a detailed decision trace for why a command would be allowed or denied.
 /// Currently wraps the evaluator result; full tracing integration is future work.
-#[allow(clippy::needless_pass_by_value)] // Value consumed from CLI args
 fn handle_explain(
     config: &Config,
     command: &str,
     format: ExplainFormat,
     extra_packs: Option<Vec<String>>,
 ) {
-    use crate::trace::{MatchInfo, TraceCollector, TraceDetails};
+    let (trace, _) = build_explain_trace(config, command, extra_packs);
+    print_explain_trace(&trace, format);
+}
+
+/// Evaluate `command` with tracing enabled.
+///
+/// Returns the trace along with the ordered list of enabled pack IDs.
+#[allow(clippy::needless_pass_by_value)] // Value consumed from CLI args
+fn build_explain_trace(
+    config: &Config,
+    command: &str,
+    extra_packs: Option<Vec<String>>,
+) -> (crate::trace::ExplainTrace, Vec<String>) {
+    use crate::trace::{AllowlistInfo, TraceCollector, TraceDetails};
 
     // Build effective config with extra packs if specified
     let effective_config = extra_packs.map_or_else(
@@ -4863,6 +6124,9 @@ fn handle_explain(
             modified
         },
     );
+    let shell_command =
+        crate::evaluator::normalize_for_shell(command, &effective_config.evaluation);
+    let command = shell_command.as_ref();
 
     // Get enabled packs and collect keywords
     let enabled_packs = effective_config.enabled_pack_ids();
@@ -4871,7 +6135,7 @@ fn handle_explain(
     let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
     let heredoc_settings = effective_config.heredoc_settings();
     let compiled_overrides = effective_config.overrides.compile();
-    let allowlists = crate::LayeredAllowlist::default();
+    let allowlists = load_default_allowlists();
 
     // Start tracing
     let mut collector = TraceCollector::new(command);
@@ -4890,44 +6154,143 @@ fn handle_explain(
     collector.end_step(
         "full_evaluation",
         TraceDetails::KeywordGating {
-            quick_rejected: result.decision == EvaluationDecision::Allow
-                && result.pattern_info.is_none(),
+            quick_rejected: result.decision == Decision::Allow && result.pattern_info.is_none(),
             keywords_checked: enabled_keywords.iter().map(|s| (*s).to_string()).collect(),
             first_match: result.pattern_info.as_ref().and_then(|p| p.pack_id.clone()),
         },
     );
     collector.set_budget_skip(result.skipped_due_to_budget);
 
+    // Run the same post-processing as hook mode so explain reports its decision
+    collector.begin_step();
+    let analysis_incomplete = result.skipped_due_to_budget;
+    let catalog = effective_config.i18n.load_catalog().ok().flatten();
+    let result = crate::evaluator::post_process(
+        command,
+        result,
+        &effective_config,
+        &allowlists,
+        catalog.as_deref(),
+        None,
+        |target| {
+            evaluate_command_with_pack_order(
+                target,
+                &enabled_keywords,
+                &ordered_packs,
+                keyword_index.as_ref(),
+                &compiled_overrides,
+                &allowlists,
+                &heredoc_settings,
+            )
+        },
+    );
+    collector.end_step(
+        "post_processing",
+        TraceDetails::ErrorPolicy {
+            on_error: effective_config.evaluation.on_error.label().to_string(),
+            analysis_incomplete,
+        },
+    );
+
     // Add match info if present
     if let Some(ref pattern) = result.pattern_info {
-        let rule_id = pattern
-            .pack_id
-            .as_ref()
-            .zip(pattern.pattern_name.as_ref())
-            .map(|(pack, name)| format!("{pack}:{name}"));
-        collector.set_match(MatchInfo {
-            rule_id,
-            pack_id: pattern.pack_id.clone(),
-            pattern_name: pattern.pattern_name.clone(),
-            severity: pattern.severity,
-            reason: pattern.reason.clone(),
-            source: pattern.source,
-            match_start: pattern.matched_span.map(|s| s.start),
-            match_end: pattern.matched_span.map(|s| s.end),
-            matched_text_preview: pattern.matched_text_preview.clone(),
-            explanation: pattern.explanation.clone(),
+        collector.set_match(explain_match_info(pattern));
+    }
+    if let Some(ref allowlisted) = result.allowlist_override {
+        collector.set_allowlist(AllowlistInfo {
+            layer: allowlisted.layer,
+            entry_reason: allowlisted.reason.clone(),
+            original_match: explain_match_info(&allowlisted.matched),
         });
     }
+    if let Some(reason) = explain_allow_reason(&effective_config, command, &ordered_packs, &result)
+    {
+        collector.set_allow_reason(reason);
+    }
+    collector.set_effective_mode(result.effective_mode);
+
+    (collector.finish(result.decision), ordered_packs)
+}
+
+fn explain_match_info(pattern: &crate::evaluator::PatternMatch) -> crate::trace::MatchInfo {
+    let rule_id = pattern
+        .pack_id
+        .as_ref()
+        .zip(pattern.pattern_name.as_ref())
+        .map(|(pack, name)| format!("{pack}:{name}"));
+    crate::trace::MatchInfo {
+        rule_id,
+        pack_id: pattern.pack_id.clone(),
+        pattern_name: pattern.pattern_name.clone(),
+        severity: pattern.severity,
+        reason: pattern.reason.clone(),
+        source: pattern.source,
+        match_start: pattern.matched_span.map(|s| s.start),
+        match_end: pattern.matched_span.map(|s| s.end),
+        matched_text_preview: pattern.matched_text_preview.clone(),
+        explanation: pattern.explanation.clone(),
+    }
+}
+
+/// Work out why an explained command is allowed.
+///
+/// Returns `None` when the command is denied or when analysis was cut short
+/// by the time budget.
+fn explain_allow_reason(
+    config: &Config,
+    command: &str,
+    ordered_packs: &[String],
+    result: &EvaluationResult,
+) -> Option<crate::trace::AllowReason> {
+    use crate::trace::AllowReason;
+
+    if let Some(ref allowlisted) = result.allowlist_override {
+        return Some(AllowReason::Allowlist(allowlisted.layer));
+    }
+    if result.pattern_info.is_some() {
+        return match result.effective_mode.unwrap_or(DecisionMode::Deny) {
+            DecisionMode::Deny | DecisionMode::Ask => None,
+            DecisionMode::Warn | DecisionMode::Log if config.policy().in_observe_window() => {
+                Some(AllowReason::ReportMode)
+            }
+            DecisionMode::Warn | DecisionMode::Log => Some(AllowReason::BelowThreshold),
+        };
+    }
+    if result.skipped_due_to_budget {
+        return None;
+    }
+
+    let safe_rule = ordered_packs.iter().find_map(|pack_id| {
+        let pack = REGISTRY.get(pack_id)?;
+        if !pack.might_match(command) || !pack.matches_safe_command(command) {
+            return None;
+        }
+        pack.matching_safe_pattern(command)
+            .map(|name| format!("{pack_id}:{name}"))
+    });
+    Some(safe_rule.map_or(AllowReason::NoMatch, AllowReason::SafePattern))
+}
 
-    // Finish and get trace
-    let trace = collector.finish(result.decision);
+/// Map an explain decision to the standard exit codes for `--exit-code`.
+///
+/// The code follows the evaluated decision: a warn is `EXIT_WARNING`, and a
+/// deny whose effective mode only logs is `EXIT_SUCCESS`.
+const fn explain_exit_code(trace: &crate::trace::ExplainTrace) -> i32 {
+    match trace.decision {
+        Decision::Allow => EXIT_SUCCESS,
+        Decision::Warn => EXIT_WARNING,
+        Decision::Deny if matches!(trace.effective_mode, Some(DecisionMode::Log)) => EXIT_SUCCESS,
+        Decision::Deny | Decision::Ask => EXIT_DENIED,
+    }
+}
 
-    // Format and print based on selected format
+/// Print an explain trace in the selected format.
+fn print_explain_trace(trace: &crate::trace::ExplainTrace, format: ExplainFormat) {
     match format {
         ExplainFormat::Pretty => {
             #[cfg(feature = "rich-output")]
             {
-                explain_rich(&trace);
+                explain_rich(trace);
             }
             #[cfg(not(feature = "rich-output"))]
             {
@@ -4948,10 +6311,119 @@ fn handle_explain(
     }
 }
 
+/// Whether `dcg explain` should redact the echoed command.
+///
+/// `--redact`/`--no-redact` win; otherwise redact when output is piped or in CI,
+/// where it is likely to be pasted or archived rather than read directly.
+fn explain_should_redact(redact: bool, no_redact: bool) -> bool {
+    if redact || no_redact {
+        return redact;
+    }
+    std::env::var("CI").is_ok() || !std::io::stdout().is_terminal()
+}
+
+/// Decision tallies for `dcg explain --batch`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+struct ExplainBatchSummary {
+    total: usize,
+    allow: usize,
+    warn: usize,
+    ask: usize,
+    deny: usize,
+}
+
+/// Exit code for `dcg explain --batch --exit-code`.
+///
+/// Each command is mapped by [`explain_exit_code`] and the strictest code wins:
+/// any deny gives `EXIT_DENIED`, otherwise any warn gives `EXIT_WARNING`.
+fn explain_batch_exit_code(entries: &[(usize, crate::trace::ExplainTrace)]) -> i32 {
+    entries
+        .iter()
+        .map(|(_, trace)| explain_exit_code(trace))
+        .max_by_key(|code| match *code {
+            EXIT_DENIED => 2,
+            EXIT_WARNING => 1,
+            _ => 0,
+        })
+        .unwrap_or(EXIT_SUCCESS)
+}
+
+/// Explain each command in `contents`, one per line.
+///
+/// Blank lines and `#` comments are skipped. Returns the 1-based line number
+/// and trace for every evaluated command, plus the per-decision tallies.
+fn explain_batch(
+    config: &Config,
+    contents: &str,
+    extra_packs: Option<Vec<String>>,
+) -> (
+    Vec<(usize, crate::trace::ExplainTrace)>,
+    ExplainBatchSummary,
+) {
+    let mut summary = ExplainBatchSummary::default();
+    let entries: Vec<_> = contents
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let command = line.trim();
+            if command.is_empty() || command.starts_with('#') {
+                return None;
+            }
+            let (trace, _) = build_explain_trace(config, command, extra_packs.clone());
+            summary.total += 1;
+            match trace.decision {
+                Decision::Allow => summary.allow += 1,
+                Decision::Warn => summary.warn += 1,
+                Decision::Ask => summary.ask += 1,
+                Decision::Deny => summary.deny += 1,
+            }
+            Some((idx + 1, trace))
+        })
+        .collect();
+    (entries, summary)
+}
+
+/// Print batch explain results: one line per command, then the summary.
+fn print_explain_batch(
+    entries: &[(usize, crate::trace::ExplainTrace)],
+    summary: ExplainBatchSummary,
+    format: ExplainFormat,
+) {
+    match format {
+        ExplainFormat::Pretty | ExplainFormat::Compact => {
+            for (line, trace) in entries {
+                println!("{line:>4}: {}", trace.format_compact(None));
+            }
+            println!(
+                "\n{} command(s): {} allow, {} warn, {} ask, {} deny",
+                summary.total, summary.allow, summary.warn, summary.ask, summary.deny
+            );
+        }
+        ExplainFormat::Json => {
+            let results: Vec<_> = entries
+                .iter()
+                .map(|(line, trace)| {
+                    serde_json::json!({
+                        "line": line,
+                        "command": trace.command,
+                        "decision": trace.decision.as_str(),
+                        "rule_id": trace.match_info.as_ref().and_then(|m| m.rule_id.clone()),
+                        "reason": trace.reason(),
+                    })
+                })
+                .collect();
+            let output = serde_json::json!({ "results": results, "summary": summary });
+            let json = serde_json::to_string_pretty(&output)
+                .unwrap_or_else(|e| format!("{{\"error\": \"JSON serialization failed: {e}\"}}"));
+            println!("{json}");
+        }
+    }
+}
+
 /// Rich output for explain command with tree visualization.
 #[cfg(feature = "rich-output")]
 fn explain_rich(trace: &crate::trace::ExplainTrace) {
-    use crate::evaluator::EvaluationDecision;
+    use crate::evaluator::Decision;
     use crate::output::console::console;
     use crate::trace::TraceDetails;
 
@@ -4963,12 +6435,17 @@ fn explain_rich(trace: &crate::trace::ExplainTrace) {
 
     // Decision with color
     let (decision_icon, decision_color, decision_text) = match trace.decision {
-        EvaluationDecision::Allow => ("✓", "green", "ALLOW"),
-        EvaluationDecision::Deny => ("✗", "red", "DENY"),
+        Decision::Allow => ("✓", "green", "ALLOW"),
+        Decision::Warn => ("!", "yellow", "WARN"),
+        Decision::Deny => ("✗", "red", "DENY"),
+        Decision::Ask => ("?", "yellow", "ASK"),
     };
     con.print(&format!(
         "[bold]Decision:[/] [{decision_color} bold]{decision_icon} {decision_text}[/]"
     ));
+    if let Some(ref reason) = trace.allow_reason {
+        con.print(&format!("[bold]Allowed:[/]  {}", reason.label()));
+    }
     con.print(&format!(
         "[bold]Latency:[/]  [dim]{:.2}ms[/]",
         trace.total_duration_us as f64 / 1000.0
@@ -5174,8 +6651,10 @@ fn explain_rich(trace: &crate::trace::ExplainTrace) {
                     }
                 }
                 TraceDetails::PolicyDecision { decision, .. } => match decision {
-                    EvaluationDecision::Allow => "[green]allow[/]".to_string(),
-                    EvaluationDecision::Deny => "[red]deny[/]".to_string(),
+                    Decision::Allow => "[green]allow[/]".to_string(),
+                    Decision::Warn => "[yellow]warn[/]".to_string(),
+                    Decision::Deny => "[red]deny[/]".to_string(),
+                    Decision::Ask => "[yellow]ask[/]".to_string(),
                 },
                 _ => String::new(),
             };
@@ -5549,10 +7028,7 @@ fn run_single_corpus_test(
     );
     let duration_us = u64::try_from(start.elapsed().as_micros()).unwrap_or(u64::MAX);
 
-    let actual = match result.decision {
-        EvaluationDecision::Allow => "allow",
-        EvaluationDecision::Deny => "deny",
-    };
+    let actual = result.decision.as_str();
 
     // Extract pattern info
     let (pack_id, pattern_name, actual_rule_id, match_source) = result
@@ -5682,6 +7158,11 @@ fn handle_stats_command(
         return handle_stats_rules(config, cmd);
     }
 
+    // Handle --since mode (at-a-glance history analytics)
+    if let Some(ref since) = cmd.since {
+        return handle_stats_overview(config, cmd, since);
+    }
+
     // Determine log file path
     let log_path = if let Some(ref path) = cmd.file {
         path.clone()
@@ -5705,8 +7186,8 @@ fn handle_stats_command(
 
     // Check if log file exists
     if !log_path.exists() {
-        if matches!(cmd.format, StatsFormat::Json) {
-            // Output empty stats for JSON format
+        if cmd.format != StatsFormat::Pretty {
+            // Output empty stats for machine-readable formats
             let empty_stats = stats::AggregatedStats {
                 period_start: 0,
                 period_end: 0,
@@ -5717,7 +7198,12 @@ fn handle_stats_command(
                 total_warns: 0,
                 by_pack: vec![],
             };
-            print!("{}", stats::format_stats_json(&empty_stats));
+            match cmd.format.delimited() {
+                Some(delimited) => {
+                    print!("{}", stats::format_stats_delimited(&empty_stats, delimited));
+                }
+                None => print!("{}", stats::format_stats_json(&empty_stats)),
+            }
             return Ok(());
         }
         println!("No log file found at: {}", log_path.display());
@@ -5752,6 +7238,11 @@ fn handle_stats_command(
         StatsFormat::Json => {
             print!("{}", stats::format_stats_json(&aggregated));
         }
+        StatsFormat::Csv | StatsFormat::Tsv => {
+            if let Some(delimited) = cmd.format.delimited() {
+                print!("{}", stats::format_stats_delimited(&aggregated, delimited));
+            }
+        }
     }
 
     Ok(())
@@ -5770,6 +7261,10 @@ fn handle_stats_rules(
     let db = match HistoryDb::open(db_path) {
         Ok(db) => db,
         Err(err) => {
+            if let Some(delimited) = cmd.format.delimited() {
+                print!("{}", format_rule_metrics_delimited(&[], delimited));
+                return Ok(());
+            }
             if matches!(cmd.format, StatsFormat::Json) {
                 // Output empty metrics for JSON format
                 print!("{}", format_rule_metrics_json(&[], cmd.days)?);
@@ -5796,6 +7291,10 @@ fn handle_stats_rules(
     let metrics = db.get_rule_metrics(since, cmd.limit)?;
 
     if metrics.is_empty() {
+        if let Some(delimited) = cmd.format.delimited() {
+            print!("{}", format_rule_metrics_delimited(&[], delimited));
+            return Ok(());
+        }
         if matches!(cmd.format, StatsFormat::Json) {
             // Output empty metrics for JSON format
             print!("{}", format_rule_metrics_json(&[], cmd.days)?);
@@ -5823,11 +7322,182 @@ fn handle_stats_rules(
         StatsFormat::Json => {
             print!("{}", format_rule_metrics_json(&metrics, cmd.days)?);
         }
+        StatsFormat::Csv | StatsFormat::Tsv => {
+            if let Some(delimited) = cmd.format.delimited() {
+                print!("{}", format_rule_metrics_delimited(&metrics, delimited));
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Handle the `dcg stats --since <duration>` command.
+fn handle_stats_overview(
+    config: &Config,
+    cmd: &StatsCommand,
+    since: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::history::HistoryDb;
+    use chrono::Utc;
+
+    let duration = parse_duration_string(since)?;
+    let since_time = Utc::now() - duration;
+
+    let db_path = config.history.expanded_database_path();
+    let db = match HistoryDb::open(db_path) {
+        Ok(db) => db,
+        Err(err) => {
+            if matches!(err, crate::history::HistoryError::Disabled) {
+                println!("History is disabled. Enable it in config to use history stats.");
+                println!();
+                println!("To enable history, add to your config (~/.config/dcg/config.toml):");
+                println!();
+                println!("  [history]");
+                println!("  enabled = true");
+                return Ok(());
+            }
+            return Err(format!("Error opening history database: {err}").into());
+        }
+    };
+
+    let overview = db.compute_overview(since_time, cmd.limit)?;
+
+    match cmd.format {
+        StatsFormat::Pretty => print!("{}", format_history_overview_pretty(&overview, since)),
+        StatsFormat::Json => println!("{}", serde_json::to_string_pretty(&overview)?),
+        StatsFormat::Csv | StatsFormat::Tsv => {
+            if let Some(delimited) = cmd.format.delimited() {
+                print!(
+                    "{}",
+                    format_history_overview_delimited(&overview, delimited)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Format a history overview as delimited text.
+///
+/// The overview mixes several tables, so rows are `section,key,count`:
+/// `outcome` (allow/deny/warn/bypass), `denied_rule`, `directory`, `agent`.
+fn format_history_overview_delimited(
+    overview: &crate::history::HistoryOverview,
+    format: crate::output::DelimitedFormat,
+) -> String {
+    let outcomes = &overview.outcomes;
+    let mut rows: Vec<(&str, &str, u64)> = vec![
+        ("outcome", "allow", outcomes.allowed),
+        ("outcome", "deny", outcomes.denied),
+        ("outcome", "warn", outcomes.warned),
+        ("outcome", "bypass", outcomes.bypassed),
+    ];
+    rows.extend(
+        overview
+            .top_denied_rules
+            .iter()
+            .map(|rule| ("denied_rule", rule.rule_id.as_str(), rule.count)),
+    );
+    rows.extend(
+        overview
+            .busiest_dirs
+            .iter()
+            .map(|dir| ("directory", dir.path.as_str(), dir.command_count)),
+    );
+    rows.extend(
+        overview
+            .agents
+            .iter()
+            .map(|agent| ("agent", agent.name.as_str(), agent.total)),
+    );
+
+    let mut output = format.record(["section", "key", "count"]);
+    output.push('\n');
+    for (section, key, count) in rows {
+        output.push_str(&format.record([section, key, &count.to_string()]));
+        output.push('\n');
+    }
+    output
+}
+
+/// Format a history overview for terminal output.
+fn format_history_overview_pretty(
+    overview: &crate::history::HistoryOverview,
+    since: &str,
+) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    let _ = writeln!(output, "History overview (last {since})");
+    let _ = writeln!(output, "Total commands: {}", overview.total_commands);
+
+    if overview.total_commands == 0 {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "No commands recorded in this period.");
+        return output;
+    }
+
+    let _ = writeln!(
+        output,
+        "Outcomes: deny {} ({:.1}%) | allow {} ({:.1}%) | warn {} ({:.1}%) | bypass {} ({:.1}%)",
+        overview.outcomes.denied,
+        overview.ratios.deny * 100.0,
+        overview.outcomes.allowed,
+        overview.ratios.allow * 100.0,
+        overview.outcomes.warned,
+        overview.ratios.warn * 100.0,
+        overview.outcomes.bypassed,
+        overview.ratios.bypass * 100.0
+    );
+    if overview.latency.max_us > 0 {
+        let _ = writeln!(
+            output,
+            "Latency (us): p50 {} | p95 {} | p99 {} | max {}",
+            overview.latency.p50_us,
+            overview.latency.p95_us,
+            overview.latency.p99_us,
+            overview.latency.max_us
+        );
+    }
+
+    if !overview.top_denied_rules.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "Top denied rules:");
+        for rule in &overview.top_denied_rules {
+            let _ = writeln!(output, "  {:>6}  {}", rule.count, rule.rule_id);
+        }
+    }
+
+    if !overview.busiest_dirs.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "Busiest working directories:");
+        for dir in &overview.busiest_dirs {
+            let _ = writeln!(output, "  {:>6}  {}", dir.command_count, dir.path);
+        }
+    }
+
+    if !overview.agents.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "Agents:");
+        for agent in &overview.agents {
+            let _ = writeln!(
+                output,
+                "  {:>6}  {} (deny {} | allow {} | warn {} | bypass {})",
+                agent.total,
+                agent.name,
+                agent.outcomes.denied,
+                agent.outcomes.allowed,
+                agent.outcomes.warned,
+                agent.outcomes.bypassed
+            );
+        }
+    }
+
+    output
+}
+
 /// Format rule metrics as a pretty table.
 #[cfg(not(feature = "rich-output"))]
 #[allow(clippy::too_many_lines)]
@@ -6116,6 +7786,45 @@ struct RuleMetricsTotals {
     rule_count: usize,
 }
 
+/// Format rule metrics as CSV/TSV.
+fn format_rule_metrics_delimited(
+    metrics: &[crate::history::RuleMetrics],
+    format: crate::output::DelimitedFormat,
+) -> String {
+    let mut output = format.record([
+        "rule_id",
+        "total_hits",
+        "allowlist_overrides",
+        "override_rate",
+        "unique_commands",
+        "first_seen",
+        "last_seen",
+        "trend",
+        "is_noisy",
+    ]);
+    output.push('\n');
+    for m in metrics {
+        let trend = match m.trend {
+            crate::history::RuleTrend::Increasing => "increasing",
+            crate::history::RuleTrend::Stable => "stable",
+            crate::history::RuleTrend::Decreasing => "decreasing",
+        };
+        output.push_str(&format.record([
+            m.rule_id.clone(),
+            m.total_hits.to_string(),
+            m.allowlist_overrides.to_string(),
+            format!("{:.2}", m.override_rate),
+            m.unique_commands.to_string(),
+            m.first_seen.to_rfc3339(),
+            m.last_seen.to_rfc3339(),
+            trend.to_string(),
+            m.is_noisy.to_string(),
+        ]));
+        output.push('\n');
+    }
+    output
+}
+
 /// Format rule metrics as JSON.
 fn format_rule_metrics_json(
     metrics: &[crate::history::RuleMetrics],
@@ -6249,6 +7958,7 @@ fn handle_suggest_allowlist_command(
         since: Some(since_time),
         until: None,
         limit: None,
+        min_duration_us: None,
     };
 
     let entries = db.query_commands_for_export(&options)?;
@@ -6273,6 +7983,7 @@ fn handle_suggest_allowlist_command(
         since: Some(since_time),
         until: None,
         limit: None,
+        min_duration_us: None,
     };
     let bypass_entries = db
         .query_commands_for_export(&bypass_options)
@@ -6684,6 +8395,168 @@ fn output_suggestions_interactive(
     Ok(())
 }
 
+/// A history entry re-evaluated under the current config.
+#[derive(Debug, Clone, serde::Serialize)]
+struct ReplayedEntry {
+    id: i64,
+    command: String,
+    then: Outcome,
+    now: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    then_rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    now_rule_id: Option<String>,
+    changed: bool,
+}
+
+/// Re-evaluate history entries with the current config.
+///
+/// Each entry goes through the same post-processing as hook mode, run in the
+/// entry's recorded working directory. The "now" outcome follows hook mode:
+/// a match resolved to `Log` is recorded as `allow`, matching what the hook
+/// writes to history.
+fn replay_entries(config: &Config, entries: Vec<(i64, CommandEntry)>) -> Vec<ReplayedEntry> {
+    let enabled_packs = config.enabled_pack_ids();
+    let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+    let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
+    let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
+    let heredoc_settings = config.heredoc_settings();
+    let compiled_overrides = config.overrides.compile();
+    let allowlists = load_default_allowlists();
+    let catalog = config.i18n.load_catalog().ok().flatten();
+    let evaluate = |command: &str| {
+        evaluate_command_with_pack_order(
+            command,
+            &enabled_keywords,
+            &ordered_packs,
+            keyword_index.as_ref(),
+            &compiled_overrides,
+            &allowlists,
+            &heredoc_settings,
+        )
+    };
+
+    entries
+        .into_iter()
+        .map(|(id, entry)| {
+            let shell_command =
+                crate::evaluator::normalize_for_shell(&entry.command, &config.evaluation);
+            let command = shell_command.as_ref();
+            let working_dir = Some(std::path::Path::new(&entry.working_dir))
+                .filter(|dir| !entry.working_dir.is_empty() && dir.is_dir());
+            let result = crate::evaluator::check_strict_quoting(command, &config.evaluation)
+                .unwrap_or_else(|| {
+                    crate::evaluator::post_process(
+                        command,
+                        evaluate(command),
+                        config,
+                        &allowlists,
+                        catalog.as_deref(),
+                        working_dir,
+                        evaluate,
+                    )
+                });
+
+            let mode = if result.decision != Decision::Allow {
+                resolve_mode_for_cli(config, command, &result)
+            } else {
+                None
+            };
+            let now = match mode {
+                Some(DecisionMode::Deny | DecisionMode::Ask) => Outcome::Deny,
+                Some(DecisionMode::Warn) => Outcome::Warn,
+                Some(DecisionMode::Log) | None => Outcome::Allow,
+            };
+            let now_rule_id = mode.and(result.pattern_info.as_ref()).and_then(|info| {
+                Some(format!(
+                    "{}:{}",
+                    info.pack_id.as_deref()?,
+                    info.pattern_name.as_deref()?
+                ))
+            });
+
+            ReplayedEntry {
+                id,
+                then_rule_id: entry.get_rule_id(),
+                changed: entry.outcome != now,
+                command: entry.command,
+                then: entry.outcome,
+                now,
+                now_rule_id,
+            }
+        })
+        .collect()
+}
+
+/// Handle the `dcg replay` command.
+fn handle_replay_command(
+    config: &Config,
+    cmd: &ReplayCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let db_path = config.history.expanded_database_path();
+    let db = match HistoryDb::open(db_path) {
+        Ok(db) => db,
+        Err(err) => {
+            println!("Error opening history database: {err}");
+            return Ok(());
+        }
+    };
+
+    let entries = if let Some(id) = cmd.id {
+        let entry = db
+            .get_command(id)?
+            .ok_or_else(|| format!("No history entry with id {id}"))?;
+        vec![(id, entry)]
+    } else {
+        let outcome_filter = cmd
+            .outcome
+            .as_deref()
+            .map(|o| Outcome::parse(o).ok_or_else(|| format!("Invalid outcome: {o}")))
+            .transpose()?;
+        db.query_commands_with_ids(&ExportOptions {
+            outcome_filter,
+            since: None,
+            until: None,
+            limit: cmd.limit,
+            min_duration_us: None,
+        })?
+    };
+
+    let replayed = replay_entries(config, entries);
+    let changed = replayed.iter().filter(|r| r.changed).count();
+
+    if cmd.json {
+        let output = serde_json::json!({
+            "replayed": replayed.len(),
+            "changed": changed,
+            "entries": replayed,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    for r in &replayed {
+        let transition = format!("{} -> {}", r.then.as_str(), r.now.as_str());
+        let status = if r.changed {
+            format!("{transition:<16}").yellow().to_string()
+        } else {
+            format!("{transition:<16}").bright_black().to_string()
+        };
+        let rule = r
+            .now_rule_id
+            .as_deref()
+            .map(|rule| format!(" ({})", rule.cyan()))
+            .unwrap_or_default();
+        println!("#{:<6} {status} {}{rule}", r.id, r.command);
+    }
+    println!();
+    println!("Replayed {} entries: {changed} changed", replayed.len());
+
+    Ok(())
+}
+
 /// Handle the `dcg history` command.
 fn handle_history_command(
     config: &Config,
@@ -6740,6 +8613,44 @@ fn handle_history_command(
     Ok(())
 }
 
+/// List history entries whose evaluation took at least `min_duration_us`, slowest first.
+fn history_slowest(
+    config: &Config,
+    min_duration_us: u64,
+    limit: usize,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db = HistoryDb::open(config.history.expanded_database_path())?;
+    let entries = db.query_commands_for_export(&ExportOptions {
+        min_duration_us: Some(min_duration_us),
+        limit: Some(limit),
+        ..Default::default()
+    })?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+    if entries.is_empty() {
+        println!("No evaluations took {min_duration_us}µs or longer.");
+        return Ok(());
+    }
+    println!(
+        "{:>10}  {:<7}  {:<20}  Command",
+        "Duration", "Outcome", "Timestamp"
+    );
+    for entry in &entries {
+        println!(
+            "{:>8}µs  {:<7}  {:<20}  {}",
+            entry.eval_duration_us,
+            entry.outcome.as_str(),
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.command
+        );
+    }
+    Ok(())
+}
+
 fn history_stats(
     db: &HistoryDb,
     days: u64,
@@ -6836,6 +8747,7 @@ fn history_export(
         since: since_dt,
         until: until_dt,
         limit,
+        min_duration_us: None,
     };
 
     // Create output writer
@@ -6874,6 +8786,7 @@ fn export_to_writer<W: std::io::Write>(
         ExportFormat::Json => db.export_json(writer, options)?,
         ExportFormat::Jsonl => db.export_jsonl(writer, options)?,
         ExportFormat::Csv => db.export_csv(writer, options)?,
+        ExportFormat::Tsv => db.export_tsv(writer, options)?,
     };
     Ok(count)
 }
@@ -7565,17 +9478,10 @@ fn doctor_pretty(fix: bool) {
             if config_diag.has_errors() || config_diag.has_warnings() {
                 println!("{}", "WARNING".yellow());
                 println!("  Config: {}", path.display());
-                if !config_diag.unknown_packs.is_empty() {
-                    println!("  Unknown pack IDs: {:?}", config_diag.unknown_packs);
-                    println!("  → Run 'dcg packs list' to see available packs");
-                }
-                if !config_diag.invalid_override_patterns.is_empty() {
-                    println!("  Invalid override patterns:");
-                    for (pattern, error) in &config_diag.invalid_override_patterns {
-                        println!("    - \"{pattern}\": {error}");
-                    }
-                    println!("  → Fix the regex patterns in [overrides] section");
+                for d in &config_diag.diagnostics {
+                    println!("  {}: {}", d.field, d.message);
                 }
+                println!("  → Run 'dcg config --check' for details");
             } else {
                 println!("{} ({})", "OK".green(), path.display());
             }
@@ -7985,16 +9891,11 @@ fn collect_doctor_report(fix: bool) -> DoctorReport {
         }
         Some(path) => {
             if config_diag.has_errors() || config_diag.has_warnings() {
-                let mut details = Vec::new();
-                if !config_diag.unknown_packs.is_empty() {
-                    details.push(format!("Unknown pack IDs: {:?}", config_diag.unknown_packs));
-                }
-                if !config_diag.invalid_override_patterns.is_empty() {
-                    details.push(format!(
-                        "Invalid override patterns: {}",
-                        config_diag.invalid_override_patterns.len()
-                    ));
-                }
+                let details: Vec<String> = config_diag
+                    .diagnostics
+                    .iter()
+                    .map(|d| format!("{}: {}", d.field, d.message))
+                    .collect();
                 (
                     DoctorCheckStatus::Warning,
                     format!(
@@ -8872,20 +10773,24 @@ struct ConfigDiagnostics {
     config_path: Option<std::path::PathBuf>,
     /// TOML parse error (if any)
     parse_error: Option<String>,
-    /// Unknown pack IDs in enabled list
-    unknown_packs: Vec<String>,
-    /// Override patterns that failed to compile
-    invalid_override_patterns: Vec<(String, String)>, // (pattern, error)
+    /// Semantic problems from `Config::validate`
+    diagnostics: Vec<ConfigDiagnostic>,
 }
 
 #[allow(dead_code)]
 impl ConfigDiagnostics {
     fn has_errors(&self) -> bool {
-        self.parse_error.is_some() || !self.unknown_packs.is_empty()
+        self.parse_error.is_some()
+            || self
+                .diagnostics
+                .iter()
+                .any(|d| d.severity == ConfigDiagnosticSeverity::Error)
     }
 
     fn has_warnings(&self) -> bool {
-        !self.invalid_override_patterns.is_empty()
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == ConfigDiagnosticSeverity::Warning)
     }
 }
 
@@ -8948,61 +10853,10 @@ fn validate_config_diagnostics() -> ConfigDiagnostics {
         }
     };
 
-    // Validate pack IDs
-    for pack_id in &config.packs.enabled {
-        if !is_valid_pack_id(pack_id) {
-            diag.unknown_packs.push(pack_id.clone());
-        }
-    }
-    for pack_id in &config.packs.disabled {
-        if !is_valid_pack_id(pack_id) {
-            diag.unknown_packs.push(pack_id.clone());
-        }
-    }
-
-    // Validate override patterns
-    let compiled = config.overrides.compile();
-    for ip in &compiled.invalid_patterns {
-        diag.invalid_override_patterns
-            .push((ip.pattern.clone(), ip.error.clone()));
-    }
-
+    diag.diagnostics = config.validate();
     diag
 }
 
-/// Check if a pack ID is valid (exists in registry or is a known category).
-#[allow(dead_code)]
-fn is_valid_pack_id(id: &str) -> bool {
-    // Direct pack lookup
-    if REGISTRY.get(id).is_some() {
-        return true;
-    }
-
-    // Check if it's a category prefix (e.g., "containers" enables all containers.*)
-    let known_categories = [
-        "core",
-        "containers",
-        "kubernetes",
-        "database",
-        "cloud",
-        "infrastructure",
-        "system",
-        "strict_git",
-        "package_managers",
-    ];
-
-    if known_categories.contains(&id) {
-        return true;
-    }
-
-    // At this point:
-    // - id is NOT in REGISTRY (checked above)
-    // - id is NOT a bare category name (checked above)
-    // Therefore, if id contains a dot (e.g., "containers.fake"), it's invalid
-    // because we only accept full pack IDs that exist in REGISTRY.
-    false
-}
-
 /// Run a quick smoke test to verify the evaluator works.
 ///
 /// Tests both an allow case and a deny case to ensure basic functionality.
@@ -9090,6 +10944,7 @@ fn diagnose_allowlists() -> AllowlistDiagnostics {
                     continue;
                 }
             }
+            AllowlistLayer::Directory => loaded.path.clone(),
             AllowlistLayer::User => config_dir().join("allowlist.toml"),
             AllowlistLayer::System => continue,
         };
@@ -9183,6 +11038,11 @@ fn find_repo_root_from_cwd() -> Option<std::path::PathBuf> {
 /// Get the path to the allowlist file for a given layer.
 fn allowlist_path_for_layer(layer: AllowlistLayer) -> std::path::PathBuf {
     match layer {
+        AllowlistLayer::Directory => {
+            let cwd = std::env::current_dir().unwrap_or_default();
+            crate::allowlist::find_directory_allowlist(&cwd)
+                .unwrap_or_else(|| cwd.join(".dcg").join("allowlist.toml"))
+        }
         AllowlistLayer::Project => {
             let repo_root = find_repo_root_from_cwd()
                 .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
@@ -9194,7 +11054,10 @@ fn allowlist_path_for_layer(layer: AllowlistLayer) -> std::path::PathBuf {
 }
 
 /// Handle allowlist subcommand dispatch.
-fn handle_allowlist_command(action: AllowlistAction) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_allowlist_command(
+    config: &Config,
+    action: AllowlistAction,
+) -> Result<(), Box<dyn std::error::Error>> {
     match action {
         AllowlistAction::Add {
             rule_id,
@@ -9239,6 +11102,9 @@ fn handle_allowlist_command(action: AllowlistAction) -> Result<(), Box<dyn std::
         } => {
             allowlist_validate(project, user, strict)?;
         }
+        AllowlistAction::Explain { command, format } => {
+            allowlist_explain(config, &command, format)?;
+        }
     }
     Ok(())
 }
@@ -9275,6 +11141,7 @@ fn handle_allow_once_command(
     }
 
     let selected = select_pending_entry(&matches, cmd)?;
+    check_confirmation_token(config, pending_store.path(), selected, cmd.token.as_deref())?;
 
     let is_config_block = selected.source.as_deref() == Some("ConfigOverride");
     if is_config_block && !cmd.force {
@@ -9745,6 +11612,32 @@ fn resolve_allow_once_revoke_target(
     }
 }
 
+/// Enforce `[exceptions] require_hmac`: the short code alone is not enough,
+/// the caller must also present the token printed at block time.
+fn check_confirmation_token(
+    config: &Config,
+    pending_path: &std::path::Path,
+    record: &PendingExceptionRecord,
+    token: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !config.exceptions.require_hmac {
+        return Ok(());
+    }
+    let Some(token) = token else {
+        return Err(
+            "This installation requires a confirmation token. Re-run with --token <TOKEN> \
+             as printed with the denial."
+                .into(),
+        );
+    };
+    let secret = confirmation_secret(pending_path)
+        .map_err(|e| format!("Failed to load confirmation secret: {e}"))?;
+    if !verify_confirmation_token(&record.full_hash, &secret, token) {
+        return Err("Invalid confirmation token for this code.".into());
+    }
+    Ok(())
+}
+
 fn select_pending_entry<'a>(
     matches: &'a [PendingExceptionRecord],
     cmd: &AllowOnceCommand,
@@ -9904,7 +11797,11 @@ fn allowlist_list(
     } else if user_only {
         vec![AllowlistLayer::User]
     } else {
-        vec![AllowlistLayer::Project, AllowlistLayer::User]
+        vec![
+            AllowlistLayer::Directory,
+            AllowlistLayer::Project,
+            AllowlistLayer::User,
+        ]
     };
 
     let mut all_entries: Vec<(AllowlistLayer, std::path::PathBuf, AllowEntry)> = Vec::new();
@@ -10057,6 +11954,121 @@ fn allowlist_remove(
     Ok(())
 }
 
+/// Explain which allowlist layer (if any) would allow a command.
+///
+/// Evaluates the command without allowlists to find the blocking rule, then
+/// reports every layer in precedence order (directory > project > user > system).
+fn allowlist_explain(
+    config: &Config,
+    command: &str,
+    format: AllowlistOutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    let enabled_packs = config.enabled_pack_ids();
+    let enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+    let compiled_overrides = config.overrides.compile();
+    let result = crate::evaluator::evaluate_command(
+        command,
+        config,
+        &enabled_keywords,
+        &compiled_overrides,
+        &crate::allowlist::LayeredAllowlist::default(),
+    );
+
+    let rule = result
+        .pattern_info
+        .as_ref()
+        .filter(|_| result.decision != Decision::Allow)
+        .and_then(|info| Some((info.pack_id.as_deref()?, info.pattern_name.as_deref()?)));
+
+    let allowlists = load_default_allowlists();
+    // Hook mode matches allowlists without a project path; mirror that here.
+    let layer_matches = rule.map_or_else(Vec::new, |(pack_id, pattern_name)| {
+        allowlists.explain_rule_at_path(pack_id, pattern_name, None)
+    });
+    let winner = layer_matches.iter().find(|m| m.entry.is_some());
+
+    match format {
+        AllowlistOutputFormat::Pretty => {
+            println!("Command: {command}");
+            let Some((pack_id, pattern_name)) = rule else {
+                if result.is_denied() {
+                    println!(
+                        "{}",
+                        "Blocked by a config override; allowlists cannot allow it.".yellow()
+                    );
+                } else {
+                    println!(
+                        "{}",
+                        "Not blocked by any rule; no allowlist needed.".green()
+                    );
+                }
+                return Ok(());
+            };
+            println!("Blocked by: {}", format!("{pack_id}:{pattern_name}").cyan());
+            println!();
+            println!("{}", "Layers (highest precedence first):".bold());
+            if layer_matches.is_empty() {
+                println!("  (no allowlist files configured)");
+            }
+            for m in &layer_matches {
+                let status = match m.entry {
+                    Some(entry) if winner.is_some_and(|w| w.layer == m.layer) => {
+                        format!("{} {}", "allows:".green(), entry.reason)
+                    }
+                    Some(entry) => format!("{} {}", "shadowed:".yellow(), entry.reason),
+                    None => "no matching entry".bright_black().to_string(),
+                };
+                println!(
+                    "  {:<10} {} - {}",
+                    m.layer.label(),
+                    m.path.display(),
+                    status
+                );
+            }
+            println!();
+            match winner {
+                Some(m) => println!(
+                    "Result: {} by {} allowlist",
+                    "ALLOWED".green().bold(),
+                    m.layer.label()
+                ),
+                None => println!("Result: {}", "BLOCKED".red().bold()),
+            }
+        }
+        AllowlistOutputFormat::Json => {
+            let layers: Vec<serde_json::Value> = layer_matches
+                .iter()
+                .map(|m| {
+                    serde_json::json!({
+                        "layer": m.layer.label(),
+                        "path": m.path.display().to_string(),
+                        "matched": m.entry.is_some(),
+                        "selector": m.entry.map(|e| match &e.selector {
+                            AllowSelector::Rule(rule_id) => rule_id.to_string(),
+                            AllowSelector::ExactCommand(cmd) => cmd.clone(),
+                            AllowSelector::CommandPrefix(prefix) => prefix.clone(),
+                            AllowSelector::RegexPattern(re) => re.clone(),
+                        }),
+                        "reason": m.entry.map(|e| e.reason.as_str()),
+                    })
+                })
+                .collect();
+            let output = serde_json::json!({
+                "command": command,
+                "blocked": result.is_denied(),
+                "rule_id": rule.map(|(pack_id, pattern_name)| format!("{pack_id}:{pattern_name}")),
+                "allowed_by": winner.map(|m| m.layer.label()),
+                "layers": layers,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate allowlist entries.
 fn allowlist_validate(
     project_only: bool,
@@ -11422,6 +13434,167 @@ mod tests {
         assert!(matches!(cli.command, Some(Command::ListPacks { .. })));
     }
 
+    #[test]
+    fn test_cli_parse_packs_rules() {
+        let cli = Cli::parse_from(["dcg", "packs", "--rules", "--format", "json"]);
+        if let Some(Command::ListPacks { rules, format, .. }) = cli.command {
+            assert!(rules);
+            assert_eq!(format, PacksFormat::Json);
+        } else {
+            unreachable!("Expected ListPacks command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_hook_input_source() {
+        let cli = Cli::try_parse_from(["dcg", "--input-path", "/tmp/dcg.fifo"]).expect("parse");
+        assert_eq!(
+            cli.input_path.as_deref(),
+            Some(std::path::Path::new("/tmp/dcg.fifo"))
+        );
+        assert!(cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["dcg", "--input-fd", "3"]).expect("parse");
+        assert_eq!(cli.input_fd, Some(3));
+
+        assert!(Cli::try_parse_from(["dcg", "--input-fd", "3", "--input-path", "/tmp/x"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_hook_timeout() {
+        let cli = Cli::try_parse_from(["dcg", "--timeout", "250"]).expect("parse");
+        assert_eq!(cli.timeout, Some(250));
+        assert!(cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["dcg"]).expect("parse");
+        assert_eq!(cli.timeout, None);
+    }
+
+    #[test]
+    fn test_cli_parse_selftest_regex() {
+        let cli = Cli::parse_from(["dcg", "selftest", "--regex", "--budget-ms", "20"]);
+        if let Some(Command::SelfTest {
+            regex,
+            budget_ms,
+            format,
+        }) = cli.command
+        {
+            assert!(regex);
+            assert_eq!(budget_ms, 20);
+            assert_eq!(format, PacksFormat::Pretty);
+        } else {
+            unreachable!("Expected SelfTest command");
+        }
+
+        // A suite must be selected.
+        assert!(Cli::try_parse_from(["dcg", "selftest"]).is_err());
+    }
+
+    #[test]
+    fn test_collect_rules_includes_core_git_reset_hard() {
+        let config = Config::default();
+        let rules = collect_rules(&config, None);
+        let rule = rules
+            .iter()
+            .find(|r| r.rule_id == "core.git:reset-hard")
+            .expect("core.git:reset-hard should be listed");
+        assert_eq!(rule.pack_id, "core.git");
+        assert_eq!(rule.severity, PackSeverity::Critical.label());
+        assert!(rule.enabled);
+        assert!(
+            rules.iter().any(|r| !r.enabled),
+            "disabled packs listed too"
+        );
+        assert!(
+            collect_rules(&config, Some(true)).iter().all(|r| r.enabled),
+            "--enabled filters to enabled packs"
+        );
+        assert!(
+            collect_rules(&config, Some(false))
+                .iter()
+                .all(|r| !r.enabled),
+            "--disabled filters to disabled packs"
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_packs_enabled_disabled() {
+        let cli = Cli::parse_from(["dcg", "packs", "--disabled", "--format", "json"]);
+        if let Some(Command::ListPacks {
+            enabled,
+            disabled,
+            format,
+            ..
+        }) = cli.command
+        {
+            assert!(!enabled);
+            assert!(disabled);
+            assert_eq!(format, PacksFormat::Json);
+        } else {
+            unreachable!("Expected ListPacks command");
+        }
+
+        assert!(Cli::try_parse_from(["dcg", "packs", "--enabled", "--disabled"]).is_err());
+    }
+
+    #[test]
+    fn test_collect_packs_reports_builtin_metadata() {
+        let config = Config::default();
+        let packs = collect_packs(&config, None);
+        let git = packs
+            .iter()
+            .find(|p| p.id == "core.git")
+            .expect("core.git should be listed");
+        assert!(git.enabled);
+        assert!(git.loaded);
+        assert_eq!(git.source, PackOrigin::Builtin);
+        assert!(git.keywords.iter().any(|kw| kw == "git"));
+        assert_eq!(
+            git.pattern_count,
+            git.safe_pattern_count + git.destructive_pattern_count
+        );
+        assert!(
+            packs.iter().any(|p| !p.enabled),
+            "disabled packs listed too"
+        );
+    }
+
+    #[test]
+    fn test_collect_packs_shows_failed_custom_pack_as_enabled_not_loaded() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pack_path = dir.path().join("broken.yaml");
+        std::fs::write(
+            &pack_path,
+            r#"id: acme.broken
+name: Broken
+version: 1.0.0
+destructive_patterns:
+  - name: bad-regex
+    pattern: "rm ("
+    reason: unbalanced group
+"#,
+        )
+        .expect("write pack");
+
+        let mut config = Config::default();
+        config.packs.custom_paths = vec![pack_path.to_string_lossy().into_owned()];
+        let packs = collect_packs(&config, None);
+        let broken = packs
+            .iter()
+            .find(|p| p.id == "acme.broken")
+            .expect("failed custom pack should be listed");
+        assert!(broken.enabled);
+        assert!(!broken.loaded);
+        assert_eq!(broken.source, PackOrigin::Custom);
+        assert!(broken.error.as_deref().is_some_and(|e| !e.is_empty()));
+
+        let json = serde_json::to_value(broken).expect("serialize");
+        assert_eq!(json["enabled"], true);
+        assert_eq!(json["loaded"], false);
+        assert_eq!(json["source"], "custom");
+        assert!(json["error"].is_string());
+    }
+
     #[test]
     fn test_cli_parse_packs_verbose() {
         // Tests that `--verbose` with packs command uses the global verbose flag
@@ -11443,6 +13616,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pack_regex_test_reports_match_and_compile_error() {
+        let report = regex_test_report(r"git\s+reset\s+--(hard|merge)", "git reset --hard HEAD")
+            .expect("pattern compiles");
+        assert!(report.matched);
+        assert_eq!(report.engine, "linear");
+        assert_eq!(report.matched_text.as_deref(), Some("git reset --hard"));
+        assert_eq!(report.groups, vec![Some("hard".to_string())]);
+
+        let report = regex_test_report(r"push(?=.*--force)", "git push origin main")
+            .expect("lookahead compiles");
+        assert_eq!(report.engine, "backtracking");
+        assert!(!report.matched);
+
+        let err = regex_test_report(r"rm\s+(-rf", "rm -rf /").expect_err("unbalanced group");
+        assert!(err.contains("compile error"), "{err}");
+    }
+
     #[test]
     fn test_cli_parse_test() {
         let cli = Cli::parse_from(["dcg", "test", "git reset --hard"]);
@@ -11453,10 +13644,65 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_cli_parse_init() {
-        let cli = Cli::parse_from(["dcg", "init"]);
-        assert!(matches!(cli.command, Some(Command::Init { .. })));
+    #[test]
+    fn test_cli_parse_init() {
+        let cli = Cli::parse_from(["dcg", "init"]);
+        assert!(matches!(cli.command, Some(Command::Init { .. })));
+
+        let cli = Cli::parse_from(["dcg", "init", "--write-claude-settings"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Init {
+                write_claude_settings: true,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn export_packs_writes_loadable_yaml() {
+        use crate::packs::external::{pack_fingerprint, parse_pack_file_checked};
+        use tempfile::TempDir;
+        let temp = TempDir::new().unwrap();
+
+        export_packs(temp.path(), &["core.git".to_string()], false, true).unwrap();
+        let path = temp.path().join("core.git.yaml");
+        let exported = parse_pack_file_checked(&path).expect("export should load");
+        assert_eq!(exported.id, "custom.core_git");
+        assert_eq!(
+            pack_fingerprint(&exported.into_pack()),
+            pack_fingerprint(REGISTRY.get("core.git").unwrap())
+        );
+
+        std::fs::write(&path, "# mine\n").unwrap();
+        export_packs(temp.path(), &["core.git".to_string()], false, true).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# mine\n");
+
+        assert!(export_packs(temp.path(), &["no.such".to_string()], false, true).is_err());
+    }
+
+    #[test]
+    fn init_scaffolds_loadable_config_without_clobbering() {
+        use tempfile::TempDir;
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("dcg").join("config.toml");
+
+        assert!(scaffold_config(&path, false).unwrap());
+        assert!(temp.path().join("dcg").join("packs").is_dir());
+        let config = Config::load_from_file(&path).expect("scaffolded config should load");
+        assert!(
+            config
+                .packs
+                .enabled
+                .contains(&"containers.docker".to_string())
+        );
+
+        std::fs::write(&path, "# mine\n").unwrap();
+        assert!(!scaffold_config(&path, false).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# mine\n");
+
+        assert!(scaffold_config(&path, true).unwrap());
+        assert!(Config::load_from_file(&path).is_some());
     }
 
     #[test]
@@ -11658,6 +13904,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_allowlist_explain() {
+        let cli = Cli::parse_from(["dcg", "allowlist", "explain", "git reset --hard"]);
+        if let Some(Command::Allowlist {
+            action: AllowlistAction::Explain { command, format },
+        }) = cli.command
+        {
+            assert_eq!(command, "git reset --hard");
+            assert_eq!(format, AllowlistOutputFormat::Pretty);
+        } else {
+            unreachable!("Expected Allowlist Explain command");
+        }
+    }
+
     #[test]
     fn test_cli_parse_allowlist_add_command() {
         let cli = Cli::parse_from([
@@ -12143,6 +14403,98 @@ mod tests {
         assert!(entry.sample_commands.contains("npm run build"));
     }
 
+    #[test]
+    fn replay_reports_deny_that_now_allows_after_pack_disabled() {
+        use crate::history::HistoryDb;
+
+        let command = "aws sqs purge-queue --queue-url https://sqs.us-east-1.amazonaws.com/1/q";
+        let mut then_config = Config::default();
+        then_config
+            .packs
+            .enabled
+            .push("messaging.sqs_sns".to_string());
+
+        // Log the deny produced under the old config.
+        let db = HistoryDb::open_in_memory().unwrap();
+        let logged = replay_entries(
+            &then_config,
+            vec![(
+                0,
+                CommandEntry {
+                    command: command.to_string(),
+                    outcome: Outcome::Deny,
+                    ..Default::default()
+                },
+            )],
+        );
+        assert_eq!(logged[0].now, Outcome::Deny);
+        let id = db
+            .log_command(&CommandEntry {
+                command: command.to_string(),
+                outcome: logged[0].now,
+                pack_id: Some("messaging.sqs_sns".to_string()),
+                pattern_name: Some("aws-sqs-purge-queue".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        // Same config: decision unchanged.
+        let entry = db.get_command(id).unwrap().unwrap();
+        let unchanged = replay_entries(&then_config, vec![(id, entry.clone())]);
+        assert!(!unchanged[0].changed);
+        assert_eq!(
+            unchanged[0].now_rule_id.as_deref(),
+            Some("messaging.sqs_sns:aws-sqs-purge-queue")
+        );
+
+        // Pack disabled: the deny now flips to allow.
+        let flipped = replay_entries(&Config::default(), vec![(id, entry)]);
+        assert_eq!(flipped[0].id, id);
+        assert_eq!(flipped[0].then, Outcome::Deny);
+        assert_eq!(flipped[0].now, Outcome::Allow);
+        assert!(flipped[0].changed);
+        assert!(flipped[0].now_rule_id.is_none());
+    }
+
+    #[test]
+    fn replay_runs_hook_post_processing_in_recorded_working_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("Makefile"), "clean:\n\trm -rf /\n").unwrap();
+        let mut config = Config::default();
+        config.evaluation.resolve_script_targets = true;
+
+        let entry = CommandEntry {
+            command: "make clean".to_string(),
+            working_dir: temp.path().to_string_lossy().into_owned(),
+            outcome: Outcome::Allow,
+            ..Default::default()
+        };
+        let replayed = replay_entries(&config, vec![(1, entry)]);
+        assert_eq!(replayed[0].now, Outcome::Deny);
+        assert!(replayed[0].changed);
+    }
+
+    #[test]
+    fn test_cli_parse_replay() {
+        let cli = Cli::try_parse_from(["dcg", "replay", "42"]).expect("parse");
+        let Some(Command::Replay(cmd)) = cli.command else {
+            panic!("expected replay command");
+        };
+        assert_eq!(cmd.id, Some(42));
+        assert!(!cmd.all);
+
+        let cli =
+            Cli::try_parse_from(["dcg", "replay", "--all", "--outcome", "deny"]).expect("parse");
+        let Some(Command::Replay(cmd)) = cli.command else {
+            panic!("expected replay command");
+        };
+        assert!(cmd.all);
+        assert_eq!(cmd.outcome.as_deref(), Some("deny"));
+
+        assert!(Cli::try_parse_from(["dcg", "replay"]).is_err());
+        assert!(Cli::try_parse_from(["dcg", "replay", "1", "--all"]).is_err());
+    }
+
     #[test]
     fn suggestion_audit_entry_can_be_stored_and_retrieved() {
         use crate::history::{HistoryDb, SuggestionAction, SuggestionAuditEntry};
@@ -12250,6 +14602,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_scan_severity_at_least() {
+        let cli = Cli::try_parse_from(["dcg", "scan", "--staged", "--severity-at-least", "high"])
+            .expect("parse");
+        if let Some(Command::Scan(scan)) = cli.command {
+            assert_eq!(
+                scan.severity_at_least,
+                Some(crate::scan::ScanSeverityFilter::High)
+            );
+        } else {
+            unreachable!("Expected Scan command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_scan_group_by() {
+        let cli = Cli::try_parse_from(["dcg", "scan", "--staged", "--group-by", "severity"])
+            .expect("parse");
+        if let Some(Command::Scan(scan)) = cli.command {
+            assert_eq!(scan.group_by, Some(crate::scan::ScanGroupBy::Severity));
+        } else {
+            unreachable!("Expected Scan command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_scan_count_only() {
+        let cli = Cli::try_parse_from(["dcg", "scan", "--staged", "--count-only"]).expect("parse");
+        if let Some(Command::Scan(scan)) = cli.command {
+            assert!(scan.count_only);
+        } else {
+            unreachable!("Expected Scan command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_scan_dedupe() {
+        let cli = Cli::try_parse_from(["dcg", "scan", "--staged", "--dedupe"]).expect("parse");
+        if let Some(Command::Scan(scan)) = cli.command {
+            assert!(scan.dedupe);
+        } else {
+            unreachable!("Expected Scan command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_scan_baseline() {
+        let cli = Cli::try_parse_from(["dcg", "scan", "--staged", "--baseline", "baseline.json"])
+            .expect("parse");
+        if let Some(Command::Scan(scan)) = cli.command {
+            assert_eq!(
+                scan.baseline.as_deref(),
+                Some(std::path::Path::new("baseline.json"))
+            );
+            assert!(scan.write_baseline.is_none());
+        } else {
+            unreachable!("Expected Scan command");
+        }
+
+        let result = Cli::try_parse_from([
+            "dcg",
+            "scan",
+            "--staged",
+            "--baseline",
+            "a.json",
+            "--write-baseline",
+            "b.json",
+        ]);
+        assert!(result.is_err(), "--baseline and --write-baseline conflict");
+    }
+
     #[test]
     fn test_cli_parse_scan_max_file_size() {
         let cli = Cli::try_parse_from(["dcg", "scan", "--staged", "--max-file-size", "2048"])
@@ -12510,14 +14933,29 @@ exclude = ["target/**"]
         assert_eq!(after, existing, "should not modify unknown hook");
     }
 
+    #[test]
+    fn test_cli_parse_history_min_duration() {
+        let cli = Cli::parse_from(["dcg", "history", "--min-duration", "1000", "--limit", "5"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::History {
+                action: None,
+                min_duration: Some(1000),
+                limit: 5,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["dcg", "history"]).is_err());
+    }
+
     #[test]
     fn test_cli_parse_history_stats() {
         let cli = Cli::try_parse_from([
             "dcg", "history", "stats", "--days", "7", "--json", "--trends",
         ])
         .expect("parse");
-        if let Some(Command::History { action }) = cli.command {
-            if let HistoryAction::Stats { days, trends, json } = action {
+        if let Some(Command::History { action, .. }) = cli.command {
+            if let Some(HistoryAction::Stats { days, trends, json }) = action {
                 assert_eq!(days, 7);
                 assert!(trends);
                 assert!(json);
@@ -12529,18 +14967,43 @@ exclude = ["target/**"]
         }
     }
 
+    #[test]
+    fn test_cli_parse_stats_since() {
+        let cli = Cli::try_parse_from(["dcg", "stats", "--since", "30d", "--format", "json"])
+            .expect("parse");
+        if let Some(Command::Stats(stats)) = cli.command {
+            assert_eq!(stats.since.as_deref(), Some("30d"));
+            assert_eq!(stats.format, StatsFormat::Json);
+        } else {
+            unreachable!("Expected Stats command");
+        }
+
+        assert!(Cli::try_parse_from(["dcg", "stats", "--since", "7d", "--rules"]).is_err());
+    }
+
     #[test]
     fn test_cli_parse_explain() {
         let cli = Cli::try_parse_from(["dcg", "explain", "git reset --hard"]).expect("parse");
         if let Some(Command::Explain {
             command,
+            batch,
             format,
             with_packs,
+            dump_trace,
+            exit_code,
+            redact,
+            no_redact,
+            diff_normalization,
         }) = cli.command
         {
-            assert_eq!(command, "git reset --hard");
+            assert_eq!(command.as_deref(), Some("git reset --hard"));
+            assert!(batch.is_none());
             assert_eq!(format, ExplainFormat::Pretty);
             assert!(with_packs.is_none());
+            assert!(dump_trace.is_none());
+            assert!(!exit_code);
+            assert!(!redact && !no_redact);
+            assert!(!diff_normalization);
         } else {
             unreachable!("Expected Explain command");
         }
@@ -12555,7 +15018,7 @@ exclude = ["target/**"]
             command, format, ..
         }) = cli.command
         {
-            assert_eq!(command, "docker system prune");
+            assert_eq!(command.as_deref(), Some("docker system prune"));
             assert_eq!(format, ExplainFormat::Json);
         } else {
             unreachable!("Expected Explain command");
@@ -12614,6 +15077,217 @@ exclude = ["target/**"]
         }
     }
 
+    #[test]
+    fn test_cli_parse_test_with_strict_quoting() {
+        let cli = Cli::try_parse_from(["dcg", "test", "--strict-quoting", "git reset --hard \""])
+            .expect("parse");
+        if let Some(Command::TestCommand {
+            command,
+            strict_quoting,
+            ..
+        }) = cli.command
+        {
+            assert_eq!(command, "git reset --hard \"");
+            assert!(strict_quoting);
+        } else {
+            unreachable!("Expected TestCommand");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_explain_with_dump_trace() {
+        let cli = Cli::try_parse_from([
+            "dcg",
+            "explain",
+            "--dump-trace",
+            "trace.json",
+            "git reset --hard",
+        ])
+        .expect("parse");
+        if let Some(Command::Explain {
+            command,
+            dump_trace,
+            ..
+        }) = cli.command
+        {
+            assert_eq!(command.as_deref(), Some("git reset --hard"));
+            assert_eq!(dump_trace, Some(std::path::PathBuf::from("trace.json")));
+        } else {
+            unreachable!("Expected Explain command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_explain_batch() {
+        let cli = Cli::try_parse_from(["dcg", "explain", "--batch", "cmds.txt"]).expect("parse");
+        if let Some(Command::Explain { command, batch, .. }) = cli.command {
+            assert!(command.is_none());
+            assert_eq!(batch, Some(std::path::PathBuf::from("cmds.txt")));
+        } else {
+            unreachable!("Expected Explain command");
+        }
+        assert!(Cli::try_parse_from(["dcg", "explain"]).is_err());
+        assert!(Cli::try_parse_from(["dcg", "explain", "--batch", "cmds.txt", "ls"]).is_err());
+    }
+
+    #[test]
+    fn test_explain_batch_summarizes_decisions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("cmds.txt");
+        std::fs::write(
+            &path,
+            "# mixed batch\ngit status\n\ngit reset --hard\nls -la\n",
+        )
+        .expect("write batch file");
+        let contents = std::fs::read_to_string(&path).expect("read batch file");
+
+        let (entries, summary) = explain_batch(&Config::default(), &contents, None);
+        assert_eq!(
+            summary,
+            ExplainBatchSummary {
+                total: 3,
+                allow: 2,
+                warn: 0,
+                ask: 0,
+                deny: 1,
+            }
+        );
+        let lines: Vec<_> = entries.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![2, 4, 5]);
+        assert_eq!(entries[1].1.decision, Decision::Deny);
+        assert_eq!(explain_batch_exit_code(&entries), EXIT_DENIED);
+    }
+
+    #[test]
+    fn test_explain_batch_warn_only_exits_with_warning() {
+        let mut config = Config::default();
+        config.packs.enabled.push("containers.docker".to_string());
+
+        let (entries, summary) = explain_batch(&config, "git status\ndocker image prune\n", None);
+        assert_eq!(
+            summary,
+            ExplainBatchSummary {
+                total: 2,
+                allow: 1,
+                warn: 1,
+                ask: 0,
+                deny: 0,
+            }
+        );
+        assert_eq!(explain_batch_exit_code(&entries), EXIT_WARNING);
+
+        let (entries, _) = explain_batch(&config, "git status\n", None);
+        assert_eq!(explain_batch_exit_code(&entries), EXIT_SUCCESS);
+        let (entries, _) = explain_batch(&config, "docker image prune\ngit reset --hard\n", None);
+        assert_eq!(explain_batch_exit_code(&entries), EXIT_DENIED);
+    }
+
+    #[test]
+    fn test_explain_redact_masks_bearer_token() {
+        let cmd = r#"curl -H "Authorization: Bearer s3cr3t-token" https://api.example.com"#;
+        let cli = Cli::try_parse_from(["dcg", "explain", "--redact", "--format", "json", cmd])
+            .expect("parse");
+        let Some(Command::Explain {
+            command,
+            redact,
+            no_redact,
+            ..
+        }) = cli.command
+        else {
+            unreachable!("Expected Explain command");
+        };
+        assert!(explain_should_redact(redact, no_redact));
+
+        let config = Config::default();
+        let (mut trace, _) = build_explain_trace(&config, &command.unwrap_or_default(), None);
+        trace.redact_commands(&config.logging.redaction);
+        let output = serde_json::to_string(&trace.to_json_output()).expect("json");
+        assert!(!output.contains("s3cr3t-token"), "token leaked: {output}");
+        assert!(output.contains("Bearer [REDACTED]"));
+
+        let cli =
+            Cli::try_parse_from(["dcg", "explain", "--redact", "--no-redact", cmd]).expect("parse");
+        let Some(Command::Explain {
+            redact, no_redact, ..
+        }) = cli.command
+        else {
+            unreachable!("Expected Explain command");
+        };
+        assert!(!explain_should_redact(redact, no_redact));
+    }
+
+    #[test]
+    fn test_explain_matches_hook_post_processing() {
+        let config = Config::default();
+        for cmd in [
+            "cd /etc && rm -rf *",
+            "chattr -i build/important && rm -rf build/important",
+            "kubectl delete namespace kube-system",
+            "apt-get remove libc6",
+            "git status",
+        ] {
+            let (trace, _) = build_explain_trace(&config, cmd, None);
+            let expected = crate::evaluator::evaluate_detailed(cmd, &config).result;
+            assert_eq!(trace.decision, expected.decision, "{cmd}");
+            assert_eq!(
+                trace.match_info.as_ref().and_then(|m| m.severity),
+                expected.pattern_info.as_ref().and_then(|p| p.severity),
+                "{cmd}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_explain_diff_normalization_marks_quoted_argument() {
+        let cmd = r#"git commit -m "fix rm -rf bug""#;
+        let cli =
+            Cli::try_parse_from(["dcg", "explain", "--diff-normalization", cmd]).expect("parse");
+        let Some(Command::Explain {
+            command,
+            diff_normalization,
+            ..
+        }) = cli.command
+        else {
+            unreachable!("Expected Explain command");
+        };
+        assert!(diff_normalization);
+        assert!(
+            Cli::try_parse_from(["dcg", "explain", "--batch", "x", "--diff-normalization"])
+                .is_err()
+        );
+
+        let (mut trace, _) =
+            build_explain_trace(&Config::default(), &command.unwrap_or_default(), None);
+        assert!(trace.format_normalization_diff(false).is_none());
+        trace.sanitized_command =
+            Some(crate::context::sanitize_for_pattern_matching(&trace.command).into_owned());
+
+        let diff = trace.format_normalization_diff(false).expect("diff");
+        assert!(diff.contains(&format!("Raw:        {cmd}")), "{diff}");
+        assert!(diff.contains("Matched on: git commit -m "), "{diff}");
+        assert!(
+            diff.contains(r#"bytes 14..30 masked: "fix rm -rf bug""#),
+            "{diff}"
+        );
+        assert!(
+            diff.contains("Masked:                   ^^^^^^^^^^^^^^^^\n"),
+            "{diff}"
+        );
+
+        trace.sanitized_command = Some("git status".to_string());
+        trace.command = "git status".to_string();
+        let diff = trace.format_normalization_diff(false).expect("diff");
+        assert!(diff.contains("Nothing masked"), "{diff}");
+
+        trace.sanitized_command = Some("git status --short".to_string());
+        let diff = trace.format_normalization_diff(false).expect("diff");
+        assert!(
+            diff.contains("Lengths differ (10 bytes raw, 18 bytes matched on)"),
+            "{diff}"
+        );
+        assert!(!diff.contains("Nothing masked"), "{diff}");
+    }
+
     // ========================================================================
     // Scan git integration tests
     // ========================================================================
@@ -12887,6 +15561,65 @@ exclude = ["target/**"]
         }
     }
 
+    // ========================================================================
+    // GitHub Actions workflow command output
+    // ========================================================================
+
+    #[test]
+    fn cli_parse_scan_format_github() {
+        let cli =
+            Cli::try_parse_from(["dcg", "scan", "--staged", "--format", "github"]).expect("parse");
+        if let Some(Command::Scan(scan)) = cli.command {
+            assert_eq!(scan.format, Some(crate::scan::ScanFormat::Github));
+        } else {
+            unreachable!("Expected Scan command");
+        }
+    }
+
+    #[test]
+    fn github_annotation_format_for_finding() {
+        let finding = crate::scan::ScanFinding {
+            file: "ci/deploy,prod.sh".to_string(),
+            line: 12,
+            col: Some(3),
+            extractor_id: "shell.script".to_string(),
+            extracted_command: "rm -rf /var/lib/app".to_string(),
+            decision: crate::scan::ScanDecision::Deny,
+            severity: crate::scan::ScanSeverity::Error,
+            rule_id: Some("core.filesystem:rm-rf-root-home".to_string()),
+            rule_severity: Some(crate::packs::Severity::Critical),
+            reason: Some("rm -rf on a system path (100% data loss)".to_string()),
+            suggestion: None,
+            metadata: None,
+            occurrences: Vec::new(),
+        };
+
+        assert_eq!(
+            format_github_annotation(&finding),
+            "::error file=ci/deploy%2Cprod.sh,line=12,col=3,\
+             title=dcg%3A core.filesystem%3Arm-rf-root-home::\
+             rm -rf on a system path (100%25 data loss)%0Arm -rf /var/lib/app"
+        );
+
+        let warning = crate::scan::ScanFinding {
+            severity: crate::scan::ScanSeverity::Warning,
+            col: None,
+            rule_id: None,
+            reason: None,
+            ..finding.clone()
+        };
+        assert!(
+            format_github_annotation(&warning)
+                .starts_with("::warning file=ci/deploy%2Cprod.sh,line=12,title=dcg::")
+        );
+
+        let info = crate::scan::ScanFinding {
+            severity: crate::scan::ScanSeverity::Info,
+            ..finding
+        };
+        assert!(format_github_annotation(&info).starts_with("::notice "));
+    }
+
     // ==========================================================================
     // Doctor diagnostics tests (git_safety_guard-1gt.7.1)
     // ==========================================================================
@@ -13001,8 +15734,7 @@ exclude = ["target/**"]
         let diag = ConfigDiagnostics {
             config_path: Some(std::path::PathBuf::from("/test/config.toml")),
             parse_error: Some("Invalid TOML".to_string()),
-            unknown_packs: vec![],
-            invalid_override_patterns: vec![],
+            diagnostics: vec![],
         };
         assert!(diag.has_errors());
         assert!(!diag.has_warnings());
@@ -13013,8 +15745,11 @@ exclude = ["target/**"]
         let diag = ConfigDiagnostics {
             config_path: Some(std::path::PathBuf::from("/test/config.toml")),
             parse_error: None,
-            unknown_packs: vec!["nonexistent.pack".to_string()],
-            invalid_override_patterns: vec![],
+            diagnostics: vec![ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                field: "packs.enabled".to_string(),
+                message: "unknown pack ID 'nonexistent.pack'".to_string(),
+            }],
         };
         assert!(diag.has_errors());
         assert!(!diag.has_warnings());
@@ -13025,45 +15760,16 @@ exclude = ["target/**"]
         let diag = ConfigDiagnostics {
             config_path: Some(std::path::PathBuf::from("/test/config.toml")),
             parse_error: None,
-            unknown_packs: vec![],
-            invalid_override_patterns: vec![("invalid(regex".to_string(), "error".to_string())],
+            diagnostics: vec![ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Warning,
+                field: "overrides.block".to_string(),
+                message: "invalid regex 'invalid(regex': error".to_string(),
+            }],
         };
         assert!(!diag.has_errors());
         assert!(diag.has_warnings());
     }
 
-    #[test]
-    fn is_valid_pack_id_accepts_core() {
-        assert!(is_valid_pack_id("core"));
-    }
-
-    #[test]
-    fn is_valid_pack_id_accepts_category_prefix() {
-        assert!(is_valid_pack_id("containers"));
-        assert!(is_valid_pack_id("kubernetes"));
-        assert!(is_valid_pack_id("database"));
-        assert!(is_valid_pack_id("cloud"));
-    }
-
-    #[test]
-    fn is_valid_pack_id_accepts_core_git() {
-        // core.git should be a valid pack in the registry
-        assert!(is_valid_pack_id("core.git"));
-    }
-
-    #[test]
-    fn is_valid_pack_id_rejects_unknown() {
-        assert!(!is_valid_pack_id("nonexistent"));
-        assert!(!is_valid_pack_id("fake.pack"));
-        assert!(!is_valid_pack_id(""));
-    }
-
-    #[test]
-    fn is_valid_pack_id_rejects_category_with_unknown_subpack() {
-        // containers is a valid category, but containers.fake is not a valid pack
-        assert!(!is_valid_pack_id("containers.fake"));
-    }
-
     #[test]
     fn diagnose_hook_wiring_from_json_valid_settings() {
         // Test the JSON parsing logic by calling the internal helpers
@@ -13206,6 +15912,7 @@ exclude = ["target/**"]
             force: false,
             pick: Some(2),
             hash: None,
+            token: None,
         };
         let records = [a.clone(), b.clone()];
         let selected = select_pending_entry(&records, &cmd_pick).unwrap();
@@ -13222,12 +15929,45 @@ exclude = ["target/**"]
             force: false,
             pick: None,
             hash: Some(b.full_hash.clone()),
+            token: None,
         };
         let records = [a, b.clone()];
         let selected = select_pending_entry(&records, &cmd_hash).unwrap();
         assert_eq!(selected.full_hash, b.full_hash);
     }
 
+    #[test]
+    fn allow_once_require_hmac_rejects_code_without_token() {
+        use crate::logging::RedactionConfig;
+        use crate::pending_exceptions::confirmation_token;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pending_path = dir.path().join("pending_exceptions.jsonl");
+        let record = PendingExceptionRecord::new(
+            Utc::now(),
+            "/repo",
+            "git reset --hard",
+            "blocked",
+            &RedactionConfig::default(),
+            false,
+            None,
+        );
+
+        let mut config = Config::default();
+        assert!(check_confirmation_token(&config, &pending_path, &record, None).is_ok());
+
+        config.exceptions.require_hmac = true;
+        assert!(check_confirmation_token(&config, &pending_path, &record, None).is_err());
+        assert!(
+            check_confirmation_token(&config, &pending_path, &record, Some(&record.short_code))
+                .is_err()
+        );
+
+        let secret = confirmation_secret(&pending_path).expect("secret");
+        let token = confirmation_token(&record.full_hash, &secret);
+        assert!(check_confirmation_token(&config, &pending_path, &record, Some(&token)).is_ok());
+    }
+
     #[test]
     fn allow_once_disambiguation_rejects_invalid_pick() {
         use crate::logging::{RedactionConfig, RedactionMode};
@@ -13265,6 +16005,7 @@ exclude = ["target/**"]
             force: false,
             pick: Some(3),
             hash: None,
+            token: None,
         };
 
         let records = [a, b];
@@ -9,13 +9,16 @@
 //! 5. Compiled defaults (lowest priority)
 
 use crate::interactive::{InteractiveConfig, VerificationMethod};
+use crate::packs::source::{DEFAULT_REMOTE_TTL, FilePackSource, HttpPackSource, PackSource};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+use std::time::Duration;
 
 /// Environment variable prefix for all config options.
 const ENV_PREFIX: &str = "DCG";
@@ -44,6 +47,9 @@ pub struct Config {
     /// General settings.
     pub general: GeneralConfig,
 
+    /// Named preset applied beneath every config file.
+    pub mode: ModeConfig,
+
     /// Output display settings.
     pub output: OutputConfig,
 
@@ -65,6 +71,27 @@ pub struct Config {
     /// Confidence scoring configuration for ambiguous matches.
     pub confidence: ConfidenceConfig,
 
+    /// Command evaluation behavior (e.g. strict quoting).
+    pub evaluation: EvaluationConfig,
+
+    /// Kubernetes production context/namespace escalation.
+    pub kubernetes: KubernetesConfig,
+
+    /// Essential OS packages whose removal is escalated.
+    pub system_packages: SystemPackagesConfig,
+
+    /// Protected file paths (in-place edit detection).
+    pub filesystem: FilesystemConfig,
+
+    /// Scratch directories where destructive matches are downgraded.
+    pub safe_dirs: SafeDirsConfig,
+
+    /// Localized denial messages.
+    pub i18n: I18nConfig,
+
+    /// Allow-once exception approval settings.
+    pub exceptions: ExceptionsConfig,
+
     /// Structured logging configuration.
     pub logging: crate::logging::LoggingConfig,
 
@@ -104,7 +131,10 @@ pub struct Config {
 
 #[derive(Debug, Clone, Default, Deserialize)]
 struct ConfigLayer {
+    /// Files merged before this one, resolved relative to this file.
+    include: Option<Vec<String>>,
     general: Option<GeneralConfigLayer>,
+    mode: Option<ModeConfig>,
     output: Option<OutputConfigLayer>,
     theme: Option<ThemeConfigLayer>,
     packs: Option<PacksConfig>,
@@ -112,6 +142,13 @@ struct ConfigLayer {
     overrides: Option<OverridesConfig>,
     heredoc: Option<HeredocConfig>,
     confidence: Option<ConfidenceConfigLayer>,
+    evaluation: Option<EvaluationConfigLayer>,
+    kubernetes: Option<KubernetesConfigLayer>,
+    system_packages: Option<SystemPackagesConfigLayer>,
+    filesystem: Option<FilesystemConfigLayer>,
+    safe_dirs: Option<SafeDirsConfigLayer>,
+    i18n: Option<I18nConfig>,
+    exceptions: Option<ExceptionsConfigLayer>,
     logging: Option<LoggingConfigLayer>,
     history: Option<HistoryConfigLayer>,
     interactive: Option<InteractiveConfigLayer>,
@@ -153,6 +190,10 @@ struct LoggingConfigLayer {
     format: Option<crate::logging::LogFormat>,
     redaction: Option<RedactionConfigLayer>,
     events: Option<LogEventFilterLayer>,
+    syslog: Option<bool>,
+    syslog_warn: Option<bool>,
+    syslog_facility: Option<crate::logging::SyslogFacility>,
+    syslog_severity: Option<crate::logging::SyslogSeverity>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -162,6 +203,7 @@ struct HistoryConfigLayer {
     retention_days: Option<u32>,
     max_size_mb: Option<u32>,
     database_path: Option<String>,
+    log_allows: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -197,6 +239,58 @@ struct ConfidenceConfigLayer {
     protect_critical: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+struct EvaluationConfigLayer {
+    strict_quoting: Option<bool>,
+    on_error: Option<OnErrorMode>,
+    shell: Option<ShellDialect>,
+    ask_on_privileged_unknown: Option<bool>,
+    privileged_allow: Option<Vec<String>>,
+    resolve_script_targets: Option<bool>,
+    total_timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct KubernetesConfigLayer {
+    prod_contexts: Option<Vec<String>>,
+    prod_namespaces: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SystemPackagesConfigLayer {
+    essential: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct FilesystemConfigLayer {
+    protected_paths: Option<Vec<String>>,
+    protected_write_paths: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SafeDirsConfigLayer {
+    paths: Option<Vec<String>>,
+    mode: Option<PolicyMode>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct ExceptionsConfigLayer {
+    require_hmac: Option<bool>,
+}
+
+impl ConfigLayer {
+    /// Drop settings a project or `DCG_CONFIG` file may only tighten.
+    ///
+    /// `require_hmac` protects allow-once approvals from the agent itself, so
+    /// only user and system config can turn it off.
+    fn tighten_only(mut self) -> Self {
+        if let Some(exceptions) = self.exceptions.as_mut() {
+            exceptions.require_hmac = exceptions.require_hmac.filter(|require| *require);
+        }
+        self
+    }
+}
+
 /// Git-awareness configuration layer for config file parsing.
 #[derive(Debug, Clone, Default, Deserialize)]
 struct GitAwarenessConfigLayer {
@@ -207,6 +301,7 @@ struct GitAwarenessConfigLayer {
     relaxed_strictness: Option<StrictnessLevel>,
     default_strictness: Option<StrictnessLevel>,
     warn_if_not_git: Option<bool>,
+    warn_on_pushed_rewrite: Option<bool>,
 }
 
 fn expand_tilde_path(value: &str) -> (PathBuf, bool) {
@@ -248,6 +343,59 @@ pub(crate) fn resolve_config_path_value(value: &str, cwd: Option<&Path>) -> Opti
     Some(path)
 }
 
+/// Read `path` and, depth-first, every file it includes, appending each parsed
+/// layer to `layers` in merge order (includes before the including file).
+///
+/// `stack` holds the files currently being expanded and is used to reject
+/// circular includes.
+fn load_include_chain(
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+    layers: &mut Vec<(PathBuf, ConfigLayer)>,
+) -> Result<(), String> {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if stack.contains(&path) {
+        let cycle: Vec<String> = stack
+            .iter()
+            .skip_while(|entry| **entry != path)
+            .chain(std::iter::once(&path))
+            .map(|entry| entry.display().to_string())
+            .collect();
+        return Err(format!("Circular config include: {}", cycle.join(" -> ")));
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read config file '{}': {e}", path.display()))?;
+    let mut layer: ConfigLayer = toml::from_str(&content)
+        .map_err(|e| format!("Failed to parse config file '{}': {e}", path.display()))?;
+
+    let base_dir = path.parent().map(Path::to_path_buf);
+    stack.push(path.clone());
+    for include in layer.include.take().unwrap_or_default() {
+        let Some(include_path) = resolve_config_path_value(&include, base_dir.as_deref()) else {
+            continue;
+        };
+        load_include_chain(&include_path, stack, layers)?;
+    }
+    stack.pop();
+
+    layers.push((path, layer));
+    Ok(())
+}
+
+/// Files that make up the config at `path`, in merge order: every file it
+/// includes (recursively), then `path` itself.
+///
+/// # Errors
+///
+/// Returns an error if any file in the chain cannot be read or parsed, or if
+/// the includes are circular.
+pub fn config_include_chain(path: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut layers = Vec::new();
+    load_include_chain(path, &mut Vec::new(), &mut layers)?;
+    Ok(layers.into_iter().map(|(path, _)| path).collect())
+}
+
 /// Find the git repo root by searching for a `.git` directory upwards from `start_dir`.
 ///
 /// This search is bounded by `max_hops` to avoid unbounded filesystem traversal in
@@ -480,6 +628,633 @@ impl Default for ConfidenceConfig {
     }
 }
 
+/// Command evaluation behavior configuration.
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [evaluation]
+/// strict_quoting = true
+/// on_error = "closed"
+/// shell = "powershell"
+/// ask_on_privileged_unknown = true
+/// privileged_allow = ["./deploy.sh"]
+/// resolve_script_targets = true
+/// total_timeout_ms = 5000
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvaluationConfig {
+    /// Deny commands whose quotes do not balance after normalization.
+    ///
+    /// A dangling quote makes it unclear where an argument ends, so the
+    /// command is denied with reason "ambiguous quoting". Heredoc bodies and
+    /// multi-line quoted strings are not flagged.
+    ///
+    /// Default: false
+    pub strict_quoting: bool,
+
+    /// What to do when dcg cannot fully analyze a command.
+    ///
+    /// Covers evaluation budget overruns, oversized commands, and embedded
+    /// code that could not be extracted or scanned in time.
+    ///
+    /// Default: open
+    pub on_error: OnErrorMode,
+
+    /// Shell dialect that incoming commands are written in.
+    ///
+    /// `powershell` expands PowerShell aliases and abbreviated parameters
+    /// before matching (`rm -r -fo` becomes `Remove-Item -Recurse -Force`)
+    /// and enables the `windows.powershell` pack. `fish` rewrites `; and` /
+    /// `; or` chains and bare `(...)` command substitution into their POSIX
+    /// forms so compound commands split into the right segments.
+    ///
+    /// Default: bash
+    pub shell: ShellDialect,
+
+    /// Ask before running an unrecognized program under `sudo`.
+    ///
+    /// `sudo ./random-script.sh` gets an Ask decision instead of being allowed
+    /// silently. Well-known system tools (`ls`, `systemctl`, `apt`, ...) and
+    /// anything listed in `privileged_allow` stay allowed.
+    ///
+    /// Default: false
+    pub ask_on_privileged_unknown: bool,
+
+    /// Extra programs that may run under `sudo` without asking.
+    ///
+    /// Entries match the program's basename (`deploy`) or the exact word as
+    /// typed (`./scripts/deploy.sh`).
+    pub privileged_allow: Vec<String>,
+
+    /// Evaluate the commands behind `make`, `npm run`, and `just` targets.
+    ///
+    /// The target is looked up in the `Makefile`, `package.json` `scripts`,
+    /// or `justfile` in the working directory, so `npm run nuke` is denied
+    /// when `nuke` is defined as `rm -rf /`. Off by default because it reads
+    /// project files on every matching invocation.
+    ///
+    /// Default: false
+    pub resolve_script_targets: bool,
+
+    /// Hard wall-clock limit for evaluating one command, in milliseconds.
+    ///
+    /// A safety net for pathological inputs: if evaluation has not finished
+    /// by then, the hook answers right away per `on_error` with reason
+    /// "evaluation timed out" instead of leaving the agent waiting.
+    ///
+    /// Default: 5000
+    pub total_timeout_ms: Option<u64>,
+}
+
+/// Default hard limit for a whole evaluation (used when not configured).
+pub const DEFAULT_TOTAL_TIMEOUT_MS: u64 = 5_000;
+
+impl EvaluationConfig {
+    /// Get the hard limit for a whole evaluation (with default fallback).
+    #[must_use]
+    pub fn total_timeout(&self) -> Duration {
+        Duration::from_millis(self.total_timeout_ms.unwrap_or(DEFAULT_TOTAL_TIMEOUT_MS))
+    }
+
+    /// Check whether `program` is allowed to run under `sudo` without asking.
+    #[must_use]
+    pub fn is_privileged_allowed(&self, program: &str) -> bool {
+        let basename = program.rsplit('/').next().unwrap_or(program);
+        self.privileged_allow
+            .iter()
+            .any(|allowed| allowed == program || allowed == basename)
+    }
+}
+
+/// Shell dialect used to interpret commands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShellDialect {
+    /// POSIX-style shells (bash, zsh, sh).
+    #[default]
+    Bash,
+    /// Windows PowerShell and PowerShell 7 (`pwsh`).
+    PowerShell,
+    /// The fish shell.
+    Fish,
+}
+
+impl ShellDialect {
+    /// Get the config value for this dialect.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::PowerShell => "powershell",
+            Self::Fish => "fish",
+        }
+    }
+}
+
+/// Behavior when evaluation hits an internal error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnErrorMode {
+    /// Allow the command (fail-open). Keeps the hook out of the way.
+    #[default]
+    Open,
+    /// Deny the command with a message explaining that analysis was incomplete.
+    Closed,
+}
+
+impl OnErrorMode {
+    /// Get the config value for this mode.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+        }
+    }
+}
+
+/// Kubernetes production-awareness configuration.
+///
+/// When a kubectl command targets a production context or namespace (via
+/// `--context`, `--namespace`, or `-n`), the severity of a matched rule is
+/// raised one level. A delete that only warns against a dev cluster is
+/// therefore denied against production.
+///
+/// Patterns support `*` wildcards (e.g. `"*-prod"`, `"prod-*"`).
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [kubernetes]
+/// prod_contexts = ["*-prod", "production"]
+/// prod_namespaces = ["prod", "production"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KubernetesConfig {
+    /// kubectl contexts treated as production.
+    ///
+    /// Default: empty (no escalation)
+    pub prod_contexts: Vec<String>,
+
+    /// Namespaces treated as production.
+    ///
+    /// Default: empty (no escalation)
+    pub prod_namespaces: Vec<String>,
+}
+
+impl KubernetesConfig {
+    /// Returns `true` if the context matches one of `prod_contexts`.
+    #[must_use]
+    pub fn is_prod_context(&self, context: &str) -> bool {
+        self.prod_contexts
+            .iter()
+            .any(|pattern| wildcard_matches(pattern, context))
+    }
+
+    /// Returns `true` if the namespace matches one of `prod_namespaces`.
+    #[must_use]
+    pub fn is_prod_namespace(&self, namespace: &str) -> bool {
+        self.prod_namespaces
+            .iter()
+            .any(|pattern| wildcard_matches(pattern, namespace))
+    }
+}
+
+/// Essential OS package configuration.
+///
+/// The `system.packages` pack flags `apt remove`, `dnf remove`, and
+/// `pacman -R` as Medium. When one of the removed packages matches
+/// `essential`, the match is raised to High, so removing `libc6` or `systemd`
+/// is denied rather than warned.
+///
+/// Patterns support `*` wildcards (e.g. `"linux-image-*"`).
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [system_packages]
+/// essential = ["libc6", "systemd", "coreutils", "linux-image-*"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SystemPackagesConfig {
+    /// Package names whose removal is High severity.
+    ///
+    /// Default: core libraries, init, shell, and package manager packages
+    pub essential: Vec<String>,
+}
+
+impl Default for SystemPackagesConfig {
+    fn default() -> Self {
+        Self {
+            essential: [
+                "libc6",
+                "libc-bin",
+                "glibc",
+                "systemd",
+                "coreutils",
+                "util-linux",
+                "bash",
+                "sudo",
+                "login",
+                "passwd",
+                "openssh-server",
+                "dpkg",
+                "apt",
+                "rpm",
+                "dnf",
+                "yum",
+                "pacman",
+                "base",
+                "linux",
+                "linux-image-*",
+                "kernel",
+                "kernel-core",
+                "grub*",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+impl SystemPackagesConfig {
+    /// Returns `true` if the package matches one of `essential`.
+    #[must_use]
+    pub fn is_essential(&self, package: &str) -> bool {
+        self.essential
+            .iter()
+            .any(|pattern| wildcard_matches(pattern, package))
+    }
+}
+
+/// Protected file configuration.
+///
+/// `sed -i` and `perl -i` rewrite files in place; when one of the edited files
+/// matches `protected_paths`, the command is flagged as Medium severity (Low if
+/// a backup suffix such as `-i.bak` is given).
+///
+/// File-mutating agent tools (`Write`, `Edit`, `MultiEdit`) never go through a
+/// shell, so their `file_path` is checked against `protected_write_paths`
+/// instead; a match is High severity.
+///
+/// Patterns are globs. A pattern without `/` matches the file name in any
+/// directory (`"*.yaml"`); otherwise it matches the path (`"config/**"`).
+/// Paths are normalized first (`.`, `..` and repeated `/` are collapsed), and
+/// `Write`/`Edit` targets are resolved against the hook's working directory,
+/// so `/tmp/../etc/passwd` still matches `/etc/**`. A leading `~/` in
+/// `protected_write_paths` is expanded.
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [filesystem]
+/// protected_paths = ["*.yaml", ".env*", "config/**"]
+/// protected_write_paths = ["/etc/**", "~/.ssh/**", "*.env"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilesystemConfig {
+    /// Globs for files that must not be rewritten in place without review.
+    ///
+    /// Default: empty (no in-place edit detection)
+    pub protected_paths: Vec<String>,
+
+    /// Globs for files that `Write`/`Edit` tool calls must not target.
+    ///
+    /// Default: `/etc/**`, `~/.ssh/**` (empty disables the check; add `*.env`
+    /// to cover environment files)
+    pub protected_write_paths: Vec<String>,
+}
+
+impl Default for FilesystemConfig {
+    fn default() -> Self {
+        Self {
+            protected_paths: Vec::new(),
+            protected_write_paths: ["/etc/**", "~/.ssh/**"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl FilesystemConfig {
+    /// Returns `true` if `path` matches one of `protected_paths`.
+    #[must_use]
+    pub fn is_protected(&self, path: &str) -> bool {
+        glob_list_matches(&self.protected_paths, path)
+    }
+
+    /// Returns the `protected_write_paths` glob that `path` matches, if any.
+    ///
+    /// A relative `path` is resolved against `cwd`; relative globs also match
+    /// the path as seen from `cwd`.
+    #[must_use]
+    pub fn protected_write_match(&self, path: &str, cwd: Option<&Path>) -> Option<&str> {
+        let path = Path::new(path);
+        let resolved = match cwd {
+            Some(cwd) if path.is_relative() => lexically_normalize(&cwd.join(path)),
+            _ => lexically_normalize(path),
+        };
+        let relative = cwd
+            .and_then(|cwd| resolved.strip_prefix(lexically_normalize(cwd)).ok())
+            .map(|relative| relative.to_string_lossy().into_owned());
+        let resolved = resolved.to_string_lossy();
+        self.protected_write_paths
+            .iter()
+            .find(|pattern| {
+                let expanded = [expand_tilde_path(pattern).0.to_string_lossy().into_owned()];
+                glob_list_matches(&expanded, &resolved)
+                    || relative
+                        .as_deref()
+                        .is_some_and(|relative| glob_list_matches(&expanded, relative))
+            })
+            .map(String::as_str)
+    }
+}
+
+/// Match `path` against globs; a glob without `/` matches the file name alone.
+fn glob_list_matches(patterns: &[String], path: &str) -> bool {
+    let normalized = lexically_normalize(Path::new(path));
+    let path = normalized.to_string_lossy();
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(&path);
+    patterns.iter().any(|pattern| {
+        crate::allowlist::path_matches_glob(pattern, &path)
+            || (!pattern.contains('/') && crate::allowlist::path_matches_glob(pattern, file_name))
+    })
+}
+
+/// Collapse `.`, `..`, and repeated separators without touching the filesystem.
+///
+/// `..` at the root stays at the root; leading `..` in a relative path is kept.
+fn lexically_normalize(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Safe directory configuration.
+///
+/// In scratch and sandbox directories destructive commands are expected. When
+/// the working directory matches one of `paths`, a denied match is downgraded
+/// to `mode` (`warn` by default, `log` to allow silently). Critical matches
+/// (e.g. `rm -rf /`) and commands naming a path outside the safe directories
+/// are never downgraded.
+///
+/// Paths are globs; `~` is expanded. `/tmp/**` matches `/tmp` and everything
+/// below it.
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [safe_dirs]
+/// paths = ["/tmp/**", "~/scratch/**"]
+/// mode = "warn"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafeDirsConfig {
+    /// Globs for directories where destructive commands are expected.
+    ///
+    /// Default: empty (no downgrade)
+    pub paths: Vec<String>,
+
+    /// Mode applied instead of `deny` inside a safe directory.
+    ///
+    /// Default: `warn`
+    pub mode: Option<PolicyMode>,
+}
+
+impl SafeDirsConfig {
+    /// Returns the first pattern in `paths` that matches `cwd`.
+    #[must_use]
+    pub fn matching_pattern(&self, cwd: &Path) -> Option<&str> {
+        let cwd = cwd.to_string_lossy();
+        self.paths
+            .iter()
+            .find(|pattern| {
+                let expanded = resolve_config_path_value(pattern, None)
+                    .map_or_else(|| (*pattern).clone(), |p| p.to_string_lossy().into_owned());
+                crate::allowlist::path_matches_glob(&expanded, &cwd)
+                    || expanded
+                        .strip_suffix("/**")
+                        .is_some_and(|root| root == cwd.trim_end_matches('/'))
+            })
+            .map(String::as_str)
+    }
+
+    /// Downgrade a `Deny` decision when `cwd` is a safe directory and every
+    /// path argument of `command` resolves inside a safe directory.
+    ///
+    /// Other modes, Critical matches, commands outside safe directories, and
+    /// commands naming a path elsewhere (`rm -r ~/src` run from `/tmp`) are
+    /// returned unchanged. Words the shell would expand (variables, command
+    /// substitution, braces, globs) cannot be resolved and keep the deny.
+    #[must_use]
+    pub fn apply(
+        &self,
+        mode: crate::packs::DecisionMode,
+        severity: Option<crate::packs::Severity>,
+        cwd: Option<&Path>,
+        command: &str,
+    ) -> crate::packs::DecisionMode {
+        let Some(cwd) = cwd else {
+            return mode;
+        };
+        if mode != crate::packs::DecisionMode::Deny
+            || severity == Some(crate::packs::Severity::Critical)
+            || self.matching_pattern(cwd).is_none()
+            || !self.targets_inside(command, cwd)
+        {
+            return mode;
+        }
+        self.mode.map_or(
+            crate::packs::DecisionMode::Warn,
+            PolicyMode::to_decision_mode,
+        )
+    }
+
+    /// Whether every word of `command` stays inside a safe directory.
+    ///
+    /// Absolute, `~` and `..` paths must resolve into one; other relative
+    /// words stay under `cwd`. Quoted words holding several words (`bash -c
+    /// "..."`) are checked word by word, and any word with shell expansion
+    /// counts as outside.
+    fn targets_inside(&self, command: &str, cwd: &Path) -> bool {
+        use crate::normalize::{NormalizeTokenKind, tokenize_for_normalization};
+
+        tokenize_for_normalization(command)
+            .iter()
+            .filter(|token| token.kind == NormalizeTokenKind::Word)
+            .filter_map(|token| token.text(command))
+            .all(|word| {
+                if word.contains(['$', '`', '{', '}', '*', '?', '[']) {
+                    return false;
+                }
+                let word: String = word.chars().filter(|c| !matches!(c, '"' | '\'')).collect();
+                if word.contains(char::is_whitespace) {
+                    return self.targets_inside(&word, cwd);
+                }
+                // Redirection targets (`>/etc/x`) and `--opt=value`/`KEY=value`.
+                let word = word.rsplit(['<', '>']).next().unwrap_or_default();
+                let word = word.split_once('=').map_or(word, |(_, value)| value);
+                let names_path = word.starts_with(['/', '~'])
+                    || Path::new(word)
+                        .components()
+                        .any(|part| part == std::path::Component::ParentDir);
+                !names_path
+                    || resolve_config_path_value(word, Some(cwd)).is_some_and(|path| {
+                        self.matching_pattern(&lexically_normalize(&path)).is_some()
+                    })
+            })
+    }
+}
+
+/// Localized message configuration.
+///
+/// Reasons and explanations are looked up by rule id in a TOML message
+/// catalog (see [`crate::i18n`]). Rules missing from the catalog keep the
+/// built-in English text.
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [i18n]
+/// locale = "ja"
+/// catalog_path = "~/.config/dcg/messages.toml"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct I18nConfig {
+    /// Locale table to read from the catalog (e.g. `"ja"`).
+    ///
+    /// Default: none (English)
+    pub locale: Option<String>,
+
+    /// Path to the message catalog (`~` is expanded).
+    ///
+    /// Default: none (English)
+    pub catalog_path: Option<String>,
+}
+
+/// Message catalogs keyed by path and locale, so each is read and parsed
+/// once per process however many commands are evaluated. The file's
+/// modification time is stored with each entry; an edited catalog is loaded
+/// again.
+type CatalogCache = HashMap<
+    (PathBuf, String),
+    (
+        Option<std::time::SystemTime>,
+        Result<Arc<crate::i18n::MessageCatalog>, String>,
+    ),
+>;
+
+static CATALOG_CACHE: LazyLock<Mutex<CatalogCache>> = LazyLock::new(Mutex::default);
+
+impl I18nConfig {
+    /// Load the message catalog for the configured locale.
+    ///
+    /// Returns `Ok(None)` unless both `locale` and `catalog_path` are set. The
+    /// result (including a failure) is cached until the file's modification
+    /// time changes; [`Config::validate`] reports a catalog that fails to load.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the catalog cannot be read or parsed.
+    pub fn load_catalog(&self) -> Result<Option<Arc<crate::i18n::MessageCatalog>>, String> {
+        let locale = self.locale.as_deref().map(str::trim).unwrap_or_default();
+        let Some(path) = self
+            .catalog_path
+            .as_deref()
+            .and_then(|value| resolve_config_path_value(value, None))
+        else {
+            return Ok(None);
+        };
+        if locale.is_empty() {
+            return Ok(None);
+        }
+        let modified = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let key = (path, locale.to_string());
+        let mut cache = CATALOG_CACHE.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((cached_modified, catalog)) = cache.get(&key) {
+            if *cached_modified == modified {
+                return catalog.clone().map(Some);
+            }
+        }
+        let catalog = crate::i18n::MessageCatalog::load(&key.0, &key.1).map(Arc::new);
+        cache.insert(key, (modified, catalog.clone()));
+        catalog.map(Some)
+    }
+}
+
+/// Allow-once exception approval settings.
+///
+/// With `require_hmac`, a denial prints a confirmation token alongside the
+/// short code: an HMAC over the pending record's `full_hash`, keyed by an
+/// owner-only per-user key file. `dcg allow-once <CODE>`
+/// then also needs `--token`, so a process that can run `dcg` cannot approve
+/// its own denials by guessing short codes.
+///
+/// Only user and system config can turn the requirement off. A project
+/// `.dcg.toml`, a `DCG_CONFIG` file, or `DCG_REQUIRE_HMAC` can turn it on but
+/// never back off, since the agent being guarded can write all three.
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [exceptions]
+/// require_hmac = true
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExceptionsConfig {
+    /// Require a confirmation token in addition to the short code.
+    ///
+    /// Default: false
+    pub require_hmac: bool,
+}
+
+/// Match `value` against a pattern where `*` matches any run of characters.
+fn wildcard_matches(pattern: &str, value: &str) -> bool {
+    let mut parts: Vec<&str> = pattern.split('*').collect();
+    let first = parts.remove(0);
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+    let Some(last) = parts.pop() else {
+        // No wildcard at all: exact match.
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
 impl HeredocConfig {
     #[must_use]
     pub fn settings(&self) -> HeredocSettings {
@@ -520,6 +1295,7 @@ impl HeredocConfig {
                     "typescript" | "ts" => Some(crate::heredoc::ScriptLanguage::TypeScript),
                     "php" => Some(crate::heredoc::ScriptLanguage::Php),
                     "go" | "golang" => Some(crate::heredoc::ScriptLanguage::Go),
+                    "sql" => Some(crate::heredoc::ScriptLanguage::Sql),
                     "unknown" => Some(crate::heredoc::ScriptLanguage::Unknown),
                     _ => None,
                 };
@@ -726,7 +1502,7 @@ fn pattern_matches(
 /// An empty or whitespace-only filter matches all languages (same as `language: None`).
 fn language_filter_matches(filter: &str, language: crate::heredoc::ScriptLanguage) -> bool {
     use crate::heredoc::ScriptLanguage::{
-        Bash, Go, JavaScript, Perl, Php, Python, Ruby, TypeScript, Unknown,
+        Bash, Go, JavaScript, Perl, Php, Python, Ruby, Sql, TypeScript, Unknown,
     };
     let filter_lower = filter.trim().to_ascii_lowercase();
 
@@ -744,6 +1520,7 @@ fn language_filter_matches(filter: &str, language: crate::heredoc::ScriptLanguag
         TypeScript => matches!(filter_lower.as_str(), "typescript" | "ts"),
         Php => matches!(filter_lower.as_str(), "php"),
         Go => matches!(filter_lower.as_str(), "go" | "golang"),
+        Sql => filter_lower == "sql",
         Unknown => filter_lower == "unknown",
     }
 }
@@ -928,16 +1705,38 @@ pub struct PacksConfig {
     /// loading valid packs.
     #[serde(default)]
     pub custom_paths: Vec<String>,
+
+    /// URL of a remote pack bundle (multi-document YAML) for fleet-wide packs.
+    ///
+    /// The bundle is cached on disk and refetched after `remote_ttl_secs`.
+    /// Fetch failures fall back to the cached copy, then to built-in packs.
+    pub remote: Option<String>,
+
+    /// Seconds a fetched remote bundle is reused before refetching (default: 3600).
+    pub remote_ttl_secs: Option<u64>,
+
+    /// Rule IDs of external-pack patterns shipped with `enabled: false` to turn on
+    /// (e.g., `["custom.deploy:experimental-rule"]`).
+    pub enable_patterns: Vec<String>,
 }
 
 impl PacksConfig {
     /// Get enabled pack IDs as a deduplicated set.
     #[must_use]
     pub fn enabled_pack_ids(&self) -> HashSet<String> {
-        let mut enabled: HashSet<String> = self.enabled.iter().cloned().collect();
+        let mut enabled: HashSet<String> = self
+            .enabled
+            .iter()
+            .flat_map(|id| crate::packs::resolve_pack_ids(id))
+            .map(str::to_string)
+            .collect();
 
         // Remove explicitly disabled packs.
-        for disabled in &self.disabled {
+        for disabled in self
+            .disabled
+            .iter()
+            .flat_map(|id| crate::packs::resolve_pack_ids(id))
+        {
             enabled.remove(disabled);
             // Also remove sub-packs if a category is disabled.
             enabled.retain(|p| !p.starts_with(&format!("{disabled}.")));
@@ -949,6 +1748,51 @@ impl PacksConfig {
         enabled
     }
 
+    /// Build the external pack sources for this config.
+    ///
+    /// Local `custom_paths` are loaded first and the `remote` bundle last, so
+    /// centrally distributed packs win over local copies with the same ID.
+    #[must_use]
+    pub fn pack_sources(&self) -> Vec<Box<dyn PackSource>> {
+        self.build_pack_sources(self.remote_source())
+    }
+
+    /// Like [`pack_sources`](Self::pack_sources), but the remote bundle is
+    /// only read from the cache. Used on the hook path, which must not wait
+    /// on the network.
+    #[must_use]
+    pub fn cached_pack_sources(&self) -> Vec<Box<dyn PackSource>> {
+        self.build_pack_sources(self.remote_source().map(HttpPackSource::cache_only))
+    }
+
+    /// The `remote` bundle source, if one is configured.
+    #[must_use]
+    pub fn remote_source(&self) -> Option<HttpPackSource> {
+        let url = self
+            .remote
+            .as_deref()
+            .filter(|url| !url.trim().is_empty())?;
+        let ttl = self
+            .remote_ttl_secs
+            .map_or(DEFAULT_REMOTE_TTL, Duration::from_secs);
+        Some(HttpPackSource::new(url.trim(), ttl))
+    }
+
+    fn build_pack_sources(&self, remote: Option<HttpPackSource>) -> Vec<Box<dyn PackSource>> {
+        let mut sources: Vec<Box<dyn PackSource>> = Vec::new();
+
+        let paths = self.expand_custom_paths();
+        if !paths.is_empty() {
+            sources.push(Box::new(FilePackSource::new(paths)));
+        }
+
+        if let Some(remote) = remote {
+            sources.push(Box::new(remote));
+        }
+
+        sources
+    }
+
     /// Expand custom_paths, resolving tilde and glob patterns.
     ///
     /// Returns a list of concrete file paths that exist on disk.
@@ -992,7 +1836,148 @@ impl PacksConfig {
             }
         }
 
-        result
+        result
+    }
+}
+
+/// Preset selection (`[mode]` section).
+///
+/// # Example Configuration (TOML)
+///
+/// ```toml
+/// [mode]
+/// preset = "prod"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModeConfig {
+    /// Active preset. Its settings are applied before any config file, so
+    /// explicit keys in config files and environment variables override it.
+    ///
+    /// Default: none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<ConfigPreset>,
+}
+
+/// Named bundle of settings selected with `[mode] preset` or `DCG_PROFILE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigPreset {
+    /// Every pack enabled; ask on medium, deny high and critical.
+    Paranoid,
+    /// Deny high and critical, warn on medium; escalate production
+    /// Kubernetes contexts and namespaces.
+    Prod,
+    /// Warn instead of deny below critical; scratch directories only log.
+    Dev,
+}
+
+impl ConfigPreset {
+    /// Get the config value for this preset.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Paranoid => "paranoid",
+            Self::Prod => "prod",
+            Self::Dev => "dev",
+        }
+    }
+
+    /// Parse a preset name (case-insensitive).
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "paranoid" => Some(Self::Paranoid),
+            "prod" | "production" => Some(Self::Prod),
+            "dev" | "development" => Some(Self::Dev),
+            _ => None,
+        }
+    }
+
+    /// The settings this preset applies, as a config file fragment.
+    #[must_use]
+    pub const fn settings_toml(self) -> &'static str {
+        match self {
+            Self::Paranoid => {
+                r#"[packs]
+enabled = [
+    "apigateway", "backup", "cdn", "cicd", "cloud", "containers", "database",
+    "dns", "email", "featureflags", "gpg_and_crypto", "infrastructure",
+    "kubernetes", "loadbalancer", "messaging", "ml_ops", "monitoring",
+    "package_managers", "payment", "platform", "remote", "search", "secrets",
+    "sql_migration", "storage", "strict_git", "system", "vcs", "windows",
+]
+
+[policy.severity]
+high = "deny"
+medium = "ask"
+"#
+            }
+            Self::Prod => {
+                r#"[packs]
+enabled = ["kubernetes"]
+
+[policy.severity]
+high = "deny"
+medium = "warn"
+
+[kubernetes]
+prod_contexts = ["*prod*"]
+prod_namespaces = ["prod", "production"]
+"#
+            }
+            Self::Dev => {
+                r#"[policy.severity]
+high = "warn"
+medium = "warn"
+low = "log"
+
+[safe_dirs]
+paths = ["/tmp/**", "/var/tmp/**", "~/scratch/**"]
+mode = "log"
+"#
+            }
+        }
+    }
+
+    fn layer(self) -> ConfigLayer {
+        toml::from_str(self.settings_toml()).expect("built-in preset must parse")
+    }
+}
+
+/// Per-severity decision modes (`[policy.severity]`).
+///
+/// Replaces the built-in severity defaults (high → deny, medium → warn,
+/// low → log). Critical matches always deny unless a per-rule override says
+/// otherwise, so there is no `critical` key.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeverityPolicy {
+    /// Mode for high-severity matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high: Option<PolicyMode>,
+    /// Mode for medium-severity matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub medium: Option<PolicyMode>,
+    /// Mode for low-severity matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low: Option<PolicyMode>,
+}
+
+impl SeverityPolicy {
+    /// Configured mode for a severity, if any.
+    #[must_use]
+    pub const fn mode_for(&self, severity: crate::packs::Severity) -> Option<PolicyMode> {
+        match severity {
+            crate::packs::Severity::Critical => None,
+            crate::packs::Severity::High => self.high,
+            crate::packs::Severity::Medium => self.medium,
+            crate::packs::Severity::Low => self.low,
+        }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.high.is_none() && self.medium.is_none() && self.low.is_none()
     }
 }
 
@@ -1026,6 +2011,10 @@ pub struct PolicyConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub observe_until: Option<ObserveUntil>,
 
+    /// Per-severity modes, replacing the severity-based defaults.
+    #[serde(default, skip_serializing_if = "SeverityPolicy::is_empty")]
+    pub severity: SeverityPolicy,
+
     /// Per-pack mode overrides.
     /// Key is `pack_id` (e.g., "core.git", "database.postgresql").
     /// Value is the mode to use for all patterns in that pack.
@@ -1050,6 +2039,8 @@ pub enum PolicyMode {
     Warn,
     /// Log only (silent allow, record for history).
     Log,
+    /// Ask the user to confirm before running (hook `ask` response).
+    Ask,
 }
 
 impl PolicyMode {
@@ -1060,6 +2051,7 @@ impl PolicyMode {
             Self::Deny => crate::packs::DecisionMode::Deny,
             Self::Warn => crate::packs::DecisionMode::Warn,
             Self::Log => crate::packs::DecisionMode::Log,
+            Self::Ask => crate::packs::DecisionMode::Ask,
         }
     }
 }
@@ -1071,7 +2063,8 @@ impl PolicyConfig {
     /// 1. Rule-specific override (via `rules["pack_id:pattern_name"]`)
     /// 2. Pack-specific override (via `packs["pack_id"]`)
     /// 3. Global default (`default_mode`)
-    /// 4. Severity-based default (from pattern's severity)
+    /// 4. Per-severity mode (`severity.<level>`)
+    /// 5. Severity-based default (from pattern's severity)
     #[must_use]
     pub fn resolve_mode(
         &self,
@@ -1082,6 +2075,15 @@ impl PolicyConfig {
         self.resolve_mode_at(Utc::now(), pack_id, pattern_name, severity)
     }
 
+    /// Whether the `observe_until` window is currently open.
+    #[must_use]
+    pub fn in_observe_window(&self) -> bool {
+        self.observe_until
+            .as_ref()
+            .and_then(ObserveUntil::parsed_utc)
+            .is_some_and(|until| &Utc::now() < until)
+    }
+
     #[must_use]
     pub fn resolve_mode_at(
         &self,
@@ -1128,8 +2130,12 @@ impl PolicyConfig {
             return mode.to_decision_mode();
         }
 
-        // 4. Severity-based default
-        severity.map_or(crate::packs::DecisionMode::Deny, |s| s.default_mode())
+        // 4. Per-severity mode, then 5. severity-based default
+        severity.map_or(crate::packs::DecisionMode::Deny, |s| {
+            self.severity
+                .mode_for(s)
+                .map_or_else(|| s.default_mode(), PolicyMode::to_decision_mode)
+        })
     }
 }
 
@@ -1563,6 +2569,9 @@ pub struct HistoryConfig {
     pub batch_size: u32,
     /// Flush interval in milliseconds for batched writes.
     pub batch_flush_interval_ms: u32,
+    /// Record clean allows (no rule matched). When false, only denied,
+    /// warned, bypassed, and allowlisted commands are written.
+    pub log_allows: bool,
 }
 
 impl HistoryConfig {
@@ -1629,6 +2638,7 @@ impl Default for HistoryConfig {
             prune_check_interval_hours: Self::DEFAULT_PRUNE_CHECK_INTERVAL_HOURS,
             batch_size: Self::DEFAULT_BATCH_SIZE,
             batch_flush_interval_ms: Self::DEFAULT_BATCH_FLUSH_INTERVAL_MS,
+            log_allows: true,
         }
     }
 }
@@ -1742,6 +2752,9 @@ impl std::fmt::Display for StrictnessLevel {
 ///
 /// # Show branch context in output
 /// show_branch_in_output = true
+///
+/// # Warn before amending/rebasing commits that are already pushed
+/// warn_on_pushed_rewrite = true
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -1752,6 +2765,8 @@ pub struct GitAwarenessConfig {
 
     /// Branch patterns that should receive extra protection.
     /// Supports glob patterns (e.g., "release/*").
+    /// Force pushes are Critical only when they target one of these branches,
+    /// whether or not `enabled` is set.
     pub protected_branches: Vec<String>,
 
     /// Strictness level for protected branches.
@@ -1786,6 +2801,12 @@ pub struct GitAwarenessConfig {
     /// evaluated using default strictness (graceful degradation).
     /// Default: `false`
     pub warn_if_not_git: bool,
+
+    /// Warn on `git commit --amend` and `git rebase` when the current branch
+    /// has an upstream and HEAD has already been pushed to it.
+    /// Works independently of `enabled`; refs are read from `.git` directly.
+    /// Default: `true`
+    pub warn_on_pushed_rewrite: bool,
 }
 
 impl Default for GitAwarenessConfig {
@@ -1809,6 +2830,7 @@ impl Default for GitAwarenessConfig {
             relaxed_disabled_packs: Vec::new(),
             show_branch_in_output: true,
             warn_if_not_git: false,
+            warn_on_pushed_rewrite: true,
         }
     }
 }
@@ -1888,6 +2910,15 @@ impl GitAwarenessConfig {
         branch.is_some_and(|b| self.matches_any_pattern(b, &self.protected_branches))
     }
 
+    /// Returns `true` if `branch` matches `protected_branches`.
+    ///
+    /// Unlike [`Self::is_protected_branch`], this ignores `enabled`: it answers
+    /// whether a branch is protected, not whether branch-aware strictness applies.
+    #[must_use]
+    pub fn matches_protected_branch(&self, branch: &str) -> bool {
+        self.matches_any_pattern(branch, &self.protected_branches)
+    }
+
     /// Returns `true` if the current branch is a relaxed branch.
     #[must_use]
     pub fn is_relaxed_branch(&self, branch: Option<&str>) -> bool {
@@ -2369,6 +3400,47 @@ impl OverridesConfig {
     }
 }
 
+/// Severity of a [`ConfigDiagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigDiagnosticSeverity {
+    /// The setting cannot take effect as written.
+    Error,
+    /// The setting is partly ignored.
+    Warning,
+}
+
+impl ConfigDiagnosticSeverity {
+    /// Get a human-readable label for this severity.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+/// A semantic problem found by [`Config::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigDiagnostic {
+    /// How serious the problem is.
+    pub severity: ConfigDiagnosticSeverity,
+    /// Dotted path of the offending setting (e.g. `packs.enabled`).
+    pub field: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+/// Check if a pack ID exists in the registry or is a category name
+/// (e.g. `containers` enables every `containers.*` pack).
+fn is_valid_pack_id(id: &str) -> bool {
+    let registry = &crate::packs::REGISTRY;
+    crate::packs::resolve_pack_ids(id).into_iter().all(|id| {
+        registry.all_pack_ids().contains(&id) || registry.all_categories().iter().any(|c| *c == id)
+    })
+}
+
 /// Project-specific configuration overrides.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
@@ -2390,23 +3462,48 @@ impl Config {
     /// 4. User config (`$XDG_CONFIG_HOME/dcg/config.toml`, `~/.config/dcg/config.toml`,
     ///    or platform-native config dir)
     /// 5. System config (`/etc/dcg/config.toml`)
-    /// 6. Compiled defaults
+    /// 6. Preset (`[mode] preset` or `DCG_PROFILE`)
+    /// 7. Compiled defaults
     #[must_use]
     pub fn load() -> Self {
-        // Start with truly empty defaults - packs must be explicitly enabled.
-        // generate_default() is for sample configs shown to users, not runtime defaults.
-        let mut config = Self::default();
+        Self::from_layers_with_env(Self::file_layers(), |key| env::var(key).ok())
+    }
+
+    /// Load configuration like [`Self::load`], with `toml` merged as the
+    /// highest-priority file layer (environment variables still win).
+    ///
+    /// Used by embedders that supply their own settings but should otherwise
+    /// reach the same verdicts as the `dcg` binary. Like a `DCG_CONFIG` file,
+    /// the inline layer may only tighten security-sensitive settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `toml` is not a valid config file or uses `include`,
+    /// which has no base directory to resolve against.
+    pub fn load_with_overlay(toml: &str) -> Result<Self, String> {
+        let overlay: ConfigLayer = toml::from_str(toml).map_err(|e| e.to_string())?;
+        if overlay.include.is_some() {
+            return Err("`include` is not supported in inline config".to_string());
+        }
+        let mut layers = Self::file_layers();
+        layers.push(overlay.tighten_only());
+        Ok(Self::from_layers_with_env(layers, |key| env::var(key).ok()))
+    }
+
+    /// Collect the file layers [`Self::load`] merges, lowest priority first.
+    fn file_layers() -> Vec<ConfigLayer> {
         let cwd = env::current_dir().ok();
+        let mut layers = Vec::new();
 
         // Optional explicit config path override (highest-priority file config).
         let explicit_layer = env::var(ENV_CONFIG_PATH)
             .ok()
             .and_then(|value| resolve_config_path_value(&value, cwd.as_deref()))
-            .and_then(|path| Self::load_layer_from_file(&path));
+            .and_then(|path| Self::load_layers_from_file(&path));
 
         // Load system config (lowest priority of file configs)
         if let Some(system_config) = Self::load_system_config_layer() {
-            config.merge_layer(system_config);
+            layers.extend(system_config);
         }
 
         // Load user config
@@ -2416,53 +3513,74 @@ impl Config {
         // reduce layering confusion.
         if explicit_layer.is_none() {
             if let Some(user_config) = Self::load_user_config_layer() {
-                config.merge_layer(user_config);
+                layers.extend(user_config);
             }
         }
 
         // Load project config (if in a git repo)
         if let Some(project_config) = Self::load_project_config_layer_from(cwd.as_deref()) {
-            config.merge_layer(project_config);
+            layers.extend(project_config.into_iter().map(ConfigLayer::tighten_only));
         }
 
         // Apply explicit config last among file configs (if present and valid).
+        // It is chosen by an environment variable, so it is trusted like one.
         if let Some(explicit_layer) = explicit_layer {
-            config.merge_layer(explicit_layer);
+            layers.extend(explicit_layer.into_iter().map(ConfigLayer::tighten_only));
+        }
+
+        layers
+    }
+
+    /// Build a config from file layers (lowest priority first) and environment.
+    ///
+    /// The preset named by `DCG_PROFILE`, or else by the highest-priority
+    /// layer that sets `[mode] preset`, is merged first so every explicit key
+    /// overrides it.
+    fn from_layers_with_env<F>(layers: Vec<ConfigLayer>, mut get_env: F) -> Self
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        // Start with truly empty defaults - packs must be explicitly enabled.
+        // generate_default() is for sample configs shown to users, not runtime defaults.
+        let mut config = Self::default();
+
+        let preset = get_env(&format!("{ENV_PREFIX}_PROFILE"))
+            .and_then(|value| ConfigPreset::parse(&value))
+            .or_else(|| {
+                layers
+                    .iter()
+                    .rev()
+                    .find_map(|layer| layer.mode.as_ref().and_then(|mode| mode.preset))
+            });
+        if let Some(preset) = preset {
+            config.merge_layer(preset.layer());
         }
 
+        config.merge_layers(layers);
+
         // Apply environment variable overrides (highest priority)
-        config.apply_env_overrides();
+        config.apply_env_overrides_from(get_env);
 
         config
     }
 
-    /// Load a configuration *layer* from a specific file.
+    /// Load the configuration *layers* for a specific file.
     ///
     /// Layers preserve field presence (via `Option<T>`) so higher-precedence
-    /// configs can explicitly set values back to defaults.
+    /// configs can explicitly set values back to defaults. Files named in
+    /// `include` come first, in order, followed by the file itself, so the
+    /// including file wins. A file whose include chain fails to load is skipped
+    /// as a whole.
     #[must_use]
-    fn load_layer_from_file(path: &Path) -> Option<ConfigLayer> {
-        let content = match fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
-            Err(e) => {
-                eprintln!(
-                    "Warning: Failed to read config file '{}': {}",
-                    path.display(),
-                    e
-                );
-                return None;
-            }
-        };
-
-        match toml::from_str(&content) {
-            Ok(layer) => Some(layer),
+    fn load_layers_from_file(path: &Path) -> Option<Vec<ConfigLayer>> {
+        if !path.exists() {
+            return None;
+        }
+        let mut layers = Vec::new();
+        match load_include_chain(path, &mut Vec::new(), &mut layers) {
+            Ok(()) => Some(layers.into_iter().map(|(_, layer)| layer).collect()),
             Err(e) => {
-                eprintln!(
-                    "Warning: Failed to parse config file '{}': {}",
-                    path.display(),
-                    e
-                );
+                eprintln!("Warning: {e}");
                 None
             }
         }
@@ -2476,9 +3594,9 @@ impl Config {
     }
 
     /// Load system-wide configuration.
-    fn load_system_config_layer() -> Option<ConfigLayer> {
+    fn load_system_config_layer() -> Option<Vec<ConfigLayer>> {
         let path = PathBuf::from("/etc/dcg").join(CONFIG_FILE_NAME);
-        Self::load_layer_from_file(&path)
+        Self::load_layers_from_file(&path)
     }
 
     /// Load user configuration.
@@ -2486,14 +3604,14 @@ impl Config {
     /// Checks XDG_CONFIG_HOME, XDG-style (`~/.config/dcg/`), and platform-native paths.
     /// This ensures users can use `~/.config/dcg/config.toml` on all platforms,
     /// including macOS where `dirs::config_dir()` returns `~/Library/Application Support`.
-    fn load_user_config_layer() -> Option<ConfigLayer> {
+    fn load_user_config_layer() -> Option<Vec<ConfigLayer>> {
         // First try XDG_CONFIG_HOME (if set)
         if let Ok(xdg_home) = env::var("XDG_CONFIG_HOME") {
             if let Some(xdg_home) = resolve_config_path_value(&xdg_home, None) {
                 let xdg_path = xdg_home.join("dcg").join(CONFIG_FILE_NAME);
                 if xdg_path.exists() {
-                    if let Some(layer) = Self::load_layer_from_file(&xdg_path) {
-                        return Some(layer);
+                    if let Some(layers) = Self::load_layers_from_file(&xdg_path) {
+                        return Some(layers);
                     }
                 }
             }
@@ -2504,8 +3622,8 @@ impl Config {
         if let Some(home) = dirs::home_dir() {
             let xdg_path = home.join(".config").join("dcg").join(CONFIG_FILE_NAME);
             if xdg_path.exists() {
-                if let Some(layer) = Self::load_layer_from_file(&xdg_path) {
-                    return Some(layer);
+                if let Some(layers) = Self::load_layers_from_file(&xdg_path) {
+                    return Some(layers);
                 }
             }
         }
@@ -2513,18 +3631,25 @@ impl Config {
         // Fall back to platform-native path (e.g., ~/Library/Application Support/dcg/ on macOS)
         let config_dir = dirs::config_dir()?;
         let path = config_dir.join("dcg").join(CONFIG_FILE_NAME);
-        Self::load_layer_from_file(&path)
+        Self::load_layers_from_file(&path)
     }
 
     /// Load project-level configuration (`.dcg.toml` in repo root).
-    fn load_project_config_layer_from(start_dir: Option<&Path>) -> Option<ConfigLayer> {
+    fn load_project_config_layer_from(start_dir: Option<&Path>) -> Option<Vec<ConfigLayer>> {
         let start_dir = start_dir?;
         let repo_root = find_repo_root(start_dir, REPO_ROOT_SEARCH_MAX_HOPS)?;
         let config_path = repo_root.join(PROJECT_CONFIG_NAME);
         if !config_path.exists() {
             return None;
         }
-        Self::load_layer_from_file(&config_path)
+        Self::load_layers_from_file(&config_path)
+    }
+
+    /// Merge a file's layers (its includes, then the file itself) in order.
+    fn merge_layers(&mut self, layers: Vec<ConfigLayer>) {
+        for layer in layers {
+            self.merge_layer(layer);
+        }
     }
 
     /// Merge another config layer into this one (other takes priority when set).
@@ -2533,6 +3658,10 @@ impl Config {
             self.merge_general_layer(general);
         }
 
+        if let Some(preset) = other.mode.and_then(|mode| mode.preset) {
+            self.mode.preset = Some(preset);
+        }
+
         if let Some(output) = other.output {
             self.merge_output_layer(output);
         }
@@ -2561,6 +3690,36 @@ impl Config {
             self.merge_confidence_layer(confidence);
         }
 
+        if let Some(evaluation) = other.evaluation {
+            self.merge_evaluation_layer(evaluation);
+        }
+
+        if let Some(kubernetes) = other.kubernetes {
+            self.merge_kubernetes_layer(kubernetes);
+        }
+
+        if let Some(system_packages) = other.system_packages {
+            self.merge_system_packages_layer(system_packages);
+        }
+
+        if let Some(filesystem) = other.filesystem {
+            self.merge_filesystem_layer(filesystem);
+        }
+
+        if let Some(safe_dirs) = other.safe_dirs {
+            self.merge_safe_dirs_layer(safe_dirs);
+        }
+
+        if let Some(i18n) = other.i18n {
+            self.merge_i18n_layer(i18n);
+        }
+
+        if let Some(exceptions) = other.exceptions {
+            if let Some(require_hmac) = exceptions.require_hmac {
+                self.exceptions.require_hmac = require_hmac;
+            }
+        }
+
         if let Some(logging) = other.logging {
             self.merge_logging_layer(logging);
         }
@@ -2642,6 +3801,13 @@ impl Config {
         self.packs.enabled.extend(packs.enabled);
         self.packs.disabled.extend(packs.disabled);
         self.packs.custom_paths.extend(packs.custom_paths);
+        self.packs.enable_patterns.extend(packs.enable_patterns);
+        if packs.remote.is_some() {
+            self.packs.remote = packs.remote;
+        }
+        if packs.remote_ttl_secs.is_some() {
+            self.packs.remote_ttl_secs = packs.remote_ttl_secs;
+        }
     }
 
     fn merge_policy_layer(&mut self, policy: PolicyConfig) {
@@ -2651,6 +3817,15 @@ impl Config {
         if policy.observe_until.is_some() {
             self.policy.observe_until = policy.observe_until;
         }
+        if policy.severity.high.is_some() {
+            self.policy.severity.high = policy.severity.high;
+        }
+        if policy.severity.medium.is_some() {
+            self.policy.severity.medium = policy.severity.medium;
+        }
+        if policy.severity.low.is_some() {
+            self.policy.severity.low = policy.severity.low;
+        }
         self.policy.packs.extend(policy.packs);
         self.policy.rules.extend(policy.rules);
     }
@@ -2709,6 +3884,72 @@ impl Config {
         }
     }
 
+    fn merge_evaluation_layer(&mut self, evaluation: EvaluationConfigLayer) {
+        if let Some(strict_quoting) = evaluation.strict_quoting {
+            self.evaluation.strict_quoting = strict_quoting;
+        }
+        if let Some(on_error) = evaluation.on_error {
+            self.evaluation.on_error = on_error;
+        }
+        if let Some(shell) = evaluation.shell {
+            self.evaluation.shell = shell;
+        }
+        if let Some(ask) = evaluation.ask_on_privileged_unknown {
+            self.evaluation.ask_on_privileged_unknown = ask;
+        }
+        if let Some(privileged_allow) = evaluation.privileged_allow {
+            self.evaluation.privileged_allow = privileged_allow;
+        }
+        if let Some(resolve) = evaluation.resolve_script_targets {
+            self.evaluation.resolve_script_targets = resolve;
+        }
+        if let Some(total_timeout_ms) = evaluation.total_timeout_ms {
+            self.evaluation.total_timeout_ms = Some(total_timeout_ms);
+        }
+    }
+
+    fn merge_kubernetes_layer(&mut self, kubernetes: KubernetesConfigLayer) {
+        if let Some(prod_contexts) = kubernetes.prod_contexts {
+            self.kubernetes.prod_contexts = prod_contexts;
+        }
+        if let Some(prod_namespaces) = kubernetes.prod_namespaces {
+            self.kubernetes.prod_namespaces = prod_namespaces;
+        }
+    }
+
+    fn merge_system_packages_layer(&mut self, system_packages: SystemPackagesConfigLayer) {
+        if let Some(essential) = system_packages.essential {
+            self.system_packages.essential = essential;
+        }
+    }
+
+    fn merge_filesystem_layer(&mut self, filesystem: FilesystemConfigLayer) {
+        if let Some(protected_paths) = filesystem.protected_paths {
+            self.filesystem.protected_paths = protected_paths;
+        }
+        if let Some(protected_write_paths) = filesystem.protected_write_paths {
+            self.filesystem.protected_write_paths = protected_write_paths;
+        }
+    }
+
+    fn merge_safe_dirs_layer(&mut self, safe_dirs: SafeDirsConfigLayer) {
+        if let Some(paths) = safe_dirs.paths {
+            self.safe_dirs.paths = paths;
+        }
+        if let Some(mode) = safe_dirs.mode {
+            self.safe_dirs.mode = Some(mode);
+        }
+    }
+
+    fn merge_i18n_layer(&mut self, i18n: I18nConfig) {
+        if let Some(locale) = i18n.locale {
+            self.i18n.locale = Some(locale);
+        }
+        if let Some(catalog_path) = i18n.catalog_path {
+            self.i18n.catalog_path = Some(catalog_path);
+        }
+    }
+
     fn merge_logging_layer(&mut self, logging: LoggingConfigLayer) {
         if let Some(enabled) = logging.enabled {
             self.logging.enabled = enabled;
@@ -2741,6 +3982,18 @@ impl Config {
                 self.logging.events.allow = allow;
             }
         }
+        if let Some(syslog) = logging.syslog {
+            self.logging.syslog = syslog;
+        }
+        if let Some(syslog_warn) = logging.syslog_warn {
+            self.logging.syslog_warn = syslog_warn;
+        }
+        if let Some(facility) = logging.syslog_facility {
+            self.logging.syslog_facility = facility;
+        }
+        if let Some(severity) = logging.syslog_severity {
+            self.logging.syslog_severity = severity;
+        }
     }
 
     fn merge_history_layer(&mut self, history: HistoryConfigLayer) {
@@ -2759,6 +4012,9 @@ impl Config {
         if let Some(database_path) = history.database_path {
             self.history.database_path = Some(database_path);
         }
+        if let Some(log_allows) = history.log_allows {
+            self.history.log_allows = log_allows;
+        }
     }
 
     fn merge_interactive_layer(&mut self, interactive: InteractiveConfigLayer) {
@@ -2810,6 +4066,9 @@ impl Config {
         if let Some(warn_if_not_git) = git_awareness.warn_if_not_git {
             self.git_awareness.warn_if_not_git = warn_if_not_git;
         }
+        if let Some(warn_on_pushed_rewrite) = git_awareness.warn_on_pushed_rewrite {
+            self.git_awareness.warn_on_pushed_rewrite = warn_on_pushed_rewrite;
+        }
     }
 
     fn merge_agents_layer(&mut self, agents: AgentsConfig) {
@@ -2820,10 +4079,6 @@ impl Config {
     }
 
     /// Apply environment variable overrides.
-    fn apply_env_overrides(&mut self) {
-        self.apply_env_overrides_from(|key| env::var(key).ok());
-    }
-
     fn apply_env_overrides_from<F>(&mut self, mut get_env: F)
     where
         F: FnMut(&str) -> Option<String>,
@@ -2833,6 +4088,13 @@ impl Config {
             self.packs.enabled = packs.split(',').map(|s| s.trim().to_string()).collect();
         }
 
+        // DCG_PROFILE=paranoid|prod|dev (settings are applied in `load`)
+        if let Some(preset) = get_env(&format!("{ENV_PREFIX}_PROFILE")) {
+            if let Some(preset) = ConfigPreset::parse(&preset) {
+                self.mode.preset = Some(preset);
+            }
+        }
+
         // DCG_DISABLE="kubernetes.helm"
         if let Some(disable) = get_env(&format!("{ENV_PREFIX}_DISABLE")) {
             self.packs.disabled = disable.split(',').map(|s| s.trim().to_string()).collect();
@@ -2843,6 +4105,11 @@ impl Config {
             self.packs.custom_paths = paths.split(',').map(|s| s.trim().to_string()).collect();
         }
 
+        // DCG_REMOTE_PACKS="https://packs.example.com/dcg.yaml"
+        if let Some(url) = get_env(&format!("{ENV_PREFIX}_REMOTE_PACKS")) {
+            self.packs.remote = Some(url.trim().to_string());
+        }
+
         // DCG_VERBOSE=0-3
         if let Some(verbose) = get_env(&format!("{ENV_PREFIX}_VERBOSE")) {
             if let Ok(level) = verbose.trim().parse::<u8>() {
@@ -2918,6 +4185,88 @@ impl Config {
             }
         }
 
+        // DCG_STRICT_QUOTING=true|false|1|0
+        if let Some(strict) = get_env(&format!("{ENV_PREFIX}_STRICT_QUOTING")) {
+            if let Some(parsed) = parse_env_bool(&strict) {
+                self.evaluation.strict_quoting = parsed;
+            }
+        }
+
+        // DCG_ON_ERROR=open|closed
+        if let Some(on_error) = get_env(&format!("{ENV_PREFIX}_ON_ERROR")) {
+            match on_error.trim().to_ascii_lowercase().as_str() {
+                "open" => self.evaluation.on_error = OnErrorMode::Open,
+                "closed" => self.evaluation.on_error = OnErrorMode::Closed,
+                _ => {}
+            }
+        }
+
+        // DCG_SHELL=bash|powershell|pwsh|fish
+        if let Some(shell) = get_env(&format!("{ENV_PREFIX}_SHELL")) {
+            match shell.trim().to_ascii_lowercase().as_str() {
+                "bash" => self.evaluation.shell = ShellDialect::Bash,
+                "powershell" | "pwsh" => self.evaluation.shell = ShellDialect::PowerShell,
+                "fish" => self.evaluation.shell = ShellDialect::Fish,
+                _ => {}
+            }
+        }
+
+        // DCG_ASK_ON_PRIVILEGED_UNKNOWN=true|false|1|0
+        if let Some(ask) = get_env(&format!("{ENV_PREFIX}_ASK_ON_PRIVILEGED_UNKNOWN")) {
+            if let Some(parsed) = parse_env_bool(&ask) {
+                self.evaluation.ask_on_privileged_unknown = parsed;
+            }
+        }
+
+        // DCG_RESOLVE_SCRIPT_TARGETS=true|false|1|0
+        if let Some(resolve) = get_env(&format!("{ENV_PREFIX}_RESOLVE_SCRIPT_TARGETS")) {
+            if let Some(parsed) = parse_env_bool(&resolve) {
+                self.evaluation.resolve_script_targets = parsed;
+            }
+        }
+
+        // DCG_TOTAL_TIMEOUT_MS=5000
+        if let Some(timeout_ms) = get_env(&format!("{ENV_PREFIX}_TOTAL_TIMEOUT_MS")) {
+            if let Ok(parsed) = timeout_ms.trim().parse::<u64>() {
+                self.evaluation.total_timeout_ms = Some(parsed);
+            }
+        }
+
+        // DCG_PROTECTED_PATHS=*.yaml,.env*,config/**
+        if let Some(paths) = get_env(&format!("{ENV_PREFIX}_PROTECTED_PATHS")) {
+            self.filesystem.protected_paths = paths
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+
+        // DCG_PROTECTED_WRITE_PATHS=/etc/**,~/.ssh/**
+        if let Some(paths) = get_env(&format!("{ENV_PREFIX}_PROTECTED_WRITE_PATHS")) {
+            self.filesystem.protected_write_paths = paths
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+
+        // DCG_LOCALE=ja
+        if let Some(locale) = get_env(&format!("{ENV_PREFIX}_LOCALE")) {
+            self.i18n.locale = Some(locale.trim().to_string());
+        }
+
+        // DCG_MESSAGE_CATALOG=/path/to/messages.toml
+        if let Some(catalog_path) = get_env(&format!("{ENV_PREFIX}_MESSAGE_CATALOG")) {
+            self.i18n.catalog_path = Some(catalog_path);
+        }
+
+        // DCG_REQUIRE_HMAC=true|1 (can only turn the requirement on)
+        if let Some(require) = get_env(&format!("{ENV_PREFIX}_REQUIRE_HMAC")) {
+            if parse_env_bool(&require) == Some(true) {
+                self.exceptions.require_hmac = true;
+            }
+        }
+
         // -----------------------------------------------------------------
         // Policy config (env overrides)
         // -----------------------------------------------------------------
@@ -2952,6 +4301,13 @@ impl Config {
             }
         }
 
+        // DCG_SYSLOG=true|false|1|0
+        if let Some(enabled) = get_env(&format!("{ENV_PREFIX}_SYSLOG")) {
+            if let Some(parsed) = parse_env_bool(&enabled) {
+                self.logging.syslog = parsed;
+            }
+        }
+
         // -----------------------------------------------------------------
         // Git awareness config (env overrides)
         // -----------------------------------------------------------------
@@ -3014,6 +4370,13 @@ impl Config {
                 self.git_awareness.warn_if_not_git = parsed;
             }
         }
+
+        // DCG_GIT_WARN_ON_PUSHED_REWRITE=true|false|1|0
+        if let Some(warn) = get_env(&format!("{ENV_PREFIX}_GIT_WARN_ON_PUSHED_REWRITE")) {
+            if let Some(parsed) = parse_env_bool(&warn) {
+                self.git_awareness.warn_on_pushed_rewrite = parsed;
+            }
+        }
     }
 
     /// Get a reference to the policy config.
@@ -3049,15 +4412,65 @@ impl Config {
     /// Get enabled pack IDs as a deduplicated set.
     #[must_use]
     pub fn enabled_pack_ids(&self) -> HashSet<String> {
-        if self.projects.is_empty() {
-            return self.packs.enabled_pack_ids();
+        let mut enabled = if self.projects.is_empty() {
+            self.packs.enabled_pack_ids()
+        } else if let Ok(cwd) = std::env::current_dir() {
+            self.effective_packs_for_project(&cwd).enabled_pack_ids()
+        } else {
+            self.packs.enabled_pack_ids()
+        };
+
+        // PowerShell mode always evaluates the PowerShell pack.
+        if self.evaluation.shell == ShellDialect::PowerShell {
+            enabled.insert("windows.powershell".to_string());
+        }
+
+        enabled
+    }
+
+    /// Check the configuration for semantic problems that parse cleanly.
+    ///
+    /// Unknown pack IDs are errors; override patterns that fail to compile
+    /// are warnings (they are skipped at evaluation time). An empty result
+    /// means the configuration is valid.
+    #[must_use]
+    pub fn validate(&self) -> Vec<ConfigDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (field, ids) in [
+            ("packs.enabled", &self.packs.enabled),
+            ("packs.disabled", &self.packs.disabled),
+        ] {
+            for id in ids.iter().filter(|id| !is_valid_pack_id(id)) {
+                diagnostics.push(ConfigDiagnostic {
+                    severity: ConfigDiagnosticSeverity::Error,
+                    field: field.to_string(),
+                    message: format!("unknown pack ID '{id}' (see 'dcg packs list')"),
+                });
+            }
+        }
+
+        for invalid in &self.overrides.compile().invalid_patterns {
+            let field = match invalid.kind {
+                PatternKind::Allow => "overrides.allow",
+                PatternKind::Block => "overrides.block",
+            };
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Warning,
+                field: field.to_string(),
+                message: format!("invalid regex '{}': {}", invalid.pattern, invalid.error),
+            });
         }
 
-        if let Ok(cwd) = std::env::current_dir() {
-            return self.effective_packs_for_project(&cwd).enabled_pack_ids();
+        if let Err(e) = self.i18n.load_catalog() {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                field: "i18n.catalog_path".to_string(),
+                message: e,
+            });
         }
 
-        self.packs.enabled_pack_ids()
+        diagnostics
     }
 
     /// Get enabled pack IDs adjusted for an agent's profile.
@@ -3108,7 +4521,13 @@ impl Config {
     /// Get effective heredoc scanning settings for evaluation.
     #[must_use]
     pub fn heredoc_settings(&self) -> HeredocSettings {
-        self.heredoc.settings()
+        let mut settings = self.heredoc.settings();
+        // Failing closed means embedded code that cannot be scanned is blocked.
+        if self.evaluation.on_error == OnErrorMode::Closed {
+            settings.fallback_on_parse_error = false;
+            settings.fallback_on_timeout = false;
+        }
+        settings
     }
 
     /// Get the path to the user config file (creates dir if needed).
@@ -3164,6 +4583,7 @@ impl Config {
     pub fn generate_default() -> Self {
         Self {
             general: GeneralConfig::default(),
+            mode: ModeConfig::default(),
             output: OutputConfig::default(),
             theme: ThemeConfig::default(),
             packs: PacksConfig {
@@ -3174,11 +4594,21 @@ impl Config {
                 ],
                 disabled: vec![],
                 custom_paths: vec![],
+                remote: None,
+                remote_ttl_secs: None,
+                enable_patterns: vec![],
             },
             policy: PolicyConfig::default(),
             overrides: OverridesConfig::default(),
             heredoc: HeredocConfig::default(),
             confidence: ConfidenceConfig::default(),
+            evaluation: EvaluationConfig::default(),
+            kubernetes: KubernetesConfig::default(),
+            system_packages: SystemPackagesConfig::default(),
+            filesystem: FilesystemConfig::default(),
+            safe_dirs: SafeDirsConfig::default(),
+            i18n: I18nConfig::default(),
+            exceptions: ExceptionsConfig::default(),
             logging: crate::logging::LoggingConfig::default(),
             history: HistoryConfig::default(),
             git_awareness: GitAwarenessConfig::default(),
@@ -3211,6 +4641,15 @@ verbose = false
 # Hook evaluation budget override (milliseconds)
 # hook_timeout_ms = 200
 
+[mode]
+# Start from a named preset; every key set in this file overrides it.
+# DCG_PROFILE=<name> selects a preset from the environment instead.
+# - "paranoid": all packs enabled, ask on medium, deny high and critical
+# - "prod": deny high and critical, warn on medium, escalate *prod* kube contexts
+# - "dev": warn below critical; /tmp, /var/tmp and ~/scratch only log
+# `dcg config` shows the active preset and what it sets.
+# preset = "prod"
+
 #─────────────────────────────────────────────────────────────
 # OUTPUT CONFIGURATION
 #─────────────────────────────────────────────────────────────
@@ -3265,13 +4704,18 @@ verbose = false
 #   cloud.aws             - AWS CLI destructive commands
 #   cloud.gcp             - GCP CLI destructive commands
 #   cloud.azure           - Azure CLI destructive commands
-#   infrastructure.terraform - Terraform destroy commands
+#   infrastructure.terraform - Terraform/Terragrunt destroy commands
 #   infrastructure.ansible   - Ansible state=absent patterns
 #   infrastructure.pulumi    - Pulumi destroy commands
 #   system.disk           - Disk operations (dd, mkfs, fdisk)
+#   system.packages       - apt/dnf/pacman removal of OS packages
 #   system.permissions    - Dangerous permission changes
 #   system.services       - Service management commands
+#   system.shell_config   - Truncating ~/.bashrc, ~/.ssh/authorized_keys, etc.
+#   system.mv             - mv onto /dev/null or into /etc, /usr/bin, etc.
+#   system.systemd        - systemctl mask/disable --now, journalctl --vacuum-*
 #   strict_git            - Extra paranoid git protections
+#   vcs.git_lfs           - git lfs prune --force, git lfs uninstall
 #   package_managers      - npm unpublish, cargo yank, etc.
 
 enabled = [
@@ -3295,6 +4739,18 @@ custom_paths = [
     # "/etc/dcg/packs/*.yaml",           # System-wide packs
 ]
 
+# Fetch packs from a central endpoint (multi-document YAML bundle).
+# The bundle is cached and refetched after remote_ttl_secs; if the fetch
+# fails, the cached copy (or just the built-in packs) is used instead.
+# remote = "https://packs.example.com/dcg.yaml"
+# remote_ttl_secs = 3600
+
+# Turn on custom-pack patterns that ship with `enabled: false`.
+# Entries are rule IDs: "<pack-id>:<pattern-name>".
+enable_patterns = [
+    # "mycompany.deploy:experimental-rule",
+]
+
 #─────────────────────────────────────────────────────────────
 # DECISION MODE POLICY
 #─────────────────────────────────────────────────────────────
@@ -3304,6 +4760,7 @@ custom_paths = [
 # - "deny": block (default)
 # - "warn": allow but print a warning to stderr (no hook JSON deny)
 # - "log": allow silently (no stderr/stdout; optional log_file history)
+# - "ask": hold the command until the user confirms it
 #
 # If unset, dcg uses severity defaults:
 # - critical/high => deny
@@ -3317,6 +4774,12 @@ custom_paths = [
 # When set and after the timestamp, `default_mode` is ignored and severity defaults apply.
 # observe_until = "2026-02-01T00:00:00Z"
 
+[policy.severity]
+# Replace the severity defaults (critical always denies).
+# high = "deny"
+# medium = "ask"
+# low = "log"
+
 [policy.packs]
 # Override mode for an entire pack (pack_id => mode).
 # Examples:
@@ -3369,17 +4832,123 @@ enabled = true
 # Extraction timeout budget (milliseconds). Parsing/matching has its own budget.
 timeout_ms = 50
 
-# Resource limits for extracted bodies (Tier 2).
-max_body_bytes = 1048576
-max_body_lines = 10000
-max_heredocs = 10
+# Resource limits for extracted bodies (Tier 2).
+max_body_bytes = 1048576
+max_body_lines = 10000
+max_heredocs = 10
+
+# Optional language filter (scan only these languages). Omit for "all".
+# languages = ["python", "bash", "javascript", "typescript", "ruby", "perl"]
+
+# Graceful degradation (hook defaults are fail-open).
+fallback_on_parse_error = true
+fallback_on_timeout = true
+
+#─────────────────────────────────────────────────────────────
+# EVALUATION
+#─────────────────────────────────────────────────────────────
+
+[evaluation]
+# Deny commands whose quotes do not balance (e.g. a dangling `"`) with
+# reason "ambiguous quoting". Heredoc bodies are not affected.
+# strict_quoting = false
+
+# What to do when dcg cannot fully analyze a command (evaluation budget
+# exceeded, oversized command, embedded code that could not be scanned).
+# "open" allows it; "closed" denies it with an explanation.
+# on_error = "open"
+
+# Shell dialect of incoming commands: "bash", "powershell", or "fish".
+# PowerShell mode expands aliases and abbreviated parameters (rm -r -fo ->
+# Remove-Item -Recurse -Force) and enables the windows.powershell pack.
+# fish mode understands "; and" / "; or" chains and (...) substitution.
+# shell = "bash"
+
+# Ask before running an unrecognized program under sudo
+# (e.g. `sudo ./random-script.sh`). Well-known system tools stay allowed;
+# add your own trusted programs to privileged_allow.
+# ask_on_privileged_unknown = false
+# privileged_allow = ["./scripts/deploy.sh"]
+
+# Look up `make <target>`, `npm run <script>`, and `just <recipe>` in the
+# Makefile, package.json, or justfile in the working directory and evaluate
+# the commands the target runs. Reads project files, so it is opt-in.
+# resolve_script_targets = false
+
+# Hard limit (ms) for evaluating one command. If it is exceeded the hook
+# answers immediately per on_error with reason "evaluation timed out".
+# total_timeout_ms = 5000
+
+#─────────────────────────────────────────────────────────────
+# KUBERNETES
+#─────────────────────────────────────────────────────────────
+
+[kubernetes]
+# kubectl commands targeting these contexts/namespaces (via --context,
+# --namespace or -n) have their severity raised one level, so a delete
+# that only warns in dev is denied in production. `*` is a wildcard.
+# prod_contexts = ["*-prod", "production"]
+# prod_namespaces = ["prod", "production"]
+
+#─────────────────────────────────────────────────────────────
+# SYSTEM PACKAGES
+#─────────────────────────────────────────────────────────────
+
+[system_packages]
+# With the system.packages pack enabled, apt/dnf/pacman removals are
+# Medium; removing one of these packages is High. `*` is a wildcard.
+# Setting this replaces the built-in list.
+# essential = ["libc6", "systemd", "coreutils", "linux-image-*"]
+
+#─────────────────────────────────────────────────────────────
+# FILESYSTEM
+#─────────────────────────────────────────────────────────────
+
+[filesystem]
+# In-place edits (sed -i, perl -i) of files matching these globs are
+# flagged as Medium severity, or Low when a backup suffix is given
+# (sed -i.bak). A glob without `/` matches the file name anywhere.
+# protected_paths = ["*.yaml", ".env*", "config/**"]
+#
+# Write/Edit tool calls whose file_path matches these globs are denied
+# (High severity). Set to [] to disable.
+# protected_write_paths = ["/etc/**", "~/.ssh/**"]   # add "*.env" for env files
+
+#─────────────────────────────────────────────────────────────
+# SAFE DIRECTORIES
+#─────────────────────────────────────────────────────────────
+
+[safe_dirs]
+# In these scratch/sandbox directories (globs, ~ expanded), denied commands
+# are downgraded to `mode` ("warn" or "log"). Critical matches, and commands
+# naming a path outside these directories, still deny.
+# paths = ["/tmp/**", "~/scratch/**"]
+# mode = "warn"
+
+#─────────────────────────────────────────────────────────────
+# I18N
+#─────────────────────────────────────────────────────────────
+
+[i18n]
+# Translate denial reasons and explanations with a TOML message catalog.
+# Entries are keyed by locale and rule id; rules missing from the catalog
+# keep the built-in English text:
+#   [ja."core.git:reset-hard"]
+#   reason = "..."
+# locale = "ja"
+# catalog_path = "~/.config/dcg/messages.toml"
 
-# Optional language filter (scan only these languages). Omit for "all".
-# languages = ["python", "bash", "javascript", "typescript", "ruby", "perl"]
+#─────────────────────────────────────────────────────────────
+# EXCEPTIONS
+#─────────────────────────────────────────────────────────────
 
-# Graceful degradation (hook defaults are fail-open).
-fallback_on_parse_error = true
-fallback_on_timeout = true
+[exceptions]
+# Require a confirmation token (printed with the denial) in addition to the
+# allow-once short code, so approvals cannot be guessed. The token is an HMAC
+# keyed by ~/.config/dcg/allow_once.key (DCG_ALLOW_ONCE_SECRET is ignored here).
+# Only user/system config can turn this off; project config and
+# DCG_REQUIRE_HMAC can only turn it on.
+# require_hmac = false
 
 #─────────────────────────────────────────────────────────────
 # HISTORY
@@ -3399,6 +4968,10 @@ max_size_mb = 500
 # Optional database path override.
 # database_path = "~/.config/dcg/history.db"
 
+# Record clean allows (commands no rule matched). Set to false to keep only
+# denied, warned, bypassed, and allowlisted commands and slow DB growth.
+log_allows = true
+
 #─────────────────────────────────────────────────────────────
 # PROJECT-SPECIFIC OVERRIDES
 #─────────────────────────────────────────────────────────────
@@ -3431,6 +5004,7 @@ fn parse_policy_mode(value: &str) -> Option<PolicyMode> {
         "deny" | "block" => Some(PolicyMode::Deny),
         "warn" | "warning" => Some(PolicyMode::Warn),
         "log" | "log-only" | "logonly" => Some(PolicyMode::Log),
+        "ask" => Some(PolicyMode::Ask),
         _ => None,
     }
 }
@@ -3544,6 +5118,9 @@ mod tests {
                 enabled: vec!["kubernetes".to_string(), "kubernetes.helm".to_string()],
                 disabled: vec!["kubernetes.helm".to_string()],
                 custom_paths: vec![],
+                remote: None,
+                remote_ttl_secs: None,
+                enable_patterns: vec![],
             },
             ..Default::default()
         };
@@ -3567,6 +5144,9 @@ mod tests {
                     enabled: vec!["database.postgresql".to_string()],
                     disabled: Vec::new(),
                     custom_paths: vec![],
+                    remote: None,
+                    remote_ttl_secs: None,
+                    enable_patterns: vec![],
                 }),
                 overrides: None,
             },
@@ -3632,6 +5212,150 @@ database_path = "/tmp/dcg-history.db"
         );
     }
 
+    #[test]
+    fn test_kubernetes_config_prod_matching() {
+        let input = r#"
+[kubernetes]
+prod_contexts = ["*-prod", "production"]
+prod_namespaces = ["prod-*"]
+"#;
+        let config: Config = toml::from_str(input).expect("config parses");
+        let k8s = &config.kubernetes;
+        assert!(k8s.is_prod_context("app-prod"));
+        assert!(k8s.is_prod_context("production"));
+        assert!(!k8s.is_prod_context("app-prod-canary"));
+        assert!(!k8s.is_prod_context("dev-cluster"));
+        assert!(k8s.is_prod_namespace("prod-payments"));
+        assert!(!k8s.is_prod_namespace("staging"));
+        assert!(!KubernetesConfig::default().is_prod_context("production"));
+    }
+
+    #[test]
+    fn is_valid_pack_id_accepts_core() {
+        assert!(is_valid_pack_id("core"));
+    }
+
+    #[test]
+    fn is_valid_pack_id_accepts_category_prefix() {
+        assert!(is_valid_pack_id("containers"));
+        assert!(is_valid_pack_id("kubernetes"));
+        assert!(is_valid_pack_id("database"));
+        assert!(is_valid_pack_id("cloud"));
+    }
+
+    #[test]
+    fn is_valid_pack_id_accepts_core_git() {
+        // core.git should be a valid pack in the registry
+        assert!(is_valid_pack_id("core.git"));
+    }
+
+    #[test]
+    fn pack_aliases_are_valid_and_disable_their_target() {
+        assert!(is_valid_pack_id("iac.terraform"));
+        assert!(is_valid_pack_id("storage.lvm"));
+        assert!(is_valid_pack_id("storage.zfs"));
+        assert!(is_valid_pack_id("cloud.gcloud"));
+        assert!(is_valid_pack_id("cloud.aws_s3"));
+        assert!(is_valid_pack_id("iac.ansible"));
+        assert!(is_valid_pack_id("vcs.git-lfs"));
+
+        let packs = PacksConfig {
+            enabled: vec!["iac.terraform".to_string()],
+            ..PacksConfig::default()
+        };
+        assert!(
+            packs
+                .enabled_pack_ids()
+                .contains("infrastructure.terraform")
+        );
+
+        let packs = PacksConfig {
+            enabled: vec!["infrastructure.terraform".to_string()],
+            disabled: vec!["iac.terraform".to_string()],
+            ..PacksConfig::default()
+        };
+        assert!(
+            !packs
+                .enabled_pack_ids()
+                .contains("infrastructure.terraform")
+        );
+    }
+
+    #[test]
+    fn vcs_forge_alias_validates_and_enables_both_platform_packs() {
+        let config: Config =
+            toml::from_str("[packs]\nenabled = [\"vcs.forge\"]\n").expect("config parses");
+        let diagnostics = config.validate();
+        assert!(
+            !diagnostics.iter().any(|d| d.field.starts_with("packs")),
+            "{diagnostics:?}"
+        );
+
+        let enabled = config.packs.enabled_pack_ids();
+        assert!(enabled.contains("platform.github"));
+        assert!(enabled.contains("platform.gitlab"));
+
+        let packs = PacksConfig {
+            enabled: vec!["platform".to_string(), "platform.github".to_string()],
+            disabled: vec!["vcs.forge".to_string()],
+            ..PacksConfig::default()
+        };
+        let enabled = packs.enabled_pack_ids();
+        assert!(!enabled.contains("platform.github"));
+        assert!(!enabled.contains("platform.gitlab"));
+    }
+
+    #[test]
+    fn is_valid_pack_id_rejects_unknown() {
+        assert!(!is_valid_pack_id("nonexistent"));
+        assert!(!is_valid_pack_id("fake.pack"));
+        assert!(!is_valid_pack_id(""));
+    }
+
+    #[test]
+    fn is_valid_pack_id_rejects_category_with_unknown_subpack() {
+        // containers is a valid category, but containers.fake is not a valid pack
+        assert!(!is_valid_pack_id("containers.fake"));
+    }
+
+    #[test]
+    fn test_validate_reports_unknown_pack_and_invalid_override() {
+        assert!(Config::default().validate().is_empty());
+
+        let input = r#"
+[packs]
+enabled = ["containers.docker", "containers.dockr"]
+
+[[overrides.block]]
+pattern = "rm (-rf"
+reason = "typo"
+"#;
+        let config: Config = toml::from_str(input).expect("config parses");
+        let diagnostics = config.validate();
+        assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].severity, ConfigDiagnosticSeverity::Error);
+        assert_eq!(diagnostics[0].field, "packs.enabled");
+        assert!(diagnostics[0].message.contains("containers.dockr"));
+        assert_eq!(diagnostics[1].severity, ConfigDiagnosticSeverity::Warning);
+        assert_eq!(diagnostics[1].field, "overrides.block");
+    }
+
+    #[test]
+    fn test_system_packages_essential_matching() {
+        let defaults = SystemPackagesConfig::default();
+        assert!(defaults.is_essential("libc6"));
+        assert!(defaults.is_essential("linux-image-6.1.0-18-amd64"));
+        assert!(!defaults.is_essential("nginx"));
+
+        let input = r#"
+[system_packages]
+essential = ["nginx"]
+"#;
+        let config: Config = toml::from_str(input).expect("config parses");
+        assert!(config.system_packages.is_essential("nginx"));
+        assert!(!config.system_packages.is_essential("libc6"));
+    }
+
     #[test]
     fn test_history_redaction_mode_parsing() {
         assert_eq!(
@@ -4014,7 +5738,7 @@ enabled = ["database.postgresql"]
         // Test that output config toggles do NOT affect the evaluator's allow decision.
         // This is a critical invariant: output settings are purely cosmetic.
         use crate::allowlist::LayeredAllowlist;
-        use crate::evaluator::{EvaluationDecision, evaluate_command};
+        use crate::evaluator::{Decision, evaluate_command};
         use crate::packs::REGISTRY;
 
         // Safe command: "ls -la" should always be allowed
@@ -4036,7 +5760,7 @@ enabled = ["database.postgresql"]
             &allowlists,
         );
         assert!(
-            matches!(result_default.decision, EvaluationDecision::Allow),
+            matches!(result_default.decision, Decision::Allow),
             "Safe command should be allowed with default config"
         );
 
@@ -4054,7 +5778,7 @@ enabled = ["database.postgresql"]
             &allowlists,
         );
         assert!(
-            matches!(result_disabled.decision, EvaluationDecision::Allow),
+            matches!(result_disabled.decision, Decision::Allow),
             "Safe command should still be allowed with disabled output toggles"
         );
 
@@ -4070,7 +5794,7 @@ enabled = ["database.postgresql"]
     fn test_output_config_does_not_affect_deny_decision() {
         // Test that output config toggles do NOT affect the evaluator's deny decision.
         use crate::allowlist::LayeredAllowlist;
-        use crate::evaluator::{EvaluationDecision, evaluate_command};
+        use crate::evaluator::{Decision, evaluate_command};
         use crate::packs::REGISTRY;
 
         // Dangerous command: "git reset --hard HEAD" should always be denied
@@ -4092,7 +5816,7 @@ enabled = ["database.postgresql"]
             &allowlists,
         );
         assert!(
-            matches!(result_default.decision, EvaluationDecision::Deny),
+            matches!(result_default.decision, Decision::Deny),
             "Destructive command should be denied with default config"
         );
 
@@ -4110,7 +5834,7 @@ enabled = ["database.postgresql"]
             &allowlists,
         );
         assert!(
-            matches!(result_disabled.decision, EvaluationDecision::Deny),
+            matches!(result_disabled.decision, Decision::Deny),
             "Destructive command should still be denied with disabled output toggles"
         );
 
@@ -4133,14 +5857,14 @@ enabled = ["database.postgresql"]
     fn test_output_config_toggles_are_purely_cosmetic() {
         // Comprehensive test: verify output toggles have zero effect on evaluation
         use crate::allowlist::LayeredAllowlist;
-        use crate::evaluator::{EvaluationDecision, evaluate_command};
+        use crate::evaluator::{Decision, evaluate_command};
         use crate::packs::REGISTRY;
 
         let test_cases = [
-            ("echo hello", EvaluationDecision::Allow),      // Safe
-            ("git status", EvaluationDecision::Allow),      // Safe git command
-            ("git reset --hard", EvaluationDecision::Deny), // Destructive
-            ("rm -rf /", EvaluationDecision::Deny),         // Destructive
+            ("echo hello", Decision::Allow),      // Safe
+            ("git status", Decision::Allow),      // Safe git command
+            ("git reset --hard", Decision::Deny), // Destructive
+            ("rm -rf /", Decision::Deny),         // Destructive
         ];
 
         let toggle_combinations = [
@@ -4311,6 +6035,296 @@ allow = false
         assert!(settings.fallback_on_timeout);
     }
 
+    #[test]
+    fn test_on_error_closed_disables_heredoc_fallbacks() {
+        let env_map: std::collections::HashMap<&str, &str> =
+            std::collections::HashMap::from([("DCG_ON_ERROR", "closed")]);
+        let mut config = Config::default();
+        assert_eq!(config.evaluation.on_error, OnErrorMode::Open);
+        config.apply_env_overrides_from(|key| env_map.get(key).map(|v| (*v).to_string()));
+
+        assert_eq!(config.evaluation.on_error, OnErrorMode::Closed);
+        let settings = config.heredoc_settings();
+        assert!(!settings.fallback_on_parse_error);
+        assert!(!settings.fallback_on_timeout);
+    }
+
+    #[test]
+    fn test_shell_powershell_enables_powershell_pack() {
+        let mut config = Config::default();
+        assert_eq!(config.evaluation.shell, ShellDialect::Bash);
+        assert!(!config.enabled_pack_ids().contains("windows.powershell"));
+
+        let env_map: std::collections::HashMap<&str, &str> =
+            std::collections::HashMap::from([("DCG_SHELL", "pwsh")]);
+        config.apply_env_overrides_from(|key| env_map.get(key).map(|v| (*v).to_string()));
+        assert_eq!(config.evaluation.shell, ShellDialect::PowerShell);
+        assert!(config.enabled_pack_ids().contains("windows.powershell"));
+    }
+
+    #[test]
+    fn test_shell_fish_from_toml_and_env() {
+        let config: Config = toml::from_str("[evaluation]\nshell = \"fish\"\n").unwrap();
+        assert_eq!(config.evaluation.shell, ShellDialect::Fish);
+        assert!(!config.enabled_pack_ids().contains("windows.powershell"));
+
+        let mut config = Config::default();
+        let env_map: std::collections::HashMap<&str, &str> =
+            std::collections::HashMap::from([("DCG_SHELL", "fish")]);
+        config.apply_env_overrides_from(|key| env_map.get(key).map(|v| (*v).to_string()));
+        assert_eq!(config.evaluation.shell, ShellDialect::Fish);
+        assert_eq!(config.evaluation.shell.label(), "fish");
+    }
+
+    #[test]
+    fn test_safe_dirs_downgrade_deny_outside_critical() {
+        use crate::packs::{DecisionMode, Severity};
+
+        let mut safe_dirs = SafeDirsConfig {
+            paths: vec!["/tmp/**".to_string()],
+            mode: None,
+        };
+        let high = Some(Severity::High);
+
+        assert_eq!(
+            safe_dirs.matching_pattern(Path::new("/tmp")),
+            Some("/tmp/**")
+        );
+        let rm = "rm -rf build";
+        assert_eq!(
+            safe_dirs.apply(DecisionMode::Deny, high, Some(Path::new("/tmp/exp/a")), rm),
+            DecisionMode::Warn
+        );
+        assert_eq!(
+            safe_dirs.apply(
+                DecisionMode::Deny,
+                high,
+                Some(Path::new("/home/me/app")),
+                rm
+            ),
+            DecisionMode::Deny
+        );
+        assert_eq!(
+            safe_dirs.apply(DecisionMode::Deny, high, Some(Path::new("/tmpfoo")), rm),
+            DecisionMode::Deny
+        );
+        assert_eq!(
+            safe_dirs.apply(
+                DecisionMode::Deny,
+                Some(Severity::Critical),
+                Some(Path::new("/tmp/exp")),
+                rm
+            ),
+            DecisionMode::Deny
+        );
+
+        safe_dirs.mode = Some(PolicyMode::Log);
+        assert_eq!(
+            safe_dirs.apply(DecisionMode::Deny, high, Some(Path::new("/tmp/exp")), rm),
+            DecisionMode::Log
+        );
+    }
+
+    #[test]
+    fn test_safe_dirs_keep_deny_for_targets_outside() {
+        use crate::packs::{DecisionMode, Severity};
+
+        let safe_dirs = SafeDirsConfig {
+            paths: vec!["/tmp/**".to_string()],
+            mode: None,
+        };
+        let apply = |command: &str| {
+            safe_dirs.apply(
+                DecisionMode::Deny,
+                Some(Severity::High),
+                Some(Path::new("/tmp/exp")),
+                command,
+            )
+        };
+
+        assert_eq!(apply("rm -rf /tmp/exp/out ./cache"), DecisionMode::Warn);
+        assert_eq!(apply("rm -rf ../other"), DecisionMode::Warn);
+        assert_eq!(apply("rm -rf /home/me/src"), DecisionMode::Deny);
+        assert_eq!(apply("rm -rf ~/src"), DecisionMode::Deny);
+        assert_eq!(apply("rm -rf ../../home/me"), DecisionMode::Deny);
+        assert_eq!(apply("rm -rf \"$HOME\"/src"), DecisionMode::Deny);
+        assert_eq!(
+            apply("git clean -fdx --exclude=/srv/keep"),
+            DecisionMode::Deny
+        );
+        // Shell expansion cannot be resolved, so the deny stands.
+        assert_eq!(apply("rm -rf {~/src,x}"), DecisionMode::Deny);
+        assert_eq!(apply("rm -rf $(pwd)/../../etc"), DecisionMode::Deny);
+        assert_eq!(apply("rm -rf `cat dirs.txt`"), DecisionMode::Deny);
+        assert_eq!(apply("rm -rf ${TARGET}"), DecisionMode::Deny);
+        assert_eq!(apply("rm -rf build/*"), DecisionMode::Deny);
+        // Quoted scripts and redirections are checked too.
+        assert_eq!(apply("bash -c \"rm -rf ~/src\""), DecisionMode::Deny);
+        assert_eq!(apply("bash -c 'rm -rf ./out'"), DecisionMode::Warn);
+        assert_eq!(apply("rm -rf out 2>/etc/log"), DecisionMode::Deny);
+        assert_eq!(apply("rm -rf out 2>&1"), DecisionMode::Warn);
+    }
+
+    #[test]
+    fn test_filesystem_protected_paths_match_name_or_path() {
+        let mut config = Config::default();
+        config.filesystem.protected_paths = vec!["*.yaml".to_string(), "config/**".to_string()];
+
+        assert!(config.filesystem.is_protected("app.yaml"));
+        assert!(config.filesystem.is_protected("deploy/k8s/app.yaml"));
+        assert!(config.filesystem.is_protected("./config/settings.toml"));
+        assert!(!config.filesystem.is_protected("src/config.rs"));
+        assert!(!config.filesystem.is_protected("README.md"));
+    }
+
+    #[test]
+    fn test_filesystem_protected_write_paths_defaults() {
+        let config = Config::default();
+
+        assert_eq!(
+            config.filesystem.protected_write_match("/etc/passwd", None),
+            Some("/etc/**")
+        );
+        // Environment files are opt-in.
+        assert_eq!(
+            config
+                .filesystem
+                .protected_write_match("/srv/app/prod.env", None),
+            None
+        );
+        if let Some(home) = dirs::home_dir() {
+            let key = home.join(".ssh/authorized_keys");
+            assert_eq!(
+                config
+                    .filesystem
+                    .protected_write_match(&key.to_string_lossy(), None),
+                Some("~/.ssh/**")
+            );
+        }
+        assert_eq!(
+            config
+                .filesystem
+                .protected_write_match("/srv/app/main.rs", None),
+            None
+        );
+        assert_eq!(
+            config
+                .filesystem
+                .protected_write_match("/etcetera/file", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_filesystem_protected_write_paths_normalize_and_resolve() {
+        let mut config = Config::default();
+        let matches = |config: &Config, path: &str, cwd: Option<&str>| {
+            config
+                .filesystem
+                .protected_write_match(path, cwd.map(Path::new))
+                .map(str::to_string)
+        };
+        for path in [
+            "/tmp/../etc/passwd",
+            "//etc/passwd",
+            "/etc/./ssh//sshd_config",
+        ] {
+            assert_eq!(
+                matches(&config, path, None).as_deref(),
+                Some("/etc/**"),
+                "{path}"
+            );
+        }
+        assert_eq!(
+            matches(&config, "../../etc/hosts", Some("/srv/app")).as_deref(),
+            Some("/etc/**")
+        );
+        assert_eq!(
+            matches(&config, "passwd", Some("/etc")).as_deref(),
+            Some("/etc/**")
+        );
+        assert_eq!(matches(&config, "etc/passwd", Some("/srv/app")), None);
+
+        config.filesystem.protected_write_paths =
+            vec!["*.env".to_string(), "config/**".to_string()];
+        assert_eq!(
+            matches(&config, "/srv/app/.env", None).as_deref(),
+            Some("*.env")
+        );
+        assert_eq!(
+            matches(&config, "./config/../config/prod.toml", Some("/srv/app")).as_deref(),
+            Some("config/**")
+        );
+    }
+
+    #[test]
+    fn test_i18n_layer_and_env_overrides() {
+        let mut config = Config::default();
+        assert!(config.i18n.load_catalog().expect("no catalog").is_none());
+
+        let layer: ConfigLayer =
+            toml::from_str("[i18n]\nlocale = \"ja\"\ncatalog_path = \"/etc/dcg/messages.toml\"\n")
+                .expect("layer parses");
+        config.merge_layer(layer);
+        assert_eq!(config.i18n.locale.as_deref(), Some("ja"));
+
+        let env_map: std::collections::HashMap<&str, &str> =
+            std::collections::HashMap::from([("DCG_LOCALE", "de")]);
+        config.apply_env_overrides_from(|key| env_map.get(key).map(|v| (*v).to_string()));
+        assert_eq!(config.i18n.locale.as_deref(), Some("de"));
+        assert_eq!(
+            config.i18n.catalog_path.as_deref(),
+            Some("/etc/dcg/messages.toml")
+        );
+    }
+
+    #[test]
+    fn test_broken_catalog_is_reported_by_validate_and_reloaded_when_edited() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("messages.toml");
+        std::fs::write(&path, "not = [valid").expect("write catalog");
+
+        let mut config = Config::default();
+        config.i18n.locale = Some("de".to_string());
+        config.i18n.catalog_path = Some(path.to_string_lossy().into_owned());
+        let diagnostics = config.validate();
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.field == "i18n.catalog_path"
+                    && d.severity == ConfigDiagnosticSeverity::Error),
+            "{diagnostics:?}"
+        );
+
+        // The failed load is cached while the file's modification time is unchanged.
+        let modified = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .expect("mtime");
+        let set_modified = |time: std::time::SystemTime| {
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(time))
+                .expect("set mtime");
+        };
+        std::fs::write(
+            &path,
+            "[de.\"core.git:reset-hard\"]\nreason = \"Verwirft\"\n",
+        )
+        .expect("rewrite catalog");
+        set_modified(modified);
+        assert!(config.i18n.load_catalog().is_err());
+
+        // An edited catalog is loaded again.
+        set_modified(modified + std::time::Duration::from_secs(5));
+        let catalog = config
+            .i18n
+            .load_catalog()
+            .expect("catalog loads")
+            .expect("catalog configured");
+        assert_eq!(catalog.len(), 1);
+    }
+
     #[test]
     fn test_heredoc_env_overrides_enabled_timeout_languages() {
         let env_map: std::collections::HashMap<&str, &str> = std::collections::HashMap::from([
@@ -4380,6 +6394,28 @@ allow = false
         assert_eq!(config.general.hook_timeout_ms, Some(150));
     }
 
+    #[test]
+    fn test_total_timeout_defaults_and_overrides() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.evaluation.total_timeout(),
+            Duration::from_millis(DEFAULT_TOTAL_TIMEOUT_MS)
+        );
+
+        let env_map: std::collections::HashMap<&str, &str> =
+            std::collections::HashMap::from([("DCG_TOTAL_TIMEOUT_MS", "250")]);
+        config.apply_env_overrides_from(|key| env_map.get(key).map(|v| (*v).to_string()));
+        assert_eq!(
+            config.evaluation.total_timeout(),
+            Duration::from_millis(250)
+        );
+
+        let layer: ConfigLayer =
+            toml::from_str("[evaluation]\ntotal_timeout_ms = 1200\n").expect("parse");
+        config.merge_layer(layer);
+        assert_eq!(config.evaluation.total_timeout_ms, Some(1200));
+    }
+
     #[test]
     fn test_heredoc_language_filter_all_is_treated_as_unfiltered() {
         let mut config = Config::default();
@@ -4408,6 +6444,59 @@ allow = false
         assert_eq!(found, repo_root);
     }
 
+    #[test]
+    fn test_include_merges_base_then_overrides() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("env")).expect("create env dir");
+        std::fs::write(
+            dir.join("base.toml"),
+            "[general]\nverbose = true\nhook_timeout_ms = 100\n\n[packs]\nenabled = [\"database.postgresql\"]\n",
+        )
+        .expect("write base");
+        std::fs::write(
+            dir.join("env/prod.toml"),
+            "[general]\nhook_timeout_ms = 500\n\n[packs]\nenabled = [\"kubernetes.kubectl\"]\n",
+        )
+        .expect("write override");
+        let main = dir.join("config.toml");
+        std::fs::write(
+            &main,
+            "include = [\"base.toml\", \"env/prod.toml\"]\n\n[general]\nverbose = false\n",
+        )
+        .expect("write main");
+
+        let mut config = Config::default();
+        config.merge_layers(Config::load_layers_from_file(&main).expect("layers load"));
+
+        assert!(!config.general.verbose);
+        assert_eq!(config.general.hook_timeout_ms, Some(500));
+        assert_eq!(
+            config.packs.enabled,
+            vec!["database.postgresql", "kubernetes.kubectl"]
+        );
+
+        let chain = config_include_chain(&main).expect("chain");
+        let names: Vec<_> = chain
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["base.toml", "prod.toml", "config.toml"]);
+    }
+
+    #[test]
+    fn test_include_rejects_cycles() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let a = temp.path().join("a.toml");
+        std::fs::write(&a, "include = [\"b.toml\"]\n").expect("write a");
+        std::fs::write(temp.path().join("b.toml"), "include = [\"a.toml\"]\n").expect("write b");
+
+        let err = config_include_chain(&a).expect_err("cycle is rejected");
+        assert!(err.starts_with("Circular config include:"), "{err}");
+        assert!(err.contains("b.toml"), "{err}");
+        assert!(Config::load_layers_from_file(&a).is_none());
+    }
+
     #[test]
     fn test_find_repo_root_respects_hop_limit() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -4978,6 +7067,7 @@ allow = false
         let policy = PolicyConfig {
             default_mode: Some(PolicyMode::Deny),
             observe_until: None,
+            severity: SeverityPolicy::default(),
             packs: std::collections::HashMap::from([("core.git".to_string(), PolicyMode::Warn)]),
             rules: std::collections::HashMap::from([(
                 "core.git:reset-hard".to_string(),
@@ -5148,6 +7238,7 @@ allow = false
             ("log", Some(PolicyMode::Log)),
             ("log-only", Some(PolicyMode::Log)),
             ("logonly", Some(PolicyMode::Log)),
+            ("ask", Some(PolicyMode::Ask)),
             ("DENY", Some(PolicyMode::Deny)), // case-insensitive
             ("invalid", None),
         ] {
@@ -5156,6 +7247,136 @@ allow = false
         }
     }
 
+    #[test]
+    fn test_require_hmac_cannot_be_loosened_by_project_or_env() {
+        let layer = |file: &str| toml::from_str::<ConfigLayer>(file).expect("layer parses");
+        let user = layer("[exceptions]\nrequire_hmac = true\n");
+        let project = layer("[exceptions]\nrequire_hmac = false\n").tighten_only();
+        let no_env = |_: &str| None;
+
+        let config = Config::from_layers_with_env(vec![user.clone(), project], no_env);
+        assert!(config.exceptions.require_hmac);
+
+        let env_map = std::collections::HashMap::from([("DCG_REQUIRE_HMAC", "0")]);
+        let config = Config::from_layers_with_env(vec![user], |key| {
+            env_map.get(key).map(|v| (*v).to_string())
+        });
+        assert!(config.exceptions.require_hmac);
+
+        // Tightening still works from every layer.
+        let project = layer("[exceptions]\nrequire_hmac = true\n").tighten_only();
+        assert!(
+            Config::from_layers_with_env(vec![project], no_env)
+                .exceptions
+                .require_hmac
+        );
+        let env_map = std::collections::HashMap::from([("DCG_REQUIRE_HMAC", "1")]);
+        let config = Config::from_layers_with_env(Vec::new(), |key| {
+            env_map.get(key).map(|v| (*v).to_string())
+        });
+        assert!(config.exceptions.require_hmac);
+    }
+
+    #[test]
+    fn test_load_with_overlay_merges_on_top_of_file_layers() {
+        let config = Config::load_with_overlay("[packs]\nenabled = [\"messaging.sqs_sns\"]\n")
+            .expect("overlay parses");
+        assert!(config.enabled_pack_ids().contains("messaging.sqs_sns"));
+
+        assert!(Config::load_with_overlay("[packs\n").is_err());
+        assert!(Config::load_with_overlay("include = [\"other.toml\"]\n").is_err());
+    }
+
+    fn preset_config(preset: &str, file: &str) -> Config {
+        let layers = vec![toml::from_str::<ConfigLayer>(file).expect("layer parses")];
+        let env_map = std::collections::HashMap::from([("DCG_PROFILE", preset)]);
+        Config::from_layers_with_env(layers, |key| env_map.get(key).map(|v| (*v).to_string()))
+    }
+
+    #[test]
+    fn test_presets_set_expected_thresholds() {
+        use crate::packs::{DecisionMode, Severity};
+
+        let modes = |config: &Config| {
+            [
+                Severity::Critical,
+                Severity::High,
+                Severity::Medium,
+                Severity::Low,
+            ]
+            .map(|severity| {
+                config
+                    .policy
+                    .resolve_mode(Some("core.git"), Some("x"), Some(severity))
+            })
+        };
+
+        let paranoid = preset_config("paranoid", "");
+        assert_eq!(paranoid.mode.preset, Some(ConfigPreset::Paranoid));
+        assert_eq!(
+            modes(&paranoid),
+            [
+                DecisionMode::Deny,
+                DecisionMode::Deny,
+                DecisionMode::Ask,
+                DecisionMode::Log
+            ]
+        );
+        let categories: HashSet<String> = crate::packs::REGISTRY
+            .all_pack_ids()
+            .into_iter()
+            .map(|id| id.split('.').next().unwrap_or_default().to_string())
+            .collect();
+        for category in categories {
+            assert!(
+                category == "core" || paranoid.packs.enabled.contains(&category),
+                "paranoid preset must enable {category}"
+            );
+        }
+
+        let prod = preset_config("prod", "");
+        assert_eq!(
+            modes(&prod),
+            [
+                DecisionMode::Deny,
+                DecisionMode::Deny,
+                DecisionMode::Warn,
+                DecisionMode::Log
+            ]
+        );
+        assert!(prod.kubernetes.is_prod_context("gke-prod-eu"));
+        assert!(prod.kubernetes.is_prod_namespace("production"));
+
+        let dev = preset_config("dev", "");
+        assert_eq!(
+            modes(&dev),
+            [
+                DecisionMode::Deny,
+                DecisionMode::Warn,
+                DecisionMode::Warn,
+                DecisionMode::Log
+            ]
+        );
+        assert_eq!(dev.safe_dirs.mode, Some(PolicyMode::Log));
+        assert!(dev.safe_dirs.paths.iter().any(|p| p == "/tmp/**"));
+    }
+
+    #[test]
+    fn test_explicit_config_overrides_preset() {
+        let config = preset_config(
+            "",
+            "[mode]\npreset = \"dev\"\n[policy.severity]\nhigh = \"deny\"\n",
+        );
+        assert_eq!(config.mode.preset, Some(ConfigPreset::Dev));
+        assert_eq!(config.policy.severity.high, Some(PolicyMode::Deny));
+        assert_eq!(config.policy.severity.medium, Some(PolicyMode::Warn));
+
+        // DCG_PROFILE wins over the file's preset.
+        let config = preset_config("prod", "[mode]\npreset = \"dev\"\n");
+        assert_eq!(config.mode.preset, Some(ConfigPreset::Prod));
+        assert!(config.safe_dirs.paths.is_empty());
+    }
+
     #[test]
     fn test_policy_config_merge() {
         let mut base = Config::default();
@@ -5169,6 +7390,7 @@ allow = false
             policy: Some(PolicyConfig {
                 default_mode: Some(PolicyMode::Warn),
                 observe_until: ObserveUntil::parse("2030-01-01T00:00:00Z"),
+                severity: SeverityPolicy::default(),
                 packs: std::collections::HashMap::from([(
                     "containers.docker".to_string(),
                     PolicyMode::Log,
@@ -86,6 +86,20 @@ impl SpanKind {
     pub const fn is_executable(self) -> bool {
         matches!(self, Self::Executed | Self::InlineCode | Self::Unknown)
     }
+
+    /// Stable snake_case label (used in trace bundles).
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Executed => "executed",
+            Self::Argument => "argument",
+            Self::InlineCode => "inline_code",
+            Self::Data => "data",
+            Self::HeredocBody => "heredoc_body",
+            Self::Unknown => "unknown",
+            Self::Comment => "comment",
+        }
+    }
 }
 
 /// A classified span within a command string.
@@ -381,6 +395,8 @@ impl ContextClassifier {
                             span_start = i;
                             stack.push(TokenizerState::Backtick);
                         }
+                        // `>|` (noclobber override) is a redirection, not a pipe.
+                        b'|' if i > 0 && bytes[i - 1] == b'>' => {}
                         b'|' | b';' | b'&' => {
                             // Check for operators
                             // For simple classification, treat as break.
@@ -1001,6 +1017,42 @@ fn is_piped_segment(command: &str, tokens: &[SanitizeToken], current_idx: usize)
     false
 }
 
+/// Find an unquoted redirection operator (`>`, `>>`, `<`, ...) in a word.
+///
+/// Returns the offset of the operator, including a leading fd number or `&`
+/// when the word starts with one (`2>`, `&>`), and whether the operator ends
+/// the word, in which case the redirection target is the next word.
+fn find_redirection(word: &str) -> Option<(usize, bool)> {
+    let bytes = word.as_bytes();
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(b'"') if b == b'\\' => i += 1,
+            Some(_) => {}
+            None if b == b'\\' => i += 1,
+            None if b == b'\'' || b == b'"' => quote = Some(b),
+            None if b == b'>' || b == b'<' => {
+                let prefix = &word[..i];
+                let start = if prefix == "&" || prefix.bytes().all(|c| c.is_ascii_digit()) {
+                    0
+                } else {
+                    i
+                };
+                let ends_word = word[i..]
+                    .bytes()
+                    .all(|c| matches!(c, b'>' | b'<' | b'|' | b'&'));
+                return Some((start, ends_word));
+            }
+            None => {}
+        }
+        i += 1;
+    }
+    None
+}
+
 #[derive(Clone, Copy)]
 struct PendingSafeFlag<'a> {
     flag: &'a str,
@@ -1052,9 +1104,11 @@ pub fn sanitize_for_pattern_matching(command: &str) -> Cow<'_, str> {
     let mut git_subcommand: Option<&str> = None;
     let mut git_waiting_for_value = false;
     let mut git_options_ended = false;
+    let mut redirect_target_pending = false;
 
     for (i, token) in tokens.iter().enumerate() {
         if token.kind == SanitizeTokenKind::Separator {
+            redirect_target_pending = false;
             segment_cmd = None;
             segment_cmd_is_all_args_data = false;
             pending_safe_flag = None;
@@ -1179,8 +1233,18 @@ pub fn sanitize_for_pattern_matching(command: &str) -> Cow<'_, str> {
 
         if segment_cmd_is_all_args_data {
             // For commands like echo/printf, treat all args as data, but never strip inline code.
-            if !token.has_inline_code {
-                mask_ranges.push(token.byte_range.clone());
+            // Redirections are not arguments: keep the operator and its target visible so
+            // packs can see what is being written (`echo x > ~/.bashrc`).
+            if std::mem::take(&mut redirect_target_pending) {
+                continue;
+            }
+            let mut data_range = token.byte_range.clone();
+            if let Some((offset, ends_word)) = find_redirection(token_text) {
+                data_range.end = data_range.start + offset;
+                redirect_target_pending = ends_word;
+            }
+            if !token.has_inline_code && !data_range.is_empty() {
+                mask_ranges.push(data_range);
             }
             continue;
         }
@@ -1847,6 +1911,12 @@ fn consume_word_token(command: &str, bytes: &[u8], mut i: usize, len: usize) ->
             break;
         }
 
+        // `>|` (noclobber override) is a redirection, not a pipe.
+        if b == b'|' && i > 0 && bytes[i - 1] == b'>' {
+            i += 1;
+            continue;
+        }
+
         if matches!(b, b'|' | b';' | b'&') {
             break;
         }
@@ -2829,6 +2899,27 @@ mod tests {
         assert!(sanitized.as_ref().contains("--description="));
     }
 
+    #[test]
+    fn sanitize_keeps_echo_redirection_targets() {
+        for (cmd, kept) in [
+            ("echo 'rm -rf /' > ~/.bashrc", "> ~/.bashrc"),
+            ("echo rm -rf />~/.bashrc", ">~/.bashrc"),
+            ("printf 'x' 2>> /tmp/err.log", "2>> /tmp/err.log"),
+            ("echo x &> out.txt", "&> out.txt"),
+        ] {
+            let sanitized = sanitize_for_pattern_matching(cmd);
+            assert!(sanitized.as_ref().contains(kept), "{cmd} -> {sanitized}");
+            assert!(
+                !sanitized.as_ref().contains("rm -rf"),
+                "{cmd} -> {sanitized}"
+            );
+        }
+
+        // A quoted `>` is data, not a redirection.
+        let sanitized = sanitize_for_pattern_matching("echo 'a > ~/.bashrc'");
+        assert!(!sanitized.as_ref().contains(".bashrc"));
+    }
+
     #[test]
     fn sanitize_strips_bd_notes_unquoted_multiword() {
         let cmd = "bd create --notes This references git reset hard";
@@ -19,7 +19,7 @@
 //!
 //! ```ignore
 //! use destructive_command_guard::config::Config;
-//! use destructive_command_guard::evaluator::{evaluate_command, EvaluationDecision};
+//! use destructive_command_guard::evaluator::{evaluate_command, Decision};
 //!
 //! let config = Config::load();
 //! let compiled_overrides = config.overrides.compile();
@@ -34,8 +34,8 @@
 //! );
 //!
 //! match result.decision {
-//!     EvaluationDecision::Allow => println!("Command allowed"),
-//!     EvaluationDecision::Deny => {
+//!     Decision::Allow => println!("Command allowed"),
+//!     Decision::Deny => {
 //!         if let Some(info) = &result.pattern_info {
 //!             println!("Blocked by {}: {}", info.pack_id.as_deref().unwrap_or("legacy"), info.reason);
 //!         }
@@ -45,12 +45,19 @@
 
 use crate::allowlist::{AllowlistLayer, LayeredAllowlist};
 use crate::ast_matcher::DEFAULT_MATCHER;
-use crate::config::Config;
+use crate::config::{
+    Config, EvaluationConfig, FilesystemConfig, GitAwarenessConfig, KubernetesConfig, OnErrorMode,
+    SafeDirsConfig, ShellDialect, SystemPackagesConfig,
+};
 use crate::context::sanitize_for_pattern_matching;
 use crate::heredoc::{
     ExtractionResult, SkipReason, TriggerResult, check_triggers, extract_content,
 };
-use crate::normalize::{PATH_NORMALIZER, QUOTED_PATH_NORMALIZER, strip_wrapper_prefixes};
+use crate::i18n::MessageCatalog;
+use crate::normalize::{
+    NormalizeTokenKind, PATH_NORMALIZER, QUOTED_PATH_NORMALIZER, has_unbalanced_quotes,
+    strip_wrapper_prefixes,
+};
 use crate::packs::{
     PatternSuggestion, REGISTRY, pack_aware_quick_reject, pack_aware_quick_reject_with_normalized,
 };
@@ -386,14 +393,52 @@ fn map_span_with_offset(
 }
 
 /// The decision made by the evaluator.
+///
+/// `Warn` lets the command run with a warning; `Ask` holds it until the user
+/// confirms, so it counts as denied for [`EvaluationResult::is_denied`].
+/// Pack matches are evaluated as `Deny` and become `Warn` at the end of
+/// [`post_process`] when their severity defaults to warn mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum EvaluationDecision {
+pub enum Decision {
     /// Command is allowed to execute.
     Allow,
+    /// Command is allowed to execute, with a warning.
+    Warn,
+    /// Command needs user confirmation before executing.
+    Ask,
     /// Command is blocked from executing.
     Deny,
 }
 
+impl Decision {
+    /// Lowercase label used in JSON and logs (`allow`, `warn`, `ask`, `deny`).
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Ask => "ask",
+            Self::Deny => "deny",
+        }
+    }
+
+    /// Whether the command may run without user intervention.
+    #[must_use]
+    pub const fn is_allowed(self) -> bool {
+        matches!(self, Self::Allow | Self::Warn)
+    }
+
+    /// Whether the command is held back (blocked or awaiting confirmation).
+    #[must_use]
+    pub const fn is_denied(self) -> bool {
+        !self.is_allowed()
+    }
+}
+
+/// Former name of [`Decision`], kept so existing embedders keep compiling.
+#[deprecated(note = "use `Decision`")]
+pub type EvaluationDecision = Decision;
+
 /// Byte span of a match within the evaluated command string.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MatchSpan {
@@ -474,9 +519,9 @@ pub struct BranchContext {
 /// Result of evaluating a command.
 #[derive(Debug, Clone)]
 pub struct EvaluationResult {
-    /// The decision (Allow or Deny).
-    pub decision: EvaluationDecision,
-    /// Pattern match information (present when decision is Deny or Warn).
+    /// The decision (Allow, Warn, Ask or Deny).
+    pub decision: Decision,
+    /// Pattern match information (present when decision is Warn, Ask or Deny).
     pub pattern_info: Option<PatternMatch>,
     /// Allowlist override information (present when decision is Allow due to allowlist).
     pub allowlist_override: Option<AllowlistOverride>,
@@ -498,7 +543,7 @@ impl EvaluationResult {
     #[must_use]
     pub const fn allowed() -> Self {
         Self {
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             pattern_info: None,
             allowlist_override: None,
             effective_mode: None,
@@ -512,7 +557,7 @@ impl EvaluationResult {
     #[must_use]
     pub const fn allowed_due_to_budget() -> Self {
         Self {
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             pattern_info: None,
             allowlist_override: None,
             effective_mode: None,
@@ -526,7 +571,7 @@ impl EvaluationResult {
     #[must_use]
     pub const fn denied_by_config(reason: String) -> Self {
         Self {
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             pattern_info: Some(PatternMatch {
                 pack_id: None,
                 pattern_name: None,
@@ -545,12 +590,46 @@ impl EvaluationResult {
         }
     }
 
+    /// Create an "ask" result from a config setting: the command is held until
+    /// the user confirms it.
+    #[must_use]
+    pub const fn ask_by_config(reason: String) -> Self {
+        Self {
+            decision: Decision::Ask,
+            pattern_info: Some(PatternMatch {
+                pack_id: None,
+                pattern_name: None,
+                severity: None,
+                reason,
+                source: MatchSource::ConfigOverride,
+                matched_span: None,
+                matched_text_preview: None,
+                explanation: None,
+                suggestions: &[],
+            }),
+            allowlist_override: None,
+            effective_mode: None,
+            skipped_due_to_budget: false,
+            branch_context: None,
+        }
+    }
+
+    /// Create a "denied" result for a command dcg could not fully analyze
+    /// (`[evaluation] on_error = "closed"`).
+    #[must_use]
+    pub fn denied_on_error(cause: &str) -> Self {
+        Self::denied_by_config(format!(
+            "dcg could not fully analyze this command ({cause}); denied because \
+             [evaluation] on_error = \"closed\""
+        ))
+    }
+
     /// Create a "denied" result from legacy pattern.
     #[inline]
     #[must_use]
     pub fn denied_by_legacy(reason: &str) -> Self {
         Self {
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             pattern_info: Some(PatternMatch {
                 pack_id: None,
                 pattern_name: None,
@@ -575,7 +654,7 @@ impl EvaluationResult {
     pub fn denied_by_legacy_with_span(reason: &str, command: &str, span: MatchSpan) -> Self {
         let preview = extract_match_preview(command, &span);
         Self {
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             pattern_info: Some(PatternMatch {
                 pack_id: None,
                 pattern_name: None,
@@ -599,7 +678,7 @@ impl EvaluationResult {
     #[must_use]
     pub fn denied_by_pack(pack_id: &str, reason: &str, explanation: Option<&str>) -> Self {
         Self {
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             pattern_info: Some(PatternMatch {
                 pack_id: Some(pack_id.to_string()),
                 pattern_name: None,
@@ -630,7 +709,7 @@ impl EvaluationResult {
     ) -> Self {
         let preview = extract_match_preview(command, &span);
         Self {
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             pattern_info: Some(PatternMatch {
                 pack_id: Some(pack_id.to_string()),
                 pattern_name: None,
@@ -661,7 +740,7 @@ impl EvaluationResult {
         suggestions: &'static [PatternSuggestion],
     ) -> Self {
         Self {
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             pattern_info: Some(PatternMatch {
                 pack_id: Some(pack_id.to_string()),
                 pattern_name: Some(pattern_name.to_string()),
@@ -695,7 +774,7 @@ impl EvaluationResult {
     ) -> Self {
         let preview = extract_match_preview(command, &span);
         Self {
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             pattern_info: Some(PatternMatch {
                 pack_id: Some(pack_id.to_string()),
                 pattern_name: Some(pattern_name.to_string()),
@@ -722,7 +801,7 @@ impl EvaluationResult {
         reason: String,
     ) -> Self {
         Self {
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             pattern_info: None,
             allowlist_override: Some(AllowlistOverride {
                 layer,
@@ -740,14 +819,14 @@ impl EvaluationResult {
     #[inline]
     #[must_use]
     pub fn is_allowed(&self) -> bool {
-        self.decision == EvaluationDecision::Allow
+        self.decision.is_allowed()
     }
 
     /// Check if the command was denied.
     #[inline]
     #[must_use]
     pub fn is_denied(&self) -> bool {
-        self.decision == EvaluationDecision::Deny
+        self.decision.is_denied()
     }
 
     /// Get the reason for denial (if denied).
@@ -902,6 +981,8 @@ pub fn evaluate_detailed_with_allowlists(
     use std::time::Instant;
 
     let start = Instant::now();
+    let shell_command = normalize_for_shell(command, &config.evaluation);
+    let command = shell_command.as_ref();
 
     // Collect enabled keywords for quick-reject tracking
     let enabled_packs = config.enabled_pack_ids();
@@ -924,20 +1005,42 @@ pub fn evaluate_detailed_with_allowlists(
     };
 
     // Perform evaluation
-    let result = evaluate_command_with_pack_order(
-        command,
-        &enabled_keywords,
-        &ordered_packs,
-        keyword_index.as_ref(),
-        &compiled_overrides,
-        allowlists,
-        &heredoc_settings,
-    );
+    let result = check_strict_quoting(command, &config.evaluation).unwrap_or_else(|| {
+        let result = evaluate_command_with_pack_order(
+            command,
+            &enabled_keywords,
+            &ordered_packs,
+            keyword_index.as_ref(),
+            &compiled_overrides,
+            allowlists,
+            &heredoc_settings,
+        );
+        let catalog = config.i18n.load_catalog().ok().flatten();
+        post_process(
+            command,
+            result,
+            config,
+            allowlists,
+            catalog.as_deref(),
+            None,
+            |target| {
+                evaluate_command_with_pack_order(
+                    target,
+                    &enabled_keywords,
+                    &ordered_packs,
+                    keyword_index.as_ref(),
+                    &compiled_overrides,
+                    allowlists,
+                    &heredoc_settings,
+                )
+            },
+        )
+    });
 
     let evaluation_time_us = start.elapsed().as_micros() as u64;
 
     // Apply confidence scoring if applicable
-    let confidence = if result.is_denied() {
+    let confidence = if result.decision != Decision::Allow {
         let sanitized = sanitize_for_pattern_matching(command);
         let sanitized_str = if matches!(sanitized, std::borrow::Cow::Owned(_)) {
             Some(sanitized.as_ref())
@@ -1063,1575 +1166,3585 @@ fn allow_once_match_force_config(
     }
 }
 
-/// Evaluate a command against all patterns and packs using a deadline.
+/// Rewrite a command into the canonical form for the configured `[evaluation] shell`.
 ///
-/// When `deadline` is provided and exceeded, evaluation fails open and returns
-/// `skipped_due_to_budget=true` so hook mode can allow the command safely.
+/// In PowerShell mode aliases and abbreviated parameters are expanded (see
+/// [`crate::normalize::normalize_powershell`]); fish chains and command
+/// substitutions are rewritten to POSIX form (see [`crate::normalize::normalize_fish`]);
+/// bash commands are returned unchanged.
 #[must_use]
-pub fn evaluate_command_with_deadline(
-    command: &str,
-    config: &Config,
-    enabled_keywords: &[&str],
-    compiled_overrides: &crate::config::CompiledOverrides,
-    allowlists: &LayeredAllowlist,
-    deadline: Option<&Deadline>,
-) -> EvaluationResult {
-    let enabled_packs: HashSet<String> = config.enabled_pack_ids();
-    let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
-    let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
-    let heredoc_settings = config.heredoc_settings();
-    evaluate_command_with_pack_order_deadline(
-        command,
-        enabled_keywords,
-        &ordered_packs,
-        keyword_index.as_ref(),
-        compiled_overrides,
-        allowlists,
-        &heredoc_settings,
-        None,
-        deadline,
-    )
+pub fn normalize_for_shell<'a>(
+    command: &'a str,
+    evaluation: &EvaluationConfig,
+) -> std::borrow::Cow<'a, str> {
+    match evaluation.shell {
+        ShellDialect::Bash => std::borrow::Cow::Borrowed(command),
+        ShellDialect::PowerShell => crate::normalize::normalize_powershell(command),
+        ShellDialect::Fish => crate::normalize::normalize_fish(command),
+    }
 }
 
-/// Evaluate a command using a precomputed pack order.
-///
-/// This is the hot-path optimized variant for hook mode: callers can compute the
-/// enabled pack set and expanded ordered pack list once at startup and reuse it
-/// for every command invocation.
-///
-/// # Arguments
+/// Deny a command with ambiguous quoting when `[evaluation] strict_quoting` is enabled.
 ///
-/// * `command` - The raw command string to evaluate
-/// * `enabled_keywords` - Keywords from enabled packs for quick rejection
-/// * `ordered_packs` - Expanded pack IDs in deterministic evaluation order
-/// * `compiled_overrides` - Precompiled config overrides
-/// * `allowlists` - Layered allowlists (project/user/system)
+/// Returns `None` when strict quoting is disabled or the quotes in the normalized
+/// command balance, in which case callers continue with regular evaluation.
 #[must_use]
-pub fn evaluate_command_with_pack_order(
+pub fn check_strict_quoting(
     command: &str,
-    enabled_keywords: &[&str],
-    ordered_packs: &[String],
-    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
-    compiled_overrides: &crate::config::CompiledOverrides,
-    allowlists: &LayeredAllowlist,
-    heredoc_settings: &crate::config::HeredocSettings,
-) -> EvaluationResult {
-    evaluate_command_with_pack_order_at_path(
-        command,
-        enabled_keywords,
-        ordered_packs,
-        keyword_index,
-        compiled_overrides,
-        allowlists,
-        heredoc_settings,
-        None,
-    )
+    evaluation: &EvaluationConfig,
+) -> Option<EvaluationResult> {
+    if !evaluation.strict_quoting {
+        return None;
+    }
+    let normalized = crate::normalize::normalize_command(command);
+    has_unbalanced_quotes(normalized.as_ref())
+        .then(|| EvaluationResult::denied_by_config("ambiguous quoting".to_string()))
 }
 
-/// Evaluate a command using a precomputed pack order and an optional project path.
+/// Apply the post-match steps every caller runs after pack evaluation.
+///
+/// Hook mode, `dcg test`, `dcg explain` and the library entry points all go
+/// through here so they reach the same decision. `project_path` is the
+/// directory the command runs in, used for git, script-target and
+/// `[safe_dirs]` lookups (the current directory when `None`);
+/// `evaluate_target` evaluates each command behind a resolved
+/// `make`/`npm run`/`just` target.
 #[must_use]
-#[allow(clippy::too_many_arguments)]
-pub fn evaluate_command_with_pack_order_at_path(
+pub fn post_process<F>(
     command: &str,
-    enabled_keywords: &[&str],
-    ordered_packs: &[String],
-    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
-    compiled_overrides: &crate::config::CompiledOverrides,
+    result: EvaluationResult,
+    config: &Config,
     allowlists: &LayeredAllowlist,
-    heredoc_settings: &crate::config::HeredocSettings,
+    catalog: Option<&MessageCatalog>,
     project_path: Option<&Path>,
-) -> EvaluationResult {
-    evaluate_command_with_pack_order_deadline_at_path(
+    evaluate_target: F,
+) -> EvaluationResult
+where
+    F: FnMut(&str) -> EvaluationResult,
+{
+    let result = apply_on_error(result, &config.evaluation);
+    let result = apply_protected_path_edits(command, result, &config.filesystem, allowlists);
+    let result = apply_pushed_history_rewrite(command, result, &config.git_awareness, project_path);
+    let result = apply_message_catalog(result, catalog);
+    let result = apply_force_push_target(command, result, &config.git_awareness);
+    let result = apply_cd_target_escalation(command, result);
+    let result = apply_immutable_removal_escalation(command, result);
+    let result = apply_kubernetes_escalation(command, result, &config.kubernetes);
+    let result = apply_essential_package_escalation(command, result, &config.system_packages);
+    let result = apply_privileged_unknown(command, result, &config.evaluation);
+    let result = apply_script_targets(
         command,
-        enabled_keywords,
-        ordered_packs,
-        keyword_index,
-        compiled_overrides,
-        allowlists,
-        heredoc_settings,
-        None,
+        result,
+        &config.evaluation,
         project_path,
-        None,
-    )
+        evaluate_target,
+    );
+    let result = resolve_policy_mode(result, config);
+    let result = apply_safe_dirs(command, result, &config.safe_dirs, project_path);
+    apply_warn_decision(result)
 }
 
-/// Evaluate a command with deadline support for fail-open behavior.
-///
-/// This is the hook-mode entry point that supports budget enforcement.
-/// If the deadline is exceeded at check points, returns `allowed_due_to_budget()`.
-///
-/// # Arguments
-///
-/// * `command` - The raw command string to evaluate
-/// * `enabled_keywords` - Keywords from enabled packs for quick rejection
-/// * `ordered_packs` - Ordered list of enabled pack IDs
-/// * `compiled_overrides` - Precompiled config overrides
-/// * `allowlists` - Layered allowlist for overrides
-/// * `heredoc_settings` - Settings for heredoc analysis
-/// * `deadline` - Optional deadline for fail-open behavior
+/// Resolve the effective mode of a pack match through the configured policy.
 ///
-/// # Returns
+/// The mode set at match time is the severity default; rule, pack and global
+/// policy settings, and the regrading steps above (force push to an
+/// unprotected branch, cd into `/`, immutable files, prod clusters, essential
+/// packages), are only applied here, so library, FFI and explain results
+/// report the same decision as the hook.
+fn resolve_policy_mode(mut result: EvaluationResult, config: &Config) -> EvaluationResult {
+    if result.decision != Decision::Deny {
+        return result;
+    }
+    let Some(info) = result.pattern_info.as_ref() else {
+        return result;
+    };
+    if !matches!(info.source, MatchSource::Pack | MatchSource::HeredocAst) {
+        return result;
+    }
+    result.effective_mode = Some(config.policy().resolve_mode(
+        info.pack_id.as_deref(),
+        info.pattern_name.as_deref(),
+        info.severity,
+    ));
+    result
+}
+
+/// Downgrade a denied pack match when the command runs in a `[safe_dirs]`
+/// directory and only touches paths inside one.
 ///
-/// An `EvaluationResult` with `skipped_due_to_budget: true` if deadline exceeded.
-#[must_use]
-#[allow(clippy::too_many_arguments)]
-pub fn evaluate_command_with_pack_order_deadline(
+/// `cwd` defaults to the current directory; see [`SafeDirsConfig::apply`] for
+/// which matches keep their deny.
+fn apply_safe_dirs(
     command: &str,
-    enabled_keywords: &[&str],
-    ordered_packs: &[String],
-    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
-    compiled_overrides: &crate::config::CompiledOverrides,
-    allowlists: &LayeredAllowlist,
-    heredoc_settings: &crate::config::HeredocSettings,
-    allow_once_audit: Option<&crate::pending_exceptions::AllowOnceAuditConfig<'_>>,
-    deadline: Option<&Deadline>,
+    mut result: EvaluationResult,
+    safe_dirs: &SafeDirsConfig,
+    cwd: Option<&Path>,
 ) -> EvaluationResult {
-    evaluate_command_with_pack_order_deadline_at_path(
-        command,
-        enabled_keywords,
-        ordered_packs,
-        keyword_index,
-        compiled_overrides,
-        allowlists,
-        heredoc_settings,
-        allow_once_audit,
-        None,
-        deadline,
-    )
+    if safe_dirs.paths.is_empty() || result.decision != Decision::Deny {
+        return result;
+    }
+    let (Some(info), Some(mode)) = (result.pattern_info.as_ref(), result.effective_mode) else {
+        return result;
+    };
+    if !matches!(info.source, MatchSource::Pack | MatchSource::HeredocAst) {
+        return result;
+    }
+    let current_dir = cwd
+        .is_none()
+        .then(std::env::current_dir)
+        .and_then(Result::ok);
+    let cwd = cwd.or(current_dir.as_deref());
+    result.effective_mode = Some(safe_dirs.apply(mode, info.severity, cwd, command));
+    result
 }
 
-/// Evaluate a command with deadline support and an optional project path.
+/// Report a match whose effective mode is warn as [`Decision::Warn`].
+///
+/// Runs last so the escalation steps above still see the match as a `Deny`.
 #[must_use]
-#[allow(clippy::too_many_arguments)]
-#[allow(clippy::too_many_lines)]
-pub fn evaluate_command_with_pack_order_deadline_at_path(
-    command: &str,
-    enabled_keywords: &[&str],
-    ordered_packs: &[String],
-    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
-    compiled_overrides: &crate::config::CompiledOverrides,
-    allowlists: &LayeredAllowlist,
-    heredoc_settings: &crate::config::HeredocSettings,
-    allow_once_audit: Option<&crate::pending_exceptions::AllowOnceAuditConfig<'_>>,
-    project_path: Option<&Path>,
-    deadline: Option<&Deadline>,
-) -> EvaluationResult {
-    // Check deadline at entry - if already exceeded, fail-open immediately.
-    if deadline_exceeded(deadline) {
-        return EvaluationResult::allowed_due_to_budget();
+pub fn apply_warn_decision(mut result: EvaluationResult) -> EvaluationResult {
+    if result.decision == Decision::Deny
+        && result.effective_mode == Some(crate::packs::DecisionMode::Warn)
+    {
+        result.decision = Decision::Warn;
     }
+    result
+}
 
-    // Empty commands are allowed (no-op)
-    if command.is_empty() {
-        return EvaluationResult::allowed();
+/// Deny a command whose evaluation was cut short when `[evaluation] on_error` is closed.
+///
+/// With the default open policy the result is returned unchanged, so a budget
+/// overrun still allows the command.
+#[must_use]
+pub fn apply_on_error(result: EvaluationResult, evaluation: &EvaluationConfig) -> EvaluationResult {
+    if result.skipped_due_to_budget && evaluation.on_error == OnErrorMode::Closed {
+        return EvaluationResult::denied_on_error("evaluation budget exceeded");
     }
+    result
+}
 
-    // Step 1: Check precompiled allow overrides first
-    if compiled_overrides.check_allow(command) {
-        return EvaluationResult::allowed();
+/// Decision to return when evaluation overran `[evaluation] total_timeout_ms`.
+///
+/// Deterministic per `on_error`: open allows (as a budget skip), closed denies
+/// with reason "evaluation timed out".
+#[must_use]
+pub fn evaluation_timed_out(evaluation: &EvaluationConfig) -> EvaluationResult {
+    match evaluation.on_error {
+        OnErrorMode::Open => EvaluationResult::allowed_due_to_budget(),
+        OnErrorMode::Closed => EvaluationResult::denied_on_error("evaluation timed out"),
     }
+}
 
-    // Step 1.5: Check precompiled block overrides (allow-once may optionally override).
-    if let Some(reason) = compiled_overrides.check_block(command) {
-        if allow_once_match_force_config(command, allow_once_audit).is_some() {
-            return EvaluationResult::allowed();
-        }
-        return EvaluationResult::denied_by_config(reason.to_string());
+/// Well-known system programs that may run under `sudo` without asking when
+/// `[evaluation] ask_on_privileged_unknown` is enabled. Destructive uses of
+/// these are still caught by the regular packs.
+const PRIVILEGED_KNOWN_COMMANDS: &[&str] = &[
+    "apt",
+    "apt-get",
+    "cat",
+    "chmod",
+    "chown",
+    "cp",
+    "df",
+    "dnf",
+    "docker",
+    "du",
+    "find",
+    "grep",
+    "head",
+    "id",
+    "ip",
+    "journalctl",
+    "kill",
+    "less",
+    "ln",
+    "ls",
+    "lsof",
+    "mkdir",
+    "mount",
+    "mv",
+    "pacman",
+    "rm",
+    "service",
+    "snap",
+    "ss",
+    "stat",
+    "systemctl",
+    "tail",
+    "tee",
+    "touch",
+    "umount",
+    "whoami",
+    "yum",
+];
+
+/// Ask before running an unrecognized program under `sudo` when
+/// `[evaluation] ask_on_privileged_unknown` is enabled.
+///
+/// Only commands that would otherwise be allowed, and were not allowlisted,
+/// are affected. Every segment of a compound command is checked, so
+/// `cd x && sudo foo` and `env A=1 sudo foo` are caught. The program is
+/// unrecognized unless its basename is a well-known system tool or it is listed
+/// in `privileged_allow`; relative paths (`./script.sh`) are never well-known.
+#[must_use]
+pub fn apply_privileged_unknown(
+    command: &str,
+    result: EvaluationResult,
+    evaluation: &EvaluationConfig,
+) -> EvaluationResult {
+    if !evaluation.ask_on_privileged_unknown
+        || !result.is_allowed()
+        || result.allowlist_override.is_some()
+    {
+        return result;
     }
-
-    // Step 1.6: Check allow-once overrides.
-    if allow_once_match(command, allow_once_audit).is_some() {
-        return EvaluationResult::allowed();
+    let tokens = crate::normalize::tokenize_for_normalization(command);
+    for segment in tokens.split(|token| token.kind == NormalizeTokenKind::Separator) {
+        let (Some(first), Some(last)) = (segment.first(), segment.last()) else {
+            continue;
+        };
+        let Some(text) = command.get(first.byte_range.start..last.byte_range.end) else {
+            continue;
+        };
+        let stripped = strip_wrapper_prefixes(text);
+        if !stripped
+            .stripped_wrappers
+            .iter()
+            .any(|wrapper| wrapper.wrapper_type == "sudo")
+        {
+            continue;
+        }
+        let Some(program) = stripped.normalized.split_whitespace().next() else {
+            continue;
+        };
+        let basename = program.rsplit('/').next().unwrap_or(program);
+        let relative = program.contains('/') && !program.starts_with('/');
+        if (!relative && PRIVILEGED_KNOWN_COMMANDS.contains(&basename))
+            || evaluation.is_privileged_allowed(program)
+        {
+            continue;
+        }
+        return EvaluationResult::ask_by_config(format!(
+            "sudo runs unrecognized program '{program}'; confirm before running it as root"
+        ));
     }
+    result
+}
 
-    if deadline_exceeded(deadline) {
-        return EvaluationResult::allowed_due_to_budget();
-    }
+/// Evaluate the commands behind `make`/`npm run`/`just` targets when
+/// `[evaluation] resolve_script_targets` is enabled.
+///
+/// Only commands that would otherwise be allowed, and were not allowlisted,
+/// are affected. Each command from the target's definition in `project_path`
+/// (or the current directory) is passed to `evaluate`; the first one that is
+/// denied denies the invocation, with the reason naming the target command.
+#[must_use]
+pub fn apply_script_targets<F>(
+    command: &str,
+    result: EvaluationResult,
+    evaluation: &EvaluationConfig,
+    project_path: Option<&Path>,
+    mut evaluate: F,
+) -> EvaluationResult
+where
+    F: FnMut(&str) -> EvaluationResult,
+{
+    if !evaluation.resolve_script_targets
+        || !result.is_allowed()
+        || result.allowlist_override.is_some()
+    {
+        return result;
+    }
+    let cwd = match project_path {
+        Some(path) => path.to_path_buf(),
+        None => match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(_) => return result,
+        },
+    };
+    for resolved in crate::script_targets::resolve_script_targets(command, &cwd) {
+        let mut target_result = evaluate(&resolved.command);
+        if target_result.is_allowed() {
+            continue;
+        }
+        if let Some(info) = target_result.pattern_info.as_mut() {
+            info.reason = format!(
+                "`{}` runs `{}` ({}): {}",
+                resolved.invocation, resolved.command, resolved.file, info.reason
+            );
+            // The span points into the target's command, not the one typed.
+            info.matched_span = None;
+        }
+        return target_result;
+    }
+    result
+}
 
-    // Step 3: Heredoc / inline-script detection (Tier 1/2/3, fail-open).
-    let mut precomputed_sanitized = None;
-    let mut heredoc_allowlist_hit: Option<(PatternMatch, AllowlistLayer, String)> = None;
+/// Replace a match's reason and explanation with the `[i18n]` catalog translation.
+///
+/// The catalog is keyed by rule id (`pack_id:pattern_name`); matches without a
+/// catalog entry, and fields the entry leaves out, keep the built-in English text.
+#[must_use]
+pub fn apply_message_catalog(
+    mut result: EvaluationResult,
+    catalog: Option<&MessageCatalog>,
+) -> EvaluationResult {
+    let Some(catalog) = catalog else {
+        return result;
+    };
+    let Some(info) = result.pattern_info.as_mut() else {
+        return result;
+    };
+    let (Some(pack_id), Some(pattern_name)) =
+        (info.pack_id.as_deref(), info.pattern_name.as_deref())
+    else {
+        return result;
+    };
+    let Some(entry) = catalog.get(&format!("{pack_id}:{pattern_name}")) else {
+        return result;
+    };
+    if let Some(reason) = &entry.reason {
+        info.reason.clone_from(reason);
+    }
+    if let Some(explanation) = &entry.explanation {
+        info.explanation = Some(explanation.clone());
+    }
+    result
+}
 
-    let project_path = resolve_project_path(heredoc_settings, project_path);
-    let project_path = project_path.as_deref();
+/// An in-place edit (`sed -i`, `perl -i`) found in a command.
+struct InPlaceEdit<'a> {
+    tool: &'static str,
+    /// Whether a backup suffix was given (`-i.bak`).
+    backup: bool,
+    files: Vec<&'a str>,
+}
 
-    if heredoc_settings.enabled {
-        if remaining_below(deadline, &crate::perf::HEREDOC_TRIGGER) {
-            return EvaluationResult::allowed_due_to_budget();
-        }
+/// Find `sed -i` / `perl -i` invocations and the files they rewrite.
+fn in_place_edits(command: &str) -> Vec<InPlaceEdit<'_>> {
+    let tokens = crate::normalize::tokenize_for_normalization(command);
+    let mut edits = Vec::new();
+    for segment in tokens.split(|token| token.kind == NormalizeTokenKind::Separator) {
+        let words: Vec<&str> = segment
+            .iter()
+            .filter_map(|token| token.text(command))
+            .collect();
+        let Some(start) = words.iter().position(|word| {
+            !word.contains('=') && !matches!(*word, "sudo" | "env" | "command" | "nohup")
+        }) else {
+            continue;
+        };
+        let program = words[start].rsplit('/').next().unwrap_or(words[start]);
+        let edit = match program {
+            "sed" | "gsed" => sed_in_place_edit(&words[start + 1..]),
+            "perl" => perl_in_place_edit(&words[start + 1..]),
+            _ => None,
+        };
+        edits.extend(edit);
+    }
+    edits
+}
 
-        if check_triggers(command) == TriggerResult::Triggered {
-            let sanitized = sanitize_for_pattern_matching(command);
-            let sanitized_str = sanitized.as_ref();
-            let should_scan = if matches!(sanitized, std::borrow::Cow::Owned(_)) {
-                check_triggers(sanitized_str) == TriggerResult::Triggered
-            } else {
-                true
-            };
-            precomputed_sanitized = Some(sanitized);
+fn unquote(word: &str) -> &str {
+    word.trim_matches(|c| c == '"' || c == '\'')
+}
 
-            if should_scan {
-                let context = HeredocEvaluationContext {
-                    allowlists,
-                    heredoc_settings,
-                    project_path,
-                    deadline,
-                    enabled_keywords,
-                    ordered_packs,
-                    keyword_index,
-                    compiled_overrides,
-                    allow_once_audit,
-                };
-                if let Some(blocked) =
-                    evaluate_heredoc(command, context, &mut heredoc_allowlist_hit)
-                {
-                    return blocked;
+/// Parse `sed` arguments, returning the edit when `-i`/`--in-place` is present.
+fn sed_in_place_edit<'a>(args: &[&'a str]) -> Option<InPlaceEdit<'a>> {
+    let mut backup = None;
+    let mut has_script = false;
+    let mut positional = Vec::new();
+    let mut iter = args.iter().copied().peekable();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            positional.extend(iter.by_ref().map(unquote));
+            break;
+        } else if arg == "--in-place" {
+            backup = Some(false);
+        } else if let Some(suffix) = arg.strip_prefix("--in-place=") {
+            backup = Some(!unquote(suffix).is_empty());
+        } else if arg == "--expression" || arg == "--file" {
+            has_script = true;
+            iter.next();
+        } else if arg.starts_with("--expression=") || arg.starts_with("--file=") {
+            has_script = true;
+        } else if arg.starts_with("--") {
+            // Remaining long options do not take a separate argument.
+        } else if let Some(flags) = arg.strip_prefix('-').filter(|flags| !flags.is_empty()) {
+            for (idx, flag) in flags.char_indices() {
+                let rest = &flags[idx + flag.len_utf8()..];
+                match flag {
+                    'n' | 'r' | 'E' | 's' | 'u' | 'z' => continue,
+                    'i' => {
+                        // BSD sed takes the suffix as a separate argument (`-i ''`).
+                        let empty_suffix = rest.is_empty()
+                            && iter
+                                .peek()
+                                .is_some_and(|next| matches!(*next, "''" | "\"\""));
+                        if empty_suffix {
+                            iter.next();
+                        }
+                        backup = Some(!rest.is_empty());
+                    }
+                    'e' | 'f' => {
+                        has_script = true;
+                        if rest.is_empty() {
+                            iter.next();
+                        }
+                    }
+                    'l' if rest.is_empty() => {
+                        iter.next();
+                    }
+                    _ => {}
                 }
+                break;
             }
+        } else {
+            positional.push(unquote(arg));
         }
     }
+    let backup = backup?;
+    let files = if has_script {
+        positional
+    } else {
+        positional.into_iter().skip(1).collect()
+    };
+    Some(InPlaceEdit {
+        tool: "sed",
+        backup,
+        files,
+    })
+}
 
-    if deadline_exceeded(deadline) {
-        return EvaluationResult::allowed_due_to_budget();
-    }
-
-    // Step 4: Quick rejection - if no relevant keywords, allow immediately
-    if pack_aware_quick_reject(command, enabled_keywords) {
-        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
-            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
+/// Parse `perl` arguments, returning the edit when `-i` is present.
+fn perl_in_place_edit<'a>(args: &[&'a str]) -> Option<InPlaceEdit<'a>> {
+    let mut backup = None;
+    let mut has_script = false;
+    let mut iter = args.iter().copied();
+    let mut positional = Vec::new();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            break;
+        }
+        let Some(flags) = arg.strip_prefix('-').filter(|flags| !flags.is_empty()) else {
+            // The first non-option argument ends option parsing.
+            positional.push(unquote(arg));
+            break;
+        };
+        let mut chars = flags.char_indices().peekable();
+        while let Some((idx, flag)) = chars.next() {
+            let rest = &flags[idx + flag.len_utf8()..];
+            match flag {
+                'p' | 'n' | 'a' | 's' | 'w' | 'W' | 't' | 'T' | 'c' | 'U' | 'X' => {}
+                'l' | '0' => {
+                    while chars.peek().is_some_and(|(_, c)| c.is_ascii_hexdigit()) {
+                        chars.next();
+                    }
+                }
+                'i' => {
+                    backup = Some(!rest.is_empty());
+                    break;
+                }
+                'e' | 'E' => {
+                    has_script = true;
+                    if rest.is_empty() {
+                        iter.next();
+                    }
+                    break;
+                }
+                'I' | 'M' | 'm' if rest.is_empty() => {
+                    iter.next();
+                    break;
+                }
+                _ => break,
+            }
         }
-        return EvaluationResult::allowed();
     }
+    let backup = backup?;
+    positional.extend(iter.map(unquote));
+    let files = if has_script {
+        positional
+    } else {
+        positional.into_iter().skip(1).collect()
+    };
+    Some(InPlaceEdit {
+        tool: "perl",
+        backup,
+        files,
+    })
+}
 
-    if deadline_exceeded(deadline) {
-        return EvaluationResult::allowed_due_to_budget();
+/// Flag `sed -i` / `perl -i` edits of files matching `[filesystem] protected_paths`.
+///
+/// An edit without a backup suffix is Medium severity; `-i.bak` keeps a copy of
+/// the original, so it is Low. Results that are already denied, and rules
+/// allowlisted as `core.filesystem:sed-in-place` / `perl-in-place`, are returned
+/// unchanged.
+#[must_use]
+pub fn apply_protected_path_edits(
+    command: &str,
+    result: EvaluationResult,
+    filesystem: &FilesystemConfig,
+    allowlists: &LayeredAllowlist,
+) -> EvaluationResult {
+    if filesystem.protected_paths.is_empty() || result.is_denied() {
+        return result;
     }
-
-    // Step 5: False-positive immunity - strip known-safe string arguments (commit messages, search
-    // patterns, issue descriptions, etc.) so dangerous substrings inside data do not trigger
-    // blocking.
-    //
-    // Also normalize the command here (Step 6) and reuse for pack evaluation.
-    // pack_aware_quick_reject_with_normalized returns both the quick-reject decision
-    // and the normalized command, avoiding duplicate normalization.
-    let sanitized = precomputed_sanitized.unwrap_or_else(|| sanitize_for_pattern_matching(command));
-    let command_for_match = sanitized.as_ref();
-
-    // Use the optimized version that returns both decision and normalized form.
-    let (quick_reject, normalized) =
-        pack_aware_quick_reject_with_normalized(command_for_match, enabled_keywords);
-    if matches!(sanitized, std::borrow::Cow::Owned(_)) && quick_reject {
-        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
-            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
+    for edit in in_place_edits(command) {
+        let Some(path) = edit.files.iter().find(|file| filesystem.is_protected(file)) else {
+            continue;
+        };
+        let pattern_name = format!("{}-in-place", edit.tool);
+        if allowlists
+            .match_rule("core.filesystem", &pattern_name)
+            .is_some()
+        {
+            continue;
         }
-        return EvaluationResult::allowed();
-    }
-
-    if deadline_exceeded(deadline) {
-        return EvaluationResult::allowed_due_to_budget();
+        let (reason, severity) = if edit.backup {
+            (
+                format!(
+                    "{} -i rewrites protected file {path} in place (a backup copy is kept).",
+                    edit.tool
+                ),
+                crate::packs::Severity::Low,
+            )
+        } else {
+            (
+                format!(
+                    "{} -i rewrites protected file {path} in place with no backup.",
+                    edit.tool
+                ),
+                crate::packs::Severity::Medium,
+            )
+        };
+        let explanation = format!(
+            "{path} matches [filesystem] protected_paths. An in-place edit replaces the file \
+             as soon as the command runs; a bad expression can empty or corrupt it.\n\n\
+             Preview the change first: {tool} ... {path} | diff {path} -\n\
+             Keep a backup: {tool} -i.bak ...",
+            tool = edit.tool
+        );
+        return EvaluationResult::denied_by_pack_pattern(
+            "core.filesystem",
+            &pattern_name,
+            &reason,
+            Some(&explanation),
+            severity,
+            &[],
+        );
     }
+    result
+}
 
-    // Check exact command and prefix allowlists (reusing normalized from quick-reject)
-    // Use path-aware matching for context-aware allowlisting (Epic 5)
+/// Evaluate a `Write`/`Edit` tool call against `[filesystem] protected_write_paths`.
+///
+/// A relative `file_path` is resolved against `cwd` (the hook input's working
+/// directory). A target matching one of the globs is denied as High severity under
+/// `core.filesystem:write-protected-path`, unless that rule is allowlisted or
+/// an allow-once code was granted for `<tool> <file_path>` (the string the
+/// hook records when it denies the call).
+#[must_use]
+pub fn evaluate_file_write(
+    tool: &str,
+    file_path: &str,
+    cwd: Option<&Path>,
+    filesystem: &FilesystemConfig,
+    allowlists: &LayeredAllowlist,
+) -> EvaluationResult {
+    const PATTERN_NAME: &str = "write-protected-path";
+    let Some(glob) = filesystem.protected_write_match(file_path, cwd) else {
+        return EvaluationResult::allowed();
+    };
     if allowlists
-        .match_exact_command_at_path(&normalized, project_path)
+        .match_rule("core.filesystem", PATTERN_NAME)
         .is_some()
-        || allowlists
-            .match_command_prefix_at_path(&normalized, project_path)
-            .is_some()
     {
         return EvaluationResult::allowed();
     }
+    if allow_once_match(&format!("{tool} {file_path}"), None).is_some() {
+        return EvaluationResult::allowed();
+    }
+    let reason = format!("{tool} targets protected file {file_path}.");
+    let explanation = format!(
+        "{file_path} matches the [filesystem] protected_write_paths glob `{glob}`. \
+         System configuration, SSH keys, and environment files are rarely edited \
+         by an agent on purpose, and an overwrite takes effect immediately.\n\n\
+         Review the change and apply it by hand, or allowlist \
+         core.filesystem:{PATTERN_NAME} if this edit is expected."
+    );
+    EvaluationResult::denied_by_pack_pattern(
+        "core.filesystem",
+        PATTERN_NAME,
+        &reason,
+        Some(&explanation),
+        crate::packs::Severity::High,
+        &[],
+    )
+}
 
-    // Step 7: Mask heredoc content for non-executing targets (cat, tee, etc.)
-    // This prevents false positives where documentation text containing dangerous
-    // patterns like "rm -rf /" in heredocs to cat/tee triggers blocking.
-    let masked = crate::heredoc::mask_non_executing_heredocs(&normalized);
-    let command_for_packs = masked.as_ref();
+/// Find `git commit --amend` or a history-rewriting `git rebase` in a command.
+fn git_history_rewrite(command: &str) -> Option<&'static str> {
+    let tokens = crate::normalize::tokenize_for_normalization(command);
+    tokens
+        .split(|token| token.kind == NormalizeTokenKind::Separator)
+        .find_map(|segment| {
+            let mut words = segment
+                .iter()
+                .filter_map(|token| token.text(command))
+                .skip_while(|word| word.rsplit('/').next() != Some("git"))
+                .skip(1);
+            // Skip global options; `-C <path>` and `-c <key=value>` take a value.
+            let subcommand = loop {
+                match words.next()? {
+                    "-C" | "-c" => {
+                        words.next();
+                    }
+                    word if word.starts_with('-') => {}
+                    word => break word,
+                }
+            };
+            let args: Vec<&str> = words.collect();
+            match subcommand {
+                "commit" if args.contains(&"--amend") => Some("git commit --amend"),
+                "rebase"
+                    if !args.iter().any(|arg| {
+                        matches!(
+                            *arg,
+                            "--continue"
+                                | "--abort"
+                                | "--skip"
+                                | "--quit"
+                                | "--edit-todo"
+                                | "--show-current-patch"
+                        )
+                    }) =>
+                {
+                    Some("git rebase")
+                }
+                _ => None,
+            }
+        })
+}
 
-    let result = evaluate_packs_with_allowlists(
-        command_for_packs,
-        &normalized,
-        command_for_match,
-        command,
-        ordered_packs,
-        allowlists,
-        keyword_index,
-        None,
-        project_path,
-    );
-    if result.allowlist_override.is_none() {
-        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
-            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
+/// Warn when `git commit --amend` or `git rebase` would rewrite pushed history.
+///
+/// Applies when `[git_awareness] warn_on_pushed_rewrite` is on and the current
+/// branch's HEAD has already been pushed to its upstream (see
+/// [`crate::git::pushed_upstream_at_path`]). The match is Medium severity, so
+/// the default decision is a warning rather than a deny. Results that are
+/// already denied are returned unchanged.
+#[must_use]
+pub fn apply_pushed_history_rewrite(
+    command: &str,
+    result: EvaluationResult,
+    git_awareness: &GitAwarenessConfig,
+    project_path: Option<&Path>,
+) -> EvaluationResult {
+    if !git_awareness.warn_on_pushed_rewrite || result.is_denied() {
+        return result;
+    }
+    let Some(operation) = git_history_rewrite(command) else {
+        return result;
+    };
+    let cwd = match project_path {
+        Some(path) => path.to_path_buf(),
+        None => match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(_) => return result,
+        },
+    };
+    let Some(upstream) = crate::git::pushed_upstream_at_path(&cwd) else {
+        return result;
+    };
+    EvaluationResult::denied_by_pack_pattern(
+        "core.git",
+        "rewrite-pushed-history",
+        &format!("{operation} rewrites commits already pushed to {upstream}."),
+        Some(&format!(
+            "HEAD is already on {upstream}. Rewriting it means a force push, and \
+             collaborators who pulled the old commits will have to reconcile their \
+             history.\n\n\
+             Add a new commit instead: git commit\n\
+             Or revert a pushed commit: git revert <commit>"
+        )),
+        crate::packs::Severity::Medium,
+        &[],
+    )
+}
+
+/// Branches updated by a `git push`, as written in its refspecs.
+///
+/// Returns an empty list when no refspec is given (the current branch is
+/// pushed) and `None` when the targets cannot be listed (`--all`, `--mirror`).
+/// `HEAD` is returned as-is for the caller to resolve.
+fn git_push_targets(command: &str) -> Option<Vec<String>> {
+    let tokens = crate::normalize::tokenize_for_normalization(command);
+    let mut pushes = tokens
+        .split(|token| token.kind == NormalizeTokenKind::Separator)
+        .filter_map(|segment| {
+            let words: Vec<&str> = segment
+                .iter()
+                .filter_map(|token| token.text(command))
+                .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+                .collect();
+            git_push_args(&words).map(<[&str]>::to_vec)
+        });
+    // Exactly one `git push` segment, or the targets are ambiguous.
+    let args = pushes.next()?;
+    if pushes.next().is_some() {
+        return None;
+    }
+    let mut words = args.into_iter();
+
+    let mut positional = Vec::new();
+    while let Some(word) = words.next() {
+        match word {
+            "--all" | "--mirror" | "--branches" => return None,
+            "-o" | "--push-option" | "--repo" | "--receive-pack" | "--exec" => {
+                words.next();
+            }
+            word if word.starts_with('-') => {}
+            word => positional.push(word),
         }
     }
 
-    result
+    Some(
+        positional
+            .iter()
+            .skip(1)
+            .map(|refspec| {
+                let refspec = refspec.trim_start_matches('+');
+                let target = refspec.rsplit_once(':').map_or(refspec, |(_, dst)| dst);
+                target
+                    .strip_prefix("refs/heads/")
+                    .unwrap_or(target)
+                    .to_string()
+            })
+            .collect(),
+    )
 }
 
-#[allow(clippy::too_many_lines)]
-#[allow(clippy::too_many_arguments)]
-fn evaluate_packs_with_allowlists(
-    command_for_packs: &str,
-    normalized: &str,
-    command_for_match: &str,
-    original_command: &str,
-    ordered_packs: &[String],
-    allowlists: &LayeredAllowlist,
-    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
-    deadline: Option<&Deadline>,
-    project_path: Option<&Path>,
+/// Arguments after `push` when a segment's command word is `git` and its
+/// subcommand (after git's global options) is `push`.
+fn git_push_args<'a, 'b>(words: &'b [&'a str]) -> Option<&'b [&'a str]> {
+    let (program, mut rest) = words.split_first()?;
+    if program.rsplit('/').next() != Some("git") {
+        return None;
+    }
+    loop {
+        let (word, tail) = rest.split_first()?;
+        match *word {
+            "push" => return Some(tail),
+            "-C" | "-c" | "--git-dir" | "--work-tree" | "--namespace" | "--config-env"
+            | "--super-prefix" => rest = tail.get(1..)?,
+            word if word.starts_with('-') => rest = tail,
+            _ => return None,
+        }
+    }
+}
+
+/// Grade `git push --force` by the branches it overwrites.
+///
+/// Force-pushing a feature branch is routine; overwriting `main` or a release
+/// branch is not. When `push-force-long`/`push-force-short` (or
+/// `push-force-with-lease-overridden`, which is a bare force) matched and every
+/// refspec names a branch outside `[git_awareness] protected_branches`, the
+/// match drops to Medium; [`post_process`] then resolves the mode for the new
+/// severity through the configured policy. A protected target keeps the
+/// Critical match. Pushes without an explicit branch refspec (no refspec,
+/// `HEAD`, `--all`) are left unchanged: where they land depends on
+/// `push.default`, `branch.<name>.merge` and `remote.<name>.push`.
+#[must_use]
+pub fn apply_force_push_target(
+    command: &str,
+    mut result: EvaluationResult,
+    git_awareness: &GitAwarenessConfig,
 ) -> EvaluationResult {
-    if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH) {
-        return EvaluationResult::allowed_due_to_budget();
+    if result.is_allowed() {
+        return result;
+    }
+    let Some(info) = result.pattern_info.as_mut() else {
+        return result;
+    };
+    if info.pack_id.as_deref() != Some("core.git")
+        || !matches!(
+            info.pattern_name.as_deref(),
+            Some("push-force-long" | "push-force-short" | "push-force-with-lease-overridden")
+        )
+    {
+        return result;
+    }
+    let Some(targets) = git_push_targets(command) else {
+        return result;
+    };
+    if targets.is_empty()
+        || targets
+            .iter()
+            .any(|target| target == "HEAD" || target.is_empty() || target.starts_with("refs/"))
+    {
+        return result;
     }
 
-    // Pre-compute which packs might match.
-    //
-    // When a keyword index is available, use a single global substring scan to
-    // conservatively select candidate packs (superset of legacy PackEntry::might_match).
-    // Otherwise, fall back to the per-pack metadata scan.
-    //
-    // External packs from custom_paths are also checked alongside built-in packs.
-    let external_store = crate::packs::get_external_packs();
-    let candidate_packs: Vec<(&String, &crate::packs::Pack)> = keyword_index.map_or_else(
-        || {
-            ordered_packs
-                .iter()
-                .filter_map(|pack_id| {
-                    // Try built-in registry first
-                    if let Some(entry) = REGISTRY.get_entry(pack_id) {
-                        if !entry.might_match(command_for_packs) {
-                            return None;
-                        }
-                        return Some((pack_id, entry.get_pack()));
-                    }
-                    // Fallback to external packs
-                    if let Some(store) = external_store {
-                        if let Some(pack) = store.get(pack_id) {
-                            if !pack.might_match(command_for_packs) {
-                                return None;
-                            }
-                            return Some((pack_id, pack));
-                        }
-                    }
-                    None
-                })
-                .collect()
-        },
-        |index| {
-            let mask = index.candidate_pack_mask(command_for_packs);
-            ordered_packs
-                .iter()
-                .enumerate()
-                .filter_map(|(i, pack_id)| {
-                    if (mask >> i) & 1 == 0 {
-                        return None;
-                    }
-                    // Try built-in registry first
-                    if let Some(entry) = REGISTRY.get_entry(pack_id) {
-                        return Some((pack_id, entry.get_pack()));
-                    }
-                    // Fallback to external packs
-                    if let Some(store) = external_store {
-                        if let Some(pack) = store.get(pack_id) {
-                            return Some((pack_id, pack));
-                        }
-                    }
-                    None
-                })
-                .collect()
-        },
-    );
-
-    let has_filesystem_pack = candidate_packs
+    if let Some(protected) = targets
         .iter()
-        .any(|(pack_id, _)| pack_id.as_str() == "core.filesystem");
-    let rm_parse = has_filesystem_pack
-        .then(|| crate::packs::core::filesystem::parse_rm_command(command_for_packs));
+        .find(|target| git_awareness.matches_protected_branch(target))
+    {
+        info.reason = format!("{} (protected branch '{protected}')", info.reason);
+    } else {
+        info.severity = Some(crate::packs::Severity::Medium);
+        info.reason = format!(
+            "{} (unprotected branch '{}')",
+            info.reason,
+            targets.join("', '")
+        );
+    }
+    result
+}
 
-    let normalized_offset = compute_normalized_offset(command_for_match, normalized);
-    let original_len = original_command.len();
+/// Extract the `--context` and `--namespace`/`-n` values from a kubectl command.
+///
+/// Only the segment containing `kubectl` is read; a `-n` after a shell
+/// separator belongs to another command.
+fn kubectl_target(segment: &str) -> (Option<&str>, Option<&str>) {
+    let mut context = None;
+    let mut namespace = None;
+    let mut tokens = segment
+        .split_whitespace()
+        .map(|token| token.trim_matches(|c| c == '"' || c == '\''))
+        .skip_while(|token| !token.ends_with("kubectl"));
+    while let Some(token) = tokens.next() {
+        if let Some(value) = token.strip_prefix("--context=") {
+            context = Some(value.trim_start_matches(['"', '\'']));
+        } else if let Some(value) = token.strip_prefix("--namespace=") {
+            namespace = Some(value.trim_start_matches(['"', '\'']));
+        } else if token == "--context" {
+            context = tokens.next();
+        } else if token == "--namespace" || token == "-n" {
+            namespace = tokens.next();
+        }
+    }
+    (context, namespace)
+}
 
-    // Single-pass per-pack evaluation: safe patterns only protect their own pack's
-    // destructive patterns, not other packs. This prevents compound command bypass
-    // where e.g., "git checkout -b foo" safe pattern would whitelist "rm -rf / ; git checkout -b foo".
-    //
-    // For each pack:
-    // 1. Check safe patterns - if match, skip this pack's destructive patterns (continue)
-    // 2. Check destructive patterns - if match, block (unless allowlisted)
-    //
-    // The rm_parse optimization for core.filesystem is handled inline.
-    let mut first_allowlist_hit: Option<(PatternMatch, AllowlistLayer, String)> = None;
+/// Escalate kubectl denials that target a production context or namespace.
+///
+/// When a `kubernetes.*` rule matched and the command's `--context` or
+/// `--namespace` matches `[kubernetes] prod_contexts`/`prod_namespaces`, the
+/// match severity is raised one level (e.g. Medium → High), so a delete that
+/// only warns in dev is denied in production.
+#[must_use]
+pub fn apply_kubernetes_escalation(
+    command: &str,
+    mut result: EvaluationResult,
+    kubernetes: &KubernetesConfig,
+) -> EvaluationResult {
+    if result.is_allowed() {
+        return result;
+    }
+    let Some(info) = result.pattern_info.as_mut() else {
+        return result;
+    };
+    let (Some(pack_id), Some(severity)) = (info.pack_id.as_deref(), info.severity) else {
+        return result;
+    };
+    if !pack_id.starts_with("kubernetes.") {
+        return result;
+    }
 
-    for &(pack_id, pack) in &candidate_packs {
-        if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH) {
-            return EvaluationResult::allowed_due_to_budget();
+    let target = command.split(['|', ';', '&', '\n']).find_map(|segment| {
+        let (context, namespace) = kubectl_target(segment);
+        context
+            .filter(|ctx| kubernetes.is_prod_context(ctx))
+            .map(|ctx| format!("context '{ctx}'"))
+            .or_else(|| {
+                namespace
+                    .filter(|ns| kubernetes.is_prod_namespace(ns))
+                    .map(|ns| format!("namespace '{ns}'"))
+            })
+    });
+    if let Some(target) = target {
+        info.severity = Some(severity.escalated());
+        info.reason = format!("{} (production {target})", info.reason);
+    }
+    result
+}
+
+/// Extract the package names an apt/dnf/yum/pacman removal targets.
+///
+/// Architecture and version suffixes (`libc6:amd64`, `vim=2:9.0`) are dropped.
+fn removed_packages(command: &str) -> Vec<&str> {
+    let mut packages = Vec::new();
+    for segment in command.split(['|', ';', '&', '\n']) {
+        let mut words = segment
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+            .skip_while(|word| *word == "sudo" || crate::normalize::is_env_assignment(word));
+        let Some(manager) = words.next().and_then(|word| word.rsplit('/').next()) else {
+            continue;
+        };
+        let args: Vec<&str> = words.collect();
+        let names = args.iter().copied().filter(|arg| !arg.starts_with('-'));
+        let removing = match manager {
+            "apt" | "apt-get" | "aptitude" | "dnf" | "yum" => matches!(
+                names.clone().next(),
+                Some("remove" | "purge" | "erase" | "autoremove")
+            ),
+            "pacman" => args
+                .iter()
+                .any(|arg| *arg == "--remove" || (arg.starts_with("-R") && !arg.starts_with("--"))),
+            _ => false,
+        };
+        if !removing {
+            continue;
         }
+        let skip = usize::from(manager != "pacman");
+        packages.extend(
+            names
+                .skip(skip)
+                .map(|name| name.split([':', '=']).next().unwrap_or(name))
+                .filter(|name| !name.is_empty()),
+        );
+    }
+    packages
+}
 
-        // Check safe patterns for this pack first.
-        // If a safe pattern matches, skip this pack's destructive patterns only.
-        // This prevents compound command bypass where one pack's safe pattern
-        // would whitelist destructive commands from other packs.
-        if pack_id == "core.filesystem" {
-            // core.filesystem uses rm_parse for more accurate safe pattern detection
-            match rm_parse.as_ref() {
-                Some(crate::packs::core::filesystem::RmParseDecision::Allow) => {
-                    continue; // Safe pattern match - skip this pack
-                }
-                Some(crate::packs::core::filesystem::RmParseDecision::NoMatch) | None => {
-                    // rm_parse didn't find rm command or wasn't computed, check safe patterns as fallback
-                    if pack.matches_safe(command_for_packs) {
-                        continue;
-                    }
-                }
-                Some(crate::packs::core::filesystem::RmParseDecision::Deny(hit)) => {
-                    if let Some(allow_hit) =
-                        allowlists.match_rule_at_path(pack_id, hit.pattern_name, project_path)
-                    {
-                        if first_allowlist_hit.is_none() {
-                            let span = hit.span.as_ref().map(|span| MatchSpan {
-                                start: span.start,
-                                end: span.end,
-                            });
-                            let mapped_span = span.and_then(|span| {
-                                map_span_with_offset(span, normalized_offset, original_len)
-                            });
-                            let preview = mapped_span
-                                .as_ref()
-                                .map(|span| extract_match_preview(original_command, span))
-                                .or_else(|| {
-                                    span.as_ref()
-                                        .map(|span| extract_match_preview(command_for_packs, span))
-                                });
-                            first_allowlist_hit = Some((
-                                PatternMatch {
-                                    pack_id: Some(pack_id.clone()),
-                                    pattern_name: Some(hit.pattern_name.to_string()),
-                                    severity: Some(hit.severity),
-                                    reason: hit.reason.to_string(),
-                                    source: MatchSource::Pack,
-                                    matched_span: mapped_span,
-                                    matched_text_preview: preview,
-                                    explanation: None,
-                                    suggestions: &[],
-                                },
-                                allow_hit.layer,
-                                allow_hit.entry.reason.clone(),
-                            ));
-                        }
-                        continue;
-                    }
+/// Escalate `system.packages` matches that remove an essential package.
+///
+/// When one of the removed packages matches `[system_packages] essential`
+/// (libc6, systemd, coreutils, ...), the match severity is raised to at least
+/// High, so the removal is denied instead of warned.
+#[must_use]
+pub fn apply_essential_package_escalation(
+    command: &str,
+    mut result: EvaluationResult,
+    system_packages: &SystemPackagesConfig,
+) -> EvaluationResult {
+    if result.is_allowed() {
+        return result;
+    }
+    let Some(info) = result.pattern_info.as_mut() else {
+        return result;
+    };
+    if info.pack_id.as_deref() != Some("system.packages")
+        || info
+            .severity
+            .is_none_or(|severity| severity.blocks_by_default())
+    {
+        return result;
+    }
 
-                    if let Some(span) = hit.span.as_ref().map(|span| MatchSpan {
-                        start: span.start,
-                        end: span.end,
-                    }) {
-                        if let Some(mapped_span) =
-                            map_span_with_offset(span, normalized_offset, original_len)
-                        {
-                            return EvaluationResult::denied_by_pack_pattern_with_span(
-                                pack_id,
-                                hit.pattern_name,
-                                hit.reason,
-                                None,
-                                hit.severity,
-                                &[], // fast_match path doesn't have suggestions
-                                original_command,
-                                mapped_span,
-                            );
-                        }
-                    }
+    if let Some(package) = removed_packages(command)
+        .into_iter()
+        .find(|package| system_packages.is_essential(package))
+    {
+        info.severity = Some(crate::packs::Severity::High);
+        info.reason = format!("{} (essential package '{package}')", info.reason);
+    }
+    result
+}
 
-                    return EvaluationResult::denied_by_pack_pattern(
-                        pack_id,
-                        hit.pattern_name,
-                        hit.reason,
-                        None,
-                        hit.severity,
-                        &[], // fast_match path doesn't have suggestions
-                    );
-                }
-            }
-        } else {
-            // Non-core.filesystem packs: check safe patterns before destructive
-            if pack.matches_safe(command_for_packs) {
-                continue; // Safe pattern match - skip this pack's destructive patterns
-            }
+/// Directories where everything below is system-owned: a delete after `cd`
+/// into any of them (or a subdirectory) is Critical.
+const CD_SENSITIVE_TREES: &[&str] = &[
+    "/etc", "/usr", "/bin", "/sbin", "/lib", "/lib64", "/boot", "/sys", "/proc", "/dev",
+];
+
+/// Directories that are sensitive themselves but usually hold project or
+/// application data further down (`/home/me/project`, `/var/tmp/build`).
+const CD_SENSITIVE_DIRS: &[&str] = &[
+    "/", "/var", "/home", "/root", "/opt", "/srv", "~", "$HOME", "${HOME}",
+];
+
+fn is_sensitive_cd_target(dir: &str) -> bool {
+    let trimmed = dir.trim_end_matches('/');
+    let dir = if trimmed.is_empty() { dir } else { trimmed };
+    CD_SENSITIVE_DIRS.contains(&dir)
+        || CD_SENSITIVE_TREES.iter().any(|tree| {
+            dir.strip_prefix(tree)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+}
+
+/// Whether a segment's words are a recursive or wildcard delete whose targets
+/// depend on the current directory (`rm -rf *`, `rm -r build`, `git clean -fdx`).
+fn is_cwd_relative_delete(words: &[&str]) -> bool {
+    let mut words = words
+        .iter()
+        .copied()
+        .skip_while(|word| *word == "sudo" || crate::normalize::is_env_assignment(word));
+    match words.next().and_then(|word| word.rsplit('/').next()) {
+        Some("rm") => {
+            let args: Vec<&str> = words.collect();
+            let recursive = args.iter().any(|arg| {
+                *arg == "--recursive"
+                    || (arg.starts_with('-') && !arg.starts_with("--") && arg.contains(['r', 'R']))
+            });
+            let relative: Vec<&&str> = args
+                .iter()
+                .filter(|arg| !arg.starts_with('-') && !arg.starts_with(['/', '~', '$']))
+                .collect();
+            !relative.is_empty() && (recursive || relative.iter().any(|arg| arg.contains('*')))
         }
+        Some("git") => words.any(|word| word == "clean"),
+        _ => false,
+    }
+}
 
-        for pattern in &pack.destructive_patterns {
-            if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH)
+/// Find a delete that runs after a literal `cd` into a sensitive directory.
+///
+/// Only `cd <literal path>` (or a bare `cd`, which goes home) chained with
+/// `&&`, `;`, or a newline is tracked; `||`, pipes, and backgrounding do not
+/// carry the directory forward. Returns the directory the delete runs in.
+fn delete_after_sensitive_cd(command: &str) -> Option<String> {
+    let tokens = crate::normalize::tokenize_for_normalization(command);
+    let mut cwd: Option<String> = None;
+    let mut words: Vec<&str> = Vec::new();
+    let mut tokens = tokens.iter();
+    loop {
+        let token = tokens.next();
+        if let Some(token) = token.filter(|t| t.kind == NormalizeTokenKind::Word) {
+            words.extend(token.text(command));
+            continue;
+        }
+
+        match words.as_slice() {
+            ["cd"] => cwd = Some("~".to_string()),
+            ["cd", dir] if !dir.contains(['`', '(', '*', '?']) && *dir != "-" => {
+                let dir = dir.trim_matches(|c| c == '"' || c == '\'');
+                cwd = if dir.starts_with(['/', '~', '$']) {
+                    Some(dir.to_string())
+                } else if dir.split('/').any(|part| part == "..") {
+                    None
+                } else {
+                    cwd.map(|base| format!("{}/{dir}", base.trim_end_matches('/')))
+                };
+            }
+            ["cd", ..] => cwd = None,
+            segment
+                if cwd.as_deref().is_some_and(is_sensitive_cd_target)
+                    && is_cwd_relative_delete(segment) =>
             {
-                return EvaluationResult::allowed_due_to_budget();
+                return cwd;
             }
+            _ => {}
+        }
+        words.clear();
 
-            // All severity levels are now evaluated. The policy layer in main.rs
-            // determines whether to deny, warn, or log based on severity and config.
+        let separator = token?.text(command);
+        if !matches!(separator, Some("&&" | ";" | "\n" | "(")) {
+            cwd = None;
+        }
+    }
+}
 
-            let matched_span = pattern
-                .regex
-                .find(command_for_packs)
-                .map(|(start, end)| MatchSpan { start, end });
-            let Some(span) = matched_span else {
-                continue;
-            };
+/// Raise a delete to Critical when a preceding `cd` moved into a sensitive directory.
+///
+/// `cd /etc && rm -rf *` removes far more than `rm -rf *` in a project
+/// checkout, so when a `core.filesystem` or `core.git` rule matched and the
+/// delete runs after a literal `cd` into `/`, the home directory, or a system
+/// directory like `/etc` or `/usr`, the match severity becomes Critical.
+#[must_use]
+pub fn apply_cd_target_escalation(command: &str, mut result: EvaluationResult) -> EvaluationResult {
+    if result.is_allowed() {
+        return result;
+    }
+    let Some(info) = result.pattern_info.as_mut() else {
+        return result;
+    };
+    if !matches!(
+        info.pack_id.as_deref(),
+        Some("core.filesystem" | "core.git")
+    ) {
+        return result;
+    }
+    if let Some(dir) = delete_after_sensitive_cd(command) {
+        info.severity = Some(crate::packs::Severity::Critical);
+        info.reason = format!("{} (after cd {dir})", info.reason);
+    }
+    result
+}
 
-            let reason = pattern.reason;
-            let mapped_span = map_span_with_offset(span, normalized_offset, original_len);
-            let preview = mapped_span
-                .as_ref()
-                .map(|span| extract_match_preview(original_command, span))
-                .or_else(|| Some(extract_match_preview(command_for_packs, &span)));
-
-            // Allowlist check: only applies when we have a stable match identity (named pattern).
-            if let Some(pattern_name) = pattern.name {
-                if let Some(hit) =
-                    allowlists.match_rule_at_path(pack_id, pattern_name, project_path)
-                {
-                    if first_allowlist_hit.is_none() {
-                        first_allowlist_hit = Some((
-                            PatternMatch {
-                                pack_id: Some(pack_id.clone()),
-                                pattern_name: Some(pattern_name.to_string()),
-                                severity: Some(pattern.severity),
-                                reason: reason.to_string(),
-                                source: MatchSource::Pack,
-                                matched_span: mapped_span,
-                                matched_text_preview: preview,
-                                explanation: pattern.explanation.map(str::to_string),
-                                suggestions: pattern.suggestions,
-                            },
-                            hit.layer,
-                            hit.entry.reason.clone(),
-                        ));
-                    }
-
-                    // Bypass only this rule and keep evaluating other rules/packs.
-                    continue;
-                }
+/// Paths whose immutable attribute a `chattr` segment removes.
+///
+/// A mode word like `-i`, `-ia`, or `=` (which clears every attribute not
+/// listed) drops immutability; `+i` and modes without `i` do not.
+fn chattr_immutable_removals<'a>(words: &[&'a str]) -> Vec<&'a str> {
+    let mut words = words
+        .iter()
+        .copied()
+        .skip_while(|word| *word == "sudo" || crate::normalize::is_env_assignment(word));
+    if words.next().and_then(|word| word.rsplit('/').next()) != Some("chattr") {
+        return Vec::new();
+    }
+    let args: Vec<&str> = words.collect();
+    let removes_immutable = args.iter().any(|arg| {
+        (arg.starts_with('-') && !arg.starts_with("--") && arg[1..].contains('i'))
+            || (arg.starts_with('=') && !arg.contains('i'))
+    });
+    if !removes_immutable {
+        return Vec::new();
+    }
+    args.into_iter()
+        .filter(|arg| !arg.starts_with(['-', '+', '=']))
+        .collect()
+}
 
-                if let Some(mapped_span) = mapped_span {
-                    return EvaluationResult::denied_by_pack_pattern_with_span(
-                        pack_id,
-                        pattern_name,
-                        reason,
-                        pattern.explanation,
-                        pattern.severity,
-                        pattern.suggestions,
-                        original_command,
-                        mapped_span,
-                    );
-                }
+/// Whether two literal paths are the same or one contains the other.
+fn paths_overlap(a: &str, b: &str) -> bool {
+    let a = a.trim_matches(|c| c == '"' || c == '\'');
+    let b = b.trim_matches(|c| c == '"' || c == '\'');
+    let a = if a == "/" { a } else { a.trim_end_matches('/') };
+    let b = if b == "/" { b } else { b.trim_end_matches('/') };
+    let contains = |outer: &str, inner: &str| {
+        inner == outer
+            || (outer == "/" && inner.starts_with('/'))
+            || inner
+                .strip_prefix(outer)
+                .is_some_and(|rest| rest.starts_with('/'))
+    };
+    contains(a, b) || contains(b, a)
+}
 
-                return EvaluationResult::denied_by_pack_pattern(
-                    pack_id,
-                    pattern_name,
-                    reason,
-                    pattern.explanation,
-                    pattern.severity,
-                    pattern.suggestions,
-                );
-            }
+/// Find a path whose immutability a `chattr -i` removes before a later
+/// segment in the same chain operates on an overlapping path.
+fn path_after_immutable_removal(command: &str) -> Option<String> {
+    let tokens = crate::normalize::tokenize_for_normalization(command);
+    let mut unlocked: Vec<String> = Vec::new();
+    let mut words: Vec<&str> = Vec::new();
+    let mut tokens = tokens.iter();
+    loop {
+        let token = tokens.next();
+        if let Some(token) = token.filter(|t| t.kind == NormalizeTokenKind::Word) {
+            words.extend(token.text(command));
+            continue;
+        }
 
-            if let Some(mapped_span) = mapped_span {
-                return EvaluationResult::denied_by_pack_with_span(
-                    pack_id,
-                    reason,
-                    pattern.explanation,
-                    original_command,
-                    mapped_span,
-                );
+        let removed = chattr_immutable_removals(&words);
+        if removed.is_empty() {
+            let overlap = words.iter().skip(1).find_map(|arg| {
+                unlocked
+                    .iter()
+                    .find(|path| !arg.starts_with('-') && paths_overlap(path, arg))
+            });
+            if let Some(path) = overlap {
+                return Some(path.clone());
             }
-
-            return EvaluationResult::denied_by_pack(pack_id, reason, pattern.explanation);
+        } else {
+            unlocked.extend(removed.into_iter().map(str::to_string));
         }
-    }
+        words.clear();
 
-    if let Some((matched, layer, reason)) = first_allowlist_hit {
-        return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
+        token?;
     }
-
-    EvaluationResult::allowed()
 }
 
-/// Evaluate a command with legacy pattern support using precompiled overrides.
-///
-/// This version includes legacy `SAFE_PATTERNS` and `DESTRUCTIVE_PATTERNS` checking.
-/// It's intended to be used by the main hook entrypoint until the legacy patterns
-/// are migrated to the pack system (git_safety_guard-99e.3.4).
-///
-/// # Arguments
-///
-/// * `command` - The raw command string to evaluate
-/// * `config` - Loaded configuration with pack settings
-/// * `enabled_keywords` - Keywords from enabled packs for quick rejection
-/// * `compiled_overrides` - Precompiled config overrides (avoids per-command regex compilation)
-/// * `safe_patterns` - Legacy safe patterns (whitelist)
-/// * `destructive_patterns` - Legacy destructive patterns (blacklist)
+/// Raise a match to Critical when it follows `chattr -i` on an overlapping path.
 ///
-/// # Type Parameters
+/// `chattr -i /important && rm -rf /important` removes the immutable attribute
+/// that was protecting the files and then destroys them. When a match runs in
+/// the same chain as an earlier `chattr -i` (or `chattr -R -i`, or `chattr =`)
+/// and a later segment names a path that contains, or is contained by, the
+/// unlocked path, the match severity becomes Critical.
+#[must_use]
+pub fn apply_immutable_removal_escalation(
+    command: &str,
+    mut result: EvaluationResult,
+) -> EvaluationResult {
+    if result.is_allowed() {
+        return result;
+    }
+    let Some(info) = result.pattern_info.as_mut() else {
+        return result;
+    };
+    if let Some(path) = path_after_immutable_removal(command) {
+        info.severity = Some(crate::packs::Severity::Critical);
+        info.reason = format!("{} (after chattr -i {path})", info.reason);
+    }
+    result
+}
+
+/// Evaluate a command against all patterns and packs using a deadline.
 ///
-/// This function accepts any types that implement pattern matching:
-/// * `S` - Safe pattern type with `is_match` method returning `bool`
-/// * `D` - Destructive pattern type with `is_match` method returning `bool` and `reason` method
-#[allow(clippy::too_many_lines)]
-pub fn evaluate_command_with_legacy<S, D>(
+/// When `deadline` is provided and exceeded, evaluation fails open and returns
+/// `skipped_due_to_budget=true` so hook mode can allow the command safely.
+#[must_use]
+pub fn evaluate_command_with_deadline(
     command: &str,
     config: &Config,
     enabled_keywords: &[&str],
     compiled_overrides: &crate::config::CompiledOverrides,
     allowlists: &LayeredAllowlist,
-    safe_patterns: &[S],
-    destructive_patterns: &[D],
-) -> EvaluationResult
-where
-    S: LegacySafePattern,
-    D: LegacyDestructivePattern,
-{
-    // Empty commands are allowed (no-op)
-    if command.is_empty() {
-        return EvaluationResult::allowed();
-    }
-
-    // Step 1: Check precompiled allow overrides first
-    if compiled_overrides.check_allow(command) {
-        return EvaluationResult::allowed();
-    }
-
-    // Step 1.5: Check allow-once overrides (may be superseded by config blocklist).
-    let allow_once = allow_once_match(command, None);
-
-    // Step 2: Check precompiled block overrides
-    if let Some(reason) = compiled_overrides.check_block(command) {
-        if allow_once
-            .as_ref()
-            .is_some_and(|entry| entry.force_allow_config)
-        {
-            return EvaluationResult::allowed();
-        }
-        return EvaluationResult::denied_by_config(reason.to_string());
-    }
-
-    if allow_once.is_some() {
-        return EvaluationResult::allowed();
+    deadline: Option<&Deadline>,
+) -> EvaluationResult {
+    let shell_command = normalize_for_shell(command, &config.evaluation);
+    let command = shell_command.as_ref();
+    if let Some(denied) = check_strict_quoting(command, &config.evaluation) {
+        return denied;
     }
 
-    // Step 2.5: Pre-calculate ordered packs for heredoc recursion (and later use)
     let enabled_packs: HashSet<String> = config.enabled_pack_ids();
     let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
     let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
-
-    // Step 3: Heredoc / inline-script detection (Tier 1/2/3, fail-open).
-    // See `evaluate_command` for detailed rationale.
     let heredoc_settings = config.heredoc_settings();
-    let mut precomputed_sanitized = None;
-    let mut heredoc_allowlist_hit: Option<(PatternMatch, AllowlistLayer, String)> = None;
-    let project_path = resolve_project_path(&heredoc_settings, None);
-    let project_path = project_path.as_deref();
-    if heredoc_settings.enabled && check_triggers(command) == TriggerResult::Triggered {
-        let sanitized = sanitize_for_pattern_matching(command);
-        let sanitized_str = sanitized.as_ref();
-        let should_scan = if matches!(sanitized, std::borrow::Cow::Owned(_)) {
-            check_triggers(sanitized_str) == TriggerResult::Triggered
-        } else {
-            true
-        };
-        precomputed_sanitized = Some(sanitized);
-
-        if should_scan {
-            let context = HeredocEvaluationContext {
-                allowlists,
-                heredoc_settings: &heredoc_settings,
-                project_path,
-                deadline: None,
-                enabled_keywords,
-                ordered_packs: &ordered_packs,
-                keyword_index: keyword_index.as_ref(),
-                compiled_overrides,
-                allow_once_audit: None,
-            };
-            if let Some(blocked) = evaluate_heredoc(command, context, &mut heredoc_allowlist_hit) {
-                return blocked;
-            }
-        }
-    }
-
-    // Step 4: Quick rejection - if no relevant keywords, allow immediately
-    if pack_aware_quick_reject(command, enabled_keywords) {
-        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
-            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
-        }
-        return EvaluationResult::allowed();
-    }
-
-    // Step 5: False-positive immunity - strip known-safe string arguments (commit messages, search
-    // patterns, issue descriptions, etc.) so dangerous substrings inside data do not trigger
-    // blocking.
-    //
-    // Also normalize the command here (Step 6) and reuse for pattern matching.
-    // pack_aware_quick_reject_with_normalized returns both the quick-reject decision
-    // and the normalized command, avoiding duplicate normalization.
-    let sanitized = precomputed_sanitized.unwrap_or_else(|| sanitize_for_pattern_matching(command));
-    let command_for_match = sanitized.as_ref();
-
-    // Use the optimized version that returns both decision and normalized form.
-    let (quick_reject, normalized) =
-        pack_aware_quick_reject_with_normalized(command_for_match, enabled_keywords);
-    if matches!(sanitized, std::borrow::Cow::Owned(_)) && quick_reject {
-        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
-            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
-        }
-        return EvaluationResult::allowed();
-    }
-
-    // Step 7: Check legacy safe patterns (whitelist, reusing normalized from quick-reject)
-    for pattern in safe_patterns {
-        if pattern.is_match(&normalized) {
-            return EvaluationResult::allowed();
-        }
-    }
-
-    let normalized_offset = compute_normalized_offset(command_for_match, &normalized);
-    let original_len = command.len();
-
-    // Step 8: Check legacy destructive patterns (blacklist)
-    for pattern in destructive_patterns {
-        if let Some(span) = pattern.find_span(&normalized) {
-            if let Some(mapped_span) = map_span_with_offset(span, normalized_offset, original_len) {
-                return EvaluationResult::denied_by_legacy_with_span(
-                    pattern.reason(),
-                    command,
-                    mapped_span,
-                );
-            }
-            return EvaluationResult::denied_by_legacy(pattern.reason());
-        }
-    }
-
-    // Step 9: Check enabled packs with allowlist override semantics.
-    // Note: Legacy function doesn't receive project_path - path-aware allowlisting not available here
-    let result = evaluate_packs_with_allowlists(
-        &normalized,
-        &normalized,
-        command_for_match,
+    let result = evaluate_command_with_pack_order_deadline(
         command,
+        enabled_keywords,
         &ordered_packs,
-        allowlists,
         keyword_index.as_ref(),
+        compiled_overrides,
+        allowlists,
+        &heredoc_settings,
         None,
-        None, // project_path: legacy function, path-aware allowlisting unavailable
+        deadline,
     );
-    if result.allowlist_override.is_none() {
-        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
-            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
-        }
-    }
-
-    result
+    let catalog = config.i18n.load_catalog().ok().flatten();
+    post_process(
+        command,
+        result,
+        config,
+        allowlists,
+        catalog.as_deref(),
+        None,
+        |target| {
+            evaluate_command_with_pack_order_deadline(
+                target,
+                enabled_keywords,
+                &ordered_packs,
+                keyword_index.as_ref(),
+                compiled_overrides,
+                allowlists,
+                &heredoc_settings,
+                None,
+                deadline,
+            )
+        },
+    )
 }
-/// Context for heredoc evaluation to avoid too many arguments.
-#[derive(Clone, Copy)]
-struct HeredocEvaluationContext<'a> {
-    allowlists: &'a LayeredAllowlist,
-    heredoc_settings: &'a crate::config::HeredocSettings,
-    project_path: Option<&'a Path>,
-    deadline: Option<&'a Deadline>,
-    enabled_keywords: &'a [&'a str],
-    ordered_packs: &'a [String],
-    keyword_index: Option<&'a crate::packs::EnabledKeywordIndex>,
-    compiled_overrides: &'a crate::config::CompiledOverrides,
-    allow_once_audit: Option<&'a crate::pending_exceptions::AllowOnceAuditConfig<'a>>,
+
+/// Evaluate a command using a precomputed pack order.
+///
+/// This is the hot-path optimized variant for hook mode: callers can compute the
+/// enabled pack set and expanded ordered pack list once at startup and reuse it
+/// for every command invocation.
+///
+/// # Arguments
+///
+/// * `command` - The raw command string to evaluate
+/// * `enabled_keywords` - Keywords from enabled packs for quick rejection
+/// * `ordered_packs` - Expanded pack IDs in deterministic evaluation order
+/// * `compiled_overrides` - Precompiled config overrides
+/// * `allowlists` - Layered allowlists (project/user/system)
+#[must_use]
+pub fn evaluate_command_with_pack_order(
+    command: &str,
+    enabled_keywords: &[&str],
+    ordered_packs: &[String],
+    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
+    compiled_overrides: &crate::config::CompiledOverrides,
+    allowlists: &LayeredAllowlist,
+    heredoc_settings: &crate::config::HeredocSettings,
+) -> EvaluationResult {
+    evaluate_command_with_pack_order_at_path(
+        command,
+        enabled_keywords,
+        ordered_packs,
+        keyword_index,
+        compiled_overrides,
+        allowlists,
+        heredoc_settings,
+        None,
+    )
+}
+
+/// Evaluate a command using a precomputed pack order and an optional project path.
+#[must_use]
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_command_with_pack_order_at_path(
+    command: &str,
+    enabled_keywords: &[&str],
+    ordered_packs: &[String],
+    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
+    compiled_overrides: &crate::config::CompiledOverrides,
+    allowlists: &LayeredAllowlist,
+    heredoc_settings: &crate::config::HeredocSettings,
+    project_path: Option<&Path>,
+) -> EvaluationResult {
+    evaluate_command_with_pack_order_deadline_at_path(
+        command,
+        enabled_keywords,
+        ordered_packs,
+        keyword_index,
+        compiled_overrides,
+        allowlists,
+        heredoc_settings,
+        None,
+        project_path,
+        None,
+    )
+}
+
+/// Evaluate a command with deadline support for fail-open behavior.
+///
+/// This is the hook-mode entry point that supports budget enforcement.
+/// If the deadline is exceeded at check points, returns `allowed_due_to_budget()`.
+///
+/// # Arguments
+///
+/// * `command` - The raw command string to evaluate
+/// * `enabled_keywords` - Keywords from enabled packs for quick rejection
+/// * `ordered_packs` - Ordered list of enabled pack IDs
+/// * `compiled_overrides` - Precompiled config overrides
+/// * `allowlists` - Layered allowlist for overrides
+/// * `heredoc_settings` - Settings for heredoc analysis
+/// * `deadline` - Optional deadline for fail-open behavior
+///
+/// # Returns
+///
+/// An `EvaluationResult` with `skipped_due_to_budget: true` if deadline exceeded.
+#[must_use]
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_command_with_pack_order_deadline(
+    command: &str,
+    enabled_keywords: &[&str],
+    ordered_packs: &[String],
+    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
+    compiled_overrides: &crate::config::CompiledOverrides,
+    allowlists: &LayeredAllowlist,
+    heredoc_settings: &crate::config::HeredocSettings,
+    allow_once_audit: Option<&crate::pending_exceptions::AllowOnceAuditConfig<'_>>,
+    deadline: Option<&Deadline>,
+) -> EvaluationResult {
+    evaluate_command_with_pack_order_deadline_at_path(
+        command,
+        enabled_keywords,
+        ordered_packs,
+        keyword_index,
+        compiled_overrides,
+        allowlists,
+        heredoc_settings,
+        allow_once_audit,
+        None,
+        deadline,
+    )
 }
 
+/// Evaluate a command with deadline support and an optional project path.
+#[must_use]
+#[allow(clippy::too_many_arguments)]
 #[allow(clippy::too_many_lines)]
-fn evaluate_heredoc(
+pub fn evaluate_command_with_pack_order_deadline_at_path(
     command: &str,
-    context: HeredocEvaluationContext<'_>,
-    first_allowlist_hit: &mut Option<(PatternMatch, AllowlistLayer, String)>,
-) -> Option<EvaluationResult> {
-    if deadline_exceeded(context.deadline)
-        || remaining_below(context.deadline, &crate::perf::FULL_HEREDOC_PIPELINE)
-    {
-        return Some(EvaluationResult::allowed_due_to_budget());
+    enabled_keywords: &[&str],
+    ordered_packs: &[String],
+    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
+    compiled_overrides: &crate::config::CompiledOverrides,
+    allowlists: &LayeredAllowlist,
+    heredoc_settings: &crate::config::HeredocSettings,
+    allow_once_audit: Option<&crate::pending_exceptions::AllowOnceAuditConfig<'_>>,
+    project_path: Option<&Path>,
+    deadline: Option<&Deadline>,
+) -> EvaluationResult {
+    // Check deadline at entry - if already exceeded, fail-open immediately.
+    if deadline_exceeded(deadline) {
+        return EvaluationResult::allowed_due_to_budget();
     }
 
-    // Check command-level allowlist before any extraction.
-    // This allows users to whitelist entire commands (e.g., "./scripts/approved.sh").
-    if let Some(ref content_allowlist) = context.heredoc_settings.content_allowlist {
-        if let Some(matched_cmd) = content_allowlist.is_command_allowlisted(command) {
-            tracing::debug!(matched_command = matched_cmd, "heredoc command allowlisted");
-            // Command is allowlisted - skip all heredoc analysis
-            return None;
-        }
+    // Empty commands are allowed (no-op)
+    if command.is_empty() {
+        return EvaluationResult::allowed();
     }
 
-    let (contents, fallback_needed) =
-        match extract_content(command, &context.heredoc_settings.limits) {
-            ExtractionResult::Extracted(contents) => (contents, false),
-            ExtractionResult::NoContent => return None,
-            ExtractionResult::Skipped(reasons) => {
-                let is_timeout = reasons
-                    .iter()
-                    .any(|r| matches!(r, SkipReason::Timeout { .. }));
+    // Step 1: Check precompiled allow overrides first
+    if compiled_overrides.check_allow(command) {
+        return EvaluationResult::allowed();
+    }
 
-                let strict_timeout = is_timeout && !context.heredoc_settings.fallback_on_timeout;
-                let strict_other = !is_timeout && !context.heredoc_settings.fallback_on_parse_error;
-                if strict_timeout || strict_other {
-                    let summary = reasons
-                        .iter()
-                        .map(std::string::ToString::to_string)
-                        .collect::<Vec<_>>()
-                        .join("; ");
-                    let reason = if strict_timeout {
-                        format!(
-                            "Embedded code blocked: extraction exceeded timeout and \
-                         fallback_on_timeout=false ({summary})"
-                        )
-                    } else {
-                        format!(
-                            "Embedded code blocked: extraction skipped and \
-                         fallback_on_parse_error=false ({summary})"
-                        )
-                    };
-                    return Some(EvaluationResult::denied_by_legacy(&reason));
-                }
+    // Step 1.5: Check precompiled block overrides (allow-once may optionally override).
+    if let Some(reason) = compiled_overrides.check_block(command) {
+        if allow_once_match_force_config(command, allow_once_audit).is_some() {
+            return EvaluationResult::allowed();
+        }
+        return EvaluationResult::denied_by_config(reason.to_string());
+    }
 
-                // Fallback check: if skipped due to size limits, perform a rudimentary
-                // substring check for critical patterns that would otherwise be missed.
-                if reasons
-                    .iter()
-                    .any(|r| matches!(r, SkipReason::ExceededSizeLimit { .. }))
-                {
-                    if let Some(blocked) = check_fallback_patterns(command) {
-                        return Some(blocked);
-                    }
-                }
+    // Step 1.6: Check allow-once overrides.
+    if allow_once_match(command, allow_once_audit).is_some() {
+        return EvaluationResult::allowed();
+    }
 
-                return None;
-            }
-            ExtractionResult::Partial { extracted, skipped } => {
-                // Check strict mode settings for skipped items
-                let is_timeout = skipped
-                    .iter()
-                    .any(|r| matches!(r, SkipReason::Timeout { .. }));
+    if deadline_exceeded(deadline) {
+        return EvaluationResult::allowed_due_to_budget();
+    }
 
-                let strict_timeout = is_timeout && !context.heredoc_settings.fallback_on_timeout;
-                let strict_other = !is_timeout && !context.heredoc_settings.fallback_on_parse_error;
-                if strict_timeout || strict_other {
-                    let summary = skipped
-                        .iter()
-                        .map(std::string::ToString::to_string)
-                        .collect::<Vec<_>>()
-                        .join("; ");
-                    let reason = if strict_timeout {
-                        format!(
-                            "Embedded code blocked: extraction exceeded timeout (partial) and \
-                         fallback_on_timeout=false ({summary})"
-                        )
-                    } else {
-                        format!(
-                            "Embedded code blocked: extraction partial and \
-                         fallback_on_parse_error=false ({summary})"
-                        )
-                    };
-                    return Some(EvaluationResult::denied_by_legacy(&reason));
-                }
+    // Step 3: Heredoc / inline-script detection (Tier 1/2/3, fail-open).
+    let mut precomputed_sanitized = None;
+    let mut heredoc_allowlist_hit: Option<(PatternMatch, AllowlistLayer, String)> = None;
 
-                // We have partial content. Analyze what we extracted first (high fidelity).
-                // Then if no block, run fallback checks on the whole command if size limit was exceeded.
-                let fallback_needed = skipped
-                    .iter()
-                    .any(|r| matches!(r, SkipReason::ExceededSizeLimit { .. }));
-
-                (extracted, fallback_needed)
-            }
-            ExtractionResult::Failed(err) => {
-                if !context.heredoc_settings.fallback_on_parse_error {
-                    let reason = format!(
-                        "Embedded code blocked: extraction failed and \
-                     fallback_on_parse_error=false ({err})"
-                    );
-                    return Some(EvaluationResult::denied_by_legacy(&reason));
-                }
-
-                return None;
-            }
-        };
+    let project_path = resolve_project_path(heredoc_settings, project_path);
+    let project_path = project_path.as_deref();
 
-    for content in contents {
-        if deadline_exceeded(context.deadline)
-            || remaining_below(context.deadline, &crate::perf::FULL_HEREDOC_PIPELINE)
-        {
-            return Some(EvaluationResult::allowed_due_to_budget());
+    if heredoc_settings.enabled {
+        if remaining_below(deadline, &crate::perf::HEREDOC_TRIGGER) {
+            return EvaluationResult::allowed_due_to_budget();
         }
 
-        if let Some(allowed) = &context.heredoc_settings.allowed_languages {
-            if !allowed.contains(&content.language) {
-                continue;
+        if check_triggers(command) == TriggerResult::Triggered {
+            let sanitized = sanitize_for_pattern_matching(command);
+            let sanitized_str = sanitized.as_ref();
+            let should_scan = if matches!(sanitized, std::borrow::Cow::Owned(_)) {
+                check_triggers(sanitized_str) == TriggerResult::Triggered
+            } else {
+                true
+            };
+            precomputed_sanitized = Some(sanitized);
+
+            if should_scan {
+                let context = HeredocEvaluationContext {
+                    allowlists,
+                    heredoc_settings,
+                    project_path,
+                    deadline,
+                    enabled_keywords,
+                    ordered_packs,
+                    keyword_index,
+                    compiled_overrides,
+                    allow_once_audit,
+                };
+                if let Some(blocked) =
+                    evaluate_heredoc(command, context, &mut heredoc_allowlist_hit)
+                {
+                    return blocked;
+                }
             }
         }
+    }
 
-        // Check content-level allowlist before AST matching.
-        // This allows users to whitelist specific patterns or content hashes.
-        if let Some(ref content_allowlist) = context.heredoc_settings.content_allowlist {
-            if let Some(hit) = content_allowlist.is_content_allowlisted(
-                &content.content,
-                content.language,
-                context.project_path,
-            ) {
-                tracing::debug!(
-                    hit_kind = hit.kind.label(),
-                    matched = hit.matched,
-                    reason = hit.reason,
-                    "heredoc content allowlisted"
-                );
-                // Content is allowlisted - skip AST matching for this heredoc
-                continue;
-            }
+    if deadline_exceeded(deadline) {
+        return EvaluationResult::allowed_due_to_budget();
+    }
+
+    // Step 4: Quick rejection - if no relevant keywords, allow immediately
+    if pack_aware_quick_reject(command, enabled_keywords) {
+        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
+            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
         }
+        return EvaluationResult::allowed();
+    }
 
-        // Skip ALL heredoc content analysis if the target command is non-executing.
-        // Commands like `cat`, `tee`, `grep`, etc. just output the heredoc content
-        // as data - they don't execute it as code. This prevents false positives
-        // where documentation text containing dangerous command examples is blocked.
-        if content
-            .target_command
-            .as_ref()
-            .is_some_and(|cmd| crate::heredoc::is_non_executing_heredoc_command(cmd))
-        {
-            tracing::trace!(
-                target_command = ?content.target_command,
-                "Skipping heredoc content analysis for non-executing target"
-            );
-            continue; // Skip to next extracted content - this heredoc is just data
+    if deadline_exceeded(deadline) {
+        return EvaluationResult::allowed_due_to_budget();
+    }
+
+    // Step 5: False-positive immunity - strip known-safe string arguments (commit messages, search
+    // patterns, issue descriptions, etc.) so dangerous substrings inside data do not trigger
+    // blocking.
+    //
+    // Also normalize the command here (Step 6) and reuse for pack evaluation.
+    // pack_aware_quick_reject_with_normalized returns both the quick-reject decision
+    // and the normalized command, avoiding duplicate normalization.
+    let sanitized = precomputed_sanitized.unwrap_or_else(|| sanitize_for_pattern_matching(command));
+    let command_for_match = sanitized.as_ref();
+
+    // Use the optimized version that returns both decision and normalized form.
+    let (quick_reject, normalized) =
+        pack_aware_quick_reject_with_normalized(command_for_match, enabled_keywords);
+    if matches!(sanitized, std::borrow::Cow::Owned(_)) && quick_reject {
+        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
+            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
         }
+        return EvaluationResult::allowed();
+    }
 
-        // Tier 2.5: Recursive Shell Analysis
-        // If content is Bash, extract inner commands and feed them back to the full evaluator.
-        // This ensures that `kubectl`, `docker`, etc. inside heredocs are checked against their packs.
-        if content.language == crate::heredoc::ScriptLanguage::Bash {
-            let inner_commands = crate::heredoc::extract_shell_commands(&content.content);
-            for inner in inner_commands {
-                if deadline_exceeded(context.deadline) {
-                    return Some(EvaluationResult::allowed_due_to_budget());
-                }
+    if deadline_exceeded(deadline) {
+        return EvaluationResult::allowed_due_to_budget();
+    }
 
-                let result = evaluate_command_with_pack_order_deadline_at_path(
-                    &inner.text,
-                    context.enabled_keywords,
-                    context.ordered_packs,
-                    context.keyword_index,
-                    context.compiled_overrides,
-                    context.allowlists,
-                    context.heredoc_settings,
-                    context.allow_once_audit,
-                    context.project_path,
-                    context.deadline,
-                );
+    // Check exact command and prefix allowlists (reusing normalized from quick-reject)
+    // Use path-aware matching for context-aware allowlisting (Epic 5)
+    if allowlists
+        .match_exact_command_at_path(&normalized, project_path)
+        .is_some()
+        || allowlists
+            .match_command_prefix_at_path(&normalized, project_path)
+            .is_some()
+    {
+        return EvaluationResult::allowed();
+    }
 
-                if result.is_denied() {
-                    // Propagate denial, wrapping the reason context
-                    if let Some(mut info) = result.pattern_info {
-                        info.reason = format!(
-                            "Embedded shell command blocked: {} (line {} of heredoc)",
-                            info.reason, inner.line_number
-                        );
-                        info.source = MatchSource::HeredocAst; // Mark as heredoc source
-                        if let Some(span) = info.matched_span {
-                            if let Some(mapped_inner) =
-                                map_heredoc_span(command, &content, inner.start, inner.end)
-                            {
-                                let mapped = MatchSpan {
-                                    start: mapped_inner.start.saturating_add(span.start),
-                                    end: mapped_inner.start.saturating_add(span.end),
-                                };
-                                if mapped.end <= command.len() {
-                                    info.matched_span = Some(mapped);
-                                    info.matched_text_preview =
-                                        Some(extract_match_preview(command, &mapped));
-                                } else {
-                                    info.matched_span = None;
-                                }
-                            } else {
-                                info.matched_span = None;
-                            }
-                        }
+    // Step 7: Mask heredoc content for non-executing targets (cat, tee, etc.)
+    // This prevents false positives where documentation text containing dangerous
+    // patterns like "rm -rf /" in heredocs to cat/tee triggers blocking.
+    let masked = crate::heredoc::mask_non_executing_heredocs(&normalized);
+    let command_for_packs = masked.as_ref();
 
-                        return Some(EvaluationResult {
-                            decision: EvaluationDecision::Deny,
-                            pattern_info: Some(info),
-                            allowlist_override: None,
-                            effective_mode: Some(crate::packs::DecisionMode::Deny),
-                            skipped_due_to_budget: false,
-                            branch_context: None,
-                        });
-                    }
-                    return Some(result);
-                }
-            }
+    let result = evaluate_packs_with_allowlists(
+        command_for_packs,
+        &normalized,
+        command_for_match,
+        command,
+        ordered_packs,
+        allowlists,
+        keyword_index,
+        None,
+        project_path,
+    );
+    if result.allowlist_override.is_none() {
+        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
+            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
         }
+    }
 
-        let matches = match DEFAULT_MATCHER.find_matches(&content.content, content.language) {
-            Ok(matches) => matches,
-            Err(err) => {
-                let is_timeout = matches!(err, crate::ast_matcher::MatchError::Timeout { .. });
-                let strict_timeout = is_timeout && !context.heredoc_settings.fallback_on_timeout;
-                let strict_other = !is_timeout && !context.heredoc_settings.fallback_on_parse_error;
-                if strict_timeout || strict_other {
-                    let reason = format!(
-                        "Embedded code blocked: AST matching error with strict fallback \
-                         configuration ({err})"
-                    );
-                    return Some(EvaluationResult::denied_by_legacy(&reason));
-                }
+    result
+}
+
+#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_arguments)]
+fn evaluate_packs_with_allowlists(
+    command_for_packs: &str,
+    normalized: &str,
+    command_for_match: &str,
+    original_command: &str,
+    ordered_packs: &[String],
+    allowlists: &LayeredAllowlist,
+    keyword_index: Option<&crate::packs::EnabledKeywordIndex>,
+    deadline: Option<&Deadline>,
+    project_path: Option<&Path>,
+) -> EvaluationResult {
+    if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH) {
+        return EvaluationResult::allowed_due_to_budget();
+    }
+
+    // Pre-compute which packs might match.
+    //
+    // When a keyword index is available, use a single global substring scan to
+    // conservatively select candidate packs (superset of legacy PackEntry::might_match).
+    // Otherwise, fall back to the per-pack metadata scan.
+    //
+    // External packs from custom_paths are also checked alongside built-in packs.
+    let external_store = crate::packs::get_external_packs();
+    let candidate_packs: Vec<(&String, &crate::packs::Pack)> = keyword_index.map_or_else(
+        || {
+            ordered_packs
+                .iter()
+                .filter_map(|pack_id| {
+                    // Try built-in registry first
+                    if let Some(entry) = REGISTRY.get_entry(pack_id) {
+                        if !entry.might_match(command_for_packs) {
+                            return None;
+                        }
+                        return Some((pack_id, entry.get_pack()));
+                    }
+                    // Fallback to external packs
+                    if let Some(store) = external_store {
+                        if let Some(pack) = store.get(pack_id) {
+                            if !pack.might_match(command_for_packs) {
+                                return None;
+                            }
+                            return Some((pack_id, pack));
+                        }
+                    }
+                    None
+                })
+                .collect()
+        },
+        |index| {
+            let mask = index.candidate_pack_mask(command_for_packs);
+            ordered_packs
+                .iter()
+                .enumerate()
+                .filter_map(|(i, pack_id)| {
+                    if (mask >> i) & 1 == 0 {
+                        return None;
+                    }
+                    // Try built-in registry first
+                    if let Some(entry) = REGISTRY.get_entry(pack_id) {
+                        return Some((pack_id, entry.get_pack()));
+                    }
+                    // Fallback to external packs
+                    if let Some(store) = external_store {
+                        if let Some(pack) = store.get(pack_id) {
+                            return Some((pack_id, pack));
+                        }
+                    }
+                    None
+                })
+                .collect()
+        },
+    );
+
+    let has_filesystem_pack = candidate_packs
+        .iter()
+        .any(|(pack_id, _)| pack_id.as_str() == "core.filesystem");
+    let rm_parse = has_filesystem_pack
+        .then(|| crate::packs::core::filesystem::parse_rm_command(command_for_packs));
+
+    let normalized_offset = compute_normalized_offset(command_for_match, normalized);
+    let original_len = original_command.len();
+
+    // Single-pass per-pack evaluation: safe patterns only protect their own pack's
+    // destructive patterns, not other packs. This prevents compound command bypass
+    // where e.g., "git checkout -b foo" safe pattern would whitelist "rm -rf / ; git checkout -b foo".
+    //
+    // For each pack:
+    // 1. Check safe patterns - if match, skip this pack's destructive patterns (continue)
+    // 2. Check destructive patterns - if match, block (unless allowlisted)
+    //
+    // The rm_parse optimization for core.filesystem is handled inline.
+    let mut first_allowlist_hit: Option<(PatternMatch, AllowlistLayer, String)> = None;
+
+    for &(pack_id, pack) in &candidate_packs {
+        if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH) {
+            return EvaluationResult::allowed_due_to_budget();
+        }
+
+        // Check safe patterns for this pack first.
+        // If a safe pattern matches, skip this pack's destructive patterns only.
+        // This prevents compound command bypass where one pack's safe pattern
+        // would whitelist destructive commands from other packs.
+        if pack_id == "core.filesystem" {
+            // core.filesystem uses rm_parse for more accurate safe pattern detection
+            match rm_parse.as_ref() {
+                Some(crate::packs::core::filesystem::RmParseDecision::Allow) => {
+                    continue; // Safe pattern match - skip this pack
+                }
+                Some(crate::packs::core::filesystem::RmParseDecision::NoMatch) | None => {
+                    // rm_parse didn't find rm command or wasn't computed, check safe patterns as fallback
+                    if pack.matches_safe_command(command_for_packs) {
+                        continue;
+                    }
+                }
+                Some(crate::packs::core::filesystem::RmParseDecision::Deny(hit)) => {
+                    if let Some(allow_hit) =
+                        allowlists.match_rule_at_path(pack_id, hit.pattern_name, project_path)
+                    {
+                        if first_allowlist_hit.is_none() {
+                            let span = hit.span.as_ref().map(|span| MatchSpan {
+                                start: span.start,
+                                end: span.end,
+                            });
+                            let mapped_span = span.and_then(|span| {
+                                map_span_with_offset(span, normalized_offset, original_len)
+                            });
+                            let preview = mapped_span
+                                .as_ref()
+                                .map(|span| extract_match_preview(original_command, span))
+                                .or_else(|| {
+                                    span.as_ref()
+                                        .map(|span| extract_match_preview(command_for_packs, span))
+                                });
+                            first_allowlist_hit = Some((
+                                PatternMatch {
+                                    pack_id: Some(pack_id.clone()),
+                                    pattern_name: Some(hit.pattern_name.to_string()),
+                                    severity: Some(hit.severity),
+                                    reason: hit.reason.to_string(),
+                                    source: MatchSource::Pack,
+                                    matched_span: mapped_span,
+                                    matched_text_preview: preview,
+                                    explanation: None,
+                                    suggestions: &[],
+                                },
+                                allow_hit.layer,
+                                allow_hit.entry.reason.clone(),
+                            ));
+                        }
+                        continue;
+                    }
+
+                    if let Some(span) = hit.span.as_ref().map(|span| MatchSpan {
+                        start: span.start,
+                        end: span.end,
+                    }) {
+                        if let Some(mapped_span) =
+                            map_span_with_offset(span, normalized_offset, original_len)
+                        {
+                            return EvaluationResult::denied_by_pack_pattern_with_span(
+                                pack_id,
+                                hit.pattern_name,
+                                hit.reason,
+                                None,
+                                hit.severity,
+                                &[], // fast_match path doesn't have suggestions
+                                original_command,
+                                mapped_span,
+                            );
+                        }
+                    }
+
+                    return EvaluationResult::denied_by_pack_pattern(
+                        pack_id,
+                        hit.pattern_name,
+                        hit.reason,
+                        None,
+                        hit.severity,
+                        &[], // fast_match path doesn't have suggestions
+                    );
+                }
+            }
+        } else {
+            // Non-core.filesystem packs: check safe patterns before destructive
+            if pack.matches_safe_command(command_for_packs) {
+                continue; // Safe pattern match - skip this pack's destructive patterns
+            }
+        }
+
+        for pattern in &pack.destructive_patterns {
+            if deadline_exceeded(deadline) || remaining_below(deadline, &crate::perf::PATTERN_MATCH)
+            {
+                return EvaluationResult::allowed_due_to_budget();
+            }
+            if !pattern.enabled {
+                continue;
+            }
+
+            // All severity levels are now evaluated. The policy layer in main.rs
+            // determines whether to deny, warn, or log based on severity and config.
+
+            let matched_span = pattern
+                .regex
+                .find(command_for_packs)
+                .map(|(start, end)| MatchSpan { start, end });
+            let Some(span) = matched_span else {
+                continue;
+            };
+
+            let reason = pattern.reason;
+            let mapped_span = map_span_with_offset(span, normalized_offset, original_len);
+            let preview = mapped_span
+                .as_ref()
+                .map(|span| extract_match_preview(original_command, span))
+                .or_else(|| Some(extract_match_preview(command_for_packs, &span)));
+
+            // Allowlist check: only applies when we have a stable match identity (named pattern).
+            if let Some(pattern_name) = pattern.name {
+                if let Some(hit) =
+                    allowlists.match_rule_at_path(pack_id, pattern_name, project_path)
+                {
+                    if first_allowlist_hit.is_none() {
+                        first_allowlist_hit = Some((
+                            PatternMatch {
+                                pack_id: Some(pack_id.clone()),
+                                pattern_name: Some(pattern_name.to_string()),
+                                severity: Some(pattern.severity),
+                                reason: reason.to_string(),
+                                source: MatchSource::Pack,
+                                matched_span: mapped_span,
+                                matched_text_preview: preview,
+                                explanation: pattern.explanation.map(str::to_string),
+                                suggestions: pattern.suggestions,
+                            },
+                            hit.layer,
+                            hit.entry.reason.clone(),
+                        ));
+                    }
+
+                    // Bypass only this rule and keep evaluating other rules/packs.
+                    continue;
+                }
+
+                if let Some(mapped_span) = mapped_span {
+                    return EvaluationResult::denied_by_pack_pattern_with_span(
+                        pack_id,
+                        pattern_name,
+                        reason,
+                        pattern.explanation,
+                        pattern.severity,
+                        pattern.suggestions,
+                        original_command,
+                        mapped_span,
+                    );
+                }
+
+                return EvaluationResult::denied_by_pack_pattern(
+                    pack_id,
+                    pattern_name,
+                    reason,
+                    pattern.explanation,
+                    pattern.severity,
+                    pattern.suggestions,
+                );
+            }
+
+            if let Some(mapped_span) = mapped_span {
+                return EvaluationResult::denied_by_pack_with_span(
+                    pack_id,
+                    reason,
+                    pattern.explanation,
+                    original_command,
+                    mapped_span,
+                );
+            }
+
+            return EvaluationResult::denied_by_pack(pack_id, reason, pattern.explanation);
+        }
+    }
+
+    if let Some((matched, layer, reason)) = first_allowlist_hit {
+        return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
+    }
+
+    EvaluationResult::allowed()
+}
+
+/// Evaluate a command with legacy pattern support using precompiled overrides.
+///
+/// This version includes legacy `SAFE_PATTERNS` and `DESTRUCTIVE_PATTERNS` checking.
+/// It's intended to be used by the main hook entrypoint until the legacy patterns
+/// are migrated to the pack system (git_safety_guard-99e.3.4).
+///
+/// # Arguments
+///
+/// * `command` - The raw command string to evaluate
+/// * `config` - Loaded configuration with pack settings
+/// * `enabled_keywords` - Keywords from enabled packs for quick rejection
+/// * `compiled_overrides` - Precompiled config overrides (avoids per-command regex compilation)
+/// * `safe_patterns` - Legacy safe patterns (whitelist)
+/// * `destructive_patterns` - Legacy destructive patterns (blacklist)
+///
+/// # Type Parameters
+///
+/// This function accepts any types that implement pattern matching:
+/// * `S` - Safe pattern type with `is_match` method returning `bool`
+/// * `D` - Destructive pattern type with `is_match` method returning `bool` and `reason` method
+#[allow(clippy::too_many_lines)]
+pub fn evaluate_command_with_legacy<S, D>(
+    command: &str,
+    config: &Config,
+    enabled_keywords: &[&str],
+    compiled_overrides: &crate::config::CompiledOverrides,
+    allowlists: &LayeredAllowlist,
+    safe_patterns: &[S],
+    destructive_patterns: &[D],
+) -> EvaluationResult
+where
+    S: LegacySafePattern,
+    D: LegacyDestructivePattern,
+{
+    // Empty commands are allowed (no-op)
+    if command.is_empty() {
+        return EvaluationResult::allowed();
+    }
+
+    // Step 1: Check precompiled allow overrides first
+    if compiled_overrides.check_allow(command) {
+        return EvaluationResult::allowed();
+    }
+
+    // Step 1.5: Check allow-once overrides (may be superseded by config blocklist).
+    let allow_once = allow_once_match(command, None);
+
+    // Step 2: Check precompiled block overrides
+    if let Some(reason) = compiled_overrides.check_block(command) {
+        if allow_once
+            .as_ref()
+            .is_some_and(|entry| entry.force_allow_config)
+        {
+            return EvaluationResult::allowed();
+        }
+        return EvaluationResult::denied_by_config(reason.to_string());
+    }
+
+    if allow_once.is_some() {
+        return EvaluationResult::allowed();
+    }
+
+    // Step 2.5: Pre-calculate ordered packs for heredoc recursion (and later use)
+    let enabled_packs: HashSet<String> = config.enabled_pack_ids();
+    let ordered_packs = REGISTRY.expand_enabled_ordered(&enabled_packs);
+    let keyword_index = REGISTRY.build_enabled_keyword_index(&ordered_packs);
+
+    // Step 3: Heredoc / inline-script detection (Tier 1/2/3, fail-open).
+    // See `evaluate_command` for detailed rationale.
+    let heredoc_settings = config.heredoc_settings();
+    let mut precomputed_sanitized = None;
+    let mut heredoc_allowlist_hit: Option<(PatternMatch, AllowlistLayer, String)> = None;
+    let project_path = resolve_project_path(&heredoc_settings, None);
+    let project_path = project_path.as_deref();
+    if heredoc_settings.enabled && check_triggers(command) == TriggerResult::Triggered {
+        let sanitized = sanitize_for_pattern_matching(command);
+        let sanitized_str = sanitized.as_ref();
+        let should_scan = if matches!(sanitized, std::borrow::Cow::Owned(_)) {
+            check_triggers(sanitized_str) == TriggerResult::Triggered
+        } else {
+            true
+        };
+        precomputed_sanitized = Some(sanitized);
+
+        if should_scan {
+            let context = HeredocEvaluationContext {
+                allowlists,
+                heredoc_settings: &heredoc_settings,
+                project_path,
+                deadline: None,
+                enabled_keywords,
+                ordered_packs: &ordered_packs,
+                keyword_index: keyword_index.as_ref(),
+                compiled_overrides,
+                allow_once_audit: None,
+            };
+            if let Some(blocked) = evaluate_heredoc(command, context, &mut heredoc_allowlist_hit) {
+                return blocked;
+            }
+        }
+    }
+
+    // Step 4: Quick rejection - if no relevant keywords, allow immediately
+    if pack_aware_quick_reject(command, enabled_keywords) {
+        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
+            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
+        }
+        return EvaluationResult::allowed();
+    }
+
+    // Step 5: False-positive immunity - strip known-safe string arguments (commit messages, search
+    // patterns, issue descriptions, etc.) so dangerous substrings inside data do not trigger
+    // blocking.
+    //
+    // Also normalize the command here (Step 6) and reuse for pattern matching.
+    // pack_aware_quick_reject_with_normalized returns both the quick-reject decision
+    // and the normalized command, avoiding duplicate normalization.
+    let sanitized = precomputed_sanitized.unwrap_or_else(|| sanitize_for_pattern_matching(command));
+    let command_for_match = sanitized.as_ref();
+
+    // Use the optimized version that returns both decision and normalized form.
+    let (quick_reject, normalized) =
+        pack_aware_quick_reject_with_normalized(command_for_match, enabled_keywords);
+    if matches!(sanitized, std::borrow::Cow::Owned(_)) && quick_reject {
+        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
+            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
+        }
+        return EvaluationResult::allowed();
+    }
+
+    // Step 7: Check legacy safe patterns (whitelist, reusing normalized from quick-reject)
+    for pattern in safe_patterns {
+        if pattern.is_match(&normalized) {
+            return EvaluationResult::allowed();
+        }
+    }
+
+    let normalized_offset = compute_normalized_offset(command_for_match, &normalized);
+    let original_len = command.len();
+
+    // Step 8: Check legacy destructive patterns (blacklist)
+    for pattern in destructive_patterns {
+        if let Some(span) = pattern.find_span(&normalized) {
+            if let Some(mapped_span) = map_span_with_offset(span, normalized_offset, original_len) {
+                return EvaluationResult::denied_by_legacy_with_span(
+                    pattern.reason(),
+                    command,
+                    mapped_span,
+                );
+            }
+            return EvaluationResult::denied_by_legacy(pattern.reason());
+        }
+    }
+
+    // Step 9: Check enabled packs with allowlist override semantics.
+    // Note: Legacy function doesn't receive project_path - path-aware allowlisting not available here
+    let result = evaluate_packs_with_allowlists(
+        &normalized,
+        &normalized,
+        command_for_match,
+        command,
+        &ordered_packs,
+        allowlists,
+        keyword_index.as_ref(),
+        None,
+        None, // project_path: legacy function, path-aware allowlisting unavailable
+    );
+    if result.allowlist_override.is_none() {
+        if let Some((matched, layer, reason)) = heredoc_allowlist_hit {
+            return EvaluationResult::allowed_by_allowlist(matched, layer, reason);
+        }
+    }
+
+    result
+}
+/// Context for heredoc evaluation to avoid too many arguments.
+#[derive(Clone, Copy)]
+struct HeredocEvaluationContext<'a> {
+    allowlists: &'a LayeredAllowlist,
+    heredoc_settings: &'a crate::config::HeredocSettings,
+    project_path: Option<&'a Path>,
+    deadline: Option<&'a Deadline>,
+    enabled_keywords: &'a [&'a str],
+    ordered_packs: &'a [String],
+    keyword_index: Option<&'a crate::packs::EnabledKeywordIndex>,
+    compiled_overrides: &'a crate::config::CompiledOverrides,
+    allow_once_audit: Option<&'a crate::pending_exceptions::AllowOnceAuditConfig<'a>>,
+}
+
+#[allow(clippy::too_many_lines)]
+fn evaluate_heredoc(
+    command: &str,
+    context: HeredocEvaluationContext<'_>,
+    first_allowlist_hit: &mut Option<(PatternMatch, AllowlistLayer, String)>,
+) -> Option<EvaluationResult> {
+    if deadline_exceeded(context.deadline)
+        || remaining_below(context.deadline, &crate::perf::FULL_HEREDOC_PIPELINE)
+    {
+        return Some(EvaluationResult::allowed_due_to_budget());
+    }
+
+    // Check command-level allowlist before any extraction.
+    // This allows users to whitelist entire commands (e.g., "./scripts/approved.sh").
+    if let Some(ref content_allowlist) = context.heredoc_settings.content_allowlist {
+        if let Some(matched_cmd) = content_allowlist.is_command_allowlisted(command) {
+            tracing::debug!(matched_command = matched_cmd, "heredoc command allowlisted");
+            // Command is allowlisted - skip all heredoc analysis
+            return None;
+        }
+    }
+
+    let (contents, fallback_needed) =
+        match extract_content(command, &context.heredoc_settings.limits) {
+            ExtractionResult::Extracted(contents) => (contents, false),
+            ExtractionResult::NoContent => return None,
+            ExtractionResult::Skipped(reasons) => {
+                let is_timeout = reasons
+                    .iter()
+                    .any(|r| matches!(r, SkipReason::Timeout { .. }));
+
+                let strict_timeout = is_timeout && !context.heredoc_settings.fallback_on_timeout;
+                let strict_other = !is_timeout && !context.heredoc_settings.fallback_on_parse_error;
+                if strict_timeout || strict_other {
+                    let summary = reasons
+                        .iter()
+                        .map(std::string::ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    let reason = if strict_timeout {
+                        format!(
+                            "Embedded code blocked: extraction exceeded timeout and \
+                         fallback_on_timeout=false ({summary})"
+                        )
+                    } else {
+                        format!(
+                            "Embedded code blocked: extraction skipped and \
+                         fallback_on_parse_error=false ({summary})"
+                        )
+                    };
+                    return Some(EvaluationResult::denied_by_legacy(&reason));
+                }
+
+                // Fallback check: if skipped due to size limits, perform a rudimentary
+                // substring check for critical patterns that would otherwise be missed.
+                if reasons
+                    .iter()
+                    .any(|r| matches!(r, SkipReason::ExceededSizeLimit { .. }))
+                {
+                    if let Some(blocked) = check_fallback_patterns(command) {
+                        return Some(blocked);
+                    }
+                }
+
+                return None;
+            }
+            ExtractionResult::Partial { extracted, skipped } => {
+                // Check strict mode settings for skipped items
+                let is_timeout = skipped
+                    .iter()
+                    .any(|r| matches!(r, SkipReason::Timeout { .. }));
+
+                let strict_timeout = is_timeout && !context.heredoc_settings.fallback_on_timeout;
+                let strict_other = !is_timeout && !context.heredoc_settings.fallback_on_parse_error;
+                if strict_timeout || strict_other {
+                    let summary = skipped
+                        .iter()
+                        .map(std::string::ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    let reason = if strict_timeout {
+                        format!(
+                            "Embedded code blocked: extraction exceeded timeout (partial) and \
+                         fallback_on_timeout=false ({summary})"
+                        )
+                    } else {
+                        format!(
+                            "Embedded code blocked: extraction partial and \
+                         fallback_on_parse_error=false ({summary})"
+                        )
+                    };
+                    return Some(EvaluationResult::denied_by_legacy(&reason));
+                }
+
+                // We have partial content. Analyze what we extracted first (high fidelity).
+                // Then if no block, run fallback checks on the whole command if size limit was exceeded.
+                let fallback_needed = skipped
+                    .iter()
+                    .any(|r| matches!(r, SkipReason::ExceededSizeLimit { .. }));
+
+                (extracted, fallback_needed)
+            }
+            ExtractionResult::Failed(err) => {
+                if !context.heredoc_settings.fallback_on_parse_error {
+                    let reason = format!(
+                        "Embedded code blocked: extraction failed and \
+                     fallback_on_parse_error=false ({err})"
+                    );
+                    return Some(EvaluationResult::denied_by_legacy(&reason));
+                }
+
+                return None;
+            }
+        };
+
+    for content in contents {
+        if deadline_exceeded(context.deadline)
+            || remaining_below(context.deadline, &crate::perf::FULL_HEREDOC_PIPELINE)
+        {
+            return Some(EvaluationResult::allowed_due_to_budget());
+        }
+
+        if let Some(allowed) = &context.heredoc_settings.allowed_languages {
+            if !allowed.contains(&content.language) {
+                continue;
+            }
+        }
+
+        // Check content-level allowlist before AST matching.
+        // This allows users to whitelist specific patterns or content hashes.
+        if let Some(ref content_allowlist) = context.heredoc_settings.content_allowlist {
+            if let Some(hit) = content_allowlist.is_content_allowlisted(
+                &content.content,
+                content.language,
+                context.project_path,
+            ) {
+                tracing::debug!(
+                    hit_kind = hit.kind.label(),
+                    matched = hit.matched,
+                    reason = hit.reason,
+                    "heredoc content allowlisted"
+                );
+                // Content is allowlisted - skip AST matching for this heredoc
+                continue;
+            }
+        }
+
+        // Skip ALL heredoc content analysis if the target command is non-executing.
+        // Commands like `cat`, `tee`, `grep`, etc. just output the heredoc content
+        // as data - they don't execute it as code. This prevents false positives
+        // where documentation text containing dangerous command examples is blocked.
+        if content
+            .target_command
+            .as_ref()
+            .is_some_and(|cmd| crate::heredoc::is_non_executing_heredoc_command(cmd))
+        {
+            tracing::trace!(
+                target_command = ?content.target_command,
+                "Skipping heredoc content analysis for non-executing target"
+            );
+            continue; // Skip to next extracted content - this heredoc is just data
+        }
+
+        // Tier 2.4: SQL bodies fed to a database client are checked against the
+        // destructive patterns of the matching database pack, statement by statement.
+        if content.language == crate::heredoc::ScriptLanguage::Sql {
+            if let Some(result) =
+                evaluate_sql_heredoc(command, &content, context, first_allowlist_hit)
+            {
+                return Some(result);
+            }
+            continue;
+        }
+
+        // Tier 2.5: Recursive Shell Analysis
+        // If content is Bash, extract inner commands and feed them back to the full evaluator.
+        // This ensures that `kubectl`, `docker`, etc. inside heredocs are checked against their packs.
+        if content.language == crate::heredoc::ScriptLanguage::Bash {
+            let inner_commands = crate::heredoc::extract_shell_commands(&content.content);
+            for inner in inner_commands {
+                if deadline_exceeded(context.deadline) {
+                    return Some(EvaluationResult::allowed_due_to_budget());
+                }
+
+                let result = evaluate_command_with_pack_order_deadline_at_path(
+                    &inner.text,
+                    context.enabled_keywords,
+                    context.ordered_packs,
+                    context.keyword_index,
+                    context.compiled_overrides,
+                    context.allowlists,
+                    context.heredoc_settings,
+                    context.allow_once_audit,
+                    context.project_path,
+                    context.deadline,
+                );
+
+                if result.is_denied() {
+                    // Propagate denial, wrapping the reason context
+                    if let Some(mut info) = result.pattern_info {
+                        info.reason = format!(
+                            "Embedded shell command blocked: {} (line {} of heredoc)",
+                            info.reason, inner.line_number
+                        );
+                        info.source = MatchSource::HeredocAst; // Mark as heredoc source
+                        if let Some(span) = info.matched_span {
+                            if let Some(mapped_inner) =
+                                map_heredoc_span(command, &content, inner.start, inner.end)
+                            {
+                                let mapped = MatchSpan {
+                                    start: mapped_inner.start.saturating_add(span.start),
+                                    end: mapped_inner.start.saturating_add(span.end),
+                                };
+                                if mapped.end <= command.len() {
+                                    info.matched_span = Some(mapped);
+                                    info.matched_text_preview =
+                                        Some(extract_match_preview(command, &mapped));
+                                } else {
+                                    info.matched_span = None;
+                                }
+                            } else {
+                                info.matched_span = None;
+                            }
+                        }
+
+                        return Some(EvaluationResult {
+                            decision: Decision::Deny,
+                            pattern_info: Some(info),
+                            allowlist_override: None,
+                            effective_mode: Some(crate::packs::DecisionMode::Deny),
+                            skipped_due_to_budget: false,
+                            branch_context: None,
+                        });
+                    }
+                    return Some(result);
+                }
+            }
+        }
+
+        let matches = match DEFAULT_MATCHER.find_matches(&content.content, content.language) {
+            Ok(matches) => matches,
+            Err(err) => {
+                let is_timeout = matches!(err, crate::ast_matcher::MatchError::Timeout { .. });
+                let strict_timeout = is_timeout && !context.heredoc_settings.fallback_on_timeout;
+                let strict_other = !is_timeout && !context.heredoc_settings.fallback_on_parse_error;
+                if strict_timeout || strict_other {
+                    let reason = format!(
+                        "Embedded code blocked: AST matching error with strict fallback \
+                         configuration ({err})"
+                    );
+                    return Some(EvaluationResult::denied_by_legacy(&reason));
+                }
+
+                continue;
+            }
+        };
+
+        for m in matches {
+            if deadline_exceeded(context.deadline)
+                || remaining_below(context.deadline, &crate::perf::FULL_HEREDOC_PIPELINE)
+            {
+                return Some(EvaluationResult::allowed_due_to_budget());
+            }
+
+            if !m.severity.blocks_by_default() {
+                continue;
+            }
+
+            let (pack_id, pattern_name) = split_ast_rule_id(&m.rule_id);
+
+            if let Some(hit) = context.allowlists.match_rule(&pack_id, &pattern_name) {
+                if first_allowlist_hit.is_none() {
+                    let reason =
+                        format_heredoc_denial_reason(&content, &m, &pack_id, &pattern_name);
+                    let mapped_span = map_heredoc_span(command, &content, m.start, m.end);
+                    *first_allowlist_hit = Some((
+                        PatternMatch {
+                            pack_id: Some(pack_id),
+                            pattern_name: Some(pattern_name),
+                            severity: Some(ast_severity_to_pack_severity(m.severity)),
+                            reason,
+                            source: MatchSource::HeredocAst,
+                            matched_span: mapped_span,
+                            matched_text_preview: Some(m.matched_text_preview),
+                            explanation: None,
+                            suggestions: &[],
+                        },
+                        hit.layer,
+                        hit.entry.reason.clone(),
+                    ));
+                }
+                continue;
+            }
+
+            let reason = format_heredoc_denial_reason(&content, &m, &pack_id, &pattern_name);
+            let mapped_span = map_heredoc_span(command, &content, m.start, m.end);
+            return Some(EvaluationResult {
+                decision: Decision::Deny,
+                pattern_info: Some(PatternMatch {
+                    pack_id: Some(pack_id),
+                    pattern_name: Some(pattern_name),
+                    severity: Some(ast_severity_to_pack_severity(m.severity)),
+                    reason,
+                    source: MatchSource::HeredocAst,
+                    matched_span: mapped_span,
+                    matched_text_preview: Some(m.matched_text_preview),
+                    explanation: None,
+                    suggestions: &[],
+                }),
+                allowlist_override: None,
+                effective_mode: Some(crate::packs::DecisionMode::Deny),
+                skipped_due_to_budget: false,
+                branch_context: None,
+            });
+        }
+    }
+
+    if fallback_needed {
+        if let Some(blocked) = check_fallback_patterns(command) {
+            return Some(blocked);
+        }
+    }
+
+    None
+}
+
+/// Map the database client that receives a SQL heredoc to its pack ID.
+///
+/// Uses the last client name that appears before the heredoc operator, so
+/// wrappers and connection flags (`sudo -u postgres psql -d prod <<EOF`) are
+/// skipped.
+fn sql_heredoc_pack_id(
+    command: &str,
+    content: &crate::heredoc::ExtractedContent,
+) -> Option<&'static str> {
+    let end = content.byte_range.start.min(command.len());
+    command
+        .get(..end)?
+        .split(|c: char| c.is_whitespace() || matches!(c, '|' | ';' | '&' | '('))
+        .rev()
+        .find_map(|token| {
+            let name = token
+                .rsplit('/')
+                .next()
+                .unwrap_or(token)
+                .to_ascii_lowercase();
+            let name = name.strip_suffix(".exe").unwrap_or(&name);
+            match name {
+                "psql" => Some("database.postgresql"),
+                "mysql" | "mariadb" => Some("database.mysql"),
+                _ if name.starts_with("sqlite") => Some("database.sqlite"),
+                _ => None,
+            }
+        })
+}
+
+/// Evaluate a SQL heredoc body against the database pack for its client.
+///
+/// The body is split into `;`-terminated statements; each statement is checked
+/// against the pack's safe patterns and then its destructive patterns. Only
+/// packs that are enabled are consulted.
+fn evaluate_sql_heredoc(
+    command: &str,
+    content: &crate::heredoc::ExtractedContent,
+    context: HeredocEvaluationContext<'_>,
+    first_allowlist_hit: &mut Option<(PatternMatch, AllowlistLayer, String)>,
+) -> Option<EvaluationResult> {
+    let pack_id = sql_heredoc_pack_id(command, content)?;
+    if !context.ordered_packs.iter().any(|id| id == pack_id) {
+        return None;
+    }
+    let pack = REGISTRY.get_entry(pack_id)?.get_pack();
+
+    let body = content.content.as_str();
+    let mut offset = 0;
+    for statement in body.split_inclusive(';') {
+        let start = offset;
+        offset += statement.len();
+
+        if deadline_exceeded(context.deadline)
+            || remaining_below(context.deadline, &crate::perf::PATTERN_MATCH)
+        {
+            return Some(EvaluationResult::allowed_due_to_budget());
+        }
+        if statement.trim().is_empty() || pack.matches_safe_command(statement) {
+            continue;
+        }
+
+        for pattern in &pack.destructive_patterns {
+            if !pattern.enabled || !pattern.severity.blocks_by_default() {
+                continue;
+            }
+            let Some((m_start, m_end)) = pattern.regex.find(statement) else {
+                continue;
+            };
+
+            let body_start = start + m_start;
+            let line_number = body[..body_start].matches('\n').count() + 1;
+            let matched_span = map_heredoc_span(command, content, body_start, start + m_end);
+            let preview = matched_span.as_ref().map_or_else(
+                || {
+                    extract_match_preview(
+                        body,
+                        &MatchSpan {
+                            start: body_start,
+                            end: start + m_end,
+                        },
+                    )
+                },
+                |span| extract_match_preview(command, span),
+            );
+            let info = PatternMatch {
+                pack_id: Some(pack_id.to_string()),
+                pattern_name: pattern.name.map(str::to_string),
+                severity: Some(pattern.severity),
+                reason: format!(
+                    "Embedded SQL blocked: {} (line {line_number} of heredoc)",
+                    pattern.reason
+                ),
+                source: MatchSource::HeredocAst,
+                matched_span,
+                matched_text_preview: Some(preview),
+                explanation: pattern.explanation.map(str::to_string),
+                suggestions: pattern.suggestions,
+            };
+
+            if let Some(pattern_name) = pattern.name {
+                if let Some(hit) = context.allowlists.match_rule_at_path(
+                    pack_id,
+                    pattern_name,
+                    context.project_path,
+                ) {
+                    if first_allowlist_hit.is_none() {
+                        *first_allowlist_hit = Some((info, hit.layer, hit.entry.reason.clone()));
+                    }
+                    break;
+                }
+            }
+
+            return Some(EvaluationResult {
+                decision: Decision::Deny,
+                pattern_info: Some(info),
+                allowlist_override: None,
+                effective_mode: Some(crate::packs::DecisionMode::Deny),
+                skipped_due_to_budget: false,
+                branch_context: None,
+            });
+        }
+    }
+
+    None
+}
+
+#[allow(dead_code)]
+fn check_fallback_patterns(command: &str) -> Option<EvaluationResult> {
+    // List of critical destructive patterns to check when AST analysis is skipped (e.g. oversized input).
+    // These patterns must be robust to whitespace variations where applicable.
+    static FALLBACK_PATTERNS: LazyLock<RegexSet> = LazyLock::new(|| {
+        RegexSet::new([
+            r"shutil\.rmtree",
+            r"os\.remove",
+            r"os\.rmdir",
+            r"os\.unlink",
+            r"fs\.rmSync",
+            r"fs\.rmdirSync",
+            r"child_process\.execSync",
+            r"child_process\.spawnSync",
+            r"os\.RemoveAll",
+            r"\brm\s+(?:-[a-zA-Z]*r[a-zA-Z]*f|-[a-zA-Z]*f[a-zA-Z]*r)\b", // rm -rf, rm -fr, rm -r -f
+            r"\bgit\s+reset\s+--hard\b",
+        ])
+        .expect("fallback patterns must compile")
+    });
+
+    // Sanitize the command first to mask comments and safe arguments (e.g. commit messages).
+    // This prevents false positives where a destructive command is mentioned in a comment
+    // inside a large heredoc.
+    let sanitized = sanitize_for_pattern_matching(command);
+    let check_target = sanitized.as_ref();
+
+    if FALLBACK_PATTERNS.is_match(check_target) {
+        return Some(EvaluationResult::denied_by_legacy(
+            "Oversized command contains destructive pattern (fallback check)",
+        ));
+    }
+
+    None
+}
+
+fn split_ast_rule_id(rule_id: &str) -> (String, String) {
+    // Expected format: heredoc.<language>.<pattern>[.<suffix>...]
+    if let Some(rest) = rule_id.strip_prefix("heredoc.") {
+        if let Some((lang, tail)) = rest.split_once('.') {
+            let pack_id = format!("heredoc.{lang}");
+            return (pack_id, tail.to_string());
+        }
+        return ("heredoc".to_string(), rule_id.to_string());
+    }
+
+    // Fallback: best-effort split on last dot.
+    if let Some((pack_id, pattern_name)) = rule_id.rsplit_once('.') {
+        return (pack_id.to_string(), pattern_name.to_string());
+    }
+
+    ("unknown".to_string(), rule_id.to_string())
+}
+
+fn format_heredoc_denial_reason(
+    extracted: &crate::heredoc::ExtractedContent,
+    m: &crate::ast_matcher::PatternMatch,
+    pack_id: &str,
+    pattern_name: &str,
+) -> String {
+    let lang = match extracted.language {
+        crate::heredoc::ScriptLanguage::Bash => "bash",
+        crate::heredoc::ScriptLanguage::Go => "go",
+        crate::heredoc::ScriptLanguage::Python => "python",
+        crate::heredoc::ScriptLanguage::Ruby => "ruby",
+        crate::heredoc::ScriptLanguage::Perl => "perl",
+        crate::heredoc::ScriptLanguage::JavaScript => "javascript",
+        crate::heredoc::ScriptLanguage::TypeScript => "typescript",
+        crate::heredoc::ScriptLanguage::Php => "php",
+        crate::heredoc::ScriptLanguage::Sql => "sql",
+        crate::heredoc::ScriptLanguage::Unknown => "unknown",
+    };
+
+    format!(
+        "Embedded {lang} code blocked: {} (rule {pack_id}:{pattern_name}, line {}, matched: {})",
+        m.reason, m.line_number, m.matched_text_preview
+    )
+}
+
+fn map_heredoc_span(
+    command: &str,
+    content: &crate::heredoc::ExtractedContent,
+    start: usize,
+    end: usize,
+) -> Option<MatchSpan> {
+    let range = content.content_range.as_ref()?;
+    let raw = command.get(range.clone())?;
+    if raw.len() != content.content.len() {
+        return None;
+    }
+    if raw != content.content {
+        return None;
+    }
+
+    let mapped_start = range.start.saturating_add(start);
+    let mapped_end = range.start.saturating_add(end);
+    if mapped_start <= mapped_end && mapped_end <= command.len() {
+        Some(MatchSpan {
+            start: mapped_start,
+            end: mapped_end,
+        })
+    } else {
+        None
+    }
+}
+
+/// Trait for legacy safe patterns.
+pub trait LegacySafePattern {
+    /// Check if the pattern matches the command.
+    fn is_match(&self, cmd: &str) -> bool;
+}
+
+/// Trait for legacy destructive patterns.
+pub trait LegacyDestructivePattern {
+    /// Check if the pattern matches the command.
+    fn is_match(&self, cmd: &str) -> bool;
+    /// Find the first match span, if available.
+    fn find_span(&self, cmd: &str) -> Option<MatchSpan> {
+        let _ = cmd;
+        None
+    }
+    /// Get the reason for blocking.
+    fn reason(&self) -> &str;
+}
+
+impl LegacySafePattern for crate::packs::SafePattern {
+    fn is_match(&self, cmd: &str) -> bool {
+        self.regex.is_match(cmd)
+    }
+}
+
+impl LegacyDestructivePattern for crate::packs::DestructivePattern {
+    fn is_match(&self, cmd: &str) -> bool {
+        self.regex.is_match(cmd)
+    }
+
+    fn find_span(&self, cmd: &str) -> Option<MatchSpan> {
+        self.regex
+            .find(cmd)
+            .map(|(start, end)| MatchSpan { start, end })
+    }
+
+    fn reason(&self) -> &str {
+        self.reason
+    }
+}
+
+// =============================================================================
+// Confidence Scoring Integration (git_safety_guard-t8x.5)
+// =============================================================================
+
+/// Result of applying confidence scoring to a decision.
+#[derive(Debug, Clone)]
+pub struct ConfidenceResult {
+    /// The (potentially adjusted) decision mode.
+    pub mode: crate::packs::DecisionMode,
+    /// The confidence score (if computed).
+    pub score: Option<crate::confidence::ConfidenceScore>,
+    /// Whether the mode was downgraded due to low confidence.
+    pub downgraded: bool,
+}
+
+/// Apply confidence scoring to potentially downgrade a Deny to Warn.
+///
+/// This function computes a confidence score for the pattern match and
+/// optionally downgrades the decision mode if confidence is low.
+///
+/// # Arguments
+///
+/// * `command` - The original command being evaluated
+/// * `sanitized_command` - The sanitized version (with safe data masked), if available
+/// * `result` - The evaluation result (must have `pattern_info` for confidence to apply)
+/// * `current_mode` - The decision mode from policy resolution
+/// * `config` - Confidence scoring configuration
+///
+/// # Returns
+///
+/// A `ConfidenceResult` with the (potentially adjusted) mode and confidence details.
+#[must_use]
+pub fn apply_confidence_scoring(
+    command: &str,
+    sanitized_command: Option<&str>,
+    result: &EvaluationResult,
+    current_mode: crate::packs::DecisionMode,
+    config: &crate::config::ConfidenceConfig,
+) -> ConfidenceResult {
+    // If confidence scoring is disabled, return unchanged mode
+    if !config.enabled {
+        return ConfidenceResult {
+            mode: current_mode,
+            score: None,
+            downgraded: false,
+        };
+    }
+
+    // Only apply confidence scoring to Deny decisions that might be downgraded
+    if current_mode != crate::packs::DecisionMode::Deny {
+        return ConfidenceResult {
+            mode: current_mode,
+            score: None,
+            downgraded: false,
+        };
+    }
+
+    // Need pattern info to compute confidence
+    let Some(info) = &result.pattern_info else {
+        return ConfidenceResult {
+            mode: current_mode,
+            score: None,
+            downgraded: false,
+        };
+    };
+
+    // Protect Critical severity from downgrading (if configured)
+    if config.protect_critical
+        && info
+            .severity
+            .is_some_and(|s| s == crate::packs::Severity::Critical)
+    {
+        return ConfidenceResult {
+            mode: current_mode,
+            score: None,
+            downgraded: false,
+        };
+    }
+
+    // Get match span for confidence computation
+    let Some(span) = &info.matched_span else {
+        // No span = can't compute confidence = conservative (keep Deny)
+        return ConfidenceResult {
+            mode: current_mode,
+            score: None,
+            downgraded: false,
+        };
+    };
+
+    // Compute confidence
+    let ctx = crate::confidence::ConfidenceContext {
+        command,
+        sanitized_command,
+        match_start: span.start,
+        match_end: span.end,
+    };
+    let score = crate::confidence::compute_match_confidence(&ctx);
+
+    // Check if we should downgrade
+    let should_downgrade = score.is_low(config.warn_threshold);
+    let new_mode = if should_downgrade {
+        crate::packs::DecisionMode::Warn
+    } else {
+        current_mode
+    };
+
+    ConfidenceResult {
+        mode: new_mode,
+        score: Some(score),
+        downgraded: should_downgrade,
+    }
+}
+
+/// Apply git branch-aware strictness to an evaluation result.
+///
+/// This function modifies the evaluation result based on the current git branch:
+/// - On protected branches (e.g., main, master), stricter settings are applied
+/// - On relaxed branches (e.g., feature/*), more permissive settings are applied
+/// - The branch_context field is populated with branch information
+///
+/// # Arguments
+/// * `result` - The original evaluation result
+/// * `config` - Configuration containing git_awareness settings
+/// * `project_path` - Optional path to the project directory (for branch detection)
+///
+/// # Returns
+/// A modified evaluation result with branch context applied.
+#[must_use]
+pub fn apply_branch_strictness(
+    mut result: EvaluationResult,
+    config: &Config,
+    project_path: Option<&Path>,
+) -> EvaluationResult {
+    // Early return if git awareness is disabled
+    let git_awareness = &config.git_awareness;
+    if !git_awareness.enabled {
+        return result;
+    }
 
-                continue;
-            }
-        };
+    // Get branch info
+    let branch_info = match project_path {
+        Some(path) => crate::git::get_branch_info_at_path(path),
+        None => crate::git::get_branch_info(),
+    };
 
-        for m in matches {
-            if deadline_exceeded(context.deadline)
-                || remaining_below(context.deadline, &crate::perf::FULL_HEREDOC_PIPELINE)
-            {
-                return Some(EvaluationResult::allowed_due_to_budget());
+    // Extract branch name if available
+    let branch_name = match &branch_info {
+        crate::git::BranchInfo::Branch(name) => Some(name.clone()),
+        crate::git::BranchInfo::DetachedHead(_) => None,
+        crate::git::BranchInfo::NotGitRepo => {
+            // Not in a git repo - graceful degradation with default strictness
+            tracing::debug!(
+                "Not in git repository, using default strictness (git_awareness enabled but no repo detected)"
+            );
+            // Optionally warn if configured
+            if config.git_awareness.warn_if_not_git {
+                tracing::warn!(
+                    "dcg git_awareness is enabled but not in a git repository - using default strictness"
+                );
             }
+            return result;
+        }
+    };
 
-            if !m.severity.blocks_by_default() {
-                continue;
-            }
+    // Determine branch characteristics
+    let is_protected = branch_name
+        .as_ref()
+        .is_some_and(|name| git_awareness.is_protected_branch(Some(name.as_str())));
+    let is_relaxed = branch_name
+        .as_ref()
+        .is_some_and(|name| git_awareness.is_relaxed_branch(Some(name.as_str())));
+    let strictness = git_awareness.strictness_for_branch(branch_name.as_deref());
 
-            let (pack_id, pattern_name) = split_ast_rule_id(&m.rule_id);
+    // Determine if the decision should be affected
+    let mut affected_decision = false;
 
-            if let Some(hit) = context.allowlists.match_rule(&pack_id, &pattern_name) {
-                if first_allowlist_hit.is_none() {
-                    let reason =
-                        format_heredoc_denial_reason(&content, &m, &pack_id, &pattern_name);
-                    let mapped_span = map_heredoc_span(command, &content, m.start, m.end);
-                    *first_allowlist_hit = Some((
-                        PatternMatch {
-                            pack_id: Some(pack_id),
-                            pattern_name: Some(pattern_name),
-                            severity: Some(ast_severity_to_pack_severity(m.severity)),
-                            reason,
-                            source: MatchSource::HeredocAst,
-                            matched_span: mapped_span,
-                            matched_text_preview: Some(m.matched_text_preview),
-                            explanation: None,
-                            suggestions: &[],
-                        },
-                        hit.layer,
-                        hit.entry.reason.clone(),
-                    ));
+    // If the result is Deny and we have severity info, check strictness
+    if result.is_denied() {
+        if let Some(ref pattern_info) = result.pattern_info {
+            if let Some(severity) = pattern_info.severity {
+                // Check if this severity should be blocked at the current strictness
+                if !strictness.should_block(severity) {
+                    // Convert Deny to Allow because strictness permits it
+                    result.decision = Decision::Allow;
+                    affected_decision = true;
                 }
-                continue;
             }
-
-            let reason = format_heredoc_denial_reason(&content, &m, &pack_id, &pattern_name);
-            let mapped_span = map_heredoc_span(command, &content, m.start, m.end);
-            return Some(EvaluationResult {
-                decision: EvaluationDecision::Deny,
-                pattern_info: Some(PatternMatch {
-                    pack_id: Some(pack_id),
-                    pattern_name: Some(pattern_name),
-                    severity: Some(ast_severity_to_pack_severity(m.severity)),
-                    reason,
-                    source: MatchSource::HeredocAst,
-                    matched_span: mapped_span,
-                    matched_text_preview: Some(m.matched_text_preview),
-                    explanation: None,
-                    suggestions: &[],
-                }),
-                allowlist_override: None,
-                effective_mode: Some(crate::packs::DecisionMode::Deny),
-                skipped_due_to_budget: false,
-                branch_context: None,
-            });
         }
     }
 
-    if fallback_needed {
-        if let Some(blocked) = check_fallback_patterns(command) {
-            return Some(blocked);
-        }
-    }
+    // Populate branch context
+    result.branch_context = Some(BranchContext {
+        branch_name,
+        is_protected,
+        is_relaxed,
+        strictness,
+        affected_decision,
+    });
 
-    None
+    result
 }
 
-#[allow(dead_code)]
-fn check_fallback_patterns(command: &str) -> Option<EvaluationResult> {
-    // List of critical destructive patterns to check when AST analysis is skipped (e.g. oversized input).
-    // These patterns must be robust to whitespace variations where applicable.
-    static FALLBACK_PATTERNS: LazyLock<RegexSet> = LazyLock::new(|| {
-        RegexSet::new([
-            r"shutil\.rmtree",
-            r"os\.remove",
-            r"os\.rmdir",
-            r"os\.unlink",
-            r"fs\.rmSync",
-            r"fs\.rmdirSync",
-            r"child_process\.execSync",
-            r"child_process\.spawnSync",
-            r"os\.RemoveAll",
-            r"\brm\s+(?:-[a-zA-Z]*r[a-zA-Z]*f|-[a-zA-Z]*f[a-zA-Z]*r)\b", // rm -rf, rm -fr, rm -r -f
-            r"\bgit\s+reset\s+--hard\b",
-        ])
-        .expect("fallback patterns must compile")
-    });
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allowlist::{
+        AllowEntry, AllowSelector, AllowlistFile, LoadedAllowlistLayer, RuleId,
+    };
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
-    // Sanitize the command first to mask comments and safe arguments (e.g. commit messages).
-    // This prevents false positives where a destructive command is mentioned in a comment
-    // inside a large heredoc.
-    let sanitized = sanitize_for_pattern_matching(command);
-    let check_target = sanitized.as_ref();
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
 
-    if FALLBACK_PATTERNS.is_match(check_target) {
-        return Some(EvaluationResult::denied_by_legacy(
-            "Oversized command contains destructive pattern (fallback check)",
-        ));
+    fn default_config() -> Config {
+        Config::default()
     }
 
-    None
-}
+    fn default_compiled_overrides() -> crate::config::CompiledOverrides {
+        crate::config::CompiledOverrides::default()
+    }
 
-fn split_ast_rule_id(rule_id: &str) -> (String, String) {
-    // Expected format: heredoc.<language>.<pattern>[.<suffix>...]
-    if let Some(rest) = rule_id.strip_prefix("heredoc.") {
-        if let Some((lang, tail)) = rest.split_once('.') {
-            let pack_id = format!("heredoc.{lang}");
-            return (pack_id, tail.to_string());
+    fn default_allowlists() -> LayeredAllowlist {
+        LayeredAllowlist::default()
+    }
+
+    fn project_allowlists_for_rule(rule: &str, reason: &str) -> LayeredAllowlist {
+        let rule = RuleId::parse(rule).expect("rule id must parse");
+        LayeredAllowlist {
+            layers: vec![LoadedAllowlistLayer {
+                layer: AllowlistLayer::Project,
+                path: PathBuf::from("project-allowlist.toml"),
+                file: AllowlistFile {
+                    entries: vec![AllowEntry {
+                        selector: AllowSelector::Rule(rule),
+                        reason: reason.to_string(),
+                        added_by: None,
+                        added_at: None,
+                        expires_at: None,
+                        ttl: None,
+                        session: None,
+                        context: None,
+                        conditions: HashMap::new(),
+                        environments: Vec::new(),
+                        paths: None,
+                        risk_acknowledged: false,
+                    }],
+                    errors: Vec::new(),
+                },
+            }],
         }
-        return ("heredoc".to_string(), rule_id.to_string());
     }
 
-    // Fallback: best-effort split on last dot.
-    if let Some((pack_id, pattern_name)) = rule_id.rsplit_once('.') {
-        return (pack_id.to_string(), pattern_name.to_string());
+    #[allow(dead_code)]
+    fn project_allowlists_for_pack_wildcard(pack_id: &str, reason: &str) -> LayeredAllowlist {
+        LayeredAllowlist {
+            layers: vec![LoadedAllowlistLayer {
+                layer: AllowlistLayer::Project,
+                path: PathBuf::from("project-allowlist.toml"),
+                file: AllowlistFile {
+                    entries: vec![AllowEntry {
+                        selector: AllowSelector::Rule(RuleId {
+                            pack_id: pack_id.to_string(),
+                            pattern_name: "*".to_string(),
+                        }),
+                        reason: reason.to_string(),
+                        added_by: None,
+                        added_at: None,
+                        expires_at: None,
+                        ttl: None,
+                        session: None,
+                        context: None,
+                        conditions: HashMap::new(),
+                        environments: Vec::new(),
+                        paths: None,
+                        risk_acknowledged: false,
+                    }],
+                    errors: Vec::new(),
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn test_empty_command_allowed() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let result = evaluate_command("", &config, &[], &compiled, &allowlists);
+        assert!(result.is_allowed());
+        assert!(result.pattern_info.is_none());
+    }
+
+    #[test]
+    fn test_safe_command_allowed() {
+        let config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let result = evaluate_command("ls -la", &config, &["git", "rm"], &compiled, &allowlists);
+        assert!(result.is_allowed());
+    }
+
+    #[test]
+    fn test_result_helper_methods() {
+        let allowed = EvaluationResult::allowed();
+        assert!(allowed.is_allowed());
+        assert!(!allowed.is_denied());
+        assert!(allowed.reason().is_none());
+        assert!(allowed.pack_id().is_none());
+
+        let denied = EvaluationResult::denied_by_pack("test.pack", "test reason", None);
+        assert!(!denied.is_allowed());
+        assert!(denied.is_denied());
+        assert_eq!(denied.reason(), Some("test reason"));
+        assert_eq!(denied.pack_id(), Some("test.pack"));
     }
 
-    ("unknown".to_string(), rule_id.to_string())
-}
+    #[test]
+    fn test_denied_by_config() {
+        let denied = EvaluationResult::denied_by_config("config block".to_string());
+        assert!(denied.is_denied());
+        assert_eq!(denied.reason(), Some("config block"));
+        assert!(denied.pack_id().is_none());
+        assert_eq!(
+            denied.pattern_info.as_ref().unwrap().source,
+            MatchSource::ConfigOverride
+        );
+    }
 
-fn format_heredoc_denial_reason(
-    extracted: &crate::heredoc::ExtractedContent,
-    m: &crate::ast_matcher::PatternMatch,
-    pack_id: &str,
-    pattern_name: &str,
-) -> String {
-    let lang = match extracted.language {
-        crate::heredoc::ScriptLanguage::Bash => "bash",
-        crate::heredoc::ScriptLanguage::Go => "go",
-        crate::heredoc::ScriptLanguage::Python => "python",
-        crate::heredoc::ScriptLanguage::Ruby => "ruby",
-        crate::heredoc::ScriptLanguage::Perl => "perl",
-        crate::heredoc::ScriptLanguage::JavaScript => "javascript",
-        crate::heredoc::ScriptLanguage::TypeScript => "typescript",
-        crate::heredoc::ScriptLanguage::Php => "php",
-        crate::heredoc::ScriptLanguage::Unknown => "unknown",
-    };
+    #[test]
+    fn test_strict_quoting_denies_dangling_quote() {
+        let mut config = default_config();
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let keywords: Vec<&str> = vec!["git"];
 
-    format!(
-        "Embedded {lang} code blocked: {} (rule {pack_id}:{pattern_name}, line {}, matched: {})",
-        m.reason, m.line_number, m.matched_text_preview
-    )
-}
+        let result = evaluate_command(
+            "git reset --hard \"",
+            &config,
+            &keywords,
+            &compiled,
+            &allowlists,
+        );
+        assert_ne!(result.reason(), Some("ambiguous quoting"));
 
-fn map_heredoc_span(
-    command: &str,
-    content: &crate::heredoc::ExtractedContent,
-    start: usize,
-    end: usize,
-) -> Option<MatchSpan> {
-    let range = content.content_range.as_ref()?;
-    let raw = command.get(range.clone())?;
-    if raw.len() != content.content.len() {
-        return None;
-    }
-    if raw != content.content {
-        return None;
+        config.evaluation.strict_quoting = true;
+        let result = evaluate_command(
+            "git reset --hard \"",
+            &config,
+            &keywords,
+            &compiled,
+            &allowlists,
+        );
+        assert!(result.is_denied());
+        assert_eq!(result.reason(), Some("ambiguous quoting"));
     }
 
-    let mapped_start = range.start.saturating_add(start);
-    let mapped_end = range.start.saturating_add(end);
-    if mapped_start <= mapped_end && mapped_end <= command.len() {
-        Some(MatchSpan {
-            start: mapped_start,
-            end: mapped_end,
-        })
-    } else {
-        None
-    }
-}
+    #[test]
+    fn test_powershell_mode_denies_remove_item_recurse_force() {
+        let mut config = default_config();
+        let command = r"Remove-Item -Recurse -Force C:\data";
+        assert!(!evaluate_detailed(command, &config).result.is_denied());
 
-/// Trait for legacy safe patterns.
-pub trait LegacySafePattern {
-    /// Check if the pattern matches the command.
-    fn is_match(&self, cmd: &str) -> bool;
-}
+        config.evaluation.shell = ShellDialect::PowerShell;
+        let result = evaluate_detailed(command, &config).result;
+        assert!(result.is_denied());
+        assert_eq!(
+            result
+                .pattern_info
+                .as_ref()
+                .and_then(|p| p.pack_id.as_deref()),
+            Some("windows.powershell")
+        );
 
-/// Trait for legacy destructive patterns.
-pub trait LegacyDestructivePattern {
-    /// Check if the pattern matches the command.
-    fn is_match(&self, cmd: &str) -> bool;
-    /// Find the first match span, if available.
-    fn find_span(&self, cmd: &str) -> Option<MatchSpan> {
-        let _ = cmd;
-        None
+        // Aliases and abbreviated parameters resolve to the same cmdlet.
+        let result = evaluate_detailed(r"rm -r -fo C:\data", &config).result;
+        assert!(result.is_denied());
+        assert_eq!(
+            result
+                .pattern_info
+                .as_ref()
+                .and_then(|p| p.pattern_name.as_deref()),
+            Some("remove-item-recurse-force")
+        );
+        assert!(
+            !evaluate_detailed(r"Remove-Item C:\data\file.txt", &config)
+                .result
+                .is_denied()
+        );
     }
-    /// Get the reason for blocking.
-    fn reason(&self) -> &str;
-}
 
-impl LegacySafePattern for crate::packs::SafePattern {
-    fn is_match(&self, cmd: &str) -> bool {
-        self.regex.is_match(cmd)
-    }
-}
+    #[test]
+    fn cd_into_system_directory_escalates_delete_to_critical() {
+        let config = default_config();
+        let severity = |command: &str| {
+            let result = evaluate_detailed(command, &config).result;
+            assert!(result.is_denied(), "{command}");
+            result.pattern_info.and_then(|info| info.severity)
+        };
 
-impl LegacyDestructivePattern for crate::packs::DestructivePattern {
-    fn is_match(&self, cmd: &str) -> bool {
-        self.regex.is_match(cmd)
+        assert_eq!(
+            severity("cd /etc && rm -rf *"),
+            Some(crate::packs::Severity::Critical)
+        );
+        assert_eq!(
+            severity("cd / ; rm -rf ./*"),
+            Some(crate::packs::Severity::Critical)
+        );
+        let baseline = severity("rm -rf *");
+        assert_ne!(baseline, Some(crate::packs::Severity::Critical));
+        assert_eq!(severity("cd ./build && rm -rf *"), baseline);
+        // `rm` only runs when `cd` failed, so it stays in the original directory.
+        assert_eq!(severity("cd /etc || rm -rf *"), baseline);
+        // Absolute targets do not depend on the directory.
+        assert_eq!(
+            severity("cd /etc && rm -rf /opt/app/cache"),
+            severity("rm -rf /opt/app/cache")
+        );
+
+        let result = evaluate_detailed("cd /usr/lib && rm -rf *", &config).result;
+        assert!(
+            result
+                .reason()
+                .is_some_and(|reason| reason.ends_with("(after cd /usr/lib)"))
+        );
     }
 
-    fn find_span(&self, cmd: &str) -> Option<MatchSpan> {
-        self.regex
-            .find(cmd)
-            .map(|(start, end)| MatchSpan { start, end })
+    #[test]
+    fn delete_after_sensitive_cd_tracks_literal_chain() {
+        assert_eq!(
+            delete_after_sensitive_cd("cd /etc && cd nginx && rm -r sites").as_deref(),
+            Some("/etc/nginx")
+        );
+        assert_eq!(
+            delete_after_sensitive_cd("cd && git clean -fdx").as_deref(),
+            Some("~")
+        );
+        assert!(delete_after_sensitive_cd("cd /home/me/project && rm -rf *").is_none());
+        assert!(delete_after_sensitive_cd("cd \"$DIR\" && rm -rf *").is_none());
+        assert!(delete_after_sensitive_cd("cd / | rm -rf *").is_none());
+        assert!(delete_after_sensitive_cd("cd /etc && ls; rm file.txt").is_none());
     }
 
-    fn reason(&self) -> &str {
-        self.reason
+    #[test]
+    fn brace_expansion_exposes_each_target() {
+        let config = default_config();
+        assert!(
+            evaluate_detailed("rm -rf /{bin,etc}", &config)
+                .result
+                .is_denied()
+        );
+        assert!(
+            evaluate_detailed("git push origin --{force,tags}", &config)
+                .result
+                .is_denied()
+        );
+        assert!(
+            evaluate_detailed("git push origin --tags", &config)
+                .result
+                .is_allowed()
+        );
+        assert!(evaluate_detailed("echo {a,b}", &config).result.is_allowed());
+        assert!(
+            evaluate_detailed("echo {1..100000}", &config)
+                .result
+                .is_allowed()
+        );
     }
-}
 
-// =============================================================================
-// Confidence Scoring Integration (git_safety_guard-t8x.5)
-// =============================================================================
+    #[test]
+    fn chattr_immutable_removal_escalates_delete_to_critical() {
+        let config = default_config();
+        let severity = |command: &str| {
+            let result = evaluate_detailed(command, &config).result;
+            assert!(result.is_denied(), "{command}");
+            result.pattern_info.and_then(|info| info.severity)
+        };
 
-/// Result of applying confidence scoring to a decision.
-#[derive(Debug, Clone)]
-pub struct ConfidenceResult {
-    /// The (potentially adjusted) decision mode.
-    pub mode: crate::packs::DecisionMode,
-    /// The confidence score (if computed).
-    pub score: Option<crate::confidence::ConfidenceScore>,
-    /// Whether the mode was downgraded due to low confidence.
-    pub downgraded: bool,
-}
+        let baseline = severity("rm -rf build/important");
+        assert_ne!(baseline, Some(crate::packs::Severity::Critical));
+        assert_eq!(
+            severity("chattr -i build/important && rm -rf build/important"),
+            Some(crate::packs::Severity::Critical)
+        );
+        assert_eq!(
+            severity("sudo chattr -R -i build/important; sudo rm -rf build/important/data"),
+            Some(crate::packs::Severity::Critical)
+        );
+        // Unrelated paths and adding immutability leave the match unchanged.
+        assert_eq!(
+            severity("chattr -i build/other && rm -rf build/important"),
+            baseline
+        );
+        assert_eq!(
+            severity("chattr +i build/important && rm -rf build/important"),
+            baseline
+        );
 
-/// Apply confidence scoring to potentially downgrade a Deny to Warn.
-///
-/// This function computes a confidence score for the pattern match and
-/// optionally downgrades the decision mode if confidence is low.
-///
-/// # Arguments
-///
-/// * `command` - The original command being evaluated
-/// * `sanitized_command` - The sanitized version (with safe data masked), if available
-/// * `result` - The evaluation result (must have `pattern_info` for confidence to apply)
-/// * `current_mode` - The decision mode from policy resolution
-/// * `config` - Confidence scoring configuration
-///
-/// # Returns
-///
-/// A `ConfidenceResult` with the (potentially adjusted) mode and confidence details.
-#[must_use]
-pub fn apply_confidence_scoring(
-    command: &str,
-    sanitized_command: Option<&str>,
-    result: &EvaluationResult,
-    current_mode: crate::packs::DecisionMode,
-    config: &crate::config::ConfidenceConfig,
-) -> ConfidenceResult {
-    // If confidence scoring is disabled, return unchanged mode
-    if !config.enabled {
-        return ConfidenceResult {
-            mode: current_mode,
-            score: None,
-            downgraded: false,
-        };
+        let result = evaluate_detailed(
+            "chattr -i build/important && rm -rf build/important",
+            &config,
+        )
+        .result;
+        assert!(
+            result
+                .reason()
+                .is_some_and(|reason| reason.ends_with("(after chattr -i build/important)"))
+        );
     }
 
-    // Only apply confidence scoring to Deny decisions that might be downgraded
-    if current_mode != crate::packs::DecisionMode::Deny {
-        return ConfidenceResult {
-            mode: current_mode,
-            score: None,
-            downgraded: false,
-        };
+    #[test]
+    fn path_after_immutable_removal_tracks_chattr_modes() {
+        assert_eq!(
+            path_after_immutable_removal("chattr -ia /srv/a && shred /srv/a/key").as_deref(),
+            Some("/srv/a")
+        );
+        assert_eq!(
+            path_after_immutable_removal("chattr = /srv/a && rm /srv/a").as_deref(),
+            Some("/srv/a")
+        );
+        assert!(path_after_immutable_removal("chattr =i /srv/a && rm /srv/a").is_none());
+        assert!(path_after_immutable_removal("rm -rf /srv/a && chattr -i /srv/a").is_none());
+        assert!(path_after_immutable_removal("chattr -i /srv/ab && rm /srv/a").is_none());
     }
 
-    // Need pattern info to compute confidence
-    let Some(info) = &result.pattern_info else {
-        return ConfidenceResult {
-            mode: current_mode,
-            score: None,
-            downgraded: false,
-        };
-    };
+    #[test]
+    fn echo_truncating_shell_config_is_denied_but_append_is_allowed() {
+        let mut config = default_config();
+        config.packs.enabled.push("system.shell_config".to_string());
 
-    // Protect Critical severity from downgrading (if configured)
-    if config.protect_critical
-        && info
-            .severity
-            .is_some_and(|s| s == crate::packs::Severity::Critical)
-    {
-        return ConfidenceResult {
-            mode: current_mode,
-            score: None,
-            downgraded: false,
-        };
+        let result = evaluate_detailed("echo 'alias ll=ls' > ~/.bashrc", &config).result;
+        assert!(result.is_denied());
+        assert_eq!(result.pack_id(), Some("system.shell_config"));
+
+        let result = evaluate_detailed("echo 'alias ll=ls' >> ~/.bashrc", &config).result;
+        assert!(result.is_allowed());
     }
 
-    // Get match span for confidence computation
-    let Some(span) = &info.matched_span else {
-        // No span = can't compute confidence = conservative (keep Deny)
-        return ConfidenceResult {
-            mode: current_mode,
-            score: None,
-            downgraded: false,
-        };
-    };
+    #[test]
+    fn noclobber_override_reaches_shell_config_pack() {
+        let mut config = default_config();
+        config.packs.enabled.push("system.shell_config".to_string());
+
+        let result = evaluate_detailed("echo x >| important.conf", &config).result;
+        let info = result.pattern_info.expect("`>|` must match");
+        assert_eq!(info.pattern_name.as_deref(), Some("noclobber-override"));
+        assert_eq!(info.severity, Some(crate::packs::Severity::Medium));
 
-    // Compute confidence
-    let ctx = crate::confidence::ConfidenceContext {
-        command,
-        sanitized_command,
-        match_start: span.start,
-        match_end: span.end,
-    };
-    let score = crate::confidence::compute_match_confidence(&ctx);
+        let result = evaluate_detailed("echo x > important.conf", &config).result;
+        assert!(result.pattern_info.is_none());
+    }
 
-    // Check if we should downgrade
-    let should_downgrade = score.is_low(config.warn_threshold);
-    let new_mode = if should_downgrade {
-        crate::packs::DecisionMode::Warn
-    } else {
-        current_mode
-    };
+    #[test]
+    fn git_segments_are_classified_independently() {
+        let config = default_config();
 
-    ConfidenceResult {
-        mode: new_mode,
-        score: Some(score),
-        downgraded: should_downgrade,
+        for command in [
+            "git status && git reset --hard",
+            "git fetch && git reset --hard origin/main",
+            "git checkout -b tmp && git reset --hard origin/main",
+            "git clean -n; git clean -fd",
+            "git stash list || git stash clear",
+        ] {
+            let result = evaluate_detailed(command, &config).result;
+            assert!(result.is_denied(), "{command} should be denied");
+            assert_eq!(result.pack_id(), Some("core.git"), "{command}");
+        }
+
+        for command in ["git fetch && git status", "git checkout -b tmp && git log"] {
+            let result = evaluate_detailed(command, &config).result;
+            assert!(result.is_allowed(), "{command} should be allowed");
+        }
     }
-}
 
-/// Apply git branch-aware strictness to an evaluation result.
-///
-/// This function modifies the evaluation result based on the current git branch:
-/// - On protected branches (e.g., main, master), stricter settings are applied
-/// - On relaxed branches (e.g., feature/*), more permissive settings are applied
-/// - The branch_context field is populated with branch information
-///
-/// # Arguments
-/// * `result` - The original evaluation result
-/// * `config` - Configuration containing git_awareness settings
-/// * `project_path` - Optional path to the project directory (for branch detection)
-///
-/// # Returns
-/// A modified evaluation result with branch context applied.
-#[must_use]
-pub fn apply_branch_strictness(
-    mut result: EvaluationResult,
-    config: &Config,
-    project_path: Option<&Path>,
-) -> EvaluationResult {
-    // Early return if git awareness is disabled
-    let git_awareness = &config.git_awareness;
-    if !git_awareness.enabled {
-        return result;
+    #[test]
+    fn measured_evaluation_records_nonzero_duration() {
+        use std::time::{Duration, Instant};
+
+        let config = default_config();
+        let start = Instant::now();
+        let result = evaluate_detailed("git status", &config).result;
+        assert!(result.is_allowed());
+        assert!(crate::history::eval_duration_us(start.elapsed(), true) > 0);
+
+        // Sub-microsecond evaluations only round up when measuring.
+        assert_eq!(crate::history::eval_duration_us(Duration::ZERO, true), 1);
+        assert_eq!(crate::history::eval_duration_us(Duration::ZERO, false), 0);
     }
 
-    // Get branch info
-    let branch_info = match project_path {
-        Some(path) => crate::git::get_branch_info_at_path(path),
-        None => crate::git::get_branch_info(),
-    };
+    #[test]
+    fn removing_essential_package_escalates_to_deny() {
+        let mut config = default_config();
+        config.packs.enabled.push("system.packages".to_string());
+        let compiled = config.overrides.compile();
+        let allowlists = default_allowlists();
 
-    // Extract branch name if available
-    let branch_name = match &branch_info {
-        crate::git::BranchInfo::Branch(name) => Some(name.clone()),
-        crate::git::BranchInfo::DetachedHead(_) => None,
-        crate::git::BranchInfo::NotGitRepo => {
-            // Not in a git repo - graceful degradation with default strictness
-            tracing::debug!(
-                "Not in git repository, using default strictness (git_awareness enabled but no repo detected)"
+        let decision_for = |command: &str| {
+            let result = evaluate_command(
+                command,
+                &config,
+                &["apt", "dnf", "pacman"],
+                &compiled,
+                &allowlists,
             );
-            // Optionally warn if configured
-            if config.git_awareness.warn_if_not_git {
-                tracing::warn!(
-                    "dcg git_awareness is enabled but not in a git repository - using default strictness"
-                );
-            }
-            return result;
-        }
-    };
+            let info = result
+                .pattern_info
+                .as_ref()
+                .expect("package removal should match");
+            assert_eq!(info.pack_id.as_deref(), Some("system.packages"));
+            let mode = config.policy().resolve_mode(
+                info.pack_id.as_deref(),
+                info.pattern_name.as_deref(),
+                info.severity,
+            );
+            assert_eq!(result.effective_mode, Some(mode), "{command}");
+            result.decision
+        };
 
-    // Determine branch characteristics
-    let is_protected = branch_name
-        .as_ref()
-        .is_some_and(|name| git_awareness.is_protected_branch(Some(name.as_str())));
-    let is_relaxed = branch_name
-        .as_ref()
-        .is_some_and(|name| git_awareness.is_relaxed_branch(Some(name.as_str())));
-    let strictness = git_awareness.strictness_for_branch(branch_name.as_deref());
+        assert_eq!(decision_for("apt purge nginx"), Decision::Warn);
+        assert_eq!(decision_for("apt remove libc6"), Decision::Deny);
+        assert_eq!(
+            decision_for("sudo apt-get -y purge libc6:amd64"),
+            Decision::Deny
+        );
+        assert_eq!(decision_for("pacman -Rns systemd"), Decision::Deny);
+        assert_eq!(
+            decision_for("dnf remove linux-image-6.1.0-18-amd64"),
+            Decision::Deny
+        );
+    }
 
-    // Determine if the decision should be affected
-    let mut affected_decision = false;
+    #[test]
+    fn removed_packages_reads_removal_targets() {
+        assert_eq!(
+            removed_packages("sudo apt-get -y purge libc6:amd64 vim=2:9.0"),
+            vec!["libc6", "vim"]
+        );
+        assert_eq!(
+            removed_packages("pacman --noconfirm -Rns systemd"),
+            vec!["systemd"]
+        );
+        assert_eq!(
+            removed_packages("apt install nginx && dnf erase httpd"),
+            vec!["httpd"]
+        );
+        assert!(removed_packages("apt autoremove").is_empty());
+        assert!(removed_packages("pacman -Syu coreutils").is_empty());
+    }
 
-    // If the result is Deny and we have severity info, check strictness
-    if result.decision == EvaluationDecision::Deny {
-        if let Some(ref pattern_info) = result.pattern_info {
-            if let Some(severity) = pattern_info.severity {
-                // Check if this severity should be blocked at the current strictness
-                if !strictness.should_block(severity) {
-                    // Convert Deny to Allow because strictness permits it
-                    result.decision = EvaluationDecision::Allow;
-                    affected_decision = true;
-                }
-            }
-        }
+    #[test]
+    fn kubectl_target_reads_only_the_kubectl_segment() {
+        assert_eq!(
+            kubectl_target("kubectl --context prod-eu delete pod web -n kube-system"),
+            (Some("prod-eu"), Some("kube-system"))
+        );
+        assert!(
+            "kubectl get ns && echo -n kube-system"
+                .split(['|', ';', '&', '\n'])
+                .all(|segment| kubectl_target(segment) == (None, None))
+        );
     }
 
-    // Populate branch context
-    result.branch_context = Some(BranchContext {
-        branch_name,
-        is_protected,
-        is_relaxed,
-        strictness,
-        affected_decision,
-    });
+    #[test]
+    fn force_push_severity_depends_on_target_branch() {
+        let config = default_config();
 
-    result
-}
+        let result = evaluate_detailed("git push --force origin main", &config).result;
+        assert!(result.is_denied());
+        let info = result.pattern_info.as_ref().expect("pattern info");
+        assert_eq!(info.severity, Some(crate::packs::Severity::Critical));
+        assert!(info.reason.ends_with("(protected branch 'main')"));
+        assert_eq!(
+            result.effective_mode,
+            Some(crate::packs::DecisionMode::Deny)
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::allowlist::{
-        AllowEntry, AllowSelector, AllowlistFile, LoadedAllowlistLayer, RuleId,
-    };
-    use std::collections::HashMap;
-    use std::path::PathBuf;
-    use std::sync::atomic::{AtomicUsize, Ordering};
+        let result = evaluate_detailed("git push --force origin feature/x", &config).result;
+        assert_eq!(result.decision, Decision::Warn);
+        let info = result.pattern_info.as_ref().expect("pattern info");
+        assert_eq!(info.severity, Some(crate::packs::Severity::Medium));
+        assert_eq!(
+            result.effective_mode,
+            Some(crate::packs::DecisionMode::Warn)
+        );
 
-    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let result = evaluate_detailed("git push -f origin HEAD:release/1.2", &config).result;
+        let info = result.pattern_info.as_ref().expect("pattern info");
+        assert_eq!(info.severity, Some(crate::packs::Severity::Critical));
+    }
 
-    fn default_config() -> Config {
-        Config::default()
+    #[test]
+    fn force_push_without_explicit_branch_keeps_severity() {
+        let config = default_config();
+        // Where these land depends on push.default and the upstream config,
+        // not on the local branch name.
+        for command in [
+            "git push --force",
+            "git push --force origin",
+            "git push -f origin HEAD",
+            "git push -f origin HEAD:refs/tags/v1",
+        ] {
+            let result = evaluate_detailed(command, &config).result;
+            assert!(result.is_denied(), "{command} should stay denied");
+            let info = result.pattern_info.as_ref().expect("pattern info");
+            assert_eq!(
+                info.severity,
+                Some(crate::packs::Severity::Critical),
+                "{command}"
+            );
+        }
     }
 
-    fn default_compiled_overrides() -> crate::config::CompiledOverrides {
-        crate::config::CompiledOverrides::default()
+    #[test]
+    fn force_push_downgrade_keeps_configured_rule_mode() {
+        let mut config = default_config();
+        config.policy.rules.insert(
+            "core.git:push-force-long".to_string(),
+            crate::config::PolicyMode::Deny,
+        );
+
+        let result = evaluate_detailed("git push --force origin feature/x", &config).result;
+        assert!(result.is_denied());
+        let info = result.pattern_info.as_ref().expect("pattern info");
+        assert_eq!(info.severity, Some(crate::packs::Severity::Medium));
+        assert_eq!(
+            result.effective_mode,
+            Some(crate::packs::DecisionMode::Deny)
+        );
     }
 
-    fn default_allowlists() -> LayeredAllowlist {
-        LayeredAllowlist::default()
+    #[test]
+    fn git_push_targets_reads_refspec_destinations() {
+        let targets = |command: &str| git_push_targets(command);
+        assert_eq!(
+            targets("git push --force origin main"),
+            Some(vec!["main".to_string()])
+        );
+        assert_eq!(
+            targets("git push -f -o ci.skip origin +feature/x:refs/heads/main dev"),
+            Some(vec!["main".to_string(), "dev".to_string()])
+        );
+        assert_eq!(targets("git -C repo push --force"), Some(Vec::new()));
+        assert_eq!(targets("git push --force --all origin"), None);
+        assert_eq!(
+            targets("cargo test && git push --force-with-lease origin 'topic'"),
+            Some(vec!["topic".to_string()])
+        );
+        assert_eq!(
+            targets("git -c push.default=current -C repo push -f origin main"),
+            Some(vec!["main".to_string()])
+        );
+        // Only the segment that actually runs `git push` counts.
+        assert_eq!(
+            targets("echo push; git push --force origin main"),
+            Some(vec!["main".to_string()])
+        );
+        // Several pushes in one command: targets are ambiguous.
+        assert_eq!(
+            targets("git push -f origin feature/x && git push -f origin main"),
+            None
+        );
+        assert_eq!(targets("echo git push --force"), None);
     }
 
-    fn project_allowlists_for_rule(rule: &str, reason: &str) -> LayeredAllowlist {
-        let rule = RuleId::parse(rule).expect("rule id must parse");
-        LayeredAllowlist {
-            layers: vec![LoadedAllowlistLayer {
-                layer: AllowlistLayer::Project,
-                path: PathBuf::from("project-allowlist.toml"),
-                file: AllowlistFile {
-                    entries: vec![AllowEntry {
-                        selector: AllowSelector::Rule(rule),
-                        reason: reason.to_string(),
-                        added_by: None,
-                        added_at: None,
-                        expires_at: None,
-                        ttl: None,
-                        session: None,
-                        context: None,
-                        conditions: HashMap::new(),
-                        environments: Vec::new(),
-                        paths: None,
-                        risk_acknowledged: false,
-                    }],
-                    errors: Vec::new(),
-                },
-            }],
-        }
+    #[test]
+    fn force_push_decoy_segment_stays_critical() {
+        let config = default_config();
+        let result = evaluate_detailed("echo push; git push --force origin main", &config).result;
+        assert!(result.is_denied());
+        let info = result.pattern_info.expect("force push matched");
+        assert_eq!(info.severity, Some(crate::packs::Severity::Critical));
+        assert!(
+            info.reason.contains("protected branch 'main'"),
+            "{}",
+            info.reason
+        );
     }
 
-    #[allow(dead_code)]
-    fn project_allowlists_for_pack_wildcard(pack_id: &str, reason: &str) -> LayeredAllowlist {
-        LayeredAllowlist {
-            layers: vec![LoadedAllowlistLayer {
-                layer: AllowlistLayer::Project,
-                path: PathBuf::from("project-allowlist.toml"),
-                file: AllowlistFile {
-                    entries: vec![AllowEntry {
-                        selector: AllowSelector::Rule(RuleId {
-                            pack_id: pack_id.to_string(),
-                            pattern_name: "*".to_string(),
-                        }),
-                        reason: reason.to_string(),
-                        added_by: None,
-                        added_at: None,
-                        expires_at: None,
-                        ttl: None,
-                        session: None,
-                        context: None,
-                        conditions: HashMap::new(),
-                        environments: Vec::new(),
-                        paths: None,
-                        risk_acknowledged: false,
-                    }],
-                    errors: Vec::new(),
-                },
-            }],
+    #[test]
+    fn test_fish_mode_evaluates_chains_and_substitutions() {
+        let mut config = default_config();
+        // Bash reads fish `(...)` substitution as a plain argument.
+        assert!(
+            !evaluate_detailed("echo (rm -rf /)", &config)
+                .result
+                .is_denied()
+        );
+
+        config.evaluation.shell = ShellDialect::Fish;
+        for command in [
+            "echo (rm -rf /)",
+            "rm -rf /; and echo done",
+            "make build; or git reset --hard",
+        ] {
+            assert!(
+                evaluate_detailed(command, &config).result.is_denied(),
+                "{command}"
+            );
         }
+        assert!(
+            !evaluate_detailed("git status; and echo done", &config)
+                .result
+                .is_denied()
+        );
     }
 
     #[test]
-    fn test_empty_command_allowed() {
-        let config = default_config();
+    fn test_resolve_script_targets_package_json() {
+        let mut config = default_config();
         let compiled = default_compiled_overrides();
         let allowlists = default_allowlists();
-        let result = evaluate_command("", &config, &[], &compiled, &allowlists);
-        assert!(result.is_allowed());
-        assert!(result.pattern_info.is_none());
+        let keywords: Vec<&str> = vec!["git", "rm"];
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/script_targets");
+        let evaluate = |command: &str, config: &Config| {
+            let result = evaluate_command(command, config, &keywords, &compiled, &allowlists);
+            apply_script_targets(
+                command,
+                result,
+                &config.evaluation,
+                Some(&fixture),
+                |target| evaluate_command(target, config, &keywords, &compiled, &allowlists),
+            )
+        };
+
+        // Off by default: the invocation alone is harmless.
+        assert_eq!(evaluate("npm run nuke", &config).decision, Decision::Allow);
+
+        config.evaluation.resolve_script_targets = true;
+        assert_eq!(evaluate("npm run build", &config).decision, Decision::Allow);
+        assert_eq!(evaluate("npm test", &config).decision, Decision::Allow);
+
+        let result = evaluate("npm run nuke", &config);
+        assert_eq!(result.decision, Decision::Deny);
+        let reason = result.reason().unwrap();
+        assert!(
+            reason.contains("`npm run nuke` runs `rm -rf /`"),
+            "{reason}"
+        );
+        assert!(result.pattern_info.unwrap().matched_span.is_none());
+
+        // Scripts that call other scripts are followed.
+        let result = evaluate("cd . && npm run reset", &config);
+        assert_eq!(result.decision, Decision::Deny);
+        assert!(result.reason().unwrap().contains("rm -rf /"));
     }
 
     #[test]
-    fn test_safe_command_allowed() {
-        let config = default_config();
+    fn test_ask_on_privileged_unknown() {
+        let mut config = default_config();
         let compiled = default_compiled_overrides();
         let allowlists = default_allowlists();
-        let result = evaluate_command("ls -la", &config, &["git", "rm"], &compiled, &allowlists);
-        assert!(result.is_allowed());
+        let keywords: Vec<&str> = vec!["git", "rm"];
+
+        let result = evaluate_command(
+            "sudo ./random-script.sh",
+            &config,
+            &keywords,
+            &compiled,
+            &allowlists,
+        );
+        assert_eq!(result.decision, Decision::Allow);
+
+        config.evaluation.ask_on_privileged_unknown = true;
+        let result = evaluate_command(
+            "sudo ./random-script.sh",
+            &config,
+            &keywords,
+            &compiled,
+            &allowlists,
+        );
+        assert_eq!(result.decision, Decision::Ask);
+        assert!(result.reason().unwrap().contains("./random-script.sh"));
+
+        for command in ["sudo ls", "sudo /bin/ls -la /root", "./random-script.sh"] {
+            let result = evaluate_command(command, &config, &keywords, &compiled, &allowlists);
+            assert_eq!(
+                result.decision,
+                Decision::Allow,
+                "should allow: {command:?}"
+            );
+        }
+
+        let result = evaluate_command("sudo ./ls", &config, &keywords, &compiled, &allowlists);
+        assert_eq!(result.decision, Decision::Ask);
+
+        // Destructive commands stay denied rather than downgraded to Ask.
+        let result = evaluate_command("sudo rm -rf /", &config, &keywords, &compiled, &allowlists);
+        assert_eq!(result.decision, Decision::Deny);
+
+        config.evaluation.privileged_allow = vec!["./random-script.sh".to_string()];
+        let result = evaluate_command(
+            "sudo ./random-script.sh",
+            &config,
+            &keywords,
+            &compiled,
+            &allowlists,
+        );
+        assert_eq!(result.decision, Decision::Allow);
     }
 
     #[test]
-    fn test_result_helper_methods() {
-        let allowed = EvaluationResult::allowed();
-        assert!(allowed.is_allowed());
-        assert!(!allowed.is_denied());
-        assert!(allowed.reason().is_none());
-        assert!(allowed.pack_id().is_none());
-
-        let denied = EvaluationResult::denied_by_pack("test.pack", "test reason", None);
-        assert!(!denied.is_allowed());
-        assert!(denied.is_denied());
-        assert_eq!(denied.reason(), Some("test reason"));
-        assert_eq!(denied.pack_id(), Some("test.pack"));
+    fn privileged_unknown_checks_every_segment() {
+        let mut config = default_config();
+        config.evaluation.ask_on_privileged_unknown = true;
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let keywords: Vec<&str> = vec!["git", "rm"];
+
+        for command in [
+            "cd build && sudo ./random-script.sh",
+            "env FOO=1 sudo ./random-script.sh",
+            "ls; sudo -E ./random-script.sh --all",
+        ] {
+            let result = evaluate_command(command, &config, &keywords, &compiled, &allowlists);
+            assert_eq!(result.decision, Decision::Ask, "should ask: {command:?}");
+        }
+        let result = evaluate_command(
+            "cd build && sudo ls",
+            &config,
+            &keywords,
+            &compiled,
+            &allowlists,
+        );
+        assert_eq!(result.decision, Decision::Allow);
     }
 
     #[test]
-    fn test_denied_by_config() {
-        let denied = EvaluationResult::denied_by_config("config block".to_string());
-        assert!(denied.is_denied());
-        assert_eq!(denied.reason(), Some("config block"));
-        assert!(denied.pack_id().is_none());
-        assert_eq!(
-            denied.pattern_info.as_ref().unwrap().source,
-            MatchSource::ConfigOverride
+    fn privileged_unknown_keeps_allowlisted_results() {
+        let evaluation = EvaluationConfig {
+            ask_on_privileged_unknown: true,
+            ..EvaluationConfig::default()
+        };
+        let matched = EvaluationResult::denied_by_pack_pattern(
+            "core.filesystem",
+            "rm-rf-general",
+            "rm -rf",
+            None,
+            crate::packs::Severity::High,
+            &[],
+        )
+        .pattern_info
+        .expect("pattern info");
+        let allowed = EvaluationResult::allowed_by_allowlist(
+            matched,
+            AllowlistLayer::Project,
+            "release cleanup".to_string(),
         );
+
+        let result = apply_privileged_unknown("sudo ./cleanup.sh", allowed, &evaluation);
+        assert_eq!(result.decision, Decision::Allow);
+        assert!(result.allowlist_override.is_some());
+    }
+
+    #[test]
+    fn test_strict_quoting_allows_heredocs_and_multiline_strings() {
+        let mut config = default_config();
+        config.evaluation.strict_quoting = true;
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+        let keywords: Vec<&str> = vec!["git"];
+
+        for command in [
+            "cat <<EOF\nit's fine\nEOF",
+            "git commit -m \"first line\nsecond line\"",
+            "echo 'balanced' # don't worry",
+        ] {
+            let result = evaluate_command(command, &config, &keywords, &compiled, &allowlists);
+            assert!(result.is_allowed(), "should allow: {command:?}");
+        }
     }
 
     #[test]
@@ -2790,6 +4903,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sql_heredoc_is_routed_to_the_client_database_pack() {
+        let mut config = default_config();
+        config.packs.enabled.push("database.postgresql".to_string());
+        let compiled = default_compiled_overrides();
+        let allowlists = default_allowlists();
+
+        let cmd = "psql -d prod <<EOF\nSELECT 1;\nDROP DATABASE prod;\nEOF";
+        let result = evaluate_command(cmd, &config, &["psql"], &compiled, &allowlists);
+        assert!(result.is_denied());
+
+        let info = result.pattern_info.expect("deny must include pattern info");
+        assert_eq!(info.source, MatchSource::HeredocAst);
+        assert_eq!(info.pack_id.as_deref(), Some("database.postgresql"));
+        assert_eq!(info.pattern_name.as_deref(), Some("drop-database"));
+        assert!(info.reason.contains("line 2 of heredoc"), "{}", info.reason);
+        assert_eq!(info.matched_text_preview.as_deref(), Some("DROP DATABASE"));
+
+        // The body is data for the client, not SQL for another engine.
+        let cat = "cat <<EOF\nDROP DATABASE prod;\nEOF";
+        let result = evaluate_command(cat, &config, &["psql"], &compiled, &allowlists);
+        assert!(result.is_allowed());
+    }
+
     #[test]
     fn heredoc_commands_with_non_blocking_matches_are_allowed() {
         let config = default_config();
@@ -2936,9 +5073,9 @@ mod tests {
 
     #[test]
     fn test_evaluation_decision_equality() {
-        assert_eq!(EvaluationDecision::Allow, EvaluationDecision::Allow);
-        assert_eq!(EvaluationDecision::Deny, EvaluationDecision::Deny);
-        assert_ne!(EvaluationDecision::Allow, EvaluationDecision::Deny);
+        assert_eq!(Decision::Allow, Decision::Allow);
+        assert_eq!(Decision::Deny, Decision::Deny);
+        assert_ne!(Decision::Allow, Decision::Deny);
     }
 
     #[test]
@@ -3530,10 +5667,11 @@ mod tests {
             &allowlists,
         );
 
-        // Evaluator should return Deny (policy layer converts to Warn)
-        assert!(
-            result.is_denied(),
-            "Medium severity pattern should be evaluated and return Deny"
+        // Warn-severity matches are reported as Warn; the policy layer may still block them
+        assert_eq!(
+            result.decision,
+            Decision::Warn,
+            "Medium severity pattern should be evaluated and return Warn"
         );
 
         // Verify severity is Medium
@@ -3550,6 +5688,416 @@ mod tests {
         assert_eq!(info.pattern_name.as_deref(), Some("image-prune"));
     }
 
+    #[test]
+    fn kubectl_delete_escalates_on_prod_context() {
+        let mut config = default_config();
+        config.packs.enabled.push("kubernetes.kubectl".to_string());
+        config.kubernetes.prod_contexts = vec!["*-prod".to_string(), "production".to_string()];
+        config.kubernetes.prod_namespaces = vec!["kube-system".to_string()];
+        let compiled = config.overrides.compile();
+        let allowlists = default_allowlists();
+
+        let mode_for = |command: &str| {
+            let result = evaluate_command(command, &config, &["kubectl"], &compiled, &allowlists);
+            let info = result
+                .pattern_info
+                .as_ref()
+                .expect("kubectl delete should match");
+            assert_eq!(info.pattern_name.as_deref(), Some("delete-pod"));
+            let mode = config.policy().resolve_mode(
+                info.pack_id.as_deref(),
+                info.pattern_name.as_deref(),
+                info.severity,
+            );
+            assert_eq!(result.effective_mode, Some(mode), "{command}");
+            mode
+        };
+
+        assert_eq!(
+            mode_for("kubectl --context dev-cluster delete pod web-1"),
+            crate::packs::DecisionMode::Warn
+        );
+        assert_eq!(
+            mode_for("kubectl --context app-prod delete pod web-1"),
+            crate::packs::DecisionMode::Deny
+        );
+        assert_eq!(
+            mode_for("kubectl delete pod web-1 --context=production"),
+            crate::packs::DecisionMode::Deny
+        );
+        assert_eq!(
+            mode_for("kubectl delete pod web-1 -n kube-system"),
+            crate::packs::DecisionMode::Deny
+        );
+        // -n after a separator is not a kubectl flag
+        assert_eq!(
+            mode_for("kubectl delete pod web-1 && echo -n kube-system"),
+            crate::packs::DecisionMode::Warn
+        );
+    }
+
+    #[test]
+    fn on_error_closed_denies_when_budget_exceeded() {
+        let mut config = default_config();
+        let compiled = config.overrides.compile();
+        let allowlists = default_allowlists();
+        let deadline = Deadline::new(std::time::Duration::ZERO);
+
+        let open = evaluate_command_with_deadline(
+            "git status",
+            &config,
+            &["git"],
+            &compiled,
+            &allowlists,
+            Some(&deadline),
+        );
+        assert!(open.is_allowed());
+        assert!(open.skipped_due_to_budget);
+
+        config.evaluation.on_error = OnErrorMode::Closed;
+        let closed = evaluate_command_with_deadline(
+            "git status",
+            &config,
+            &["git"],
+            &compiled,
+            &allowlists,
+            Some(&deadline),
+        );
+        assert!(closed.is_denied());
+        assert!(!closed.skipped_due_to_budget);
+        let reason = closed.reason().expect("denial carries a reason");
+        assert!(reason.contains("on_error = \"closed\""), "{reason}");
+    }
+
+    #[test]
+    fn total_timeout_fallback_fires_within_budget() {
+        let mut config = default_config();
+        config.evaluation.on_error = OnErrorMode::Closed;
+        config.evaluation.total_timeout_ms = Some(50);
+        let budget = config.evaluation.total_timeout();
+        let slowed_by = std::time::Duration::from_millis(500);
+
+        let start = std::time::Instant::now();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let evaluation = config.evaluation.clone();
+        let watchdog = crate::perf::EvaluationWatchdog::start(budget, move || {
+            let _ = tx.send((start.elapsed(), evaluation_timed_out(&evaluation)));
+        });
+
+        // Artificially slowed evaluation: finishes long after the budget.
+        std::thread::sleep(slowed_by);
+        assert!(!watchdog.finish(), "watchdog already claimed the decision");
+        let (fired_at, fallback) = rx.try_recv().expect("watchdog fired the fallback");
+
+        assert!(fired_at >= budget, "fired early: {fired_at:?}");
+        assert!(fired_at < slowed_by, "fired late: {fired_at:?}");
+        assert!(fallback.is_denied());
+        let reason = fallback.reason().expect("denial carries a reason");
+        assert!(reason.contains("evaluation timed out"), "{reason}");
+
+        config.evaluation.on_error = OnErrorMode::Open;
+        let open = evaluation_timed_out(&config.evaluation);
+        assert!(open.is_allowed());
+        assert!(open.skipped_due_to_budget);
+    }
+
+    #[test]
+    fn message_catalog_translates_reason_by_rule_id() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let catalog_path = dir.path().join("messages.toml");
+        std::fs::write(
+            &catalog_path,
+            r#"
+[ja."core.git:reset-hard"]
+reason = "git reset --hard はコミットされていない変更を破棄します"
+"#,
+        )
+        .expect("write catalog");
+
+        let mut config = default_config();
+        config.i18n.locale = Some("ja".to_string());
+        config.i18n.catalog_path = Some(catalog_path.to_string_lossy().into_owned());
+        let compiled = config.overrides.compile();
+        let allowlists = default_allowlists();
+        let evaluate = |command: &str| {
+            evaluate_command_with_deadline(command, &config, &["git"], &compiled, &allowlists, None)
+        };
+
+        let translated = evaluate("git reset --hard HEAD");
+        assert!(translated.is_denied());
+        assert_eq!(
+            translated.reason(),
+            Some("git reset --hard はコミットされていない変更を破棄します")
+        );
+
+        // Rules missing from the catalog keep the built-in English reason.
+        let fallback = evaluate("git clean -fd");
+        assert!(fallback.is_denied());
+        assert!(fallback.reason().is_some_and(str::is_ascii));
+    }
+
+    #[test]
+    fn in_place_edit_of_protected_file_is_medium_without_backup() {
+        let mut config = default_config();
+        config.filesystem.protected_paths = vec!["*.yaml".to_string(), ".env".to_string()];
+        let compiled = config.overrides.compile();
+        let allowlists = default_allowlists();
+        let evaluate = |command: &str| {
+            evaluate_command_with_deadline(command, &config, &["sed"], &compiled, &allowlists, None)
+        };
+
+        let no_backup = evaluate("sed -i 's/.*//' config.yaml");
+        assert_eq!(no_backup.decision, Decision::Warn);
+        let info = no_backup.pattern_info.as_ref().expect("match");
+        assert_eq!(info.pattern_name.as_deref(), Some("sed-in-place"));
+        assert_eq!(info.severity, Some(crate::packs::Severity::Medium));
+
+        let with_backup = evaluate("sed -i.bak 's/.*//' config.yaml");
+        assert!(with_backup.is_denied());
+        let info = with_backup.pattern_info.as_ref().expect("match");
+        assert_eq!(info.severity, Some(crate::packs::Severity::Low));
+
+        let perl = evaluate("perl -pi -e 's/foo/bar/' deploy/app.yaml");
+        let info = perl.pattern_info.as_ref().expect("match");
+        assert_eq!(info.pattern_name.as_deref(), Some("perl-in-place"));
+        assert_eq!(info.severity, Some(crate::packs::Severity::Medium));
+        let perl_backup = evaluate("perl -i.orig -pe 's/foo/bar/' .env");
+        let info = perl_backup.pattern_info.as_ref().expect("match");
+        assert_eq!(info.severity, Some(crate::packs::Severity::Low));
+    }
+
+    #[test]
+    fn decision_helpers_agree_with_variants() {
+        let cases = [
+            (Decision::Allow, "allow", true),
+            (Decision::Warn, "warn", true),
+            (Decision::Ask, "ask", false),
+            (Decision::Deny, "deny", false),
+        ];
+        for (decision, label, allowed) in cases {
+            assert_eq!(decision.as_str(), label);
+            assert_eq!(decision.is_allowed(), allowed, "{label}");
+            assert_eq!(decision.is_denied(), !allowed, "{label}");
+
+            let mut result = EvaluationResult::allowed();
+            result.decision = decision;
+            assert_eq!(result.is_allowed(), decision.is_allowed(), "{label}");
+            assert_eq!(result.is_denied(), decision.is_denied(), "{label}");
+        }
+    }
+
+    #[test]
+    fn post_process_reports_warn_severity_matches_as_warn() {
+        let config = default_config();
+        let allowlists = default_allowlists();
+        let finish = |severity| {
+            let matched = EvaluationResult::denied_by_pack_pattern(
+                "test.pack",
+                "test-pattern",
+                "test reason",
+                None,
+                severity,
+                &[],
+            );
+            post_process(
+                "echo test",
+                matched,
+                &config,
+                &allowlists,
+                None,
+                None,
+                |_| EvaluationResult::allowed(),
+            )
+        };
+
+        let warned = finish(crate::packs::Severity::Medium);
+        assert_eq!(warned.decision, Decision::Warn);
+        assert!(!warned.is_denied());
+        assert!(warned.pattern_info.is_some());
+
+        let denied = finish(crate::packs::Severity::High);
+        assert_eq!(denied.decision, Decision::Deny);
+        assert!(denied.is_denied());
+    }
+
+    #[test]
+    fn post_process_downgrades_deny_in_safe_dirs() {
+        let mut config = default_config();
+        config.safe_dirs.paths = vec!["/srv/scratch/**".to_string()];
+        let allowlists = default_allowlists();
+        let finish = |command: &str, cwd: &str, severity| {
+            let matched = EvaluationResult::denied_by_pack_pattern(
+                "core.filesystem",
+                "rm-rf-general",
+                "test reason",
+                None,
+                severity,
+                &[],
+            );
+            post_process(
+                command,
+                matched,
+                &config,
+                &allowlists,
+                None,
+                Some(Path::new(cwd)),
+                |_| EvaluationResult::allowed(),
+            )
+        };
+        let high = crate::packs::Severity::High;
+
+        let downgraded = finish("rm -rf build", "/srv/scratch/exp", high);
+        assert_eq!(downgraded.decision, Decision::Warn);
+        assert_eq!(
+            downgraded.effective_mode,
+            Some(crate::packs::DecisionMode::Warn)
+        );
+
+        assert!(finish("rm -rf build", "/srv/app", high).is_denied());
+        assert!(finish("rm -rf {~/src,x}", "/srv/scratch/exp", high).is_denied());
+        assert!(
+            finish(
+                "rm -rf build",
+                "/srv/scratch/exp",
+                crate::packs::Severity::Critical
+            )
+            .is_denied()
+        );
+    }
+
+    #[test]
+    fn file_write_to_protected_path_is_denied() {
+        let config = default_config();
+        let allowlists = default_allowlists();
+
+        let result = evaluate_file_write(
+            "Write",
+            "/etc/passwd",
+            None,
+            &config.filesystem,
+            &allowlists,
+        );
+        assert!(result.is_denied());
+        let info = result.pattern_info.as_ref().expect("match");
+        assert_eq!(info.pack_id.as_deref(), Some("core.filesystem"));
+        assert_eq!(info.pattern_name.as_deref(), Some("write-protected-path"));
+        assert_eq!(info.severity, Some(crate::packs::Severity::High));
+
+        assert!(
+            evaluate_file_write(
+                "Edit",
+                "/repo/src/main.rs",
+                None,
+                &config.filesystem,
+                &allowlists
+            )
+            .is_allowed()
+        );
+
+        let mut disabled = config.filesystem.clone();
+        disabled.protected_write_paths.clear();
+        assert!(
+            evaluate_file_write("Write", "/etc/passwd", None, &disabled, &allowlists).is_allowed()
+        );
+
+        // Traversal and relative targets are resolved before matching.
+        for (path, cwd) in [
+            ("/tmp/../etc/passwd", None),
+            ("//etc/passwd", None),
+            ("../etc/shadow", Some(Path::new("/srv"))),
+        ] {
+            assert!(
+                evaluate_file_write("Write", path, cwd, &config.filesystem, &allowlists)
+                    .is_denied(),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn in_place_edit_ignores_streaming_and_unprotected_files() {
+        let mut config = default_config();
+        config.filesystem.protected_paths = vec!["*.yaml".to_string()];
+        let compiled = config.overrides.compile();
+        let allowlists = default_allowlists();
+        let evaluate = |command: &str| {
+            evaluate_command_with_deadline(command, &config, &["sed"], &compiled, &allowlists, None)
+        };
+
+        // Without -i sed streams to stdout.
+        assert!(evaluate("sed 's/.*//' config.yaml").is_allowed());
+        assert!(evaluate("sed -n -e '/x/p' config.yaml").is_allowed());
+        // The script itself is not a target file.
+        assert!(evaluate("sed -i 's/a.yaml/b.yaml/' notes.txt").is_allowed());
+        assert!(evaluate("sed -i 's/x//' src/main.rs").is_allowed());
+
+        // Nothing is flagged until protected paths are configured.
+        let empty = default_config();
+        let result = evaluate_command_with_deadline(
+            "sed -i 's/.*//' config.yaml",
+            &empty,
+            &["sed"],
+            &compiled,
+            &allowlists,
+            None,
+        );
+        assert!(result.is_allowed());
+    }
+
+    #[test]
+    fn pushed_history_rewrite_warns_on_amend_and_rebase() {
+        const HEAD: &str = "0123456789abcdef0123456789abcdef01234567";
+        let temp = tempfile::tempdir().expect("tempdir");
+        let git_dir = temp.path().join(".git");
+        std::fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+        std::fs::create_dir_all(git_dir.join("refs/remotes/origin")).unwrap();
+        std::fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        std::fs::write(git_dir.join("refs/heads/main"), format!("{HEAD}\n")).unwrap();
+        std::fs::write(
+            git_dir.join("refs/remotes/origin/main"),
+            format!("{HEAD}\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            git_dir.join("config"),
+            "[branch \"main\"]\n\tremote = origin\n\tmerge = refs/heads/main\n",
+        )
+        .unwrap();
+
+        let config = default_config();
+        let check = |command: &str| {
+            apply_pushed_history_rewrite(
+                command,
+                EvaluationResult::allowed(),
+                &config.git_awareness,
+                Some(temp.path()),
+            )
+        };
+
+        let amend = check("git commit --amend --no-edit");
+        assert!(amend.is_denied());
+        let info = amend.pattern_info.as_ref().expect("match");
+        assert_eq!(info.pattern_name.as_deref(), Some("rewrite-pushed-history"));
+        assert_eq!(info.severity, Some(crate::packs::Severity::Medium));
+        assert_eq!(amend.effective_mode, Some(crate::packs::DecisionMode::Warn));
+        assert!(info.reason.contains("origin/main"), "{}", info.reason);
+
+        assert!(check("git rebase -i HEAD~3").is_denied());
+        assert!(check("git -C . rebase main").is_denied());
+        assert!(check("git rebase --continue").is_allowed());
+        assert!(check("git commit -m 'new work'").is_allowed());
+
+        let mut disabled = config.git_awareness.clone();
+        disabled.warn_on_pushed_rewrite = false;
+        let result = apply_pushed_history_rewrite(
+            "git commit --amend",
+            EvaluationResult::allowed(),
+            &disabled,
+            Some(temp.path()),
+        );
+        assert!(result.is_allowed());
+    }
+
     #[test]
     fn medium_severity_git_patterns_are_evaluated() {
         // Test git branch -D and stash drop (both Medium severity)
@@ -3565,8 +6113,9 @@ mod tests {
             &compiled,
             &allowlists,
         );
-        assert!(
-            branch_result.is_denied(),
+        assert_eq!(
+            branch_result.decision,
+            Decision::Warn,
             "git branch -D should be evaluated"
         );
         let branch_info = branch_result.pattern_info.as_ref().unwrap();
@@ -3584,8 +6133,9 @@ mod tests {
             &compiled,
             &allowlists,
         );
-        assert!(
-            stash_result.is_denied(),
+        assert_eq!(
+            stash_result.decision,
+            Decision::Warn,
             "git stash drop should be evaluated"
         );
         let stash_info = stash_result.pattern_info.as_ref().unwrap();
@@ -3788,7 +6338,7 @@ mod tests {
 
         fn create_deny_result_with_severity(severity: Severity) -> EvaluationResult {
             EvaluationResult {
-                decision: EvaluationDecision::Deny,
+                decision: Decision::Deny,
                 pattern_info: Some(PatternMatch {
                     pack_id: Some("test.pack".to_string()),
                     pattern_name: Some("test_pattern".to_string()),
@@ -3815,7 +6365,7 @@ mod tests {
             let modified = apply_branch_strictness(result, &config, None);
 
             // Decision should remain Deny
-            assert_eq!(modified.decision, EvaluationDecision::Deny);
+            assert_eq!(modified.decision, Decision::Deny);
             // No branch context should be set
             assert!(modified.branch_context.is_none());
         }
@@ -3864,6 +6414,7 @@ mod tests {
                 relaxed_disabled_packs: vec![],
                 show_branch_in_output: true,
                 warn_if_not_git: false,
+                warn_on_pushed_rewrite: true,
             };
 
             assert!(config.is_protected_branch(Some("main")));
@@ -3884,6 +6435,7 @@ mod tests {
                 relaxed_disabled_packs: vec![],
                 show_branch_in_output: true,
                 warn_if_not_git: false,
+                warn_on_pushed_rewrite: true,
             };
 
             assert!(config.is_relaxed_branch(Some("feature/my-feature")));
@@ -3904,6 +6456,7 @@ mod tests {
                 relaxed_disabled_packs: vec![],
                 show_branch_in_output: true,
                 warn_if_not_git: false,
+                warn_on_pushed_rewrite: true,
             };
 
             // Protected branch gets protected strictness
@@ -3935,7 +6488,7 @@ mod tests {
 
             // Create a result that would normally be blocked
             let result = EvaluationResult {
-                decision: EvaluationDecision::Deny,
+                decision: Decision::Deny,
                 pattern_info: Some(PatternMatch {
                     reason: "test reason".to_string(),
                     pattern_name: Some("test-pattern".to_string()),
@@ -0,0 +1,313 @@
+//! C ABI for embedding dcg in non-Rust programs (feature `ffi`).
+//!
+//! Build a shared library with:
+//!
+//! ```bash
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! ```c
+//! typedef struct DcgEngine DcgEngine;
+//!
+//! typedef enum {
+//!     DCG_OK = 0,
+//!     DCG_NULL_ARGUMENT = 1,
+//!     DCG_INVALID_UTF8 = 2,
+//!     DCG_INVALID_CONFIG = 3,
+//!     DCG_INTERNAL_ERROR = 4,
+//! } DcgStatus;
+//!
+//! DcgStatus dcg_engine_new(const char *config_toml, DcgEngine **out_engine);
+//! DcgStatus dcg_evaluate(const DcgEngine *engine, const char *command, char **out_json);
+//! void dcg_free(char *json);
+//! void dcg_engine_free(DcgEngine *engine);
+//! ```
+//!
+//! An engine holds the loaded config, compiled overrides, and allowlists, so
+//! it is created once and reused for every command. `dcg_evaluate` returns the
+//! same decision JSON as the MCP `check_command` tool.
+//!
+//! # Errors
+//!
+//! Fallible calls return a [`DcgStatus`] and write their result through an
+//! out-pointer, which is set to NULL on any status other than `DCG_OK`. A
+//! panic inside dcg is caught at the boundary and reported as
+//! `DCG_INTERNAL_ERROR`; it never unwinds into the caller.
+//!
+//! # Ownership
+//!
+//! - Input strings are borrowed for the duration of the call and must be
+//!   NUL-terminated UTF-8; dcg never frees or retains them.
+//! - Every string returned by `dcg_evaluate` is owned by the caller and must be
+//!   released with exactly one `dcg_free` call (not the C `free`).
+//! - Every engine returned by `dcg_engine_new` must be released with exactly
+//!   one `dcg_engine_free` call, after all calls using it have returned.
+//! - Passing NULL to `dcg_free` or `dcg_engine_free` is a no-op.
+
+use crate::config::Config;
+use crate::evaluator::evaluate_command;
+use crate::mcp::CheckCommandResponse;
+use crate::scan::ScanEvalContext;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// Result of a fallible C entry point.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DcgStatus {
+    /// The call succeeded and the out-pointer is set.
+    Ok = 0,
+    /// A required pointer argument was NULL.
+    NullArgument = 1,
+    /// A string argument was not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// The config TOML could not be parsed.
+    InvalidConfig = 3,
+    /// dcg failed internally (including a caught panic).
+    InternalError = 4,
+}
+
+/// An evaluation engine: a config plus everything precomputed from it.
+pub struct DcgEngine {
+    config: Config,
+    ctx: ScanEvalContext,
+}
+
+impl DcgEngine {
+    fn new(config: Config) -> Self {
+        let ctx = ScanEvalContext::from_config(&config);
+        Self { config, ctx }
+    }
+
+    fn evaluate_json(&self, command: &str) -> Option<String> {
+        let result = evaluate_command(
+            command,
+            &self.config,
+            &self.ctx.enabled_keywords,
+            &self.ctx.compiled_overrides,
+            &self.ctx.allowlists,
+        );
+        serde_json::to_string(&CheckCommandResponse::from_result(&result)).ok()
+    }
+}
+
+/// Run `body` and store its value in `out`, converting panics to an error.
+///
+/// `out` is set to NULL before `body` runs, so it is NULL on every error path.
+///
+/// # Safety
+///
+/// `out` must be NULL or valid for writes.
+unsafe fn call_with_out<T>(
+    out: *mut *mut T,
+    body: impl FnOnce() -> Result<*mut T, DcgStatus>,
+) -> DcgStatus {
+    // SAFETY: `out` is NULL or writable per the caller contract.
+    let Some(out) = (unsafe { out.as_mut() }) else {
+        return DcgStatus::NullArgument;
+    };
+    *out = std::ptr::null_mut();
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => {
+            *out = value;
+            DcgStatus::Ok
+        }
+        Ok(Err(status)) => status,
+        Err(_) => DcgStatus::InternalError,
+    }
+}
+
+/// Borrow a NUL-terminated UTF-8 string.
+///
+/// # Safety
+///
+/// `ptr` must be NULL or point to a NUL-terminated string that outlives `'a`.
+unsafe fn borrow_str<'a>(ptr: *const c_char) -> Result<&'a str, DcgStatus> {
+    if ptr.is_null() {
+        return Err(DcgStatus::NullArgument);
+    }
+    // SAFETY: the caller guarantees a valid NUL-terminated string.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| DcgStatus::InvalidUtf8)
+}
+
+/// Create an engine and store it in `*out_engine`.
+///
+/// Config is loaded the same way the `dcg` binary loads it (config files plus
+/// `DCG_*` environment overrides). A non-NULL `config_toml` is merged on top
+/// as the highest-priority file layer; see [`Config::load_with_overlay`].
+///
+/// # Safety
+///
+/// `config_toml` must be NULL or a valid NUL-terminated string, and
+/// `out_engine` must be NULL or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dcg_engine_new(
+    config_toml: *const c_char,
+    out_engine: *mut *mut DcgEngine,
+) -> DcgStatus {
+    let body = || {
+        let config = if config_toml.is_null() {
+            Config::load()
+        } else {
+            // SAFETY: non-null and valid per the caller contract.
+            let toml = unsafe { borrow_str(config_toml) }?;
+            Config::load_with_overlay(toml).map_err(|_| DcgStatus::InvalidConfig)?
+        };
+        Ok(Box::into_raw(Box::new(DcgEngine::new(config))))
+    };
+    // SAFETY: `out_engine` is NULL or writable per the caller contract.
+    unsafe { call_with_out(out_engine, body) }
+}
+
+/// Evaluate a command and store the decision JSON in `*out_json`.
+///
+/// The returned string must be released with [`dcg_free`].
+///
+/// # Safety
+///
+/// `engine` must be NULL or a live pointer from [`dcg_engine_new`],
+/// `command` must be NULL or a valid NUL-terminated string, and `out_json`
+/// must be NULL or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dcg_evaluate(
+    engine: *const DcgEngine,
+    command: *const c_char,
+    out_json: *mut *mut c_char,
+) -> DcgStatus {
+    let body = || {
+        // SAFETY: `engine` is NULL or live per the caller contract.
+        let engine = unsafe { engine.as_ref() }.ok_or(DcgStatus::NullArgument)?;
+        // SAFETY: valid per the caller contract.
+        let command = unsafe { borrow_str(command) }?;
+        engine
+            .evaluate_json(command)
+            .and_then(|json| CString::new(json).ok())
+            .map(CString::into_raw)
+            .ok_or(DcgStatus::InternalError)
+    };
+    // SAFETY: `out_json` is NULL or writable per the caller contract.
+    unsafe { call_with_out(out_json, body) }
+}
+
+/// Release a string returned by [`dcg_evaluate`].
+///
+/// # Safety
+///
+/// `json` must be NULL or a pointer returned by [`dcg_evaluate`] that has not
+/// been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dcg_free(json: *mut c_char) {
+    if !json.is_null() {
+        // SAFETY: the pointer came from `CString::into_raw` in `dcg_evaluate`.
+        let json = unsafe { CString::from_raw(json) };
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(json)));
+    }
+}
+
+/// Release an engine created by [`dcg_engine_new`].
+///
+/// # Safety
+///
+/// `engine` must be NULL or a pointer returned by [`dcg_engine_new`] that has
+/// not been freed yet, with no calls using it still in progress.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dcg_engine_free(engine: *mut DcgEngine) {
+    if !engine.is_null() {
+        // SAFETY: the pointer came from `Box::into_raw` in `dcg_engine_new`.
+        let engine = unsafe { Box::from_raw(engine) };
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(engine)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_engine(config: &str) -> *mut DcgEngine {
+        let config = CString::new(config).unwrap();
+        let mut engine = std::ptr::null_mut();
+        let status = unsafe { dcg_engine_new(config.as_ptr(), &raw mut engine) };
+        assert_eq!(status, DcgStatus::Ok);
+        assert!(!engine.is_null());
+        engine
+    }
+
+    fn evaluate(engine: *const DcgEngine, command: &str) -> serde_json::Value {
+        let command = CString::new(command).unwrap();
+        let mut json = std::ptr::null_mut();
+        let status = unsafe { dcg_evaluate(engine, command.as_ptr(), &raw mut json) };
+        assert_eq!(status, DcgStatus::Ok);
+        let value = serde_json::from_str(unsafe { CStr::from_ptr(json) }.to_str().unwrap())
+            .expect("decision JSON");
+        unsafe { dcg_free(json) };
+        value
+    }
+
+    #[test]
+    fn round_trip_through_c_abi() {
+        let engine = new_engine("[packs]\nenabled = [\"messaging.sqs_sns\"]\n");
+
+        let denied = evaluate(engine, "git reset --hard");
+        assert_eq!(denied["decision"], "deny");
+        assert_eq!(denied["allowed"], false);
+        assert_eq!(denied["rule_id"], "core.git:reset-hard");
+
+        let purge = evaluate(engine, "aws sqs purge-queue --queue-url q");
+        assert_eq!(purge["pack_id"], "messaging.sqs_sns");
+
+        let allowed = evaluate(engine, "git status");
+        assert_eq!(allowed["decision"], "allow");
+        assert_eq!(allowed["allowed"], true);
+
+        unsafe { dcg_engine_free(engine) };
+    }
+
+    #[test]
+    fn invalid_inputs_return_error_status() {
+        let bad = CString::new("[packs\n").unwrap();
+        let mut engine = std::ptr::NonNull::<DcgEngine>::dangling().as_ptr();
+        assert_eq!(
+            unsafe { dcg_engine_new(bad.as_ptr(), &raw mut engine) },
+            DcgStatus::InvalidConfig
+        );
+        assert!(engine.is_null(), "out-pointer is cleared on error");
+
+        let engine = new_engine("");
+        let mut json = std::ptr::null_mut();
+        assert_eq!(
+            unsafe { dcg_evaluate(engine, std::ptr::null(), &raw mut json) },
+            DcgStatus::NullArgument
+        );
+        let invalid = c"\xff";
+        assert_eq!(
+            unsafe { dcg_evaluate(engine, invalid.as_ptr(), &raw mut json) },
+            DcgStatus::InvalidUtf8
+        );
+        let command = CString::new("ls").unwrap();
+        assert_eq!(
+            unsafe { dcg_evaluate(std::ptr::null(), command.as_ptr(), &raw mut json) },
+            DcgStatus::NullArgument
+        );
+        assert_eq!(
+            unsafe { dcg_evaluate(engine, command.as_ptr(), std::ptr::null_mut()) },
+            DcgStatus::NullArgument
+        );
+        assert!(json.is_null());
+
+        unsafe {
+            dcg_free(std::ptr::null_mut());
+            dcg_engine_free(engine);
+            dcg_engine_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn panics_are_caught_at_the_boundary() {
+        let mut out: *mut u8 = std::ptr::null_mut();
+        let status = unsafe { call_with_out(&raw mut out, || panic!("boom")) };
+        assert_eq!(status, DcgStatus::InternalError);
+        assert!(out.is_null());
+    }
+}
@@ -252,11 +252,15 @@ fn get_detached_head_hash(working_dir: Option<&std::path::Path>) -> Option<Strin
 /// Format: `ref: refs/heads/<branch-name>` for branches
 /// or a commit hash for detached HEAD.
 fn get_branch_from_head_file(working_dir: Option<&std::path::Path>) -> BranchInfo {
-    let git_dir = find_git_dir(working_dir);
-    let head_path = match git_dir {
-        Some(dir) => dir.join("HEAD"),
-        None => return BranchInfo::NotGitRepo,
-    };
+    match find_git_dir(working_dir) {
+        Some(git_dir) => read_head_file(&git_dir),
+        None => BranchInfo::NotGitRepo,
+    }
+}
+
+/// Parse `HEAD` inside a resolved git directory.
+fn read_head_file(git_dir: &std::path::Path) -> BranchInfo {
+    let head_path = git_dir.join("HEAD");
 
     let head_content = match std::fs::read_to_string(&head_path) {
         Ok(content) => content,
@@ -291,6 +295,11 @@ fn get_branch_from_head_file(working_dir: Option<&std::path::Path>) -> BranchInf
 /// Handles both regular repositories (.git as directory) and worktrees
 /// (.git as file pointing to the actual git directory).
 fn find_git_dir(working_dir: Option<&std::path::Path>) -> Option<PathBuf> {
+    find_repo(working_dir).map(|(_, git_dir)| git_dir)
+}
+
+/// Find the repository root and its git directory, walking up from `working_dir`.
+fn find_repo(working_dir: Option<&std::path::Path>) -> Option<(PathBuf, PathBuf)> {
     let start_dir = working_dir
         .map(PathBuf::from)
         .or_else(|| std::env::current_dir().ok())?;
@@ -302,7 +311,7 @@ fn find_git_dir(working_dir: Option<&std::path::Path>) -> Option<PathBuf> {
 
         if git_path.is_dir() {
             // Regular git directory
-            return Some(git_path);
+            return Some((current.to_path_buf(), git_path));
         }
 
         if git_path.is_file() {
@@ -317,7 +326,7 @@ fn find_git_dir(working_dir: Option<&std::path::Path>) -> Option<PathBuf> {
                         current.join(gitdir_path)
                     };
                     if resolved.is_dir() {
-                        return Some(resolved);
+                        return Some((current.to_path_buf(), resolved));
                     }
                 }
             }
@@ -328,6 +337,107 @@ fn find_git_dir(working_dir: Option<&std::path::Path>) -> Option<PathBuf> {
     }
 }
 
+/// Repository provenance for a directory: the enclosing repo root and branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoContext {
+    /// Top-level directory of the enclosing repository (or worktree).
+    pub root: PathBuf,
+    /// Current branch, or `None` in detached HEAD state.
+    pub branch: Option<String>,
+}
+
+/// Get the enclosing repository root and branch for `path`.
+///
+/// Unlike [`get_branch_info_at_path`], this never spawns `git`: it walks up to
+/// the nearest `.git` and reads `HEAD` directly, so it is cheap enough to call
+/// for every history entry. Returns `None` outside a git repository.
+#[must_use]
+pub fn repo_context_at_path(path: &std::path::Path) -> Option<RepoContext> {
+    let (root, git_dir) = find_repo(Some(path))?;
+    match read_head_file(&git_dir) {
+        BranchInfo::Branch(branch) => Some(RepoContext {
+            root,
+            branch: Some(branch),
+        }),
+        BranchInfo::DetachedHead(_) => Some(RepoContext { root, branch: None }),
+        BranchInfo::NotGitRepo => None,
+    }
+}
+
+/// Get the upstream of the current branch when HEAD has already been pushed to it.
+///
+/// Best-effort and never spawns `git`: the branch's `remote`/`merge` settings
+/// are read from the repository config, and HEAD counts as pushed when it equals
+/// the remote-tracking ref or appears in that ref's reflog (the upstream has been
+/// at this commit, so HEAD is at or behind it). Returns the upstream name (e.g.
+/// `origin/main`), or `None` outside a repository, in detached HEAD, without an
+/// upstream, or when HEAD has commits the upstream has not seen.
+#[must_use]
+pub fn pushed_upstream_at_path(path: &std::path::Path) -> Option<String> {
+    let (_, git_dir) = find_repo(Some(path))?;
+    let BranchInfo::Branch(branch) = read_head_file(&git_dir) else {
+        return None;
+    };
+    // Worktrees keep HEAD in their own git dir but share refs and config.
+    let common_dir = std::fs::read_to_string(git_dir.join("commondir"))
+        .map_or_else(|_| git_dir.clone(), |dir| git_dir.join(dir.trim()));
+
+    let (remote, merge) = branch_upstream(&common_dir, &branch)?;
+    let upstream_branch = merge.strip_prefix("refs/heads/")?;
+    if remote == "." {
+        return None;
+    }
+    let tracking_ref = format!("refs/remotes/{remote}/{upstream_branch}");
+    let head = resolve_ref(&common_dir, &format!("refs/heads/{branch}"))?;
+    let upstream = resolve_ref(&common_dir, &tracking_ref)?;
+
+    let seen_by_upstream = head == upstream
+        || std::fs::read_to_string(common_dir.join("logs").join(&tracking_ref)).is_ok_and(|log| {
+            log.lines()
+                .any(|line| line.split(' ').nth(1) == Some(&head))
+        });
+    seen_by_upstream.then(|| format!("{remote}/{upstream_branch}"))
+}
+
+/// Read `branch.<name>.remote` and `branch.<name>.merge` from the repository config.
+fn branch_upstream(git_dir: &std::path::Path, branch: &str) -> Option<(String, String)> {
+    let config = std::fs::read_to_string(git_dir.join("config")).ok()?;
+    let section = format!("[branch \"{branch}\"]");
+    let mut in_section = false;
+    let mut remote = None;
+    let mut merge = None;
+    for line in config.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_section = line == section;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "remote" => remote = Some(value.trim().to_string()),
+            "merge" => merge = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    Some((remote?, merge?))
+}
+
+/// Resolve a ref to its commit hash from loose refs or `packed-refs`.
+fn resolve_ref(git_dir: &std::path::Path, name: &str) -> Option<String> {
+    if let Ok(content) = std::fs::read_to_string(git_dir.join(name)) {
+        return Some(content.trim().to_string());
+    }
+    let packed = std::fs::read_to_string(git_dir.join("packed-refs")).ok()?;
+    packed.lines().find_map(|line| {
+        let (hash, ref_name) = line.split_once(' ')?;
+        (ref_name.trim() == name).then(|| hash.to_string())
+    })
+}
+
 /// Check if the current directory is in a git repository.
 #[must_use]
 pub fn is_in_git_repo() -> bool {
@@ -440,4 +550,57 @@ mod tests {
         // Just verify it doesn't panic
         drop(result);
     }
+
+    #[test]
+    fn test_pushed_upstream_reads_refs_without_git() {
+        const PUSHED: &str = "1111111111111111111111111111111111111111";
+        const LOCAL: &str = "2222222222222222222222222222222222222222";
+
+        let temp = tempfile::tempdir().unwrap();
+        let git_dir = temp.path().join(".git");
+        std::fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+        std::fs::write(git_dir.join("HEAD"), "ref: refs/heads/feature\n").unwrap();
+        std::fs::write(git_dir.join("refs/heads/feature"), format!("{PUSHED}\n")).unwrap();
+        assert_eq!(pushed_upstream_at_path(temp.path()), None, "no upstream");
+
+        std::fs::write(
+            git_dir.join("config"),
+            "[core]\n\tbare = false\n[branch \"feature\"]\n\tremote = origin\n\tmerge = refs/heads/feature\n",
+        )
+        .unwrap();
+        std::fs::write(
+            git_dir.join("packed-refs"),
+            format!("# pack-refs with: peeled\n{PUSHED} refs/remotes/origin/feature\n"),
+        )
+        .unwrap();
+        assert_eq!(
+            pushed_upstream_at_path(temp.path()).as_deref(),
+            Some("origin/feature")
+        );
+
+        // A new local commit has not been pushed yet.
+        std::fs::write(git_dir.join("refs/heads/feature"), format!("{LOCAL}\n")).unwrap();
+        assert_eq!(pushed_upstream_at_path(temp.path()), None);
+    }
+
+    #[test]
+    fn test_repo_context_reads_head_without_git() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(repo.join("src/nested")).unwrap();
+        std::fs::write(repo.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+
+        let ctx = repo_context_at_path(&repo.join("src/nested")).expect("inside repo");
+        assert_eq!(ctx.root, repo);
+        assert_eq!(ctx.branch.as_deref(), Some("main"));
+
+        std::fs::write(
+            repo.join(".git/HEAD"),
+            "0123456789abcdef0123456789abcdef01234567\n",
+        )
+        .unwrap();
+        let ctx = repo_context_at_path(&repo).expect("detached HEAD is still a repo");
+        assert_eq!(ctx.branch, None);
+    }
 }
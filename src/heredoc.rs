@@ -472,6 +472,8 @@ pub enum ScriptLanguage {
     Perl,
     JavaScript,
     TypeScript,
+    /// SQL fed to a database client (`psql`, `mysql`, `sqlite3`).
+    Sql,
     Unknown,
 }
 
@@ -523,6 +525,12 @@ impl ScriptLanguage {
             || matches_interpreter("fish")
         {
             Self::Bash
+        } else if matches_interpreter("psql")
+            || matches_interpreter("mysql")
+            || matches_interpreter("mariadb")
+            || matches_interpreter("sqlite")
+        {
+            Self::Sql
         } else {
             Self::Unknown
         }
@@ -2725,6 +2733,8 @@ mod tests {
                 ("ruby <<EOF\nputs 'hello'\nEOF", ScriptLanguage::Ruby),
                 ("perl <<EOF\nprint \"hello\";\nEOF", ScriptLanguage::Perl),
                 ("bash <<EOF\necho hello\nEOF", ScriptLanguage::Bash),
+                ("psql <<EOF\nSELECT 1;\nEOF", ScriptLanguage::Sql),
+                ("sqlite3 app.db <<EOF\n.tables\nEOF", ScriptLanguage::Sql),
             ];
 
             for (cmd, expected) in cases {
@@ -45,12 +45,13 @@ use std::time::{Duration, Instant};
 use tracing::{debug, error, trace, warn};
 
 pub use schema::{
-    AgentStat, BackupResult, CURRENT_SCHEMA_VERSION, CheckResult, CommandEntry,
-    DEFAULT_DB_FILENAME, ExportFilters, ExportOptions, ExportedData, FrequentBlock,
-    HistoryAnalyzer, HistoryDb, HistoryError, HistoryStats, Outcome, OutcomeStats,
-    PackEffectivenessAnalysis, PackRecommendation, PathCluster, PatternEffectiveness, PatternStat,
-    PerformanceStats, PotentialGap, ProjectStat, RecommendationType, RuleMetrics, RuleTrend,
-    StatsTrends, SuggestionAction, SuggestionAuditEntry, SuggestionCandidate,
+    AgentOutcomeStat, AgentStat, BackupResult, CURRENT_SCHEMA_VERSION, CheckResult, CommandEntry,
+    DEFAULT_DB_FILENAME, DeniedRuleStat, ExportFilters, ExportOptions, ExportedData, FrequentBlock,
+    HistoryAnalyzer, HistoryDb, HistoryError, HistoryOverview, HistoryStats, Outcome,
+    OutcomeRatios, OutcomeStats, PackEffectivenessAnalysis, PackRecommendation, PathCluster,
+    PatternEffectiveness, PatternStat, PerformanceStats, PotentialGap, ProjectStat,
+    RecommendationType, RuleMetrics, RuleTrend, StatsTrends, SuggestionAction,
+    SuggestionAuditEntry, SuggestionCandidate,
 };
 
 /// Environment variable to override the history database path.
@@ -59,6 +60,31 @@ pub const ENV_HISTORY_DB_PATH: &str = "DCG_HISTORY_DB";
 /// Environment variable to disable history collection entirely.
 pub const ENV_HISTORY_DISABLED: &str = "DCG_HISTORY_DISABLED";
 
+/// Environment variable to record hook latency in history.
+///
+/// When set to `1`/`true`, every hook evaluation is logged (even if
+/// `[history] enabled = false`) with a nonzero `eval_duration_us`.
+/// Hook stdout/stderr are unchanged.
+pub const ENV_MEASURE: &str = "DCG_MEASURE";
+
+/// Whether [`ENV_MEASURE`] is set.
+#[must_use]
+pub fn measure_enabled() -> bool {
+    std::env::var(ENV_MEASURE)
+        .map(|v| v == "1" || v.to_lowercase() == "true")
+        .unwrap_or(false)
+}
+
+/// Convert an evaluation duration to the `eval_duration_us` value to log.
+///
+/// History stats treat `0` as "not recorded", so measured evaluations that
+/// finish in under a microsecond are logged as `1`.
+#[must_use]
+pub fn eval_duration_us(elapsed: Duration, measure: bool) -> u64 {
+    let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+    if measure { micros.max(1) } else { micros }
+}
+
 enum HistoryMessage {
     Entry(Box<CommandEntry>),
     Flush(mpsc::Sender<()>),
@@ -123,6 +149,7 @@ pub struct HistoryWriter {
     handle: Option<thread::JoinHandle<()>>,
     redaction_mode: HistoryRedactionMode,
     session_id: String,
+    log_allows: bool,
 }
 
 impl HistoryWriter {
@@ -174,6 +201,8 @@ impl HistoryWriter {
             handle: Some(handle),
             redaction_mode: config.redaction_mode,
             session_id,
+            // Latency measurement needs every evaluation, allows included.
+            log_allows: config.log_allows || measure_enabled(),
         }
     }
 
@@ -184,6 +213,7 @@ impl HistoryWriter {
             handle: None,
             redaction_mode: HistoryRedactionMode::Pattern,
             session_id: String::new(),
+            log_allows: true,
         }
     }
 
@@ -200,13 +230,41 @@ impl HistoryWriter {
         })
     }
 
+    /// Whether `entry` should be written under `[history] log_allows`.
+    ///
+    /// Clean allows (no rule matched, no allowlist involved) are dropped when
+    /// `log_allows` is false; everything else is always recorded.
+    #[must_use]
+    pub fn should_record(&self, entry: &CommandEntry) -> bool {
+        self.log_allows
+            || entry.outcome != Outcome::Allow
+            || entry.rule_id.is_some()
+            || entry.pattern_name.is_some()
+            || entry.allowlist_layer.is_some()
+    }
+
     /// Log a command entry asynchronously.
     pub fn log(&self, mut entry: CommandEntry) {
+        if !self.should_record(&entry) {
+            return;
+        }
         entry.command = redact_for_history(&entry.command, self.redaction_mode);
+        entry.normalized_command = entry
+            .normalized_command
+            .map(|normalized| redact_for_history(&normalized, self.redaction_mode));
         // Set session ID if not already set
         if entry.session_id.is_none() && !self.session_id.is_empty() {
             entry.session_id = Some(self.session_id.clone());
         }
+        // Best-effort provenance: enclosing repo root and branch (no git subprocess)
+        if self.sender.is_some() && entry.repo_root.is_none() && !entry.working_dir.is_empty() {
+            if let Some(repo) =
+                crate::git::repo_context_at_path(std::path::Path::new(&entry.working_dir))
+            {
+                entry.repo_root = Some(repo.root.to_string_lossy().into_owned());
+                entry.branch = repo.branch;
+            }
+        }
         if let Some(sender) = &self.sender {
             if let Err(e) = sender.send(HistoryMessage::Entry(Box::new(entry))) {
                 // Channel disconnected - worker thread likely crashed or shutdown
@@ -19,6 +19,8 @@ use std::env;
 use std::fmt::Write as FmtWrite;
 use std::path::{Path, PathBuf};
 
+use crate::output::DelimitedFormat;
+
 // ============================================================================
 // SqliteValue Conversion Helpers
 // ============================================================================
@@ -132,7 +134,7 @@ fn inline_params(sql: &str, params: &[SqliteValue]) -> String {
 }
 
 /// Current schema version for migrations.
-pub const CURRENT_SCHEMA_VERSION: u32 = 5;
+pub const CURRENT_SCHEMA_VERSION: u32 = 7;
 
 /// Default database filename.
 pub const DEFAULT_DB_FILENAME: &str = "history.db";
@@ -251,8 +253,12 @@ pub struct CommandEntry {
     pub agent_type: String,
     /// Working directory where the command was executed.
     pub working_dir: String,
-    /// The actual command string.
+    /// The command string as the agent submitted it.
     pub command: String,
+    /// The command as evaluated, when the shell normalizer rewrote it
+    /// (e.g. PowerShell aliases expanded to cmdlets).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normalized_command: Option<String>,
     /// Evaluation outcome.
     pub outcome: Outcome,
     /// Pack ID that matched (if any).
@@ -287,6 +293,12 @@ pub struct CommandEntry {
     /// Bypass code used (if command was bypassed).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bypass_code: Option<String>,
+    /// Root of the git repository enclosing `working_dir` (if any).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo_root: Option<String>,
+    /// Git branch checked out in `repo_root` (None when detached or outside a repo).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
 }
 
 impl Default for CommandEntry {
@@ -296,6 +308,7 @@ impl Default for CommandEntry {
             agent_type: String::new(),
             working_dir: String::new(),
             command: String::new(),
+            normalized_command: None,
             outcome: Outcome::Allow,
             pack_id: None,
             pattern_name: None,
@@ -307,6 +320,8 @@ impl Default for CommandEntry {
             hostname: None,
             allowlist_layer: None,
             bypass_code: None,
+            repo_root: None,
+            branch: None,
         }
     }
 }
@@ -388,6 +403,45 @@ pub struct StatsTrends {
     pub top_pattern_change: Vec<(String, i32)>,
 }
 
+/// Count of denials attributed to a single rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeniedRuleStat {
+    pub rule_id: String,
+    pub count: u64,
+}
+
+/// Per-agent outcome breakdown.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentOutcomeStat {
+    pub name: String,
+    pub total: u64,
+    pub outcomes: OutcomeStats,
+}
+
+/// Share of commands per outcome, as fractions of the total (0.0 - 1.0).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OutcomeRatios {
+    pub deny: f64,
+    pub allow: f64,
+    pub warn: f64,
+    pub bypass: f64,
+}
+
+/// At-a-glance history analytics for `dcg stats --since`.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryOverview {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub total_commands: u64,
+    pub outcomes: OutcomeStats,
+    pub ratios: OutcomeRatios,
+    pub top_denied_rules: Vec<DeniedRuleStat>,
+    pub busiest_dirs: Vec<ProjectStat>,
+    pub agents: Vec<AgentOutcomeStat>,
+    /// Evaluation latency over commands with a recorded duration.
+    pub latency: PerformanceStats,
+}
+
 /// Result of a database health check.
 #[derive(Debug, Clone, Serialize)]
 pub struct CheckResult {
@@ -628,6 +682,52 @@ fn format_timestamp(dt: DateTime<Utc>) -> String {
     dt.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
 }
 
+/// Columns read by [`command_entry_from_values`], in order.
+const COMMAND_ENTRY_COLUMNS: &str = "id, timestamp, agent_type, working_dir, command, outcome,
+    pack_id, pattern_name, rule_id, eval_duration_us, session_id,
+    exit_code, parent_command_id, hostname, allowlist_layer, bypass_code,
+    repo_root, branch, normalized_command";
+
+/// Build a `(id, CommandEntry)` from a row selected with [`COMMAND_ENTRY_COLUMNS`].
+fn command_entry_from_values(vals: &[SqliteValue]) -> (i64, CommandEntry) {
+    let timestamp_str = sv_to_string(&vals[1]);
+    let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
+        .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc));
+
+    let outcome_str = sv_to_string(&vals[5]);
+    let outcome = Outcome::parse(&outcome_str).unwrap_or(Outcome::Allow);
+
+    let eval_duration_us = sv_to_i64(&vals[9]);
+
+    let entry = CommandEntry {
+        timestamp,
+        agent_type: sv_to_string(&vals[2]),
+        working_dir: sv_to_string(&vals[3]),
+        command: sv_to_string(&vals[4]),
+        outcome,
+        pack_id: sv_to_opt_string(&vals[6]),
+        pattern_name: sv_to_opt_string(&vals[7]),
+        rule_id: sv_to_opt_string(&vals[8]),
+        eval_duration_us: u64::try_from(eval_duration_us).unwrap_or(0),
+        session_id: sv_to_opt_string(&vals[10]),
+        exit_code: match &vals[11] {
+            SqliteValue::Integer(i) => Some(i32::try_from(*i).unwrap_or(0)),
+            _ => None,
+        },
+        parent_command_id: match &vals[12] {
+            SqliteValue::Integer(i) => Some(*i),
+            _ => None,
+        },
+        hostname: sv_to_opt_string(&vals[13]),
+        allowlist_layer: sv_to_opt_string(&vals[14]),
+        bypass_code: sv_to_opt_string(&vals[15]),
+        repo_root: sv_to_opt_string(&vals[16]),
+        branch: sv_to_opt_string(&vals[17]),
+        normalized_command: sv_to_opt_string(&vals[18]),
+    };
+    (sv_to_i64(&vals[0]), entry)
+}
+
 fn percentile_from_sorted(values: &[u64], numerator: usize, denominator: usize) -> u64 {
     if values.is_empty() || denominator == 0 {
         return 0;
@@ -926,6 +1026,125 @@ impl HistoryDb {
         })
     }
 
+    /// Compute an at-a-glance overview of history since `since`.
+    ///
+    /// All figures come from aggregate queries; `limit` caps the number of
+    /// denied rules and working directories returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any underlying query fails.
+    pub fn compute_overview(
+        &self,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<HistoryOverview, HistoryError> {
+        let until = Utc::now();
+        let ts_params = &[
+            SqliteValue::Text(format_timestamp(since)),
+            SqliteValue::Text(format_timestamp(until)),
+            SqliteValue::Integer(i64::try_from(limit).unwrap_or(i64::MAX)),
+        ];
+
+        let mut outcomes = OutcomeStats::default();
+        let mut agents: Vec<AgentOutcomeStat> = Vec::new();
+        let agent_rows = self.conn.query(&inline_params(
+            "SELECT agent_type, outcome, COUNT(*) FROM commands
+             WHERE timestamp >= ?1 AND timestamp < ?2
+             GROUP BY agent_type, outcome",
+            ts_params,
+        ))?;
+        for row in &agent_rows {
+            let vals = row.values();
+            let name = sv_to_string(&vals[0]);
+            let count = u64::try_from(sv_to_i64(&vals[2])).unwrap_or(0);
+            let Some(outcome) = Outcome::parse(&sv_to_string(&vals[1])) else {
+                continue;
+            };
+
+            let idx = agents
+                .iter()
+                .position(|a| a.name == name)
+                .unwrap_or_else(|| {
+                    agents.push(AgentOutcomeStat {
+                        name,
+                        total: 0,
+                        outcomes: OutcomeStats::default(),
+                    });
+                    agents.len() - 1
+                });
+            let agent = &mut agents[idx];
+            agent.total += count;
+            for stats in [&mut agent.outcomes, &mut outcomes] {
+                match outcome {
+                    Outcome::Allow => stats.allowed += count,
+                    Outcome::Deny => stats.denied += count,
+                    Outcome::Warn => stats.warned += count,
+                    Outcome::Bypass => stats.bypassed += count,
+                }
+            }
+        }
+        agents.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.name.cmp(&b.name)));
+
+        let total_commands =
+            outcomes.allowed + outcomes.denied + outcomes.warned + outcomes.bypassed;
+        let ratios = OutcomeRatios {
+            deny: ratio(outcomes.denied, total_commands),
+            allow: ratio(outcomes.allowed, total_commands),
+            warn: ratio(outcomes.warned, total_commands),
+            bypass: ratio(outcomes.bypassed, total_commands),
+        };
+
+        let mut top_denied_rules = Vec::new();
+        let rule_rows = self.conn.query(&inline_params(
+            "SELECT rule_id, COUNT(*) FROM commands
+             WHERE timestamp >= ?1 AND timestamp < ?2
+               AND outcome = 'deny' AND rule_id IS NOT NULL
+             GROUP BY rule_id
+             ORDER BY COUNT(*) DESC, rule_id ASC
+             LIMIT ?3",
+            ts_params,
+        ))?;
+        for row in &rule_rows {
+            let vals = row.values();
+            top_denied_rules.push(DeniedRuleStat {
+                rule_id: sv_to_string(&vals[0]),
+                count: u64::try_from(sv_to_i64(&vals[1])).unwrap_or(0),
+            });
+        }
+
+        let mut busiest_dirs = Vec::new();
+        let dir_rows = self.conn.query(&inline_params(
+            "SELECT working_dir, COUNT(*) FROM commands
+             WHERE timestamp >= ?1 AND timestamp < ?2
+             GROUP BY working_dir
+             ORDER BY COUNT(*) DESC, working_dir ASC
+             LIMIT ?3",
+            ts_params,
+        ))?;
+        for row in &dir_rows {
+            let vals = row.values();
+            busiest_dirs.push(ProjectStat {
+                path: sv_to_string(&vals[0]),
+                command_count: u64::try_from(sv_to_i64(&vals[1])).unwrap_or(0),
+            });
+        }
+
+        let latency = self.compute_performance(ts_params)?;
+
+        Ok(HistoryOverview {
+            since,
+            until,
+            total_commands,
+            outcomes,
+            ratios,
+            top_denied_rules,
+            busiest_dirs,
+            agents,
+            latency,
+        })
+    }
+
     #[allow(clippy::too_many_lines)]
     fn compute_stats_range(
         &self,
@@ -1025,6 +1244,26 @@ impl HistoryDb {
             });
         }
 
+        let performance = self.compute_performance(ts_params)?;
+
+        Ok(StatsSnapshot {
+            total_commands,
+            outcomes,
+            block_rate,
+            top_patterns,
+            top_projects,
+            agents,
+            performance,
+        })
+    }
+
+    /// Evaluation latency percentiles for `?1 <= timestamp < ?2`.
+    ///
+    /// Rows without a recorded duration (`eval_duration_us = 0`) are ignored.
+    fn compute_performance(
+        &self,
+        ts_params: &[SqliteValue],
+    ) -> Result<PerformanceStats, HistoryError> {
         let mut durations = Vec::new();
         let dur_rows = self.conn.query(&inline_params(
             "SELECT eval_duration_us FROM commands
@@ -1039,26 +1278,15 @@ impl HistoryDb {
             }
         }
 
-        let performance = if durations.is_empty() {
-            PerformanceStats::default()
-        } else {
-            let max_us = *durations.last().unwrap_or(&0);
-            PerformanceStats {
-                p50_us: percentile_from_sorted(&durations, 50, 100),
-                p95_us: percentile_from_sorted(&durations, 95, 100),
-                p99_us: percentile_from_sorted(&durations, 99, 100),
-                max_us,
-            }
-        };
-
-        Ok(StatsSnapshot {
-            total_commands,
-            outcomes,
-            block_rate,
-            top_patterns,
-            top_projects,
-            agents,
-            performance,
+        if durations.is_empty() {
+            return Ok(PerformanceStats::default());
+        }
+        let max_us = *durations.last().unwrap_or(&0);
+        Ok(PerformanceStats {
+            p50_us: percentile_from_sorted(&durations, 50, 100),
+            p95_us: percentile_from_sorted(&durations, 95, 100),
+            p99_us: percentile_from_sorted(&durations, 99, 100),
+            max_us,
         })
     }
 
@@ -1081,9 +1309,10 @@ impl HistoryDb {
                 timestamp, agent_type, working_dir, command, command_hash,
                 outcome, pack_id, pattern_name, rule_id, eval_duration_us,
                 session_id, exit_code, parent_command_id, hostname,
-                allowlist_layer, bypass_code
+                allowlist_layer, bypass_code, repo_root, branch, normalized_command
             ) VALUES (
-                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                ?17, ?18, ?19
             )",
             &[
                 SqliteValue::Text(timestamp),
@@ -1102,6 +1331,9 @@ impl HistoryDb {
                 opt_string_to_sv(entry.hostname.as_ref()),
                 opt_string_to_sv(entry.allowlist_layer.as_ref()),
                 opt_string_to_sv(entry.bypass_code.as_ref()),
+                opt_string_to_sv(entry.repo_root.as_ref()),
+                opt_string_to_sv(entry.branch.as_ref()),
+                opt_string_to_sv(entry.normalized_command.as_ref()),
             ],
         )?;
 
@@ -1183,7 +1415,10 @@ impl HistoryDb {
                 parent_command_id INTEGER REFERENCES commands(id),
                 hostname TEXT,
                 allowlist_layer TEXT,
-                bypass_code TEXT
+                bypass_code TEXT,
+                repo_root TEXT,
+                branch TEXT,
+                normalized_command TEXT
             )",
         )?;
 
@@ -1198,6 +1433,7 @@ impl HistoryDb {
         self.conn
             .execute("CREATE INDEX IF NOT EXISTS idx_commands_pack_id ON commands(pack_id)")?;
         self.conn.execute("CREATE INDEX IF NOT EXISTS idx_commands_rule_id ON commands(rule_id) WHERE rule_id IS NOT NULL")?;
+        self.conn.execute("CREATE INDEX IF NOT EXISTS idx_commands_branch ON commands(branch) WHERE branch IS NOT NULL")?;
         self.conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_commands_agent_type ON commands(agent_type)",
         )?;
@@ -1295,6 +1531,12 @@ impl HistoryDb {
         if from_version < 5 {
             self.migrate_v4_to_v5()?;
         }
+        if from_version < 6 {
+            self.migrate_v5_to_v6()?;
+        }
+        if from_version < 7 {
+            self.migrate_v6_to_v7()?;
+        }
 
         // Ensure we're at the expected version
         let current = self.get_schema_version()?;
@@ -1449,6 +1691,64 @@ impl HistoryDb {
         Ok(())
     }
 
+    fn migrate_v5_to_v6(&self) -> Result<(), HistoryError> {
+        // Add repo_root/branch provenance columns
+        let rows = self.conn.query("PRAGMA table_info(commands)")?;
+        let has_column = |name: &str| {
+            rows.iter()
+                .any(|row| sv_to_string(&row.values()[1]) == name)
+        };
+
+        if !has_column("repo_root") {
+            self.conn
+                .execute("ALTER TABLE commands ADD COLUMN repo_root TEXT")?;
+        }
+        if !has_column("branch") {
+            self.conn
+                .execute("ALTER TABLE commands ADD COLUMN branch TEXT")?;
+        }
+
+        self.conn.execute(
+            r"CREATE INDEX IF NOT EXISTS idx_commands_branch
+              ON commands(branch) WHERE branch IS NOT NULL",
+        )?;
+
+        // Record migration
+        self.conn.execute_with_params(
+            "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+            &[
+                SqliteValue::Integer(6),
+                SqliteValue::Text("Add repo_root and branch provenance columns".to_string()),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn migrate_v6_to_v7(&self) -> Result<(), HistoryError> {
+        // Keep the submitted command next to its shell-normalized form
+        let rows = self.conn.query("PRAGMA table_info(commands)")?;
+        let has_normalized = rows
+            .iter()
+            .any(|row| sv_to_string(&row.values()[1]) == "normalized_command");
+
+        if !has_normalized {
+            self.conn
+                .execute("ALTER TABLE commands ADD COLUMN normalized_command TEXT")?;
+        }
+
+        // Record migration
+        self.conn.execute_with_params(
+            "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+            &[
+                SqliteValue::Integer(7),
+                SqliteValue::Text("Add normalized_command column".to_string()),
+            ],
+        )?;
+
+        Ok(())
+    }
+
     // ========================================================================
     // Batch Operations
     // ========================================================================
@@ -1483,9 +1783,11 @@ impl HistoryDb {
                         timestamp, agent_type, working_dir, command, command_hash,
                         outcome, pack_id, pattern_name, eval_duration_us,
                         session_id, exit_code, parent_command_id, hostname,
-                        allowlist_layer, bypass_code, rule_id
+                        allowlist_layer, bypass_code, rule_id, repo_root, branch,
+                        normalized_command
                     ) VALUES (
-                        ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16
+                        ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                        ?17, ?18, ?19
                     )",
                     &[
                         SqliteValue::Text(timestamp),
@@ -1504,6 +1806,9 @@ impl HistoryDb {
                         opt_string_to_sv(entry.allowlist_layer.as_ref()),
                         opt_string_to_sv(entry.bypass_code.as_ref()),
                         opt_string_to_sv(entry.get_rule_id().as_ref()),
+                        opt_string_to_sv(entry.repo_root.as_ref()),
+                        opt_string_to_sv(entry.branch.as_ref()),
+                        opt_string_to_sv(entry.normalized_command.as_ref()),
                     ],
                 );
                 self.conn.execute(&sql)?;
@@ -1934,12 +2239,23 @@ impl HistoryDb {
         &self,
         options: &ExportOptions,
     ) -> Result<Vec<CommandEntry>, HistoryError> {
-        let mut sql = String::from(
-            "SELECT timestamp, agent_type, working_dir, command, outcome,
-                    pack_id, pattern_name, rule_id, eval_duration_us, session_id,
-                    exit_code, parent_command_id, hostname, allowlist_layer, bypass_code
-             FROM commands WHERE 1=1",
-        );
+        Ok(self
+            .query_commands_with_ids(options)?
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect())
+    }
+
+    /// Query commands with optional filtering, keeping each row's database id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn query_commands_with_ids(
+        &self,
+        options: &ExportOptions,
+    ) -> Result<Vec<(i64, CommandEntry)>, HistoryError> {
+        let mut sql = format!("SELECT {COMMAND_ENTRY_COLUMNS} FROM commands WHERE 1=1");
         let mut params: Vec<SqliteValue> = Vec::new();
         let mut param_idx = 1;
 
@@ -1961,7 +2277,16 @@ impl HistoryDb {
             param_idx += 1;
         }
 
-        sql.push_str(" ORDER BY timestamp DESC");
+        if let Some(min_duration_us) = options.min_duration_us {
+            write!(sql, " AND eval_duration_us >= ?{param_idx}").unwrap();
+            params.push(SqliteValue::Integer(
+                i64::try_from(min_duration_us).unwrap_or(i64::MAX),
+            ));
+            param_idx += 1;
+            sql.push_str(" ORDER BY eval_duration_us DESC, timestamp DESC");
+        } else {
+            sql.push_str(" ORDER BY timestamp DESC");
+        }
 
         if let Some(limit) = options.limit {
             write!(sql, " LIMIT ?{param_idx}").unwrap();
@@ -1971,46 +2296,25 @@ impl HistoryDb {
         }
 
         let rows = self.conn.query(&inline_params(&sql, &params))?;
+        Ok(rows
+            .iter()
+            .map(|row| command_entry_from_values(row.values()))
+            .collect())
+    }
 
-        let mut entries = Vec::new();
-        for row in &rows {
-            let vals = row.values();
-            let timestamp_str = sv_to_string(&vals[0]);
-            let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
-                .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc));
-
-            let outcome_str = sv_to_string(&vals[4]);
-            let outcome = Outcome::parse(&outcome_str).unwrap_or(Outcome::Allow);
-
-            let eval_duration_us = sv_to_i64(&vals[8]);
-
-            entries.push(CommandEntry {
-                timestamp,
-                agent_type: sv_to_string(&vals[1]),
-                working_dir: sv_to_string(&vals[2]),
-                command: sv_to_string(&vals[3]),
-                outcome,
-                pack_id: sv_to_opt_string(&vals[5]),
-                pattern_name: sv_to_opt_string(&vals[6]),
-                rule_id: sv_to_opt_string(&vals[7]),
-                eval_duration_us: u64::try_from(eval_duration_us).unwrap_or(0),
-                session_id: sv_to_opt_string(&vals[9]),
-                exit_code: match &vals[10] {
-                    SqliteValue::Integer(i) => Some(i32::try_from(*i).unwrap_or(0)),
-                    SqliteValue::Null => None,
-                    _ => None,
-                },
-                parent_command_id: match &vals[11] {
-                    SqliteValue::Integer(i) => Some(*i),
-                    SqliteValue::Null => None,
-                    _ => None,
-                },
-                hostname: sv_to_opt_string(&vals[12]),
-                allowlist_layer: sv_to_opt_string(&vals[13]),
-                bypass_code: sv_to_opt_string(&vals[14]),
-            });
-        }
-        Ok(entries)
+    /// Look up a single command by its database id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub fn get_command(&self, id: i64) -> Result<Option<CommandEntry>, HistoryError> {
+        let sql = format!("SELECT {COMMAND_ENTRY_COLUMNS} FROM commands WHERE id = ?1");
+        let rows = self
+            .conn
+            .query(&inline_params(&sql, &[SqliteValue::Integer(id)]))?;
+        Ok(rows
+            .first()
+            .map(|row| command_entry_from_values(row.values()).1))
     }
 
     /// Export commands to JSON format.
@@ -2069,7 +2373,7 @@ impl HistoryDb {
         Ok(count)
     }
 
-    /// Export commands to CSV format.
+    /// Export commands to CSV format (RFC 4180 quoting).
     ///
     /// Includes a header row followed by data rows.
     ///
@@ -2080,29 +2384,63 @@ impl HistoryDb {
         &self,
         writer: &mut W,
         options: &ExportOptions,
+    ) -> Result<usize, HistoryError> {
+        self.export_delimited(writer, options, DelimitedFormat::Csv)
+    }
+
+    /// Export commands to tab-separated values.
+    ///
+    /// Includes a header row followed by data rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query or write fails.
+    pub fn export_tsv<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        options: &ExportOptions,
+    ) -> Result<usize, HistoryError> {
+        self.export_delimited(writer, options, DelimitedFormat::Tsv)
+    }
+
+    fn export_delimited<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+        options: &ExportOptions,
+        format: DelimitedFormat,
     ) -> Result<usize, HistoryError> {
         let entries = self.query_commands_for_export(options)?;
         let count = entries.len();
 
-        // Write header
         writeln!(
             writer,
-            "timestamp,agent_type,working_dir,command,outcome,pack_id,pattern_name,eval_duration_us"
+            "{}",
+            format.record([
+                "timestamp",
+                "agent_type",
+                "working_dir",
+                "command",
+                "outcome",
+                "pack_id",
+                "pattern_name",
+                "eval_duration_us",
+            ])
         )?;
 
-        // Write data rows
         for entry in &entries {
             writeln!(
                 writer,
-                "{},{},{},{},{},{},{},{}",
-                csv_escape(&format_timestamp(entry.timestamp)),
-                csv_escape(&entry.agent_type),
-                csv_escape(&entry.working_dir),
-                csv_escape(&entry.command),
-                entry.outcome.as_str(),
-                entry.pack_id.as_deref().unwrap_or(""),
-                entry.pattern_name.as_deref().unwrap_or(""),
-                entry.eval_duration_us,
+                "{}",
+                format.record([
+                    format_timestamp(entry.timestamp).as_str(),
+                    entry.agent_type.as_str(),
+                    entry.working_dir.as_str(),
+                    entry.command.as_str(),
+                    entry.outcome.as_str(),
+                    entry.pack_id.as_deref().unwrap_or(""),
+                    entry.pattern_name.as_deref().unwrap_or(""),
+                    entry.eval_duration_us.to_string().as_str(),
+                ])
             )?;
         }
 
@@ -2980,6 +3318,9 @@ pub struct ExportOptions {
     pub until: Option<DateTime<Utc>>,
     /// Maximum number of records to export.
     pub limit: Option<usize>,
+    /// Include only commands whose evaluation took at least this many
+    /// microseconds. Results are then ordered slowest first.
+    pub min_duration_us: Option<u64>,
 }
 
 /// Exported data container with metadata.
@@ -3283,15 +3624,6 @@ impl Default for SuggestionAuditEntry {
     }
 }
 
-/// Escape a string for CSV output.
-fn csv_escape(s: &str) -> String {
-    if s.contains(',') || s.contains('"') || s.contains('\n') || s.contains('\r') {
-        format!("\"{}\"", s.replace('"', "\"\""))
-    } else {
-        s.to_string()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4037,6 +4369,7 @@ mod tests {
             agent_type: "claude_code".to_string(),
             working_dir: "/project".to_string(),
             command: "test command".to_string(),
+            normalized_command: Some("test-command".to_string()),
             outcome: Outcome::Deny,
             pack_id: Some("core.git".to_string()),
             pattern_name: Some("force-push".to_string()),
@@ -4048,6 +4381,8 @@ mod tests {
             hostname: Some("dev-machine".to_string()),
             allowlist_layer: None,
             bypass_code: Some("ab12".to_string()),
+            repo_root: Some("/project".to_string()),
+            branch: Some("main".to_string()),
         };
 
         let id = db.log_command(&entry).unwrap();
@@ -4076,6 +4411,18 @@ mod tests {
         assert_eq!(session_id, Some("session-123".to_string()));
         assert_eq!(hostname, Some("dev-machine".to_string()));
         assert_eq!(bypass_code, Some("ab12".to_string()));
+
+        let row = db
+            .conn
+            .query_row_with_params(
+                "SELECT repo_root, branch, normalized_command FROM commands WHERE id = ?1",
+                &[SqliteValue::Integer(id)],
+            )
+            .unwrap();
+        let vals = row.values();
+        assert_eq!(sv_to_opt_string(&vals[0]), Some("/project".to_string()));
+        assert_eq!(sv_to_opt_string(&vals[1]), Some("main".to_string()));
+        assert_eq!(sv_to_opt_string(&vals[2]), Some("test-command".to_string()));
     }
 
     #[test]
@@ -4328,6 +4675,71 @@ mod tests {
         assert_eq!(entries.len(), 5);
     }
 
+    #[test]
+    fn test_query_commands_min_duration_slowest_first() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        for (command, eval_duration_us) in [
+            ("git status", 40),
+            ("git reset --hard", 5_000),
+            ("ls -la", 999),
+            ("rm -rf ./build", 1_000),
+            ("kubectl delete ns prod", 250_000),
+        ] {
+            db.log_command(&CommandEntry {
+                command: command.to_string(),
+                eval_duration_us,
+                ..Default::default()
+            })
+            .unwrap();
+        }
+
+        let slow = db
+            .query_commands_for_export(&ExportOptions {
+                min_duration_us: Some(1_000),
+                ..Default::default()
+            })
+            .unwrap();
+        let durations: Vec<u64> = slow.iter().map(|e| e.eval_duration_us).collect();
+        assert_eq!(durations, [250_000, 5_000, 1_000]);
+        assert_eq!(slow[0].command, "kubectl delete ns prod");
+
+        let slowest = db
+            .query_commands_for_export(&ExportOptions {
+                min_duration_us: Some(1_000),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(slowest.len(), 1);
+        assert_eq!(slowest[0].eval_duration_us, 250_000);
+    }
+
+    #[test]
+    fn test_get_command_by_id() {
+        let db = HistoryDb::open_in_memory().unwrap();
+        let id = db
+            .log_command(&CommandEntry {
+                command: "git reset --hard".to_string(),
+                outcome: Outcome::Deny,
+                pack_id: Some("core.git".to_string()),
+                pattern_name: Some("reset-hard".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let entry = db.get_command(id).unwrap().expect("entry exists");
+        assert_eq!(entry.command, "git reset --hard");
+        assert_eq!(entry.outcome, Outcome::Deny);
+        assert_eq!(entry.pack_id.as_deref(), Some("core.git"));
+        assert!(db.get_command(id + 1).unwrap().is_none());
+
+        let with_ids = db
+            .query_commands_with_ids(&ExportOptions::default())
+            .unwrap();
+        assert_eq!(with_ids.len(), 1);
+        assert_eq!(with_ids[0].0, id);
+    }
+
     // ========================================================================
     // History Analyzer Tests
     // ========================================================================
@@ -38,6 +38,9 @@ pub struct HookInput {
     /// May be a JSON string (e.g. "{\"command\":\"...\"}") or an object.
     #[serde(alias = "toolArgs")]
     pub tool_args: Option<serde_json::Value>,
+
+    /// Working directory of the agent session, when the client sends one.
+    pub cwd: Option<String>,
 }
 
 /// Tool-specific input containing the command to execute.
@@ -45,6 +48,26 @@ pub struct HookInput {
 pub struct ToolInput {
     /// The command string (for Bash tools).
     pub command: Option<serde_json::Value>,
+
+    /// The target file (for Write/Edit tools).
+    pub file_path: Option<serde_json::Value>,
+}
+
+/// A file-mutating tool call (`Write`, `Edit`, `MultiEdit`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileWrite {
+    /// Tool name as sent by the client.
+    pub tool: String,
+    /// Target file path.
+    pub file_path: String,
+}
+
+impl FileWrite {
+    /// Command-like rendering used for denial output, history, and logs.
+    #[must_use]
+    pub fn display_command(&self) -> String {
+        format!("{} {}", self.tool, self.file_path)
+    }
 }
 
 /// Output structure for denying a command.
@@ -62,7 +85,7 @@ pub struct HookSpecificOutput<'a> {
     #[serde(rename = "hookEventName")]
     pub hook_event_name: &'static str,
 
-    /// The permission decision: "allow" or "deny".
+    /// The permission decision: "allow", "deny", or "ask".
     #[serde(rename = "permissionDecision")]
     pub permission_decision: &'static str,
 
@@ -170,6 +193,8 @@ pub enum HookProtocol {
 pub struct AllowOnceInfo {
     pub code: String,
     pub full_hash: String,
+    /// Confirmation token (`[exceptions] require_hmac`); shown on stderr only.
+    pub token: Option<String>,
 }
 
 /// Remediation suggestions for blocked commands.
@@ -215,10 +240,22 @@ pub enum HookResult {
     ParseError,
 }
 
+/// Where hook mode reads its JSON input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HookInputSource {
+    /// Standard input (the default).
+    #[default]
+    Stdin,
+    /// An inherited file descriptor (`--input-fd 3`).
+    Fd(u32),
+    /// A file or named pipe (`--input-path /tmp/fifo`).
+    Path(std::path::PathBuf),
+}
+
 /// Error type for reading and parsing hook input.
 #[derive(Debug)]
 pub enum HookReadError {
-    /// Failed to read from stdin.
+    /// Failed to read the input source.
     Io(io::Error),
     /// Input exceeded the configured size limit.
     InputTooLarge(usize),
@@ -234,14 +271,50 @@ pub enum HookReadError {
 /// if the input is not valid hook JSON, or [`HookReadError::InputTooLarge`] if
 /// the input exceeds `max_bytes`.
 pub fn read_hook_input(max_bytes: usize) -> Result<HookInput, HookReadError> {
+    read_hook_input_from(&HookInputSource::Stdin, max_bytes)
+}
+
+/// Read and parse hook input from stdin, a file descriptor, or a path.
+///
+/// File descriptors are opened through `/dev/fd`, so `--input-fd` is only
+/// available on Unix.
+///
+/// # Errors
+///
+/// Same as [`read_hook_input`]; [`HookReadError::Io`] also covers a path or
+/// descriptor that cannot be opened.
+pub fn read_hook_input_from(
+    source: &HookInputSource,
+    max_bytes: usize,
+) -> Result<HookInput, HookReadError> {
+    // Read up to limit + 1 to detect overflow
+    let limit = max_bytes as u64 + 1;
     let mut input = String::with_capacity(256);
-    {
-        let stdin = io::stdin();
-        // Read up to limit + 1 to detect overflow
-        let mut handle = stdin.lock().take(max_bytes as u64 + 1);
-        handle
-            .read_to_string(&mut input)
-            .map_err(HookReadError::Io)?;
+    match source {
+        HookInputSource::Stdin => {
+            let stdin = io::stdin();
+            stdin
+                .lock()
+                .take(limit)
+                .read_to_string(&mut input)
+                .map_err(HookReadError::Io)?;
+        }
+        HookInputSource::Fd(fd) => {
+            if !cfg!(unix) {
+                return Err(HookReadError::Io(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "--input-fd is only supported on Unix",
+                )));
+            }
+            std::fs::File::open(format!("/dev/fd/{fd}"))
+                .and_then(|file| file.take(limit).read_to_string(&mut input))
+                .map_err(HookReadError::Io)?;
+        }
+        HookInputSource::Path(path) => {
+            std::fs::File::open(path)
+                .and_then(|file| file.take(limit).read_to_string(&mut input))
+                .map_err(HookReadError::Io)?;
+        }
     }
 
     if input.len() > max_bytes {
@@ -330,6 +403,34 @@ pub fn extract_command_with_protocol(input: &HookInput) -> Option<(String, HookP
     None
 }
 
+fn is_supported_file_tool(tool_name: Option<&str>) -> bool {
+    let Some(tool_name) = tool_name else {
+        return false;
+    };
+
+    matches!(
+        tool_name.to_ascii_lowercase().as_str(),
+        "write" | "edit" | "multiedit"
+    )
+}
+
+/// Extract the target of a file-mutating tool call from hook input.
+#[must_use]
+pub fn extract_file_write(input: &HookInput) -> Option<FileWrite> {
+    let tool = input.tool_name.as_deref()?;
+    if !is_supported_file_tool(Some(tool)) {
+        return None;
+    }
+
+    match input.tool_input.as_ref()?.file_path.as_ref()? {
+        serde_json::Value::String(path) if !path.is_empty() => Some(FileWrite {
+            tool: tool.to_string(),
+            file_path: path.clone(),
+        }),
+        _ => None,
+    }
+}
+
 /// Extract the command string from hook input.
 #[must_use]
 pub fn extract_command(input: &HookInput) -> Option<String> {
@@ -459,6 +560,7 @@ pub fn print_colorful_warning(
     pattern: Option<&str>,
     explanation: Option<&str>,
     allow_once_code: Option<&str>,
+    allow_once_token: Option<&str>,
     matched_span: Option<&MatchSpan>,
     pattern_suggestions: &[PatternSuggestion],
     severity: Option<crate::packs::Severity>,
@@ -545,6 +647,9 @@ pub fn print_colorful_warning(
     if let Some(ref rule) = rule_id {
         eprintln!("  $ {cyan}dcg allowlist add {rule} --project{reset}");
     }
+    if let (Some(code), Some(token)) = (allow_once_code, allow_once_token) {
+        eprintln!("  $ {cyan}dcg allow-once {code} --token {token}{reset}");
+    }
 
     eprintln!();
     eprintln!("{footer_style}False positive? File an issue:{reset}");
@@ -651,6 +756,7 @@ pub fn output_denial_for_protocol(
         pattern,
         explanation,
         allow_once_code,
+        allow_once.and_then(|info| info.token.as_deref()),
         matched_span,
         pattern_suggestions,
         severity,
@@ -743,6 +849,62 @@ pub fn output_denial(
     );
 }
 
+/// Output an "ask" response: the agent must get user confirmation before running
+/// the command.
+///
+/// Copilot hooks have no ask decision, so the command is denied there instead.
+#[cold]
+#[inline(never)]
+pub fn output_ask_for_protocol(protocol: HookProtocol, command: &str, reason: &str) {
+    if protocol == HookProtocol::Copilot {
+        output_denial_for_protocol(
+            protocol,
+            command,
+            reason,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            &[],
+        );
+        return;
+    }
+
+    let stderr = io::stderr();
+    let mut handle = stderr.lock();
+    let _ = writeln!(handle);
+    let _ = writeln!(
+        handle,
+        "{} {}",
+        "dcg ASK (confirmation required):".yellow().bold(),
+        reason
+    );
+    let _ = writeln!(handle, "  {} {}", "Command:".bright_black(), command);
+    drop(handle);
+
+    let output = HookOutput {
+        hook_specific_output: HookSpecificOutput {
+            hook_event_name: "PreToolUse",
+            permission_decision: "ask",
+            permission_decision_reason: Cow::Owned(format!("dcg: {reason}")),
+            allow_once_code: None,
+            allow_once_full_hash: None,
+            rule_id: None,
+            pack_id: None,
+            severity: None,
+            confidence: None,
+            remediation: None,
+        },
+    };
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    let _ = serde_json::to_writer(&mut handle, &output);
+    let _ = writeln!(handle);
+}
+
 /// Output a warning to stderr (no JSON deny; command is allowed).
 #[cold]
 #[inline(never)]
@@ -792,6 +954,10 @@ pub fn output_warning(
 
 /// Log a blocked command to a file (if logging is enabled).
 ///
+/// `command` is what the agent submitted; `evaluated` is the form dcg matched
+/// against and is logged on its own line when the shell normalizer rewrote it.
+/// Both lines go through `redaction` before they are written.
+///
 /// # Errors
 ///
 /// Returns any I/O errors encountered while creating directories or appending
@@ -799,8 +965,10 @@ pub fn output_warning(
 pub fn log_blocked_command(
     log_file: &str,
     command: &str,
+    evaluated: &str,
     reason: &str,
     pack: Option<&str>,
+    redaction: &crate::logging::RedactionConfig,
 ) -> io::Result<()> {
     use std::fs::OpenOptions;
 
@@ -825,7 +993,18 @@ pub fn log_blocked_command(
     let pack_str = pack.unwrap_or("unknown");
 
     writeln!(file, "[{timestamp}] [{pack_str}] {reason}")?;
-    writeln!(file, "  Command: {command}")?;
+    writeln!(
+        file,
+        "  Command: {}",
+        crate::logging::redact_command(command, redaction)
+    )?;
+    if evaluated != command {
+        writeln!(
+            file,
+            "  Evaluated as: {}",
+            crate::logging::redact_command(evaluated, redaction)
+        )?;
+    }
     writeln!(file)?;
 
     Ok(())
@@ -952,6 +1131,29 @@ mod tests {
         assert_eq!(extract_command(&input), None);
     }
 
+    #[test]
+    fn test_parse_write_tool_file_path() {
+        let json =
+            r#"{"tool_name":"Write","tool_input":{"file_path":"/etc/passwd","content":"x"}}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(extract_command(&input), None);
+        assert_eq!(
+            extract_file_write(&input),
+            Some(FileWrite {
+                tool: "Write".to_string(),
+                file_path: "/etc/passwd".to_string(),
+            })
+        );
+
+        let json = r#"{"tool_name":"Read","tool_input":{"file_path":"/etc/passwd"}}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(extract_file_write(&input), None);
+
+        let json = r#"{"tool_name":"Bash","tool_input":{"command":"ls"}}"#;
+        let input: HookInput = serde_json::from_str(json).unwrap();
+        assert_eq!(extract_file_write(&input), None);
+    }
+
     #[test]
     fn test_parse_missing_command() {
         let json = r#"{"tool_name":"Bash","tool_input":{}}"#;
@@ -1015,4 +1217,28 @@ mod tests {
 
         assert!(std::env::var(key).is_err());
     }
+
+    #[test]
+    fn test_log_blocked_command_redacts_evaluated_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("blocked.log");
+        let redaction = crate::logging::RedactionConfig {
+            enabled: true,
+            ..Default::default()
+        };
+
+        log_blocked_command(
+            log_path.to_str().unwrap(),
+            "Remove-Item -Recurse -Force C:\\ -Token TOKEN=hunter2",
+            "rm -rf C:\\ TOKEN=hunter2",
+            "destructive",
+            Some("windows.powershell"),
+            &redaction,
+        )
+        .unwrap();
+
+        let written = std::fs::read_to_string(&log_path).unwrap();
+        assert!(written.contains("  Evaluated as: "));
+        assert!(!written.contains("hunter2"), "secret leaked: {written}");
+    }
 }
@@ -0,0 +1,138 @@
+//! Message catalogs for localized denial output.
+//!
+//! A catalog is a TOML file with one table per locale, keyed by rule id
+//! (`pack_id:pattern_name`). Any field left out falls back to the built-in
+//! English text:
+//!
+//! ```toml
+//! [ja."core.git:reset-hard"]
+//! reason = "git reset --hard はコミットされていない変更を破棄します。"
+//! explanation = "作業ツリーとインデックスの変更がすべて失われます。"
+//! ```
+//!
+//! Select the locale and catalog in config:
+//!
+//! ```toml
+//! [i18n]
+//! locale = "ja"
+//! catalog_path = "~/.config/dcg/messages.toml"
+//! ```
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Translated strings for a single rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CatalogEntry {
+    /// Replacement for the pattern's short reason.
+    pub reason: Option<String>,
+    /// Replacement for the pattern's detailed explanation.
+    pub explanation: Option<String>,
+}
+
+/// Messages for one locale, keyed by rule id.
+#[derive(Debug, Clone, Default)]
+pub struct MessageCatalog {
+    locale: String,
+    entries: HashMap<String, CatalogEntry>,
+}
+
+impl MessageCatalog {
+    /// Parse catalog TOML and keep only the entries for `locale`.
+    ///
+    /// A catalog without a table for `locale` yields an empty catalog, so every
+    /// lookup falls back to English.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is malformed or an entry has unknown fields.
+    pub fn parse(content: &str, locale: &str) -> Result<Self, String> {
+        let mut locales: HashMap<String, HashMap<String, CatalogEntry>> =
+            toml::from_str(content).map_err(|e| format!("invalid message catalog: {e}"))?;
+        Ok(Self {
+            locale: locale.to_string(),
+            entries: locales.remove(locale).unwrap_or_default(),
+        })
+    }
+
+    /// Read and parse a catalog file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: &Path, locale: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read message catalog {}: {e}", path.display()))?;
+        Self::parse(&content, locale)
+    }
+
+    /// The locale this catalog was loaded for.
+    #[must_use]
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Number of rules translated for this locale.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no rules are translated for this locale.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Look up the translation for a rule id (`pack_id:pattern_name`).
+    #[must_use]
+    pub fn get(&self, rule_id: &str) -> Option<&CatalogEntry> {
+        self.entries.get(rule_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keeps_only_the_selected_locale() {
+        let catalog = MessageCatalog::parse(
+            r#"
+[ja."core.git:reset-hard"]
+reason = "作業ツリーの変更を破棄します"
+
+[de."core.git:reset-hard"]
+reason = "Verwirft Änderungen"
+"#,
+            "ja",
+        )
+        .expect("catalog parses");
+
+        assert_eq!(catalog.locale(), "ja");
+        assert_eq!(catalog.len(), 1);
+        let entry = catalog.get("core.git:reset-hard").expect("entry");
+        assert_eq!(
+            entry.reason.as_deref(),
+            Some("作業ツリーの変更を破棄します")
+        );
+        assert!(entry.explanation.is_none());
+        assert!(catalog.get("core.git:clean-force").is_none());
+    }
+
+    #[test]
+    fn parse_missing_locale_is_empty() {
+        let catalog = MessageCatalog::parse("[ja.\"core.git:reset-hard\"]\nreason = \"x\"\n", "fr")
+            .expect("catalog parses");
+        assert!(catalog.is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_unknown_fields() {
+        let err = MessageCatalog::parse("[ja.\"core.git:reset-hard\"]\nreasn = \"x\"\n", "ja")
+            .expect_err("typo is rejected");
+        assert!(err.contains("invalid message catalog"), "{err}");
+    }
+}
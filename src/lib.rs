@@ -1,5 +1,7 @@
-// Forbid unsafe code in production, but allow in tests for env var manipulation
-#![cfg_attr(not(test), forbid(unsafe_code))]
+// Forbid unsafe code in production, but allow in tests for env var manipulation.
+// The `ffi` feature relaxes this to `deny` so only the C ABI module can opt in.
+#![cfg_attr(not(any(test, feature = "ffi")), forbid(unsafe_code))]
+#![cfg_attr(all(not(test), feature = "ffi"), deny(unsafe_code))]
 //! Destructive Command Guard (dcg) library.
 //!
 //! This library provides the core functionality for blocking destructive commands
@@ -41,7 +43,7 @@
 //!
 //! ```ignore
 //! use destructive_command_guard::config::Config;
-//! use destructive_command_guard::evaluator::{evaluate_command, EvaluationDecision};
+//! use destructive_command_guard::evaluator::{evaluate_command, Decision};
 //!
 //! let config = Config::load();
 //! let compiled_overrides = config.overrides.compile();
@@ -70,11 +72,15 @@ pub mod context;
 pub mod error_codes;
 pub mod evaluator;
 pub mod exit_codes;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
 pub mod git;
 pub mod heredoc;
 pub mod highlight;
 pub mod history;
 pub mod hook;
+pub mod i18n;
 pub mod interactive;
 pub mod logging;
 pub mod mcp;
@@ -85,10 +91,13 @@ pub mod pending_exceptions;
 pub mod perf;
 pub mod sarif;
 pub mod scan;
+pub mod script_targets;
 pub mod simulate;
 pub mod stats;
 pub mod suggest;
 pub mod suggestions;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod trace;
 pub mod update;
 
@@ -99,13 +108,20 @@ pub use allowlist::{
 };
 pub use config::Config;
 pub use error_codes::{DcgError, ErrorCategory, ErrorCode, ErrorResponse};
+#[allow(deprecated)]
+pub use evaluator::EvaluationDecision;
 pub use evaluator::{
-    ConfidenceResult, DetailedEvaluationResult, EvaluationDecision, EvaluationResult,
+    ConfidenceResult, Decision, DetailedEvaluationResult, EvaluationResult,
     LegacyDestructivePattern, LegacySafePattern, MatchSource, MatchSpan, PatternMatch,
-    apply_confidence_scoring, evaluate_command, evaluate_command_with_deadline,
-    evaluate_command_with_pack_order, evaluate_command_with_pack_order_at_path,
-    evaluate_command_with_pack_order_deadline, evaluate_command_with_pack_order_deadline_at_path,
-    evaluate_detailed, evaluate_detailed_with_allowlists,
+    apply_cd_target_escalation, apply_confidence_scoring, apply_essential_package_escalation,
+    apply_force_push_target, apply_immutable_removal_escalation, apply_kubernetes_escalation,
+    apply_message_catalog, apply_on_error, apply_privileged_unknown, apply_protected_path_edits,
+    apply_pushed_history_rewrite, apply_script_targets, apply_warn_decision, check_strict_quoting,
+    evaluate_command, evaluate_command_with_deadline, evaluate_command_with_pack_order,
+    evaluate_command_with_pack_order_at_path, evaluate_command_with_pack_order_deadline,
+    evaluate_command_with_pack_order_deadline_at_path, evaluate_detailed,
+    evaluate_detailed_with_allowlists, evaluate_file_write, evaluation_timed_out,
+    normalize_for_shell, post_process,
 };
 pub use exit_codes::{
     EXIT_CONFIG_ERROR, EXIT_DENIED, EXIT_IO_ERROR, EXIT_PARSE_ERROR, EXIT_SUCCESS, EXIT_WARNING,
@@ -208,9 +224,9 @@ pub use confidence::{
 // Re-export history types for command tracking
 pub use history::{
     AgentStat, BackupResult, CURRENT_SCHEMA_VERSION, CheckResult, CommandEntry,
-    DEFAULT_DB_FILENAME, ENV_HISTORY_DB_PATH, ENV_HISTORY_DISABLED, HistoryDb, HistoryError,
-    HistoryStats, HistoryWriter, Outcome as HistoryOutcome, OutcomeStats, PatternStat,
-    PerformanceStats, ProjectStat, StatsTrends,
+    DEFAULT_DB_FILENAME, ENV_HISTORY_DB_PATH, ENV_HISTORY_DISABLED, ENV_MEASURE, HistoryDb,
+    HistoryError, HistoryStats, HistoryWriter, Outcome as HistoryOutcome, OutcomeStats,
+    PatternStat, PerformanceStats, ProjectStat, StatsTrends,
 };
 
 // Re-export interactive prompt types for human verification
@@ -10,7 +10,7 @@ use std::path::Path;
 use std::sync::Mutex;
 use std::time::SystemTime;
 
-use crate::evaluator::{EvaluationDecision, EvaluationResult};
+use crate::evaluator::{Decision, EvaluationResult};
 use crate::packs::DecisionMode;
 
 // ============================================================================
@@ -31,6 +31,14 @@ pub struct LoggingConfig {
     pub redaction: RedactionConfig,
     /// Events to log.
     pub events: LogEventFilter,
+    /// Send deny events to the local syslog (`/dev/log`).
+    pub syslog: bool,
+    /// Also send warn events to syslog.
+    pub syslog_warn: bool,
+    /// Syslog facility for dcg records.
+    pub syslog_facility: SyslogFacility,
+    /// Syslog severity for deny records (warn records use `notice`).
+    pub syslog_severity: SyslogSeverity,
 }
 
 impl Default for LoggingConfig {
@@ -41,6 +49,10 @@ impl Default for LoggingConfig {
             format: LogFormat::Text,
             redaction: RedactionConfig::default(),
             events: LogEventFilter::default(),
+            syslog: false,
+            syslog_warn: false,
+            syslog_facility: SyslogFacility::default(),
+            syslog_severity: SyslogSeverity::default(),
         }
     }
 }
@@ -83,6 +95,74 @@ pub enum RedactionMode {
     Full,
 }
 
+/// Syslog facility (RFC 5424 numbering).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogFacility {
+    #[default]
+    User,
+    Daemon,
+    Auth,
+    Authpriv,
+    Local0,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+impl SyslogFacility {
+    const fn code(self) -> u8 {
+        match self {
+            Self::User => 1,
+            Self::Daemon => 3,
+            Self::Auth => 4,
+            Self::Authpriv => 10,
+            Self::Local0 => 16,
+            Self::Local1 => 17,
+            Self::Local2 => 18,
+            Self::Local3 => 19,
+            Self::Local4 => 20,
+            Self::Local5 => 21,
+            Self::Local6 => 22,
+            Self::Local7 => 23,
+        }
+    }
+}
+
+/// Syslog severity (RFC 5424 numbering).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogSeverity {
+    Emerg,
+    Alert,
+    Crit,
+    Err,
+    #[default]
+    Warning,
+    Notice,
+    Info,
+    Debug,
+}
+
+impl SyslogSeverity {
+    const fn code(self) -> u8 {
+        match self {
+            Self::Emerg => 0,
+            Self::Alert => 1,
+            Self::Crit => 2,
+            Self::Err => 3,
+            Self::Warning => 4,
+            Self::Notice => 5,
+            Self::Info => 6,
+            Self::Debug => 7,
+        }
+    }
+}
+
 /// Filter for which events to log.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -150,19 +230,11 @@ impl LogEntry {
             );
 
         let decision_str = match result.decision {
-            EvaluationDecision::Allow => "allow",
-            EvaluationDecision::Deny => match mode {
-                DecisionMode::Deny => "deny",
-                DecisionMode::Warn => "warn",
-                DecisionMode::Log => "log",
-            },
+            Decision::Allow | Decision::Warn | Decision::Ask => result.decision.as_str(),
+            Decision::Deny => mode.label(),
         };
 
-        let mode_str = match mode {
-            DecisionMode::Deny => "deny",
-            DecisionMode::Warn => "warn",
-            DecisionMode::Log => "log",
-        };
+        let mode_str = mode.label();
 
         let (pack_id, pattern_name, rule_id, reason) =
             result
@@ -308,17 +380,174 @@ impl DecisionLogger {
     /// the pattern still matched, so users who enable deny logging should see it.
     const fn should_log(&self, result: &EvaluationResult, mode: DecisionMode) -> bool {
         match result.decision {
-            EvaluationDecision::Allow => self.config.events.allow,
-            EvaluationDecision::Deny => match mode {
+            Decision::Allow => self.config.events.allow,
+            Decision::Warn => self.config.events.warn,
+            Decision::Ask | Decision::Deny => match mode {
                 DecisionMode::Warn => self.config.events.warn,
                 // Log mode: pattern matched but we're just observing. Use deny filter
                 // since a destructive pattern did match, even if we're not blocking.
-                DecisionMode::Deny | DecisionMode::Log => self.config.events.deny,
+                DecisionMode::Deny | DecisionMode::Ask | DecisionMode::Log => {
+                    self.config.events.deny
+                }
             },
         }
     }
 }
 
+// ============================================================================
+// Syslog
+// ============================================================================
+
+/// Local syslog sockets, tried in order (Linux, macOS, BSD).
+#[cfg(unix)]
+const SYSLOG_SOCKETS: &[&str] = &["/dev/log", "/var/run/syslog", "/var/run/log"];
+
+/// Sends deny (and optionally warn) decisions to the local syslog daemon.
+///
+/// Records are RFC 3164 datagrams written to the local socket, so journald and
+/// rsyslog pick them up without extra configuration. Every failure is ignored:
+/// a missing or unreachable syslog never affects the hook decision. The socket
+/// is non-blocking, so a stalled daemon drops the record instead of delaying
+/// the hook.
+///
+/// Commands are always redacted with `[logging.redaction] mode`, even when
+/// file-log redaction is disabled, because syslog is typically world-readable
+/// and shipped off-host. Set `mode = "none"` to send commands verbatim.
+pub struct SyslogSink {
+    facility: SyslogFacility,
+    severity: SyslogSeverity,
+    include_warn: bool,
+    redaction: RedactionConfig,
+    #[cfg(unix)]
+    socket: Option<std::os::unix::net::UnixDatagram>,
+}
+
+impl SyslogSink {
+    /// Connect to the local syslog socket if `[logging] syslog` is enabled.
+    #[must_use]
+    pub fn new(config: &LoggingConfig) -> Option<Self> {
+        if !config.syslog {
+            return None;
+        }
+        #[cfg(unix)]
+        {
+            let socket = SYSLOG_SOCKETS
+                .iter()
+                .find_map(|path| connect_datagram(Path::new(path)));
+            Some(Self::with_socket(config, socket))
+        }
+        #[cfg(not(unix))]
+        {
+            Some(Self {
+                facility: config.syslog_facility,
+                severity: config.syslog_severity,
+                include_warn: config.syslog_warn,
+                redaction: syslog_redaction(config),
+            })
+        }
+    }
+
+    /// Create a sink that writes to the datagram socket at `path`.
+    #[cfg(unix)]
+    #[must_use]
+    pub fn with_socket_path(config: &LoggingConfig, path: &Path) -> Self {
+        Self::with_socket(config, connect_datagram(path))
+    }
+
+    #[cfg(unix)]
+    fn with_socket(
+        config: &LoggingConfig,
+        socket: Option<std::os::unix::net::UnixDatagram>,
+    ) -> Self {
+        Self {
+            facility: config.syslog_facility,
+            severity: config.syslog_severity,
+            include_warn: config.syslog_warn,
+            redaction: syslog_redaction(config),
+            socket,
+        }
+    }
+
+    /// Record a decision. Allow and log-only decisions are never sent.
+    ///
+    /// `command` is what the agent submitted; `evaluated` is the form dcg
+    /// matched against and is added as `evaluated=` when it differs.
+    pub fn record(
+        &self,
+        mode: DecisionMode,
+        rule_id: Option<&str>,
+        command: &str,
+        evaluated: &str,
+        cwd: &str,
+    ) {
+        let severity = match mode {
+            DecisionMode::Deny | DecisionMode::Ask => self.severity,
+            DecisionMode::Warn if self.include_warn => SyslogSeverity::Notice,
+            DecisionMode::Warn | DecisionMode::Log => return,
+        };
+        let message = self.format_record(severity, mode, rule_id, command, evaluated, cwd);
+        #[cfg(unix)]
+        if let Some(socket) = &self.socket {
+            // A full receive queue returns WouldBlock; the record is dropped.
+            let _ = socket.send(message.as_bytes());
+        }
+        #[cfg(not(unix))]
+        let _ = message;
+    }
+
+    /// Format a record as `<PRI>dcg[PID]: key=value ...`.
+    fn format_record(
+        &self,
+        severity: SyslogSeverity,
+        mode: DecisionMode,
+        rule_id: Option<&str>,
+        command: &str,
+        evaluated: &str,
+        cwd: &str,
+    ) -> String {
+        let priority = u16::from(self.facility.code()) * 8 + u16::from(severity.code());
+        let decision = mode.label();
+        let mut record = format!(
+            "<{priority}>dcg[{}]: decision={decision} rule_id={} cwd={} command={}",
+            std::process::id(),
+            rule_id.unwrap_or("-"),
+            syslog_quote(cwd),
+            syslog_quote(&redact_command(command, &self.redaction)),
+        );
+        if evaluated != command {
+            record.push_str(" evaluated=");
+            record.push_str(&syslog_quote(&redact_command(evaluated, &self.redaction)));
+        }
+        record
+    }
+}
+
+/// Syslog redaction: the configured mode, applied regardless of `enabled`.
+fn syslog_redaction(config: &LoggingConfig) -> RedactionConfig {
+    RedactionConfig {
+        enabled: true,
+        ..config.redaction.clone()
+    }
+}
+
+#[cfg(unix)]
+fn connect_datagram(path: &Path) -> Option<std::os::unix::net::UnixDatagram> {
+    let socket = std::os::unix::net::UnixDatagram::unbound().ok()?;
+    socket.connect(path).ok()?;
+    socket.set_nonblocking(true).ok()?;
+    Some(socket)
+}
+
+/// Quote a value for a key=value syslog record.
+fn syslog_quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    format!("\"{escaped}\"")
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
@@ -346,10 +575,61 @@ pub(crate) fn redact_command(command: &str, config: &RedactionConfig) -> String
     match config.mode {
         RedactionMode::None => command.to_string(),
         RedactionMode::Full => "[REDACTED]".to_string(),
-        RedactionMode::Arguments => redact_arguments(command, config.max_argument_len),
+        RedactionMode::Arguments => {
+            redact_arguments(&mask_credentials(command), config.max_argument_len)
+        }
     }
 }
 
+/// Keys whose `KEY=VALUE` values are masked in arguments mode.
+const CREDENTIAL_KEYS: &[&str] = &["token", "secret", "password", "passwd", "api_key", "apikey"];
+
+/// Mask credential values that are too short to be caught by argument truncation:
+/// the word after `Bearer`/`Basic`, and values of `KEY=VALUE` pairs with sensitive keys.
+fn mask_credentials(command: &str) -> String {
+    let is_quote = |c: char| c == '"' || c == '\'';
+    let mut result = String::with_capacity(command.len());
+    let mut mask_next = false;
+
+    for piece in command.split_inclusive(char::is_whitespace) {
+        let word = piece.trim_end_matches(char::is_whitespace);
+        let separator = &piece[word.len()..];
+        let bare = word.trim_matches(|c| is_quote(c) || c == ':');
+
+        if bare.is_empty() {
+            result.push_str(piece);
+            continue;
+        }
+
+        if mask_next {
+            let start = word.find(bare).unwrap_or(0);
+            result.push_str(&word[..start]);
+            result.push_str("[REDACTED]");
+            result.push_str(&word[start + bare.len()..]);
+            result.push_str(separator);
+            mask_next = false;
+            continue;
+        }
+        mask_next = bare.eq_ignore_ascii_case("bearer") || bare.eq_ignore_ascii_case("basic");
+
+        if let Some(eq) = word.find('=') {
+            let key = word[..eq]
+                .trim_start_matches(|c| is_quote(c) || c == '-')
+                .to_ascii_lowercase();
+            let value = word[eq + 1..].trim_end_matches(is_quote);
+            if !value.is_empty() && CREDENTIAL_KEYS.iter().any(|k| key.ends_with(k)) {
+                result.push_str(&word[..=eq]);
+                result.push_str("[REDACTED]");
+                result.push_str(&word[eq + 1 + value.len()..]);
+                result.push_str(separator);
+                continue;
+            }
+        }
+        result.push_str(piece);
+    }
+    result
+}
+
 fn redact_arguments(command: &str, max_len: usize) -> String {
     let mut result = String::with_capacity(command.len());
     let mut in_quote = false;
@@ -1008,6 +1288,144 @@ mod tests {
         assert_eq!(config.format, LogFormat::Text);
     }
 
+    #[test]
+    fn syslog_sink_disabled_by_default() {
+        assert!(SyslogSink::new(&LoggingConfig::default()).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn syslog_deny_record_includes_rule_id() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("log.sock");
+        let listener = std::os::unix::net::UnixDatagram::bind(&path).expect("bind mock syslog");
+        listener.set_nonblocking(true).expect("nonblocking");
+
+        let config = LoggingConfig {
+            syslog: true,
+            syslog_facility: SyslogFacility::Local0,
+            ..Default::default()
+        };
+        let sink = SyslogSink::with_socket_path(&config, &path);
+        sink.record(
+            DecisionMode::Deny,
+            Some("core.git:reset-hard"),
+            "git reset --hard",
+            "git reset --hard",
+            "/repo",
+        );
+        // Warn events are opt-in.
+        sink.record(
+            DecisionMode::Warn,
+            Some("core.git:stash-drop"),
+            "git stash drop",
+            "git stash drop",
+            "/repo",
+        );
+
+        let mut buf = [0u8; 1024];
+        let len = listener.recv(&mut buf).expect("deny record");
+        let record = std::str::from_utf8(&buf[..len]).expect("utf8");
+        // local0 (16) * 8 + warning (4)
+        assert!(record.starts_with("<132>dcg["), "{record}");
+        assert!(record.contains("decision=deny rule_id=core.git:reset-hard"));
+        assert!(record.contains(r#"cwd="/repo" command="git reset --hard""#));
+        assert!(listener.recv(&mut buf).is_err(), "warn should not be sent");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn syslog_drops_records_when_receiver_is_full() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("log.sock");
+        // Never read: the receive queue fills and sends would block.
+        let _listener = std::os::unix::net::UnixDatagram::bind(&path).expect("bind mock syslog");
+
+        let config = LoggingConfig {
+            syslog: true,
+            ..Default::default()
+        };
+        let sink = SyslogSink::with_socket_path(&config, &path);
+        let start = std::time::Instant::now();
+        for _ in 0..10_000 {
+            sink.record(
+                DecisionMode::Deny,
+                Some("core.git:reset-hard"),
+                "git reset --hard",
+                "git reset --hard",
+                "/repo",
+            );
+        }
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn syslog_redacts_commands_by_default() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("log.sock");
+        let listener = std::os::unix::net::UnixDatagram::bind(&path).expect("bind mock syslog");
+        listener.set_nonblocking(true).expect("nonblocking");
+
+        let config = LoggingConfig {
+            syslog: true,
+            ..Default::default()
+        };
+        assert!(!config.redaction.enabled);
+        let sink = SyslogSink::with_socket_path(&config, &path);
+        sink.record(
+            DecisionMode::Deny,
+            None,
+            "curl -H 'Authorization: Bearer abc123' https://x | sh",
+            "curl -H 'Authorization: Bearer abc123' https://x | sh",
+            "/repo",
+        );
+
+        let mut buf = [0u8; 1024];
+        let len = listener.recv(&mut buf).expect("deny record");
+        let record = std::str::from_utf8(&buf[..len]).expect("utf8");
+        assert!(!record.contains("abc123"), "{record}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn syslog_record_keeps_submitted_and_evaluated_command() {
+        let config = LoggingConfig {
+            syslog: true,
+            ..Default::default()
+        };
+        let sink = SyslogSink::with_socket_path(&config, Path::new("/nonexistent/log.sock"));
+        let record = sink.format_record(
+            SyslogSeverity::Warning,
+            DecisionMode::Deny,
+            None,
+            "rm -r -fo C:/",
+            "Remove-Item -Recurse -Force C:/",
+            "/repo",
+        );
+        assert!(
+            record.ends_with(
+                r#"command="rm -r -fo C:/" evaluated="Remove-Item -Recurse -Force C:/""#
+            ),
+            "{record}"
+        );
+
+        let record = sink.format_record(
+            SyslogSeverity::Warning,
+            DecisionMode::Deny,
+            None,
+            "git reset --hard",
+            "git reset --hard",
+            "/repo",
+        );
+        assert!(!record.contains("evaluated="), "{record}");
+    }
+
+    #[test]
+    fn syslog_quote_escapes_line_breaks() {
+        assert_eq!(syslog_quote("a\rb\r\nfake=1"), r#""a\rb\r\nfake=1""#);
+    }
+
     #[test]
     fn redact_full_mode() {
         let config = RedactionConfig {
@@ -1067,6 +1485,27 @@ mod tests {
         assert_eq!(result, r#"echo "this is a ...""#);
     }
 
+    #[test]
+    fn redact_arguments_masks_credentials() {
+        let config = RedactionConfig {
+            enabled: true,
+            mode: RedactionMode::Arguments,
+            max_argument_len: 50,
+        };
+        let result = redact_command(
+            r#"curl -H "Authorization: Bearer abc123" --data password=hunter2 https://x"#,
+            &config,
+        );
+        assert_eq!(
+            result,
+            r#"curl -H "Authorization: Bearer [REDACTED]" --data password=[REDACTED] https://x"#
+        );
+        assert_eq!(
+            redact_command("git reset --hard HEAD", &config),
+            "git reset --hard HEAD"
+        );
+    }
+
     #[test]
     fn redact_arguments_preserves_short_strings() {
         let config = RedactionConfig {
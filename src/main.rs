@@ -6,6 +6,7 @@
 //!
 //! Exit behavior:
 //!   - Exit 0 with JSON {"hookSpecificOutput": {"permissionDecision": "deny", ...}} = block
+//!   - Exit 0 with JSON {"hookSpecificOutput": {"permissionDecision": "ask", ...}} = confirm
 //!   - Exit 0 with no output = allow
 //!
 //! # Performance
@@ -21,24 +22,28 @@ use clap::Parser;
 use colored::Colorize;
 use destructive_command_guard::cli::{self, Cli};
 // Exit codes are used by cli.rs for robot mode; main.rs uses them for hook mode errors
-use destructive_command_guard::config::Config;
+use destructive_command_guard::config::{Config, OnErrorMode};
 use destructive_command_guard::evaluator::{
-    EvaluationDecision, MatchSource, evaluate_command_with_pack_order_deadline_at_path,
+    Decision, EvaluationResult, MatchSource, evaluate_command_with_pack_order_deadline_at_path,
 };
 #[allow(unused_imports)]
 use destructive_command_guard::exit_codes::{EXIT_DENIED, EXIT_PARSE_ERROR, EXIT_SUCCESS};
 use destructive_command_guard::history::{
-    CommandEntry, ENV_HISTORY_DB_PATH, HistoryWriter, Outcome as HistoryOutcome,
+    CommandEntry, ENV_HISTORY_DB_PATH, HistoryWriter, Outcome as HistoryOutcome, eval_duration_us,
+    measure_enabled,
 };
 use destructive_command_guard::hook;
 use destructive_command_guard::load_default_allowlists;
+use destructive_command_guard::logging::SyslogSink;
 use destructive_command_guard::normalize::normalize_command;
 use destructive_command_guard::packs::load_external_packs;
 #[cfg(test)]
 use destructive_command_guard::packs::pack_aware_quick_reject;
 use destructive_command_guard::packs::{DecisionMode, REGISTRY};
-use destructive_command_guard::pending_exceptions::{PendingExceptionStore, log_maintenance};
-use destructive_command_guard::perf::{Deadline, HOOK_EVALUATION_BUDGET};
+use destructive_command_guard::pending_exceptions::{
+    PendingExceptionStore, confirmation_secret, confirmation_token, log_maintenance,
+};
+use destructive_command_guard::perf::{Deadline, EvaluationWatchdog, HOOK_EVALUATION_BUDGET};
 use destructive_command_guard::sanitize_for_pattern_matching;
 // Import HookInput for parsing stdin JSON in hook mode
 #[cfg(test)]
@@ -82,8 +87,12 @@ fn history_db_path(config: &destructive_command_guard::config::HistoryConfig) ->
     config.expanded_database_path()
 }
 
+/// `command` is what the agent submitted; `evaluated` is the form dcg matched
+/// against, recorded separately when the shell normalizer rewrote it.
+#[allow(clippy::too_many_arguments)]
 fn build_history_entry(
     command: &str,
+    evaluated: &str,
     working_dir: &str,
     outcome: HistoryOutcome,
     eval_duration: Duration,
@@ -91,12 +100,13 @@ fn build_history_entry(
     pattern_name: Option<&str>,
     allowlist_layer: Option<&str>,
 ) -> CommandEntry {
-    let eval_duration_us = u64::try_from(eval_duration.as_micros()).unwrap_or(u64::MAX);
+    let eval_duration_us = eval_duration_us(eval_duration, measure_enabled());
 
     CommandEntry {
         agent_type: HISTORY_AGENT_TYPE.to_string(),
         working_dir: working_dir.to_string(),
         command: command.to_string(),
+        normalized_command: (evaluated != command).then(|| evaluated.to_string()),
         outcome,
         pack_id: pack_id.map(str::to_string),
         pattern_name: pattern_name.map(str::to_string),
@@ -237,6 +247,12 @@ fn main() {
         colored::control::set_override(false);
     }
 
+    let input_source = match (cli.input_fd, cli.input_path.clone()) {
+        (Some(fd), _) => hook::HookInputSource::Fd(fd),
+        (None, Some(path)) => hook::HookInputSource::Path(path),
+        (None, None) => hook::HookInputSource::Stdin,
+    };
+
     // If there's a subcommand, handle it and exit.
     if cli.command.is_some() {
         if let Err(e) = cli::run_command(cli) {
@@ -269,10 +285,21 @@ fn main() {
     let mut enabled_packs: HashSet<String> = config.enabled_pack_ids();
     let mut enabled_keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
 
-    // Load external packs from custom_paths (glob + tilde expansion).
-    // External packs are loaded once and cached for the process lifetime.
-    let external_paths = config.packs.expand_custom_paths();
-    let external_store = load_external_packs(&external_paths);
+    // Load external packs from custom_paths (glob + tilde expansion) and the
+    // cached remote bundle. Loaded once and cached for the process lifetime.
+    // A stale remote bundle is refreshed by a detached `dcg pack refresh`
+    // so the hook never waits on the network.
+    let external_store = load_external_packs(
+        &config.packs.cached_pack_sources(),
+        &config.packs.enable_patterns,
+    );
+    if config
+        .packs
+        .remote_source()
+        .is_some_and(|remote| remote.needs_refresh())
+    {
+        spawn_remote_pack_refresh();
+    }
 
     // Log warnings from external pack loading (fail-open: don't block on warnings).
     if config.general.verbose {
@@ -310,11 +337,11 @@ fn main() {
 
     // Read and parse input
     let max_input_bytes = config.general.max_hook_input_bytes();
-    let hook_input = match hook::read_hook_input(max_input_bytes) {
+    let hook_input = match hook::read_hook_input_from(&input_source, max_input_bytes) {
         Ok(input) => input,
         Err(hook::HookReadError::InputTooLarge(len)) => {
             eprintln!(
-                "[dcg] Warning: stdin input ({len} bytes) exceeds limit ({max_input_bytes} bytes); allowing command (fail-open)"
+                "[dcg] Warning: hook input ({len} bytes) exceeds limit ({max_input_bytes} bytes); allowing command (fail-open)"
             );
             return;
         }
@@ -329,13 +356,38 @@ fn main() {
             .map_or(HOOK_EVALUATION_BUDGET, Duration::from_millis),
     );
 
-    let Some((command, hook_protocol)) = hook::extract_command_with_protocol(&hook_input) else {
+    // Write/Edit tool calls carry a target file instead of a shell command; they
+    // flow through the same decision path under a `<Tool> <path>` display string.
+    let file_write = hook::extract_file_write(&hook_input);
+    let Some((command, hook_protocol)) =
+        hook::extract_command_with_protocol(&hook_input).or_else(|| {
+            file_write
+                .as_ref()
+                .map(|write| (write.display_command(), hook::detect_protocol(&hook_input)))
+        })
+    else {
         return;
     };
 
-    // Check command size limit (fail-open: allow and warn)
+    // In PowerShell mode, evaluate (and report) the canonical cmdlet spelling.
+    // History and logs keep the submitted form alongside it.
+    let submitted = command.clone();
+    let canonical = match file_write {
+        Some(_) => None,
+        None => {
+            match destructive_command_guard::normalize_for_shell(&command, &config.evaluation) {
+                std::borrow::Cow::Owned(canonical) => Some(canonical),
+                std::borrow::Cow::Borrowed(_) => None,
+            }
+        }
+    };
+    let command = canonical.unwrap_or(command);
+
+    // Check command size limit (fail-open: allow and warn, unless on_error = "closed")
     let max_command_bytes = config.general.max_command_bytes();
-    if command.len() > max_command_bytes {
+    let fail_closed = config.evaluation.on_error == OnErrorMode::Closed;
+    let oversized = command.len() > max_command_bytes;
+    if oversized && !fail_closed {
         eprintln!(
             "[dcg] Warning: command ({} bytes) exceeds limit ({} bytes); allowing command (fail-open)",
             command.len(),
@@ -344,13 +396,20 @@ fn main() {
         return;
     }
 
-    let cwd_path = std::env::current_dir().ok();
+    let cwd_path = hook_input
+        .cwd
+        .as_deref()
+        .filter(|cwd| !cwd.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok());
     let working_dir = cwd_path.as_ref().map_or_else(
         || "<unknown>".to_string(),
         |path| path.to_string_lossy().to_string(),
     );
 
-    let history_writer = if config.history.enabled {
+    // DCG_MEASURE only fills in latency on entries that are already logged;
+    // `[history] enabled = false` keeps the database untouched.
+    let history_writer = if !cli.no_history && config.history.enabled {
         Some(HistoryWriter::new(
             history_db_path(&config.history),
             &config.history,
@@ -365,7 +424,7 @@ fn main() {
         }
     }
 
-    if deadline.is_exceeded() {
+    if deadline.is_exceeded() && !fail_closed {
         if let Some(log_file) = config.general.log_file.as_deref() {
             let _ = hook::log_budget_skip(
                 log_file,
@@ -378,20 +437,114 @@ fn main() {
         return;
     }
 
+    // Localized messages are best-effort: a broken catalog falls back to English.
+    let catalog = config.i18n.load_catalog().unwrap_or_else(|e| {
+        if config.general.verbose {
+            eprintln!("[dcg] Warning: {e}");
+        }
+        None
+    });
+
+    // Hard limit for the whole evaluation: if it overruns, the watchdog answers
+    // per on_error ("evaluation timed out") and exits so the agent never hangs.
+    let total_timeout = cli
+        .timeout
+        .map_or_else(|| config.evaluation.total_timeout(), Duration::from_millis);
+    let watchdog = {
+        let command = command.clone();
+        let evaluation = config.evaluation.clone();
+        let log_file = config.general.log_file.clone();
+        EvaluationWatchdog::start(total_timeout, move || {
+            let fallback = destructive_command_guard::evaluation_timed_out(&evaluation);
+            if let Some(info) = fallback.pattern_info.as_ref() {
+                hook::output_denial_for_protocol(
+                    hook_protocol,
+                    &command,
+                    &info.reason,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    &[],
+                );
+            }
+            if let Some(log_file) = log_file.as_deref() {
+                let _ = hook::log_budget_skip(
+                    log_file,
+                    &command,
+                    "total_timeout",
+                    total_timeout,
+                    total_timeout,
+                );
+            }
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            std::process::exit(0);
+        })
+    };
+
     // Use the shared evaluator for hook mode parity with `dcg test`.
     let eval_start = Instant::now();
-    let result = evaluate_command_with_pack_order_deadline_at_path(
-        &command,
-        &enabled_keywords,
-        &ordered_packs,
-        keyword_index.as_ref(),
-        &compiled_overrides,
-        &allowlists,
-        &heredoc_settings,
-        None, // allow_once_audit
-        None, // project_path
-        Some(&deadline),
-    );
+    let result = if let Some(write) = file_write.as_ref() {
+        Some(destructive_command_guard::evaluate_file_write(
+            &write.tool,
+            &write.file_path,
+            cwd_path.as_deref(),
+            &config.filesystem,
+            &allowlists,
+        ))
+    } else if oversized {
+        Some(EvaluationResult::denied_on_error(
+            "command exceeds max_command_bytes",
+        ))
+    } else {
+        destructive_command_guard::check_strict_quoting(&command, &config.evaluation)
+    };
+    let result = result.unwrap_or_else(|| {
+        let result = evaluate_command_with_pack_order_deadline_at_path(
+            &command,
+            &enabled_keywords,
+            &ordered_packs,
+            keyword_index.as_ref(),
+            &compiled_overrides,
+            &allowlists,
+            &heredoc_settings,
+            None, // allow_once_audit
+            None, // project_path
+            Some(&deadline),
+        );
+        destructive_command_guard::post_process(
+            &command,
+            result,
+            &config,
+            &allowlists,
+            catalog.as_deref(),
+            cwd_path.as_deref(),
+            |target| {
+                evaluate_command_with_pack_order_deadline_at_path(
+                    target,
+                    &enabled_keywords,
+                    &ordered_packs,
+                    keyword_index.as_ref(),
+                    &compiled_overrides,
+                    &allowlists,
+                    &heredoc_settings,
+                    None, // allow_once_audit
+                    None, // project_path
+                    Some(&deadline),
+                )
+            },
+        )
+    });
+
+    if !watchdog.finish() {
+        // The watchdog already answered and is exiting the process.
+        loop {
+            std::thread::park();
+        }
+    }
 
     // NOTE: External packs from custom_paths are now checked in evaluate_command()
     // alongside built-in packs, so no separate fallback check is needed here.
@@ -401,6 +554,7 @@ fn main() {
     if result.skipped_due_to_budget {
         if let Some(writer) = history_writer.as_ref() {
             let entry = build_history_entry(
+                &submitted,
                 &command,
                 &working_dir,
                 HistoryOutcome::Allow,
@@ -423,7 +577,8 @@ fn main() {
         return;
     }
 
-    if result.decision != EvaluationDecision::Deny {
+    // Warn matches go on to policy resolution below, which may still block them.
+    if result.decision == Decision::Allow {
         if let Some(writer) = history_writer.as_ref() {
             let mut pack_id = None;
             let mut pattern_name = None;
@@ -436,6 +591,7 @@ fn main() {
             }
 
             let entry = build_history_entry(
+                &submitted,
                 &command,
                 &working_dir,
                 HistoryOutcome::Allow,
@@ -453,6 +609,7 @@ fn main() {
         // Fail open: structurally unexpected, but hook safety wins.
         if let Some(writer) = history_writer.as_ref() {
             let entry = build_history_entry(
+                &submitted,
                 &command,
                 &working_dir,
                 HistoryOutcome::Allow,
@@ -466,20 +623,41 @@ fn main() {
         return;
     };
 
+    if result.decision == Decision::Ask {
+        // History has no "ask" outcome; the command is held, so record a deny.
+        if let Some(writer) = history_writer.as_ref() {
+            let entry = build_history_entry(
+                &submitted,
+                &command,
+                &working_dir,
+                HistoryOutcome::Deny,
+                eval_duration,
+                None,
+                None,
+                None,
+            );
+            writer.log(entry);
+        }
+        hook::output_ask_for_protocol(hook_protocol, &command, &info.reason);
+        return;
+    }
+
     let pack = info.pack_id.as_deref();
+    // post_process already resolved the policy and `[safe_dirs]` mode.
     let mut mode = match info.source {
-        MatchSource::Pack | MatchSource::HeredocAst => {
+        MatchSource::Pack | MatchSource::HeredocAst => result.effective_mode.unwrap_or_else(|| {
             config
                 .policy()
                 .resolve_mode(pack, info.pattern_name.as_deref(), info.severity)
-        }
+        }),
         // Never downgrade explicit blocks.
         MatchSource::ConfigOverride | MatchSource::LegacyPattern => DecisionMode::Deny,
     };
 
     // Apply confidence scoring (if enabled) to potentially downgrade Deny to Warn.
-    // Only applies to pack/heredoc matches, not config overrides.
-    if matches!(info.source, MatchSource::Pack | MatchSource::HeredocAst) {
+    // Only applies to pack/heredoc matches against shell commands, not config
+    // overrides or file-tool targets.
+    if file_write.is_none() && matches!(info.source, MatchSource::Pack | MatchSource::HeredocAst) {
         let sanitized = sanitize_for_pattern_matching(&command);
         let normalized_command = normalize_command(&command);
         let normalized_sanitized = normalize_command(sanitized.as_ref());
@@ -506,14 +684,17 @@ fn main() {
 
     let pattern = info.pattern_name.as_deref();
     let explanation = info.explanation.as_deref();
+    let rule_id = pack.zip(pattern).map(|(p, n)| format!("{p}:{n}"));
 
     if let Some(writer) = history_writer.as_ref() {
         let outcome = match mode {
-            DecisionMode::Deny => HistoryOutcome::Deny,
+            // History has no "ask" outcome; the command is held, so record a deny.
+            DecisionMode::Deny | DecisionMode::Ask => HistoryOutcome::Deny,
             DecisionMode::Warn => HistoryOutcome::Warn,
             DecisionMode::Log => HistoryOutcome::Allow,
         };
         let entry = build_history_entry(
+            &submitted,
             &command,
             &working_dir,
             outcome,
@@ -546,9 +727,26 @@ fn main() {
                 Some(format!("{:?}", info.source)),
                 None,
             ) {
-                allow_once_info = Some(hook::AllowOnceInfo {
+                // With require_hmac, a code without its token is useless, so a
+                // missing secret means no allow-once offer at all (fail closed).
+                let token = if config.exceptions.require_hmac {
+                    match confirmation_secret(store.path()) {
+                        Ok(secret) => Some(Some(confirmation_token(&record.full_hash, &secret))),
+                        Err(e) => {
+                            eprintln!(
+                                "[dcg] Warning: cannot load allow-once confirmation secret ({e}); \
+                                 no allow-once code offered"
+                            );
+                            None
+                        }
+                    }
+                } else {
+                    Some(None)
+                };
+                allow_once_info = token.map(|token| hook::AllowOnceInfo {
                     code: record.short_code,
                     full_hash: record.full_hash,
+                    token,
                 });
                 if let Some(log_file) = config.general.log_file.as_deref() {
                     let _ = log_maintenance(log_file, maintenance, "record_block");
@@ -571,19 +769,57 @@ fn main() {
 
             // Log if configured
             if let Some(log_file) = &config.general.log_file {
-                let _ = hook::log_blocked_command(log_file, &command, &info.reason, pack);
+                let _ = hook::log_blocked_command(
+                    log_file,
+                    &submitted,
+                    &command,
+                    &info.reason,
+                    pack,
+                    &config.logging.redaction,
+                );
             }
         }
+        DecisionMode::Ask => {
+            hook::output_ask_for_protocol(hook_protocol, &command, &info.reason);
+        }
         DecisionMode::Warn => {
             hook::output_warning(&command, &info.reason, pack, pattern, explanation);
         }
         DecisionMode::Log => {
             // Silent allow; optionally log to file for history.
             if let Some(log_file) = &config.general.log_file {
-                let _ = hook::log_blocked_command(log_file, &command, &info.reason, pack);
+                let _ = hook::log_blocked_command(
+                    log_file,
+                    &submitted,
+                    &command,
+                    &info.reason,
+                    pack,
+                    &config.logging.redaction,
+                );
             }
         }
     }
+
+    // One sink per decision; it drops modes that are not configured for syslog.
+    if let Some(syslog) = SyslogSink::new(&config.logging) {
+        syslog.record(mode, rule_id.as_deref(), &submitted, &command, &working_dir);
+    }
+}
+
+/// Start `dcg pack refresh` in the background without waiting for it.
+///
+/// Best effort: if the child cannot be spawned, the stale cache keeps being
+/// used and the next invocation tries again.
+fn spawn_remote_pack_refresh() {
+    let Ok(exe) = std::env::current_exe() else {
+        return;
+    };
+    let _ = std::process::Command::new(exe)
+        .args(["pack", "refresh"])
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
 }
 
 /// Print help information.
@@ -645,6 +881,14 @@ fn print_help() {
         "    {}        Print this help message",
         "--help, -h".green()
     );
+    eprintln!(
+        "    {}  Read hook JSON from file descriptor N",
+        "--input-fd <N>".green()
+    );
+    eprintln!(
+        "    {} Read hook JSON from a file or named pipe",
+        "--input-path <P>".green()
+    );
     eprintln!();
 
     // Commands section
@@ -4,7 +4,7 @@
 //! checks without shell-hook overhead.
 
 use crate::config::Config;
-use crate::evaluator::{EvaluationDecision, evaluate_command};
+use crate::evaluator::{EvaluationResult, evaluate_command};
 use crate::packs::REGISTRY;
 use crate::scan::{
     ScanEvalContext, ScanFailOn, ScanFormat, ScanOptions, ScanRedactMode, scan_paths,
@@ -39,8 +39,9 @@ struct AllowlistInfo {
     reason: String,
 }
 
+/// Decision JSON shared by the MCP `check_command` tool and the C ABI.
 #[derive(Serialize)]
-struct CheckCommandResponse {
+pub(crate) struct CheckCommandResponse {
     allowed: bool,
     decision: String,
     mode: Option<String>,
@@ -65,6 +66,60 @@ struct ExplainPatternResponse {
     explanation: String,
 }
 
+impl CheckCommandResponse {
+    /// Build the response for an evaluation result.
+    pub(crate) fn from_result(result: &EvaluationResult) -> Self {
+        let mode = result.effective_mode.map(|m| m.label().to_string());
+        let allowed = result
+            .effective_mode
+            .map_or_else(|| result.is_allowed(), |m| !m.blocks());
+
+        let mut response = Self {
+            allowed,
+            decision: result.decision.as_str().to_string(),
+            mode,
+            skipped_due_to_budget: result.skipped_due_to_budget,
+            reason: None,
+            rule_id: None,
+            pack_id: None,
+            pattern_name: None,
+            severity: None,
+            explanation: None,
+            matched_text_preview: None,
+            allowlist: None,
+        };
+
+        if let Some(override_) = result.allowlist_override.as_ref() {
+            response.allowlist = Some(AllowlistInfo {
+                layer: override_.layer.label().to_string(),
+                reason: override_.reason.clone(),
+            });
+        }
+
+        let match_info = result
+            .pattern_info
+            .as_ref()
+            .or_else(|| result.allowlist_override.as_ref().map(|o| &o.matched));
+
+        if let Some(info) = match_info {
+            response.reason = Some(info.reason.clone());
+            response.rule_id = DcgMcpServer::rule_id_from_match(
+                info.pack_id.as_deref(),
+                info.pattern_name.as_deref(),
+            );
+            response.pack_id.clone_from(&info.pack_id);
+            response.pattern_name.clone_from(&info.pattern_name);
+            response.severity = info.severity.map(|s| s.label().to_string());
+            response.explanation.clone_from(&info.explanation);
+            response
+                .matched_text_preview
+                .clone_from(&info.matched_text_preview);
+        }
+
+        response
+    }
+}
+
 impl DcgMcpServer {
     #[must_use]
     pub fn new() -> Self {
@@ -191,56 +246,7 @@ impl DcgMcpServer {
             &self.scan_ctx.compiled_overrides,
             &self.scan_ctx.allowlists,
         );
-
-        let mode = result.effective_mode.map(|m| m.label().to_string());
-        let allowed = result
-            .effective_mode
-            .map_or(result.decision != EvaluationDecision::Deny, |m| !m.blocks());
-
-        let mut response = CheckCommandResponse {
-            allowed,
-            decision: match result.decision {
-                EvaluationDecision::Allow => "allow".to_string(),
-                EvaluationDecision::Deny => "deny".to_string(),
-            },
-            mode,
-            skipped_due_to_budget: result.skipped_due_to_budget,
-            reason: None,
-            rule_id: None,
-            pack_id: None,
-            pattern_name: None,
-            severity: None,
-            explanation: None,
-            matched_text_preview: None,
-            allowlist: None,
-        };
-
-        if let Some(override_) = result.allowlist_override.as_ref() {
-            response.allowlist = Some(AllowlistInfo {
-                layer: override_.layer.label().to_string(),
-                reason: override_.reason.clone(),
-            });
-        }
-
-        let match_info = result
-            .pattern_info
-            .as_ref()
-            .or_else(|| result.allowlist_override.as_ref().map(|o| &o.matched));
-
-        if let Some(info) = match_info {
-            response.reason = Some(info.reason.clone());
-            response.rule_id =
-                Self::rule_id_from_match(info.pack_id.as_deref(), info.pattern_name.as_deref());
-            response.pack_id.clone_from(&info.pack_id);
-            response.pattern_name.clone_from(&info.pattern_name);
-            response.severity = info.severity.map(|s| s.label().to_string());
-            response.explanation.clone_from(&info.explanation);
-            response
-                .matched_text_preview
-                .clone_from(&info.matched_text_preview);
-        }
-
-        response
+        CheckCommandResponse::from_result(&result)
     }
 
     fn explain_pattern(rule_id: &str) -> Result<ExplainPatternResponse, CallToolError> {
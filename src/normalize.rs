@@ -753,6 +753,12 @@ pub fn consume_word_token(bytes: &[u8], mut i: usize, len: usize) -> usize {
             break;
         }
 
+        // `>|` (noclobber override) is a redirection, not a pipe.
+        if b == b'|' && i > 0 && bytes[i - 1] == b'>' {
+            i += 1;
+            continue;
+        }
+
         if matches!(b, b'|' | b';' | b'&' | b'(' | b')') {
             break;
         }
@@ -1516,9 +1522,18 @@ fn apply_path_normalizers(base: &str) -> Option<String> {
 
 /// Normalize a command by stripping absolute paths from common binaries.
 ///
-/// Returns the original command unchanged if normalization fails (fail-open).
+/// Brace groups are expanded first (see [`expand_braces`]) so each generated
+/// word is matched on its own. Returns the original command unchanged if
+/// normalization fails (fail-open).
 #[inline]
 pub fn normalize_command(cmd: &str) -> Cow<'_, str> {
+    match expand_braces(cmd) {
+        Cow::Borrowed(_) => normalize_unexpanded(cmd),
+        Cow::Owned(expanded) => Cow::Owned(normalize_unexpanded(&expanded).into_owned()),
+    }
+}
+
+fn normalize_unexpanded(cmd: &str) -> Cow<'_, str> {
     // 1. Strip wrappers (sudo, env, etc.)
     let stripped = crate::normalize::strip_wrapper_prefixes(cmd);
 
@@ -1551,6 +1566,130 @@ pub fn normalize_command(cmd: &str) -> Cow<'_, str> {
     }
 }
 
+/// Check whether a command ends inside an unterminated quote.
+///
+/// Single quotes, double quotes (with backslash escapes), and ANSI-C `$'...'`
+/// strings may legitimately span lines. Heredoc bodies and `#` comments are
+/// skipped, so prose like `cat <<EOF` / `it's fine` / `EOF` is not flagged.
+/// An unterminated heredoc body is not treated as a quoting problem.
+#[must_use]
+pub fn has_unbalanced_quotes(command: &str) -> bool {
+    let bytes = command.as_bytes();
+    let len = bytes.len();
+    let mut pending_heredocs: SmallVec<[(&[u8], bool); 2]> = SmallVec::new();
+    let mut i = 0;
+
+    while i < len {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\'' => match find_quote_end(bytes, i + 1, b'\'', false) {
+                Some(end) => i = end + 1,
+                None => return true,
+            },
+            b'$' if bytes.get(i + 1) == Some(&b'\'') => {
+                match find_quote_end(bytes, i + 2, b'\'', true) {
+                    Some(end) => i = end + 1,
+                    None => return true,
+                }
+            }
+            b'"' => match find_quote_end(bytes, i + 1, b'"', true) {
+                Some(end) => i = end + 1,
+                None => return true,
+            },
+            b'#' if i == 0
+                || bytes[i - 1].is_ascii_whitespace()
+                || matches!(bytes[i - 1], b';' | b'&' | b'|' | b'(') =>
+            {
+                while i < len && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'<' if bytes.get(i + 1) == Some(&b'<') => {
+                if bytes.get(i + 2) == Some(&b'<') {
+                    // Here-string (`<<<`): the word that follows is ordinary shell text.
+                    i += 3;
+                    continue;
+                }
+                let mut j = i + 2;
+                let strip_tabs = bytes.get(j) == Some(&b'-');
+                if strip_tabs {
+                    j += 1;
+                }
+                while j < len && matches!(bytes[j], b' ' | b'\t') {
+                    j += 1;
+                }
+                let start = j;
+                let delimiter: &[u8] = match bytes.get(j) {
+                    Some(&quote @ (b'\'' | b'"')) => {
+                        let Some(end) = find_quote_end(bytes, j + 1, quote, false) else {
+                            return true;
+                        };
+                        j = end + 1;
+                        &bytes[start + 1..end]
+                    }
+                    _ => {
+                        while j < len
+                            && !bytes[j].is_ascii_whitespace()
+                            && !matches!(bytes[j], b';' | b'&' | b'|' | b'<' | b'>' | b'(' | b')')
+                        {
+                            j += 1;
+                        }
+                        let word = &bytes[start..j];
+                        word.strip_prefix(b"\\").unwrap_or(word)
+                    }
+                };
+                if !delimiter.is_empty() {
+                    pending_heredocs.push((delimiter, strip_tabs));
+                }
+                i = j;
+            }
+            b'\n' => {
+                i += 1;
+                for (delimiter, strip_tabs) in pending_heredocs.drain(..) {
+                    i = skip_heredoc_body(bytes, i, delimiter, strip_tabs);
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    false
+}
+
+/// Find the closing `quote` starting at `start`, honoring backslash escapes if requested.
+fn find_quote_end(bytes: &[u8], start: usize, quote: u8, escapes: bool) -> Option<usize> {
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if escapes => i += 2,
+            b if b == quote => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Skip heredoc body lines up to and including the terminating delimiter line.
+fn skip_heredoc_body(bytes: &[u8], mut i: usize, delimiter: &[u8], strip_tabs: bool) -> usize {
+    while i < bytes.len() {
+        let line_end = bytes[i..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |offset| i + offset);
+        let mut line = &bytes[i..line_end];
+        if strip_tabs {
+            while let Some(rest) = line.strip_prefix(b"\t") {
+                line = rest;
+            }
+        }
+        i = (line_end + 1).min(bytes.len());
+        if line == delimiter {
+            break;
+        }
+    }
+    i
+}
+
 /// Strip leading backslash from the first command token.
 ///
 /// This handles bash alias bypass: `\git` instead of `git`.
@@ -1591,6 +1730,504 @@ fn strip_leading_backslash(command: &str) -> Option<(String, StrippedWrapper)> {
     ))
 }
 
+// =============================================================================
+// PowerShell normalization
+// =============================================================================
+
+/// A PowerShell cmdlet whose aliases and parameter prefixes are canonicalized.
+struct PowerShellCmdlet {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    /// Canonical parameter names with the shortest unambiguous prefix length.
+    params: &'static [(&'static str, usize)],
+}
+
+const POWERSHELL_CMDLETS: &[PowerShellCmdlet] = &[
+    PowerShellCmdlet {
+        name: "Remove-Item",
+        aliases: &["rm", "del", "erase", "ri", "rd", "rmdir"],
+        params: &[("-Recurse", 2), ("-Force", 3), ("-WhatIf", 3)],
+    },
+    PowerShellCmdlet {
+        name: "Clear-Content",
+        aliases: &["clc"],
+        params: &[("-WhatIf", 3)],
+    },
+    PowerShellCmdlet {
+        name: "Format-Volume",
+        aliases: &[],
+        params: &[("-Force", 3), ("-WhatIf", 3)],
+    },
+];
+
+fn powershell_cmdlet(word: &str) -> Option<&'static PowerShellCmdlet> {
+    // Module-qualified names: Microsoft.PowerShell.Management\Remove-Item
+    let word = word.rsplit('\\').next().unwrap_or(word);
+    POWERSHELL_CMDLETS.iter().find(|cmdlet| {
+        cmdlet.name.eq_ignore_ascii_case(word)
+            || cmdlet
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(word))
+    })
+}
+
+/// Expand an abbreviated parameter (`-fo`, `-REC:$true`) to its canonical name.
+fn powershell_param(cmdlet: &PowerShellCmdlet, word: &str) -> Option<String> {
+    let (name, value) = word
+        .find(':')
+        .map_or((word, ""), |idx| (&word[..idx], &word[idx..]));
+    let name_lower = name.to_ascii_lowercase();
+    cmdlet
+        .params
+        .iter()
+        .find(|(param, min_len)| {
+            name_lower.len() >= *min_len && param.to_ascii_lowercase().starts_with(&name_lower)
+        })
+        .map(|(param, _)| format!("{param}{value}"))
+}
+
+/// Rewrite a PowerShell command into canonical form for pattern matching.
+///
+/// PowerShell is case-insensitive and accepts aliases (`rm`, `del`, `rd`) and
+/// any unambiguous parameter prefix (`-r`, `-fo`). For the cmdlets covered by
+/// the `windows.powershell` pack, this expands aliases and parameter prefixes
+/// to their canonical spelling, so `rm -r -fo C:\data` becomes
+/// `Remove-Item -Recurse -Force C:\data`. Everything else is left untouched.
+///
+/// Statements are split on `;`, `|`, `&`, newlines, and braces/parentheses so
+/// pipelines and script blocks are handled. Quoted strings are never rewritten.
+#[must_use]
+pub fn normalize_powershell(command: &str) -> Cow<'_, str> {
+    let bytes = command.as_bytes();
+    let len = bytes.len();
+    let mut replacements: SmallVec<[(Range<usize>, String); 4]> = SmallVec::new();
+    let mut cmdlet: Option<&'static PowerShellCmdlet> = None;
+    let mut command_position = true;
+    let mut i = 0;
+
+    while i < len {
+        match bytes[i] {
+            b';' | b'|' | b'&' | b'\n' | b'{' | b'}' | b'(' | b')' => {
+                command_position = true;
+                cmdlet = None;
+                i += 1;
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            b'\'' | b'"' => {
+                // PowerShell escapes with backtick inside double quotes; single quotes are literal.
+                let quote = bytes[i];
+                i += 1;
+                while i < len && bytes[i] != quote {
+                    i += if quote == b'"' && bytes[i] == b'`' {
+                        2
+                    } else {
+                        1
+                    };
+                }
+                i += 1;
+                command_position = false;
+            }
+            _ => {
+                let start = i;
+                while i < len
+                    && !bytes[i].is_ascii_whitespace()
+                    && !matches!(
+                        bytes[i],
+                        b';' | b'|' | b'&' | b'{' | b'}' | b'(' | b')' | b'\'' | b'"'
+                    )
+                {
+                    i += 1;
+                }
+                let word = &command[start..i];
+                if command_position {
+                    cmdlet = powershell_cmdlet(word);
+                    if let Some(found) = cmdlet {
+                        if word != found.name {
+                            replacements.push((start..i, found.name.to_string()));
+                        }
+                    }
+                    command_position = false;
+                } else if let Some(found) = cmdlet.filter(|_| word.starts_with('-')) {
+                    if let Some(param) = powershell_param(found, word) {
+                        if param != word {
+                            replacements.push((start..i, param));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if replacements.is_empty() {
+        return Cow::Borrowed(command);
+    }
+
+    let mut out = String::with_capacity(len + 32);
+    let mut last = 0;
+    for (range, text) in replacements {
+        out.push_str(&command[last..range.start]);
+        out.push_str(&text);
+        last = range.end;
+    }
+    out.push_str(&command[last..]);
+    Cow::Owned(out)
+}
+
+// =============================================================================
+// fish normalization
+// =============================================================================
+
+/// Rewrite fish shell syntax into the POSIX form the rest of the pipeline parses.
+///
+/// fish chains commands with `; and` / `; or` (or `and` / `or` at the start of
+/// a line) and writes command substitution as a bare `(...)`. Left as-is, a
+/// segment like `and rm -rf /` starts with `and` rather than `rm`, and
+/// `echo (rm -rf /)` looks like a plain argument. This rewrites them to
+/// `&& rm -rf /` and `echo $(rm -rf /)`. Quoted strings and comments are never
+/// rewritten, and `$(...)` (fish 3.4+) is left alone.
+#[must_use]
+pub fn normalize_fish(command: &str) -> Cow<'_, str> {
+    let bytes = command.as_bytes();
+    let len = bytes.len();
+    let mut replacements: SmallVec<[(Range<usize>, String); 4]> = SmallVec::new();
+    let mut command_position = true;
+    // Start of the `;`/newline that ended the previous command, if nothing but
+    // whitespace has followed it.
+    let mut statement_end: Option<usize> = None;
+    let mut i = 0;
+
+    while i < len {
+        match bytes[i] {
+            b';' | b'\n' => {
+                command_position = true;
+                statement_end = Some(i);
+                i += 1;
+            }
+            b'|' | b'&' | b')' => {
+                command_position = bytes[i] != b')';
+                statement_end = None;
+                i += 1;
+            }
+            b'(' => {
+                if i == 0 || bytes[i - 1] != b'$' {
+                    replacements.push((i..i + 1, "$(".to_string()));
+                }
+                command_position = true;
+                statement_end = None;
+                i += 1;
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            b'#' if command_position || bytes[i - 1].is_ascii_whitespace() => {
+                while i < len && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'\'' | b'"' => {
+                // Both quote styles honor backslash escapes of the quote and backslash.
+                let quote = bytes[i];
+                i += 1;
+                while i < len && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+                command_position = false;
+                statement_end = None;
+            }
+            _ => {
+                let start = i;
+                while i < len
+                    && !bytes[i].is_ascii_whitespace()
+                    && !matches!(bytes[i], b';' | b'|' | b'&' | b'(' | b')' | b'\'' | b'"')
+                {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = i.min(len);
+                let word = &command[start..i];
+                match (statement_end, command_position, word) {
+                    (Some(end), true, "and") => replacements.push((end..i, " &&".to_string())),
+                    (Some(end), true, "or") => replacements.push((end..i, " ||".to_string())),
+                    _ => command_position = false,
+                }
+                statement_end = None;
+            }
+        }
+    }
+
+    if replacements.is_empty() {
+        return Cow::Borrowed(command);
+    }
+
+    let mut out = String::with_capacity(len + 8);
+    let mut last = 0;
+    for (range, text) in replacements {
+        out.push_str(&command[last..range.start]);
+        out.push_str(&text);
+        last = range.end;
+    }
+    out.push_str(&command[last..]);
+    Cow::Owned(out)
+}
+
+// =============================================================================
+// Brace expansion
+// =============================================================================
+
+/// Maximum number of words a single word may brace-expand into.
+///
+/// Words that would expand past this (`{1..100000}`, or several groups whose
+/// product is large) are left in their brace form.
+pub const MAX_BRACE_EXPANSIONS: usize = 64;
+
+/// Perform bash brace expansion on every unquoted word, for matching only.
+///
+/// `rm -rf /{bin,etc}` becomes `rm -rf /bin /etc` so patterns see each target.
+/// Comma lists (`{a,b,c}`, nested and with empty alternatives) and simple
+/// ranges (`{1..3}`, `{a..e}`, `{0..10..2}`) are expanded; `{}`, `{x}`, and
+/// `${var}` are literal, as in bash. Braces inside quotes are never expanded.
+/// A word whose expansion exceeds [`MAX_BRACE_EXPANSIONS`] is left unchanged.
+#[must_use]
+pub fn expand_braces(command: &str) -> Cow<'_, str> {
+    if !command.contains('{') {
+        return Cow::Borrowed(command);
+    }
+
+    let mut replacements: SmallVec<[(Range<usize>, String); 4]> = SmallVec::new();
+    for token in tokenize_for_normalization(command) {
+        if token.kind != NormalizeTokenKind::Word {
+            continue;
+        }
+        let Some(word) = token.text(command) else {
+            continue;
+        };
+        if !word.contains('{') {
+            continue;
+        }
+        if let Some(words) = expand_word(word, MAX_BRACE_EXPANSIONS) {
+            if words.len() > 1 {
+                replacements.push((token.byte_range, words.join(" ")));
+            }
+        }
+    }
+
+    if replacements.is_empty() {
+        return Cow::Borrowed(command);
+    }
+
+    let mut out = String::with_capacity(command.len() * 2);
+    let mut last = 0;
+    for (range, text) in replacements {
+        out.push_str(&command[last..range.start]);
+        out.push_str(&text);
+        last = range.end;
+    }
+    out.push_str(&command[last..]);
+    Cow::Owned(out)
+}
+
+/// Expand the first brace group in `word`, recursing into the results.
+///
+/// Returns `vec![word]` when there is nothing to expand and `None` when the
+/// expansion would produce more than `limit` words.
+fn expand_word(word: &str, limit: usize) -> Option<Vec<String>> {
+    let Some((range, alternatives)) = find_brace_group(word, limit).ok()? else {
+        return Some(vec![word.to_string()]);
+    };
+
+    let prefix = &word[..range.start];
+    let suffix = &word[range.end..];
+    let mut words = Vec::new();
+    for alternative in alternatives {
+        let candidate = format!("{prefix}{alternative}{suffix}");
+        words.extend(expand_word(&candidate, limit)?);
+        if words.len() > limit {
+            return None;
+        }
+    }
+    Some(words)
+}
+
+/// Byte range of a brace group within a word, and its alternatives.
+type BraceGroup = (Range<usize>, Vec<String>);
+
+/// Find the first expandable `{...}` group in `word`.
+///
+/// Returns the byte range of the group (braces included) and its alternatives,
+/// `Ok(None)` when no group is expandable, and `Err(())` when a range group
+/// alone exceeds `limit`.
+fn find_brace_group(word: &str, limit: usize) -> Result<Option<BraceGroup>, ()> {
+    let bytes = word.as_bytes();
+    let len = bytes.len();
+    let mut i = 0;
+
+    while i < len {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\'' | b'"' => i = skip_quoted(bytes, i),
+            b'$' if bytes.get(i + 1) == Some(&b'{') => {
+                i = matching_brace(bytes, i + 1).map_or(len, |end| end + 1);
+            }
+            b'{' => {
+                let Some(close) = matching_brace(bytes, i) else {
+                    return Ok(None);
+                };
+                let body = &word[i + 1..close];
+                let alternatives = match split_top_level_commas(body) {
+                    Some(parts) => parts,
+                    None => match brace_range(body, limit) {
+                        Ok(Some(range)) => range,
+                        Ok(None) => {
+                            i += 1;
+                            continue;
+                        }
+                        Err(()) => return Err(()),
+                    },
+                };
+                return Ok(Some((i..close + 1, alternatives)));
+            }
+            _ => i += 1,
+        }
+    }
+    Ok(None)
+}
+
+/// Index just past the quoted string starting at `start`.
+fn skip_quoted(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() && bytes[i] != quote {
+        i += if quote == b'"' && bytes[i] == b'\\' {
+            2
+        } else {
+            1
+        };
+    }
+    i + 1
+}
+
+/// Index of the `}` matching the `{` at `open`, skipping quotes and escapes.
+fn matching_brace(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => {
+                i += 2;
+                continue;
+            }
+            b'\'' | b'"' => {
+                i = skip_quoted(bytes, i);
+                continue;
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Split a brace body on commas outside nested braces and quotes.
+///
+/// Returns `None` when there is no top-level comma (not a comma list).
+fn split_top_level_commas(body: &str) -> Option<Vec<String>> {
+    let bytes = body.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\'' | b'"' => i = skip_quoted(bytes, i),
+            b'{' => i = matching_brace(bytes, i).map_or(bytes.len(), |end| end + 1),
+            b',' => {
+                parts.push(body[start..i].to_string());
+                i += 1;
+                start = i;
+            }
+            _ => i += 1,
+        }
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    parts.push(body.get(start..).unwrap_or_default().to_string());
+    Some(parts)
+}
+
+/// Expand a `{x..y}` or `{x..y..step}` range body of integers or single letters.
+///
+/// Returns `Ok(None)` for bodies that are not ranges and `Err(())` when the
+/// range has more than `limit` elements.
+fn brace_range(body: &str, limit: usize) -> Result<Option<Vec<String>>, ()> {
+    let parts: SmallVec<[&str; 3]> = body.split("..").collect();
+    let (first, last) = match parts.as_slice() {
+        [first, last] | [first, last, _] => (*first, *last),
+        _ => return Ok(None),
+    };
+    let step = match parts.get(2) {
+        Some(step) => match step.parse::<i64>() {
+            Ok(step) => step.unsigned_abs().max(1),
+            Err(_) => return Ok(None),
+        },
+        None => 1,
+    };
+
+    let (from, to, letters) = match (first.parse::<i64>(), last.parse::<i64>()) {
+        (Ok(from), Ok(to)) => (from, to, false),
+        _ => match (first.as_bytes(), last.as_bytes()) {
+            (&[from], &[to]) if from.is_ascii_alphabetic() && to.is_ascii_alphabetic() => {
+                (i64::from(from), i64::from(to), true)
+            }
+            _ => return Ok(None),
+        },
+    };
+
+    let count = from.abs_diff(to) / step + 1;
+    if count > limit as u64 {
+        return Err(());
+    }
+
+    // Bash zero-pads every element when either endpoint has a leading zero.
+    let padded = |s: &str| {
+        let digits = s.trim_start_matches('-');
+        digits.len() > 1 && digits.starts_with('0')
+    };
+    let width = if !letters && (padded(first) || padded(last)) {
+        first.len().max(last.len())
+    } else {
+        0
+    };
+
+    let values = (0..count).map(|n| {
+        // count <= limit, so n * step stays far below i64::MAX.
+        let offset = i64::try_from(n * step).unwrap_or(i64::MAX);
+        let value = if from <= to {
+            from + offset
+        } else {
+            from - offset
+        };
+        if letters {
+            u8::try_from(value).map_or_else(|_| String::new(), |b| char::from(b).to_string())
+        } else if value < 0 {
+            format!(
+                "-{:0>w$}",
+                value.unsigned_abs(),
+                w = width.saturating_sub(1)
+            )
+        } else {
+            format!("{value:0>width$}")
+        }
+    });
+    Ok(Some(values.collect()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1644,6 +2281,20 @@ mod tests {
         assert!(newline_token.is_some(), "Expected newline separator token");
     }
 
+    #[test]
+    fn test_tokenize_for_normalization_keeps_noclobber_override_redirect() {
+        let cmd = "echo x >| important.conf";
+        let tokens = tokenize_for_normalization(cmd);
+
+        assert!(
+            tokens
+                .iter()
+                .all(|tok| tok.kind != NormalizeTokenKind::Separator),
+            "`>|` must not be split into a pipe"
+        );
+        assert!(tokens.iter().any(|tok| tok.text(cmd) == Some(">|")));
+    }
+
     #[test]
     fn test_not_sudo_prefix() {
         let result = strip_wrapper_prefixes("sudoku play");
@@ -1883,6 +2534,185 @@ mod tests {
             Some("hello".to_string())
         );
     }
+
+    #[test]
+    fn test_has_unbalanced_quotes() {
+        assert!(has_unbalanced_quotes("git reset --hard \""));
+        assert!(has_unbalanced_quotes("rm -rf 'foo"));
+        assert!(has_unbalanced_quotes("echo $'it\\'s"));
+        assert!(!has_unbalanced_quotes("git commit -m \"it's done\""));
+        assert!(!has_unbalanced_quotes("echo \"a \\\" b\""));
+        assert!(!has_unbalanced_quotes("echo it\\'s"));
+        assert!(!has_unbalanced_quotes("echo \"line one\nline two\""));
+    }
+
+    #[test]
+    fn test_has_unbalanced_quotes_skips_heredocs_and_comments() {
+        assert!(!has_unbalanced_quotes("cat <<EOF\nit's fine\nEOF"));
+        assert!(!has_unbalanced_quotes(
+            "cat <<-'END' > notes.txt\n\tdon't \"quote\" me\n\tEND\necho done"
+        ));
+        assert!(!has_unbalanced_quotes("grep x <<< \"a b\" # it's ok"));
+        assert!(has_unbalanced_quotes("cat <<EOF\nbody\nEOF\necho \"oops"));
+    }
+
+    #[test]
+    fn test_normalize_powershell_expands_aliases_and_params() {
+        assert_eq!(
+            normalize_powershell(r"rm -r -fo C:\data"),
+            r"Remove-Item -Recurse -Force C:\data"
+        );
+        assert_eq!(
+            normalize_powershell(r"remove-item -REC -force:$true C:\data"),
+            r"Remove-Item -Recurse -Force:$true C:\data"
+        );
+        assert_eq!(
+            normalize_powershell(r"Get-ChildItem C:\tmp | % { del $_ -fo }; clc log.txt"),
+            r"Get-ChildItem C:\tmp | % { Remove-Item $_ -Force }; Clear-Content log.txt"
+        );
+    }
+
+    #[test]
+    fn test_normalize_powershell_leaves_other_text_alone() {
+        assert!(matches!(
+            normalize_powershell(r"Remove-Item -Recurse -Force C:\data"),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            normalize_powershell("Write-Output 'rm -r -fo'"),
+            Cow::Borrowed(_)
+        ));
+        // `-f` is ambiguous (-Filter, -Force) and is not expanded.
+        assert_eq!(normalize_powershell("ri -f x"), "Remove-Item -f x");
+    }
+
+    fn segments(command: &str) -> Vec<String> {
+        let tokens = tokenize_for_normalization(command);
+        let mut out = Vec::new();
+        let mut words: Vec<&str> = Vec::new();
+        for token in &tokens {
+            match token.kind {
+                NormalizeTokenKind::Separator => {
+                    if !words.is_empty() {
+                        out.push(words.join(" "));
+                        words.clear();
+                    }
+                }
+                NormalizeTokenKind::Word => words.extend(token.text(command)),
+            }
+        }
+        if !words.is_empty() {
+            out.push(words.join(" "));
+        }
+        out
+    }
+
+    #[test]
+    fn test_expand_braces_comma_lists() {
+        assert_eq!(expand_braces("rm -rf /{bin,etc}"), "rm -rf /bin /etc");
+        assert_eq!(expand_braces("echo {a,b}"), "echo a b");
+        assert_eq!(expand_braces("cp file{,.bak}"), "cp file file.bak");
+        assert_eq!(expand_braces("echo a{b,c{d,e}}f"), "echo abf acdf acef");
+        assert_eq!(expand_braces("echo {a,b}{1,2}"), "echo a1 a2 b1 b2");
+        assert_eq!(
+            normalize_command("sudo rm -rf /{usr,var}"),
+            "rm -rf /usr /var"
+        );
+    }
+
+    #[test]
+    fn test_expand_braces_ranges() {
+        assert_eq!(expand_braces("echo {1..3}"), "echo 1 2 3");
+        assert_eq!(expand_braces("echo {3..1}"), "echo 3 2 1");
+        assert_eq!(expand_braces("echo {0..10..5}"), "echo 0 5 10");
+        assert_eq!(expand_braces("echo {01..03}"), "echo 01 02 03");
+        assert_eq!(expand_braces("echo {a..c}"), "echo a b c");
+        assert_eq!(expand_braces("echo {e..a..2}"), "echo e c a");
+    }
+
+    #[test]
+    fn test_expand_braces_leaves_literals_alone() {
+        for command in [
+            "find . -exec rm {} \\;",
+            "git checkout HEAD@{1}",
+            "echo ${HOME}/{x}",
+            "awk '{print $1,$2}' file",
+            "echo \"{a,b}\"",
+            "echo \\{a,b}",
+            "echo {a..}",
+        ] {
+            assert!(
+                matches!(expand_braces(command), Cow::Borrowed(_)),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_braces_caps_expansion_count() {
+        assert!(matches!(
+            expand_braces("rm -rf /tmp/{1..100000}"),
+            Cow::Borrowed(_)
+        ));
+        // 8 * 8 * 8 words exceeds the cap even though each group is small.
+        let product = "touch {a,b,c,d,e,f,g,h}{a,b,c,d,e,f,g,h}{a,b,c,d,e,f,g,h}";
+        assert!(matches!(expand_braces(product), Cow::Borrowed(_)));
+        // Other words in the same command still expand.
+        assert_eq!(
+            expand_braces("rm -rf /{bin,etc} /tmp/{1..100000}"),
+            "rm -rf /bin /etc /tmp/{1..100000}"
+        );
+        let at_cap = format!("echo {{1..{MAX_BRACE_EXPANSIONS}}}");
+        assert_eq!(
+            expand_braces(&at_cap).split_whitespace().count(),
+            MAX_BRACE_EXPANSIONS + 1
+        );
+    }
+
+    #[test]
+    fn test_normalize_fish_splits_and_or_chains() {
+        let normalized = normalize_fish("rm -rf /; and echo done");
+        assert_eq!(normalized, "rm -rf / && echo done");
+        assert_eq!(segments(&normalized), ["rm -rf /", "echo done"]);
+
+        assert_eq!(
+            normalize_fish("make test; or git reset --hard"),
+            "make test || git reset --hard"
+        );
+        assert_eq!(
+            normalize_fish("cd build\nand rm -rf *"),
+            "cd build && rm -rf *"
+        );
+        // Without fish rewriting, `and` is the command word of the second segment.
+        assert_eq!(segments("true; and rm -rf /"), ["true", "and rm -rf /"]);
+    }
+
+    #[test]
+    fn test_normalize_fish_command_substitution() {
+        assert_eq!(normalize_fish("echo (rm -rf /)"), "echo $(rm -rf /)");
+        assert_eq!(
+            normalize_fish("set files (ls); and rm $files"),
+            "set files $(ls) && rm $files"
+        );
+        // fish 3.4 `$(...)` is already POSIX-shaped.
+        assert_eq!(normalize_fish("echo $(pwd)"), "echo $(pwd)");
+    }
+
+    #[test]
+    fn test_normalize_fish_leaves_quotes_and_arguments_alone() {
+        for command in [
+            "git commit -m 'fix; and (more)'",
+            "echo \"a; or b\"",
+            "echo and or",
+            "grep -e and file; echo done # (comment); and",
+            "echo 'it\\'s; and (x)'",
+        ] {
+            assert!(
+                matches!(normalize_fish(command), Cow::Borrowed(_)),
+                "{command}"
+            );
+        }
+    }
 }
 
 #[cfg(test)]
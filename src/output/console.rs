@@ -17,11 +17,17 @@
 //! // Get a console and print styled text
 //! console().print("[bold red]Error:[/] Something went wrong");
 //! ```
+//!
+//! Embedders can capture output instead of writing to stderr:
+//!
+//! ```ignore
+//! let buf = Arc::new(Mutex::new(Vec::new()));
+//! DcgConsole::with_writer(Arc::clone(&buf)).print("captured");
+//! ```
 
-use std::io;
-#[cfg(not(feature = "rich-output"))]
-use std::io::Write;
-use std::sync::OnceLock;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex, OnceLock};
 
 /// Global flag indicating whether rich output should be used.
 static USE_RICH: OnceLock<bool> = OnceLock::new();
@@ -33,22 +39,76 @@ static USE_RICH: OnceLock<bool> = OnceLock::new();
 ///
 /// Note: This struct creates a new Console on each operation to avoid
 /// thread-safety issues with the underlying rich_rust Console.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct DcgConsole {
     force_plain: bool,
+    sink: Option<ConsoleSink>,
+}
+
+/// Shared handle to a caller-provided output sink.
+#[derive(Clone)]
+struct ConsoleSink(Arc<Mutex<dyn Write + Send>>);
+
+impl Write for ConsoleSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .map_err(|_| io::Error::other("console sink mutex poisoned"))?
+            .write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0
+            .lock()
+            .map_err(|_| io::Error::other("console sink mutex poisoned"))?
+            .flush()
+    }
+}
+
+impl fmt::Debug for ConsoleSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ConsoleSink(..)")
+    }
 }
 
 impl DcgConsole {
     /// Create a new console with rich formatting (if available).
     #[must_use]
     pub const fn new() -> Self {
-        Self { force_plain: false }
+        Self {
+            force_plain: false,
+            sink: None,
+        }
     }
 
     /// Create a plain-text console (no colors, no unicode).
     #[must_use]
     pub const fn plain() -> Self {
-        Self { force_plain: true }
+        Self {
+            force_plain: true,
+            sink: None,
+        }
+    }
+
+    /// Create a console that writes to `writer` instead of stderr.
+    ///
+    /// The caller keeps a clone of the `Arc` to read the output back, e.g. to
+    /// render explain/scan output into a buffer or a GUI.
+    #[must_use]
+    pub fn with_writer<W: Write + Send + 'static>(writer: Arc<Mutex<W>>) -> Self {
+        Self {
+            force_plain: false,
+            sink: Some(ConsoleSink(writer)),
+        }
+    }
+
+    /// Create a plain-text console that writes to `writer` instead of stderr.
+    #[must_use]
+    pub fn plain_with_writer<W: Write + Send + 'static>(writer: Arc<Mutex<W>>) -> Self {
+        Self {
+            force_plain: true,
+            sink: Some(ConsoleSink(writer)),
+        }
     }
 
     /// Print styled text using markup syntax.
@@ -65,12 +125,12 @@ impl DcgConsole {
         }
     }
 
-    /// Print text without rich-output feature (plain text to stderr).
+    /// Print text without rich-output feature (plain text to the sink).
     #[cfg(not(feature = "rich-output"))]
     pub fn print(&self, text: &str) {
         // Strip markup-like patterns for plain output
         let plain_text = strip_markup(text);
-        let _ = writeln!(io::stderr(), "{plain_text}");
+        let _ = writeln!(self.writer(), "{plain_text}");
     }
 
     /// Print a renderable (Panel, Table, etc.).
@@ -100,7 +160,7 @@ impl DcgConsole {
         } else {
             "-".repeat(width)
         };
-        let _ = writeln!(io::stderr(), "{line}");
+        let _ = writeln!(self.writer(), "{line}");
     }
 
     /// Get terminal width.
@@ -124,10 +184,18 @@ impl DcgConsole {
         self.force_plain
     }
 
+    /// Output destination: the custom sink if one was provided, otherwise stderr.
+    fn writer(&self) -> Box<dyn Write + Send> {
+        match &self.sink {
+            Some(sink) => Box::new(sink.clone()),
+            None => Box::new(io::stderr()), // CRITICAL: default output goes to stderr
+        }
+    }
+
     /// Create the underlying rich_rust Console instance.
     #[cfg(feature = "rich-output")]
     fn create_inner_console(&self) -> rich_rust::console::Console {
-        let mut builder = rich_rust::console::Console::builder().file(Box::new(io::stderr())); // CRITICAL: all output to stderr
+        let mut builder = rich_rust::console::Console::builder().file(self.writer());
 
         if self.force_plain {
             builder = builder.no_color();
@@ -266,6 +334,23 @@ mod tests {
         console.rule(Some(""));
     }
 
+    #[test]
+    fn test_with_writer_captures_output() {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let console = DcgConsole::plain_with_writer(Arc::clone(&buf));
+        let clone = console.clone();
+        console.print("[bold]captured[/] line");
+        clone.rule(Some("Section"));
+
+        let output = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("captured line"), "got: {output:?}");
+        assert!(output.contains("Section"), "got: {output:?}");
+        assert!(
+            !output.contains("[bold]"),
+            "markup should be rendered: {output:?}"
+        );
+    }
+
     #[test]
     fn test_console_function_returns_valid_console() {
         // console() should always return a valid console in any environment
@@ -38,7 +38,7 @@ pub use progress::{
 #[cfg(feature = "rich-output")]
 pub use progress::{RichProgressStyle, render_progress_bar_rich};
 pub use rich_theme::{RichThemeExt, color_to_markup, severity_badge_markup, severity_panel_title};
-pub use tables::{ScanResultRow, ScanResultsTable, TableStyle};
+pub use tables::{DelimitedFormat, ScanResultRow, ScanResultsTable, TableStyle};
 pub use test::{AllowedReason, TestOutcome, TestResultBox};
 pub use theme::{BorderStyle, Severity, SeverityColors, Theme, ThemePalette};
 pub use tree::{DcgTree, DcgTreeGuides, ExplainTreeBuilder, TreeNode};
@@ -16,6 +16,8 @@
 //! - Markdown - GitHub-flavored markdown tables
 //! - Compact - Minimal spacing for dense output
 //!
+//! Spreadsheet exports (`--format csv|tsv`) go through [`DelimitedFormat`].
+//!
 //! # Feature Flags
 //!
 //! When the `rich-output` feature is enabled, tables are rendered using `rich_rust`
@@ -913,6 +915,71 @@ pub fn format_summary(total: usize, categories: &[(&str, usize)]) -> String {
     }
 }
 
+/// Delimited text export for spreadsheets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelimitedFormat {
+    /// RFC 4180 CSV: fields containing a comma, quote, or line break are
+    /// wrapped in double quotes, with embedded quotes doubled.
+    Csv,
+    /// Tab-separated values: tabs, line breaks, and backslashes inside a field
+    /// are written as `\t`, `\n`, `\r`, and `\\`.
+    Tsv,
+}
+
+impl DelimitedFormat {
+    /// Field separator.
+    #[must_use]
+    pub const fn separator(self) -> char {
+        match self {
+            Self::Csv => ',',
+            Self::Tsv => '\t',
+        }
+    }
+
+    /// Escape a single field.
+    #[must_use]
+    pub fn escape(self, field: &str) -> std::borrow::Cow<'_, str> {
+        match self {
+            Self::Csv => {
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\"")).into()
+                } else {
+                    field.into()
+                }
+            }
+            Self::Tsv => {
+                if field.contains(['\t', '\n', '\r', '\\']) {
+                    field
+                        .replace('\\', "\\\\")
+                        .replace('\t', "\\t")
+                        .replace('\n', "\\n")
+                        .replace('\r', "\\r")
+                        .into()
+                } else {
+                    field.into()
+                }
+            }
+        }
+    }
+
+    /// Escape and join `fields` into one record (without a line terminator).
+    #[must_use]
+    pub fn record<I, S>(self, fields: I) -> String
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut line = String::new();
+        for (i, field) in fields.into_iter().enumerate() {
+            if i > 0 {
+                line.push(self.separator());
+            }
+            line.push_str(&self.escape(field.as_ref()));
+        }
+        line
+    }
+}
+
 fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
     let text_len = text.chars().count();
     if text_len <= max_chars {
@@ -933,6 +1000,67 @@ fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
 mod tests {
     use super::*;
 
+    /// Minimal RFC 4180 reader: quoted fields may hold commas, doubled
+    /// quotes, and line breaks.
+    fn parse_csv(text: &str) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (in_quotes, c) {
+                (true, '"') if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                (true, '"') => in_quotes = false,
+                (true, c) => field.push(c),
+                (false, '"') => in_quotes = true,
+                (false, ',') => record.push(std::mem::take(&mut field)),
+                (false, '\n') => {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                (false, c) => field.push(c),
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn csv_record_round_trips_through_parser() {
+        let command = "psql -c \"SELECT a, b FROM t\"\necho 'done'";
+        let csv = DelimitedFormat::Csv;
+        let text = format!(
+            "{}\n{}\n",
+            csv.record(["command", "cwd"]),
+            csv.record([command, "/tmp/a,b"])
+        );
+        let records = parse_csv(&text);
+        assert_eq!(
+            records,
+            vec![
+                vec!["command".to_string(), "cwd".to_string()],
+                vec![command.to_string(), "/tmp/a,b".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn delimited_format_escapes_fields() {
+        let csv = DelimitedFormat::Csv;
+        assert_eq!(
+            csv.record(["a", "b,c", "say \"hi\""]),
+            "a,\"b,c\",\"say \"\"hi\"\"\""
+        );
+        assert_eq!(csv.escape("line\nbreak"), "\"line\nbreak\"");
+
+        let tsv = DelimitedFormat::Tsv;
+        assert_eq!(tsv.record(["a", "b\tc", "d\\e\nf"]), "a\tb\\tc\td\\\\e\\nf");
+        assert_eq!(tsv.escape("plain, \"text\""), "plain, \"text\"");
+    }
+
     #[test]
     fn test_scan_results_table_empty() {
         let table = ScanResultsTable::new(vec![]);
@@ -12,7 +12,7 @@ use super::terminal_width;
 #[cfg(not(feature = "rich-output"))]
 use super::theme::BorderStyle;
 use super::theme::Theme;
-use crate::evaluator::{EvaluationDecision, EvaluationResult, PatternMatch};
+use crate::evaluator::{Decision, EvaluationResult, PatternMatch};
 #[cfg(feature = "rich-output")]
 use crate::output::rich_theme::RichThemeExt;
 use crate::packs::Severity;
@@ -80,7 +80,7 @@ impl TestResultBox {
         let command = command.into();
 
         let result = match eval.decision {
-            EvaluationDecision::Deny => {
+            Decision::Warn | Decision::Ask | Decision::Deny => {
                 let pattern_info = eval.pattern_info.as_ref();
                 TestOutcome::Blocked {
                     pattern_id: pattern_info.and_then(|p| p.pattern_name.clone()),
@@ -93,7 +93,7 @@ impl TestResultBox {
                     confidence: pattern_info.and_then(confidence_from_severity),
                 }
             }
-            EvaluationDecision::Allow => {
+            Decision::Allow => {
                 if eval.skipped_due_to_budget {
                     TestOutcome::Allowed {
                         reason: AllowedReason::BudgetExhausted,
@@ -949,7 +949,7 @@ mod tests {
     #[test]
     fn test_from_evaluation_denied() {
         let eval = EvaluationResult {
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             pattern_info: Some(PatternMatch {
                 pack_id: Some("core.git".to_string()),
                 pattern_name: Some("reset_hard".to_string()),
@@ -3,6 +3,7 @@
 //! This includes patterns for:
 //! - ec2 terminate-instances
 //! - s3 rm --recursive
+//! - s3 sync --delete
 //! - rds delete-db-instance
 //! - cloudformation delete-stack
 
@@ -109,6 +110,23 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Consider versioning for recovery:\n  \
              aws s3api list-object-versions --bucket bucket"
         ),
+        // s3 sync --delete (--dryrun only previews)
+        destructive_pattern!(
+            "s3-sync-delete",
+            r"aws\s+s3\s+sync\b(?!.*--dryrun\b).*--delete(?=\s|$)",
+            "aws s3 sync --delete removes objects at the destination that aren't at the source.",
+            High,
+            "s3 sync --delete makes the destination an exact mirror of the source, deleting \
+             everything at the destination that the source lacks. The last path is the \
+             destination, so the direction decides what gets destroyed:\n\n\
+             - ./local s3://bucket --delete: Deletes remote objects missing locally\n\
+             - s3://bucket ./local --delete: Deletes local files missing in the bucket\n\n\
+             Swapped arguments or an empty/wrong source can wipe the destination.\n\n\
+             Preview what would be deleted:\n  \
+             aws s3 sync <src> <dest> --delete --dryrun\n\n\
+             Omit --delete for an additive-only sync:\n  \
+             aws s3 sync <src> <dest>"
+        ),
         // s3 rb (remove bucket)
         destructive_pattern!(
             "s3-rb",
@@ -314,6 +332,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::Severity;
     use crate::packs::test_helpers::*;
 
     #[test]
@@ -370,4 +389,28 @@ mod tests {
             "delete-log-stream",
         );
     }
+
+    #[test]
+    fn s3_sync_delete_blocks_in_either_direction() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "aws s3 sync ./local s3://bucket --delete",
+            "s3-sync-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws s3 sync s3://bucket/prefix ./local --delete --exact-timestamps",
+            "s3-sync-delete",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "aws s3 sync ./local s3://bucket --delete",
+            Severity::High,
+        );
+
+        assert_allows(&pack, "aws s3 sync ./local s3://bucket");
+        assert_allows(&pack, "aws s3 sync s3://bucket ./local --exclude '*.tmp'");
+        assert_allows(&pack, "aws s3 sync ./local s3://bucket --delete --dryrun");
+    }
 }
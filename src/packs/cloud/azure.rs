@@ -4,7 +4,8 @@
 //! - vm delete
 //! - storage account delete
 //! - sql server delete
-//! - group delete
+//! - group delete (Critical when --yes skips the confirmation prompt)
+//! - aks delete
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -98,12 +99,26 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Point-in-time restore possible within retention period\n\n\
              Create backup: az sql db export --name DB --server SRV --storage-uri URI"
         ),
-        // group delete (resource group)
+        // group delete --yes skips the confirmation prompt (often paired with --no-wait)
+        destructive_pattern!(
+            "group-delete-yes",
+            r"az\s+group\s+delete\b(?=.*\s(?:--yes|-y)(?=\s|$))",
+            "az group delete --yes deletes the resource group and ALL its resources without confirmation!",
+            Critical,
+            "group delete --yes removes the ENTIRE resource group with no prompt:\n\n\
+             - ALL resources in the group deleted\n\
+             - VMs, storage, databases, networks - everything\n\
+             - --yes skips the only confirmation step\n\
+             - --no-wait returns immediately while deletion continues in the background\n\n\
+             Review what would be removed first:\n  \
+             az resource list --resource-group NAME -o table"
+        ),
+        // group delete (resource group); az prompts for confirmation without --yes
         destructive_pattern!(
             "group-delete",
             r"az\s+group\s+delete",
             "az group delete removes the entire resource group and ALL resources within it!",
-            Critical,
+            High,
             "group delete removes ENTIRE resource group:\n\n\
              - ALL resources in the group deleted\n\
              - VMs, storage, databases, networks - everything\n\
@@ -231,8 +246,81 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::Severity;
     use crate::packs::test_helpers::*;
 
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "cloud.azure");
+        assert!(pack.keywords.contains(&"az"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn group_delete_with_yes_is_critical() {
+        let pack = create_pack();
+        for cmd in [
+            "az group delete --yes --no-wait --name prod-rg",
+            "az group delete --name prod-rg --yes --no-wait",
+            "az group delete -n prod-rg -y",
+            "az group delete -y -n prod-rg",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "group-delete-yes");
+            assert_blocks_with_severity(&pack, cmd, Severity::Critical);
+        }
+
+        assert_blocks_with_pattern(&pack, "az group delete --name prod-rg", "group-delete");
+        assert_blocks_with_severity(&pack, "az group delete --name prod-rg", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "az group delete --name yes-rg --no-wait",
+            "group-delete",
+        );
+    }
+
+    #[test]
+    fn resource_deletes_block() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "az vm delete -g rg -n vm1 --yes", "vm-delete");
+        assert_blocks_with_pattern(
+            &pack,
+            "az storage account delete -n acct -g rg",
+            "storage-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "az sql db delete -g rg -s srv -n db --yes",
+            "sql-delete",
+        );
+        assert_blocks_with_pattern(&pack, "az aks delete -g rg -n cluster", "aks-delete");
+        assert_blocks_with_severity(&pack, "az sql db delete -n db", Severity::Critical);
+    }
+
+    #[test]
+    fn read_only_commands_are_allowed() {
+        let pack = create_pack();
+        test_batch_allows(
+            &pack,
+            &[
+                "az account show",
+                "az group list",
+                "az vm list -o table",
+                "az group show --name prod-rg",
+                "az group show -n prod-rg --yes",
+            ],
+        );
+        assert_safe_pattern_matches(&pack, "az group show --name prod-rg");
+        // Not blocked even if the safe pattern were skipped
+        assert!(
+            pack.matches_destructive("az group show -n prod-rg --yes")
+                .is_none()
+        );
+    }
+
     #[test]
     fn acr_patterns_block() {
         let pack = create_pack();
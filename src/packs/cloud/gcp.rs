@@ -3,8 +3,9 @@
 //! This includes patterns for:
 //! - compute instances delete
 //! - sql instances delete
-//! - storage rm -r
+//! - gsutil rm -r (including the parallel `gsutil -m rm -r` form)
 //! - projects delete
+//! - container clusters delete
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -60,7 +61,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             "compute-delete",
             r"gcloud\s+compute\s+instances\s+delete",
             "gcloud compute instances delete permanently destroys VM instances.",
-            Critical,
+            High,
             "compute instances delete permanently destroys VMs:\n\n\
              - Instance is stopped and deleted\n\
              - Boot disk deleted (unless --keep-disks specified)\n\
@@ -93,10 +94,10 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - IP addresses released\n\n\
              Export data first: gcloud sql export sql INSTANCE gs://bucket/file.sql"
         ),
-        // gsutil rm -r
+        // gsutil rm -r/-R/--recursive, or a ** wildcard, after any top-level options (-m, -o ...)
         destructive_pattern!(
             "gsutil-rm-recursive",
-            r"gsutil\s+(?:-m\s+)?rm\s+.*-r|gsutil\s+(?:-m\s+)?rm\s+-[a-z]*r",
+            r"gsutil\s+(?:\S+\s+)*?rm\s+(?:\S+\s+)*?(?:-[a-zA-Z]*[rR][a-zA-Z]*\b|--recursive\b|\S*\*\*)",
             "gsutil rm -r permanently deletes all objects in the path.",
             Critical,
             "gsutil rm -r recursively deletes all objects:\n\n\
@@ -123,7 +124,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             "gke-delete",
             r"gcloud\s+container\s+clusters\s+delete",
             "gcloud container clusters delete removes the entire GKE cluster.",
-            Critical,
+            High,
             "container clusters delete removes the entire GKE cluster:\n\n\
              - All nodes and workloads terminated\n\
              - Persistent volumes may be deleted\n\
@@ -222,8 +223,102 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::Severity;
     use crate::packs::test_helpers::*;
 
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "cloud.gcp");
+        assert!(pack.keywords.contains(&"gcloud"));
+        assert!(pack.keywords.contains(&"gsutil"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn irreversible_deletes_are_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "gcloud projects delete my-project", "project-delete");
+        assert_blocks_with_severity(
+            &pack,
+            "gcloud projects delete my-project",
+            Severity::Critical,
+        );
+        assert_blocks_with_pattern(&pack, "gcloud sql instances delete prod-db", "sql-delete");
+        assert_blocks_with_severity(
+            &pack,
+            "gcloud sql instances delete prod-db --quiet",
+            Severity::Critical,
+        );
+    }
+
+    #[test]
+    fn compute_and_cluster_deletes_are_high() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "gcloud compute instances delete web-1 --zone us-central1-a",
+            "compute-delete",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "gcloud compute instances delete web-1",
+            Severity::High,
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "gcloud container clusters delete prod --region us-central1",
+            "gke-delete",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "gcloud container clusters delete prod",
+            Severity::High,
+        );
+    }
+
+    #[test]
+    fn gsutil_recursive_rm_blocks() {
+        let pack = create_pack();
+        for cmd in [
+            "gsutil rm -r gs://bucket/path",
+            "gsutil -m rm -r gs://bucket/**",
+            "gsutil -m rm -R gs://bucket",
+            "gsutil rm -rf gs://bucket/logs",
+            "gsutil -o GSUtil:parallel_thread_count=8 -m rm -r gs://bucket",
+            "gsutil rm -a -r gs://bucket/dir",
+            "gsutil rm --recursive gs://bucket/dir",
+            "gsutil -m rm gs://bucket/**",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "gsutil-rm-recursive");
+        }
+        assert_blocks_with_severity(&pack, "gsutil -m rm -r gs://bucket/**", Severity::Critical);
+
+        assert_no_match(&pack, "gsutil rm gs://bucket/file.txt");
+        assert_no_match(&pack, "gsutil rm gs://bucket/my-release/file.txt");
+    }
+
+    #[test]
+    fn read_only_commands_are_allowed() {
+        let pack = create_pack();
+        test_batch_allows(
+            &pack,
+            &[
+                "gcloud config list",
+                "gcloud compute instances list",
+                "gcloud sql instances describe prod-db",
+                "gsutil ls",
+                "gsutil ls -r gs://bucket/path/",
+            ],
+        );
+        assert_safe_pattern_matches(&pack, "gcloud config list");
+        assert_safe_pattern_matches(&pack, "gcloud compute instances list");
+        assert_safe_pattern_matches(&pack, "gsutil ls gs://bucket");
+    }
+
     #[test]
     fn container_registry_patterns_block() {
         let pack = create_pack();
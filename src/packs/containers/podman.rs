@@ -1,8 +1,8 @@
 //! Podman patterns - protections against destructive podman commands.
 //!
 //! This includes patterns for:
-//! - system prune (removes unused data)
-//! - rm/rmi with force flags
+//! - system prune (removes unused data; Critical with `-a`/`--all`)
+//! - rm/rmi with force or all flags, including grouped short flags (`rm -af`)
 //! - volume/pod prune
 //! - Similar to Docker but for Podman
 
@@ -47,6 +47,21 @@ fn create_safe_patterns() -> Vec<SafePattern> {
 
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        // system prune -a/--all - also removes every image without a container
+        destructive_pattern!(
+            "system-prune-all",
+            r"podman\s+system\s+prune\s+(?:\S+\s+)*?(?:-[a-zA-Z]*a[a-zA-Z]*|--all)(?=\s|$)",
+            "podman system prune -a removes ALL unused containers, pods, images, and build cache.",
+            Critical,
+            "podman system prune --all goes beyond the default cleanup: every image not used \
+             by an existing container is removed, not just dangling ones. Combined with a \
+             stopped workload this leaves nothing to restart from, and with --volumes it also \
+             deletes volume data.\n\n\
+             Safer alternatives:\n\
+             - podman system df: Preview disk usage first\n\
+             - podman system prune: Without -a, only dangling images are removed\n\
+             - podman image prune: Only remove dangling images"
+        ),
         // system prune - removes all unused data
         destructive_pattern!(
             "system-prune",
@@ -97,6 +112,21 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - podman pod rm <pod>: Remove specific pods\n\
              - podman pod start <pod>: Restart instead of removing"
         ),
+        // image prune -a/--all - removes every image not used by a container
+        destructive_pattern!(
+            "image-prune-all",
+            r"podman\s+image\s+prune\s+(?:\S+\s+)*?(?:-[a-zA-Z]*a[a-zA-Z]*|--all)(?=\s|$)",
+            "podman image prune --all removes every image not used by a container.",
+            High,
+            "podman image prune --all removes all images without at least one container \
+             associated with them, including tagged base images and local builds that were \
+             never pushed. Those must be re-pulled or rebuilt, which may not be possible for \
+             local-only images.\n\n\
+             Safer alternatives:\n\
+             - podman image prune: Without --all, only dangling images are removed\n\
+             - podman images: Review all images\n\
+             - podman rmi <image>: Remove specific images"
+        ),
         // image prune - removes unused images (Medium: only affects unused images)
         destructive_pattern!(
             "image-prune",
@@ -128,10 +158,24 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - podman rm <container>: Remove specific containers\n\
              - podman start <container>: Restart instead of removing"
         ),
+        // rm -af / --all --force (force remove every container)
+        destructive_pattern!(
+            "rm-all-force",
+            r"podman\s+rm\s+(?=(?:\S+\s+)*?(?:-[a-zA-Z]*a[a-zA-Z]*|--all)(?:\s|$))(?=(?:\S+\s+)*?(?:-[a-zA-Z]*f[a-zA-Z]*|--force)(?:\s|$))",
+            "podman rm -af forcibly removes EVERY container, running or not.",
+            Critical,
+            "podman rm --all --force kills every running container and removes it along with \
+             all stopped ones. Every workload on the host goes down at once, and any data \
+             written inside the containers (outside volumes) is lost.\n\n\
+             Safer alternatives:\n\
+             - podman ps -a: List all containers first\n\
+             - podman container prune: Only remove stopped containers\n\
+             - podman rm -f <container>: Remove specific containers"
+        ),
         // rm -f (force remove containers)
         destructive_pattern!(
             "rm-force",
-            r"podman\s+rm\s+.*-f|podman\s+rm\s+.*--force",
+            r"podman\s+rm\s+.*(?:-[a-zA-Z0-9]*f|--force)",
             "podman rm -f forcibly removes containers, potentially losing data.",
             High,
             "podman rm -f forcibly stops and removes containers. This is dangerous because:\n\n\
@@ -144,10 +188,24 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - podman rm <container>: Then remove\n\
              - podman ps: Check container status first"
         ),
+        // rmi -a/--all (remove every image)
+        destructive_pattern!(
+            "rmi-all",
+            r"podman\s+rmi\s+(?:\S+\s+)*?(?:-[a-zA-Z]*a[a-zA-Z]*|--all)(?=\s|$)",
+            "podman rmi -a removes ALL local images.",
+            High,
+            "podman rmi --all removes every image in local storage. Tagged base images and \
+             local builds that were never pushed are deleted, and with -f images in use by \
+             containers are removed too.\n\n\
+             Safer alternatives:\n\
+             - podman image prune: Only remove dangling images\n\
+             - podman images: Review images before removal\n\
+             - podman rmi <image>: Remove specific images"
+        ),
         // rmi -f (force remove images)
         destructive_pattern!(
             "rmi-force",
-            r"podman\s+rmi\s+.*-f|podman\s+rmi\s+.*--force",
+            r"podman\s+rmi\s+.*(?:-[a-zA-Z0-9]*f|--force)",
             "podman rmi -f forcibly removes images even if in use.",
             High,
             "podman rmi -f forcibly removes images, even if containers reference them. \
@@ -179,3 +237,71 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_full_prunes_are_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "podman system prune -a", "system-prune-all");
+        assert_blocks_with_pattern(&pack, "podman system prune --all", "system-prune-all");
+        assert_blocks_with_pattern(&pack, "podman system prune -af", "system-prune-all");
+        assert_blocks_with_pattern(
+            &pack,
+            "podman system prune --volumes -f -a",
+            "system-prune-all",
+        );
+        assert_blocks_with_severity(&pack, "podman system prune -a", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "podman system prune", "system-prune");
+        assert_blocks_with_severity(&pack, "podman system prune -f", Severity::High);
+
+        assert_blocks_with_pattern(&pack, "podman rm -af", "rm-all-force");
+        assert_blocks_with_pattern(&pack, "podman rm -fa", "rm-all-force");
+        assert_blocks_with_pattern(&pack, "podman rm --all --force", "rm-all-force");
+        assert_blocks_with_severity(&pack, "podman rm -af", Severity::Critical);
+    }
+
+    #[test]
+    fn test_image_removal() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "podman image prune --all", "image-prune-all");
+        assert_blocks_with_pattern(&pack, "podman image prune -af", "image-prune-all");
+        assert_blocks_with_pattern(&pack, "podman image prune", "image-prune");
+        assert_blocks_with_pattern(&pack, "podman rmi -a", "rmi-all");
+        assert_blocks_with_pattern(&pack, "podman rmi --all --force", "rmi-all");
+        assert_blocks_with_pattern(&pack, "podman rmi -f myimage", "rmi-force");
+        assert_blocks_with_pattern(&pack, "podman rmi -nf myimage", "rmi-force");
+
+        assert_allows(&pack, "podman rmi myimage");
+    }
+
+    #[test]
+    fn test_rm_force() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "podman rm -f web", "rm-force");
+        assert_blocks_with_pattern(&pack, "podman rm --force web", "rm-force");
+        assert_blocks_with_pattern(&pack, "podman rm -vf web", "rm-force");
+        assert_blocks_with_pattern(&pack, "podman volume prune", "volume-prune");
+
+        assert_allows(&pack, "podman rm web");
+        assert_allows(&pack, "podman rm -a");
+    }
+
+    #[test]
+    fn test_read_only_commands_allowed() {
+        let pack = create_pack();
+        test_batch_allows(
+            &pack,
+            &[
+                "podman ps -a",
+                "podman images",
+                "podman logs -f web",
+                "podman inspect web",
+            ],
+        );
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! This includes patterns for:
 //! - rm -rf outside temp directories (blocked)
+//! - rm -r of a `.git` directory (blocked, Critical)
+//! - rm -r rooted at a bare variable like `"$VAR/"` (becomes `/` when unset)
 //! - rm -rf in /tmp, /var/tmp, $TMPDIR (allowed)
 
 use crate::packs::{DestructivePattern, Pack, PatternSuggestion, Platform, SafePattern, Severity};
@@ -62,6 +64,18 @@ const RM_RF_GENERAL_SUGGESTIONS: &[PatternSuggestion] = &[
 ];
 
 /// Suggestions for `rm -r -f` (separate flags) pattern.
+/// Suggestions for `rm -r` rooted at a bare variable.
+const RM_RF_VAR_ROOT_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "rm -rf \"${VAR:?VAR is unset}/\"",
+        "Abort instead of expanding to / when the variable is unset or empty",
+    ),
+    PatternSuggestion::new(
+        "rm -rf \"$VAR/{subdir}\"",
+        "Delete a specific subdirectory rather than the variable's root",
+    ),
+];
+
 const RM_R_F_SEPARATE_SUGGESTIONS: &[PatternSuggestion] = &[
     PatternSuggestion::new(
         "rm -ri {path}",
@@ -103,6 +117,9 @@ const RM_RF_ROOT_HOME_NAME: &str = "rm-rf-root-home";
 const RM_RF_ROOT_HOME_REASON: &str = "rm -rf on root or home paths is EXTREMELY DANGEROUS. This command will NOT be executed. Ask the user to run it manually if truly needed.";
 const RM_RF_GENERAL_NAME: &str = "rm-rf-general";
 const RM_RF_GENERAL_REASON: &str = "rm -rf is destructive and requires human approval. Explain what you want to delete and why, then ask the user to run the command manually.";
+const RM_RF_VAR_ROOT_NAME: &str = "rm-rf-var-root";
+const RM_RF_VAR_ROOT_REASON: &str =
+    "rm -rf on a bare variable with a trailing slash deletes / if the variable is unset or empty.";
 const RM_R_F_SEPARATE_NAME: &str = "rm-r-f-separate";
 const RM_R_F_SEPARATE_REASON: &str =
     "rm with separate -r -f flags is destructive and requires human approval.";
@@ -327,12 +344,18 @@ fn parse_rm_segment(
         && !flag_state.saw_terminator
         && first_path.is_some_and(path_is_root_home);
 
+    let is_var_root = flag_state.style == RmFlagStyle::Combined
+        && !flag_state.saw_terminator
+        && paths.iter().any(path_is_bare_var_root);
+
     let (pattern_name, reason, severity) = if is_critical {
         (
             RM_RF_ROOT_HOME_NAME,
             RM_RF_ROOT_HOME_REASON,
             Severity::Critical,
         )
+    } else if is_var_root {
+        (RM_RF_VAR_ROOT_NAME, RM_RF_VAR_ROOT_REASON, Severity::High)
     } else {
         match flag_state.style {
             RmFlagStyle::Combined => (RM_RF_GENERAL_NAME, RM_RF_GENERAL_REASON, Severity::High),
@@ -449,6 +472,28 @@ fn path_is_root_home(path: &PathToken<'_>) -> bool {
     false
 }
 
+/// Whether `path` is a lone variable followed by `/` (e.g. `"$VAR/"`, `${VAR}/*`).
+///
+/// When the variable is unset or empty the path expands to `/`. Single-quoted
+/// paths are not expanded, and `${VAR:?}/` aborts instead, so neither counts.
+fn path_is_bare_var_root(path: &PathToken<'_>) -> bool {
+    if path.quote == QuoteKind::Single {
+        return false;
+    }
+
+    let text = path.unquoted.replace('"', "");
+    let text = text.strip_suffix('*').unwrap_or(&text);
+    let Some(name) = text.strip_prefix('$').and_then(|t| t.strip_suffix('/')) else {
+        return false;
+    };
+    let name = name
+        .strip_prefix('{')
+        .and_then(|n| n.strip_suffix('}'))
+        .unwrap_or(name);
+
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
 /// Create the core filesystem pack.
 #[must_use]
 pub fn create_pack() -> Pack {
@@ -625,6 +670,41 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              find /path/to/directory -type f | head -20",
             RM_RF_ROOT_HOME_SUGGESTIONS
         ),
+        // Deleting a .git directory destroys the repository's entire history (CRITICAL)
+        destructive_pattern!(
+            "rm-rf-git-dir",
+            r#"\brm\s+(?:[^;&|\s]+\s+)*?(?:-[a-zA-Z]*[rR][a-zA-Z]*|--recursive)\s+(?:[^;&|\s]+\s+)*?['"]?(?:[^;&|\s'"]*/)?\.git/?['"]?(?:\s|$|[;&|)])"#,
+            "rm -r on a .git directory deletes the repository's entire history. This command will NOT be executed. Ask the user to run it manually if truly needed.",
+            Critical,
+            "The .git directory IS the repository: every commit, branch, tag, stash, \
+             reflog entry, and local config lives there. Deleting it leaves only the \
+             files currently checked out.\n\n\
+             What gets destroyed:\n\
+             - All local commits that were never pushed\n\
+             - All local branches, stashes, and the reflog\n\
+             - Hooks and repository-specific configuration\n\n\
+             There is no recovery unless the history was pushed to a remote.\n\n\
+             If you want a fresh history, keep a backup first:\n  \
+             mv .git /tmp/old-git-backup\n\n\
+             Check what would be lost:\n  \
+             git log --branches --not --remotes --oneline"
+        ),
+        // rm -rf "$VAR/" - an unset variable turns this into rm -rf / (the Steam bug)
+        destructive_pattern!(
+            "rm-rf-var-root",
+            r#"\brm\s+(?:-[a-zA-Z]*[rR][a-zA-Z]*f[a-zA-Z]*|-[a-zA-Z]*f[a-zA-Z]*[rR][a-zA-Z]*)\s+(?:[^;&|\s]+\s+)*?"?\$\{?\w+\}?/"?\*?(?=\s|$|[;&|)])"#,
+            "rm -rf on a bare variable with a trailing slash deletes / if the variable is unset or empty.",
+            High,
+            "The path is nothing but a variable followed by a slash. If the variable is \
+             unset or empty (a typo, a failed cd, a missing export), \"$VAR/\" expands to \
+             \"/\" and the command becomes rm -rf / - the bug that wiped home directories \
+             in a Steam update script.\n\n\
+             Make the shell abort when the variable is empty:\n  \
+             rm -rf \"${VAR:?VAR is unset}/\"\n\n\
+             Or delete a specific subdirectory instead of the variable's root:\n  \
+             rm -rf \"$VAR/build\"",
+            RM_RF_VAR_ROOT_SUGGESTIONS
+        ),
         // General rm -rf (caught after safe patterns) - High because temp paths are allowed
         destructive_pattern!(
             "rm-rf-general",
@@ -688,6 +768,19 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              find /path --maxdepth 2 -ls | head -30",
             RM_RECURSIVE_FORCE_SUGGESTIONS
         ),
+        // rm -r "$VAR/" without -f: same hazard, but rm still prompts on write-protected files (warn)
+        destructive_pattern!(
+            "rm-r-var-root",
+            r#"\brm\s+(?:-[a-zA-Z]+\s+)*?(?:-[a-zA-Z]*[rR][a-zA-Z]*|--recursive)\s+(?:[^;&|\s]+\s+)*?"?\$\{?\w+\}?/"?\*?(?=\s|$|[;&|)])"#,
+            "rm -r on a bare variable with a trailing slash deletes / if the variable is unset or empty.",
+            Medium,
+            "The path is nothing but a variable followed by a slash. If the variable is \
+             unset or empty, \"$VAR/\" expands to \"/\" and rm -r starts deleting from the \
+             filesystem root.\n\n\
+             Make the shell abort when the variable is empty:\n  \
+             rm -r \"${VAR:?VAR is unset}/\"",
+            RM_RF_VAR_ROOT_SUGGESTIONS
+        ),
     ]
 }
 
@@ -715,6 +808,22 @@ mod tests {
         assert_blocks_with_pattern(&pack, "rm -rf /", "rm-rf-root-home");
     }
 
+    #[test]
+    fn test_rm_rf_git_dir_critical() {
+        let pack = create_pack();
+        assert_blocks_with_severity(&pack, "rm -rf .git", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "rm -rf .git", "rm-rf-git-dir");
+        assert_blocks_with_pattern(&pack, "rm -rf ./.git/", "rm-rf-git-dir");
+        assert_blocks_with_pattern(&pack, "rm -r -f project/.git", "rm-rf-git-dir");
+        assert_blocks_with_pattern(&pack, "rm -fr \".git\" && git init", "rm-rf-git-dir");
+        assert_blocks_with_pattern(&pack, "rm --recursive .git", "rm-rf-git-dir");
+
+        // Similar names are not the .git directory
+        assert_blocks_with_pattern(&pack, "rm -rf .github", "rm-rf-general");
+        assert_blocks_with_pattern(&pack, "rm -rf .gitignore", "rm-rf-general");
+        assert_allows(&pack, "rm .git/index.lock");
+    }
+
     #[test]
     fn test_rm_rf_general_high() {
         let pack = create_pack();
@@ -858,6 +967,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rm_bare_variable_trailing_slash() {
+        let pack = create_pack();
+        for cmd in [
+            r#"rm -rf "$STEAMROOT/""#,
+            "rm -rf $STEAMROOT/",
+            r#"rm -rf "${STEAMROOT}/""#,
+            r#"rm -rf "$STEAMROOT/"*"#,
+            r#"rm -fr ./cache "$BUILD_DIR/""#,
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "rm-rf-var-root");
+            assert_rm_parser_denies(cmd, RM_RF_VAR_ROOT_NAME, Severity::High);
+        }
+
+        assert_blocks_with_pattern(&pack, r#"rm -r "$STEAMROOT/""#, "rm-r-var-root");
+        assert_blocks_with_severity(&pack, r#"rm -r "$STEAMROOT/""#, Severity::Medium);
+        assert_rm_parser_no_match(r#"rm -r "$STEAMROOT/""#);
+
+        // A specific path under the variable is an ordinary rm -rf
+        assert_blocks_with_pattern(&pack, r#"rm -rf "$VAR/subdir/specific""#, "rm-rf-general");
+        assert_rm_parser_denies(
+            r#"rm -rf "$VAR/subdir/specific""#,
+            RM_RF_GENERAL_NAME,
+            Severity::High,
+        );
+        assert_no_match(&pack, r#"rm -r "$VAR/subdir/specific""#);
+        // ${VAR:?msg} aborts on unset, and single quotes never expand
+        assert_no_match(&pack, r#"rm -r "${VAR:?VAR is unset}/""#);
+        // Split so the literal doesn't read as a `{VAR:?}` format argument
+        assert_no_match(&pack, concat!(r#"rm -r "${VAR"#, r#":?}/""#));
+        assert_rm_parser_denies(
+            r#"rm -rf "${VAR:?VAR is unset}/""#,
+            RM_RF_GENERAL_NAME,
+            Severity::High,
+        );
+        assert_rm_parser_denies("rm -rf '$VAR/'", RM_RF_GENERAL_NAME, Severity::High);
+        // $TMPDIR/ stays on the temp-dir allow path
+        assert_rm_parser_allows("rm -rf $TMPDIR/");
+    }
+
     #[test]
     fn test_rm_parser_option_terminator() {
         assert_rm_parser_no_match("rm -- -rf /tmp/safe");
@@ -3,7 +3,10 @@
 //! This includes patterns for:
 //! - Work destruction (reset --hard, checkout --, restore)
 //! - History rewriting (push --force, branch -D)
+//! - Tag and note deletion (tag -d, push :refs/tags/..., notes remove)
 //! - Stash destruction (stash drop, stash clear)
+//! - Worktree removal (worktree remove --force, worktree prune)
+//! - Submodule removal (submodule deinit --force)
 
 use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -48,6 +51,11 @@ fn create_safe_patterns() -> Vec<SafePattern> {
             r"git\s+(?:\S+\s+)*clean\s+-[a-z]*n[a-z]*"
         ),
         safe_pattern!("clean-dry-run-long", r"git\s+(?:\S+\s+)*clean\s+--dry-run"),
+        // Submodule inspection and checkout; anchored so a chained command is still checked
+        safe_pattern!(
+            "submodule-status-update",
+            r"^git\s+(?:[^;&|\s]+\s+)*submodule\s+(?:status|update)\b[^;&|]*$"
+        ),
     ]
 }
 
@@ -60,12 +68,12 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
     // - Low: Log only
 
     vec![
-        // checkout -- discards uncommitted changes
+        // checkout -- . (or another repo-wide pathspec) discards uncommitted changes
         destructive_pattern!(
             "checkout-discard",
-            r"git\s+(?:\S+\s+)*checkout\s+--\s+",
+            r"git\s+(?:\S+\s+)*checkout(?!\s+--(?:\s+(?!(?:\./?|:/|\*)(?:[\s;&|)]|$))[^\s;&|)]+)+\s*(?:[;&|)]|$))\s+--\s+",
             "git checkout -- discards uncommitted changes permanently. Use 'git stash' first.",
-            High,
+            Critical,
             "git checkout -- <path> discards all uncommitted changes to the specified files \
              in your working directory. These changes are permanently lost - they cannot be \
              recovered because they were never committed.\n\n\
@@ -86,6 +94,31 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                 ]
             }
         ),
+        // checkout -- <specific paths> only discards changes to those paths
+        destructive_pattern!(
+            "checkout-discard-pathspec",
+            r"git\s+(?:\S+\s+)*checkout\s+--(?:\s+(?!(?:\./?|:/|\*)(?:[\s;&|)]|$))[^\s;&|)]+)+\s*(?:[;&|)]|$)",
+            "git checkout -- discards uncommitted changes to the listed paths. Use 'git stash' first.",
+            High,
+            "git checkout -- <path> discards uncommitted changes to the listed files. Other \
+             files are untouched, but changes to these paths were never committed and cannot \
+             be recovered.\n\n\
+             Safer alternatives:\n\
+             - git stash push -- <path>: Save changes to just these paths\n\
+             - git diff -- <path>: Review what would be lost before discarding",
+            &const {
+                [
+                    PatternSuggestion::new(
+                        "git stash push -- {path}",
+                        "Save changes to just these paths",
+                    ),
+                    PatternSuggestion::new(
+                        "git diff -- {path}",
+                        "Review what would be lost before discarding",
+                    ),
+                ]
+            }
+        ),
         destructive_pattern!(
             "checkout-ref-discard",
             r"git\s+(?:\S+\s+)*checkout\s+(?!-b\b)(?!--orphan\b)[^\s]+\s+--\s+",
@@ -115,6 +148,83 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                 ]
             }
         ),
+        // Without `--`, `git checkout <arg>` either switches branches or discards
+        // changes to a path. Refnames cannot start with '.', end with '/', or be
+        // followed by a second operand, so those forms are treated as pathspecs.
+        destructive_pattern!(
+            "checkout-dot",
+            r"git\s+(?:\S+\s+)*checkout\s+(?:\./?|:/|\*)(?=\s*(?:[;&|)]|$))",
+            "git checkout . discards all uncommitted changes in the current directory. Use 'git stash' first.",
+            Critical,
+            "git checkout . restores every file under the current directory from the index, \
+             discarding all uncommitted changes. '.' can never name a branch, so this is \
+             always a discard, not a branch switch.\n\n\
+             Safer alternatives:\n\
+             - git stash: Save changes temporarily, restore later with 'git stash pop'\n\
+             - git diff: Review what would be lost before discarding",
+            &const {
+                [
+                    PatternSuggestion::new(
+                        "git stash",
+                        "Save changes temporarily, restore later with 'git stash pop'",
+                    ),
+                    PatternSuggestion::new(
+                        "git diff",
+                        "Review what would be lost before discarding",
+                    ),
+                ]
+            }
+        ),
+        destructive_pattern!(
+            "checkout-ref-pathspec",
+            r"git\s+(?:\S+\s+)*checkout\s+(?!-)[^\s;&|)<>]+\s+(?!-)[^\s;&|)<>]+(?=[\s;&|)]|$)",
+            "git checkout <ref> <path> overwrites the listed files. Use 'git stash' first.",
+            High,
+            "git checkout <ref> <path> (for example 'git checkout HEAD src/main.rs') is the \
+             same as 'git checkout <ref> -- <path>': the listed files are replaced with their \
+             versions from <ref> and uncommitted changes to them are lost. A branch switch \
+             takes a single operand, so a second operand means a pathspec.\n\n\
+             Safer alternatives:\n\
+             - git stash push -- <path>: Save changes to just these paths\n\
+             - git show <ref>:<path>: View the file content without overwriting",
+            &const {
+                [
+                    PatternSuggestion::new(
+                        "git stash push -- {path}",
+                        "Save changes to just these paths",
+                    ),
+                    PatternSuggestion::new(
+                        "git show {ref}:{path}",
+                        "View the file content without overwriting",
+                    ),
+                ]
+            }
+        ),
+        destructive_pattern!(
+            "checkout-path-like",
+            r"git\s+(?:\S+\s+)*checkout\s+(?:\.[^\s;&|)<>]*|[^\s;&|)<>\-][^\s;&|)<>]*(?:\.[A-Za-z][A-Za-z0-9]{0,7}|/))(?=[\s;&|)]|$)",
+            "git checkout <path> discards uncommitted changes to that path if it is not a branch.",
+            Medium,
+            "The argument looks like a file path (it starts with '.', ends with '/', or has a \
+             file extension). If it is a path rather than a branch, git checkout silently \
+             replaces the file with the staged version and uncommitted changes are lost.\n\n\
+             Be explicit instead:\n\
+             - git switch <branch>: Switch branches; never touches files\n\
+             - git restore <path>: Discard changes to a path on purpose\n\
+             - git diff -- <path>: Review what would be lost first",
+            &const {
+                [
+                    PatternSuggestion::new(
+                        "git switch {branch}",
+                        "Switch branches without any risk of discarding file changes",
+                    ),
+                    PatternSuggestion::new(
+                        "git diff -- {path}",
+                        "Review what would be lost before discarding",
+                    ),
+                ]
+            }
+        ),
         // restore without --staged affects working tree
         destructive_pattern!(
             "restore-worktree",
@@ -178,7 +288,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // reset --hard destroys uncommitted work (CRITICAL - extremely common mistake)
         destructive_pattern!(
             "reset-hard",
-            r"git\s+(?:\S+\s+)*reset\s+--hard",
+            r"git\s+(?:\S+\s+)*reset\s+--hard(?!(?:\s+[^\s;&|)-][^\s;&|)]*)?\s+--(?:\s+(?!(?:\./?|:/|\*)(?:[\s;&|)]|$))[^\s;&|)]+)+\s*(?:[;&|)]|$))",
             "git reset --hard destroys uncommitted changes. Use 'git stash' first.",
             Critical,
             "git reset --hard discards ALL uncommitted changes in your working directory \
@@ -214,6 +324,19 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                 ]
             }
         ),
+        // reset --hard -- <specific paths> is limited to those paths
+        destructive_pattern!(
+            "reset-hard-pathspec",
+            r"git\s+(?:\S+\s+)*reset\s+--hard(?:\s+[^\s;&|)-][^\s;&|)]*)?\s+--(?:\s+(?!(?:\./?|:/|\*)(?:[\s;&|)]|$))[^\s;&|)]+)+\s*(?:[;&|)]|$)",
+            "git reset --hard -- <path> targets only the listed paths. Use 'git stash' first.",
+            High,
+            "git reset --hard with a pathspec is scoped to the listed files rather than the \
+             whole repository (recent git versions refuse it outright; use git restore). \
+             Uncommitted changes to those paths would still be lost.\n\n\
+             Safer alternatives:\n\
+             - git restore --staged <path>: Unstage without touching the working tree\n\
+             - git stash push -- <path>: Save changes to just these paths"
+        ),
         destructive_pattern!(
             "reset-merge",
             r"git\s+(?:\S+\s+)*reset\s+--merge",
@@ -276,10 +399,37 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                 ]
             }
         ),
-        // force push can destroy remote history (CRITICAL - affects shared history)
+        // --force alongside --force-with-lease: the unconditional force wins
+        destructive_pattern!(
+            "push-force-with-lease-overridden",
+            r#"git\s+(?:\S+\s+)*push\s+(?=(?:[^;&|'"]|'[^']*'|"[^"]*")*--force-with-lease)(?=(?:[^;&|'"]|'[^']*'|"[^"]*")*(?:--force(?![-a-z])|\s-f\b))"#,
+            "--force overrides --force-with-lease, so the lease check is skipped. Drop --force.",
+            Critical,
+            "When git push is given both --force and --force-with-lease, the plain --force \
+             wins: git pushes unconditionally and never compares the remote ref against \
+             your last fetch. The lease gives no protection, and commits others pushed \
+             can be destroyed exactly as with a bare --force.\n\n\
+             Safer alternative:\n\
+             - git push --force-with-lease: Only forces if remote matches your last fetch",
+            &const {
+                [
+                    PatternSuggestion::new(
+                        "git push --force-with-lease",
+                        "Keep only the lease so the remote is checked before overwriting",
+                    ),
+                    PatternSuggestion::new(
+                        "git push --force-with-lease --force-if-includes",
+                        "Even safer: also checks that your local ref includes the remote ref",
+                    ),
+                ]
+            }
+        ),
+        // force push can destroy remote history (CRITICAL - affects shared history).
+        // The flag must be in the push's own segment; quoted option values
+        // (`-o "a;b"`) are skipped whole so a separator inside them does not end it.
         destructive_pattern!(
             "push-force-long",
-            r"git\s+(?:\S+\s+)*push\s+.*--force(?![-a-z])",
+            r#"git\s+(?:\S+\s+)*push\s+(?:[^;&|'"]|'[^']*'|"[^"]*")*--force(?![-a-z])"#,
             "Force push can destroy remote history. Use --force-with-lease if necessary.",
             Critical,
             "git push --force overwrites remote history with your local history. This can \
@@ -312,7 +462,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         ),
         destructive_pattern!(
             "push-force-short",
-            r"git\s+(?:\S+\s+)*push\s+.*-f\b",
+            r#"git\s+(?:\S+\s+)*push\s+(?:[^;&|'"]|'[^']*'|"[^"]*")*-f\b"#,
             "Force push (-f) can destroy remote history. Use --force-with-lease if necessary.",
             Critical,
             "git push -f (short for --force) overwrites remote history with your local history. \
@@ -375,6 +525,61 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                 ]
             }
         ),
+        // tag -d deletes a local tag (Medium: the tagged commit remains, the marker is gone)
+        destructive_pattern!(
+            "tag-delete",
+            r"git\s+(?:[^;&|\s]+\s+)*tag\s+(?:[^;&|\s]+\s+)*(?:-d|--delete)(?=\s|$)",
+            "git tag -d deletes a tag, often the only marker of a released version.",
+            Medium,
+            "git tag -d removes a local tag. The tagged commit is not deleted, but the name \
+             that marked a shipped version is, and annotated tags carry a message and \
+             signature that are lost with it. If the tag was never pushed, it cannot be \
+             restored from a remote.\n\n\
+             Safer alternatives:\n\
+             - git tag -l: List tags first\n\
+             - git show <tag>: Record the commit and message before deleting\n\n\
+             Recovery if needed:\n\
+               git fsck --unreachable | grep tag  # annotated tags only\n\
+               git tag <name> <commit>",
+            &const {
+                [
+                    PatternSuggestion::new("git show {tag}", "Record what the tag points to first"),
+                    PatternSuggestion::new("git tag -l", "List existing tags"),
+                ]
+            }
+        ),
+        // Deleting a tag on the remote removes the release marker for everyone (High)
+        destructive_pattern!(
+            "push-delete-tag",
+            r"git\s+(?:[^;&|\s]+\s+)*push\b[^;&|]*?(?:\s\+?:refs/tags/|\s(?:--delete|-d)\s+(?:[^;&|\s]+\s+)*(?:refs/tags/|tag\s))",
+            "Pushing a tag deletion removes a release tag from the remote for everyone.",
+            High,
+            "A refspec with an empty source (`:refs/tags/v1.0`) or `--delete tag v1.0` \
+             deletes the tag on the remote. Release tooling, package registries, and \
+             anyone who fetches will lose the marker for that version, and re-creating \
+             it on a different commit silently changes what the version means.\n\n\
+             Safer alternatives:\n\
+             - git ls-remote --tags origin: Check which tags exist remotely\n\
+             - Create a new tag instead of moving or deleting a published one",
+            &const {
+                [PatternSuggestion::new(
+                    "git ls-remote --tags {remote}",
+                    "List remote tags before deleting",
+                )]
+            }
+        ),
+        // notes remove discards note metadata attached to commits (Low)
+        destructive_pattern!(
+            "notes-remove",
+            r"git\s+(?:\S+\s+)*notes\s+(?:-\S+\s+)*remove\b",
+            "git notes remove discards the notes attached to a commit.",
+            Low,
+            "git notes remove deletes the note attached to each given object. Notes often \
+             hold review, CI, or release metadata that is not stored anywhere else.\n\n\
+             Safer alternatives:\n\
+             - git notes show <commit>: Review the note first\n\
+             - git notes edit <commit>: Change a note instead of removing it"
+        ),
         // stash destruction (Medium: single stash, recoverable via fsck/unreachable objects)
         destructive_pattern!(
             "stash-drop",
@@ -444,6 +649,86 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
                 ]
             }
         ),
+        // worktree remove --force deletes a worktree even with uncommitted changes
+        destructive_pattern!(
+            "worktree-remove-force",
+            r"git\s+(?:\S+\s+)*worktree\s+remove\s+(?:.*\s)?(?:--force\b|-f\b)",
+            "git worktree remove --force deletes a worktree including its uncommitted changes.",
+            High,
+            "git worktree remove refuses to delete a worktree that has uncommitted or \
+             untracked changes. --force (or -f) overrides that check and deletes the \
+             directory anyway; passing it twice also removes locked worktrees.\n\n\
+             What gets destroyed:\n\
+             - Modified and untracked files in the worktree directory\n\
+             - Commits are safe (they live in the shared repository)\n\n\
+             Safer alternatives:\n\
+             - git -C <path> status: Check the worktree for changes first\n\
+             - git worktree remove <path>: Fails safely if there are changes",
+            &const {
+                [
+                    PatternSuggestion::new(
+                        "git -C {path} status",
+                        "Check the worktree for uncommitted changes first",
+                    ),
+                    PatternSuggestion::new(
+                        "git worktree remove {path}",
+                        "Remove without --force; refuses if there are changes",
+                    ),
+                ]
+            }
+        ),
+        // submodule deinit --force discards local changes in the submodule working tree
+        destructive_pattern!(
+            "submodule-deinit-force",
+            r"git\s+(?:[^;&|\s]+\s+)*submodule\s+deinit\s+(?:[^;&|]*\s)?(?:--force\b|-f\b)",
+            "git submodule deinit --force removes submodule working trees including their local changes.",
+            High,
+            "git submodule deinit unregisters a submodule and empties its working tree. \
+             Without --force it refuses when the submodule has local modifications; \
+             --force (or -f) removes the working tree anyway. With --all every \
+             submodule in the repository is removed at once.\n\n\
+             What gets destroyed:\n\
+             - Uncommitted and untracked files inside each submodule\n\
+             - Commits that were never pushed from the submodule (if its git dir is later pruned)\n\n\
+             Safer alternatives:\n\
+             - git submodule foreach git status: Check every submodule for changes first\n\
+             - git submodule deinit <path>: Fails safely if there are changes",
+            &const {
+                [
+                    PatternSuggestion::new(
+                        "git submodule foreach git status",
+                        "Check each submodule for uncommitted changes first",
+                    ),
+                    PatternSuggestion::new(
+                        "git submodule deinit {path}",
+                        "Deinit without --force; refuses if there are changes",
+                    ),
+                ]
+            }
+        ),
+        // worktree prune drops metadata for worktrees whose directories are missing (Medium)
+        destructive_pattern!(
+            "worktree-prune",
+            r"git\s+(?:\S+\s+)*worktree\s+prune\b(?!.*(?:--dry-run\b|\s-n\b))",
+            "git worktree prune forgets worktrees whose directories are missing. Preview with --dry-run.",
+            Medium,
+            "git worktree prune removes administrative data for worktrees whose directories \
+             no longer exist. A worktree on an unmounted drive or moved directory looks \
+             missing and will be forgotten, along with its checked-out branch association.\n\n\
+             Safer alternatives:\n\
+             - git worktree prune --dry-run: Preview what would be pruned\n\
+             - git worktree lock <path>: Protect worktrees on removable media\n\
+             - git worktree repair: Fix moved worktrees instead of pruning them",
+            &const {
+                [
+                    PatternSuggestion::new(
+                        "git worktree prune --dry-run",
+                        "Preview which worktrees would be pruned",
+                    ),
+                    PatternSuggestion::new("git worktree list", "Review all worktrees first"),
+                ]
+            }
+        ),
     ]
 }
 
@@ -522,6 +807,65 @@ mod tests {
             "git push --force origin main",
             "destroy remote history",
         );
+        // Separators inside quoted option values do not end the push segment
+        assert_blocks_with_pattern(
+            &pack,
+            r#"git push -o "a;b" --force origin main"#,
+            "push-force-long",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git push -o 'x|y' -f origin main",
+            "push-force-short",
+        );
+    }
+
+    #[test]
+    fn test_push_force_with_lease_combined_with_force() {
+        let pack = create_pack();
+
+        assert_blocks_with_severity(
+            &pack,
+            "git push --force-with-lease --force origin main",
+            Severity::Critical,
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git push --force --force-with-lease origin main",
+            "push-force-with-lease-overridden",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git push --force-with-lease -f origin main",
+            "push-force-with-lease-overridden",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git push --force-with-lease --force origin main && rm -f build.log",
+            "push-force-with-lease-overridden",
+        );
+    }
+
+    #[test]
+    fn test_push_force_with_lease_allowed() {
+        let pack = create_pack();
+
+        test_batch_allows(
+            &pack,
+            &[
+                "git push --force-with-lease",
+                "git push --force-with-lease origin main",
+                "git push --force-with-lease --force-if-includes origin main",
+                "git push --force-with-lease=main origin main",
+                "git push --force-with-lease=main:abc1234 origin main",
+                // An empty expected value requires the remote ref to not exist yet
+                "git push --force-with-lease=main: origin main",
+                "git push --force-with-lease=refs/heads/main:'' origin main",
+                // A later segment's -f/--force does not belong to this push
+                "git push --force-with-lease origin main && rm -f build.log",
+                "git push --force-with-lease origin main; cp --force a b",
+            ],
+        );
     }
 
     #[test]
@@ -540,11 +884,94 @@ mod tests {
     fn test_checkout_discard_high() {
         let pack = create_pack();
 
-        assert_blocks_with_severity(&pack, "git checkout -- file.txt", Severity::High);
-        assert_blocks_with_pattern(&pack, "git checkout -- file.txt", "checkout-discard");
+        assert_blocks_with_severity(&pack, "git checkout -- .", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "git checkout -- .", "checkout-discard");
+        assert_blocks_with_pattern(&pack, "git checkout -- :/", "checkout-discard");
+        assert_blocks_with_pattern(&pack, "git checkout -- src .", "checkout-discard");
         assert_blocks(&pack, "git checkout -- .", "discards uncommitted changes");
     }
 
+    #[test]
+    fn test_checkout_pathspec_vs_branch_switch() {
+        let pack = create_pack();
+
+        assert_blocks_with_pattern(
+            &pack,
+            "git checkout -- src/main.rs",
+            "checkout-discard-pathspec",
+        );
+        assert_blocks_with_pattern(&pack, "git checkout .", "checkout-dot");
+        assert_blocks_with_severity(&pack, "git checkout .", Severity::Critical);
+        assert_blocks_with_pattern(
+            &pack,
+            "git checkout HEAD src/main.rs",
+            "checkout-ref-pathspec",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git checkout main README.md docs/",
+            "checkout-ref-pathspec",
+        );
+        assert_blocks_with_severity(&pack, "git checkout HEAD src/main.rs", Severity::High);
+        for path in ["src/main.rs", "./src", "src/", ".env", "../Cargo.toml"] {
+            let command = format!("git checkout {path}");
+            assert_blocks_with_pattern(&pack, &command, "checkout-path-like");
+            assert_blocks_with_severity(&pack, &command, Severity::Medium);
+        }
+
+        test_batch_allows(
+            &pack,
+            &[
+                "git checkout main",
+                "git checkout feature/login",
+                "git checkout release-2.0",
+                "git checkout v1.2.3",
+                "git checkout HEAD~1",
+                "git checkout -",
+                "git checkout main && cargo test",
+                "git checkout main 2>/dev/null",
+                "git checkout -b feature/new origin/main",
+                "git checkout --track origin/feature",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_pathspec_scoped_discards_are_high() {
+        let pack = create_pack();
+
+        assert_blocks_with_severity(&pack, "git checkout -- file.txt", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "git checkout -- file.txt",
+            "checkout-discard-pathspec",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git checkout -- src/a.rs src/b.rs",
+            "checkout-discard-pathspec",
+        );
+
+        assert_blocks_with_severity(&pack, "git reset --hard -- file.txt", Severity::High);
+        assert_blocks_with_pattern(&pack, "git reset --hard -- file.txt", "reset-hard-pathspec");
+        assert_blocks_with_pattern(
+            &pack,
+            "git reset --hard HEAD -- file.txt",
+            "reset-hard-pathspec",
+        );
+
+        // Repo-wide pathspecs and unscoped forms keep their full severity.
+        assert_blocks_with_severity(&pack, "git reset --hard", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "git reset --hard -- .", "reset-hard");
+        assert_blocks_with_pattern(&pack, "git reset --hard HEAD -- *", "reset-hard");
+        // A scoped reset chained with an unscoped one is still Critical.
+        assert_blocks_with_pattern(
+            &pack,
+            "git reset --hard -- file.txt && git reset --hard",
+            "reset-hard",
+        );
+    }
+
     #[test]
     fn test_restore_worktree_high() {
         let pack = create_pack();
@@ -568,6 +995,67 @@ mod tests {
         assert_blocks_with_pattern(&pack, "git branch -f feature", "branch-force-delete");
     }
 
+    #[test]
+    fn test_tag_delete_medium() {
+        let pack = create_pack();
+
+        assert_blocks_with_severity(&pack, "git tag -d v1.0.0", Severity::Medium);
+        assert_blocks_with_pattern(&pack, "git tag --delete v1.0.0 v1.0.1", "tag-delete");
+        assert_blocks_with_pattern(&pack, "git -C repo tag -d v1.0.0", "tag-delete");
+
+        assert_allows(&pack, "git tag");
+        assert_allows(&pack, "git tag -l 'v1.*'");
+        assert_allows(&pack, "git tag -a v1.0.0 -m 'Release 1.0.0'");
+        assert_allows(&pack, "git tag v1.0.0-dev");
+        // -d after a shell separator belongs to another command
+        assert_allows(&pack, "git tag v1; rm -d x");
+    }
+
+    #[test]
+    fn test_push_delete_tag_high() {
+        let pack = create_pack();
+
+        assert_blocks_with_severity(&pack, "git push origin :refs/tags/v1.0.0", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "git push origin main :refs/tags/v1.0.0",
+            "push-delete-tag",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git push --delete origin tag v1.0.0",
+            "push-delete-tag",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git push origin --delete refs/tags/v1.0.0",
+            "push-delete-tag",
+        );
+
+        assert_allows(&pack, "git push origin v1.0.0");
+        assert_allows(&pack, "git push origin refs/tags/v1.0.0:refs/tags/v1.0.0");
+        assert_allows(&pack, "git push --tags");
+        // A tag deletion in a later segment is not a push
+        assert_blocks_with_pattern(
+            &pack,
+            "git push origin main && git tag -d old",
+            "tag-delete",
+        );
+        assert_allows(&pack, "git push origin main && echo -d tag v1");
+        assert_allows(&pack, "git push origin main; echo :refs/tags/v1");
+    }
+
+    #[test]
+    fn test_notes_remove_low() {
+        let pack = create_pack();
+
+        assert_blocks_with_severity(&pack, "git notes remove HEAD", Severity::Low);
+        assert_blocks_with_pattern(&pack, "git notes --ref=ci remove abc123", "notes-remove");
+
+        assert_allows(&pack, "git notes add -m 'reviewed' HEAD");
+        assert_allows(&pack, "git notes show HEAD");
+    }
+
     #[test]
     fn test_stash_drop_medium() {
         // Stash drop is Medium severity (recoverable via fsck)
@@ -577,6 +1065,95 @@ mod tests {
         assert_blocks(&pack, "git stash drop stash@{0}", "Recoverable");
     }
 
+    #[test]
+    fn test_worktree_remove_force_high() {
+        let pack = create_pack();
+
+        assert_blocks_with_severity(&pack, "git worktree remove --force ../wt", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "git worktree remove --force ../wt",
+            "worktree-remove-force",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git worktree remove ../wt -f",
+            "worktree-remove-force",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git worktree remove -f -f ../locked-wt",
+            "worktree-remove-force",
+        );
+        assert_allows(&pack, "git worktree remove ../wt");
+    }
+
+    #[test]
+    fn test_submodule_deinit_force_high() {
+        let pack = create_pack();
+
+        assert_blocks_with_severity(&pack, "git submodule deinit --force --all", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "git submodule deinit -f libs/vendor",
+            "submodule-deinit-force",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git -C repo submodule deinit --all --force",
+            "submodule-deinit-force",
+        );
+        assert_allows(&pack, "git submodule deinit libs/vendor");
+        // --force after a shell separator belongs to another command
+        assert_allows(&pack, "git submodule deinit sub && cp --force a b");
+        assert_allows(&pack, "git submodule deinit sub; rm -f stale.lock");
+    }
+
+    #[test]
+    fn test_submodule_status_update_safe() {
+        let pack = create_pack();
+
+        assert_safe_pattern_matches(&pack, "git submodule status");
+        assert_safe_pattern_matches(&pack, "git submodule update --init --recursive");
+        assert_allows(&pack, "git submodule update --remote");
+        // A safe submodule command does not mask a destructive one in the same line
+        assert_blocks_with_pattern(
+            &pack,
+            "git submodule status && git submodule deinit --force --all",
+            "submodule-deinit-force",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git reset --hard; git submodule update",
+            "reset-hard",
+        );
+    }
+
+    #[test]
+    fn test_worktree_prune_medium() {
+        let pack = create_pack();
+
+        assert_blocks_with_severity(&pack, "git worktree prune", Severity::Medium);
+        assert_blocks_with_pattern(&pack, "git worktree prune --expire now", "worktree-prune");
+        assert_allows(&pack, "git worktree prune --dry-run");
+        assert_allows(&pack, "git worktree prune -n -v");
+    }
+
+    #[test]
+    fn test_worktree_add_and_list_allowed() {
+        let pack = create_pack();
+
+        test_batch_allows(
+            &pack,
+            &[
+                "git worktree list",
+                "git worktree list --porcelain",
+                "git worktree add ../feature feature-branch",
+                "git worktree add -b hotfix ../hotfix main",
+            ],
+        );
+    }
+
     // =========================================================================
     // Safe Pattern Tests
     // =========================================================================
@@ -615,6 +1192,26 @@ mod tests {
         assert_allows(&pack, "git clean --dry-run");
     }
 
+    #[test]
+    fn test_safe_segment_does_not_clear_destructive_segment() {
+        let pack = create_pack();
+
+        assert_blocks_with_pattern(&pack, "git clean -n && git clean -fd", "clean-force");
+        assert_blocks_with_pattern(
+            &pack,
+            "git checkout -b tmp && git reset --hard origin/main",
+            "reset-hard",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git restore --staged a; git restore b",
+            "restore-worktree",
+        );
+
+        assert_allows(&pack, "git clean -n && git status");
+        assert_allows(&pack, "git checkout -b tmp && git push -u origin tmp");
+    }
+
     // =========================================================================
     // Specificity Tests (False Positive Prevention)
     // =========================================================================
@@ -35,14 +35,14 @@
 //!     description: Non-production deployments are allowed
 //! ```
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::io;
 use std::path::{Path, PathBuf};
 
 use super::regex_engine::LazyCompiledRegex;
-use super::{DestructivePattern, Pack, REGISTRY, SafePattern, Severity};
+use super::{DestructivePattern, Pack, Platform, REGISTRY, SafePattern, Severity};
 
 /// Current schema version for external pack files.
 pub const CURRENT_SCHEMA_VERSION: u32 = 1;
@@ -54,7 +54,7 @@ const ID_PATTERN: &str = r"^[a-z][a-z0-9_]*\.[a-z][a-z0-9_]*$";
 const VERSION_PATTERN: &str = r"^\d+\.\d+\.\d+$";
 
 /// An external pack definition loaded from YAML.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ExternalPack {
     /// Schema version for forward compatibility.
     #[serde(default = "default_schema_version")]
@@ -71,7 +71,7 @@ pub struct ExternalPack {
     pub version: String,
 
     /// Description of what this pack protects against.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
     /// Keywords that trigger evaluation for this pack.
@@ -84,7 +84,7 @@ pub struct ExternalPack {
     pub destructive_patterns: Vec<ExternalDestructivePattern>,
 
     /// Safe patterns that explicitly allow commands.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub safe_patterns: Vec<ExternalSafePattern>,
 }
 
@@ -94,7 +94,7 @@ const fn default_schema_version() -> u32 {
 }
 
 /// A destructive pattern from an external pack file.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ExternalDestructivePattern {
     /// Stable pattern identifier within the pack.
     pub name: String,
@@ -107,20 +107,36 @@ pub struct ExternalDestructivePattern {
     pub severity: ExternalSeverity,
 
     /// Short human-readable reason shown on denial.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
     /// Longer explanation shown in verbose output.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub explanation: Option<String>,
 
     /// Safer command alternatives to suggest when this pattern matches.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub suggestions: Vec<ExternalSuggestion>,
+
+    /// Whether the pattern matches by default (default: true).
+    ///
+    /// A disabled pattern is loaded but never matches until its rule ID
+    /// (`pack-id:pattern-name`) is listed in `packs.enable_patterns`.
+    #[serde(default = "default_enabled", skip_serializing_if = "is_enabled")]
+    pub enabled: bool,
+}
+
+const fn default_enabled() -> bool {
+    true
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde passes the field by reference
+const fn is_enabled(enabled: &bool) -> bool {
+    *enabled
 }
 
 /// A safer command suggestion from an external pack file.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ExternalSuggestion {
     /// The safer command alternative.
     pub command: String,
@@ -129,12 +145,12 @@ pub struct ExternalSuggestion {
     pub description: String,
 
     /// Platform this suggestion applies to (default: all).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "ExternalPlatform::is_all")]
     pub platform: ExternalPlatform,
 }
 
 /// Platform specifier for external pack suggestions.
-#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ExternalPlatform {
     #[default]
@@ -146,6 +162,12 @@ pub enum ExternalPlatform {
     Bsd,
 }
 
+impl ExternalPlatform {
+    const fn is_all(&self) -> bool {
+        matches!(self, Self::All)
+    }
+}
+
 impl From<ExternalPlatform> for super::Platform {
     fn from(platform: ExternalPlatform) -> Self {
         match platform {
@@ -158,8 +180,20 @@ impl From<ExternalPlatform> for super::Platform {
     }
 }
 
+impl From<Platform> for ExternalPlatform {
+    fn from(platform: Platform) -> Self {
+        match platform {
+            Platform::All => Self::All,
+            Platform::Linux => Self::Linux,
+            Platform::MacOS => Self::MacOS,
+            Platform::Windows => Self::Windows,
+            Platform::Bsd => Self::Bsd,
+        }
+    }
+}
+
 /// A safe pattern from an external pack file.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ExternalSafePattern {
     /// Stable pattern identifier within the pack.
     pub name: String,
@@ -168,12 +202,12 @@ pub struct ExternalSafePattern {
     pub pattern: String,
 
     /// Short reason for allowlisting (for documentation).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
 
 /// Severity level as specified in external pack files.
-#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ExternalSeverity {
     Low,
@@ -194,6 +228,17 @@ impl From<ExternalSeverity> for Severity {
     }
 }
 
+impl From<Severity> for ExternalSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Low => Self::Low,
+            Severity::Medium => Self::Medium,
+            Severity::High => Self::High,
+            Severity::Critical => Self::Critical,
+        }
+    }
+}
+
 /// Errors that can occur when parsing a pack file.
 #[derive(Debug)]
 pub enum PackParseError {
@@ -492,9 +537,15 @@ impl ExternalPack {
     /// Note: The returned `Pack` uses `'static` strings for the struct fields that
     /// require it. Since external packs are loaded at runtime, we use `Box::leak`
     /// to create static references. This is acceptable because:
-    /// 1. External packs are loaded once at startup
+    /// 1. External packs are loaded at startup, or when a source is reloaded
     /// 2. The pack data lives for the entire program lifetime
     /// 3. The leaked memory is a small, bounded amount per pack
+    ///
+    /// Pattern regexes compile through the shared cache
+    /// ([`super::regex_engine::compile_shared`]), so rebuilding a pack on
+    /// reload (a [`super::source::PackSource`] loaded again by `dcg pack
+    /// refresh` or a long-lived embedder) reuses the compiled regex for every
+    /// unchanged pattern.
     #[must_use]
     pub fn into_pack(self) -> Pack {
         // Leak the dynamic strings to get 'static lifetimes
@@ -522,7 +573,7 @@ impl ExternalPack {
             .map(|p| {
                 let name: &'static str = Box::leak(p.name.into_boxed_str());
                 SafePattern {
-                    regex: LazyCompiledRegex::new(Box::leak(p.pattern.into_boxed_str())),
+                    regex: LazyCompiledRegex::new_shared(Box::leak(p.pattern.into_boxed_str())),
                     name,
                 }
             })
@@ -560,12 +611,13 @@ impl ExternalPack {
                 };
 
                 DestructivePattern {
-                    regex: LazyCompiledRegex::new(Box::leak(p.pattern.into_boxed_str())),
+                    regex: LazyCompiledRegex::new_shared(Box::leak(p.pattern.into_boxed_str())),
                     reason,
                     name: Some(name),
                     severity: p.severity.into(),
                     explanation,
                     suggestions,
+                    enabled: p.enabled,
                 }
             })
             .collect();
@@ -579,6 +631,128 @@ impl ExternalPack {
             destructive_patterns,
         )
     }
+
+    /// Build an external pack definition from a runtime `Pack`.
+    ///
+    /// This is the inverse of [`Self::into_pack`]: the result serializes to the
+    /// same YAML schema the loader reads, so `dcg export-packs` output can be
+    /// edited and loaded back through `custom_paths`. Built-in packs carry no
+    /// version of their own, so the dcg version is used.
+    #[must_use]
+    pub fn from_pack(pack: &Pack) -> Self {
+        let destructive_patterns = pack
+            .destructive_patterns
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| ExternalDestructivePattern {
+                name: p
+                    .name
+                    .map_or_else(|| format!("pattern-{}", idx + 1), str::to_string),
+                pattern: p.regex.as_str().to_string(),
+                severity: p.severity.into(),
+                description: Some(p.reason.to_string()),
+                explanation: p.explanation.map(str::to_string),
+                suggestions: p
+                    .suggestions
+                    .iter()
+                    .map(|s| ExternalSuggestion {
+                        command: s.command.to_string(),
+                        description: s.description.to_string(),
+                        platform: s.platform.into(),
+                    })
+                    .collect(),
+                enabled: p.enabled,
+            })
+            .collect();
+
+        let safe_patterns = pack
+            .safe_patterns
+            .iter()
+            .map(|p| ExternalSafePattern {
+                name: p.name.to_string(),
+                pattern: p.regex.as_str().to_string(),
+                description: None,
+            })
+            .collect();
+
+        Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            id: pack.id.clone(),
+            name: pack.name.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            description: (!pack.description.is_empty()).then(|| pack.description.to_string()),
+            keywords: pack.keywords.iter().map(|k| (*k).to_string()).collect(),
+            destructive_patterns,
+            safe_patterns,
+        }
+    }
+
+    /// Serialize this pack to YAML in the external pack schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PackParseError::Yaml` if serialization fails.
+    pub fn to_yaml(&self) -> Result<String, PackParseError> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+}
+
+/// Pack ID used when exporting a built-in pack for customization.
+///
+/// External packs may not reuse a built-in ID, and some built-in IDs
+/// (e.g. `gpg_and_crypto`) have no namespace, so exports live under
+/// `custom.` with dots flattened: `core.git` becomes `custom.core_git`.
+#[must_use]
+pub fn export_pack_id(builtin_id: &str) -> String {
+    format!("custom.{}", builtin_id.replace('.', "_"))
+}
+
+/// Compute a stable fingerprint of a pack's evaluation-relevant content.
+///
+/// Covers the name, description, keywords, and every pattern (regex, name,
+/// severity, reason, explanation, suggestions) in order. The ID is left out
+/// so that an exported copy under [`export_pack_id`] still matches the
+/// built-in it came from.
+#[must_use]
+pub fn pack_fingerprint(pack: &Pack) -> String {
+    use sha2::{Digest, Sha256};
+    use std::fmt::Write;
+
+    let mut hasher = Sha256::new();
+    let mut field = |value: &str| {
+        hasher.update(value.len().to_le_bytes());
+        hasher.update(value.as_bytes());
+    };
+
+    field(pack.name);
+    field(pack.description);
+    for keyword in pack.keywords {
+        field(keyword);
+    }
+    for p in &pack.safe_patterns {
+        field("safe");
+        field(p.name);
+        field(p.regex.as_str());
+    }
+    for p in &pack.destructive_patterns {
+        field("destructive");
+        field(p.name.unwrap_or_default());
+        field(p.regex.as_str());
+        field(&format!("{:?}", p.severity));
+        field(p.reason);
+        field(p.explanation.unwrap_or_default());
+        for s in p.suggestions {
+            field(s.command);
+            field(s.description);
+            field(&format!("{:?}", s.platform));
+        }
+    }
+
+    let mut hex = String::with_capacity(64);
+    for byte in hasher.finalize() {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
 }
 
 /// Type of regex engine used for a pattern.
@@ -695,6 +869,16 @@ pub fn summarize_pack_engines(pack: &ExternalPack) -> EngineSummary {
     }
 }
 
+/// Read just the `id` field of a pack file, even if the pack itself is invalid.
+///
+/// Used to attribute load failures to the pack the user configured.
+#[must_use]
+pub fn peek_pack_id(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let value: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
+    value.get("id")?.as_str().map(str::to_string)
+}
+
 /// A loaded external pack plus its source path.
 #[derive(Debug)]
 pub struct LoadedExternalPack {
@@ -709,6 +893,8 @@ pub struct LoadedExternalPack {
 /// A warning emitted while loading external packs.
 #[derive(Debug)]
 pub struct PackLoadWarning {
+    /// Pack ID declared in the file, if it could still be read.
+    pub id: Option<String>,
     /// Path to the pack file.
     pub path: PathBuf,
     /// Error encountered while parsing/validating.
@@ -764,6 +950,7 @@ impl ExternalPackLoader {
                 }
                 Err(error) => {
                     warnings.push(PackLoadWarning {
+                        id: peek_pack_id(path),
                         path: path.clone(),
                         error,
                     });
@@ -797,6 +984,7 @@ impl ExternalPackLoader {
                 }
                 Err(error) => {
                     warnings.push(PackLoadWarning {
+                        id: peek_pack_id(path),
                         path: path.clone(),
                         error,
                     });
@@ -1204,4 +1392,90 @@ destructive_patterns:
         let result = validate_pack_with_collision_check(&pack);
         assert!(matches!(result, Err(PackParseError::IdCollision { .. })));
     }
+
+    #[test]
+    fn test_exported_builtin_pack_round_trips() {
+        let builtin = REGISTRY.get("core.git").expect("core.git is built in");
+        let yaml = ExternalPack::from_pack(builtin).to_yaml().unwrap();
+
+        let reloaded = parse_pack_string(&yaml).unwrap().into_pack();
+        assert_eq!(reloaded.id, "core.git");
+        assert_eq!(pack_fingerprint(&reloaded), pack_fingerprint(builtin));
+    }
+
+    #[test]
+    fn test_export_pack_id_is_loadable() {
+        assert_eq!(export_pack_id("core.git"), "custom.core_git");
+        assert_eq!(export_pack_id("gpg_and_crypto"), "custom.gpg_and_crypto");
+    }
+
+    #[test]
+    fn test_all_builtin_packs_export_to_loadable_yaml() {
+        for builtin in REGISTRY.iter() {
+            let mut external = ExternalPack::from_pack(builtin);
+            external.id = export_pack_id(&builtin.id);
+            let yaml = external.to_yaml().unwrap();
+            let reloaded = parse_pack_string_checked(&yaml)
+                .unwrap_or_else(|e| panic!("{} failed to re-load: {e}", builtin.id))
+                .into_pack();
+            assert_eq!(
+                pack_fingerprint(&reloaded),
+                pack_fingerprint(builtin),
+                "{} changed on round trip",
+                builtin.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_pack_fingerprint_detects_pattern_changes() {
+        let yaml = r#"
+id: mycompany.test
+name: Test
+version: 1.0.0
+destructive_patterns:
+  - name: drop
+    pattern: drop\s+table
+    severity: high
+"#;
+        let original = parse_pack_string(yaml).unwrap().into_pack();
+        let changed = parse_pack_string(&yaml.replace("severity: high", "severity: medium"))
+            .unwrap()
+            .into_pack();
+        assert_ne!(pack_fingerprint(&original), pack_fingerprint(&changed));
+    }
+
+    #[test]
+    fn test_rebuilt_pack_reuses_compiled_regex() {
+        let yaml = r#"
+id: mycompany.regexcache
+name: Regex Cache
+version: 1.0.0
+destructive_patterns:
+  - name: purge
+    pattern: regexcache-purge\s+--all
+    severity: high
+safe_patterns:
+  - name: dry-run
+    pattern: regexcache-purge\s+--dry-run
+"#;
+        let first = parse_pack_string(yaml).unwrap().into_pack();
+        let second = parse_pack_string(yaml).unwrap().into_pack();
+
+        assert!(
+            first.destructive_patterns[0]
+                .regex
+                .shares_compiled_with(&second.destructive_patterns[0].regex)
+        );
+        assert!(
+            first.safe_patterns[0]
+                .regex
+                .shares_compiled_with(&second.safe_patterns[0].regex)
+        );
+        assert!(
+            second.destructive_patterns[0]
+                .regex
+                .is_match("regexcache-purge --all")
+        );
+    }
 }
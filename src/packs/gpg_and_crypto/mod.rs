@@ -0,0 +1,296 @@
+//! GPG and disk encryption pack - protections for irreversible key destruction.
+//!
+//! Covers destructive CLI operations:
+//! - `gpg --delete-secret-keys` / `--delete-secret-and-public-keys` (private key removal)
+//! - `cryptsetup luksErase` / `cryptsetup erase` (wipes all LUKS keyslots)
+//! - `cryptsetup luksRemoveKey` / `luksKillSlot` (removes a passphrase or keyslot)
+//! - `ssh-keygen -f` / `openssl genrsa -out` / `openssl req -keyout` writing over
+//!   a well-known private key location (`~/.ssh/id_rsa` etc., SSH host keys, system TLS keys)
+//!
+//! Listing keys (`gpg --list-keys`) and inspecting volumes (`cryptsetup status`)
+//! are allowed. Generating a key to any other path is allowed, since the pack
+//! cannot tell whether that file already exists.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the GPG and disk encryption pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "gpg_and_crypto".to_string(),
+        name: "GPG and Disk Encryption",
+        description: "Protects against irreversible key destruction like deleting GPG secret \
+                      keys, erasing or removing LUKS keyslots with cryptsetup, and overwriting \
+                      SSH or TLS private keys with ssh-keygen or openssl.",
+        keywords: &["gpg", "cryptsetup", "ssh-keygen", "openssl"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Anchored to the end of the command so a chained destructive call is still evaluated.
+    vec![
+        safe_pattern!(
+            "gpg-list-keys",
+            r"\bgpg2?\s+(?!.*--delete)(?:[^;&|]*\s)?(?:-k|-K|--list-(?:keys|secret-keys|public-keys|sigs)|--fingerprint)(?:\s[^;&|]*)?$"
+        ),
+        safe_pattern!(
+            "cryptsetup-status",
+            r"\bcryptsetup(?:\s+--?\S+)*\s+(?:status|luksDump|isLuks|luksUUID)(?:\s[^;&|]*)?$"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "gpg-delete-secret-keys",
+            r"\bgpg2?\s+(?:[^;&|]*\s)?--delete-secret-(?:and-public-)?keys?\b",
+            "gpg --delete-secret-keys permanently removes private keys from the keyring.",
+            Critical,
+            "gpg --delete-secret-keys removes secret key material:\n\n\
+             - Data encrypted to the key can no longer be decrypted\n\
+             - Signatures can no longer be made with the key\n\
+             - There is no undo unless a backup of the secret key exists\n\n\
+             Back up first: gpg --export-secret-keys --armor <key-id> > key.asc\n\
+             List secret keys: gpg --list-secret-keys"
+        ),
+        destructive_pattern!(
+            "cryptsetup-luks-erase",
+            r"\bcryptsetup\s+(?:[^;&|]*\s)?(?:luksErase|erase)\b",
+            "cryptsetup luksErase wipes every keyslot, making the encrypted volume permanently inaccessible.",
+            Critical,
+            "cryptsetup luksErase destroys all LUKS keyslots:\n\n\
+             - No passphrase or keyfile can unlock the volume afterwards\n\
+             - The encrypted data is effectively destroyed\n\
+             - Only a LUKS header backup can restore access\n\n\
+             Back up the header first: cryptsetup luksHeaderBackup <device> --header-backup-file hdr.img\n\
+             Inspect keyslots: cryptsetup luksDump <device>"
+        ),
+        destructive_pattern!(
+            "cryptsetup-luks-remove-key",
+            r"\bcryptsetup\s+(?:[^;&|]*\s)?luksRemoveKey\b",
+            "cryptsetup luksRemoveKey removes a passphrase and can lock you out of the volume.",
+            Critical,
+            "cryptsetup luksRemoveKey deletes the keyslot matching a passphrase:\n\n\
+             - Removing the last working passphrase makes the volume unrecoverable\n\
+             - Scripts and crypttab entries using that passphrase stop working\n\n\
+             Confirm another keyslot works first: cryptsetup luksOpen --test-passphrase <device>\n\
+             Inspect keyslots: cryptsetup luksDump <device>"
+        ),
+        destructive_pattern!(
+            "cryptsetup-luks-kill-slot",
+            r"\bcryptsetup\s+(?:[^;&|]*\s)?luksKillSlot\b",
+            "cryptsetup luksKillSlot wipes a keyslot and can lock you out of the volume.",
+            Critical,
+            "cryptsetup luksKillSlot destroys a keyslot by number:\n\n\
+             - Killing the last active keyslot makes the volume unrecoverable\n\
+             - Slot numbers are easy to mix up\n\n\
+             Inspect keyslots first: cryptsetup luksDump <device>"
+        ),
+        // Protected paths: the default ~/.ssh/id_<type> keys (not .pub), SSH host keys, and keys under the
+        // system TLS private directories. Modes that never regenerate the key
+        // (-l fingerprint, -y print public key, -e/-i convert, -F/-R/-H known_hosts,
+        // -p/-c change passphrase/comment in place) are excluded up front.
+        destructive_pattern!(
+            "ssh-keygen-overwrite-key",
+            r#"\bssh-keygen\b(?![^;&|]*\s-[a-zA-Z]*[lyeiFRHpcDQLkKsAYGT])(?:[^;&|]*\s)?-f\s*["']?(?:(?:~|\$HOME|\$\{HOME\}|/root|/home/[^/\s]+|/Users/[^/\s]+)/\.ssh/id_(?:rsa|dsa|ecdsa|ed25519)(?:_sk)?|/etc/ssh/ssh_host_\w+_key|/etc/(?:ssl/private|pki/tls/private|letsencrypt/(?:live|archive))/[^\s"';&|]+?\.(?:key|pem))(?![\w.-])"#,
+            "ssh-keygen -f on an existing private key path replaces the key.",
+            High,
+            "ssh-keygen writes the new key to the -f path. When that path already holds a \
+             private key, answering 'y' to the overwrite prompt (or scripting it) destroys \
+             the old key:\n\n\
+             - Servers that trust the old public key will reject the new one\n\
+             - Anything encrypted or signed with the old key cannot be recovered\n\
+             - For host keys, every client sees a host key mismatch\n\n\
+             Generate to a new path instead: ssh-keygen -t ed25519 -f ~/.ssh/id_ed25519_new\n\
+             Back up first: cp ~/.ssh/id_rsa ~/.ssh/id_rsa.bak"
+        ),
+        destructive_pattern!(
+            "openssl-overwrite-key",
+            r#"\bopenssl\s+(?:req\b[^;&|]*\s-keyout|(?:genrsa|genpkey|gendsa|ecparam)\b[^;&|]*\s-out)\s+["']?(?:(?:~|\$HOME|\$\{HOME\}|/root|/home/[^/\s]+|/Users/[^/\s]+)/\.ssh/id_(?:rsa|dsa|ecdsa|ed25519)(?:_sk)?|/etc/ssh/ssh_host_\w+_key|/etc/(?:ssl/private|pki/tls/private|letsencrypt/(?:live|archive))/[^\s"';&|]+?\.(?:key|pem))(?![\w.-])"#,
+            "openssl key generation writing to an existing private key path replaces the key.",
+            High,
+            "openssl writes generated keys without prompting, so -keyout/-out pointing at a \
+             key that is already in use silently replaces it:\n\n\
+             - Certificates issued for the old key stop matching and TLS fails to start\n\
+             - The old private key is gone unless a backup exists\n\n\
+             Generate to a new path and move it into place once the certificate is ready:\n  \
+             openssl req -new -newkey rsa:4096 -nodes -keyout new.key -out new.csr\n\
+             Back up first: cp <key> <key>.bak"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "gpg_and_crypto");
+        assert_eq!(pack.name, "GPG and Disk Encryption");
+        assert!(!pack.description.is_empty());
+        assert!(pack.keywords.contains(&"gpg"));
+        assert!(pack.keywords.contains(&"cryptsetup"));
+        assert!(pack.keywords.contains(&"ssh-keygen"));
+        assert!(pack.keywords.contains(&"openssl"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn gpg_delete_secret_keys_is_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "gpg --delete-secret-keys ABCD1234",
+            "gpg-delete-secret-keys",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "gpg --batch --yes --delete-secret-and-public-keys ABCD1234",
+            "gpg-delete-secret-keys",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "gpg2 --delete-secret-key user@example.com",
+            "gpg-delete-secret-keys",
+        );
+        assert_blocks_with_severity(&pack, "gpg --delete-secret-keys ABCD", Severity::Critical);
+    }
+
+    #[test]
+    fn cryptsetup_erase_and_key_removal_are_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "cryptsetup luksErase /dev/sdb1",
+            "cryptsetup-luks-erase",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "cryptsetup -q erase /dev/sdb1",
+            "cryptsetup-luks-erase",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "cryptsetup luksRemoveKey /dev/sdb1",
+            "cryptsetup-luks-remove-key",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "cryptsetup luksKillSlot /dev/sdb1 1",
+            "cryptsetup-luks-kill-slot",
+        );
+        assert_blocks_with_severity(&pack, "cryptsetup luksErase /dev/sdb1", Severity::Critical);
+        assert_blocks_with_severity(
+            &pack,
+            "cryptsetup luksRemoveKey /dev/sdb1",
+            Severity::Critical,
+        );
+    }
+
+    #[test]
+    fn allows_safe_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "gpg --list-keys");
+        assert_safe_pattern_matches(&pack, "gpg --list-secret-keys --keyid-format long");
+        assert_safe_pattern_matches(&pack, "gpg -k user@example.com");
+        assert_safe_pattern_matches(&pack, "cryptsetup status cryptroot");
+        assert_safe_pattern_matches(&pack, "cryptsetup luksDump /dev/sdb1");
+        assert_allows(&pack, "gpg --encrypt --recipient user@example.com file.txt");
+        assert_allows(&pack, "gpg --delete-keys ABCD1234");
+        assert_allows(&pack, "cryptsetup luksOpen /dev/sdb1 data");
+        assert_allows(&pack, "cryptsetup luksAddKey /dev/sdb1");
+    }
+
+    #[test]
+    fn key_generation_over_existing_key_is_high() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "ssh-keygen -t rsa -b 4096 -f ~/.ssh/id_rsa -N ''",
+            "ssh-keygen-overwrite-key",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "yes y | ssh-keygen -q -t ed25519 -f \"$HOME/.ssh/id_ed25519\"",
+            "ssh-keygen-overwrite-key",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "ssh-keygen -t ecdsa -f /etc/ssh/ssh_host_ecdsa_key -N ''",
+            "ssh-keygen-overwrite-key",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "openssl req -x509 -newkey rsa:2048 -nodes -keyout /etc/ssl/private/server.key -out cert.pem",
+            "openssl-overwrite-key",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "openssl genrsa -out /etc/letsencrypt/live/example.com/privkey.pem 2048",
+            "openssl-overwrite-key",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "openssl genpkey -algorithm ed25519 -out ~/.ssh/id_ed25519",
+            "openssl-overwrite-key",
+        );
+        assert_blocks_with_severity(&pack, "ssh-keygen -f ~/.ssh/id_rsa", Severity::High);
+        assert_blocks_with_severity(
+            &pack,
+            "openssl genrsa -out /etc/pki/tls/private/host.key 4096",
+            Severity::High,
+        );
+    }
+
+    #[test]
+    fn key_generation_to_new_path_is_allowed() {
+        let pack = create_pack();
+        assert_allows(
+            &pack,
+            "ssh-keygen -t ed25519 -f ~/.ssh/id_ed25519_work -N ''",
+        );
+        assert_allows(&pack, "ssh-keygen -t ed25519 -f ./deploy_key");
+        assert_allows(&pack, "ssh-keygen -t ed25519");
+        assert_allows(&pack, "ssh-keygen -y -f ~/.ssh/id_rsa");
+        assert_allows(&pack, "ssh-keygen -lf ~/.ssh/id_rsa.pub");
+        assert_allows(&pack, "ssh-keygen -p -f ~/.ssh/id_rsa");
+        assert_allows(&pack, "ssh-keygen -R github.com");
+        assert_allows(
+            &pack,
+            "openssl req -new -newkey rsa:2048 -nodes -keyout server.key -out server.csr",
+        );
+        assert_allows(&pack, "openssl genrsa -out /tmp/test.key 2048");
+        assert_allows(
+            &pack,
+            "openssl req -new -key /etc/ssl/private/server.key -out server.csr",
+        );
+        assert_allows(
+            &pack,
+            "openssl x509 -in /etc/ssl/private/server.pem -noout -text",
+        );
+    }
+
+    #[test]
+    fn safe_listing_does_not_mask_chained_deletion() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "gpg --list-keys && gpg --delete-secret-keys ABCD1234",
+            "gpg-delete-secret-keys",
+        );
+    }
+}
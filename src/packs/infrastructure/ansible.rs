@@ -3,6 +3,10 @@
 //! This includes patterns for:
 //! - ansible-playbook with dangerous patterns
 //! - ansible with shell/command modules doing destructive things
+//! - ad-hoc `-m file` with `state=absent` on broad paths
+//! - ad-hoc `-m user` with `remove=yes`
+//!
+//! Dry runs (`--check`, `--syntax-check`) and `-m ping` are allowed.
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -14,7 +18,7 @@ pub fn create_pack() -> Pack {
         id: "infrastructure.ansible".to_string(),
         name: "Ansible",
         description: "Protects against destructive Ansible operations like dangerous shell \
-                      commands and unchecked playbook runs",
+                      commands, file/user removal modules, and unchecked playbook runs",
         keywords: &["ansible", "playbook"],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
@@ -26,10 +30,16 @@ pub fn create_pack() -> Pack {
 
 fn create_safe_patterns() -> Vec<SafePattern> {
     vec![
-        // --check is dry-run mode (safe)
-        safe_pattern!("ansible-check", r"ansible(?:-playbook)?\s+.*--check"),
-        // --diff shows changes (safe)
-        safe_pattern!("ansible-diff", r"ansible(?:-playbook)?\s+.*--diff"),
+        // --check/-C is dry-run mode (safe), with or without --diff
+        safe_pattern!(
+            "ansible-check",
+            r"ansible(?:-playbook)?\s+.*\s(?:--check|-C)(?=\s|$)"
+        ),
+        // Ad-hoc ping only tests connectivity (no other module on the line)
+        safe_pattern!(
+            "ansible-ping",
+            r#"\bansible\s+(?!.*(?:\s-m|--module-name)(?:\s+|=)['\"]?(?!(?:ansible\.builtin\.)?ping\b)).*(?:\s-m|--module-name)(?:\s+|=)['\"]?(?:ansible\.builtin\.)?ping\b"#
+        ),
         // --list-hosts just lists (safe)
         safe_pattern!(
             "ansible-list-hosts",
@@ -56,10 +66,10 @@ fn create_safe_patterns() -> Vec<SafePattern> {
 
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
-        // ansible with rm -rf in command
+        // ansible with rm -rf in command (module and -a args in any order)
         destructive_pattern!(
             "shell-rm-rf",
-            r"ansible\s+.*-m\s+(?:shell|command)\s+.*rm\s+-rf",
+            r#"\bansible\s+(?=.*(?:-m|--module-name)(?:\s+|=)['\"]?(?:ansible\.builtin\.)?(?:shell|command|raw)\b).*\brm\s+(?:-[a-zA-Z]*[rR][a-zA-Z]*f|-[a-zA-Z]*f[a-zA-Z]*[rR]|(?:-[rR]|--recursive)\s+(?:-f|--force)|(?:-f|--force)\s+(?:-[rR]|--recursive))\b"#,
             "Ansible shell/command with 'rm -rf' is destructive. Review carefully.",
             Critical,
             "Running 'rm -rf' via Ansible shell or command module executes destructive deletion \
@@ -75,6 +85,37 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Use --limit to restrict to specific hosts first\n\
              - Verify target path with debug module before deletion"
         ),
+        // file module removing /, a system directory, or a home directory
+        destructive_pattern!(
+            "file-absent-broad",
+            r#"\bansible\s+(?=.*(?:-m|--module-name)(?:\s+|=)['\"]?(?:ansible\.builtin\.)?file\b)(?=.*\bstate=['\"]?absent\b).*\b(?:path|dest|name)=['\"]?(?:/(?:bin|boot|etc|home|lib|lib64|opt|root|srv|usr|var)?/?|~/?|/?\*)(?=['\"\s]|$)"#,
+            "Ansible file module with state=absent on a broad path deletes it on every targeted host.",
+            Critical,
+            "The file module with state=absent removes the path recursively, like 'rm -rf'. \
+             Pointed at /, a system directory, or a home directory, it wipes that tree on \
+             every host the pattern matches, in parallel:\n\n\
+             - Deletion happens on all targeted hosts at once\n\
+             - Directories are removed with all of their contents\n\
+             - Nothing can be restored without backups on each host\n\n\
+             Safer alternatives:\n\
+             - Point path= at the specific file or directory to remove\n\
+             - Add --check to see which hosts would change\n\
+             - Use --limit to try a single host first"
+        ),
+        // user module with remove=yes deletes the home directory and mail spool
+        destructive_pattern!(
+            "user-remove",
+            r#"\bansible\s+(?=.*(?:-m|--module-name)(?:\s+|=)['\"]?(?:ansible\.builtin\.)?user\b).*\bremove=['\"]?(?:yes|true|True|1)\b"#,
+            "Ansible user module with remove=yes deletes the user's home directory on every targeted host.",
+            High,
+            "remove=yes (with state=absent) makes the user module run 'userdel --remove', which \
+             deletes the account's home directory and mail spool along with the account. \
+             Anything stored there is lost on every targeted host.\n\n\
+             Safer alternatives:\n\
+             - Drop remove=yes to delete the account but keep its files\n\
+             - Archive the home directory before removing the user\n\
+             - Add --check to see which hosts would change"
+        ),
         // ansible with reboot
         destructive_pattern!(
             "shell-reboot",
@@ -111,10 +152,10 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Add serial: 1 to playbook for rolling updates\n\
              - Review the playbook tasks before running"
         ),
-        // ansible with -e that might contain dangerous variables
+        // ansible/ansible-playbook with -e that might contain dangerous variables
         destructive_pattern!(
             "extra-vars-delete",
-            r#"ansible\s+.*-e\s+['\"].*(?:delete|remove|destroy|drop)"#,
+            r#"\bansible(?:-playbook)?\s+.*(?:\s-e|--extra-vars)(?:\s+|=).*(?:delete|remove|destroy|drop|state=['\"]?absent\b)"#,
             "Ansible extra-vars contains potentially destructive keywords. Review carefully.",
             Medium,
             "Extra variables containing destructive keywords like 'delete', 'remove', 'destroy', \
@@ -130,3 +171,115 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "infrastructure.ansible");
+        assert!(pack.keywords.contains(&"ansible"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn adhoc_destructive_modules_are_blocked() {
+        let pack = create_pack();
+        for cmd in [
+            r#"ansible all -m shell -a "rm -rf /var/lib/app""#,
+            r#"ansible web -a "rm -fr /srv/www" -m command"#,
+            r#"ansible all -m ansible.builtin.shell -a "rm -r -f /opt/app""#,
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "shell-rm-rf");
+        }
+        assert_blocks_with_severity(
+            &pack,
+            "ansible all -m shell -a 'rm -rf /'",
+            Severity::Critical,
+        );
+
+        for cmd in [
+            "ansible all -m file -a 'path=/etc state=absent'",
+            r#"ansible all -a "state=absent path=/" -m file"#,
+            "ansible all -m ansible.builtin.file -a 'path=/home/ state=absent'",
+            "ansible all -m file -a 'path=~ state=absent'",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "file-absent-broad");
+        }
+        assert_blocks_with_severity(
+            &pack,
+            "ansible all -m file -a 'path=/etc state=absent'",
+            Severity::Critical,
+        );
+        assert_no_match(
+            &pack,
+            "ansible all -m file -a 'path=/etc/app.conf state=absent'",
+        );
+        assert_no_match(&pack, "ansible all -m file -a 'path=/etc state=directory'");
+
+        assert_blocks_with_pattern(
+            &pack,
+            "ansible all -m user -a 'name=bob state=absent remove=yes'",
+            "user-remove",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "ansible all -m user -a 'name=bob state=absent remove=yes'",
+            Severity::High,
+        );
+        assert_no_match(&pack, "ansible all -m user -a 'name=bob state=absent'");
+    }
+
+    #[test]
+    fn playbook_check_is_allowed_but_unchecked_absent_is_flagged() {
+        let pack = create_pack();
+        assert_allows(&pack, "ansible-playbook site.yml --check --diff");
+        assert_safe_pattern_matches(&pack, "ansible-playbook site.yml --check --diff");
+        assert_allows(
+            &pack,
+            "ansible-playbook destroy.yml -e state=absent --check",
+        );
+
+        assert_blocks_with_pattern(
+            &pack,
+            "ansible-playbook destroy.yml -e state=absent",
+            "extra-vars-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "ansible-playbook destroy.yml --extra-vars 'app_state=absent' --diff",
+            "extra-vars-delete",
+        );
+        assert_no_match(&pack, "ansible-playbook site.yml -e env=prod");
+    }
+
+    #[test]
+    fn read_only_commands_are_allowed() {
+        let pack = create_pack();
+        test_batch_allows(
+            &pack,
+            &[
+                "ansible all -m ping",
+                "ansible all -i inventory.ini -m ansible.builtin.ping",
+                "ansible-playbook site.yml --check",
+                "ansible-playbook site.yml -C",
+                "ansible-playbook site.yml --syntax-check",
+                "ansible-playbook site.yml --list-hosts",
+                "ansible-inventory --list",
+                "ansible-doc file",
+            ],
+        );
+        assert_safe_pattern_matches(&pack, "ansible all -m ping");
+        assert!(
+            pack.check("ansible all -m ping -m shell -a 'rm -rf /'")
+                .is_some()
+        );
+    }
+}
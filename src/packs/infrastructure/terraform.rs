@@ -1,10 +1,12 @@
 //! Terraform patterns - protections against destructive terraform commands.
 //!
 //! This includes patterns for:
-//! - terraform destroy
+//! - terraform destroy and apply -destroy
 //! - terraform taint
 //! - terraform apply with -auto-approve
-//! - terraform state rm
+//! - terraform state rm and workspace delete
+//! - the same commands under OpenTofu (`tofu`)
+//! - terragrunt destroy, including `run-all`/`--all` across a stack
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -15,9 +17,16 @@ pub fn create_pack() -> Pack {
     Pack {
         id: "infrastructure.terraform".to_string(),
         name: "Terraform",
-        description: "Protects against destructive Terraform operations like destroy, \
-                      taint, and apply with -auto-approve",
-        keywords: &["terraform", "destroy", "taint", "state"],
+        description: "Protects against destructive Terraform/OpenTofu operations like destroy, \
+                      taint, and apply with -auto-approve, including Terragrunt stacks",
+        keywords: &[
+            "terraform",
+            "tofu",
+            "terragrunt",
+            "destroy",
+            "taint",
+            "state",
+        ],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
@@ -28,27 +37,53 @@ pub fn create_pack() -> Pack {
 
 fn create_safe_patterns() -> Vec<SafePattern> {
     vec![
-        // plan is safe (read-only)
-        safe_pattern!("terraform-plan", r"terraform\s+plan(?!\s+.*-destroy)"),
+        // plan is safe (read-only), including plan -destroy which only previews
+        safe_pattern!("terraform-plan", r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*plan"),
         // init is safe
-        safe_pattern!("terraform-init", r"terraform\s+init"),
+        safe_pattern!("terraform-init", r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*init"),
         // validate is safe
-        safe_pattern!("terraform-validate", r"terraform\s+validate"),
+        safe_pattern!(
+            "terraform-validate",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*validate"
+        ),
         // fmt is safe
-        safe_pattern!("terraform-fmt", r"terraform\s+fmt"),
+        safe_pattern!("terraform-fmt", r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*fmt"),
         // show is safe
-        safe_pattern!("terraform-show", r"terraform\s+show"),
+        safe_pattern!("terraform-show", r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*show"),
         // output is safe
-        safe_pattern!("terraform-output", r"terraform\s+output"),
+        safe_pattern!(
+            "terraform-output",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*output"
+        ),
         // state list/show are safe (read-only)
-        safe_pattern!("terraform-state-list", r"terraform\s+state\s+list"),
-        safe_pattern!("terraform-state-show", r"terraform\s+state\s+show"),
+        safe_pattern!(
+            "terraform-state-list",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*state\s+list"
+        ),
+        safe_pattern!(
+            "terraform-state-show",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*state\s+show"
+        ),
         // graph is safe
-        safe_pattern!("terraform-graph", r"terraform\s+graph"),
+        safe_pattern!(
+            "terraform-graph",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*graph"
+        ),
         // version is safe
-        safe_pattern!("terraform-version", r"terraform\s+version"),
+        safe_pattern!(
+            "terraform-version",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*version"
+        ),
         // providers is safe
-        safe_pattern!("terraform-providers", r"terraform\s+providers"),
+        safe_pattern!(
+            "terraform-providers",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*providers"
+        ),
+        // terragrunt plan is safe (read-only), with or without run-all/--all
+        safe_pattern!(
+            "terragrunt-plan",
+            r"terragrunt\s+(?:run-all\s+|run\s+--all\s+(?:--\s+)?)?plan(?!\s+.*-destroy)"
+        ),
     ]
 }
 
@@ -57,7 +92,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // destroy
         destructive_pattern!(
             "destroy",
-            r"terraform\s+destroy",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*destroy",
             "terraform destroy removes ALL managed infrastructure. Use 'terraform plan -destroy' first.",
             Critical,
             "terraform destroy removes ALL managed infrastructure:\n\n\
@@ -67,22 +102,22 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Use -target to destroy specific resources only\n\n\
              Preview first: terraform plan -destroy"
         ),
-        // plan -destroy is a preview but can be scary
+        // apply -destroy is terraform destroy spelled differently
         destructive_pattern!(
-            "plan-destroy",
-            r"terraform\s+plan\s+.*-destroy",
-            "terraform plan -destroy shows what would be destroyed. Review carefully before applying.",
-            Medium,
-            "terraform plan -destroy shows destruction preview:\n\n\
-             - This is a read-only operation (safe to run)\n\
-             - Shows what WOULD be destroyed if you apply\n\
-             - Review output carefully before proceeding\n\n\
-             This is actually the safe way to preview destroy."
+            "apply-destroy",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*apply\s+.*-destroy\b",
+            "terraform apply -destroy removes ALL managed infrastructure, same as terraform destroy.",
+            Critical,
+            "terraform apply -destroy is the same operation as terraform destroy:\n\n\
+             - Every resource in your state file is destroyed\n\
+             - With -auto-approve there is no confirmation prompt\n\
+             - Cannot be undone without backups/recreation\n\n\
+             Preview first: terraform plan -destroy"
         ),
         // apply with -auto-approve (skips confirmation)
         destructive_pattern!(
             "apply-auto-approve",
-            r"terraform\s+apply\s+.*-auto-approve",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*apply\s+.*-auto-approve",
             "terraform apply -auto-approve skips confirmation. Remove -auto-approve for safety.",
             High,
             "terraform apply -auto-approve skips confirmation:\n\n\
@@ -94,7 +129,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // taint marks resource for recreation
         destructive_pattern!(
             "taint",
-            r"terraform\s+taint\b",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*taint\b",
             "terraform taint marks a resource to be destroyed and recreated on next apply.",
             High,
             "terraform taint marks resource for recreation:\n\n\
@@ -107,7 +142,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // state rm removes from state (orphans resource)
         destructive_pattern!(
             "state-rm",
-            r"terraform\s+state\s+rm\b",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*state\s+rm\b",
             "terraform state rm removes resource from state without destroying it. Resource becomes unmanaged.",
             High,
             "terraform state rm orphans resources:\n\n\
@@ -120,7 +155,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // state mv can cause issues if done incorrectly
         destructive_pattern!(
             "state-mv",
-            r"terraform\s+state\s+mv\b",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*state\s+mv\b",
             "terraform state mv moves resources in state. Incorrect moves can cause resource recreation.",
             High,
             "terraform state mv moves resources in state:\n\n\
@@ -133,7 +168,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // force-unlock
         destructive_pattern!(
             "force-unlock",
-            r"terraform\s+force-unlock\b",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*force-unlock\b",
             "terraform force-unlock removes state lock. Only use if lock is stale.",
             High,
             "terraform force-unlock removes state locks:\n\n\
@@ -146,9 +181,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // workspace delete
         destructive_pattern!(
             "workspace-delete",
-            r"terraform\s+workspace\s+delete\b",
+            r"\b(?:terraform|tofu)\s+(?:-\S+\s+)*workspace\s+delete\b",
             "terraform workspace delete removes a workspace. Ensure it's not in use.",
-            Medium,
+            High,
             "terraform workspace delete removes workspace:\n\n\
              - Workspace and its state file deleted\n\
              - Does NOT destroy actual infrastructure\n\
@@ -156,5 +191,151 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Cannot be undone without state backup\n\n\
              Destroy resources first: terraform destroy, then delete workspace"
         ),
+        // terragrunt destroy across every module in a stack
+        destructive_pattern!(
+            "terragrunt-run-all-destroy",
+            r"terragrunt\s+(?:\S+\s+)*?(?:run-all|run\s+(?:\S+\s+)*?--all)\s+(?:\S+\s+)*?destroy\b|terragrunt\s+(?:\S+\s+)*?destroy-all\b|terragrunt\s+(?:\S+\s+)*?destroy\b.*\s--all\b",
+            "terragrunt run-all destroy destroys every module in the stack. Run it per module after 'terragrunt run-all plan -destroy'.",
+            Critical,
+            "terragrunt run-all destroy (or run --all destroy) runs terraform destroy in \
+             every module below the current directory:\n\n\
+             - Whole environments (networking, databases, clusters) go at once\n\
+             - Dependency order is followed, so nothing stops partway on its own\n\
+             - --terragrunt-non-interactive / --non-interactive skips the prompt\n\
+             - Cannot be undone without backups/recreation\n\n\
+             Preview first: terragrunt run-all plan -destroy"
+        ),
+        // terragrunt destroy in a single module
+        destructive_pattern!(
+            "terragrunt-destroy",
+            r"terragrunt\s+(?:-\S+\s+)*destroy\b",
+            "terragrunt destroy removes all infrastructure managed by the module. Use 'terragrunt plan -destroy' first.",
+            Critical,
+            "terragrunt destroy runs terraform destroy for the module:\n\n\
+             - Every resource in the module's state is destroyed\n\
+             - Dependent modules may break once their inputs are gone\n\
+             - Cannot be undone without backups/recreation\n\n\
+             Preview first: terragrunt plan -destroy"
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "infrastructure.terraform");
+        assert!(pack.keywords.contains(&"terraform"));
+        assert!(pack.keywords.contains(&"terragrunt"));
+        assert!(pack.keywords.contains(&"tofu"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn destroy_and_apply_destroy_are_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "terraform destroy -auto-approve", "destroy");
+        assert_blocks_with_severity(&pack, "terraform destroy -auto-approve", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "terraform -chdir=infra destroy", "destroy");
+        assert_blocks_with_pattern(
+            &pack,
+            "terraform apply -destroy -auto-approve",
+            "apply-destroy",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "terraform apply -destroy -auto-approve",
+            Severity::Critical,
+        );
+    }
+
+    #[test]
+    fn plan_destroy_only_previews() {
+        let pack = create_pack();
+        assert_allows(&pack, "terraform plan -destroy");
+        assert_allows(&pack, "terraform plan -destroy -out=destroy.tfplan");
+        assert_safe_pattern_matches(&pack, "terraform plan -destroy");
+        assert_allows(&pack, "terraform validate");
+        assert_allows(&pack, "terraform fmt -check");
+    }
+
+    #[test]
+    fn state_rm_orphans_resources() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "terraform state rm aws_instance.web", "state-rm");
+        assert_blocks_with_severity(&pack, "terraform state rm aws_instance.web", Severity::High);
+        assert_allows(&pack, "terraform state list");
+    }
+
+    #[test]
+    fn opentofu_is_covered() {
+        let pack = create_pack();
+        assert!(pack.might_match("tofu destroy"));
+        assert_blocks_with_pattern(&pack, "tofu destroy -auto-approve", "destroy");
+        assert_blocks_with_pattern(&pack, "tofu apply -destroy", "apply-destroy");
+        assert_blocks_with_pattern(&pack, "tofu state rm module.db", "state-rm");
+        assert_blocks_with_pattern(&pack, "tofu workspace delete dev", "workspace-delete");
+        assert_allows(&pack, "tofu plan -destroy");
+        assert_allows(&pack, "tofu show");
+    }
+
+    #[test]
+    fn force_unlock_and_workspace_delete_are_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "terraform force-unlock 1234-abcd", "force-unlock");
+        assert_blocks_with_severity(&pack, "terraform force-unlock -force 1234", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "terraform workspace delete staging",
+            "workspace-delete",
+        );
+        assert_blocks_with_severity(&pack, "terraform workspace delete staging", Severity::High);
+        assert_no_match(&pack, "terraform workspace list");
+    }
+
+    #[test]
+    fn terragrunt_stack_destroy_is_critical() {
+        let pack = create_pack();
+        for command in [
+            "terragrunt run-all destroy",
+            "terragrunt run-all destroy --terragrunt-non-interactive",
+            "terragrunt --terragrunt-non-interactive run-all destroy",
+            "terragrunt run --all destroy",
+            "terragrunt run --all -- destroy",
+            "terragrunt destroy --all",
+            "terragrunt destroy-all",
+        ] {
+            assert_blocks_with_pattern(&pack, command, "terragrunt-run-all-destroy");
+            assert_blocks_with_severity(&pack, command, Severity::Critical);
+        }
+    }
+
+    #[test]
+    fn terragrunt_single_module_destroy_is_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "terragrunt destroy", "terragrunt-destroy");
+        assert_blocks_with_pattern(
+            &pack,
+            "terragrunt destroy --terragrunt-non-interactive -auto-approve",
+            "terragrunt-destroy",
+        );
+    }
+
+    #[test]
+    fn terraform_show_and_terragrunt_plan_are_allowed() {
+        let pack = create_pack();
+        assert_allows(&pack, "terraform show");
+        assert_allows(&pack, "terragrunt plan");
+        assert_allows(&pack, "terragrunt run-all plan");
+        assert_allows(&pack, "terragrunt run --all plan");
+        assert_no_match(&pack, "terragrunt output");
+    }
+}
@@ -5,6 +5,8 @@
 //! - drain nodes
 //! - cordon nodes
 //! - delete without dry-run
+//! - replace --force and apply --prune (delete and recreate / delete unlisted)
+//! - any other delete (warn by default, escalated on production contexts)
 
 use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -97,6 +99,30 @@ const APPLY_FORCE_SUGGESTIONS: &[PatternSuggestion] = &[
     ),
 ];
 
+/// Suggestions for `kubectl replace --force` pattern.
+const REPLACE_FORCE_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "kubectl replace -f {file}",
+        "Replace in place without deleting the live object",
+    ),
+    PatternSuggestion::new(
+        "kubectl diff -f {file}",
+        "Preview what changes would be applied",
+    ),
+];
+
+/// Suggestions for `kubectl apply --prune` pattern.
+const APPLY_PRUNE_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "kubectl apply -f {file} --prune --dry-run=server",
+        "List the resources --prune would delete without deleting them",
+    ),
+    PatternSuggestion::new(
+        "kubectl apply -f {file}",
+        "Apply without --prune so unlisted resources are left alone",
+    ),
+];
+
 /// Suggestions for `kubectl delete -f` with directory pattern.
 const DELETE_FROM_DIR_SUGGESTIONS: &[PatternSuggestion] = &[
     PatternSuggestion::new(
@@ -266,15 +292,31 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Remove taint:\n  \
              kubectl taint nodes <node> key=value:NoExecute-"
         ),
-        // delete deployment/statefulset/daemonset
+        // delete statefulset (checked before the generic workload rule)
+        destructive_pattern!(
+            "delete-statefulset",
+            r"kubectl\s+delete\s+(?:statefulsets?|sts)\b(?!.*--dry-run)",
+            "kubectl delete statefulset removes the workload and may leave its PVCs orphaned. Use --dry-run first.",
+            High,
+            "Deleting a StatefulSet terminates all of its pods:\n\n\
+             - Pods are shut down and the service goes down\n\
+             - PVCs created from volumeClaimTemplates are NOT deleted by default\n\
+             - Orphaned PVCs keep consuming storage and are reattached by name if\n\
+               a StatefulSet with the same name is recreated later\n\n\
+             Preview first:\n  \
+             kubectl delete statefulset <name> --dry-run=client\n  \
+             kubectl get pvc -l app=<name>  # Check claims left behind\n\n\
+             Consider scaling down first:\n  \
+             kubectl scale statefulset <name> --replicas=0"
+        ),
+        // delete deployment/daemonset/replicaset
         destructive_pattern!(
             "delete-workload",
-            r"kubectl\s+delete\s+(?:deployment|statefulset|daemonset|replicaset)\b(?!.*--dry-run)",
-            "kubectl delete deployment/statefulset/daemonset removes the workload. Use --dry-run first.",
+            r"kubectl\s+delete\s+(?:deployments?|deploy|daemonsets?|ds|replicasets?|rs)\b(?!.*--dry-run)",
+            "kubectl delete deployment/daemonset removes the workload. Use --dry-run first.",
             High,
             "Deleting a workload terminates all its pods:\n\n\
              - Deployment: All replicas terminated, service goes down\n\
-             - StatefulSet: Ordered shutdown, PVCs may be orphaned\n\
              - DaemonSet: Removed from all nodes\n\
              - ReplicaSet: Pods terminated (usually managed by Deployment)\n\n\
              Preview first:\n  \
@@ -322,9 +364,9 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         // scale to 0
         destructive_pattern!(
             "scale-to-zero",
-            r"kubectl\s+scale\s+.*--replicas=0",
+            r"kubectl\s+scale\s+.*--replicas(?:=|\s+)0+\b",
             "kubectl scale --replicas=0 stops all pods for the workload.",
-            High,
+            Medium,
             "Scaling to zero replicas terminates ALL pods for the workload:\n\n\
              - Service becomes unavailable\n\
              - Endpoints are removed from Service\n\
@@ -374,6 +416,38 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              kubectl apply --server-side -f <file>",
             APPLY_FORCE_SUGGESTIONS
         ),
+        // replace --force
+        destructive_pattern!(
+            "replace-force",
+            r"kubectl\s+replace\s+.*--force\b",
+            "kubectl replace --force deletes and recreates resources, dropping in-cluster state.",
+            High,
+            "kubectl replace --force deletes the live object and creates it again from the \
+             manifest:\n\n\
+             - Pods are terminated before replacements start\n\
+             - Status, runtime annotations and generated fields are lost\n\
+             - Objects owned by the replaced resource may be garbage-collected\n\n\
+             Preview changes first:\n  \
+             kubectl diff -f <file>\n\n\
+             Replace in place instead:\n  \
+             kubectl replace -f <file>",
+            REPLACE_FORCE_SUGGESTIONS
+        ),
+        // apply --prune
+        destructive_pattern!(
+            "apply-prune",
+            r"kubectl\s+apply\s+.*--prune\b",
+            "kubectl apply --prune deletes resources that are not in the applied manifests.",
+            High,
+            "kubectl apply --prune deletes every matching resource that is missing from the \
+             manifests being applied:\n\n\
+             - Applying a single file or a partial directory deletes everything else selected\n\
+             - A broad label selector or --all can reach resources owned by other teams\n\
+             - PVCs removed this way may take their data with them\n\n\
+             See what would be pruned first:\n  \
+             kubectl apply -f <dir> --prune -l <selector> --dry-run=server",
+            APPLY_PRUNE_SUGGESTIONS
+        ),
         // delete -f with directory (batch deletion)
         destructive_pattern!(
             "delete-from-directory",
@@ -391,5 +465,88 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              kubectl delete -f <specific-file.yaml>",
             DELETE_FROM_DIR_SUGGESTIONS
         ),
+        // delete pod: warns by default; `[kubernetes] prod_contexts` escalates
+        // it to a deny in production.
+        destructive_pattern!(
+            "delete-pod",
+            r"kubectl(?:\s+--?\S+(?:\s+\S+)?)*\s+delete\s+(?:pods?|po)(?:/|\s|$)",
+            "kubectl delete pod terminates the named pods.",
+            Medium,
+            "kubectl delete pod terminates the named pods:\n\n\
+             - Pods owned by a controller are recreated, but in-flight work is lost\n\
+             - Bare pods (no Deployment/Job) are gone for good\n\
+             - Targeting the wrong context or namespace affects a different cluster\n\n\
+             Check the target first:\n  \
+             kubectl config current-context\n  \
+             kubectl delete pod <name> --dry-run=client"
+        ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn replace_force_and_apply_prune_are_high() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl replace --force -f pod.yaml",
+            "replace-force",
+        );
+        assert_blocks_with_severity(&pack, "kubectl replace -f x.yaml --force", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl apply -f manifests/ --prune -l app=web",
+            "apply-prune",
+        );
+        assert_blocks_with_severity(&pack, "kubectl apply --prune --all -f .", Severity::High);
+
+        assert_allows(&pack, "kubectl apply -f deploy.yaml");
+        assert_allows(&pack, "kubectl replace -f deploy.yaml");
+        assert_allows(
+            &pack,
+            "kubectl apply -f dir/ --prune -l app=web --dry-run=server",
+        );
+    }
+
+    #[test]
+    fn scale_to_zero_warns_and_workload_delete_is_high() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "kubectl scale deployment web --replicas=0",
+            "scale-to-zero",
+        );
+        assert_blocks_with_severity(&pack, "kubectl scale sts db --replicas 0", Severity::Medium);
+        assert_allows(&pack, "kubectl scale deployment web --replicas=3");
+        assert_allows(&pack, "kubectl scale deployment web --replicas=10");
+
+        assert_blocks_with_pattern(&pack, "kubectl delete deployment web", "delete-workload");
+        assert_blocks_with_severity(&pack, "kubectl delete ds fluentd", Severity::High);
+        assert_blocks_with_pattern(&pack, "kubectl delete statefulset db", "delete-statefulset");
+        let matched = pack
+            .check("kubectl delete sts db -n prod")
+            .expect("statefulset delete should match");
+        assert_eq!(matched.severity, Severity::High);
+        assert!(matched.reason.contains("PVC"));
+
+        assert_allows(&pack, "kubectl get deployments");
+        assert_allows(&pack, "kubectl delete deployment web --dry-run=client");
+    }
+
+    #[test]
+    fn delete_pod_warns_and_other_kinds_are_not_caught_all() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "kubectl delete pod web-1", "delete-pod");
+        assert_blocks_with_pattern(&pack, "kubectl -n dev delete po/web-1", "delete-pod");
+        assert_blocks_with_severity(&pack, "kubectl delete pods web-1 web-2", Severity::Medium);
+
+        assert_allows(&pack, "kubectl delete configmap settings");
+        assert_allows(&pack, "kubectl delete podsecuritypolicy restricted");
+        assert_allows(&pack, "kubectl delete pod web-1 --dry-run=client");
+    }
+}
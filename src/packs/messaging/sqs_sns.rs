@@ -59,7 +59,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             "aws-sqs-delete-queue",
             r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+sqs\s+delete-queue\b",
             "aws sqs delete-queue permanently deletes an SQS queue.",
-            Critical,
+            High,
             "Deleting an SQS queue removes the queue and all messages in it. Messages \
              in flight are lost, and any applications sending to this queue will \
              receive errors. You must wait 60 seconds before recreating a queue \
@@ -112,7 +112,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             "aws-sns-delete-topic",
             r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+sns\s+delete-topic\b",
             "aws sns delete-topic removes an SNS topic and its subscriptions.",
-            Critical,
+            High,
             "Deleting an SNS topic removes all subscriptions and stops all message \
              delivery. Publishers will receive errors. Lambda functions, SQS queues, \
              and other subscribers will no longer receive notifications.\n\n\
@@ -166,6 +166,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::Severity;
     use crate::packs::test_helpers::*;
 
     #[test]
@@ -175,6 +176,8 @@ mod tests {
         assert_eq!(pack.name, "AWS SQS/SNS");
         assert!(!pack.description.is_empty());
         assert!(pack.keywords.contains(&"aws"));
+        assert!(pack.keywords.contains(&"sqs"));
+        assert!(pack.keywords.contains(&"sns"));
 
         assert_patterns_compile(&pack);
         assert_all_patterns_have_reasons(&pack);
@@ -245,4 +248,42 @@ mod tests {
             "aws-sns-delete-platform-application",
         );
     }
+
+    #[test]
+    fn purge_and_delete_are_high() {
+        let pack = create_pack();
+        assert_blocks_with_severity(
+            &pack,
+            "aws sqs purge-queue --queue-url https://sqs.us-east-1.amazonaws.com/123/queue",
+            Severity::High,
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "aws sqs delete-queue --queue-url https://sqs.us-east-1.amazonaws.com/123/queue",
+            Severity::High,
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "aws sns delete-topic --topic-arn arn:aws:sns:us-east-1:123:topic",
+            Severity::High,
+        );
+    }
+
+    #[test]
+    fn global_options_before_service_still_match() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "aws --region us-east-1 --profile prod sqs purge-queue --queue-url https://sqs.us-east-1.amazonaws.com/123/queue",
+            "aws-sqs-purge-queue",
+        );
+        assert_safe_pattern_matches(
+            &pack,
+            "aws --region us-east-1 sqs receive-message --queue-url https://sqs.us-east-1.amazonaws.com/123/queue",
+        );
+        assert_no_match(
+            &pack,
+            "aws sqs send-message --queue-url q --message-body hi",
+        );
+    }
 }
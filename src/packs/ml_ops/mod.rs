@@ -0,0 +1,202 @@
+//! ML Ops pack - protections for experiment tracking and data versioning tools.
+//!
+//! Covers destructive CLI operations:
+//! - `wandb artifact delete` (artifact version removal)
+//! - `mlflow experiments delete` / `mlflow runs delete` / `mlflow gc`
+//! - `dvc destroy` (removes all DVC metadata from a project)
+//! - `dvc gc` (deletes cached data versions)
+//! - `dvc remove` (stops tracking outputs, optionally deleting them)
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the ML Ops pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "ml_ops".to_string(),
+        name: "ML Ops",
+        description: "Protects against destructive ML experiment tracking and data versioning \
+                      operations like deleting wandb artifacts, mlflow experiments, and DVC \
+                      caches or metadata.",
+        keywords: &["wandb", "mlflow", "dvc"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        safe_pattern!("wandb-status", r"wandb\s+status\b"),
+        safe_pattern!("wandb-artifact-ls", r"wandb\s+artifact\s+(?:ls|get)\b"),
+        safe_pattern!(
+            "mlflow-list",
+            r"mlflow\s+(?:experiments|runs)\s+(?:list|search|describe|get)\b"
+        ),
+        safe_pattern!(
+            "dvc-status",
+            r"dvc\s+(?:status|diff|list|ls|doctor|version)\b"
+        ),
+        safe_pattern!(
+            "dvc-show",
+            r"dvc\s+(?:metrics|params|plots|exp)\s+(?:show|diff)\b"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "dvc-destroy",
+            r"dvc(?:\s+--?\S+)*\s+destroy\b",
+            "dvc destroy removes all DVC metadata and cached data from the project.",
+            Critical,
+            "dvc destroy tears down DVC in the current repository:\n\n\
+             - Deletes the .dvc/ directory, including the local cache\n\
+             - Removes all .dvc files and dvc.yaml/dvc.lock stage files\n\
+             - Data not pushed to a remote is permanently lost\n\n\
+             Push data first: dvc push\n\
+             Check what is tracked: dvc status"
+        ),
+        destructive_pattern!(
+            "dvc-gc",
+            r"dvc(?:\s+--?\S+)*\s+gc\b",
+            "dvc gc deletes cached data versions that are not referenced by the workspace.",
+            High,
+            "dvc gc garbage-collects the DVC cache:\n\n\
+             - Removes cached versions not used by the selected revisions\n\
+             - With --cloud, also deletes objects from remote storage\n\
+             - Older dataset and model versions become unrecoverable\n\n\
+             Scope it explicitly: dvc gc --all-commits --all-branches\n\
+             Preview usage first: dvc status --cloud"
+        ),
+        destructive_pattern!(
+            "dvc-remove",
+            r"dvc(?:\s+--?\S+)*\s+remove\b",
+            "dvc remove stops tracking outputs and can delete them from the workspace.",
+            Medium,
+            "dvc remove drops stages or .dvc files:\n\n\
+             - Stops DVC tracking for the given targets\n\
+             - With --outs, also deletes the outputs from the workspace\n\
+             - Cached data remains until dvc gc is run\n\n\
+             Check first: dvc status"
+        ),
+        destructive_pattern!(
+            "wandb-artifact-delete",
+            r"wandb\s+artifact\s+(?:delete|rm)\b",
+            "wandb artifact delete permanently removes artifact versions.",
+            High,
+            "wandb artifact delete removes artifact versions from W&B:\n\n\
+             - Deleted artifact versions cannot be restored\n\
+             - Runs and downstream jobs that reference the artifact will break\n\n\
+             List versions first: wandb artifact ls <project>"
+        ),
+        destructive_pattern!(
+            "mlflow-experiment-delete",
+            r"mlflow\s+(?:experiments\s+delete|delete-experiment)\b",
+            "mlflow experiment deletion removes the experiment and all of its runs.",
+            High,
+            "mlflow experiments delete marks an experiment as deleted:\n\n\
+             - All runs in the experiment are hidden from the UI\n\
+             - A subsequent mlflow gc permanently removes them\n\n\
+             Restore if needed: mlflow experiments restore --experiment-id <id>"
+        ),
+        destructive_pattern!(
+            "mlflow-run-delete",
+            r"mlflow\s+runs\s+delete\b",
+            "mlflow runs delete removes a tracked run.",
+            Medium,
+            "mlflow runs delete marks a run as deleted:\n\n\
+             - The run disappears from experiment views\n\
+             - A subsequent mlflow gc permanently removes it\n\n\
+             Restore if needed: mlflow runs restore --run-id <id>"
+        ),
+        destructive_pattern!(
+            "mlflow-gc",
+            r"mlflow\s+gc\b",
+            "mlflow gc permanently deletes runs and artifacts marked as deleted.",
+            High,
+            "mlflow gc purges deleted runs from the backend store:\n\n\
+             - Run metadata and artifacts are permanently removed\n\
+             - Deleted experiments can no longer be restored\n\n\
+             Review deleted runs first: mlflow runs list --view deleted_only"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "ml_ops");
+        assert_eq!(pack.name, "ML Ops");
+        assert!(!pack.description.is_empty());
+        assert!(pack.keywords.contains(&"wandb"));
+        assert!(pack.keywords.contains(&"mlflow"));
+        assert!(pack.keywords.contains(&"dvc"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn dvc_destroy_is_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "dvc destroy", "dvc-destroy");
+        assert_blocks_with_pattern(&pack, "dvc destroy -f", "dvc-destroy");
+        assert_blocks_with_severity(&pack, "dvc destroy", Severity::Critical);
+    }
+
+    #[test]
+    fn dvc_gc_is_high() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "dvc gc", "dvc-gc");
+        assert_blocks_with_pattern(&pack, "dvc gc --workspace --cloud -f", "dvc-gc");
+        assert_blocks_with_severity(&pack, "dvc gc -w", Severity::High);
+    }
+
+    #[test]
+    fn blocks_destructive_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "dvc remove data.csv.dvc", "dvc-remove");
+        assert_blocks_with_pattern(
+            &pack,
+            "wandb artifact delete team/project/model:v3",
+            "wandb-artifact-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "mlflow experiments delete --experiment-id 12",
+            "mlflow-experiment-delete",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "mlflow runs delete --run-id abc",
+            "mlflow-run-delete",
+        );
+        assert_blocks_with_pattern(&pack, "mlflow gc --older-than 30d", "mlflow-gc");
+    }
+
+    #[test]
+    fn allows_safe_commands() {
+        let pack = create_pack();
+        assert_safe_pattern_matches(&pack, "wandb status");
+        assert_safe_pattern_matches(&pack, "wandb artifact ls team/project");
+        assert_safe_pattern_matches(&pack, "mlflow experiments search");
+        assert_safe_pattern_matches(&pack, "mlflow runs list --experiment-id 1");
+        assert_safe_pattern_matches(&pack, "dvc status");
+        assert_safe_pattern_matches(&pack, "dvc list . data");
+        assert_safe_pattern_matches(&pack, "dvc metrics show");
+        assert_allows(&pack, "dvc push");
+        assert_allows(&pack, "dvc pull");
+    }
+}
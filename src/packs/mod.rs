@@ -25,10 +25,12 @@ pub mod dns;
 pub mod email;
 pub mod external;
 pub mod featureflags;
+pub mod gpg_and_crypto;
 pub mod infrastructure;
 pub mod kubernetes;
 pub mod loadbalancer;
 pub mod messaging;
+pub mod ml_ops;
 pub mod monitoring;
 pub mod package_managers;
 pub mod payment;
@@ -38,9 +40,13 @@ pub mod remote;
 pub mod safe;
 pub mod search;
 pub mod secrets;
+pub mod source;
+pub mod sql_migration;
 pub mod storage;
 pub mod strict_git;
 pub mod system;
+pub mod vcs;
+pub mod windows;
 
 // Testing infrastructure
 pub mod test_helpers;
@@ -50,8 +56,9 @@ mod test_template;
 pub use crate::normalize::normalize_command;
 use memchr::memmem;
 use regex_engine::LazyCompiledRegex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
+use source::PackSource;
 use std::collections::{HashMap, HashSet};
 use std::sync::{LazyLock, OnceLock};
 
@@ -65,7 +72,7 @@ pub type PackId = String;
 /// - **High**: Block by default, but allowlistable by rule ID.
 /// - **Medium**: Warn by default (log + continue), blockable via config.
 /// - **Low**: Log only (for history/learning), warneable/blockable via config.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// Always block. Irreversible operations with high confidence.
@@ -103,6 +110,16 @@ impl Severity {
         matches!(self, Self::Critical | Self::High)
     }
 
+    /// Get the next stricter severity level (Critical stays Critical).
+    #[must_use]
+    pub const fn escalated(&self) -> Self {
+        match self {
+            Self::Critical | Self::High => Self::Critical,
+            Self::Medium => Self::High,
+            Self::Low => Self::Medium,
+        }
+    }
+
     /// Get a human-readable label for this severity.
     #[must_use]
     pub const fn label(&self) -> &'static str {
@@ -127,13 +144,18 @@ pub enum DecisionMode {
 
     /// Log only (silent allow, record for history).
     Log,
+
+    /// Hold the command until the user confirms it (hook `ask` response).
+    Ask,
 }
 
 impl DecisionMode {
     /// Returns true if this mode blocks command execution.
+    ///
+    /// `Ask` counts as blocking: the agent cannot run the command on its own.
     #[must_use]
     pub const fn blocks(&self) -> bool {
-        matches!(self, Self::Deny)
+        matches!(self, Self::Deny | Self::Ask)
     }
 
     /// Get a human-readable label for this mode.
@@ -143,6 +165,7 @@ impl DecisionMode {
             Self::Deny => "deny",
             Self::Warn => "warn",
             Self::Log => "log",
+            Self::Ask => "ask",
         }
     }
 }
@@ -281,6 +304,10 @@ pub struct DestructivePattern {
     /// Safer command alternatives to suggest when this pattern matches.
     /// Each suggestion includes the command, why it's safer, and which platforms it applies to.
     pub suggestions: &'static [PatternSuggestion],
+    /// Whether this pattern participates in matching.
+    /// Built-in patterns are always enabled; external packs can ship a rule
+    /// disabled and have it turned on through `packs.enable_patterns`.
+    pub enabled: bool,
 }
 
 impl std::fmt::Debug for DestructivePattern {
@@ -292,6 +319,7 @@ impl std::fmt::Debug for DestructivePattern {
             .field("severity", &self.severity)
             .field("explanation", &self.explanation)
             .field("suggestions", &self.suggestions)
+            .field("enabled", &self.enabled)
             .finish()
     }
 }
@@ -331,6 +359,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::High,
             explanation: None,
             suggestions: &[],
+            enabled: true,
         }
     };
     // Named pattern, default severity (High)
@@ -342,6 +371,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::High,
             explanation: None,
             suggestions: &[],
+            enabled: true,
         }
     };
     // Named pattern with explicit severity
@@ -353,6 +383,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::$severity,
             explanation: None,
             suggestions: &[],
+            enabled: true,
         }
     };
     // Named pattern with explicit severity and explanation
@@ -364,6 +395,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::$severity,
             explanation: Some($explanation),
             suggestions: &[],
+            enabled: true,
         }
     };
     // Named pattern with explicit severity, explanation, and suggestions
@@ -375,6 +407,7 @@ macro_rules! destructive_pattern {
             severity: $crate::packs::Severity::$severity,
             explanation: Some($explanation),
             suggestions: $suggestions,
+            enabled: true,
         }
     };
 }
@@ -505,13 +538,71 @@ impl Pack {
         self.safe_patterns.iter().any(|p| p.regex.is_match(cmd))
     }
 
+    /// Name of the first safe pattern that matches a command, if any.
+    #[must_use]
+    pub fn matching_safe_pattern(&self, cmd: &str) -> Option<&'static str> {
+        self.safe_patterns
+            .iter()
+            .find(|p| p.regex.is_match(cmd))
+            .map(|p| p.name)
+    }
+
+    /// Check if this pack's safe patterns clear a command.
+    ///
+    /// Safe patterns are unanchored, so in a compound command a safe segment
+    /// must not clear a destructive sibling (`git checkout -b tmp && git reset
+    /// --hard`). The command is cleared only if it matches a safe pattern and
+    /// no segment that fails the safe patterns matches a destructive one on
+    /// its own.
+    #[must_use]
+    pub fn matches_safe_command(&self, cmd: &str) -> bool {
+        if !self.matches_safe(cmd) {
+            return false;
+        }
+
+        let tokens = crate::normalize::tokenize_for_normalization(cmd);
+        if !tokens
+            .iter()
+            .any(|t| t.kind == crate::normalize::NormalizeTokenKind::Separator)
+        {
+            return true;
+        }
+
+        let mut segment_start: Option<usize> = None;
+        let mut segment_end = 0;
+        let mut segments = Vec::new();
+        for token in &tokens {
+            match token.kind {
+                crate::normalize::NormalizeTokenKind::Separator => {
+                    if let Some(start) = segment_start.take() {
+                        segments.push(start..segment_end);
+                    }
+                }
+                crate::normalize::NormalizeTokenKind::Word => {
+                    segment_start.get_or_insert(token.byte_range.start);
+                    segment_end = token.byte_range.end;
+                }
+            }
+        }
+        if let Some(start) = segment_start {
+            segments.push(start..segment_end);
+        }
+
+        segments.into_iter().all(|range| {
+            let segment = &cmd[range];
+            !self.might_match(segment)
+                || self.matches_safe(segment)
+                || self.matches_destructive(segment).is_none()
+        })
+    }
+
     /// Check if a command matches any destructive pattern.
     /// Returns the matched pattern's reason, name, severity, and explanation if found.
     #[must_use]
     pub fn matches_destructive(&self, cmd: &str) -> Option<DestructiveMatch> {
         self.destructive_patterns
             .iter()
-            .find(|p| p.regex.is_match(cmd))
+            .find(|p| p.enabled && p.regex.is_match(cmd))
             .map(|p| DestructiveMatch {
                 reason: p.reason,
                 name: p.name,
@@ -530,7 +621,7 @@ impl Pack {
         }
 
         // Check safe patterns first (whitelist)
-        if self.matches_safe(cmd) {
+        if self.matches_safe_command(cmd) {
             return None;
         }
 
@@ -793,9 +884,53 @@ impl EnabledKeywordIndex {
     }
 }
 
+/// Alternate pack IDs and the built-in pack that holds their rules.
+///
+/// These rules were added to an existing pack rather than a new one, so the
+/// requested IDs resolve to it wherever a pack ID is accepted (`[packs]`,
+/// `DCG_PACKS`, `dcg packs info`).
+pub const PACK_ALIASES: &[(&str, &str)] = &[
+    ("iac.terraform", "infrastructure.terraform"),
+    ("iac.ansible", "infrastructure.ansible"),
+    ("cloud.aws_s3", "storage.s3"),
+    ("cloud.gcloud", "cloud.gcp"),
+    ("storage.zfs", "system.disk"),
+    ("storage.lvm", "system.disk"),
+    ("vcs.git-lfs", "vcs.git_lfs"),
+];
+
+/// Alternate pack IDs whose rules were split across several built-in packs.
+///
+/// Like a category, enabling or disabling one of these IDs applies to every
+/// listed pack.
+pub const PACK_GROUP_ALIASES: &[(&str, &[&str])] =
+    &[("vcs.forge", &["platform.github", "platform.gitlab"])];
+
+/// Resolve a pack ID alias to the registered pack ID (other IDs are returned as-is).
+#[must_use]
+pub fn canonical_pack_id(id: &str) -> &str {
+    PACK_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == id)
+        .map_or(id, |(_, target)| target)
+}
+
+/// Resolve a pack ID to the registered pack IDs it stands for: the targets of
+/// a group alias, otherwise [`canonical_pack_id`].
+#[must_use]
+pub fn resolve_pack_ids(id: &str) -> Vec<&str> {
+    PACK_GROUP_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == id)
+        .map_or_else(
+            || vec![canonical_pack_id(id)],
+            |(_, targets)| targets.to_vec(),
+        )
+}
+
 /// Static pack entries - metadata is available without instantiating packs.
 /// Packs are built lazily on first access.
-static PACK_ENTRIES: [PackEntry; 82] = [
+static PACK_ENTRIES: [PackEntry; 92] = [
     PackEntry::new("core.git", &["git"], core::git::create_pack),
     PackEntry::new(
         "core.filesystem",
@@ -821,6 +956,11 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         remote::ssh::create_pack,
     ),
     PackEntry::new("remote.scp", &["scp"], remote::scp::create_pack),
+    PackEntry::new(
+        "remote.pipe_to_shell",
+        &["curl", "wget"],
+        remote::pipe_to_shell::create_pack,
+    ),
     PackEntry::new(
         "cicd.github_actions",
         &["gh"],
@@ -1171,7 +1311,7 @@ static PACK_ENTRIES: [PackEntry; 82] = [
     ),
     PackEntry::new(
         "infrastructure.terraform",
-        &["terraform", "tofu"],
+        &["terraform", "tofu", "terragrunt"],
         infrastructure::terraform::create_pack,
     ),
     PackEntry::new(
@@ -1204,12 +1344,19 @@ static PACK_ENTRIES: [PackEntry; 82] = [
             "lvresize",
             "pvmove",
             "lvconvert",
+            "zfs",
+            "zpool",
         ],
         system::disk::create_pack,
     ),
+    PackEntry::new(
+        "system.packages",
+        &["apt", "dnf", "yum", "pacman"],
+        system::packages::create_pack,
+    ),
     PackEntry::new(
         "system.permissions",
-        &["chmod", "chown", "setfacl"],
+        &["chmod", "chown", "setfacl", "chattr"],
         system::permissions::create_pack,
     ),
     PackEntry::new(
@@ -1217,7 +1364,27 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         &["systemctl", "service"],
         system::services::create_pack,
     ),
+    PackEntry::new(
+        "system.shell_config",
+        &[
+            ".bashrc",
+            ".zshrc",
+            ".profile",
+            ".bash_profile",
+            "authorized_keys",
+            ".ssh/config",
+            ">|",
+        ],
+        system::shell_config::create_pack,
+    ),
+    PackEntry::new("system.mv", &["mv"], system::mv::create_pack),
+    PackEntry::new(
+        "system.systemd",
+        &["systemctl", "journalctl"],
+        system::systemd::create_pack,
+    ),
     PackEntry::new("strict_git", &["git"], strict_git::create_pack),
+    PackEntry::new("vcs.git_lfs", &["lfs"], vcs::git_lfs::create_pack),
     PackEntry::new(
         "package_managers",
         &[
@@ -1225,6 +1392,31 @@ static PACK_ENTRIES: [PackEntry; 82] = [
         ],
         package_managers::create_pack,
     ),
+    PackEntry::new("ml_ops", &["wandb", "mlflow", "dvc"], ml_ops::create_pack),
+    PackEntry::new(
+        "sql_migration",
+        &[
+            "rails",
+            "rake",
+            "alembic",
+            "artisan",
+            "manage.py",
+            "django-admin",
+            "knex",
+            "prisma",
+        ],
+        sql_migration::create_pack,
+    ),
+    PackEntry::new(
+        "gpg_and_crypto",
+        &["gpg", "cryptsetup", "ssh-keygen", "openssl"],
+        gpg_and_crypto::create_pack,
+    ),
+    PackEntry::new(
+        "windows.powershell",
+        &["Remove-Item", "Clear-Content", "Format-Volume"],
+        windows::powershell::create_pack,
+    ),
 ];
 
 impl PackRegistry {
@@ -1288,15 +1480,38 @@ impl PackRegistry {
     /// This instantiates the pack lazily on first access.
     #[must_use]
     pub fn get(&self, id: &str) -> Option<&Pack> {
-        self.index.get(id).map(|&idx| self.entries[idx].get_pack())
+        self.index
+            .get(canonical_pack_id(id))
+            .map(|&idx| self.entries[idx].get_pack())
     }
 
-    /// Get all pack IDs.
+    /// Get all pack IDs, sorted lexicographically.
     ///
     /// This is a **metadata-only** operation - does not instantiate packs.
     #[must_use]
     pub fn all_pack_ids(&self) -> Vec<&'static str> {
-        self.entries.iter().map(|e| e.id).collect()
+        let mut ids: Vec<&'static str> = self.entries.iter().map(|e| e.id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Iterate over every registered pack in [`Self::all_pack_ids`] order.
+    ///
+    /// Packs are instantiated lazily as the iterator advances.
+    pub fn iter(&self) -> impl Iterator<Item = &Pack> {
+        self.all_pack_ids()
+            .into_iter()
+            .filter_map(|id| self.get(id))
+    }
+
+    /// Iterate over the enabled packs (categories expanded to their sub-packs)
+    /// in [`Self::all_pack_ids`] order.
+    pub fn iter_enabled<'a>(
+        &'a self,
+        enabled: &HashSet<String>,
+    ) -> impl Iterator<Item = &'a Pack> + use<'a> {
+        let expanded = self.expand_enabled(enabled);
+        self.iter().filter(move |pack| expanded.contains(&pack.id))
     }
 
     /// Get all categories.
@@ -1328,8 +1543,8 @@ impl PackRegistry {
                     expanded.insert(sub_pack.to_string());
                 }
             }
-            // Also add the ID itself (in case it's a specific pack)
-            expanded.insert(id.clone());
+            // Also add the ID itself (in case it's a specific pack or an alias)
+            expanded.extend(resolve_pack_ids(id).into_iter().map(str::to_string));
         }
 
         expanded
@@ -1341,16 +1556,16 @@ impl PackRegistry {
     /// multiple packs could match the same command. The ordering is:
     ///
     /// 0. **Tier 0 (safe)**: `safe.*` packs - safe patterns checked first to whitelist
-    /// 1. **Tier 1 (core/storage/remote)**: `core.*`, `storage.*`, `remote.*` packs - most fundamental protections
-    /// 2. **Tier 2 (system)**: `system.*` - disk, permissions, services
+    /// 1. **Tier 1 (core/storage/remote/windows)**: `core.*`, `storage.*`, `remote.*`, `windows.*` packs - most fundamental protections
+    /// 2. **Tier 2 (system)**: `system.*` - disk, permissions, services; `gpg_and_crypto`
     /// 3. **Tier 3 (infrastructure)**: `infrastructure.*` - terraform, ansible, pulumi
     /// 4. **Tier 4 (apigateway/cloud/dns/platform/cdn/loadbalancer)**: `apigateway.*`, `cloud.*`, `dns.*`, `platform.*`, `cdn.*`, `loadbalancer.*`
     /// 5. **Tier 5 (kubernetes)**: `kubernetes.*` - kubectl, helm, kustomize
     /// 6. **Tier 6 (containers)**: `containers.*` - docker, compose, podman
-    /// 7. **Tier 7 (database/search/messaging/backup)**: `database.*`, `search.*`, `messaging.*`, `backup.*`
+    /// 7. **Tier 7 (database/search/messaging/backup)**: `database.*`, `search.*`, `messaging.*`, `backup.*`, `sql_migration`
     /// 8. **Tier 8 (`package_managers`)**: package manager protections
-    /// 9. **Tier 9 (`strict_git`)**: extra git paranoia
-    /// 10. **Tier 10 (services)**: `cicd.*`, `email.*`, `featureflags.*`, `secrets.*`, `monitoring.*`, `payment.*`
+    /// 9. **Tier 9 (`strict_git`/vcs)**: extra git paranoia; `vcs.*` - git lfs
+    /// 10. **Tier 10 (services)**: `cicd.*`, `email.*`, `featureflags.*`, `secrets.*`, `monitoring.*`, `payment.*`, `ml_ops`
     ///
     /// Within each tier, packs are sorted lexicographically by ID.
     #[must_use]
@@ -1381,16 +1596,18 @@ impl PackRegistry {
         let category = pack_id.split('.').next().unwrap_or(pack_id);
         match category {
             "safe" => 0,
-            "core" | "storage" | "remote" => 1,
-            "system" => 2,
+            "core" | "storage" | "remote" | "windows" => 1,
+            "system" | "gpg_and_crypto" => 2,
             "infrastructure" => 3,
             "apigateway" | "cdn" | "cloud" | "dns" | "loadbalancer" | "platform" => 4,
             "kubernetes" => 5,
             "containers" => 6,
-            "backup" | "database" | "messaging" | "search" => 7,
+            "backup" | "database" | "messaging" | "search" | "sql_migration" => 7,
             "package_managers" => 8,
-            "strict_git" => 9,
-            "cicd" | "email" | "featureflags" | "secrets" | "monitoring" | "payment" => 10, // CI/CD + email + feature flags + secrets + monitoring + payment tooling
+            "strict_git" | "vcs" => 9,
+            "cicd" | "email" | "featureflags" | "secrets" | "monitoring" | "payment" | "ml_ops" => {
+                10 // CI/CD + email + feature flags + secrets + monitoring + payment + ML tooling
+            }
             _ => 11, // Unknown categories go last
         }
     }
@@ -1440,7 +1657,7 @@ impl PackRegistry {
         // If any pack's safe pattern matches, allow the command immediately.
         // This enables "safe" packs (like `safe.cleanup`) to whitelist commands across pack boundaries.
         for (_pack_id, pack) in &candidate_packs {
-            if pack.matches_safe(cmd) {
+            if pack.matches_safe_command(cmd) {
                 return CheckResult::allowed();
             }
         }
@@ -1642,6 +1859,55 @@ impl ExternalPackStore {
         }
     }
 
+    /// Build a store from pack sources.
+    ///
+    /// Sources are loaded in order; a later pack with the same ID replaces an
+    /// earlier one. Loading is fail-open: source errors become warnings.
+    #[must_use]
+    pub fn from_sources(sources: &[Box<dyn PackSource>]) -> Self {
+        let mut store = Self::new();
+
+        for source in sources {
+            let (packs, warnings) = source.load_fail_open();
+            store.warnings.extend(warnings);
+
+            for pack in packs {
+                // Collect keywords
+                for kw in pack.keywords {
+                    if !store.keywords.contains(kw) {
+                        store.keywords.push(kw);
+                    }
+                }
+
+                store.packs.insert(pack.id.clone(), pack);
+            }
+        }
+
+        store
+    }
+
+    /// Turn on patterns that their pack ships with `enabled: false`.
+    ///
+    /// Each entry is a rule ID (`pack-id:pattern-name`). Entries that don't name
+    /// a loaded external pattern are recorded as warnings.
+    pub fn enable_patterns(&mut self, rule_ids: &[String]) {
+        for rule_id in rule_ids {
+            let pattern = rule_id.split_once(':').and_then(|(pack_id, name)| {
+                self.packs
+                    .get_mut(pack_id)?
+                    .destructive_patterns
+                    .iter_mut()
+                    .find(|p| p.name == Some(name))
+            });
+            match pattern {
+                Some(pattern) => pattern.enabled = true,
+                None => self.warnings.push(format!(
+                    "enable_patterns: no external pattern named '{rule_id}'"
+                )),
+            }
+        }
+    }
+
     /// Get a pack by ID.
     #[must_use]
     pub fn get(&self, id: &str) -> Option<&Pack> {
@@ -1692,7 +1958,7 @@ impl ExternalPackStore {
             if !enabled_ids.contains(id) {
                 continue;
             }
-            if pack.matches_safe(cmd) {
+            if pack.matches_safe_command(cmd) {
                 return Some(CheckResult::allowed());
             }
         }
@@ -1732,7 +1998,7 @@ impl ExternalPackStore {
             if !enabled_ids.contains(id) {
                 continue;
             }
-            if pack.matches_safe(cmd) {
+            if pack.matches_safe_command(cmd) {
                 return Some(ExternalCheckResult {
                     blocked: false,
                     reason: None,
@@ -1789,53 +2055,26 @@ pub struct ExternalCheckResult {
 /// Global storage for external packs (initialized once at startup).
 static EXTERNAL_PACKS: OnceLock<ExternalPackStore> = OnceLock::new();
 
-/// Load external packs from the given file paths.
+/// Load external packs from the given sources.
 ///
 /// This should be called once at startup after config is loaded.
 /// Subsequent calls are no-ops (returns the already-loaded store).
 ///
 /// # Arguments
 ///
-/// * `paths` - Expanded file paths (after glob/tilde expansion)
+/// * `sources` - Pack sources, usually from `PacksConfig::pack_sources()`
+/// * `enable_patterns` - Rule IDs of disabled patterns to turn on (`packs.enable_patterns`)
 ///
 /// # Returns
 ///
 /// Reference to the external pack store.
-pub fn load_external_packs(paths: &[String]) -> &'static ExternalPackStore {
+pub fn load_external_packs(
+    sources: &[Box<dyn PackSource>],
+    enable_patterns: &[String],
+) -> &'static ExternalPackStore {
     EXTERNAL_PACKS.get_or_init(|| {
-        let mut store = ExternalPackStore::new();
-
-        if paths.is_empty() {
-            return store;
-        }
-
-        let loader = external::ExternalPackLoader::from_paths(paths);
-        let result = loader.load_all_deduped();
-
-        // Collect warnings
-        for warning in result.warnings {
-            store.warnings.push(format!(
-                "Failed to load external pack from {}: {}",
-                warning.path.display(),
-                warning.error
-            ));
-        }
-
-        // Convert and store loaded packs
-        for loaded in result.packs {
-            let id = loaded.id.clone();
-            let pack = loaded.pack.into_pack();
-
-            // Collect keywords
-            for kw in pack.keywords {
-                if !store.keywords.contains(kw) {
-                    store.keywords.push(kw);
-                }
-            }
-
-            store.packs.insert(id, pack);
-        }
-
+        let mut store = ExternalPackStore::from_sources(sources);
+        store.enable_patterns(enable_patterns);
         store
     })
 }
@@ -2129,6 +2368,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn disk_pack_entry_routes_zfs_and_zpool() {
+        // The registry gates packs on PACK_ENTRIES keywords before building
+        // them, so these must be listed for the ZFS rules to ever run.
+        let entry = REGISTRY
+            .get_entry("system.disk")
+            .expect("system.disk entry");
+        for keyword in ["zfs", "zpool"] {
+            assert!(entry.keywords.contains(&keyword), "{keyword}");
+            assert!(entry.get_pack().keywords.contains(&keyword), "{keyword}");
+        }
+    }
+
+    #[test]
+    fn pack_aliases_resolve_to_registered_packs() {
+        for (alias, target) in PACK_ALIASES {
+            assert!(REGISTRY.get_entry(target).is_some(), "{alias} -> {target}");
+            assert_eq!(
+                REGISTRY.get(alias).map(|pack| pack.id.as_str()),
+                Some(*target)
+            );
+            let enabled = HashSet::from([(*alias).to_string()]);
+            assert_eq!(
+                REGISTRY.expand_enabled_ordered(&enabled),
+                vec![(*target).to_string()]
+            );
+        }
+        for (alias, targets) in PACK_GROUP_ALIASES {
+            let enabled = HashSet::from([(*alias).to_string()]);
+            let mut expanded = REGISTRY.expand_enabled_ordered(&enabled);
+            expanded.sort();
+            assert_eq!(expanded, targets.to_vec(), "{alias}");
+        }
+    }
+
+    #[test]
+    fn terraform_pack_entry_routes_terragrunt() {
+        let entry = REGISTRY
+            .get_entry("infrastructure.terraform")
+            .expect("infrastructure.terraform entry");
+        assert!(entry.keywords.contains(&"terragrunt"));
+        assert!(entry.get_pack().keywords.contains(&"terragrunt"));
+    }
+
     #[test]
     fn pack_aware_quick_reject_ignores_substring_matches() {
         let keywords: Vec<&str> = vec!["git", "rm", "docker"];
@@ -2291,10 +2574,12 @@ mod tests {
         assert_eq!(PackRegistry::pack_tier("core.filesystem"), 1);
         assert_eq!(PackRegistry::pack_tier("storage.s3"), 1);
         assert_eq!(PackRegistry::pack_tier("remote.rsync"), 1);
+        assert_eq!(PackRegistry::pack_tier("windows.powershell"), 1);
 
         // System should be tier 2
         assert_eq!(PackRegistry::pack_tier("system.disk"), 2);
         assert_eq!(PackRegistry::pack_tier("system.permissions"), 2);
+        assert_eq!(PackRegistry::pack_tier("gpg_and_crypto"), 2);
 
         // Infrastructure should be tier 3
         assert_eq!(PackRegistry::pack_tier("infrastructure.terraform"), 3);
@@ -2321,6 +2606,7 @@ mod tests {
         assert_eq!(PackRegistry::pack_tier("backup.rclone"), 7);
         assert_eq!(PackRegistry::pack_tier("backup.restic"), 7);
         assert_eq!(PackRegistry::pack_tier("backup.velero"), 7);
+        assert_eq!(PackRegistry::pack_tier("sql_migration"), 7);
         assert_eq!(PackRegistry::pack_tier("messaging.kafka"), 7);
         assert_eq!(PackRegistry::pack_tier("search.elasticsearch"), 7);
 
@@ -2329,6 +2615,7 @@ mod tests {
 
         // Strict git should be tier 9
         assert_eq!(PackRegistry::pack_tier("strict_git"), 9);
+        assert_eq!(PackRegistry::pack_tier("vcs.git_lfs"), 9);
 
         // Tier 10 service packs should be tier 10
         assert_eq!(PackRegistry::pack_tier("cicd.github_actions"), 10);
@@ -2338,6 +2625,7 @@ mod tests {
         assert_eq!(PackRegistry::pack_tier("email.ses"), 10);
         assert_eq!(PackRegistry::pack_tier("featureflags.launchdarkly"), 10);
         assert_eq!(PackRegistry::pack_tier("secrets.vault"), 10);
+        assert_eq!(PackRegistry::pack_tier("ml_ops"), 10);
         assert_eq!(PackRegistry::pack_tier("monitoring.splunk"), 10);
         assert_eq!(PackRegistry::pack_tier("payment.stripe"), 10);
 
@@ -2502,6 +2790,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn all_pack_ids_are_sorted_and_iteration_matches() {
+        let ids = REGISTRY.all_pack_ids();
+        assert!(ids.contains(&"core.git"));
+        assert!(ids.windows(2).all(|w| w[0] < w[1]), "ids must be sorted");
+        assert_eq!(ids.len(), REGISTRY.pack_count());
+        assert_eq!(ids, REGISTRY.all_pack_ids(), "ordering must be stable");
+
+        let iterated: Vec<&str> = REGISTRY.iter().map(|pack| pack.id.as_str()).collect();
+        assert_eq!(iterated, ids);
+
+        let enabled = HashSet::from(["containers".to_string(), "core.git".to_string()]);
+        let enabled_ids: Vec<&str> = REGISTRY
+            .iter_enabled(&enabled)
+            .map(|pack| pack.id.as_str())
+            .collect();
+        assert_eq!(
+            enabled_ids,
+            [
+                "containers.compose",
+                "containers.docker",
+                "containers.podman",
+                "core.git"
+            ]
+        );
+    }
+
     /// Test category expansion produces ordered results.
     #[test]
     fn category_expansion_is_ordered() {
@@ -2749,6 +3064,7 @@ mod tests {
         // These rules should ALWAYS be Critical - they're the most dangerous
         let critical_rules = [
             "reset-hard",
+            "checkout-discard",
             "clean-force",
             "push-force-long",
             "push-force-short",
@@ -2804,11 +3120,12 @@ mod tests {
 
         // These should be at least High (blocking by default)
         let high_or_above_rules = [
-            "checkout-discard",
+            "checkout-discard-pathspec",
             "checkout-ref-discard",
             "restore-worktree",
             "restore-worktree-explicit",
             "reset-merge",
+            "reset-hard-pathspec",
         ];
 
         for rule_name in high_or_above_rules {
@@ -2838,9 +3155,15 @@ mod tests {
     fn core_rules_have_appropriate_severity() {
         // Patterns that should be Medium (recoverable operations)
         let medium_patterns = [
-            ("core.git", "branch-force-delete"), // Recoverable via reflog
-            ("core.git", "stash-drop"),          // Recoverable via fsck
+            ("core.git", "branch-force-delete"),  // Recoverable via reflog
+            ("core.git", "tag-delete"),           // Recreatable from the printed SHA
+            ("core.git", "stash-drop"),           // Recoverable via fsck
+            ("core.git", "worktree-prune"),       // Only drops metadata for missing dirs
+            ("core.git", "checkout-path-like"),   // Ambiguous: may be a branch switch
+            ("core.filesystem", "rm-r-var-root"), // No -f; only hazardous if unset
         ];
+        // Patterns that should be Low (metadata only)
+        let low_patterns = [("core.git", "notes-remove")];
 
         for pack_id in ["core.git", "core.filesystem"] {
             let pack = REGISTRY.get(pack_id).expect("Pack should exist");
@@ -2851,11 +3174,20 @@ mod tests {
                     .iter()
                     .any(|(pid, pname)| *pid == pack_id && *pname == name);
 
+                let is_expected_low = low_patterns
+                    .iter()
+                    .any(|(pid, pname)| *pid == pack_id && *pname == name);
+
                 if is_expected_medium {
                     assert!(
                         matches!(pattern.severity, Severity::Medium),
                         "Core pack rule {pack_id}:{name} should be Medium severity (recoverable)"
                     );
+                } else if is_expected_low {
+                    assert!(
+                        matches!(pattern.severity, Severity::Low),
+                        "Core pack rule {pack_id}:{name} should be Low severity (metadata only)"
+                    );
                 } else {
                     assert!(
                         pattern.severity.blocks_by_default(),
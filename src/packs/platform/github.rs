@@ -68,6 +68,10 @@ fn create_safe_patterns() -> Vec<SafePattern> {
             "gh-variable-list",
             r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:repo|gist|release|issue|ssh-key|secret|variable|run|auth|status|api)\b)(?:(?:\x22[^\x22]*\x22)|(?:'[^']*')|\S+))?)*\s+variable\s+list\b"
         ),
+        safe_pattern!(
+            "gh-pr-list-view",
+            r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:repo|gist|release|issue|ssh-key|secret|variable|run|auth|status|api)\b)(?:(?:\x22[^\x22]*\x22)|(?:'[^']*')|\S+))?)*\s+pr\s+(?:list|view|status)\b"
+        ),
         safe_pattern!(
             "gh-auth-status",
             r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:repo|gist|release|issue|ssh-key|secret|variable|run|auth|status|api)\b)(?:(?:\x22[^\x22]*\x22)|(?:'[^']*')|\S+))?)*\s+auth\s+status\b"
@@ -89,7 +93,15 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         destructive_pattern!(
             "gh-repo-delete",
             r"gh(?:\s+--?[A-Za-z][A-Za-z0-9-]*\b(?:\s+(?!(?:repo|gist|release|issue|ssh-key|secret|variable|run|auth|status|api)\b)(?:(?:\x22[^\x22]*\x22)|(?:'[^']*')|\S+))?)*\s+repo\s+delete\b",
-            "gh repo delete permanently deletes a GitHub repository. This cannot be undone."
+            "gh repo delete permanently deletes a GitHub repository. This cannot be undone.",
+            Critical,
+            "Deleting a repository removes its code, issues, pull requests, wikis, releases, \
+             and Actions history for every collaborator at once. Forks are not a backup of \
+             issues or pull requests, and the CLI gives no grace period.\n\n\
+             Safer alternatives:\n\
+             - gh repo archive owner/repo: Make the repository read-only instead\n\
+             - gh repo clone owner/repo -- --mirror: Take a full backup first\n\
+             - gh repo edit owner/repo --visibility private: Hide it without deleting"
         ),
         destructive_pattern!(
             "gh-repo-archive",
@@ -188,6 +200,8 @@ mod tests {
         assert!(pack.check("gh variable list").is_none());
         assert!(pack.check("gh auth status").is_none());
         assert!(pack.check("gh status").is_none());
+        assert!(pack.check("gh pr list").is_none());
+        assert!(pack.check("gh pr view 42").is_none());
 
         // With global flags
         assert!(pack.check("gh -R owner/repo repo view").is_none());
@@ -244,4 +258,15 @@ mod tests {
             assert_eq!(matched.name, Some(expected_rule), "Command: {cmd}");
         }
     }
+
+    #[test]
+    fn repo_delete_is_critical_and_release_delete_high() {
+        let pack = create_pack();
+        let repo = pack
+            .check("gh repo delete owner/repo --yes")
+            .expect("match");
+        assert_eq!(repo.severity, crate::packs::Severity::Critical);
+        let release = pack.check("gh release delete v1.0 --yes").expect("match");
+        assert_eq!(release.severity, crate::packs::Severity::High);
+    }
 }
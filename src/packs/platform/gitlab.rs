@@ -77,7 +77,14 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         destructive_pattern!(
             "glab-repo-delete",
             r"glab(?:\s+--?\S+(?:\s+\S+)?)*\s+repo\s+delete\b",
-            "glab repo delete permanently deletes a GitLab project."
+            "glab repo delete permanently deletes a GitLab project.",
+            Critical,
+            "Deleting a project removes its repository, issues, merge requests, CI/CD \
+             history, and registries for every member at once. Depending on instance \
+             settings the deletion is immediate or only briefly delayed.\n\n\
+             Safer alternatives:\n\
+             - glab repo archive group/project: Make the project read-only instead\n\
+             - glab repo clone group/project -- --mirror: Take a full backup first"
         ),
         destructive_pattern!(
             "glab-repo-archive",
@@ -154,6 +161,11 @@ mod tests {
     fn test_repo_delete_blocked() {
         let pack = create_pack();
         assert_blocks_with_pattern(&pack, "glab repo delete my/group", "glab-repo-delete");
+        assert_blocks_with_severity(
+            &pack,
+            "glab repo delete my/group --yes",
+            crate::packs::Severity::Critical,
+        );
     }
 
     #[test]
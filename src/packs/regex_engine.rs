@@ -7,12 +7,16 @@
 //! This module provides:
 //! - [`CompiledRegex`]: Eagerly compiled abstraction that auto-selects engine
 //! - [`LazyCompiledRegex`]: Lazily compiled regex using `CompiledRegex` (for pack patterns)
+//! - [`compile_shared`]: Process-wide, LRU-bounded cache of compiled patterns
+//!   (for custom packs that are rebuilt on reload)
+//! - [`adversarial_inputs`]: ReDoS probe inputs derived from a pattern's syntax tree
 //!
 //! The lazy variant avoids regex compilation during pack registry initialization,
 //! improving startup latency for the common allow-path case.
 
 use std::borrow::Cow;
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 
 /// A compiled regex that auto-selects between linear-time and backtracking engines.
 ///
@@ -118,6 +122,26 @@ impl CompiledRegex {
         }
     }
 
+    /// Find the first match and return the spans of all capture groups.
+    ///
+    /// Index 0 is the whole match; groups that did not participate are `None`.
+    /// For backtracking engine, returns `None` on regex execution errors.
+    #[must_use]
+    pub fn captures(&self, text: &str) -> Option<Vec<Option<(usize, usize)>>> {
+        match self {
+            Self::Linear(re) => re.captures(text).map(|caps| {
+                caps.iter()
+                    .map(|m| m.map(|m| (m.start(), m.end())))
+                    .collect()
+            }),
+            Self::Backtracking(re) => re.captures(text).ok().flatten().map(|caps| {
+                caps.iter()
+                    .map(|m| m.map(|m| (m.start(), m.end())))
+                    .collect()
+            }),
+        }
+    }
+
     /// Get the pattern string.
     #[must_use]
     pub fn as_str(&self) -> &str {
@@ -201,6 +225,99 @@ pub fn needs_backtracking_engine(pattern: &str) -> bool {
     false
 }
 
+// ============================================================================
+// Shared Compiled-Regex Cache
+// ============================================================================
+
+/// Maximum number of patterns kept in the shared compiled-regex cache.
+///
+/// Sized well above the pattern count of typical custom pack sets so that a
+/// reload never evicts patterns that are still in use.
+pub const SHARED_REGEX_CACHE_CAPACITY: usize = 1024;
+
+/// LRU-bounded map from pattern text to its compiled regex.
+///
+/// Recency is tracked with a monotonic tick per entry; eviction scans for the
+/// oldest tick, which is cheap at this capacity and only happens on insert.
+#[derive(Debug)]
+struct SharedRegexCache {
+    entries: HashMap<String, (Arc<CompiledRegex>, u64)>,
+    tick: u64,
+    capacity: usize,
+}
+
+impl SharedRegexCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            tick: 0,
+            capacity,
+        }
+    }
+
+    fn get(&mut self, pattern: &str) -> Option<Arc<CompiledRegex>> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(pattern).map(|(compiled, last_used)| {
+            *last_used = tick;
+            Arc::clone(compiled)
+        })
+    }
+
+    /// Insert a compiled pattern, returning the cached entry if another
+    /// thread inserted the same pattern first.
+    fn insert(&mut self, pattern: &str, compiled: Arc<CompiledRegex>) -> Arc<CompiledRegex> {
+        if let Some(existing) = self.get(pattern) {
+            return existing;
+        }
+        if self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries
+            .insert(pattern.to_string(), (Arc::clone(&compiled), self.tick));
+        compiled
+    }
+}
+
+static SHARED_REGEX_CACHE: OnceLock<Mutex<SharedRegexCache>> = OnceLock::new();
+
+/// Compile a pattern through the process-wide shared cache.
+///
+/// Custom packs are rebuilt from scratch on every reload (remote pack sources
+/// with a TTL, long-lived embedders). Routing their patterns through this
+/// cache means an unchanged pattern reuses the already-compiled regex instead
+/// of paying for compilation again. The cache holds at most
+/// [`SHARED_REGEX_CACHE_CAPACITY`] patterns, evicting the least recently used.
+///
+/// Compile errors are not cached. Compilation happens outside the lock, so
+/// two threads may compile the same new pattern concurrently; the first to
+/// insert wins and both receive that entry.
+///
+/// # Errors
+/// Returns an error if the pattern fails to compile.
+pub fn compile_shared(pattern: &str) -> Result<Arc<CompiledRegex>, String> {
+    let cache = SHARED_REGEX_CACHE
+        .get_or_init(|| Mutex::new(SharedRegexCache::new(SHARED_REGEX_CACHE_CAPACITY)));
+
+    if let Some(compiled) = cache.lock().ok().and_then(|mut c| c.get(pattern)) {
+        return Ok(compiled);
+    }
+
+    let compiled = Arc::new(CompiledRegex::new(pattern)?);
+    Ok(match cache.lock() {
+        Ok(mut c) => c.insert(pattern, compiled),
+        // A poisoned lock only disables caching; the regex itself is fine.
+        Err(_) => compiled,
+    })
+}
+
 // ============================================================================
 // Lazy Regex Primitive
 // ============================================================================
@@ -237,7 +354,9 @@ pub fn needs_backtracking_engine(pattern: &str) -> bool {
 #[derive(Debug)]
 pub struct LazyCompiledRegex {
     pattern: PatternText,
-    compiled: OnceLock<Result<CompiledRegex, String>>,
+    /// Compile through [`compile_shared`] instead of privately.
+    shared: bool,
+    compiled: OnceLock<Result<Arc<CompiledRegex>, String>>,
 }
 
 #[derive(Debug)]
@@ -264,6 +383,20 @@ impl LazyCompiledRegex {
     pub const fn new(pattern: &'static str) -> Self {
         Self {
             pattern: PatternText::Static(pattern),
+            shared: false,
+            compiled: OnceLock::new(),
+        }
+    }
+
+    /// Create a new lazy regex pattern that compiles through the shared cache.
+    ///
+    /// Used for custom pack patterns, which are rebuilt on every reload; see
+    /// [`compile_shared`].
+    #[must_use]
+    pub const fn new_shared(pattern: &'static str) -> Self {
+        Self {
+            pattern: PatternText::Static(pattern),
+            shared: true,
             compiled: OnceLock::new(),
         }
     }
@@ -274,6 +407,7 @@ impl LazyCompiledRegex {
     pub fn new_owned(pattern: String) -> Self {
         Self {
             pattern: PatternText::Owned(pattern),
+            shared: false,
             compiled: OnceLock::new(),
         }
     }
@@ -282,8 +416,18 @@ impl LazyCompiledRegex {
     ///
     /// Returns `None` if compilation fails (fail-open).
     fn get_compiled(&self) -> Option<&CompiledRegex> {
+        self.get_compiled_arc().map(AsRef::as_ref)
+    }
+
+    fn get_compiled_arc(&self) -> Option<&Arc<CompiledRegex>> {
         self.compiled
-            .get_or_init(|| CompiledRegex::new(self.pattern.as_str()))
+            .get_or_init(|| {
+                if self.shared {
+                    compile_shared(self.pattern.as_str())
+                } else {
+                    CompiledRegex::new(self.pattern.as_str()).map(Arc::new)
+                }
+            })
             .as_ref()
             .ok()
     }
@@ -322,6 +466,18 @@ impl LazyCompiledRegex {
     pub fn is_compiled(&self) -> bool {
         matches!(self.compiled.get(), Some(Ok(_)))
     }
+
+    /// Check whether both patterns resolved to the same compiled regex object.
+    ///
+    /// Compiles both patterns if needed. Only patterns built with
+    /// [`Self::new_shared`] can share a compiled regex.
+    #[must_use]
+    pub fn shares_compiled_with(&self, other: &Self) -> bool {
+        match (self.get_compiled_arc(), other.get_compiled_arc()) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
 }
 
 impl std::fmt::Display for LazyCompiledRegex {
@@ -330,6 +486,144 @@ impl std::fmt::Display for LazyCompiledRegex {
     }
 }
 
+// =============================================================================
+// Adversarial input generation (ReDoS self-test)
+// =============================================================================
+
+/// Upper bound on the length of a generated adversarial input.
+///
+/// Long enough to expose polynomial backtracking, short enough that a
+/// linear-time engine scans it in microseconds.
+const ADVERSARIAL_MAX_LEN: usize = 4096;
+
+/// How many times an unbounded repetition is pumped per nesting level.
+const ADVERSARIAL_PUMP: usize = 64;
+
+/// Filler characters tried (in order) when a character class must be rendered.
+///
+/// Each ordering favours a different character family so that adjacent
+/// classes that overlap (e.g. `\s+` next to `[^;]*`) get fed ambiguous input.
+const ADVERSARIAL_FILLERS: [&[char]; 3] = [
+    &['a', 'x', '0', '-', '/', '.', '_', '=', ' ', 'A'],
+    &[' ', '\t', 'a', '0', '-', '/', '.'],
+    &['-', '/', '.', '0', 'a', ' ', '='],
+];
+
+/// Generate inputs designed to trigger catastrophic backtracking in `pattern`.
+///
+/// The pattern is parsed into its syntax tree and rendered into strings
+/// where every repetition is pumped far past its minimum, alternations
+/// inside repetitions rotate between branches, and character classes are
+/// filled with overlapping characters. Each rendering is also truncated at
+/// every top-level element boundary and suffixed with an unmatchable
+/// character, forcing the engine to fail after exploring the pumped prefix.
+///
+/// Returns an empty list if the pattern cannot be parsed.
+#[must_use]
+pub fn adversarial_inputs(pattern: &str) -> Vec<String> {
+    let Ok(tree) = fancy_regex::Expr::parse_tree(pattern) else {
+        return Vec::new();
+    };
+    let parts: Vec<&fancy_regex::Expr> = match &tree.expr {
+        fancy_regex::Expr::Concat(children) => children.iter().collect(),
+        other => vec![other],
+    };
+
+    let mut inputs: Vec<String> = Vec::new();
+    for fillers in ADVERSARIAL_FILLERS {
+        let mut renderer = AdversarialRenderer::new(fillers);
+        for end in 1..=parts.len() {
+            let mut body = String::new();
+            for part in &parts[..end] {
+                renderer.render(part, 0, &mut body);
+            }
+            for suffix in ["", "\u{1}"] {
+                let input = format!("{body}{suffix}");
+                if !inputs.contains(&input) {
+                    inputs.push(input);
+                }
+            }
+        }
+    }
+    inputs
+}
+
+/// Renders a parsed regex into a single adversarial string.
+struct AdversarialRenderer {
+    fillers: &'static [char],
+    class_cache: std::collections::HashMap<String, String>,
+}
+
+impl AdversarialRenderer {
+    fn new(fillers: &'static [char]) -> Self {
+        Self {
+            fillers,
+            class_cache: std::collections::HashMap::new(),
+        }
+    }
+
+    fn render(&mut self, expr: &fancy_regex::Expr, seed: usize, out: &mut String) {
+        use fancy_regex::Expr;
+
+        if out.len() >= ADVERSARIAL_MAX_LEN {
+            return;
+        }
+        match expr {
+            Expr::Any { .. } => out.push(self.fillers[0]),
+            Expr::Literal { val, .. } => out.push_str(val),
+            Expr::Concat(children) => {
+                for child in children {
+                    self.render(child, seed, out);
+                }
+            }
+            Expr::Alt(branches) => {
+                if let Some(branch) = branches.get(seed % branches.len().max(1)) {
+                    self.render(branch, seed, out);
+                }
+            }
+            Expr::Group(child) | Expr::AtomicGroup(child) => self.render(child, seed, out),
+            Expr::Repeat { child, lo, hi, .. } => {
+                let count = if *hi > *lo {
+                    (*lo).max(ADVERSARIAL_PUMP).min(*hi)
+                } else {
+                    *lo
+                };
+                for i in 0..count {
+                    if out.len() >= ADVERSARIAL_MAX_LEN {
+                        break;
+                    }
+                    self.render(child, seed.wrapping_add(i), out);
+                }
+            }
+            Expr::Delegate { inner, casei, .. } => {
+                let sample = self.class_sample(inner, *casei);
+                out.push_str(&sample);
+            }
+            // Zero-width or context-dependent constructs contribute no text.
+            _ => {}
+        }
+    }
+
+    /// Pick the first filler character accepted by a delegated character class.
+    fn class_sample(&mut self, inner: &str, casei: bool) -> String {
+        if let Some(cached) = self.class_cache.get(inner) {
+            return cached.clone();
+        }
+        let flags = if casei { "(?i)" } else { "" };
+        let sample = regex::Regex::new(&format!("^{flags}(?:{inner})"))
+            .ok()
+            .and_then(|re| {
+                self.fillers
+                    .iter()
+                    .map(char::to_string)
+                    .find_map(|c| re.find(&c).map(|m| c[..m.end()].to_string()))
+            })
+            .unwrap_or_default();
+        self.class_cache.insert(inner.to_string(), sample.clone());
+        sample
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -680,6 +974,67 @@ mod tests {
         assert_eq!(lazy.find("test"), Some((0, 0)));
     }
 
+    #[test]
+    fn test_adversarial_inputs_pump_nested_quantifiers() {
+        let inputs = adversarial_inputs(r"(a+)+b");
+
+        // Nested repetition is pumped well past its minimum, and a truncated
+        // variant without the trailing `b` forces the engine to fail.
+        assert!(
+            inputs
+                .iter()
+                .any(|i| i.len() >= 1000 && i.chars().all(|c| c == 'a'))
+        );
+        assert!(inputs.iter().all(|i| i.len() <= ADVERSARIAL_MAX_LEN + 1));
+    }
+
+    #[test]
+    fn test_adversarial_inputs_fill_character_classes() {
+        let inputs = adversarial_inputs(r"rm\s+[^;&|]*\s+-rf");
+        assert!(inputs.iter().any(|i| i.starts_with("rm   ")));
+        assert!(
+            inputs
+                .iter()
+                .any(|i| i.starts_with("rm ") && i.contains("aaaa"))
+        );
+        assert!(adversarial_inputs(r"(unclosed").is_empty());
+    }
+
+    #[test]
+    fn test_shared_cache_evicts_least_recently_used() {
+        let mut cache = SharedRegexCache::new(2);
+        let a = cache.insert("a+", Arc::new(CompiledRegex::new("a+").unwrap()));
+        cache.insert("b+", Arc::new(CompiledRegex::new("b+").unwrap()));
+
+        // Touch "a+" so "b+" becomes the eviction candidate.
+        assert!(Arc::ptr_eq(&cache.get("a+").unwrap(), &a));
+        cache.insert("c+", Arc::new(CompiledRegex::new("c+").unwrap()));
+
+        assert_eq!(cache.entries.len(), 2);
+        assert!(cache.get("a+").is_some());
+        assert!(cache.get("b+").is_none());
+        assert!(cache.get("c+").is_some());
+    }
+
+    #[test]
+    fn test_shared_cache_keeps_first_insert() {
+        let mut cache = SharedRegexCache::new(4);
+        let first = cache.insert("x", Arc::new(CompiledRegex::new("x").unwrap()));
+        let second = cache.insert("x", Arc::new(CompiledRegex::new("x").unwrap()));
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_lazy_shared_regex_reuses_compiled_across_instances() {
+        let a = LazyCompiledRegex::new_shared(r"shared-cache-test\s+(?=--yes)");
+        let b = LazyCompiledRegex::new_shared(r"shared-cache-test\s+(?=--yes)");
+        assert!(a.shares_compiled_with(&b));
+        assert!(b.is_match("shared-cache-test --yes"));
+
+        let private = LazyCompiledRegex::new(r"shared-cache-test\s+(?=--yes)");
+        assert!(!a.shares_compiled_with(&private));
+    }
+
     #[test]
     fn test_lazy_regex_reuses_compiled() {
         let lazy = LazyCompiledRegex::new(r"test");
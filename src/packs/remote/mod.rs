@@ -1,5 +1,6 @@
 //! Remote access packs - protections for remote sync and access tooling.
 
+pub mod pipe_to_shell;
 pub mod rsync;
 pub mod scp;
 pub mod ssh;
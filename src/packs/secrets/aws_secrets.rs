@@ -1,6 +1,8 @@
 //! `AWS` Secrets Manager + `SSM` pack - protections for destructive secrets operations.
 //!
 //! Blocks delete and mutation commands that can remove or overwrite secrets.
+//! `delete-secret --force-delete-without-recovery`, which skips the recovery
+//! window, has its own rule ahead of the generic delete-secret rule.
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -65,6 +67,20 @@ fn create_safe_patterns() -> Vec<SafePattern> {
 
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        destructive_pattern!(
+            "aws-secretsmanager-force-delete-secret",
+            r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+secretsmanager\s+delete-secret\b.*\s--force-delete-without-recovery\b",
+            "aws secretsmanager delete-secret --force-delete-without-recovery permanently deletes the secret with no recovery window.",
+            Critical,
+            "--force-delete-without-recovery skips the recovery window entirely. The secret \
+             and all of its versions are deleted immediately and cannot be restored with \
+             restore-secret. Applications using this secret will fail to authenticate or \
+             decrypt data.\n\n\
+             Safer alternatives:\n\
+             - aws secretsmanager get-secret-value: Export value first\n\
+             - Drop the flag and use --recovery-window-in-days 7 (or longer)\n\
+             - aws secretsmanager restore-secret: Undo a scheduled deletion"
+        ),
         destructive_pattern!(
             "aws-secretsmanager-delete-secret",
             r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+secretsmanager\s+delete-secret\b",
@@ -162,6 +178,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::Severity;
     use crate::packs::test_helpers::*;
 
     #[test]
@@ -192,6 +209,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_force_delete_without_recovery_is_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "aws secretsmanager delete-secret --secret-id my/secret --force-delete-without-recovery",
+            "aws-secretsmanager-force-delete-secret",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws --profile prod secretsmanager delete-secret --force-delete-without-recovery --secret-id my/secret",
+            "aws-secretsmanager-force-delete-secret",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "aws secretsmanager delete-secret --secret-id my/secret --force-delete-without-recovery",
+            Severity::Critical,
+        );
+        // Without the flag the generic delete-secret rule matches.
+        assert_blocks_with_pattern(
+            &pack,
+            "aws secretsmanager delete-secret --secret-id my/secret --recovery-window-in-days 7",
+            "aws-secretsmanager-delete-secret",
+        );
+    }
+
     #[test]
     fn test_update_and_put_secret_value_blocked() {
         let pack = create_pack();
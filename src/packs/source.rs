@@ -0,0 +1,574 @@
+//! Pluggable sources for external packs.
+//!
+//! A [`PackSource`] produces packs from some backend. Two sources are built in:
+//!
+//! - [`FilePackSource`]: YAML files matched by `[packs] custom_paths`
+//! - [`HttpPackSource`]: a YAML bundle fetched from `[packs] remote` and cached
+//!   on disk for `remote_ttl_secs`
+//!
+//! Loading is fail-open. A source that cannot produce packs contributes a
+//! warning and evaluation continues with the built-in packs (and, for remote
+//! sources, the last successfully fetched bundle).
+//!
+//! Fetching needs the `remote-packs` cargo feature. Hook mode never fetches:
+//! it reads the cached bundle and leaves refreshing to `dcg pack refresh`.
+//!
+//! # Remote Bundle Format
+//!
+//! The remote endpoint returns one or more pack documents in a single YAML
+//! stream, separated by `---`. Each document follows the same schema as a
+//! custom pack file.
+
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use super::Pack;
+use super::external::{
+    ExternalPack, ExternalPackLoader, PackParseError, validate_pack_with_collision_check,
+};
+
+/// Default time a fetched remote bundle is reused before refetching.
+pub const DEFAULT_REMOTE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Upper bound on a remote fetch.
+#[cfg(feature = "remote-packs")]
+const FETCH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A backend that provides external packs.
+pub trait PackSource {
+    /// Human-readable label for diagnostics (a path, glob list, or URL).
+    fn describe(&self) -> String;
+
+    /// Load every pack this source provides.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PackLoadError`] if the source cannot produce a usable set of packs.
+    fn load(&self) -> Result<Vec<Pack>, PackLoadError>;
+
+    /// Load packs without failing, reporting problems as warnings.
+    ///
+    /// The default turns an error from [`load`](Self::load) into a single
+    /// warning. Sources that can partially succeed override this.
+    fn load_fail_open(&self) -> (Vec<Pack>, Vec<String>) {
+        match self.load() {
+            Ok(packs) => (packs, Vec::new()),
+            Err(e) => (
+                Vec::new(),
+                vec![format!(
+                    "Failed to load packs from {}: {e}",
+                    self.describe()
+                )],
+            ),
+        }
+    }
+}
+
+/// Errors that can occur when loading packs from a source.
+#[derive(Debug)]
+pub enum PackLoadError {
+    /// A pack definition failed to parse or validate.
+    Parse {
+        /// Where the definition came from (file path or URL).
+        origin: String,
+        error: PackParseError,
+    },
+
+    /// A remote source could not be fetched and no cached copy exists.
+    Fetch { url: String, reason: String },
+}
+
+impl fmt::Display for PackLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse { origin, error } => write!(f, "{origin}: {error}"),
+            Self::Fetch { url, reason } => write!(f, "Failed to fetch {url}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for PackLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse { error, .. } => Some(error),
+            Self::Fetch { .. } => None,
+        }
+    }
+}
+
+/// Packs loaded from YAML files on disk.
+#[derive(Debug, Clone)]
+pub struct FilePackSource {
+    paths: Vec<String>,
+}
+
+impl FilePackSource {
+    /// Create a source from concrete file paths (already glob-expanded).
+    #[must_use]
+    pub const fn new(paths: Vec<String>) -> Self {
+        Self { paths }
+    }
+}
+
+impl PackSource for FilePackSource {
+    fn describe(&self) -> String {
+        match self.paths.as_slice() {
+            [single] => single.clone(),
+            paths => format!("{} pack files", paths.len()),
+        }
+    }
+
+    fn load(&self) -> Result<Vec<Pack>, PackLoadError> {
+        let result = ExternalPackLoader::from_paths(&self.paths).load_all_deduped();
+        if let Some(warning) = result.warnings.into_iter().next() {
+            return Err(PackLoadError::Parse {
+                origin: warning.path.display().to_string(),
+                error: warning.error,
+            });
+        }
+        Ok(result
+            .packs
+            .into_iter()
+            .map(|loaded| loaded.pack.into_pack())
+            .collect())
+    }
+
+    fn load_fail_open(&self) -> (Vec<Pack>, Vec<String>) {
+        let result = ExternalPackLoader::from_paths(&self.paths).load_all_deduped();
+        let warnings = result
+            .warnings
+            .into_iter()
+            .map(|warning| {
+                format!(
+                    "Failed to load external pack from {}: {}",
+                    warning.path.display(),
+                    warning.error
+                )
+            })
+            .collect();
+        let packs = result
+            .packs
+            .into_iter()
+            .map(|loaded| loaded.pack.into_pack())
+            .collect();
+        (packs, warnings)
+    }
+}
+
+/// Packs fetched over HTTP(S) and cached on disk.
+///
+/// A cached bundle younger than the TTL is used without touching the network.
+/// Otherwise the bundle is refetched; if the fetch fails or the response does
+/// not validate, the stale cached copy is used instead. Every fetch attempt
+/// is stamped next to the cache, so a failing endpoint is retried at most
+/// once per TTL.
+///
+/// A [`cache_only`](Self::cache_only) source never fetches; hook mode uses it
+/// and calls [`needs_refresh`](Self::needs_refresh) to decide whether to start
+/// a background refresh.
+#[derive(Debug, Clone)]
+pub struct HttpPackSource {
+    url: String,
+    ttl: Duration,
+    cache_path: Option<PathBuf>,
+    cache_only: bool,
+}
+
+impl HttpPackSource {
+    /// Create a source for `url`, caching under the user cache directory.
+    #[must_use]
+    pub fn new(url: impl Into<String>, ttl: Duration) -> Self {
+        let url = url.into();
+        let cache_path = dirs::cache_dir().map(|dir| {
+            dir.join("dcg")
+                .join("remote_packs")
+                .join(format!("{}.yaml", url_digest(&url)))
+        });
+        Self {
+            url,
+            ttl,
+            cache_path,
+            cache_only: false,
+        }
+    }
+
+    /// Override where the fetched bundle is cached.
+    #[must_use]
+    pub fn with_cache_path(mut self, path: PathBuf) -> Self {
+        self.cache_path = Some(path);
+        self
+    }
+
+    /// Serve only the cached bundle and never fetch, even when it is stale.
+    #[must_use]
+    pub const fn cache_only(mut self) -> Self {
+        self.cache_only = true;
+        self
+    }
+
+    /// Whether the cache is stale and no fetch was attempted within the TTL.
+    #[must_use]
+    pub fn needs_refresh(&self) -> bool {
+        let Some(path) = self.cache_path.as_deref() else {
+            return false;
+        };
+        !self.is_fresh(path) && !self.is_fresh(&attempt_stamp_path(path))
+    }
+
+    /// Fetch the bundle now and replace the cache, returning the pack count.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PackLoadError`] if the fetch fails or the bundle does not
+    /// validate; the cached copy is left in place.
+    pub fn refresh(&self) -> Result<usize, PackLoadError> {
+        self.stamp_attempt();
+        let body = self.fetch()?;
+        let packs = parse_pack_bundle(&body, &self.url)?;
+        self.write_cache(&body);
+        Ok(packs.len())
+    }
+
+    fn is_fresh(&self, path: &Path) -> bool {
+        fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < self.ttl)
+    }
+
+    /// Read the cached bundle, returning its contents and whether it is fresh.
+    fn read_cache(&self) -> Option<(String, bool)> {
+        let path = self.cache_path.as_deref()?;
+        let contents = fs::read_to_string(path).ok()?;
+        Some((contents, self.is_fresh(path)))
+    }
+
+    /// Record that a fetch is being attempted, so failures back off for a TTL.
+    fn stamp_attempt(&self) {
+        let Some(path) = self.cache_path.as_deref() else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let stamp = attempt_stamp_path(path);
+        if let Ok(file) = fs::File::create(&stamp) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+    }
+
+    fn write_cache(&self, contents: &str) {
+        let Some(path) = self.cache_path.as_deref() else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(path, contents);
+    }
+
+    #[cfg(not(feature = "remote-packs"))]
+    fn fetch(&self) -> Result<String, PackLoadError> {
+        Err(PackLoadError::Fetch {
+            url: self.url.clone(),
+            reason: "dcg was built without the `remote-packs` feature".to_string(),
+        })
+    }
+
+    #[cfg(feature = "remote-packs")]
+    fn fetch(&self) -> Result<String, PackLoadError> {
+        let fetch_error = |reason: String| PackLoadError::Fetch {
+            url: self.url.clone(),
+            reason,
+        };
+        let client = reqwest::blocking::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .user_agent(concat!("dcg/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| fetch_error(e.to_string()))?;
+        client
+            .get(&self.url)
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status)
+            .and_then(reqwest::blocking::Response::text)
+            .map_err(|e| fetch_error(e.to_string()))
+    }
+
+    fn load_cached(&self, contents: &str) -> Result<Vec<Pack>, PackLoadError> {
+        let origin = self
+            .cache_path
+            .as_deref()
+            .map_or_else(|| self.url.clone(), |path| path.display().to_string());
+        parse_pack_bundle(contents, &origin)
+    }
+}
+
+impl PackSource for HttpPackSource {
+    fn describe(&self) -> String {
+        self.url.clone()
+    }
+
+    fn load(&self) -> Result<Vec<Pack>, PackLoadError> {
+        let cached = self.read_cache();
+        if let Some((contents, true)) = &cached {
+            return self.load_cached(contents);
+        }
+        if self.cache_only {
+            return match cached {
+                Some((contents, _)) => self.load_cached(&contents),
+                None => Err(PackLoadError::Fetch {
+                    url: self.url.clone(),
+                    reason: "not cached yet; run `dcg pack refresh`".to_string(),
+                }),
+            };
+        }
+        if !self.needs_refresh() {
+            // A recent attempt failed; keep serving the stale copy until the TTL passes.
+            if let Some((contents, false)) = &cached {
+                return self.load_cached(contents);
+            }
+        }
+
+        self.stamp_attempt();
+        let fetched = self
+            .fetch()
+            .and_then(|body| parse_pack_bundle(&body, &self.url).map(|packs| (body, packs)));
+        match (fetched, cached) {
+            (Ok((body, packs)), _) => {
+                self.write_cache(&body);
+                Ok(packs)
+            }
+            (Err(_), Some((contents, false))) => self.load_cached(&contents),
+            (Err(e), _) => Err(e),
+        }
+    }
+}
+
+/// Parse a multi-document YAML stream into validated packs.
+fn parse_pack_bundle(contents: &str, origin: &str) -> Result<Vec<Pack>, PackLoadError> {
+    let parse_error = |error: PackParseError| PackLoadError::Parse {
+        origin: origin.to_string(),
+        error,
+    };
+    let mut packs = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(contents) {
+        let pack = ExternalPack::deserialize(document).map_err(|e| parse_error(e.into()))?;
+        validate_pack_with_collision_check(&pack).map_err(parse_error)?;
+        packs.push(pack.into_pack());
+    }
+    Ok(packs)
+}
+
+/// Marker whose mtime records the last fetch attempt for a cached bundle.
+fn attempt_stamp_path(cache_path: &Path) -> PathBuf {
+    cache_path.with_extension("attempt")
+}
+
+/// Stable, filesystem-safe cache key for a URL.
+fn url_digest(url: &str) -> String {
+    let digest = Sha256::digest(url.as_bytes());
+    let mut hex = String::with_capacity(16);
+    for byte in &digest[..8] {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::ExternalPackStore;
+    use std::collections::HashSet;
+
+    const DEPLOY_PACK: &str = r"
+schema_version: 1
+id: acme.deploy
+name: Acme Deploy
+version: 1.0.0
+keywords: [deploy]
+destructive_patterns:
+  - name: prod
+    pattern: deploy\s+--env\s*=?\s*prod
+    description: Direct production deployment
+";
+
+    const RELEASE_PACK: &str = r"
+schema_version: 1
+id: acme.release
+name: Acme Release
+version: 1.0.0
+keywords: [release]
+destructive_patterns:
+  - name: yank
+    pattern: release\s+yank
+    description: Yanking a release
+";
+
+    struct MockSource {
+        result: fn() -> Result<Vec<Pack>, PackLoadError>,
+    }
+
+    impl PackSource for MockSource {
+        fn describe(&self) -> String {
+            "mock://packs".to_string()
+        }
+
+        fn load(&self) -> Result<Vec<Pack>, PackLoadError> {
+            (self.result)()
+        }
+    }
+
+    #[test]
+    fn store_merges_sources_and_fails_open() {
+        let sources: Vec<Box<dyn PackSource>> = vec![
+            Box::new(MockSource {
+                result: || parse_pack_bundle(DEPLOY_PACK, "mock"),
+            }),
+            Box::new(MockSource {
+                result: || {
+                    Err(PackLoadError::Fetch {
+                        url: "mock://packs".to_string(),
+                        reason: "unreachable".to_string(),
+                    })
+                },
+            }),
+        ];
+
+        let store = ExternalPackStore::from_sources(&sources);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.warnings().len(), 1);
+        assert!(store.warnings()[0].contains("mock://packs"));
+
+        let enabled: HashSet<String> = store.pack_ids().cloned().collect();
+        let blocked = store
+            .check_command("deploy --env prod", &enabled)
+            .expect("mock pack should match");
+        assert!(blocked.blocked);
+        assert!(store.check_command("deploy --env dev", &enabled).is_none());
+    }
+
+    #[test]
+    fn disabled_pattern_matches_only_once_enabled_by_config() {
+        let sources: Vec<Box<dyn PackSource>> = vec![Box::new(MockSource {
+            result: || {
+                parse_pack_bundle(
+                    r"
+schema_version: 1
+id: acme.deploy
+name: Acme Deploy
+version: 1.0.0
+keywords: [deploy]
+destructive_patterns:
+  - name: prod
+    pattern: deploy\s+--env\s*=?\s*prod
+  - name: experimental-rule
+    pattern: deploy\s+--skip-checks
+    enabled: false
+",
+                    "mock",
+                )
+            },
+        })];
+
+        let mut store = ExternalPackStore::from_sources(&sources);
+        let enabled: HashSet<String> = store.pack_ids().cloned().collect();
+        assert!(store.check_command("deploy --env prod", &enabled).is_some());
+        assert!(
+            store
+                .check_command("deploy --skip-checks", &enabled)
+                .is_none()
+        );
+
+        let config: crate::config::PacksConfig = toml::from_str(
+            r#"enable_patterns = ["acme.deploy:experimental-rule", "acme.deploy:missing"]"#,
+        )
+        .unwrap();
+        store.enable_patterns(&config.enable_patterns);
+
+        let blocked = store
+            .check_command("deploy --skip-checks", &enabled)
+            .expect("enabled pattern should match");
+        assert_eq!(blocked.pattern_name.as_deref(), Some("experimental-rule"));
+        assert_eq!(store.warnings().len(), 1);
+        assert!(store.warnings()[0].contains("acme.deploy:missing"));
+    }
+
+    #[test]
+    fn reloading_a_source_reuses_compiled_regexes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deploy.yaml");
+        fs::write(&path, DEPLOY_PACK).unwrap();
+        let source = FilePackSource::new(vec![path.display().to_string()]);
+
+        let first = source.load().expect("first load");
+        let reloaded = source.load().expect("reload");
+        let first = &first[0].destructive_patterns[0].regex;
+        let reloaded = &reloaded[0].destructive_patterns[0].regex;
+        assert!(first.shares_compiled_with(reloaded));
+        assert!(reloaded.is_match("deploy --env prod"));
+
+        let bundle = parse_pack_bundle(DEPLOY_PACK, "mock").unwrap();
+        assert!(first.shares_compiled_with(&bundle[0].destructive_patterns[0].regex));
+    }
+
+    #[test]
+    fn bundle_parses_multiple_documents() {
+        let bundle = format!("{DEPLOY_PACK}---{RELEASE_PACK}");
+        let packs = parse_pack_bundle(&bundle, "test").expect("bundle should parse");
+        let ids: Vec<&str> = packs.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["acme.deploy", "acme.release"]);
+
+        let err = parse_pack_bundle("schema_version: 1\nid: core.git\n", "test").unwrap_err();
+        assert!(matches!(err, PackLoadError::Parse { .. }));
+    }
+
+    #[test]
+    fn http_source_uses_cache_when_fresh_or_fetch_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("bundle.yaml");
+        fs::write(&cache, DEPLOY_PACK).unwrap();
+        // Nothing listens on the discard port, so any fetch attempt fails fast.
+        let url = "http://127.0.0.1:9/packs.yaml";
+
+        let fresh = HttpPackSource::new(url, DEFAULT_REMOTE_TTL).with_cache_path(cache.clone());
+        assert_eq!(fresh.load().unwrap().len(), 1);
+
+        let stale = HttpPackSource::new(url, Duration::ZERO).with_cache_path(cache);
+        assert_eq!(stale.load().unwrap().len(), 1);
+
+        let uncached = HttpPackSource::new(url, Duration::ZERO)
+            .with_cache_path(dir.path().join("missing.yaml"));
+        assert!(matches!(uncached.load(), Err(PackLoadError::Fetch { .. })));
+    }
+
+    #[test]
+    fn cache_only_source_never_fetches_and_failures_back_off() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("bundle.yaml");
+        let url = "http://127.0.0.1:9/packs.yaml";
+
+        let source = HttpPackSource::new(url, DEFAULT_REMOTE_TTL).with_cache_path(cache.clone());
+        assert!(source.needs_refresh());
+        let err = source.clone().cache_only().load().unwrap_err();
+        assert!(err.to_string().contains("dcg pack refresh"), "{err}");
+        assert!(!attempt_stamp_path(&cache).exists());
+
+        // A failed refresh is stamped, so it is not retried within the TTL.
+        assert!(source.refresh().is_err());
+        assert!(!source.needs_refresh());
+        assert!(!cache.exists());
+
+        fs::write(&cache, DEPLOY_PACK).unwrap();
+        let stale = HttpPackSource::new(url, Duration::ZERO)
+            .with_cache_path(cache)
+            .cache_only();
+        assert_eq!(stale.load().unwrap().len(), 1);
+        assert!(stale.needs_refresh());
+    }
+}
@@ -0,0 +1,370 @@
+//! SQL migration pack - protections for framework migration commands.
+//!
+//! Covers commands that drop, reset, or roll back an application database:
+//! - Rails: `rails db:drop`, `rails db:reset`, `rails db:rollback`
+//! - Alembic: `alembic downgrade base` / `alembic downgrade <rev>`
+//! - Laravel: `php artisan migrate:fresh`, `migrate:reset`, `db:wipe`
+//! - Django: `manage.py flush`, `manage.py migrate <app> zero`
+//! - Knex: `knex migrate:rollback --all`
+//! - Prisma: `prisma migrate reset`, `prisma db push --force-reset`
+//!
+//! Forward migrations (`rails db:migrate`, `artisan migrate`, `prisma migrate deploy`, ...)
+//! are allowed.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the SQL migration pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "sql_migration".to_string(),
+        name: "SQL Migrations",
+        description: "Protects against framework migration commands that drop, reset, or roll \
+                      back the application database (Rails, Alembic, Laravel, Django, Knex, \
+                      Prisma).",
+        keywords: &[
+            "rails",
+            "rake",
+            "alembic",
+            "artisan",
+            "manage.py",
+            "django-admin",
+            "knex",
+            "prisma",
+        ],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    // Forward migrations only. Each pattern is anchored to the end of the
+    // command so a chained `&& rails db:drop` is still evaluated.
+    vec![
+        safe_pattern!(
+            "rails-db-migrate",
+            r"\b(?:rails|rake)\s+db:migrate(?::status)?(?:\s[^;&|]*)?$"
+        ),
+        safe_pattern!(
+            "alembic-upgrade",
+            r"alembic(?:\s+--?\S+(?:\s+\S+)?)*\s+(?:upgrade|current|history|heads|check)(?:\s[^;&|]*)?$"
+        ),
+        safe_pattern!(
+            "artisan-migrate",
+            r"artisan\s+migrate(?::status)?(?:\s[^;&|]*)?$"
+        ),
+        safe_pattern!(
+            "django-migrate",
+            r"(?:manage\.py|django-admin)\s+(?!.*\bzero\b)(?:migrate|showmigrations|makemigrations)(?:\s[^;&|]*)?$"
+        ),
+        safe_pattern!(
+            "knex-migrate-latest",
+            r"knex\s+migrate:(?:latest|up|status|list|make)(?:\s[^;&|]*)?$"
+        ),
+        safe_pattern!(
+            "prisma-migrate-deploy",
+            r"prisma\s+migrate\s+(?:deploy|dev|status|diff)(?:\s[^;&|]*)?$"
+        ),
+    ]
+}
+
+#[allow(clippy::too_many_lines)]
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // Rails
+        destructive_pattern!(
+            "rails-db-drop",
+            r"\b(?:rails|rake)\s+(?:\S+\s+)*?db:drop\b",
+            "rails db:drop deletes the application database.",
+            Critical,
+            "rails db:drop drops the database for the current environment:\n\n\
+             - All tables and data are permanently deleted\n\
+             - db:drop:all drops every database in config/database.yml\n\
+             - Nothing is recoverable without a backup\n\n\
+             Roll back a single migration instead: rails db:rollback\n\
+             Check the target first: rails db:migrate:status"
+        ),
+        destructive_pattern!(
+            "rails-db-reset",
+            r"\b(?:rails|rake)\s+(?:\S+\s+)*?db:(?:reset|purge|migrate:reset)\b",
+            "rails db:reset/db:purge drops and recreates the database, deleting all data.",
+            High,
+            "rails db:reset and related tasks rebuild the database from scratch:\n\n\
+             - db:reset drops the database and reloads schema + seeds\n\
+             - db:purge empties the database\n\
+             - db:migrate:reset drops and re-runs every migration\n\n\
+             Apply pending migrations instead: rails db:migrate"
+        ),
+        destructive_pattern!(
+            "rails-db-rollback",
+            r"\b(?:rails|rake)\s+(?:\S+\s+)*?db:(?:rollback|migrate:down|migrate:redo)\b",
+            "rails db:rollback runs down migrations, which can drop tables or columns.",
+            Medium,
+            "rails db:rollback reverts the most recent migrations:\n\n\
+             - down migrations often drop tables or columns\n\
+             - STEP=n reverts several migrations at once\n\n\
+             Review the migration's down method first: rails db:migrate:status"
+        ),
+        // Alembic
+        destructive_pattern!(
+            "alembic-downgrade-base",
+            r"alembic(?:\s+--?\S+(?:\s+\S+)?)*\s+downgrade\s+(?:\S+\s+)*?base\b",
+            "alembic downgrade base reverts every migration, dropping the whole schema.",
+            High,
+            "alembic downgrade base runs every downgrade script:\n\n\
+             - All migrated tables are dropped\n\
+             - Data in those tables is permanently lost\n\n\
+             Downgrade one revision instead: alembic downgrade -1\n\
+             Check the current revision: alembic current"
+        ),
+        destructive_pattern!(
+            "alembic-downgrade",
+            r"alembic(?:\s+--?\S+(?:\s+\S+)?)*\s+downgrade\b",
+            "alembic downgrade runs down migrations, which can drop tables or columns.",
+            Medium,
+            "alembic downgrade reverts migrations to an earlier revision:\n\n\
+             - Downgrade scripts often drop tables or columns\n\
+             - Data in dropped objects is lost\n\n\
+             Preview the SQL first: alembic downgrade <rev> --sql"
+        ),
+        // Laravel
+        destructive_pattern!(
+            "artisan-migrate-fresh",
+            r"artisan\s+(?:migrate:fresh|db:wipe)\b",
+            "artisan migrate:fresh/db:wipe drops every table in the database.",
+            Critical,
+            "php artisan migrate:fresh drops all tables before migrating:\n\n\
+             - Every table is dropped, not just migrated ones\n\
+             - db:wipe drops all tables, views and types without migrating\n\
+             - All data is permanently lost\n\n\
+             Apply pending migrations instead: php artisan migrate\n\
+             Check status first: php artisan migrate:status"
+        ),
+        destructive_pattern!(
+            "artisan-migrate-reset",
+            r"artisan\s+migrate:(?:reset|refresh)\b",
+            "artisan migrate:reset/refresh rolls back every migration, deleting all data.",
+            High,
+            "php artisan migrate:reset rolls back all migrations:\n\n\
+             - Every down method runs, dropping migrated tables\n\
+             - migrate:refresh also re-runs them, leaving empty tables\n\n\
+             Roll back one batch instead: php artisan migrate:rollback"
+        ),
+        destructive_pattern!(
+            "artisan-migrate-rollback",
+            r"artisan\s+migrate:rollback\b",
+            "artisan migrate:rollback runs down migrations, which can drop tables or columns.",
+            Medium,
+            "php artisan migrate:rollback reverts the last migration batch:\n\n\
+             - down methods often drop tables or columns\n\
+             - --step=n reverts several migrations at once\n\n\
+             Preview first: php artisan migrate:rollback --pretend"
+        ),
+        // Django
+        destructive_pattern!(
+            "django-flush",
+            r"(?:manage\.py|django-admin)\s+(?:\S+\s+)*?flush\b",
+            "manage.py flush deletes all data from every table.",
+            High,
+            "Django's flush command empties the database:\n\n\
+             - All rows in all tables are deleted\n\
+             - Users, sessions and content are removed\n\
+             - Schema remains, but data is unrecoverable without a backup\n\n\
+             Dump data first: python manage.py dumpdata > backup.json"
+        ),
+        destructive_pattern!(
+            "django-migrate-zero",
+            r"(?:manage\.py|django-admin)\s+migrate\s+\S+\s+zero\b",
+            "manage.py migrate <app> zero reverts every migration for the app, dropping its tables.",
+            High,
+            "Migrating an app to zero unapplies all of its migrations:\n\n\
+             - Every table created by the app is dropped\n\
+             - Data in those tables is permanently lost\n\n\
+             Check applied migrations: python manage.py showmigrations <app>"
+        ),
+        // Knex
+        destructive_pattern!(
+            "knex-rollback-all",
+            r"knex\s+migrate:rollback\b.*\s--all\b",
+            "knex migrate:rollback --all reverts every migration, dropping the schema.",
+            High,
+            "knex migrate:rollback --all runs every down migration:\n\n\
+             - All migrated tables are dropped\n\
+             - Data in those tables is permanently lost\n\n\
+             Roll back the last batch instead: knex migrate:rollback\n\
+             Check status first: knex migrate:status"
+        ),
+        destructive_pattern!(
+            "knex-rollback",
+            r"knex\s+migrate:(?:rollback|down)\b",
+            "knex migrate:rollback runs down migrations, which can drop tables or columns.",
+            Medium,
+            "knex migrate:rollback reverts the last batch of migrations:\n\n\
+             - down functions often drop tables or columns\n\n\
+             Check status first: knex migrate:status"
+        ),
+        // Prisma
+        destructive_pattern!(
+            "prisma-migrate-reset",
+            r"prisma\s+migrate\s+reset\b",
+            "prisma migrate reset drops the database and re-applies all migrations.",
+            Critical,
+            "prisma migrate reset rebuilds the database from scratch:\n\n\
+             - The database (or schema) is dropped and recreated\n\
+             - All data is permanently lost\n\
+             - Seed scripts run afterwards, masking the data loss\n\n\
+             Apply pending migrations instead: prisma migrate deploy\n\
+             Check status first: prisma migrate status"
+        ),
+        destructive_pattern!(
+            "prisma-db-push-force-reset",
+            r"prisma\s+db\s+push\b.*\s--force-reset\b",
+            "prisma db push --force-reset drops the database before pushing the schema.",
+            Critical,
+            "prisma db push --force-reset resets the database:\n\n\
+             - All tables and data are dropped\n\
+             - The schema is then pushed without migration history\n\n\
+             Push without resetting: prisma db push"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "sql_migration");
+        assert_eq!(pack.name, "SQL Migrations");
+        assert!(!pack.description.is_empty());
+        for keyword in ["rails", "alembic", "artisan", "manage.py", "knex", "prisma"] {
+            assert!(pack.keywords.contains(&keyword), "missing {keyword}");
+        }
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn rails_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "rails db:drop", "rails-db-drop");
+        assert_blocks_with_pattern(&pack, "bin/rails db:drop:all", "rails-db-drop");
+        assert_blocks_with_pattern(&pack, "bundle exec rake db:drop", "rails-db-drop");
+        assert_blocks_with_severity(&pack, "rails db:drop", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "rails db:reset", "rails-db-reset");
+        assert_blocks_with_pattern(&pack, "rails db:migrate:reset", "rails-db-reset");
+        assert_blocks_with_severity(&pack, "rails db:reset", Severity::High);
+        assert_blocks_with_pattern(&pack, "rails db:rollback STEP=3", "rails-db-rollback");
+        assert_blocks_with_severity(&pack, "rails db:rollback", Severity::Medium);
+        assert_safe_pattern_matches(&pack, "rails db:migrate");
+        assert_safe_pattern_matches(&pack, "bin/rails db:migrate RAILS_ENV=test");
+        assert_blocks_with_pattern(&pack, "rails db:migrate && rails db:drop", "rails-db-drop");
+    }
+
+    #[test]
+    fn alembic_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "alembic downgrade base", "alembic-downgrade-base");
+        assert_blocks_with_pattern(
+            &pack,
+            "alembic -c alembic.ini downgrade base",
+            "alembic-downgrade-base",
+        );
+        assert_blocks_with_severity(&pack, "alembic downgrade base", Severity::High);
+        assert_blocks_with_pattern(&pack, "alembic downgrade -1", "alembic-downgrade");
+        assert_blocks_with_severity(&pack, "alembic downgrade -1", Severity::Medium);
+        assert_safe_pattern_matches(&pack, "alembic upgrade head");
+        assert_safe_pattern_matches(&pack, "alembic current");
+    }
+
+    #[test]
+    fn laravel_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "php artisan migrate:fresh", "artisan-migrate-fresh");
+        assert_blocks_with_pattern(
+            &pack,
+            "php artisan migrate:fresh --seed",
+            "artisan-migrate-fresh",
+        );
+        assert_blocks_with_pattern(&pack, "php artisan db:wipe", "artisan-migrate-fresh");
+        assert_blocks_with_severity(&pack, "php artisan migrate:fresh", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "php artisan migrate:reset", "artisan-migrate-reset");
+        assert_blocks_with_pattern(
+            &pack,
+            "php artisan migrate:refresh",
+            "artisan-migrate-reset",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "php artisan migrate:rollback --step=2",
+            "artisan-migrate-rollback",
+        );
+        assert_safe_pattern_matches(&pack, "php artisan migrate");
+        assert_safe_pattern_matches(&pack, "php artisan migrate --force");
+        assert_safe_pattern_matches(&pack, "php artisan migrate:status");
+    }
+
+    #[test]
+    fn django_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "python manage.py flush", "django-flush");
+        assert_blocks_with_pattern(&pack, "python manage.py flush --noinput", "django-flush");
+        assert_blocks_with_pattern(&pack, "django-admin flush", "django-flush");
+        assert_blocks_with_severity(&pack, "python manage.py flush", Severity::High);
+        assert_blocks_with_pattern(
+            &pack,
+            "python manage.py migrate billing zero",
+            "django-migrate-zero",
+        );
+        assert_safe_pattern_matches(&pack, "python manage.py migrate");
+        assert_safe_pattern_matches(&pack, "python manage.py migrate billing 0003");
+        assert_safe_pattern_matches(&pack, "python manage.py showmigrations");
+    }
+
+    #[test]
+    fn knex_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "knex migrate:rollback --all", "knex-rollback-all");
+        assert_blocks_with_pattern(
+            &pack,
+            "npx knex migrate:rollback --env production --all",
+            "knex-rollback-all",
+        );
+        assert_blocks_with_severity(&pack, "knex migrate:rollback --all", Severity::High);
+        assert_blocks_with_pattern(&pack, "knex migrate:rollback", "knex-rollback");
+        assert_blocks_with_severity(&pack, "knex migrate:rollback", Severity::Medium);
+        assert_safe_pattern_matches(&pack, "knex migrate:latest");
+        assert_safe_pattern_matches(&pack, "npx knex migrate:status");
+    }
+
+    #[test]
+    fn prisma_commands() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "prisma migrate reset", "prisma-migrate-reset");
+        assert_blocks_with_pattern(
+            &pack,
+            "npx prisma migrate reset --force",
+            "prisma-migrate-reset",
+        );
+        assert_blocks_with_severity(&pack, "npx prisma migrate reset", Severity::Critical);
+        assert_blocks_with_pattern(
+            &pack,
+            "npx prisma db push --force-reset",
+            "prisma-db-push-force-reset",
+        );
+        assert_safe_pattern_matches(&pack, "npx prisma migrate deploy");
+        assert_safe_pattern_matches(&pack, "prisma migrate status");
+        assert_allows(&pack, "npx prisma generate");
+        assert_allows(&pack, "npx prisma db push");
+    }
+}
@@ -1,7 +1,7 @@
 //! `AWS S3` pack - protections for destructive bucket and object operations.
 //!
 //! Covers destructive CLI operations:
-//! - Bucket removal
+//! - Bucket removal (`rb --force` empties the bucket first)
 //! - Recursive object deletion
 //! - API object deletion
 //! - Sync with delete
@@ -37,6 +37,11 @@ fn create_safe_patterns() -> Vec<SafePattern> {
     vec![
         safe_pattern!("s3-list", r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+ls\b"),
         safe_pattern!("s3-copy", r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+cp\b"),
+        // sync without --delete only adds or overwrites objects
+        safe_pattern!(
+            "s3-sync-no-delete",
+            r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+sync\b(?!.*\s--delete(?=\s|$))"
+        ),
         safe_pattern!(
             "s3-presign",
             r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+presign\b"
@@ -59,11 +64,26 @@ fn create_safe_patterns() -> Vec<SafePattern> {
 
 fn create_destructive_patterns() -> Vec<DestructivePattern> {
     vec![
+        // --force deletes every object before removing the bucket
+        destructive_pattern!(
+            "s3-rb-force",
+            r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+rb\b.*\s--force\b",
+            "aws s3 rb --force deletes every object in the bucket, then the bucket itself.",
+            Critical,
+            "With --force, rb first runs the equivalent of 'aws s3 rm --recursive' on the \
+             whole bucket and then removes it. Every object is deleted, and the bucket name \
+             becomes available for anyone else to claim. Unversioned data cannot be \
+             recovered.\n\n\
+             Safer alternatives:\n\
+             - aws s3 ls s3://bucket --recursive --summarize: See what would be deleted\n\
+             - aws s3 rm s3://bucket --recursive --dryrun: Preview the object deletions\n\
+             - Enable bucket versioning for recovery options"
+        ),
         destructive_pattern!(
             "s3-rb",
             r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+rb\b",
             "aws s3 rb removes an S3 bucket and is destructive.",
-            Critical,
+            High,
             "Removing an S3 bucket deletes the bucket and optionally all objects within \
              it (with --force). Bucket names are globally unique and may be claimed by \
              others after deletion. Versioned objects require additional cleanup.\n\n\
@@ -72,6 +92,19 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              - Empty the bucket before removal\n\
              - Enable bucket versioning for recovery options"
         ),
+        destructive_pattern!(
+            "s3-rm-recursive",
+            r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+rm\b(?!.*\s--dryrun\b).*\s--recursive\b",
+            "aws s3 rm --recursive deletes every object under the prefix.",
+            Critical,
+            "With --recursive, rm deletes all objects under the given bucket or prefix. \
+             A short or mistyped prefix (or a bare s3://bucket) can wipe the whole \
+             bucket. For unversioned buckets, the data is permanently lost.\n\n\
+             Safer alternatives:\n\
+             - aws s3 rm s3://bucket/prefix --recursive --dryrun: Preview deletions\n\
+             - aws s3 ls s3://bucket/prefix --recursive: Review objects first\n\
+             - Enable versioning for recovery options"
+        ),
         destructive_pattern!(
             "s3-rm",
             r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+rm\b",
@@ -87,7 +120,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         ),
         destructive_pattern!(
             "s3-sync-delete",
-            r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+sync\b(?:\s+[^\n]*)?\s+--delete\b",
+            r"aws(?:\s+--?\S+(?:\s+\S+)?)*\s+s3\s+sync\b(?!.*\s--dryrun\b).*\s--delete(?=\s|$)",
             "aws s3 sync --delete removes destination objects not in source.",
             High,
             "The --delete flag removes files from the destination that don't exist in \
@@ -143,6 +176,7 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::Severity;
     use crate::packs::test_helpers::*;
 
     #[test]
@@ -175,9 +209,13 @@ mod tests {
     fn blocks_destructive_commands() {
         let pack = create_pack();
         assert_blocks_with_pattern(&pack, "aws s3 rb s3://bucket", "s3-rb");
-        assert_blocks_with_pattern(&pack, "aws s3 rb s3://bucket --force", "s3-rb");
+        assert_blocks_with_pattern(&pack, "aws s3 rb s3://bucket --force", "s3-rb-force");
         assert_blocks_with_pattern(&pack, "aws s3 rm s3://bucket/key", "s3-rm");
-        assert_blocks_with_pattern(&pack, "aws s3 rm s3://bucket --recursive", "s3-rm");
+        assert_blocks_with_pattern(
+            &pack,
+            "aws s3 rm s3://bucket --recursive",
+            "s3-rm-recursive",
+        );
         assert_blocks_with_pattern(
             &pack,
             "aws s3 sync s3://src s3://dest --delete",
@@ -199,4 +237,45 @@ mod tests {
             "s3api-delete-objects",
         );
     }
+
+    #[test]
+    fn force_and_recursive_deletes_are_critical() {
+        let pack = create_pack();
+        assert_blocks_with_severity(&pack, "aws s3 rb s3://prod --force", Severity::Critical);
+        assert_blocks_with_severity(
+            &pack,
+            "aws --profile prod s3 rb --force s3://prod",
+            Severity::Critical,
+        );
+        assert_blocks_with_severity(&pack, "aws s3 rb s3://empty", Severity::High);
+        assert_blocks_with_severity(
+            &pack,
+            "aws s3 rm s3://prod/logs --recursive --exclude '*.keep'",
+            Severity::Critical,
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "aws s3 rm s3://prod/logs --recursive --dryrun",
+            "s3-rm",
+        );
+    }
+
+    #[test]
+    fn sync_delete_is_flagged_even_when_buried() {
+        let pack = create_pack();
+        for cmd in [
+            "aws s3 sync ./site s3://bucket --delete",
+            "aws s3 sync ./site s3://bucket --exclude '*.tmp' --size-only --delete",
+            "aws s3 sync s3://bucket ./backup --delete --exact-timestamps",
+            "aws --region us-east-1 s3 sync ./a s3://b --acl private --delete --quiet",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "s3-sync-delete");
+            assert_blocks_with_severity(&pack, cmd, Severity::High);
+        }
+
+        assert_allows(&pack, "aws s3 sync ./site s3://bucket");
+        assert_allows(&pack, "aws s3 sync ./site s3://bucket --exclude '*.tmp'");
+        assert_safe_pattern_matches(&pack, "aws s3 sync ./site s3://bucket --size-only");
+        assert_allows(&pack, "aws s3 sync ./site s3://bucket --delete --dryrun");
+    }
 }
@@ -5,6 +5,7 @@
 //! - Block rebase operations
 //! - Block amending commits that have been pushed
 //! - Block git filter-branch and other history rewriting
+//! - Block reflog expiry, pruning gc, and repacks that drop unreachable objects
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -94,6 +95,20 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"git\s+gc\s+.*--(?:aggressive|prune)",
             "git gc with aggressive/prune options can remove recoverable objects."
         ),
+        // Block repacks that discard unreachable objects immediately
+        destructive_pattern!(
+            "repack-unreachable-now",
+            r"git\s+repack\b(?=.*\s-[a-zA-Z]*d)(?=.*--unpack-unreachable=now\b)",
+            "git repack -d --unpack-unreachable=now drops unreachable objects that may be the only way to recover lost commits.",
+            Medium,
+            "Repacking with -d deletes the old packs, and --unpack-unreachable=now skips the \
+             grace period that normally keeps unreachable objects around as loose files. \
+             Commits lost to a bad reset or rebase that are no longer in the reflog become \
+             unrecoverable.\n\n\
+             Safer alternatives:\n\
+             - git repack -a -d: Repack without expiring unreachable objects early\n\
+             - git fsck --lost-found: Recover dangling commits before repacking"
+        ),
         // Block worktree remove
         destructive_pattern!(
             "worktree-remove",
@@ -139,4 +154,28 @@ mod tests {
         assert_allows(&pack, "git push origin feature-master");
         assert_allows(&pack, "git push origin master-fix");
     }
+
+    #[test]
+    fn test_repack_unreachable_now() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "git repack -a -d -f --unpack-unreachable=now",
+            "repack-unreachable-now",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "git repack -Ad --unpack-unreachable=now",
+            "repack-unreachable-now",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "git repack -a -d -f --unpack-unreachable=now",
+            crate::packs::Severity::Medium,
+        );
+
+        assert_allows(&pack, "git repack");
+        assert_allows(&pack, "git repack -a -d");
+        assert_allows(&pack, "git repack -A -d --unpack-unreachable=2.weeks.ago");
+    }
 }
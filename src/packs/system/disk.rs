@@ -9,7 +9,10 @@
 //! - btrfs filesystem operations
 //! - dmsetup device-mapper operations
 //! - nbd-client network block device
-//! - LVM destructive commands (pvremove, vgremove, lvremove, etc.)
+//! - LVM destructive commands (pvremove, vgremove, lvremove, lvreduce, etc.),
+//!   with -f/-y auto-confirmation detected in any position
+//! - ZFS dataset and pool destruction (zfs destroy, zfs rollback, zpool destroy,
+//!   zpool labelclear)
 
 use crate::packs::{DestructivePattern, Pack, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -22,7 +25,7 @@ pub fn create_pack() -> Pack {
         name: "Disk Operations",
         description: "Protects against destructive disk operations like dd to devices, \
                       mkfs, partition table modifications, RAID management, \
-                      btrfs/LVM/device-mapper operations, and network block devices",
+                      btrfs/ZFS/LVM/device-mapper operations, and network block devices",
         keywords: &[
             "dd",
             "fdisk",
@@ -42,6 +45,8 @@ pub fn create_pack() -> Pack {
             "lvreduce",
             "lvresize",
             "pvmove",
+            "zfs",
+            "zpool",
         ],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
@@ -97,6 +102,16 @@ fn create_safe_patterns() -> Vec<SafePattern> {
         safe_pattern!("btrfs-property-get", r"btrfs\s+property\s+(?:get|list)\b"),
         // btrfs scrub status (read-only)
         safe_pattern!("btrfs-scrub-status", r"btrfs\s+scrub\s+status\b"),
+        // --- ZFS safe patterns ---
+        // zfs list/get (read-only)
+        safe_pattern!("zfs-list", r"\bzfs\s+(?:list|get)\b"),
+        // zpool status/list (read-only)
+        safe_pattern!("zpool-status", r"\bzpool\s+(?:status|list)\b"),
+        // zfs destroy -n (dry run, prints what would be destroyed)
+        safe_pattern!(
+            "zfs-destroy-dry-run",
+            r"\bzfs\s+destroy\s+(?:-[a-zA-Z]+\s+)*-[a-zA-Z]*n"
+        ),
         // --- dmsetup safe patterns ---
         // dmsetup ls (list devices)
         safe_pattern!("dmsetup-ls", r"dmsetup\s+ls\b"),
@@ -221,7 +236,8 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         destructive_pattern!(
             "btrfs-subvolume-delete",
             r"btrfs\s+subvolume\s+delete\b",
-            "btrfs subvolume delete PERMANENTLY removes a subvolume and all its data."
+            "btrfs subvolume delete PERMANENTLY removes a subvolume and all its data.",
+            Critical
         ),
         // btrfs device remove/delete
         destructive_pattern!(
@@ -310,6 +326,21 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             "nbd-client connecting a device can expose or overwrite data. Verify server and device."
         ),
         // --- LVM destructive patterns ---
+        // pvremove with -y/--yes or -f/-ff skips confirmation (and with -ff
+        // wipes a PV that still belongs to a volume group)
+        destructive_pattern!(
+            "pvremove-force",
+            r"\bpvremove\b(?=.*\s(?:-[a-zA-Z]*[fy][a-zA-Z]*|--force|--yes)(?:\s|$))",
+            "pvremove with -f/-y ERASES LVM metadata from a physical volume without asking.",
+            Critical,
+            "pvremove wipes the LVM label so the device is no longer a physical volume. \
+             -y/--yes answers every prompt, and -ff removes the label even when the PV is \
+             still part of a volume group, leaving that group's logical volumes \
+             unreadable.\n\n\
+             Check first:\n\
+             - pvs -o pv_name,vg_name: Confirm the PV is not in a volume group\n\
+             - vgreduce <vg> <pv>: Detach it from its volume group before removing"
+        ),
         // pvremove (removes physical volume)
         destructive_pattern!(
             "pvremove",
@@ -320,9 +351,23 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
         destructive_pattern!(
             "vgremove",
             r"\bvgremove\b",
-            "vgremove DELETES a volume group and all logical volumes within it."
+            "vgremove DELETES a volume group and all logical volumes within it.",
+            Critical
         ),
-        // lvremove (removes logical volume)
+        // lvremove with -f/--force or -y/--yes (any position) skips the confirmation prompt
+        destructive_pattern!(
+            "lvremove-force",
+            r"\blvremove\b(?=.*\s(?:-[a-zA-Z]*[fy][a-zA-Z]*|--force|--yes)(?:\s|$))",
+            "lvremove with -f/-y PERMANENTLY deletes a logical volume and ALL its data without asking.",
+            Critical,
+            "lvremove deletes the logical volume and everything on it. Without -f or -y, \
+             LVM asks for confirmation before removing an active volume; with them the \
+             volume is gone immediately, even if it is mounted or in use.\n\n\
+             Check first:\n\
+             - lvs -o lv_name,lv_size,lv_active <vg>: Confirm which volume you are removing\n\
+             - lvcreate -s -n <lv>-backup -L <size> <vg>/<lv>: Snapshot it before removal"
+        ),
+        // lvremove (removes logical volume; LVM prompts before removing an active LV)
         destructive_pattern!(
             "lvremove",
             r"\blvremove\b",
@@ -334,6 +379,21 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"\bvgreduce\b",
             "vgreduce removes a physical volume from a volume group. Data may be lost."
         ),
+        // lvreduce with an explicit size/extent count truncates the volume
+        destructive_pattern!(
+            "lvreduce-shrink",
+            r"\blvreduce\b.*\s(?:-[Ll]|--size\b|--extents\b)",
+            "lvreduce -L/-l SHRINKS a logical volume, truncating anything past the new size.",
+            Critical,
+            "lvreduce cuts the end off the logical volume. Unless the filesystem on it was \
+             shrunk first (or -r/--resizefs is given), the filesystem still expects the \
+             truncated blocks and is corrupted; with -f or -y there is no prompt to stop \
+             it.\n\n\
+             Safer alternatives:\n\
+             - lvreduce -r -L <size> <vg>/<lv>: Shrink the filesystem together with the volume\n\
+             - lvextend -L +<size> <vg>/<lv>: Growing is non-destructive if you need space instead\n\
+             - lvs -o lv_name,lv_size <vg>: Check the current size first"
+        ),
         // lvreduce (shrinks logical volume)
         destructive_pattern!(
             "lvreduce",
@@ -358,12 +418,88 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
             r"lvconvert\s+(?:.*\s+)?--merge\b",
             "lvconvert --merge reverts LV to snapshot state, discarding changes since snapshot."
         ),
+        // --- ZFS destructive patterns ---
+        // zfs destroy -r/-R (recursive: child datasets, snapshots, and with -R clones)
+        destructive_pattern!(
+            "zfs-destroy-recursive",
+            r"\bzfs\s+destroy\s+(?:-[a-zA-Z]+\s+)*-[a-zA-Z]*[rR]",
+            "zfs destroy -r PERMANENTLY destroys the dataset and every child dataset and snapshot.",
+            Critical,
+            "zfs destroy -r recursively destroys all descendants of the dataset: child \
+             datasets, their snapshots, and (with -R) dependent clones outside the \
+             hierarchy. Snapshots are usually the only way to recover from mistakes, and \
+             they go too.\n\n\
+             Safer alternatives:\n\
+             - zfs destroy -rnv <dataset>: Dry run listing everything that would be destroyed\n\
+             - zfs list -r -t all <dataset>: Review children and snapshots first\n\
+             - zfs snapshot -r <dataset>@before-destroy: Keep a recovery point elsewhere"
+        ),
+        // zfs destroy of a single snapshot (dataset@snap) leaves the live dataset intact
+        destructive_pattern!(
+            "zfs-destroy-snapshot",
+            r"\bzfs\s+destroy\s+(?:-[a-zA-Z]+\s+)*[^\s@-][^\s@]*@\S+",
+            "zfs destroy PERMANENTLY destroys the snapshot and any recovery point it provided.",
+            High,
+            "Destroying a snapshot leaves the live dataset untouched, but the snapshot is \
+             often the only way to roll back or recover files from that point in time. \
+             Snapshot ranges (tank/data@a%b) remove every snapshot in between.\n\n\
+             Safer alternatives:\n\
+             - zfs destroy -nv <dataset>@<snap>: Dry run showing what would be destroyed\n\
+             - zfs list -t snapshot -o name,used,creation <dataset>: Review snapshots first\n\
+             - zfs hold keep <dataset>@<snap>: Protect snapshots you still need"
+        ),
+        // zfs destroy (dataset or volume)
+        destructive_pattern!(
+            "zfs-destroy",
+            r"\bzfs\s+destroy\b",
+            "zfs destroy PERMANENTLY destroys a dataset, volume, or snapshot and its data.",
+            Critical
+        ),
+        // zfs rollback discards all changes since the snapshot; -r/-R also destroys
+        // the newer snapshots (and with -R their clones)
+        destructive_pattern!(
+            "zfs-rollback",
+            r"\bzfs\s+rollback\b",
+            "zfs rollback discards every change made to the dataset since the snapshot.",
+            High,
+            "Rolling back reverts the live dataset to the snapshot's state; everything \
+             written since is lost. ZFS refuses to roll back past newer snapshots unless \
+             -r is given, in which case those snapshots are destroyed too (-R also \
+             destroys their clones).\n\n\
+             Safer alternatives:\n\
+             - zfs diff <dataset>@<snap>: See what would be discarded\n\
+             - zfs snapshot <dataset>@before-rollback: Keep the current state first\n\
+             - zfs clone <dataset>@<snap> <pool>/restore: Recover files without rolling back"
+        ),
+        // zpool destroy (whole pool)
+        destructive_pattern!(
+            "zpool-destroy",
+            r"\bzpool\s+destroy\b",
+            "zpool destroy destroys the entire pool and every dataset in it.",
+            Critical
+        ),
+        // zpool labelclear wipes the ZFS label from a device
+        destructive_pattern!(
+            "zpool-labelclear",
+            r"\bzpool\s+labelclear\b",
+            "zpool labelclear erases the ZFS label, so the device's pool can no longer be imported.",
+            High,
+            "The label identifies a device as a member of a pool. Clearing it on a disk \
+             that still belongs to a pool (or an exported pool you meant to import later) \
+             makes that pool's data unreachable through normal import. -f forces the wipe \
+             even when the device appears to be in use.\n\n\
+             Check first:\n\
+             - zpool import: List pools that are still importable from attached devices\n\
+             - zpool status -v: Confirm the device is not part of an active pool"
+        ),
     ]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
 
     #[test]
     fn wipefs_is_reachable_via_keywords() {
@@ -378,6 +514,134 @@ mod tests {
         assert_eq!(matched.name, Some("wipefs"));
     }
 
+    #[test]
+    fn zfs_destroy_recursive_is_critical() {
+        let pack = create_pack();
+        for cmd in [
+            "zfs destroy -r tank/data",
+            "zfs destroy -R tank/data",
+            "zfs destroy -fr tank/data@snap",
+            "sudo zfs destroy -f -r tank/home",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "zfs-destroy-recursive");
+            assert_blocks_with_severity(&pack, cmd, Severity::Critical);
+        }
+        assert_blocks_with_pattern(&pack, "zfs destroy tank/data", "zfs-destroy");
+        assert_blocks_with_severity(&pack, "zfs destroy tank/data", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "zpool destroy tank", "zpool-destroy");
+    }
+
+    #[test]
+    fn zfs_snapshot_destroy_is_high() {
+        let pack = create_pack();
+        for cmd in [
+            "zfs destroy tank/data@snapshot",
+            "zfs destroy -f tank/data@2024-01-01",
+            "zfs destroy tank/data@a%b",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "zfs-destroy-snapshot");
+            assert_blocks_with_severity(&pack, cmd, Severity::High);
+        }
+        // -r on a snapshot destroys that snapshot in every descendant dataset
+        assert_blocks_with_pattern(
+            &pack,
+            "zfs destroy -r tank/data@snapshot",
+            "zfs-destroy-recursive",
+        );
+        assert_allows(&pack, "zfs list -t snapshot");
+        assert_allows(&pack, "zfs list -t snapshot -o name,used tank/data");
+    }
+
+    #[test]
+    fn zfs_rollback_and_labelclear_are_high() {
+        let pack = create_pack();
+        for cmd in [
+            "zfs rollback tank/data@snap",
+            "zfs rollback -r tank/data@snap",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "zfs-rollback");
+            assert_blocks_with_severity(&pack, cmd, Severity::High);
+        }
+        for cmd in ["zpool labelclear /dev/sdb", "zpool labelclear -f /dev/sdb1"] {
+            assert_blocks_with_pattern(&pack, cmd, "zpool-labelclear");
+            assert_blocks_with_severity(&pack, cmd, Severity::High);
+        }
+    }
+
+    #[test]
+    fn advanced_fs_removals_are_critical() {
+        let pack = create_pack();
+        assert_blocks_with_severity(
+            &pack,
+            "btrfs subvolume delete /mnt/data/snap",
+            Severity::Critical,
+        );
+        assert_blocks_with_severity(&pack, "lvremove -f /dev/vg0/lv0", Severity::Critical);
+        assert_blocks_with_severity(&pack, "vgremove -f vg0", Severity::Critical);
+    }
+
+    #[test]
+    fn lvm_auto_confirm_and_shrink_are_critical() {
+        let pack = create_pack();
+        for cmd in [
+            "lvremove -f /dev/vg0/lv0",
+            "lvremove -y vg0/data",
+            "lvremove vg0/data -y",
+            "lvremove vg0/data --yes",
+            "lvremove --force vg0/data",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "lvremove-force");
+            assert_blocks_with_severity(&pack, cmd, Severity::Critical);
+        }
+        assert_blocks_with_pattern(&pack, "lvremove vg0/data", "lvremove");
+        assert_blocks_with_severity(&pack, "lvremove vg0/data", Severity::High);
+
+        for cmd in [
+            "lvreduce -L 10G vg0/data",
+            "lvreduce -L-5G vg0/data",
+            "lvreduce vg0/data -l -100",
+            "lvreduce -y --size 10G vg0/data",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "lvreduce-shrink");
+            assert_blocks_with_severity(&pack, cmd, Severity::Critical);
+        }
+        assert_blocks_with_pattern(&pack, "lvreduce vg0/data", "lvreduce");
+
+        assert_blocks_with_pattern(&pack, "pvremove -y /dev/sdb", "pvremove-force");
+        assert_blocks_with_pattern(&pack, "pvremove /dev/sdb -ff", "pvremove-force");
+        assert_blocks_with_severity(&pack, "pvremove -ff /dev/sdb", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "pvremove /dev/sdb", "pvremove");
+        assert_blocks_with_severity(&pack, "vgremove -y vg0", Severity::Critical);
+    }
+
+    #[test]
+    fn lvm_read_only_and_grow_commands_are_allowed() {
+        let pack = create_pack();
+        test_batch_allows(
+            &pack,
+            &[
+                "lvs",
+                "vgs -o +vg_free",
+                "pvs",
+                "lvdisplay vg0/data",
+                "lvextend -L +10G vg0/data",
+                "lvextend -r -l +100%FREE vg0/data",
+            ],
+        );
+        assert_no_match(&pack, "lvextend -L +10G vg0/data");
+    }
+
+    #[test]
+    fn zfs_read_only_commands_are_allowed() {
+        let pack = create_pack();
+        assert_allows(&pack, "zfs list -r tank");
+        assert_allows(&pack, "zfs get compression tank/data");
+        assert_allows(&pack, "zpool status tank");
+        assert_allows(&pack, "zpool list");
+        assert_allows(&pack, "zfs destroy -rnv tank/data");
+        assert_allows(&pack, "btrfs subvolume list /mnt");
+    }
+
     #[test]
     fn keyword_absent_skips_pack() {
         let pack = create_pack();
@@ -2,9 +2,17 @@
 //!
 //! This pack provides protection against destructive system operations:
 //! - Disk operations (dd, fdisk, mkfs)
+//! - OS package removal (apt, dnf/yum, pacman)
 //! - Permission changes (chmod, chown with dangerous patterns)
 //! - Service management (systemctl, service)
+//! - Shell startup and SSH file clobbering (`> ~/.bashrc`)
+//! - mv onto /dev/null or into system directories
+//! - systemd unit masking and journal vacuuming (systemctl, journalctl)
 
 pub mod disk;
+pub mod mv;
+pub mod packages;
 pub mod permissions;
 pub mod services;
+pub mod shell_config;
+pub mod systemd;
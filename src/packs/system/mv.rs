@@ -0,0 +1,133 @@
+//! mv patterns - protections against moves that delete or clobber.
+//!
+//! This includes patterns for:
+//! - Moving anything onto `/dev/null` (the source is silently deleted)
+//! - Moving files or directories into protected system directories
+//!   (`/etc`, `/usr`, `/bin`, `/sbin`, `/lib`, `/boot`)
+//!
+//! Redirections such as `mv a b 2> /dev/null` are not matched, and
+//! `/usr/local` is left alone since it is the conventional install location.
+//! A redirection after the destination (`mv a /dev/null 2>/dev/null`) does not
+//! hide it.
+
+use crate::destructive_pattern;
+use crate::packs::{DestructivePattern, Pack};
+
+/// Create the mv pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.mv".to_string(),
+        name: "mv",
+        description: "Protects against `mv` onto /dev/null (a silent delete) and moves that \
+                      overwrite files in protected system directories like /etc and /usr/bin",
+        keywords: &["mv"],
+        // Ordinary renames never name /dev/null or a system directory as the
+        // destination, so no safe patterns are needed.
+        safe_patterns: vec![],
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+// Arguments are whitespace-separated tokens that contain no redirection or
+// control characters, so `2> /dev/null` never counts as the destination. The
+// destination must be the last argument of the command, optionally followed
+// by redirections.
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "mv-to-dev-null",
+            r#"\bmv(?:\s+[^\s<>|;&]+)+\s+["']?/dev/null["']?(?=\s*(?:$|[;&|)<>])|\s+\d*[<>])"#,
+            "mv onto /dev/null deletes the source without using rm.",
+            Critical,
+            "As root, mv removes the /dev/null device node, puts the source file's \
+             contents at /dev/null as a regular file, and deletes the source. The next \
+             process that writes to /dev/null overwrites that data, and everything that \
+             relies on /dev/null discarding output breaks until the device is recreated \
+             (mknod -m 666 /dev/null c 1 3). As a regular user the move usually fails \
+             with a permission error, but the command is still a disguised delete.\n\n\
+             Safer alternatives:\n\
+             - rm <file>: Delete explicitly so the intent is visible\n\
+             - mv <file> /tmp/<file>.bak: Move it aside if you might need it\n\
+             - > <file>: Truncate the contents but keep the file"
+        ),
+        destructive_pattern!(
+            "mv-onto-system-dir",
+            r#"\bmv(?:\s+[^\s<>|;&]+)+\s+["']?/(?:etc|usr(?!/local(?:/|\b))|bin|sbin|lib(?:32|64)?|boot)(?:/[^\s<>|;&"']*)?["']?(?=\s*(?:$|[;&|)<>])|\s+\d*[<>])"#,
+            "mv into a protected system directory can overwrite system files.",
+            Medium,
+            "mv replaces an existing file at the destination without prompting. Moving \
+             into /etc, /usr, /bin, /sbin, /lib or /boot can overwrite binaries, \
+             libraries, or configuration that the system needs to boot or run, and a \
+             half-finished `mv old backup && mv new old` swap leaves nothing in place.\n\n\
+             Safer alternatives:\n\
+             - mv -i / mv -n: Prompt or refuse instead of overwriting\n\
+             - install -b -m 755 <file> /usr/bin/: Keep a backup of the replaced file\n\
+             - Use /usr/local/bin or ~/.local/bin for locally built tools"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.mv");
+        assert!(pack.keywords.contains(&"mv"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn mv_to_dev_null_is_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "mv important.db /dev/null", "mv-to-dev-null");
+        assert_blocks_with_severity(&pack, "mv -f ~/notes.txt /dev/null", Severity::Critical);
+        assert_blocks_with_pattern(&pack, "mv a b c /dev/null && ls", "mv-to-dev-null");
+        assert_blocks_with_pattern(&pack, "sudo mv data \"/dev/null\"", "mv-to-dev-null");
+        // Trailing redirections do not hide the destination
+        assert_blocks_with_pattern(
+            &pack,
+            "mv secret.db /dev/null 2>/dev/null",
+            "mv-to-dev-null",
+        );
+        assert_blocks_with_pattern(&pack, "mv secret.db /dev/null >/dev/null", "mv-to-dev-null");
+        assert_blocks_with_pattern(&pack, "mv secret.db /dev/null 2>&1", "mv-to-dev-null");
+
+        assert_no_match(&pack, "mv a b 2> /dev/null");
+        assert_no_match(&pack, "mv a b >/dev/null 2>&1");
+        assert_no_match(&pack, "mv /dev/null.bak /tmp/");
+    }
+
+    #[test]
+    fn mv_onto_system_dir_warns() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "mv ./newbin /usr/bin", "mv-onto-system-dir");
+        assert_blocks_with_severity(&pack, "sudo mv /tmp/stuff /etc", Severity::Medium);
+        assert_blocks_with_pattern(&pack, "mv hosts /etc/hosts", "mv-onto-system-dir");
+        assert_blocks_with_pattern(&pack, "mv vmlinuz /boot/", "mv-onto-system-dir");
+        assert_blocks_with_pattern(&pack, "mv hosts /etc/hosts 2>&1", "mv-onto-system-dir");
+
+        assert_no_match(&pack, "sudo mv dcg /usr/local/bin/");
+        assert_no_match(&pack, "mv /etc/motd.new ~/motd");
+    }
+
+    #[test]
+    fn ordinary_renames_are_allowed() {
+        let pack = create_pack();
+        assert_no_match(&pack, "mv src/old.rs src/new.rs");
+        assert_no_match(&pack, "mv build.log /tmp/");
+        assert_no_match(&pack, "mv -n README.md docs/");
+        assert_no_match(&pack, "mvn package");
+    }
+}
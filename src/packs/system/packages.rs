@@ -0,0 +1,170 @@
+//! System package patterns - protections against removing OS packages.
+//!
+//! This includes patterns for:
+//! - `apt`/`apt-get`/`aptitude` remove and purge
+//! - `apt autoremove` cascades
+//! - `dnf`/`yum` remove and erase
+//! - `pacman -R`, including `-Rs`/`-Rc` dependency cascades
+//!
+//! Matches are Medium. Removing a package listed in
+//! `[system_packages] essential` (libc6, systemd, coreutils, ...) is escalated
+//! to High after evaluation.
+
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+/// Create the System Packages pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.packages".to_string(),
+        name: "System Packages",
+        description: "Protects against removing OS packages with apt, dnf/yum, and pacman, \
+                      escalating removal of essential packages (libc6, systemd, coreutils)",
+        keywords: &["apt", "dnf", "yum", "pacman"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // Simulated runs print the plan without removing anything.
+        safe_pattern!(
+            "apt-simulate",
+            r"\b(?:apt(?:-get)?|aptitude)\b[^|;&\n]*\s(?:-s|--simulate|--just-print|--dry-run|--no-act|--recon)(?=\s|$)"
+        ),
+        safe_pattern!(
+            "dnf-assumeno",
+            r"\b(?:dnf|yum)\b[^|;&\n]*\s--assumeno(?=\s|$)"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "apt-autoremove",
+            r"\b(?:apt(?:-get)?|aptitude)(?:\s+-\S+)*\s+autoremove\b",
+            "apt autoremove removes every package apt considers unneeded, which can cascade into packages you rely on.",
+            Medium,
+            "autoremove uninstalls every automatically installed package that nothing \
+             depends on anymore. Packages installed as dependencies but used directly \
+             (compilers, runtimes, drivers) are removed too, and a previously held or \
+             misconfigured package can pull half the system with it.\n\n\
+             Safer alternatives:\n\
+             - apt-get -s autoremove: Preview the removal list\n\
+             - apt-mark manual <pkg>: Keep a package you use directly"
+        ),
+        destructive_pattern!(
+            "apt-remove",
+            r"\b(?:apt(?:-get)?|aptitude)(?:\s+-\S+)*\s+(?:remove|purge)\b",
+            "apt remove/purge uninstalls packages and everything that depends on them.",
+            Medium,
+            "apt removes the named packages plus every installed package that depends \
+             on them. purge also deletes their configuration files. Removing a shared \
+             library or core tool can leave the system unbootable or without a package \
+             manager.\n\n\
+             Safer alternatives:\n\
+             - apt-get -s remove <pkg>: Preview what would be removed\n\
+             - apt-cache rdepends --installed <pkg>: Check what depends on it"
+        ),
+        destructive_pattern!(
+            "dnf-remove",
+            r"\b(?:dnf|yum)(?:\s+-\S+)*\s+(?:remove|erase|autoremove)\b",
+            "dnf/yum remove uninstalls packages and everything that depends on them.",
+            Medium,
+            "dnf removes the named packages along with installed packages that require \
+             them, and with clean_requirements_on_remove it also removes their unused \
+             dependencies. Removing a core package can break the system.\n\n\
+             Safer alternatives:\n\
+             - dnf remove --assumeno <pkg>: Preview the transaction\n\
+             - dnf repoquery --installed --whatrequires <pkg>: Check dependents"
+        ),
+        destructive_pattern!(
+            "pacman-remove-cascade",
+            r"\bpacman(?:\s+-\S+)*\s+-R[a-zA-Z]*[sc]",
+            "pacman -Rs/-Rc removes packages together with their dependencies or dependents.",
+            Medium,
+            "-s removes dependencies that nothing else needs and -c removes every \
+             package that depends on the target. Both can remove far more than the \
+             named package.\n\n\
+             Safer alternatives:\n\
+             - pacman -Rp <pkg>: Print the targets without removing\n\
+             - pacman -Qi <pkg>: Check 'Required By' first"
+        ),
+        destructive_pattern!(
+            "pacman-remove",
+            r"\bpacman(?:\s+-\S+)*\s+(?:-R[a-zA-Z]*|--remove)\b",
+            "pacman -R uninstalls packages.",
+            Medium,
+            "pacman -R removes the named packages. Dependency checks stop obvious \
+             breakage, but -d/-dd skip them and can remove libraries other packages \
+             need.\n\n\
+             Safer alternatives:\n\
+             - pacman -Rp <pkg>: Print the targets without removing\n\
+             - pacman -Qi <pkg>: Check 'Required By' first"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.packages");
+        assert!(pack.keywords.contains(&"apt"));
+        assert!(pack.keywords.contains(&"pacman"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn apt_remove_and_purge_are_medium() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "apt purge nginx", "apt-remove");
+        assert_blocks_with_pattern(&pack, "sudo apt-get -y purge libc6", "apt-remove");
+        assert_blocks_with_pattern(&pack, "apt-get remove --purge vim", "apt-remove");
+        assert_blocks_with_severity(&pack, "apt remove nginx", Severity::Medium);
+        assert_blocks_with_pattern(&pack, "apt autoremove", "apt-autoremove");
+        assert_blocks_with_pattern(&pack, "apt-get --purge autoremove", "apt-autoremove");
+    }
+
+    #[test]
+    fn dnf_and_pacman_removals_are_medium() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "dnf remove httpd", "dnf-remove");
+        assert_blocks_with_pattern(&pack, "yum -y erase httpd", "dnf-remove");
+        assert_blocks_with_pattern(&pack, "pacman -Rns firefox", "pacman-remove-cascade");
+        assert_blocks_with_pattern(
+            &pack,
+            "pacman --noconfirm -Rc qt5-base",
+            "pacman-remove-cascade",
+        );
+        assert_blocks_with_pattern(&pack, "pacman -R vim", "pacman-remove");
+        assert_blocks_with_severity(&pack, "pacman -Rdd glibc", Severity::Medium);
+    }
+
+    #[test]
+    fn installs_updates_and_simulations_are_allowed() {
+        let pack = create_pack();
+        assert_no_match(&pack, "apt install nginx");
+        assert_no_match(&pack, "apt-get update && apt-get upgrade -y");
+        assert_no_match(&pack, "dnf install httpd");
+        assert_no_match(&pack, "pacman -Syu");
+        assert_no_match(&pack, "apt-cache rdepends libc6");
+        assert_allows(&pack, "apt-get -s remove libc6");
+        assert_allows(&pack, "apt remove --dry-run nginx");
+        assert_allows(&pack, "dnf remove --assumeno httpd");
+    }
+}
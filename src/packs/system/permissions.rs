@@ -5,6 +5,7 @@
 //! - chmod -R on system directories
 //! - chown -R on system directories
 //! - setfacl with dangerous patterns
+//! - chattr -R -i (removing immutability) on system directories
 
 use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
 use crate::{destructive_pattern, safe_pattern};
@@ -42,8 +43,8 @@ pub fn create_pack() -> Pack {
         id: "system.permissions".to_string(),
         name: "Permissions",
         description: "Protects against dangerous permission changes like chmod 777, \
-                      recursive chmod/chown on system directories",
-        keywords: &["chmod", "chown", "chgrp", "setfacl"],
+                      recursive chmod/chown on system directories, and recursive chattr -i",
+        keywords: &["chmod", "chown", "chgrp", "setfacl", "chattr"],
         safe_patterns: create_safe_patterns(),
         destructive_patterns: create_destructive_patterns(),
         keyword_matcher: None,
@@ -149,6 +150,22 @@ fn create_destructive_patterns() -> Vec<DestructivePattern> {
              Consider using group ownership instead:\n  \
              chgrp <group> <path>"
         ),
+        // chattr -R -i on root or system directories strips the immutable attribute
+        // that was protecting the tree, usually as a precursor to deleting it.
+        destructive_pattern!(
+            "chattr-recursive-immutable-root",
+            r"chattr\s+(?=(?:[^\s;&|]+\s+)*-[a-zA-Z]*R[a-zA-Z]*\s)(?=(?:[^\s;&|]+\s+)*-[a-zA-Z]*i[a-zA-Z]*\s)[^;&|]*\s/(?:$|\s|(?:bin|boot|dev|etc|lib|lib64|opt|proc|root|run|sbin|srv|sys|usr|var)\b)",
+            "chattr -R -i on system directories removes immutability protection from the whole tree.",
+            High,
+            "The immutable attribute (chattr +i) stops files from being modified, renamed, or \
+             deleted, even by root. Removing it recursively from / or a system directory \
+             takes that protection away from every file beneath it, and is typically done \
+             right before deleting or overwriting those files.\n\n\
+             List which files are immutable first:\n  \
+             lsattr -R <path> 2>/dev/null | grep -- '-i-'\n\n\
+             Remove the attribute only from the specific file you need to change:\n  \
+             chattr -i <specific-file>"
+        ),
         // setfacl with dangerous patterns
         destructive_pattern!(
             "setfacl-all",
@@ -0,0 +1,214 @@
+//! Shell config patterns - protections against clobbering startup and SSH files.
+//!
+//! This includes patterns for:
+//! - Truncating redirects (`>`, `>|`, `&>`) onto `~/.bashrc`, `~/.zshrc`,
+//!   `~/.profile`, `~/.bash_profile`
+//! - Truncating redirects onto `~/.ssh/config`
+//! - Truncating redirects onto `~/.ssh/authorized_keys` (lockout/backdoor)
+//! - `>|` onto any file, which deliberately overrides `set -o noclobber`
+//!
+//! `>|` onto a protected file is one severity level above the plain `>` form.
+//! Appending (`>>`) is not matched.
+
+use crate::destructive_pattern;
+use crate::packs::{DestructivePattern, Pack};
+
+/// Create the Shell Config pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.shell_config".to_string(),
+        name: "Shell Config",
+        description: "Protects against truncating shell startup files and SSH config with `>` \
+                      (e.g. `echo ... > ~/.bashrc`, `> ~/.ssh/authorized_keys`) while \
+                      allowing `>>` appends, and flags `>|` noclobber overrides",
+        keywords: &[
+            ".bashrc",
+            ".zshrc",
+            ".profile",
+            ".bash_profile",
+            "authorized_keys",
+            ".ssh/config",
+            ">|",
+        ],
+        // Appends never match the truncating-redirect patterns, so no safe
+        // patterns are needed.
+        safe_patterns: vec![],
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+// Each pattern is a single `>` (optionally `>|`, `N>`, or `&>`) that is not
+// part of `>>`, followed by a target in the user's home directory (or a bare
+// relative name). The `>|` forms come first so they win over the plain ones.
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "authorized-keys-truncate",
+            r#"(?<!>)>(?!>)\|?\s*["']?(?:(?:~|\$HOME|\$\{HOME\}|/root|/home/[^/\s"']+|/Users/[^/\s"']+)/)?\.ssh/authorized_keys2?(?=["'\s;&|)]|$)"#,
+            "Truncating ~/.ssh/authorized_keys replaces every trusted key. Use >> to append a key.",
+            Critical,
+            "A `>` redirect empties authorized_keys before writing. Every key that was \
+             previously trusted stops working, which can lock you out of the machine, \
+             and whatever is written becomes the only way in, which is how SSH backdoors \
+             are planted.\n\n\
+             Safer alternatives:\n\
+             - echo 'ssh-ed25519 ...' >> ~/.ssh/authorized_keys: Append a key\n\
+             - ssh-copy-id user@host: Install a key without touching existing ones\n\
+             - cp ~/.ssh/authorized_keys ~/.ssh/authorized_keys.bak: Back up first"
+        ),
+        destructive_pattern!(
+            "ssh-config-clobber",
+            r#"(?<!>)>\|\s*["']?(?:(?:~|\$HOME|\$\{HOME\}|/root|/home/[^/\s"']+|/Users/[^/\s"']+)/)?\.ssh/config(?=["'\s;&|)]|$)"#,
+            "`>|` forces truncation of ~/.ssh/config past noclobber. Use >> to append.",
+            Critical,
+            "`>|` overrides `set -o noclobber`, the shell setting whose only job is to stop \
+             `>` from overwriting an existing file. Using it on ~/.ssh/config is a deliberate \
+             choice to discard every host entry.\n\n\
+             Safer alternatives:\n\
+             - cat >> ~/.ssh/config: Append a host entry\n\
+             - cp ~/.ssh/config ~/.ssh/config.bak: Back up first"
+        ),
+        destructive_pattern!(
+            "shell-rc-clobber",
+            r#"(?<!>)>\|\s*["']?(?:(?:~|\$HOME|\$\{HOME\}|/root|/home/[^/\s"']+|/Users/[^/\s"']+)/)?\.(?:bashrc|zshrc|bash_profile|profile)(?=["'\s;&|)]|$)"#,
+            "`>|` forces truncation of a shell startup file past noclobber. Use >> to append.",
+            Critical,
+            "`>|` overrides `set -o noclobber`, the shell setting whose only job is to stop \
+             `>` from overwriting an existing file. Using it on a shell startup file is a \
+             deliberate choice to discard PATH setup, aliases, and tool initialization.\n\n\
+             Safer alternatives:\n\
+             - echo 'export FOO=bar' >> ~/.bashrc: Append a line\n\
+             - cp ~/.bashrc ~/.bashrc.bak: Back up first"
+        ),
+        destructive_pattern!(
+            "ssh-config-truncate",
+            r#"(?<!>)>(?!>)\s*["']?(?:(?:~|\$HOME|\$\{HOME\}|/root|/home/[^/\s"']+|/Users/[^/\s"']+)/)?\.ssh/config(?=["'\s;&|)]|$)"#,
+            "Truncating ~/.ssh/config discards every host entry. Use >> to append.",
+            High,
+            "A `>` redirect empties ~/.ssh/config before writing. Host aliases, jump hosts, \
+             identity files, and per-host options are lost, which can break deploys and \
+             git remotes that depend on them.\n\n\
+             Safer alternatives:\n\
+             - cat >> ~/.ssh/config: Append a host entry\n\
+             - Include ~/.ssh/config.d/*: Keep generated entries in separate files\n\
+             - cp ~/.ssh/config ~/.ssh/config.bak: Back up first"
+        ),
+        destructive_pattern!(
+            "shell-rc-truncate",
+            r#"(?<!>)>(?!>)\s*["']?(?:(?:~|\$HOME|\$\{HOME\}|/root|/home/[^/\s"']+|/Users/[^/\s"']+)/)?\.(?:bashrc|zshrc|bash_profile|profile)(?=["'\s;&|)]|$)"#,
+            "Truncating a shell startup file discards your shell configuration. Use >> to append.",
+            High,
+            "A `>` redirect empties the file before writing. PATH setup, aliases, and \
+             tool initialization are lost, which can leave new shells broken or unable \
+             to find installed tools.\n\n\
+             Safer alternatives:\n\
+             - echo 'export FOO=bar' >> ~/.bashrc: Append a line\n\
+             - cp ~/.bashrc ~/.bashrc.bak: Back up first\n\
+             - Put additions in a separate file and source it from the rc file"
+        ),
+        destructive_pattern!(
+            "noclobber-override",
+            r#"(?<!>)>\|\s*(?!["']?/dev/(?:null|stdout|stderr|tty)\b)["']?[^\s;&|)<>]"#,
+            "`>|` overrides noclobber to overwrite a file that may already exist.",
+            Medium,
+            "With `set -o noclobber`, `>` refuses to overwrite an existing file and `>|` \
+             is the explicit way around that. Reaching for `>|` means the overwrite is \
+             intended, so check that the target is not something you need.\n\n\
+             Safer alternatives:\n\
+             - >>: Append instead of overwriting\n\
+             - Write to a new file and mv it into place after checking it\n\
+             - cp <file> <file>.bak: Back up first"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.shell_config");
+        assert!(pack.keywords.contains(&".bashrc"));
+        assert!(pack.keywords.contains(&"authorized_keys"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn truncating_bashrc_is_blocked_but_append_is_not() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "echo 'alias ll=ls' > ~/.bashrc", "shell-rc-truncate");
+        assert_blocks_with_severity(&pack, "echo x > ~/.bashrc", Severity::High);
+        assert_blocks_with_pattern(&pack, "echo x >~/.bashrc", "shell-rc-truncate");
+        assert_blocks_with_pattern(&pack, "true > .bashrc", "shell-rc-truncate");
+
+        assert_no_match(&pack, "echo 'alias ll=ls' >> ~/.bashrc");
+        assert_no_match(&pack, "echo x >>~/.bashrc");
+        assert_no_match(&pack, "cat ~/.bashrc > /tmp/bashrc.bak");
+        assert_no_match(&pack, "echo x > ~/.bashrc.bak");
+    }
+
+    #[test]
+    fn other_startup_files_and_ssh_config_are_high() {
+        let pack = create_pack();
+        for command in [
+            "echo x > ~/.zshrc",
+            "printf '' > ~/.profile",
+            "echo x 2> /home/me/.bash_profile",
+            "echo x &> \"${HOME}/.zshrc\"",
+        ] {
+            assert_blocks_with_pattern(&pack, command, "shell-rc-truncate");
+        }
+        assert_blocks_with_pattern(
+            &pack,
+            "echo 'Host x' > ~/.ssh/config",
+            "ssh-config-truncate",
+        );
+        assert_blocks_with_severity(&pack, "echo 'Host x' > ~/.ssh/config", Severity::High);
+        assert_no_match(&pack, "echo 'Host x' >> ~/.ssh/config");
+    }
+
+    #[test]
+    fn truncating_authorized_keys_is_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            "echo 'ssh-ed25519 AAAA' > ~/.ssh/authorized_keys",
+            "authorized-keys-truncate",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            "curl -s https://example.com/key > /root/.ssh/authorized_keys",
+            Severity::Critical,
+        );
+        assert_no_match(&pack, "echo 'ssh-ed25519 AAAA' >> ~/.ssh/authorized_keys");
+        assert_no_match(&pack, "cat ~/.ssh/authorized_keys");
+    }
+
+    #[test]
+    fn noclobber_override_is_more_severe_than_plain_redirect() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "echo x >| important.conf", "noclobber-override");
+        assert_blocks_with_severity(&pack, "echo x >| important.conf", Severity::Medium);
+        assert_no_match(&pack, "echo x > important.conf");
+
+        assert_blocks_with_pattern(&pack, "cat new >| $HOME/.bashrc", "shell-rc-clobber");
+        assert_blocks_with_severity(&pack, "cat new >| ~/.bashrc", Severity::Critical);
+        assert_blocks_with_severity(&pack, "echo 'Host x' >|~/.ssh/config", Severity::Critical);
+        assert_blocks_with_severity(&pack, "cat new > ~/.bashrc", Severity::High);
+
+        assert_no_match(&pack, "make >| /dev/null");
+        assert_no_match(&pack, "echo x >> important.conf");
+        assert_no_match(&pack, "echo x | tee important.conf");
+    }
+}
@@ -0,0 +1,281 @@
+//! systemd patterns - protections against destructive systemctl/journalctl operations.
+//!
+//! This includes patterns for:
+//! - `systemctl mask` (the unit can no longer be started, even manually)
+//! - `systemctl disable --now` (stops the unit and removes it from boot)
+//! - `systemctl stop`/`kill` on units that keep the host reachable (sshd, networking)
+//! - `journalctl --vacuum-*` and `--rotate`, which discard or archive journal files
+//!
+//! Read-only commands like `systemctl status`, `systemctl cat`, and
+//! `journalctl -u <unit> -f` are explicitly allowed.
+
+use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+// ============================================================================
+// Suggestion constants (must be 'static for the pattern struct)
+// ============================================================================
+
+const MASK_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "systemctl disable {unit}",
+        "Keep the unit from starting at boot while still allowing manual starts",
+    ),
+    PatternSuggestion::new(
+        "systemctl list-dependencies --reverse {unit}",
+        "See which units depend on it before masking",
+    ),
+];
+
+const DISABLE_NOW_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "systemctl disable {unit}",
+        "Remove it from boot without stopping the running instance",
+    ),
+    PatternSuggestion::new(
+        "systemctl status {unit}",
+        "Check what the unit is doing before stopping it",
+    ),
+];
+
+const STOP_CRITICAL_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "systemctl restart {unit}",
+        "Restart instead of stopping so the host stays reachable",
+    ),
+    PatternSuggestion::new(
+        "systemctl reload {unit}",
+        "Reload configuration without dropping existing connections",
+    ),
+];
+
+const JOURNAL_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "journalctl --disk-usage",
+        "Check how much space the journal actually uses first",
+    ),
+    PatternSuggestion::new(
+        "journalctl -u {unit} --since {time} > {file}",
+        "Export the logs you need before discarding anything",
+    ),
+];
+
+/// Create the systemd pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "system.systemd".to_string(),
+        name: "systemd",
+        description: "Protects against masking or disabling units, stopping units that keep \
+                      the host reachable (sshd, networking), and journalctl vacuum/rotate \
+                      that discards logs",
+        keywords: &["systemctl", "journalctl"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // status/show/cat only read unit state and files
+        safe_pattern!(
+            "systemctl-status",
+            r"systemctl\s+(?:-\S+\s+)*(?:status|show|cat)\b"
+        ),
+        // list-units, list-unit-files, list-timers, list-dependencies, ...
+        safe_pattern!("systemctl-list", r"systemctl\s+(?:-\S+\s+)*list-[a-z-]+"),
+        // is-active/is-enabled/is-failed
+        safe_pattern!(
+            "systemctl-is",
+            r"systemctl\s+(?:-\S+\s+)*is-(?:active|enabled|failed|system-running)\b"
+        ),
+        // Following or reading a unit's logs never touches journal files
+        safe_pattern!(
+            "journalctl-unit-follow",
+            r"journalctl(?=(?:\s+\S+)*\s+(?:-u|--unit)(?:\s+|=)\S+)(?!.*--(?:vacuum-|rotate|flush))(?:\s+\S+)*\s+(?:-f|--follow)\b"
+        ),
+        // Disk usage report is read-only
+        safe_pattern!("journalctl-disk-usage", r"journalctl\s+--disk-usage\b"),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // mask links the unit to /dev/null; `unmask` is not matched
+        destructive_pattern!(
+            "systemctl-mask",
+            r"systemctl\s+(?:-\S+\s+)*mask\b",
+            "systemctl mask makes a unit impossible to start, even manually.",
+            High,
+            "Masking links the unit file to /dev/null. The unit cannot be started by \
+             anything, including dependencies and manual 'systemctl start', until it is \
+             unmasked. Masking the wrong unit (or a unit other services depend on) can \
+             leave the host without a service nobody can restart.\n\n\
+             See what depends on the unit first:\n  \
+             systemctl list-dependencies --reverse <unit>\n\n\
+             Undo with:\n  \
+             systemctl unmask <unit>",
+            MASK_SUGGESTIONS
+        ),
+        // disable --now both stops the unit and removes it from boot
+        destructive_pattern!(
+            "systemctl-disable-now",
+            r"systemctl\s+(?=(?:\S+\s+)*--now\b)(?:-\S+\s+)*disable\b",
+            "systemctl disable --now stops the unit immediately and keeps it from starting at boot.",
+            High,
+            "'disable --now' is two operations in one: the unit is stopped right away and \
+             its boot-time symlinks are removed. Anything relying on the service fails \
+             immediately, and it stays down across reboots until it is re-enabled.\n\n\
+             Check the unit's state first:\n  \
+             systemctl status <unit>\n\n\
+             Disable without stopping the running instance:\n  \
+             systemctl disable <unit>",
+            DISABLE_NOW_SUGGESTIONS
+        ),
+        // Stopping the units that keep a remote host reachable
+        destructive_pattern!(
+            "systemctl-stop-critical",
+            r"systemctl\s+(?:-\S+\s+)*(?:stop|kill|disable)\s+(?:\S+\s+)*(?:ssh|sshd|networking|network|NetworkManager|systemd-networkd|systemd-resolved|dbus)(?:\.service|\.socket)?(?=[\s;&|)]|$)",
+            "Stopping sshd or networking can cut off remote access to the host.",
+            High,
+            "Stopping or killing sshd, the network stack, or dbus on a remote machine \
+             usually severs the session that issued the command. There may be no way to \
+             reconnect and start the unit again without console access.\n\n\
+             Check the unit first:\n  \
+             systemctl status <unit>\n\n\
+             Restart instead of stopping if you are applying a config change:\n  \
+             systemctl restart <unit>",
+            STOP_CRITICAL_SUGGESTIONS
+        ),
+        // --vacuum-size/--vacuum-time/--vacuum-files delete archived journal files
+        destructive_pattern!(
+            "journalctl-vacuum",
+            r"journalctl\s+(?:\S+\s+)*--vacuum-(?:size|time|files)\b",
+            "journalctl --vacuum-* permanently deletes archived journal files.",
+            Medium,
+            "Vacuuming removes archived journal files until the size, age, or file-count \
+             limit is met. The deleted logs are gone for good, including any evidence \
+             needed to debug an incident or audit what happened on the host.\n\n\
+             Check journal size first:\n  \
+             journalctl --disk-usage\n\n\
+             Export the logs you still need:\n  \
+             journalctl -u <unit> --since <time> > <file>",
+            JOURNAL_SUGGESTIONS
+        ),
+        // --rotate archives the active files, usually right before a vacuum
+        destructive_pattern!(
+            "journalctl-rotate",
+            r"journalctl\s+(?:\S+\s+)*--rotate\b",
+            "journalctl --rotate archives active journal files so a vacuum can delete them.",
+            Medium,
+            "Rotating marks the currently active journal files as archived. On its own \
+             nothing is deleted, but archived files are exactly what --vacuum-* removes, \
+             so 'journalctl --rotate --vacuum-time=1s' wipes nearly the entire journal.\n\n\
+             Check journal size first:\n  \
+             journalctl --disk-usage",
+            JOURNAL_SUGGESTIONS
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "system.systemd");
+        assert!(pack.keywords.contains(&"systemctl"));
+        assert!(pack.keywords.contains(&"journalctl"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn mask_and_disable_now_are_high() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "systemctl mask nginx", "systemctl-mask");
+        assert_blocks_with_pattern(&pack, "sudo systemctl --now mask cron", "systemctl-mask");
+        assert_blocks_with_severity(&pack, "systemctl mask nginx", Severity::High);
+
+        assert_blocks_with_pattern(
+            &pack,
+            "systemctl disable --now nginx",
+            "systemctl-disable-now",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "systemctl --now disable nginx",
+            "systemctl-disable-now",
+        );
+        assert_blocks_with_severity(&pack, "systemctl disable --now nginx", Severity::High);
+
+        assert_no_match(&pack, "systemctl unmask nginx");
+        assert_no_match(&pack, "systemctl disable nginx");
+        assert_no_match(&pack, "systemctl enable --now nginx");
+    }
+
+    #[test]
+    fn stopping_critical_units_is_blocked() {
+        let pack = create_pack();
+        for cmd in [
+            "systemctl stop sshd",
+            "sudo systemctl stop ssh.service",
+            "systemctl stop networking",
+            "systemctl kill NetworkManager",
+            "systemctl stop nginx sshd",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "systemctl-stop-critical");
+        }
+
+        assert_no_match(&pack, "systemctl stop nginx");
+        assert_no_match(&pack, "systemctl stop sshd-keygen-helper");
+        assert_no_match(&pack, "systemctl restart sshd");
+    }
+
+    #[test]
+    fn journal_discards_are_medium() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "journalctl --vacuum-size=100M", "journalctl-vacuum");
+        assert_blocks_with_pattern(
+            &pack,
+            "sudo journalctl --vacuum-time=2d",
+            "journalctl-vacuum",
+        );
+        assert_blocks_with_severity(&pack, "journalctl --vacuum-files=1", Severity::Medium);
+        assert_blocks_with_pattern(&pack, "journalctl --rotate", "journalctl-rotate");
+        assert_blocks_with_severity(&pack, "journalctl --rotate", Severity::Medium);
+    }
+
+    #[test]
+    fn read_only_commands_are_allowed() {
+        let pack = create_pack();
+        test_batch_allows(
+            &pack,
+            &[
+                "systemctl status sshd",
+                "systemctl status networking",
+                "systemctl list-units --type=service",
+                "systemctl list-unit-files",
+                "systemctl cat sshd",
+                "systemctl show -p ActiveState sshd",
+                "systemctl is-active sshd",
+                "journalctl -u foo -f",
+                "journalctl --unit=sshd --follow",
+                "journalctl --disk-usage",
+                "journalctl -xe",
+            ],
+        );
+        assert_allows(&pack, "systemctl status sshd");
+        assert_safe_pattern_matches(&pack, "systemctl status sshd");
+        assert_safe_pattern_matches(&pack, "journalctl -u foo -f");
+    }
+}
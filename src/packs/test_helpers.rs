@@ -735,10 +735,8 @@ pub fn create_debug_runner(pack: &Pack) -> LoggedPackTestRunner<'_> {
 
 use crate::Config;
 use crate::allowlist::AllowlistLayer;
-use crate::evaluator::{
-    EvaluationDecision, EvaluationResult, MatchSource, evaluate_command_with_pack_order,
-};
-use crate::packs::{DecisionMode, REGISTRY};
+use crate::evaluator::{EvaluationResult, MatchSource, evaluate_command_with_pack_order};
+use crate::packs::REGISTRY;
 use std::path::Path;
 
 /// A stable, comparable snapshot of an evaluation result for golden testing.
@@ -783,16 +781,9 @@ impl EvalSnapshot {
     /// Create a snapshot from an evaluation result.
     #[must_use]
     pub fn from_result(command: &str, result: &EvaluationResult) -> Self {
-        let decision = match result.decision {
-            EvaluationDecision::Allow => "allow",
-            EvaluationDecision::Deny => "deny",
-        };
+        let decision = result.decision.as_str();
 
-        let effective_mode = result.effective_mode.map(|m| match m {
-            DecisionMode::Deny => "deny".to_string(),
-            DecisionMode::Warn => "warn".to_string(),
-            DecisionMode::Log => "log".to_string(),
-        });
+        let effective_mode = result.effective_mode.map(|m| m.label().to_string());
 
         let (pack_id, pattern_name, rule_id, match_source, reason_preview, matched_text_preview) =
             result
@@ -833,6 +824,7 @@ impl EvalSnapshot {
                 });
 
         let allowlist_layer = result.allowlist_override.as_ref().map(|ao| match ao.layer {
+            AllowlistLayer::Directory => "directory".to_string(),
             AllowlistLayer::Project => "project".to_string(),
             AllowlistLayer::User => "user".to_string(),
             AllowlistLayer::System => "system".to_string(),
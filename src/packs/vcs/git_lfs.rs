@@ -0,0 +1,179 @@
+//! Git LFS patterns - protections against losing large-file content.
+//!
+//! This includes patterns for:
+//! - `git lfs prune --force` (deletes local LFS objects, including ones the
+//!   checked-out refs still need)
+//! - `git lfs uninstall` (removes the LFS filters, leaving pointer files
+//!   where content used to be)
+//!
+//! Read-only commands like `git lfs ls-files`, `git lfs status`, and
+//! `git lfs prune --dry-run` are explicitly allowed.
+
+use crate::packs::{DestructivePattern, Pack, PatternSuggestion, SafePattern};
+use crate::{destructive_pattern, safe_pattern};
+
+// ============================================================================
+// Suggestion constants (must be 'static for the pattern struct)
+// ============================================================================
+
+const PRUNE_FORCE_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "git lfs prune --dry-run --verbose",
+        "List the objects that would be deleted first",
+    ),
+    PatternSuggestion::new(
+        "git lfs push --all origin",
+        "Make sure every LFS object is on the remote before pruning",
+    ),
+    PatternSuggestion::new(
+        "git lfs prune --verify-remote",
+        "Prune only objects confirmed to exist on the remote",
+    ),
+];
+
+const UNINSTALL_SUGGESTIONS: &[PatternSuggestion] = &[
+    PatternSuggestion::new(
+        "git lfs uninstall --local",
+        "Remove the hooks and filters from this repository only",
+    ),
+    PatternSuggestion::new(
+        "git lfs ls-files",
+        "See which files are tracked by LFS in this repository",
+    ),
+];
+
+/// Create the Git LFS pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "vcs.git_lfs".to_string(),
+        name: "Git LFS",
+        description: "Protects against losing large-file content with git lfs prune --force \
+                      and git lfs uninstall",
+        keywords: &["lfs"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+fn create_safe_patterns() -> Vec<SafePattern> {
+    vec![
+        // ls-files/status/env/version only read state
+        safe_pattern!(
+            "git-lfs-read-only",
+            r"git(?:\s+|-)lfs\s+(?:ls-files|status|env|version)\b"
+        ),
+        // prune --dry-run only reports what would be deleted
+        safe_pattern!(
+            "git-lfs-prune-dry-run",
+            r"git(?:\s+|-)lfs\s+prune\b.*\s(?:--dry-run|-d)(?:\s|$)"
+        ),
+    ]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        // prune --force also drops objects the current checkout and recent refs need
+        destructive_pattern!(
+            "git-lfs-prune-force",
+            r"git(?:\s+|-)lfs\s+prune\b.*\s--force(?:\s|$)",
+            "git lfs prune --force deletes local LFS objects, including any that were never pushed.",
+            High,
+            "git lfs prune normally keeps objects referenced by the checked-out commit, \
+             recent refs, and anything not yet pushed. --force drops those safeguards and \
+             deletes every local LFS object it can. If an object was never pushed to the \
+             LFS remote, its content is gone and the repository is left with a pointer \
+             file that cannot be resolved.\n\n\
+             Check what would be deleted:\n  \
+             git lfs prune --dry-run --verbose\n\n\
+             Push everything first:\n  \
+             git lfs push --all origin",
+            PRUNE_FORCE_SUGGESTIONS
+        ),
+        // uninstall removes the smudge/clean filters; checkouts then contain pointers
+        destructive_pattern!(
+            "git-lfs-uninstall",
+            r"git(?:\s+|-)lfs\s+uninstall\b",
+            "git lfs uninstall removes the LFS filters, so checkouts contain pointer files instead of content.",
+            Medium,
+            "Uninstalling removes the LFS smudge/clean filters and hooks (globally unless \
+             --local is given). Files already checked out stay as they are, but every \
+             later checkout, clone, or pull writes small pointer files in place of the \
+             real content, and new commits of tracked files store the full binary in git \
+             history.\n\n\
+             Limit the change to this repository:\n  \
+             git lfs uninstall --local\n\n\
+             Re-enable later with:\n  \
+             git lfs install",
+            UNINSTALL_SUGGESTIONS
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "vcs.git_lfs");
+        assert!(pack.keywords.contains(&"lfs"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn prune_force_is_high() {
+        let pack = create_pack();
+        for cmd in [
+            "git lfs prune --force",
+            "git lfs prune --verbose --force",
+            "git-lfs prune --force",
+        ] {
+            assert_blocks_with_pattern(&pack, cmd, "git-lfs-prune-force");
+            assert_blocks_with_severity(&pack, cmd, Severity::High);
+        }
+
+        assert_no_match(&pack, "git lfs prune");
+        assert_no_match(&pack, "git lfs prune --verify-remote");
+    }
+
+    #[test]
+    fn uninstall_is_a_warning() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, "git lfs uninstall", "git-lfs-uninstall");
+        assert_blocks_with_pattern(&pack, "git lfs uninstall --local", "git-lfs-uninstall");
+        assert_blocks_with_severity(&pack, "git lfs uninstall", Severity::Medium);
+
+        assert_no_match(&pack, "git lfs install");
+    }
+
+    #[test]
+    fn read_only_commands_are_allowed() {
+        let pack = create_pack();
+        test_batch_allows(
+            &pack,
+            &[
+                "git lfs ls-files",
+                "git lfs ls-files --all",
+                "git lfs status",
+                "git lfs env",
+                "git lfs prune --dry-run --force",
+                "git lfs prune -d --verbose",
+                "git lfs pull",
+                "git lfs track '*.bin'",
+            ],
+        );
+        assert_safe_pattern_matches(&pack, "git lfs ls-files");
+        assert_safe_pattern_matches(&pack, "git lfs status");
+        assert_safe_pattern_matches(&pack, "git lfs prune --dry-run --force");
+    }
+}
@@ -0,0 +1,3 @@
+//! Version control packs - protections for VCS extensions beyond core git.
+
+pub mod git_lfs;
@@ -0,0 +1,6 @@
+//! Windows packs - protections for Windows shells.
+//!
+//! These packs expect commands in canonical form. Set `[evaluation] shell =
+//! "powershell"` so aliases and abbreviated parameters are expanded first.
+
+pub mod powershell;
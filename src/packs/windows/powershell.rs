@@ -0,0 +1,193 @@
+//! PowerShell pack - protections for destructive cmdlets.
+//!
+//! Covers:
+//! - `Remove-Item -Recurse -Force` (and aliases such as `rm -r -fo`, `del`, `rd`)
+//! - `Clear-Content` (truncates files in place)
+//! - `Format-Volume` (erases a whole volume)
+//!
+//! Commands carrying `-WhatIf` (or `-WhatIf:$true`) are previews and are allowed;
+//! `-WhatIf:$false` runs for real and is not exempt. Patterns assume the
+//! PowerShell normalizer has already expanded aliases and parameter prefixes
+//! (see [`crate::normalize::normalize_powershell`]).
+
+use crate::destructive_pattern;
+use crate::packs::{DestructivePattern, Pack, SafePattern};
+
+/// Create the PowerShell pack.
+#[must_use]
+pub fn create_pack() -> Pack {
+    Pack {
+        id: "windows.powershell".to_string(),
+        name: "PowerShell",
+        description: "Protects against destructive PowerShell cmdlets like Remove-Item -Recurse \
+                      -Force, Clear-Content, and Format-Volume.",
+        keywords: &["Remove-Item", "Clear-Content", "Format-Volume"],
+        safe_patterns: create_safe_patterns(),
+        destructive_patterns: create_destructive_patterns(),
+        keyword_matcher: None,
+        safe_regex_set: None,
+        safe_regex_set_is_complete: false,
+    }
+}
+
+const fn create_safe_patterns() -> Vec<SafePattern> {
+    // No safe patterns - `-WhatIf` previews are excluded by each destructive pattern,
+    // so a preview cannot mask a real deletion chained after it.
+    vec![]
+}
+
+fn create_destructive_patterns() -> Vec<DestructivePattern> {
+    vec![
+        destructive_pattern!(
+            "remove-item-drive-root",
+            r"(?i)\bRemove-Item\b(?![^;&|\n]*\s-WhatIf(?::\$true)?(?=\s|$|[;&|]))(?=[^;&|\n]*\s-Recurse\b)(?=[^;&|\n]*\s['\x22]?(?:[A-Z]:\\?|\$env:(?:SystemDrive|SystemRoot|USERPROFILE|windir)\\?|~|\$HOME)['\x22]?(?:\s|$|[;&|]))",
+            "Remove-Item -Recurse on a drive root, the Windows directory, or the user profile destroys the system or all user data.",
+            Critical,
+            "Remove-Item -Recurse on C:\\, $env:SystemRoot, or $env:USERPROFILE deletes \
+             everything beneath it:\n\n\
+             - Removing a drive root or the Windows directory leaves the machine unbootable\n\
+             - Removing the user profile destroys documents, keys, and settings\n\
+             - Files removed this way bypass the Recycle Bin\n\n\
+             Preview first: Remove-Item -Recurse -WhatIf <path>\n\
+             Target a specific subdirectory instead of the root."
+        ),
+        destructive_pattern!(
+            "remove-item-recurse-force",
+            r"(?i)\bRemove-Item\b(?![^;&|\n]*\s-WhatIf(?::\$true)?(?=\s|$|[;&|]))(?=[^;&|\n]*\s-Recurse\b)(?=[^;&|\n]*\s-Force\b)",
+            "Remove-Item -Recurse -Force permanently deletes a directory tree, including hidden and read-only files.",
+            High,
+            "Remove-Item -Recurse -Force is the PowerShell equivalent of rm -rf:\n\n\
+             - Every file and subdirectory under the path is deleted\n\
+             - -Force also removes hidden, system, and read-only items\n\
+             - Files removed this way bypass the Recycle Bin\n\n\
+             Preview first: Remove-Item -Recurse -Force -WhatIf <path>\n\
+             List contents: Get-ChildItem -Recurse <path>"
+        ),
+        destructive_pattern!(
+            "clear-content",
+            r"(?i)\bClear-Content\b(?![^;&|\n]*\s-WhatIf(?::\$true)?(?=\s|$|[;&|]))",
+            "Clear-Content erases the contents of files in place without removing them.",
+            High,
+            "Clear-Content truncates every matched file:\n\n\
+             - File contents are discarded; only empty files remain\n\
+             - Wildcards can match far more files than intended\n\n\
+             Preview first: Clear-Content -WhatIf <path>\n\
+             Back up first: Copy-Item <path> <path>.bak"
+        ),
+        destructive_pattern!(
+            "format-volume",
+            r"(?i)\bFormat-Volume\b(?![^;&|\n]*\s-WhatIf(?::\$true)?(?=\s|$|[;&|]))",
+            "Format-Volume erases every file on the target volume.",
+            Critical,
+            "Format-Volume creates a new file system on a volume:\n\n\
+             - All existing data on the volume is destroyed\n\
+             - The wrong -DriveLetter wipes the wrong disk\n\n\
+             Inspect volumes first: Get-Volume\n\
+             Preview: Format-Volume -DriveLetter <letter> -WhatIf"
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packs::Severity;
+    use crate::packs::test_helpers::*;
+
+    #[test]
+    fn test_pack_creation() {
+        let pack = create_pack();
+        assert_eq!(pack.id, "windows.powershell");
+        assert_eq!(pack.name, "PowerShell");
+        assert!(!pack.description.is_empty());
+        assert!(pack.keywords.contains(&"Remove-Item"));
+
+        assert_patterns_compile(&pack);
+        assert_all_patterns_have_reasons(&pack);
+        assert_unique_pattern_names(&pack);
+    }
+
+    #[test]
+    fn remove_item_recurse_force_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            r"Remove-Item -Recurse -Force C:\data",
+            "remove-item-recurse-force",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            r"Remove-Item -Force -Path .\build -Recurse",
+            "remove-item-recurse-force",
+        );
+        assert_blocks_with_severity(
+            &pack,
+            r"Remove-Item -Recurse -Force C:\data",
+            Severity::High,
+        );
+    }
+
+    #[test]
+    fn remove_item_on_root_is_critical() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            r"Remove-Item -Recurse -Force C:\",
+            "remove-item-drive-root",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "Remove-Item -Recurse $env:USERPROFILE",
+            "remove-item-drive-root",
+        );
+        assert_blocks_with_severity(&pack, r"Remove-Item -Recurse C:\", Severity::Critical);
+    }
+
+    #[test]
+    fn clear_content_and_format_volume_blocked() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(&pack, r"Clear-Content C:\logs\*.log", "clear-content");
+        assert_blocks_with_pattern(&pack, "Format-Volume -DriveLetter D", "format-volume");
+        assert_blocks_with_severity(&pack, "Format-Volume -DriveLetter D", Severity::Critical);
+    }
+
+    #[test]
+    fn allows_previews_and_non_recursive_removal() {
+        let pack = create_pack();
+        assert_allows(&pack, r"Remove-Item -Recurse -Force -WhatIf C:\data");
+        assert_allows(&pack, r"Remove-Item C:\data\file.txt");
+        assert_allows(&pack, "Format-Volume -DriveLetter D -WhatIf");
+        assert_allows(&pack, "Get-ChildItem -Recurse C:\\data");
+        assert_allows(&pack, r"Remove-Item -Recurse -Force -WhatIf:$true C:\data");
+    }
+
+    #[test]
+    fn whatif_false_is_not_a_preview() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            r"Remove-Item -Recurse -Force -WhatIf:$false C:\data",
+            "remove-item-recurse-force",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            r"Clear-Content -WhatIf:$false C:\app.log",
+            "clear-content",
+        );
+        assert_blocks_with_pattern(
+            &pack,
+            "Format-Volume -DriveLetter D -WhatIf:$false",
+            "format-volume",
+        );
+    }
+
+    #[test]
+    fn preview_does_not_mask_chained_deletion() {
+        let pack = create_pack();
+        assert_blocks_with_pattern(
+            &pack,
+            r"Remove-Item -Recurse -WhatIf C:\a; Remove-Item -Recurse -Force C:\data",
+            "remove-item-recurse-force",
+        );
+    }
+}
@@ -26,9 +26,18 @@ pub const ENV_ALLOW_ONCE_PATH: &str = "DCG_ALLOW_ONCE_PATH";
 /// Optional HMAC secret for short-code hardening.
 /// When set, codes cannot be forged without knowing the secret.
 pub const ENV_ALLOW_ONCE_SECRET: &str = "DCG_ALLOW_ONCE_SECRET";
+/// Seconds a consumed single-use entry keeps allowing the identical command
+/// in the same cwd (0 disables the cooldown).
+pub const ENV_ALLOW_ONCE_COOLDOWN_SECS: &str = "DCG_ALLOW_ONCE_COOLDOWN_SECS";
 
 const PENDING_EXCEPTIONS_FILE: &str = "pending_exceptions.jsonl";
 const ALLOW_ONCE_FILE: &str = "allow_once.jsonl";
+const ALLOW_ONCE_COOLDOWN_FILE: &str = "allow_once_cooldown.jsonl";
+const CONFIRMATION_KEY_FILE: &str = "allow_once.key";
+const CONFIRMATION_TOKEN_BYTES: usize = 8;
+const DEFAULT_COOLDOWN_SECS: i64 = 10;
+const MAX_COOLDOWN_SECS: i64 = 300;
+const MAX_COOLDOWN_RECORDS: usize = 64;
 const SCHEMA_VERSION: u32 = 1;
 const EXPIRY_HOURS: i64 = 24;
 
@@ -360,13 +369,40 @@ impl PendingExceptionStore {
 #[derive(Debug, Clone)]
 pub struct AllowOnceStore {
     path: PathBuf,
+    cooldown: Duration,
+}
+
+/// Short-lived record that keeps a just-consumed single-use entry matching, so
+/// an agent retrying the approved command right away is not re-prompted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AllowOnceCooldown {
+    command_hash: String,
+    cwd: String,
+    until: String,
+    entry: AllowOnceEntry,
 }
 
 impl AllowOnceStore {
+    /// Create a store at `path`, reading the cooldown from
+    /// `DCG_ALLOW_ONCE_COOLDOWN_SECS` (default 10s, clamped to 300s).
     #[must_use]
-    #[allow(clippy::missing_const_for_fn)]
     pub fn new(path: PathBuf) -> Self {
-        Self { path }
+        let secs = env::var(ENV_ALLOW_ONCE_COOLDOWN_SECS)
+            .ok()
+            .and_then(|value| value.trim().parse::<i64>().ok())
+            .unwrap_or(DEFAULT_COOLDOWN_SECS)
+            .clamp(0, MAX_COOLDOWN_SECS);
+        Self {
+            path,
+            cooldown: Duration::seconds(secs),
+        }
+    }
+
+    /// Override the single-use cooldown window (zero disables it).
+    #[must_use]
+    pub const fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
     }
 
     #[must_use]
@@ -526,7 +562,7 @@ impl AllowOnceStore {
         allow_once_audit: Option<&AllowOnceAuditConfig<'_>>,
     ) -> io::Result<Option<AllowOnceEntry>> {
         if !self.path.exists() {
-            return Ok(None);
+            return Ok(self.cooldown_match(command, cwd, now, false, allow_once_audit));
         }
 
         let mut file = open_locked(&self.path)?;
@@ -541,7 +577,7 @@ impl AllowOnceStore {
             .position(|entry| entry.command_raw == command && entry.matches_scope(cwd));
 
         let Some(idx) = idx else {
-            return Ok(None);
+            return Ok(self.cooldown_match(command, cwd, now, false, allow_once_audit));
         };
 
         let mut selected = active[idx].clone();
@@ -549,6 +585,7 @@ impl AllowOnceStore {
             selected.consumed_at = Some(format_timestamp(now));
             active.remove(idx);
             rewrite_allow_once_records(&mut file, &active)?;
+            self.start_cooldown(&selected, cwd, now);
         }
 
         if let Some(audit) = allow_once_audit {
@@ -593,7 +630,7 @@ impl AllowOnceStore {
         allow_once_audit: Option<&AllowOnceAuditConfig<'_>>,
     ) -> io::Result<Option<AllowOnceEntry>> {
         if !self.path.exists() {
-            return Ok(None);
+            return Ok(self.cooldown_match(command, cwd, now, true, allow_once_audit));
         }
 
         let mut file = open_locked(&self.path)?;
@@ -608,7 +645,7 @@ impl AllowOnceStore {
             .position(|entry| entry.command_raw == command && entry.matches_scope(cwd));
 
         let Some(idx) = idx else {
-            return Ok(None);
+            return Ok(self.cooldown_match(command, cwd, now, true, allow_once_audit));
         };
 
         if !active[idx].force_allow_config {
@@ -620,6 +657,7 @@ impl AllowOnceStore {
             selected.consumed_at = Some(format_timestamp(now));
             active.remove(idx);
             rewrite_allow_once_records(&mut file, &active)?;
+            self.start_cooldown(&selected, cwd, now);
         }
 
         if let Some(audit) = allow_once_audit {
@@ -645,6 +683,79 @@ impl AllowOnceStore {
 
         Ok(Some(selected))
     }
+
+    fn cooldown_path(&self) -> PathBuf {
+        self.path.with_file_name(ALLOW_ONCE_COOLDOWN_FILE)
+    }
+
+    /// Record a consumed single-use entry so the identical command in the same
+    /// cwd keeps matching for the cooldown window. Best-effort: failures only
+    /// mean the next retry is prompted again.
+    fn start_cooldown(&self, entry: &AllowOnceEntry, cwd: &Path, now: DateTime<Utc>) {
+        if self.cooldown <= Duration::zero() {
+            return;
+        }
+        let Ok(mut file) = open_locked(&self.cooldown_path()) else {
+            return;
+        };
+        let mut records = load_cooldowns(&mut file, now);
+        records.push(AllowOnceCooldown {
+            command_hash: command_hash(&entry.command_raw),
+            cwd: cwd.to_string_lossy().into_owned(),
+            until: format_timestamp(now + self.cooldown),
+            entry: entry.clone(),
+        });
+        if records.len() > MAX_COOLDOWN_RECORDS {
+            records.drain(..records.len() - MAX_COOLDOWN_RECORDS);
+        }
+        let _ = rewrite_cooldowns(&mut file, &records);
+    }
+
+    /// Look up an unexpired cooldown for `command` in exactly `cwd`.
+    ///
+    /// Fail-open: any I/O or parse problem reads as "no cooldown".
+    fn cooldown_match(
+        &self,
+        command: &str,
+        cwd: &Path,
+        now: DateTime<Utc>,
+        require_force_allow_config: bool,
+        allow_once_audit: Option<&AllowOnceAuditConfig<'_>>,
+    ) -> Option<AllowOnceEntry> {
+        if self.cooldown <= Duration::zero() {
+            return None;
+        }
+        let path = self.cooldown_path();
+        if !path.exists() {
+            return None;
+        }
+        let mut file = open_locked(&path).ok()?;
+        let records = load_cooldowns(&mut file, now);
+        let hash = command_hash(command);
+        let cwd_str = cwd.to_string_lossy();
+        let entry = records
+            .into_iter()
+            .find(|record| {
+                record.command_hash == hash
+                    && record.cwd == cwd_str
+                    && record.entry.command_raw == command
+                    && (!require_force_allow_config || record.entry.force_allow_config)
+            })?
+            .entry;
+
+        if let Some(audit) = allow_once_audit {
+            let _ = log_allow_granted(
+                audit.log_file,
+                &entry,
+                audit.redaction,
+                "allow_once_cooldown",
+                audit.format,
+                cwd_str.as_ref(),
+            );
+        }
+
+        Some(entry)
+    }
 }
 
 /// Write a maintenance log entry (optional).
@@ -1221,6 +1332,31 @@ fn rewrite_allow_once_records(file: &mut File, records: &[AllowOnceEntry]) -> io
     Ok(())
 }
 
+/// Load unexpired cooldown records; corrupt lines are skipped.
+fn load_cooldowns(file: &mut File, now: DateTime<Utc>) -> Vec<AllowOnceCooldown> {
+    if file.seek(SeekFrom::Start(0)).is_err() {
+        return Vec::new();
+    }
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<AllowOnceCooldown>(line.trim()).ok())
+        .filter(|record| !is_expired(&record.until, now))
+        .collect()
+}
+
+fn rewrite_cooldowns(file: &mut File, records: &[AllowOnceCooldown]) -> io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    for record in records {
+        let line = serde_json::to_string(record).map_err(io::Error::other)?;
+        file.write_all(line.as_bytes())?;
+        file.write_all(b"\n")?;
+    }
+    file.sync_data()?;
+    Ok(())
+}
+
 fn append_record(file: &mut File, record: &PendingExceptionRecord) -> io::Result<()> {
     file.seek(SeekFrom::End(0))?;
     let line = serde_json::to_string(record).map_err(io::Error::other)?;
@@ -1248,6 +1384,14 @@ fn is_expired(expires_at: &str, now: DateTime<Utc>) -> bool {
     true
 }
 
+fn command_hash(command: &str) -> String {
+    let mut hex = String::with_capacity(64);
+    for byte in sha256_digest(command) {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
 fn format_timestamp(timestamp: DateTime<Utc>) -> String {
     timestamp.format("%Y-%m-%dT%H:%M:%SZ").to_string()
 }
@@ -1303,6 +1447,94 @@ fn compute_full_hash_with_secret(
     hex
 }
 
+/// Resolve the secret that keys confirmation tokens (`[exceptions] require_hmac`).
+///
+/// Reads `allow_once.key` next to the pending exceptions file, creating it
+/// (owner-only on Unix) with a random key on first use. `DCG_ALLOW_ONCE_SECRET`
+/// is deliberately not consulted: the guarded agent can set environment
+/// variables, and would then be able to mint tokens for its own denials.
+///
+/// # Errors
+///
+/// Returns any I/O error from reading or creating the key file.
+pub fn confirmation_secret(pending_path: &Path) -> io::Result<String> {
+    let key_path = pending_path.with_file_name(CONFIRMATION_KEY_FILE);
+    if let Ok(existing) = std::fs::read_to_string(&key_path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return Ok(existing.to_string());
+        }
+    }
+
+    if let Some(parent) = key_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let key: [u8; 32] = rand::random();
+    let mut secret = String::with_capacity(key.len() * 2);
+    for byte in key {
+        let _ = write!(secret, "{byte:02x}");
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    match options.open(&key_path) {
+        Ok(mut file) => {
+            writeln!(file, "{secret}")?;
+            Ok(secret)
+        }
+        // Another process created the key first; use theirs.
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            Ok(std::fs::read_to_string(&key_path)?.trim().to_string())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Derive the confirmation token for a pending exception's `full_hash`.
+///
+/// The token is printed to the user at block time (never in hook JSON), so a
+/// process that only sees the short code cannot approve its own denial.
+#[must_use]
+pub fn confirmation_token(full_hash: &str, secret: &str) -> String {
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return String::new();
+    };
+    mac.update(full_hash.as_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let mut hex = String::with_capacity(CONFIRMATION_TOKEN_BYTES * 2);
+    for byte in &digest[..CONFIRMATION_TOKEN_BYTES] {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+/// Check a user-supplied confirmation token in constant time.
+#[must_use]
+pub fn verify_confirmation_token(full_hash: &str, secret: &str, token: &str) -> bool {
+    let token = token.trim();
+    if token.len() != CONFIRMATION_TOKEN_BYTES * 2 || !token.is_ascii() {
+        return false;
+    }
+    let Ok(bytes) = (0..token.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&token[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+    else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(full_hash.as_bytes());
+    mac.verify_truncated_left(&bytes).is_ok()
+}
+
 /// Generate a 5-digit numeric short code from a hex hash.
 ///
 /// Takes the last 8 hex characters (32 bits) and converts to a 5-digit
@@ -1509,10 +1741,77 @@ mod tests {
         let first = store.match_command("git status", cwd, now, None).unwrap();
         assert!(first.is_some());
 
-        let second = store.match_command("git status", cwd, now, None).unwrap();
+        // Past the cooldown window the consumed entry no longer matches.
+        let later = now + Duration::seconds(DEFAULT_COOLDOWN_SECS + 1);
+        let second = store.match_command("git status", cwd, later, None).unwrap();
         assert!(second.is_none());
     }
 
+    #[test]
+    fn test_allow_once_single_use_cooldown_allows_immediate_retry() {
+        let dir = TempDir::new().expect("tempdir");
+        let allow_path = dir.path().join("allow_once.jsonl");
+        let store = AllowOnceStore::new(allow_path.clone())
+            .with_cooldown(Duration::seconds(DEFAULT_COOLDOWN_SECS));
+        let now = DateTime::parse_from_rfc3339("2026-01-10T06:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let redaction = redaction_config();
+
+        let pending =
+            PendingExceptionRecord::new(now, "/repo", "git status", "ok", &redaction, false, None);
+        let entry = AllowOnceEntry::from_pending(
+            &pending,
+            now,
+            AllowOnceScopeKind::Cwd,
+            "/repo",
+            true,
+            false,
+            &redaction,
+        );
+        store.add_entry(&entry, now).unwrap();
+
+        let cwd = Path::new("/repo");
+        assert!(
+            store
+                .match_command("git status", cwd, now, None)
+                .unwrap()
+                .is_some()
+        );
+
+        // Immediate retry in the same cwd is still allowed.
+        let retry = now + Duration::seconds(2);
+        assert!(
+            store
+                .match_command("git status", cwd, retry, None)
+                .unwrap()
+                .is_some()
+        );
+
+        // The cooldown is keyed on cwd and command.
+        assert!(
+            store
+                .match_command("git status", Path::new("/other"), retry, None)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            store
+                .match_command("git log", cwd, retry, None)
+                .unwrap()
+                .is_none()
+        );
+
+        // A disabled cooldown falls back to strict single use.
+        let strict = AllowOnceStore::new(allow_path).with_cooldown(Duration::zero());
+        assert!(
+            strict
+                .match_command("git status", cwd, retry, None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_allow_once_project_scope_matches_subdir() {
         let dir = TempDir::new().expect("tempdir");
@@ -2223,4 +2522,44 @@ mod tests {
         // The value is 1 % 100000 = 1, formatted as "00001"
         assert_eq!(code, "00001");
     }
+
+    #[test]
+    fn test_confirmation_token_requires_matching_secret() {
+        let full_hash = "a".repeat(64);
+        let token = confirmation_token(&full_hash, "secret-one");
+        assert_eq!(token.len(), CONFIRMATION_TOKEN_BYTES * 2);
+        assert!(verify_confirmation_token(&full_hash, "secret-one", &token));
+        assert!(verify_confirmation_token(
+            &full_hash,
+            "secret-one",
+            &format!(" {} ", token.to_uppercase())
+        ));
+        assert!(!verify_confirmation_token(&full_hash, "secret-two", &token));
+        assert!(!verify_confirmation_token(
+            &"b".repeat(64),
+            "secret-one",
+            &token
+        ));
+        assert!(!verify_confirmation_token(
+            &full_hash,
+            "secret-one",
+            &token[..8]
+        ));
+        assert!(!verify_confirmation_token(
+            &full_hash,
+            "secret-one",
+            "zzzzzzzzzzzzzzzz"
+        ));
+    }
+
+    #[test]
+    fn test_confirmation_secret_key_file_is_stable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pending_path = dir.path().join(PENDING_EXCEPTIONS_FILE);
+        let first = confirmation_secret(&pending_path).expect("create key");
+        let second = confirmation_secret(&pending_path).expect("read key");
+        assert_eq!(first.len(), 64);
+        assert_eq!(first, second);
+        assert!(dir.path().join(CONFIRMATION_KEY_FILE).exists());
+    }
 }
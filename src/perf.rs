@@ -30,6 +30,9 @@
 //! Any operation exceeding 200ms triggers fail-open behavior in hook mode.
 //! This ensures dcg never blocks a user's workflow indefinitely.
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
 use std::time::{Duration, Instant};
 
 /// Performance budget for a single operation tier.
@@ -183,6 +186,75 @@ impl Deadline {
     }
 }
 
+// =============================================================================
+// Evaluation Watchdog (hard limit for the whole evaluation)
+// =============================================================================
+
+/// Hard wall-clock limit for a whole evaluation.
+///
+/// [`Deadline`] is cooperative: it only helps where the evaluator checks it.
+/// The watchdog runs on its own thread and calls `on_timeout` once the limit
+/// passes, even if the evaluator is stuck in a slow regex or a huge input.
+/// Whichever side claims the decision first wins: the evaluator claims it by
+/// calling [`EvaluationWatchdog::finish`], and `on_timeout` never runs after that.
+///
+/// # Example
+///
+/// ```
+/// use destructive_command_guard::perf::EvaluationWatchdog;
+/// use std::time::Duration;
+///
+/// let watchdog = EvaluationWatchdog::start(Duration::from_secs(5), || {
+///     // Emit the fallback decision
+/// });
+/// // ... evaluate ...
+/// assert!(watchdog.finish());
+/// ```
+#[derive(Debug)]
+pub struct EvaluationWatchdog {
+    /// Set by whichever side decides first.
+    claimed: Arc<AtomicBool>,
+    /// Dropped on finish to wake the watchdog thread early.
+    _done: Sender<()>,
+}
+
+impl EvaluationWatchdog {
+    /// Start the watchdog; `on_timeout` runs on its thread once `timeout` passes.
+    ///
+    /// If the thread cannot be spawned the evaluation simply runs unbounded.
+    #[must_use]
+    pub fn start<F>(timeout: Duration, on_timeout: F) -> Self
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let claimed = Arc::new(AtomicBool::new(false));
+        let (done, wait) = mpsc::channel::<()>();
+        let watchdog_claimed = Arc::clone(&claimed);
+        let _ = std::thread::Builder::new()
+            .name("dcg-watchdog".to_string())
+            .spawn(move || {
+                if wait.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout)
+                    && !watchdog_claimed.swap(true, Ordering::SeqCst)
+                {
+                    on_timeout();
+                }
+            });
+        Self {
+            claimed,
+            _done: done,
+        }
+    }
+
+    /// Claim the decision for the evaluator.
+    ///
+    /// Returns false if the watchdog already fired; the caller must then not
+    /// emit a decision of its own.
+    #[must_use]
+    pub fn finish(self) -> bool {
+        !self.claimed.swap(true, Ordering::SeqCst)
+    }
+}
+
 // =============================================================================
 // Tier 0: Quick Reject (no relevant keywords)
 // =============================================================================
@@ -395,4 +467,17 @@ mod tests {
         // Should not have budget for operations that take longer than the deadline
         assert!(!deadline.has_budget_for(&large_budget));
     }
+
+    #[test]
+    fn watchdog_stays_quiet_when_evaluation_finishes_in_time() {
+        let fired = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&fired);
+        let watchdog = EvaluationWatchdog::start(Duration::from_millis(50), move || {
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        assert!(watchdog.finish());
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!fired.load(Ordering::SeqCst));
+    }
 }
@@ -548,8 +548,11 @@ mod tests {
             decision,
             severity,
             rule_id: Some("core.filesystem:recursive-delete-root".to_string()),
+            rule_severity: None,
             reason: Some("Recursively deletes the entire filesystem".to_string()),
             suggestion: Some("Use a specific path instead of root".to_string()),
+            metadata: None,
+            occurrences: Vec::new(),
         }
     }
 
@@ -561,6 +564,8 @@ mod tests {
                 files_skipped: 0,
                 commands_extracted: 2,
                 findings_total: 2,
+                findings_deduped: None,
+                baseline_suppressed: None,
                 decisions: crate::scan::ScanDecisionCounts::default(),
                 severities: crate::scan::ScanSeverityCounts::default(),
                 max_findings_reached: false,
@@ -28,15 +28,13 @@
 //! Scan output uses this evaluator behavior for parity.
 
 use crate::config::{Config, HeredocSettings};
-use crate::evaluator::{
-    EvaluationDecision, MatchSource, PatternMatch, evaluate_command_with_pack_order_at_path,
-};
+use crate::evaluator::{MatchSource, PatternMatch, evaluate_command_with_pack_order_at_path};
 use crate::packs::{DecisionMode, REGISTRY, Severity};
 use crate::suggestions::{SuggestionKind, get_suggestion_by_kind};
 use clap::ValueEnum;
 use memchr::memmem;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 pub const SCAN_SCHEMA_VERSION: u32 = 1;
@@ -139,6 +137,17 @@ pub enum ScanFormat {
     Markdown,
     /// SARIF 2.1.0 output (for code scanning tools)
     Sarif,
+    /// GitHub Actions workflow commands (`::error file=...::`) plus a summary
+    Github,
+}
+
+/// Returns `true` when running inside a GitHub Actions step.
+///
+/// `dcg scan` defaults to `--format github` there unless a format is set on
+/// the command line or in `.dcg/hooks.toml`.
+#[must_use]
+pub fn running_in_github_actions() -> bool {
+    std::env::var("GITHUB_ACTIONS").is_ok_and(|v| v == "true")
 }
 
 /// Controls scan failure behavior (CI integration).
@@ -161,6 +170,83 @@ impl ScanFailOn {
     }
 }
 
+/// How to section findings in human-readable scan output (`--group-by`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanGroupBy {
+    /// Pack that owns the matched rule (`core.git`)
+    Pack,
+    /// Rule severity, most severe first
+    Severity,
+    /// File the command was extracted from
+    File,
+    /// Full rule ID (`core.git:reset-hard`)
+    Rule,
+}
+
+impl ScanGroupBy {
+    /// Group key for a finding. Findings without a rule or rule severity fall
+    /// into a shared `(none)` / `unknown` group.
+    fn key(self, finding: &ScanFinding) -> String {
+        match self {
+            Self::Pack => finding
+                .rule_id
+                .as_deref()
+                .and_then(|rule_id| rule_id.split_once(':'))
+                .map_or_else(|| "(none)".to_string(), |(pack, _)| pack.to_string()),
+            Self::Severity => finding
+                .rule_severity
+                .map_or("unknown", |severity| severity.label())
+                .to_string(),
+            Self::File => finding.file.clone(),
+            Self::Rule => finding
+                .rule_id
+                .clone()
+                .unwrap_or_else(|| "(none)".to_string()),
+        }
+    }
+}
+
+/// Findings that share a `--group-by` key.
+#[derive(Debug, Clone)]
+pub struct ScanFindingGroup<'a> {
+    pub key: String,
+    pub findings: Vec<&'a ScanFinding>,
+}
+
+/// Minimum rule severity for findings shown in scan output
+/// (`--severity-at-least`).
+///
+/// This only filters what is displayed; the summary and `--fail-on` still see
+/// every finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanSeverityFilter {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+impl ScanSeverityFilter {
+    /// Returns `true` if a finding with this rule severity should be shown.
+    ///
+    /// Findings without a rule severity (e.g. missing match metadata) are
+    /// always shown.
+    #[must_use]
+    pub const fn includes(&self, severity: Option<Severity>) -> bool {
+        let Some(severity) = severity else {
+            return true;
+        };
+        match self {
+            Self::Critical => matches!(severity, Severity::Critical),
+            Self::High => matches!(severity, Severity::Critical | Severity::High),
+            Self::Medium => !matches!(severity, Severity::Low),
+            Self::Low => true,
+        }
+    }
+}
+
 /// Redaction mode for scan output.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "snake_case")]
@@ -229,10 +315,55 @@ pub struct ScanFinding {
     pub severity: ScanSeverity,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rule_id: Option<String>,
+    /// Severity of the matched rule (`critical`, `high`, `medium`, `low`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_severity: Option<Severity>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub suggestion: Option<String>,
+    /// Extractor context for the command (e.g. CI `job`/`step`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    /// Every location of this finding when `--dedupe` collapsed duplicates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub occurrences: Vec<ScanOccurrence>,
+}
+
+/// One location of a finding collapsed by `dcg scan --dedupe`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanOccurrence {
+    pub file: String,
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub col: Option<usize>,
+}
+
+impl ScanFinding {
+    /// Rule ID plus normalized command: what `--dedupe` and `--baseline`
+    /// treat as "the same finding" regardless of location.
+    fn identity_key(&self) -> (Option<String>, String) {
+        (
+            self.rule_id.clone(),
+            dedupe_command_key(&self.extracted_command),
+        )
+    }
+
+    /// Human-readable CI context, e.g. `job build, step Clean up`.
+    #[must_use]
+    pub fn context_label(&self) -> Option<String> {
+        let metadata = self.metadata.as_ref()?;
+        let parts: Vec<String> = ["job", "step"]
+            .into_iter()
+            .filter_map(|key| {
+                metadata
+                    .get(key)
+                    .and_then(serde_json::Value::as_str)
+                    .map(|value| format!("{key} {value}"))
+            })
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
 }
 
 /// Counts of findings by decision.
@@ -258,6 +389,12 @@ pub struct ScanSummary {
     pub files_skipped: usize,
     pub commands_extracted: usize,
     pub findings_total: usize,
+    /// Findings left after `--dedupe` (`findings_total` stays the raw count).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub findings_deduped: Option<usize>,
+    /// Findings dropped by `--baseline` because the baseline already had them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline_suppressed: Option<usize>,
     pub decisions: ScanDecisionCounts,
     pub severities: ScanSeverityCounts,
     pub max_findings_reached: bool,
@@ -273,6 +410,125 @@ pub struct ScanReport {
     pub findings: Vec<ScanFinding>,
 }
 
+/// Finding totals without per-finding detail (`dcg scan --count-only`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCounts {
+    pub schema_version: u32,
+    pub findings_total: usize,
+    pub severities: ScanSeverityCounts,
+    pub max_findings_reached: bool,
+}
+
+impl ScanReport {
+    /// Drop findings below `min` from `findings`, leaving `summary` untouched so
+    /// it still counts what was hidden.
+    pub fn retain_severity_at_least(&mut self, min: ScanSeverityFilter) {
+        self.findings
+            .retain(|finding| min.includes(finding.rule_severity));
+    }
+
+    /// Section `findings` by `by`, keeping report order within each group.
+    ///
+    /// Severity groups run from critical to low, then `unknown`; other groupings
+    /// are sorted by key.
+    #[must_use]
+    pub fn group_findings(&self, by: ScanGroupBy) -> Vec<ScanFindingGroup<'_>> {
+        let mut groups: BTreeMap<(u8, String), Vec<&ScanFinding>> = BTreeMap::new();
+        for finding in &self.findings {
+            let rank = match by {
+                ScanGroupBy::Severity => {
+                    finding
+                        .rule_severity
+                        .map_or(u8::MAX, |severity| match severity {
+                            Severity::Critical => 0,
+                            Severity::High => 1,
+                            Severity::Medium => 2,
+                            Severity::Low => 3,
+                        })
+                }
+                ScanGroupBy::Pack | ScanGroupBy::File | ScanGroupBy::Rule => 0,
+            };
+            groups
+                .entry((rank, by.key(finding)))
+                .or_default()
+                .push(finding);
+        }
+        groups
+            .into_iter()
+            .map(|((_, key), findings)| ScanFindingGroup { key, findings })
+            .collect()
+    }
+
+    /// Collapse findings with the same rule ID and normalized command into the
+    /// first one, listing every location in `occurrences`. Display only, like
+    /// [`Self::retain_severity_at_least`]; the raw total stays in `summary`.
+    pub fn dedupe(&mut self) {
+        let mut first_by_key: HashMap<(Option<String>, String), usize> = HashMap::new();
+        let mut deduped: Vec<ScanFinding> = Vec::with_capacity(self.findings.len());
+
+        for mut finding in self.findings.drain(..) {
+            let key = finding.identity_key();
+            let occurrence = ScanOccurrence {
+                file: finding.file.clone(),
+                line: finding.line,
+                col: finding.col,
+            };
+            if let Some(&index) = first_by_key.get(&key) {
+                deduped[index].occurrences.push(occurrence);
+            } else {
+                first_by_key.insert(key, deduped.len());
+                finding.occurrences = vec![occurrence];
+                deduped.push(finding);
+            }
+        }
+
+        // A finding seen once keeps the plain (non-deduped) shape.
+        for finding in &mut deduped {
+            if finding.occurrences.len() == 1 {
+                finding.occurrences.clear();
+            }
+        }
+
+        self.summary.findings_deduped = Some(deduped.len());
+        self.findings = deduped;
+    }
+
+    /// Keep only findings that `baseline` (an earlier `--write-baseline`
+    /// report) does not already contain, so `--fail-on` sees just the new ones.
+    ///
+    /// Findings match on rule ID and normalized command, so edits that only
+    /// move a command to another line or file keep it baselined. Each baseline
+    /// entry suppresses one occurrence: extra copies of a baselined command
+    /// are still reported as new.
+    pub fn retain_new_since(&mut self, baseline: &Self) {
+        let mut known: HashMap<(Option<String>, String), usize> = HashMap::new();
+        for finding in &baseline.findings {
+            *known.entry(finding.identity_key()).or_default() += finding.occurrences.len().max(1);
+        }
+        let before = self.findings.len();
+        self.findings
+            .retain(|finding| match known.get_mut(&finding.identity_key()) {
+                Some(remaining) if *remaining > 0 => {
+                    *remaining -= 1;
+                    false
+                }
+                _ => true,
+            });
+        self.summary.baseline_suppressed = Some(before - self.findings.len());
+    }
+
+    /// Severity histogram and total for CI gates that only need the numbers.
+    #[must_use]
+    pub fn counts(&self) -> ScanCounts {
+        ScanCounts {
+            schema_version: self.schema_version,
+            findings_total: self.summary.findings_total,
+            severities: self.summary.severities.clone(),
+            max_findings_reached: self.summary.max_findings_reached,
+        }
+    }
+}
+
 /// In-memory scan configuration (CLI + defaults).
 #[derive(Debug, Clone)]
 pub struct ScanOptions {
@@ -318,6 +574,14 @@ impl ScanEvalContext {
     }
 }
 
+/// Grouping key for `--dedupe`: the normalized command with whitespace collapsed.
+fn dedupe_command_key(command: &str) -> String {
+    crate::normalize::normalize_command(command)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 #[must_use]
 pub fn should_fail(report: &ScanReport, fail_on: ScanFailOn) -> bool {
     report.findings.iter().any(|f| fail_on.blocks(f.severity))
@@ -373,7 +637,7 @@ pub fn evaluate_extracted_command(
         project_path.as_deref(),
     );
 
-    if result.decision == EvaluationDecision::Allow {
+    if result.is_allowed() {
         return None;
     }
 
@@ -387,15 +651,18 @@ pub fn evaluate_extracted_command(
             decision: ScanDecision::Deny,
             severity: ScanSeverity::Error,
             rule_id: None,
+            rule_severity: None,
             reason: Some("Blocked (missing match metadata)".to_string()),
             suggestion: None,
+            metadata: extracted.metadata.clone(),
+            occurrences: Vec::new(),
         });
     };
 
     let (rule_id, severity, decision_mode) = resolve_severity_and_rule_id(config, &pattern);
 
     let scan_decision = match decision_mode {
-        Some(DecisionMode::Deny) | None => ScanDecision::Deny,
+        Some(DecisionMode::Deny | DecisionMode::Ask) | None => ScanDecision::Deny,
         Some(DecisionMode::Warn) => ScanDecision::Warn,
         Some(DecisionMode::Log) => ScanDecision::Allow,
     };
@@ -422,8 +689,11 @@ pub fn evaluate_extracted_command(
         decision: scan_decision,
         severity: scan_severity,
         rule_id,
+        rule_severity: severity,
         reason: Some(pattern.reason),
         suggestion,
+        metadata: extracted.metadata.clone(),
+        occurrences: Vec::new(),
     })
 }
 
@@ -1671,6 +1941,13 @@ pub fn extract_github_actions_workflow_from_str(
     let mut out = Vec::new();
     let mut steps_indent: Option<usize> = None;
     let mut skip_indent: Option<usize> = None;
+    // Job/step context for findings.
+    let mut jobs_indent: Option<usize> = None;
+    let mut job_indent: Option<usize> = None;
+    let mut current_job: Option<String> = None;
+    let mut step_indent: Option<usize> = None;
+    let mut step_index = 0usize;
+    let mut step_name: Option<String> = None;
 
     let mut idx = 0usize;
     while idx < lines.len() {
@@ -1694,10 +1971,29 @@ pub fn extract_github_actions_workflow_from_str(
             }
         }
 
+        if jobs_indent.is_some_and(|jobs| indent <= jobs) {
+            jobs_indent = None;
+            job_indent = None;
+            current_job = None;
+        }
+        if let Some(jobs) = jobs_indent {
+            if indent > jobs && job_indent.is_none_or(|job| indent <= job) {
+                job_indent = Some(indent);
+                current_job = yaml_mapping_key(trimmed_start).map(str::to_string);
+            }
+        } else if yaml_key_value(trimmed_start, "jobs")
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('#'))
+        {
+            jobs_indent = Some(indent);
+        }
+
         if let Some(steps) = steps_indent {
             // Exit steps block when indentation returns to the steps key level (or less).
             if !trimmed_start.starts_with('-') && indent <= steps {
                 steps_indent = None;
+                step_indent = None;
+                step_index = 0;
+                step_name = None;
             }
         }
 
@@ -1725,8 +2021,22 @@ pub fn extract_github_actions_workflow_from_str(
         let mut candidate = trimmed_start;
         if let Some(after_dash) = candidate.strip_prefix('-') {
             candidate = after_dash.trim_start();
+            if step_indent.is_none_or(|step| indent <= step) {
+                step_indent = Some(indent);
+                step_index += 1;
+                step_name = None;
+            }
         }
 
+        if let Some(name) = yaml_key_value(candidate, "name") {
+            step_name = Some(unquote_yaml_scalar(name));
+        }
+        let step_label = || {
+            step_name
+                .clone()
+                .unwrap_or_else(|| format!("#{step_index}"))
+        };
+
         if yaml_key_value(candidate, "env").is_some()
             || yaml_key_value(candidate, "with").is_some()
             || yaml_key_value(candidate, "secrets").is_some()
@@ -1764,12 +2074,16 @@ pub fn extract_github_actions_workflow_from_str(
                 j += 1;
             }
 
-            out.extend(extract_shell_script_with_offset_and_id(
-                file,
-                block_start_line,
-                &block,
-                enabled_keywords,
-                EXTRACTOR_ID,
+            out.extend(with_ci_context(
+                extract_shell_script_with_offset_and_id(
+                    file,
+                    block_start_line,
+                    &block,
+                    enabled_keywords,
+                    EXTRACTOR_ID,
+                ),
+                current_job.as_deref(),
+                Some(&step_label()),
             ));
 
             idx = j;
@@ -1777,12 +2091,16 @@ pub fn extract_github_actions_workflow_from_str(
         }
 
         let unquoted = unquote_yaml_scalar(run_value);
-        out.extend(extract_shell_script_with_offset_and_id(
-            file,
-            line_no,
-            &unquoted,
-            enabled_keywords,
-            EXTRACTOR_ID,
+        out.extend(with_ci_context(
+            extract_shell_script_with_offset_and_id(
+                file,
+                line_no,
+                &unquoted,
+                enabled_keywords,
+                EXTRACTOR_ID,
+            ),
+            current_job.as_deref(),
+            Some(&step_label()),
         ));
 
         idx += 1;
@@ -1791,6 +2109,42 @@ pub fn extract_github_actions_workflow_from_str(
     out
 }
 
+/// Attach CI `job`/`step` context to extracted commands as metadata.
+fn with_ci_context(
+    mut commands: Vec<ExtractedCommand>,
+    job: Option<&str>,
+    step: Option<&str>,
+) -> Vec<ExtractedCommand> {
+    let mut context = serde_json::Map::new();
+    if let Some(job) = job {
+        context.insert("job".to_string(), job.into());
+    }
+    if let Some(step) = step {
+        context.insert("step".to_string(), step.into());
+    }
+    if context.is_empty() {
+        return commands;
+    }
+    for command in &mut commands {
+        command.metadata = Some(serde_json::Value::Object(context.clone()));
+    }
+    commands
+}
+
+/// Key of a `key:` mapping line (not a sequence item), with quotes removed.
+fn yaml_mapping_key(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('-') || trimmed.starts_with('#') {
+        return None;
+    }
+    let (key, rest) = trimmed.split_once(':')?;
+    if !(rest.is_empty() || rest.starts_with(char::is_whitespace)) {
+        return None;
+    }
+    let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+    (!key.is_empty()).then_some(key)
+}
+
 fn unquote_yaml_scalar(s: &str) -> String {
     let s = s.trim();
     if s.starts_with('"') && s.ends_with('"') {
@@ -1856,6 +2210,9 @@ pub fn extract_gitlab_ci_from_str(
     let mut out = Vec::new();
     let mut anchors: HashMap<String, Vec<ExtractedCommand>> = HashMap::new();
     let mut skip_indent: Option<usize> = None;
+    // Commands from `job_start..` belong to `current_job` (a top-level key).
+    let mut current_job: Option<String> = None;
+    let mut job_start = 0usize;
 
     let mut idx = 0usize;
     while idx < lines.len() {
@@ -1870,6 +2227,14 @@ pub fn extract_gitlab_ci_from_str(
 
         let indent = raw_line.len() - trimmed_start.len();
 
+        if indent == 0 {
+            if let Some(key) = yaml_mapping_key(trimmed_start) {
+                tag_gitlab_job(&mut out[job_start..], current_job.as_deref());
+                job_start = out.len();
+                current_job = (!GITLAB_GLOBAL_KEYWORDS.contains(&key)).then(|| key.to_string());
+            }
+        }
+
         if let Some(skip) = skip_indent {
             if indent <= skip {
                 skip_indent = None;
@@ -2039,9 +2404,33 @@ pub fn extract_gitlab_ci_from_str(
         idx += 1;
     }
 
+    tag_gitlab_job(&mut out[job_start..], current_job.as_deref());
     out
 }
 
+/// Top-level `.gitlab-ci.yml` keys that configure the pipeline rather than name a job.
+const GITLAB_GLOBAL_KEYWORDS: &[&str] = &[
+    "after_script",
+    "before_script",
+    "cache",
+    "default",
+    "image",
+    "include",
+    "services",
+    "stages",
+    "variables",
+    "workflow",
+];
+
+fn tag_gitlab_job(commands: &mut [ExtractedCommand], job: Option<&str>) {
+    let Some(job) = job else {
+        return;
+    };
+    for command in commands {
+        command.metadata = Some(serde_json::json!({ "job": job }));
+    }
+}
+
 fn gitlab_anchor_definition(line: &str) -> Option<String> {
     let (_, rest) = line.split_once(':')?;
     let rest = rest.trim_start();
@@ -3463,6 +3852,8 @@ pub fn build_report(
             files_skipped,
             commands_extracted,
             findings_total: findings.len(),
+            findings_deduped: None,
+            baseline_suppressed: None,
             decisions,
             severities,
             max_findings_reached,
@@ -3657,8 +4048,11 @@ fail_on = "nope"
                     decision: ScanDecision::Deny,
                     severity: ScanSeverity::Error,
                     rule_id: Some("core.filesystem:rm-rf-general".to_string()),
+                    rule_severity: None,
                     reason: Some("blocked".to_string()),
                     suggestion: None,
+                    metadata: None,
+                    occurrences: Vec::new(),
                 },
                 ScanFinding {
                     file: "b".to_string(),
@@ -3669,8 +4063,11 @@ fail_on = "nope"
                     decision: ScanDecision::Warn,
                     severity: ScanSeverity::Warning,
                     rule_id: None,
+                    rule_severity: None,
                     reason: Some("warn".to_string()),
                     suggestion: None,
+                    metadata: None,
+                    occurrences: Vec::new(),
                 },
             ],
             2,
@@ -3697,8 +4094,11 @@ fail_on = "nope"
                 decision: ScanDecision::Warn,
                 severity: ScanSeverity::Warning,
                 rule_id: Some("pack:rule".to_string()),
+                rule_severity: None,
                 reason: None,
                 suggestion: None,
+                metadata: None,
+                occurrences: Vec::new(),
             },
             ScanFinding {
                 file: "a".to_string(),
@@ -3709,8 +4109,11 @@ fail_on = "nope"
                 decision: ScanDecision::Deny,
                 severity: ScanSeverity::Error,
                 rule_id: Some("pack:rule".to_string()),
+                rule_severity: None,
                 reason: None,
                 suggestion: None,
+                metadata: None,
+                occurrences: Vec::new(),
             },
         ];
 
@@ -4060,8 +4463,11 @@ resource "null_resource" "test" {
                 decision: ScanDecision::Deny,
                 severity: ScanSeverity::Error,
                 rule_id: Some("core.filesystem:rm-rf-root-home".to_string()),
+                rule_severity: None,
                 reason: Some("dangerous".to_string()),
                 suggestion: Some("use safer rm".to_string()),
+                metadata: None,
+                occurrences: Vec::new(),
             }],
             1,
             0,
@@ -4120,6 +4526,225 @@ resource "null_resource" "test" {
         assert_eq!(report.summary.severities.error, 1);
     }
 
+    #[test]
+    fn severity_filter_hides_findings_but_keeps_summary() {
+        let findings = [
+            Some(Severity::Critical),
+            Some(Severity::High),
+            Some(Severity::Medium),
+            Some(Severity::Low),
+            None,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, rule_severity)| ScanFinding {
+            rule_severity,
+            ..make_finding(&i.to_string(), ScanDecision::Deny, ScanSeverity::Error)
+        })
+        .collect();
+        let mut report = build_report(findings, 1, 0, 5, false, None);
+
+        report.retain_severity_at_least(ScanSeverityFilter::High);
+
+        let shown: Vec<_> = report.findings.iter().map(|f| f.file.as_str()).collect();
+        assert_eq!(shown, ["0", "1", "4"]);
+        assert_eq!(report.summary.findings_total, 5);
+        assert_eq!(report.summary.severities.error, 5);
+    }
+
+    #[test]
+    fn group_by_severity_sections_findings() {
+        let finding = |file: &str, rule_severity: Option<Severity>| ScanFinding {
+            rule_severity,
+            ..make_finding(file, ScanDecision::Deny, ScanSeverity::Error)
+        };
+        let findings = vec![
+            finding("a.sh", Some(Severity::Medium)),
+            finding("b.sh", Some(Severity::Critical)),
+            finding("c.sh", None),
+            finding("d.sh", Some(Severity::Medium)),
+            finding("e.sh", Some(Severity::High)),
+        ];
+        let report = build_report(findings, 5, 0, 5, false, None);
+
+        let groups: Vec<(String, Vec<&str>)> = report
+            .group_findings(ScanGroupBy::Severity)
+            .into_iter()
+            .map(|group| {
+                let files = group.findings.iter().map(|f| f.file.as_str()).collect();
+                (group.key, files)
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("critical".to_string(), vec!["b.sh"]),
+                ("high".to_string(), vec!["e.sh"]),
+                ("medium".to_string(), vec!["a.sh", "d.sh"]),
+                ("unknown".to_string(), vec!["c.sh"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn group_by_pack_uses_rule_id_prefix() {
+        let finding = |file: &str, rule_id: Option<&str>| ScanFinding {
+            rule_id: rule_id.map(str::to_string),
+            ..make_finding(file, ScanDecision::Deny, ScanSeverity::Error)
+        };
+        let findings = vec![
+            finding("a.sh", Some("core.git:reset-hard")),
+            finding("b.sh", Some("core.filesystem:rm-rf-general")),
+            finding("c.sh", Some("core.git:clean-force")),
+            finding("d.sh", None),
+        ];
+        let report = build_report(findings, 4, 0, 4, false, None);
+
+        let keys: Vec<(String, usize)> = report
+            .group_findings(ScanGroupBy::Pack)
+            .into_iter()
+            .map(|group| (group.key, group.findings.len()))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                ("(none)".to_string(), 1),
+                ("core.filesystem".to_string(), 1),
+                ("core.git".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn dedupe_collapses_same_rule_and_command() {
+        let finding = |file: &str, line: usize, command: &str, rule_id: &str| ScanFinding {
+            line,
+            extracted_command: command.to_string(),
+            rule_id: Some(rule_id.to_string()),
+            ..make_finding(file, ScanDecision::Deny, ScanSeverity::Error)
+        };
+        let findings = vec![
+            finding(
+                "a.sh",
+                3,
+                "docker system prune -af",
+                "containers.docker:system-prune",
+            ),
+            finding(
+                "b.sh",
+                7,
+                "docker  system prune -af",
+                "containers.docker:system-prune",
+            ),
+            finding("b.sh", 9, "git reset --hard", "core.git:reset-hard"),
+        ];
+        let mut report = build_report(findings, 2, 0, 3, false, None);
+
+        report.dedupe();
+
+        assert_eq!(report.findings.len(), 2);
+        assert_eq!(report.summary.findings_total, 3);
+        assert_eq!(report.summary.findings_deduped, Some(2));
+        let prune = &report.findings[0];
+        assert_eq!(
+            prune.occurrences,
+            [
+                ScanOccurrence {
+                    file: "a.sh".to_string(),
+                    line: 3,
+                    col: None
+                },
+                ScanOccurrence {
+                    file: "b.sh".to_string(),
+                    line: 7,
+                    col: None
+                },
+            ]
+        );
+        assert!(report.findings[1].occurrences.is_empty());
+    }
+
+    #[test]
+    fn baseline_suppresses_known_findings_but_not_new_ones() {
+        let finding = |file: &str, line: usize, command: &str, rule_id: &str| ScanFinding {
+            line,
+            extracted_command: command.to_string(),
+            rule_id: Some(rule_id.to_string()),
+            ..make_finding(file, ScanDecision::Deny, ScanSeverity::Error)
+        };
+        let baseline = build_report(
+            vec![finding(
+                "a.sh",
+                3,
+                "git reset --hard",
+                "core.git:reset-hard",
+            )],
+            1,
+            0,
+            1,
+            false,
+            None,
+        );
+
+        // Same finding, shifted down a few lines: still baselined.
+        let mut unchanged = build_report(
+            vec![finding(
+                "a.sh",
+                10,
+                "git  reset --hard",
+                "core.git:reset-hard",
+            )],
+            1,
+            0,
+            1,
+            false,
+            None,
+        );
+        unchanged.retain_new_since(&baseline);
+        assert!(unchanged.findings.is_empty());
+        assert_eq!(unchanged.summary.baseline_suppressed, Some(1));
+        assert!(!should_fail(&unchanged, ScanFailOn::Error));
+
+        // A new finding alongside the baselined one still fails.
+        let mut with_new = build_report(
+            vec![
+                finding("a.sh", 10, "git reset --hard", "core.git:reset-hard"),
+                finding("a.sh", 11, "git clean -fd", "core.git:clean-force"),
+            ],
+            1,
+            0,
+            2,
+            false,
+            None,
+        );
+        with_new.retain_new_since(&baseline);
+        assert_eq!(with_new.findings.len(), 1);
+        assert_eq!(
+            with_new.findings[0].rule_id.as_deref(),
+            Some("core.git:clean-force")
+        );
+        assert_eq!(with_new.summary.findings_total, 2);
+        assert!(should_fail(&with_new, ScanFailOn::Error));
+
+        // A second copy of the baselined command is new.
+        let mut duplicated = build_report(
+            vec![
+                finding("a.sh", 10, "git reset --hard", "core.git:reset-hard"),
+                finding("b.sh", 4, "git reset --hard", "core.git:reset-hard"),
+            ],
+            2,
+            0,
+            2,
+            false,
+            None,
+        );
+        duplicated.retain_new_since(&baseline);
+        assert_eq!(duplicated.findings.len(), 1);
+        assert_eq!(duplicated.findings[0].file, "b.sh");
+        assert_eq!(duplicated.summary.baseline_suppressed, Some(1));
+        assert!(should_fail(&duplicated, ScanFailOn::Error));
+    }
+
     fn make_finding(file: &str, decision: ScanDecision, severity: ScanSeverity) -> ScanFinding {
         ScanFinding {
             file: file.to_string(),
@@ -4130,8 +4755,11 @@ resource "null_resource" "test" {
             decision,
             severity,
             rule_id: None,
+            rule_severity: None,
             reason: None,
             suggestion: None,
+            metadata: None,
+            occurrences: Vec::new(),
         }
     }
 
@@ -4642,6 +5270,52 @@ jobs:
         assert_eq!(extracted[0].command, "rm -rf ./build");
     }
 
+    #[test]
+    fn ci_extractors_record_job_and_step_context() {
+        let content = r"jobs:
+  build:
+    steps:
+      - name: Clean
+        run: |
+          echo start
+          rm -rf ./dist
+      - run: rm -rf ./tmp
+  deploy:
+    steps:
+      - run: >
+          rm -rf ./out
+";
+        let extracted =
+            extract_github_actions_workflow_from_str(".github/workflows/ci.yml", content, &["rm"]);
+        let context: Vec<(usize, serde_json::Value)> = extracted
+            .iter()
+            .map(|cmd| (cmd.line, cmd.metadata.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            context,
+            vec![
+                (7, serde_json::json!({ "job": "build", "step": "Clean" })),
+                (8, serde_json::json!({ "job": "build", "step": "#2" })),
+                (12, serde_json::json!({ "job": "deploy", "step": "#1" })),
+            ]
+        );
+
+        let content = r"stages: [build]
+before_script:
+  - echo setup
+cleanup:
+  script:
+    - rm -rf ./build
+";
+        let extracted = extract_gitlab_ci_from_str(".gitlab-ci.yml", content, &["rm"]);
+        assert_eq!(extracted.len(), 1);
+        assert_eq!(extracted[0].line, 6);
+        assert_eq!(
+            extracted[0].metadata,
+            Some(serde_json::json!({ "job": "cleanup" }))
+        );
+    }
+
     #[test]
     fn github_actions_extractor_ignores_run_outside_steps() {
         let content = r"run: rm -rf /
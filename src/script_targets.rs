@@ -0,0 +1,449 @@
+//! Resolve script-runner targets to the commands they run.
+//!
+//! `make clean`, `npm run reset`, and `just teardown` look harmless; the
+//! destructive part lives in the project file that defines the target. When
+//! `[evaluation] resolve_script_targets` is enabled, the named target is looked
+//! up in the `Makefile`, `package.json` `scripts`, or `justfile` in the working
+//! directory and each command it runs is evaluated like a typed command:
+//!
+//! ```toml
+//! [evaluation]
+//! resolve_script_targets = true
+//! ```
+//!
+//! Resolution is best-effort. Missing or unreadable files, unknown targets, and
+//! runner flags that change which file is read (`make -f`, `just --justfile`)
+//! resolve to nothing and leave the original decision unchanged.
+
+use crate::normalize::{NormalizeTokenKind, is_env_assignment, tokenize_for_normalization};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// How many levels of prerequisites and nested runner calls are followed.
+const MAX_DEPTH: usize = 4;
+
+/// A command taken from a script target's definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedCommand {
+    /// How the target was invoked (`npm run nuke`, `make clean`).
+    pub invocation: String,
+    /// Project file the target is defined in.
+    pub file: &'static str,
+    /// One command from the target's body.
+    pub command: String,
+}
+
+/// A script-runner invocation found in a command segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Invocation {
+    Make(Vec<String>),
+    Npm(String),
+    Just(Option<String>),
+}
+
+/// Resolve every script-runner invocation in `command` against the project
+/// files in `dir`.
+///
+/// Prerequisites (`clean: clean-db`), `pre`/`post` npm scripts, and runner
+/// calls inside a target body (`"reset": "npm run nuke"`) are followed up to a
+/// small fixed depth.
+#[must_use]
+pub fn resolve_script_targets(command: &str, dir: &Path) -> Vec<ResolvedCommand> {
+    let mut resolved = Vec::new();
+    let mut seen = HashSet::new();
+    resolve_into(command, dir, 0, &mut seen, &mut resolved);
+    resolved
+}
+
+fn resolve_into(
+    command: &str,
+    dir: &Path,
+    depth: usize,
+    seen: &mut HashSet<(&'static str, String)>,
+    out: &mut Vec<ResolvedCommand>,
+) {
+    if depth >= MAX_DEPTH {
+        return;
+    }
+    for (invocation, segment) in invocations(command) {
+        let (file, bodies) = match invocation {
+            Invocation::Make(targets) => ("Makefile", make_commands(dir, &targets, seen)),
+            Invocation::Npm(script) => ("package.json", npm_commands(dir, &script, seen)),
+            Invocation::Just(recipe) => ("justfile", just_commands(dir, recipe, seen)),
+        };
+        for body in bodies {
+            resolve_into(&body, dir, depth + 1, seen, out);
+            out.push(ResolvedCommand {
+                invocation: segment.clone(),
+                file,
+                command: body,
+            });
+        }
+    }
+}
+
+/// Find script-runner invocations, one per command segment.
+fn invocations(command: &str) -> Vec<(Invocation, String)> {
+    let tokens = tokenize_for_normalization(command);
+    let mut found = Vec::new();
+    let mut words: Vec<&str> = Vec::new();
+    let mut tokens = tokens.iter();
+    loop {
+        let token = tokens.next();
+        if let Some(token) = token.filter(|t| t.kind == NormalizeTokenKind::Word) {
+            words.extend(token.text(command));
+            continue;
+        }
+        let args: Vec<&str> = words
+            .iter()
+            .copied()
+            .skip_while(|word| *word == "sudo" || is_env_assignment(word))
+            .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+            .collect();
+        if let Some(invocation) = parse_invocation(&args) {
+            found.push((invocation, args.join(" ")));
+        }
+        words.clear();
+        if token.is_none() {
+            return found;
+        }
+    }
+}
+
+fn parse_invocation(args: &[&str]) -> Option<Invocation> {
+    let (program, rest) = args.split_first()?;
+    match program.rsplit('/').next()? {
+        "make" | "gmake" => {
+            if rest
+                .iter()
+                .any(|arg| matches!(*arg, "-f" | "-C" | "--directory") || arg.starts_with("--file"))
+            {
+                return None;
+            }
+            let targets = rest
+                .iter()
+                .filter(|arg| !arg.starts_with('-') && !arg.contains('='))
+                .map(|arg| (*arg).to_string())
+                .collect();
+            Some(Invocation::Make(targets))
+        }
+        "npm" | "pnpm" | "yarn" | "bun" => {
+            let mut rest = rest.iter().filter(|arg| !arg.starts_with('-'));
+            match *rest.next()? {
+                "run" | "run-script" => rest.next().map(|s| Invocation::Npm((*s).to_string())),
+                script @ ("start" | "stop" | "restart" | "test") => {
+                    Some(Invocation::Npm(script.to_string()))
+                }
+                _ => None,
+            }
+        }
+        "just" => {
+            if rest
+                .iter()
+                .any(|arg| matches!(*arg, "-f" | "-d") || arg.starts_with("--justfile"))
+            {
+                return None;
+            }
+            let recipe = rest
+                .iter()
+                .find(|arg| !arg.starts_with('-') && !arg.contains('='))
+                .map(|arg| (*arg).to_string());
+            Some(Invocation::Just(recipe))
+        }
+        _ => None,
+    }
+}
+
+fn read_first(dir: &Path, names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| std::fs::read_to_string(dir.join(name)).ok())
+}
+
+/// Strip the echo/ignore-error prefixes make and just allow on recipe lines.
+fn recipe_line(line: &str) -> Option<String> {
+    let line = line.trim().trim_start_matches(['@', '-', '+']).trim();
+    (!line.is_empty() && !line.starts_with('#')).then(|| line.to_string())
+}
+
+// ============================================================================
+// Makefile
+// ============================================================================
+
+/// A rule: its prerequisites and recipe lines.
+type Rule = (Vec<String>, Vec<String>);
+
+fn parse_makefile(content: &str) -> (Option<String>, HashMap<String, Rule>) {
+    let mut rules: HashMap<String, Rule> = HashMap::new();
+    let mut default = None;
+    let mut current: Vec<String> = Vec::new();
+    let mut pending = String::new();
+
+    for raw in content.lines() {
+        // Join backslash continuations before interpreting the line.
+        if let Some(head) = raw.strip_suffix('\\') {
+            pending.push_str(head);
+            pending.push(' ');
+            continue;
+        }
+        let line = std::mem::take(&mut pending) + raw;
+
+        if let Some(body) = line.strip_prefix('\t') {
+            if let Some(body) = recipe_line(body) {
+                let body = body.replace("$$", "$");
+                for target in &current {
+                    rules
+                        .entry(target.clone())
+                        .or_default()
+                        .1
+                        .push(body.clone());
+                }
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        current.clear();
+        let Some((targets, prereqs)) = trimmed.split_once(':') else {
+            continue;
+        };
+        // Variable assignments (`X := y`, `X ::= y`) and target-specific variables.
+        if prereqs.starts_with('=') || prereqs.starts_with(":=") || prereqs.contains('=') {
+            continue;
+        }
+        if targets.contains(['=', '$', '%']) {
+            continue;
+        }
+        let (prereqs, inline) = prereqs
+            .split_once(';')
+            .map_or((prereqs, None), |(p, r)| (p, Some(r)));
+        let prereqs: Vec<String> = prereqs
+            .split_whitespace()
+            .filter(|p| !p.contains('$'))
+            .map(str::to_string)
+            .collect();
+        for target in targets.split_whitespace() {
+            if target.starts_with('.') {
+                continue;
+            }
+            if default.is_none() {
+                default = Some(target.to_string());
+            }
+            let rule = rules.entry(target.to_string()).or_default();
+            rule.0.extend(prereqs.iter().cloned());
+            if let Some(body) = inline.and_then(recipe_line) {
+                rule.1.push(body);
+            }
+            current.push(target.to_string());
+        }
+    }
+    (default, rules)
+}
+
+fn make_commands(
+    dir: &Path,
+    targets: &[String],
+    seen: &mut HashSet<(&'static str, String)>,
+) -> Vec<String> {
+    let Some(content) = read_first(dir, &["GNUmakefile", "makefile", "Makefile"]) else {
+        return Vec::new();
+    };
+    let (default, rules) = parse_makefile(&content);
+    let mut queue: Vec<(String, usize)> = if targets.is_empty() {
+        default.into_iter().map(|t| (t, 0)).collect()
+    } else {
+        targets.iter().map(|t| (t.clone(), 0)).collect()
+    };
+    let mut commands = Vec::new();
+    while let Some((target, depth)) = queue.pop() {
+        if depth >= MAX_DEPTH || !seen.insert(("make", target.clone())) {
+            continue;
+        }
+        let Some((prereqs, body)) = rules.get(&target) else {
+            continue;
+        };
+        queue.extend(prereqs.iter().map(|p| (p.clone(), depth + 1)));
+        commands.extend(body.iter().cloned());
+    }
+    commands
+}
+
+// ============================================================================
+// package.json
+// ============================================================================
+
+fn npm_commands(
+    dir: &Path,
+    script: &str,
+    seen: &mut HashSet<(&'static str, String)>,
+) -> Vec<String> {
+    let Some(content) = read_first(dir, &["package.json"]) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    let Some(scripts) = manifest
+        .get("scripts")
+        .and_then(serde_json::Value::as_object)
+    else {
+        return Vec::new();
+    };
+    [
+        format!("pre{script}"),
+        script.to_string(),
+        format!("post{script}"),
+    ]
+    .into_iter()
+    .filter(|name| seen.insert(("npm", name.clone())))
+    .filter_map(|name| scripts.get(&name)?.as_str().map(str::to_string))
+    .collect()
+}
+
+// ============================================================================
+// justfile
+// ============================================================================
+
+fn parse_justfile(content: &str) -> (Option<String>, HashMap<String, Rule>) {
+    let mut recipes: HashMap<String, Rule> = HashMap::new();
+    let mut default = None;
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let (Some(name), Some(body)) = (&current, recipe_line(line)) {
+                recipes.entry(name.clone()).or_default().1.push(body);
+            }
+            continue;
+        }
+        current = None;
+        let trimmed = line.trim();
+        if trimmed.is_empty()
+            || trimmed.starts_with(['#', '['])
+            || trimmed.contains(":=")
+            || ["set ", "alias ", "import ", "mod ", "export "]
+                .iter()
+                .any(|kw| trimmed.starts_with(kw))
+        {
+            continue;
+        }
+        let Some((header, deps)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let Some(name) = header
+            .split_whitespace()
+            .next()
+            .map(|name| name.trim_start_matches('@'))
+        else {
+            continue;
+        };
+        if default.is_none() {
+            default = Some(name.to_string());
+        }
+        let deps = deps
+            .split_whitespace()
+            .filter(|dep| !dep.starts_with(['(', '&']))
+            .map(|dep| dep.split('(').next().unwrap_or(dep).to_string())
+            .collect();
+        recipes.insert(name.to_string(), (deps, Vec::new()));
+        current = Some(name.to_string());
+    }
+    (default, recipes)
+}
+
+fn just_commands(
+    dir: &Path,
+    recipe: Option<String>,
+    seen: &mut HashSet<(&'static str, String)>,
+) -> Vec<String> {
+    let Some(content) = read_first(dir, &["justfile", "Justfile", ".justfile"]) else {
+        return Vec::new();
+    };
+    let (default, recipes) = parse_justfile(&content);
+    let mut queue: Vec<(String, usize)> = recipe.or(default).map(|r| (r, 0)).into_iter().collect();
+    let mut commands = Vec::new();
+    while let Some((name, depth)) = queue.pop() {
+        if depth >= MAX_DEPTH || !seen.insert(("just", name.clone())) {
+            continue;
+        }
+        let Some((deps, body)) = recipes.get(&name) else {
+            continue;
+        };
+        queue.extend(deps.iter().map(|d| (d.clone(), depth + 1)));
+        commands.extend(body.iter().cloned());
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for (name, content) in files {
+            std::fs::write(dir.path().join(name), content).expect("write fixture");
+        }
+        dir
+    }
+
+    fn commands(command: &str, dir: &Path) -> Vec<String> {
+        resolve_script_targets(command, dir)
+            .into_iter()
+            .map(|resolved| resolved.command)
+            .collect()
+    }
+
+    #[test]
+    fn resolves_npm_scripts_with_pre_and_post() {
+        let dir = project(&[(
+            "package.json",
+            r#"{"scripts": {"prereset": "echo bye", "reset": "npm run nuke", "nuke": "rm -rf /"}}"#,
+        )]);
+        assert_eq!(
+            commands("npm run reset", dir.path()),
+            ["echo bye", "rm -rf /", "npm run nuke"]
+        );
+        assert!(commands("npm run missing", dir.path()).is_empty());
+        assert!(commands("npm install", dir.path()).is_empty());
+    }
+
+    #[test]
+    fn resolves_make_targets_and_prerequisites() {
+        let dir = project(&[(
+            "Makefile",
+            "CLEAN := rm -rf\n\nbuild:\n\tcargo build\n\n.PHONY: clean\nclean: clean-db\n\t@rm -rf target\n\nclean-db:\n\t-dropdb app\n",
+        )]);
+        assert_eq!(commands("make", dir.path()), ["cargo build"]);
+        let mut clean = commands("make clean", dir.path());
+        clean.sort();
+        assert_eq!(clean, ["dropdb app", "rm -rf target"]);
+        assert!(commands("make -f other.mk clean", dir.path()).is_empty());
+    }
+
+    #[test]
+    fn resolves_just_recipes() {
+        let dir = project(&[(
+            "justfile",
+            "set shell := [\"bash\", \"-c\"]\n\ndefault:\n    just --list\n\n@teardown env='dev': stop\n    kubectl delete namespace {{env}}\n\nstop:\n    docker compose down -v\n",
+        )]);
+        let mut teardown = commands("just teardown", dir.path());
+        teardown.sort();
+        assert_eq!(
+            teardown,
+            ["docker compose down -v", "kubectl delete namespace {{env}}"]
+        );
+        assert_eq!(commands("just", dir.path()), ["just --list"]);
+    }
+
+    #[test]
+    fn finds_invocations_in_each_segment() {
+        let dir = project(&[("package.json", r#"{"scripts": {"nuke": "rm -rf /"}}"#)]);
+        let resolved = resolve_script_targets("cd . && CI=1 npm run nuke", dir.path());
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].invocation, "npm run nuke");
+        assert_eq!(resolved[0].file, "package.json");
+    }
+}
@@ -469,7 +469,7 @@ fn parse_plain_command(line: &str, max_command_bytes: Option<usize>) -> ParsedLi
 // and aggregates results into actionable summaries.
 
 use crate::config::Config;
-use crate::evaluator::{EvaluationDecision, EvaluationResult, evaluate_command_with_pack_order};
+use crate::evaluator::{Decision, EvaluationResult, evaluate_command_with_pack_order};
 use crate::packs::REGISTRY;
 use std::collections::{HashMap, HashSet};
 
@@ -494,8 +494,9 @@ impl SimulateDecision {
     #[must_use]
     pub const fn from_evaluation(result: &EvaluationResult) -> Self {
         match result.decision {
-            EvaluationDecision::Allow => Self::Allow,
-            EvaluationDecision::Deny => {
+            Decision::Allow => Self::Allow,
+            Decision::Warn => Self::Warn,
+            Decision::Ask | Decision::Deny => {
                 // Check effective_mode for warn vs deny distinction
                 match result.effective_mode {
                     Some(crate::packs::DecisionMode::Warn) => Self::Warn,
@@ -409,6 +409,27 @@ pub fn format_stats_pretty(stats: &AggregatedStats, period_days: u64) -> String
     output
 }
 
+/// Format per-pack statistics as delimited text (CSV/TSV) with a header row.
+#[must_use]
+pub fn format_stats_delimited(
+    stats: &AggregatedStats,
+    format: crate::output::DelimitedFormat,
+) -> String {
+    let mut output = format.record(["pack_id", "blocks", "allows", "bypasses", "warns"]);
+    output.push('\n');
+    for pack in &stats.by_pack {
+        output.push_str(&format.record([
+            pack.pack_id.clone(),
+            pack.blocks.to_string(),
+            pack.allows.to_string(),
+            pack.bypasses.to_string(),
+            pack.warns.to_string(),
+        ]));
+        output.push('\n');
+    }
+    output
+}
+
 /// Format statistics as JSON.
 #[must_use]
 pub fn format_stats_json(stats: &AggregatedStats) -> String {
@@ -532,5 +553,13 @@ mod tests {
         assert!(output.contains("core.git"));
         assert!(output.contains("core.rm"));
         assert!(output.contains("Total"));
+
+        let csv = format_stats_delimited(&stats, crate::output::DelimitedFormat::Csv);
+        assert_eq!(
+            csv,
+            "pack_id,blocks,allows,bypasses,warns\ncore.git,2,1,0,0\ncore.rm,1,0,1,0\n"
+        );
+        let tsv = format_stats_delimited(&stats, crate::output::DelimitedFormat::Tsv);
+        assert!(tsv.starts_with("pack_id\tblocks\tallows\tbypasses\twarns\n"));
     }
 }
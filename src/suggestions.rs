@@ -272,6 +272,27 @@ fn register_core_git_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>)
         ],
     );
 
+    m.insert(
+        "core.git:reset-hard-pathspec",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Run `git diff -- <path>` to see what would be lost",
+            )
+            .with_command("git diff -- <path>"),
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Use `git restore` to discard changes to specific paths",
+            )
+            .with_command("git restore --source=HEAD --staged --worktree -- <path>"),
+            Suggestion::new(
+                SuggestionKind::WorkflowFix,
+                "Stash just these paths with `git stash push -- <path>`",
+            )
+            .with_command("git stash push -- <path>"),
+        ],
+    );
+
     m.insert(
         "core.git:clean-force",
         vec![
@@ -309,7 +330,11 @@ fn register_core_git_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>)
         ),
     ];
     m.insert("core.git:push-force-long", force_push_suggestions.clone());
-    m.insert("core.git:push-force-short", force_push_suggestions);
+    m.insert("core.git:push-force-short", force_push_suggestions.clone());
+    m.insert(
+        "core.git:push-force-with-lease-overridden",
+        force_push_suggestions,
+    );
 
     // Checkout patterns that discard changes
     let checkout_discard_suggestions = vec![
@@ -328,9 +353,32 @@ fn register_core_git_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>)
         "core.git:checkout-discard",
         checkout_discard_suggestions.clone(),
     );
+    m.insert(
+        "core.git:checkout-discard-pathspec",
+        checkout_discard_suggestions.clone(),
+    );
     m.insert(
         "core.git:checkout-ref-discard",
-        checkout_discard_suggestions,
+        checkout_discard_suggestions.clone(),
+    );
+    m.insert(
+        "core.git:checkout-dot",
+        checkout_discard_suggestions.clone(),
+    );
+    m.insert(
+        "core.git:checkout-ref-pathspec",
+        checkout_discard_suggestions.clone(),
+    );
+    m.insert(
+        "core.git:checkout-path-like",
+        vec![
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Use `git switch` for branches and `git restore` for paths so intent is explicit",
+            )
+            .with_command("git switch <branch>"),
+            checkout_discard_suggestions[0].clone(),
+        ],
     );
 
     m.insert(
@@ -392,6 +440,54 @@ fn register_core_git_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>)
         ],
     );
 
+    // tag and note deletion
+    m.insert(
+        "core.git:tag-delete",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Note the commit the tag points to with `git rev-parse <tag>` so it can be recreated",
+            )
+            .with_command("git rev-parse <tag>"),
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Recreate a deleted tag with `git tag <tag> <sha>`",
+            )
+            .with_command("git tag <tag> <sha>"),
+        ],
+    );
+
+    m.insert(
+        "core.git:push-delete-tag",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Check which remote tags exist with `git ls-remote --tags origin`",
+            )
+            .with_command("git ls-remote --tags origin"),
+            Suggestion::new(
+                SuggestionKind::WorkflowFix,
+                "Publish a new tag instead of deleting a released one; consumers may already depend on it",
+            ),
+        ],
+    );
+
+    m.insert(
+        "core.git:notes-remove",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Review the note with `git notes show <commit>` before removing it",
+            )
+            .with_command("git notes show <commit>"),
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Edit the note with `git notes edit` instead of removing it",
+            )
+            .with_command("git notes edit <commit>"),
+        ],
+    );
+
     // stash destruction
     m.insert(
         "core.git:stash-drop",
@@ -424,6 +520,54 @@ fn register_core_git_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>>)
             .with_command("git stash drop stash@{0}"),
         ],
     );
+
+    m.insert(
+        "core.git:worktree-remove-force",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Check the worktree for uncommitted changes with `git -C <path> status`",
+            )
+            .with_command("git -C <path> status"),
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Remove without `--force`; git refuses if the worktree has changes",
+            )
+            .with_command("git worktree remove <path>"),
+        ],
+    );
+
+    m.insert(
+        "core.git:submodule-deinit-force",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Check every submodule for local changes with `git submodule foreach git status`",
+            )
+            .with_command("git submodule foreach git status"),
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Deinit without `--force`; git refuses if the submodule has changes",
+            )
+            .with_command("git submodule deinit <path>"),
+        ],
+    );
+
+    m.insert(
+        "core.git:worktree-prune",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Preview which worktrees would be pruned with `git worktree prune --dry-run`",
+            )
+            .with_command("git worktree prune --dry-run"),
+            Suggestion::new(
+                SuggestionKind::WorkflowFix,
+                "Lock worktrees on removable media with `git worktree lock` so prune skips them",
+            )
+            .with_command("git worktree lock <path>"),
+        ],
+    );
 }
 
 /// Register suggestions for core.filesystem pack rules.
@@ -450,6 +594,39 @@ fn register_core_filesystem_suggestions(m: &mut HashMap<&'static str, Vec<Sugges
     m.insert("core.filesystem:rm-rf-general", rm_rf_suggestions.clone());
     m.insert("core.filesystem:rm-r-f-separate", rm_rf_suggestions.clone());
     m.insert("core.filesystem:rm-recursive-force-long", rm_rf_suggestions);
+
+    let var_root_suggestions = vec![
+        Suggestion::new(
+            SuggestionKind::SaferAlternative,
+            "Write the path as `\"${VAR:?VAR is unset}/\"` so the shell aborts instead of expanding to `/`",
+        ),
+        Suggestion::new(
+            SuggestionKind::PreviewFirst,
+            "Print the variable first to confirm it is set",
+        )
+        .with_command("echo \"$VAR\""),
+    ];
+    m.insert(
+        "core.filesystem:rm-rf-var-root",
+        var_root_suggestions.clone(),
+    );
+    m.insert("core.filesystem:rm-r-var-root", var_root_suggestions);
+
+    m.insert(
+        "core.filesystem:rm-rf-git-dir",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "List commits that exist only locally with `git log --branches --not --remotes`",
+            )
+            .with_command("git log --branches --not --remotes --oneline"),
+            Suggestion::new(
+                SuggestionKind::SaferAlternative,
+                "Move the history aside instead of deleting it",
+            )
+            .with_command("mv .git /tmp/old-git-backup"),
+        ],
+    );
 }
 
 /// Register suggestions for heredoc pattern rules.
@@ -702,6 +879,21 @@ fn register_kubernetes_suggestions(m: &mut HashMap<&'static str, Vec<Suggestion>
         ],
     );
 
+    m.insert(
+        "kubernetes.kubectl:delete-statefulset",
+        vec![
+            Suggestion::new(
+                SuggestionKind::PreviewFirst,
+                "Use `--dry-run=client` to preview the deletion",
+            ),
+            Suggestion::new(
+                SuggestionKind::WorkflowFix,
+                "List the StatefulSet's PVCs first; they are not deleted with it",
+            )
+            .with_command("kubectl get pvc -l app=<name>"),
+        ],
+    );
+
     m.insert(
         "kubernetes.kubectl:delete-workload",
         vec![
@@ -1493,13 +1685,23 @@ mod tests {
             "core.git:clean-force",
             "core.git:push-force-long",
             "core.git:push-force-short",
+            "core.git:push-force-with-lease-overridden",
             "core.git:checkout-discard",
             "core.git:checkout-ref-discard",
+            "core.git:checkout-dot",
+            "core.git:checkout-ref-pathspec",
+            "core.git:checkout-path-like",
             "core.git:branch-force-delete",
             "core.git:restore-worktree",
             "core.git:restore-worktree-explicit",
+            "core.git:tag-delete",
+            "core.git:push-delete-tag",
+            "core.git:notes-remove",
             "core.git:stash-drop",
             "core.git:stash-clear",
+            "core.git:worktree-remove-force",
+            "core.git:submodule-deinit-force",
+            "core.git:worktree-prune",
         ];
 
         for rule in expected_rules {
@@ -1516,9 +1718,12 @@ mod tests {
         // These must match actual pattern names from src/packs/core/filesystem.rs
         let expected_rules = [
             "core.filesystem:rm-rf-root-home",
+            "core.filesystem:rm-rf-git-dir",
             "core.filesystem:rm-rf-general",
             "core.filesystem:rm-r-f-separate",
             "core.filesystem:rm-recursive-force-long",
+            "core.filesystem:rm-rf-var-root",
+            "core.filesystem:rm-r-var-root",
         ];
 
         for rule in expected_rules {
@@ -1690,6 +1895,7 @@ mod tests {
             "kubernetes.kubectl:drain-node",
             "kubernetes.kubectl:cordon-node",
             "kubernetes.kubectl:taint-noexecute",
+            "kubernetes.kubectl:delete-statefulset",
             "kubernetes.kubectl:delete-workload",
             "kubernetes.kubectl:delete-pvc",
             "kubernetes.kubectl:delete-pv",
@@ -0,0 +1,232 @@
+//! Deterministic command fixtures for property-testing the evaluator.
+//!
+//! [`CommandGenerator`] produces a reproducible stream of shell commands from a
+//! `u64` seed: plain safe and destructive commands, the same commands behind
+//! wrappers (`sudo`, `env`, `command`, `\`), and obfuscated spellings (absolute
+//! binary paths, quoted command words). Feeding the seed from a proptest
+//! strategy gives shrinkable inputs without depending on the generator's
+//! internals:
+//!
+//! ```rust,ignore
+//! proptest! {
+//!     #[test]
+//!     fn normalization_is_idempotent(seed in any::<u64>()) {
+//!         let generated = CommandGenerator::new(seed).next_command();
+//!         let once = normalize_command(&generated.command).into_owned();
+//!         prop_assert_eq!(normalize_command(&once), once.as_str());
+//!     }
+//! }
+//! ```
+//!
+//! The module is compiled for the crate's own tests and, with the `testing`
+//! feature, for downstream crates.
+
+/// Commands that no default pack blocks.
+pub const SAFE_COMMANDS: &[&str] = &[
+    "git status",
+    "git log --oneline -5",
+    "git diff HEAD",
+    "git checkout -b feature",
+    "ls -la",
+    "cat README.md",
+    "grep -rn TODO src",
+    "cargo build --release",
+    "npm install",
+    "echo hello",
+    "rm build.log",
+];
+
+/// Commands that the default packs deny.
+pub const DESTRUCTIVE_COMMANDS: &[&str] = &[
+    "git reset --hard",
+    "git reset --hard HEAD~1",
+    "git clean -fd",
+    "git push --force origin main",
+    "git checkout -- .",
+    "git stash clear",
+    "rm -rf /",
+    "rm -rf ~",
+];
+
+/// Wrapper prefixes that normalization strips before matching.
+const WRAPPERS: &[&str] = &[
+    "sudo ",
+    "sudo -E ",
+    "sudo -u root ",
+    "env ",
+    "env FOO=1 ",
+    "command ",
+    "\\",
+];
+
+/// Directories used to spell a binary by absolute path.
+const BIN_DIRS: &[&str] = &["/usr/bin/", "/bin/", "/usr/local/bin/"];
+
+/// Shape of a generated command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandKind {
+    /// A command from [`SAFE_COMMANDS`], unchanged.
+    Safe,
+    /// A command from [`DESTRUCTIVE_COMMANDS`], unchanged.
+    Destructive,
+    /// A safe or destructive command behind one or two wrappers.
+    Wrapped,
+    /// A safe or destructive command with its binary spelled differently.
+    Obfuscated,
+}
+
+impl CommandKind {
+    const ALL: [Self; 4] = [
+        Self::Safe,
+        Self::Destructive,
+        Self::Wrapped,
+        Self::Obfuscated,
+    ];
+}
+
+/// A generated command and the fixture it was derived from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedCommand {
+    /// How the fixture was transformed.
+    pub kind: CommandKind,
+    /// The command to evaluate.
+    pub command: String,
+    /// The untransformed fixture command.
+    pub base: &'static str,
+    /// Whether `base` comes from [`DESTRUCTIVE_COMMANDS`].
+    pub destructive: bool,
+}
+
+/// Seeded generator of varied commands.
+///
+/// The same seed always yields the same sequence, so a failing case can be
+/// reproduced from the seed alone.
+#[derive(Debug, Clone)]
+pub struct CommandGenerator {
+    state: u64,
+}
+
+impl CommandGenerator {
+    /// Create a generator from a seed.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generate a command of a randomly chosen kind.
+    pub fn next_command(&mut self) -> GeneratedCommand {
+        let kind = self.pick(&CommandKind::ALL);
+        self.generate(kind)
+    }
+
+    /// Generate a command of the given kind.
+    pub fn generate(&mut self, kind: CommandKind) -> GeneratedCommand {
+        let destructive = match kind {
+            CommandKind::Safe => false,
+            CommandKind::Destructive => true,
+            CommandKind::Wrapped | CommandKind::Obfuscated => self.next_u64() % 2 == 0,
+        };
+        let base = if destructive {
+            self.pick(DESTRUCTIVE_COMMANDS)
+        } else {
+            self.pick(SAFE_COMMANDS)
+        };
+        let command = match kind {
+            CommandKind::Safe | CommandKind::Destructive => base.to_string(),
+            CommandKind::Wrapped => self.wrap(base),
+            CommandKind::Obfuscated => self.obfuscate(base),
+        };
+        GeneratedCommand {
+            kind,
+            command,
+            base,
+            destructive,
+        }
+    }
+
+    fn wrap(&mut self, base: &str) -> String {
+        let mut command = String::from(self.pick(WRAPPERS));
+        if self.next_u64() % 3 == 0 {
+            command.insert_str(0, self.pick(&WRAPPERS[..WRAPPERS.len() - 1]));
+        }
+        command.push_str(base);
+        command
+    }
+
+    fn obfuscate(&mut self, base: &str) -> String {
+        let (binary, rest) = base.split_once(' ').unwrap_or((base, ""));
+        let binary = match self.next_u64() % 3 {
+            0 => format!("{}{binary}", self.pick(BIN_DIRS)),
+            1 => format!("\"{binary}\""),
+            _ => format!("'{binary}'"),
+        };
+        if rest.is_empty() {
+            binary
+        } else {
+            format!("{binary} {rest}")
+        }
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        // Truncation is fine: the index is reduced modulo a small length.
+        #[allow(clippy::cast_possible_truncation)]
+        let index = (self.next_u64() % items.len() as u64) as usize;
+        items[index]
+    }
+
+    /// `SplitMix64`: tiny, fast, and good enough for fixture selection.
+    const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl Iterator for CommandGenerator {
+    type Item = GeneratedCommand;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_command())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize::normalize_command;
+    use proptest::prelude::*;
+
+    #[test]
+    fn same_seed_yields_same_commands() {
+        let first: Vec<_> = CommandGenerator::new(7).take(32).collect();
+        let second: Vec<_> = CommandGenerator::new(7).take(32).collect();
+        assert_eq!(first, second);
+
+        let other: Vec<_> = CommandGenerator::new(8).take(32).collect();
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn generates_every_kind() {
+        let mut generator = CommandGenerator::new(0);
+        for kind in CommandKind::ALL {
+            let generated = generator.generate(kind);
+            assert_eq!(generated.kind, kind);
+            let last_word = generated.base.rsplit(' ').next().unwrap();
+            assert!(generated.command.ends_with(last_word), "{generated:?}");
+        }
+    }
+
+    proptest! {
+        /// Normalizing an already-normalized command must not change it.
+        #[test]
+        fn normalization_is_idempotent(seed in any::<u64>()) {
+            let generated = CommandGenerator::new(seed).next_command();
+            let once = normalize_command(&generated.command).into_owned();
+            let twice = normalize_command(&once);
+            prop_assert_eq!(twice.as_ref(), once.as_str(), "seed {} -> {:?}", seed, generated);
+        }
+    }
+}
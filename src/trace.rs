@@ -27,11 +27,11 @@
 //!
 //! ```ignore
 //! use destructive_command_guard::trace::TraceCollector;
-//! use destructive_command_guard::evaluator::EvaluationDecision;
+//! use destructive_command_guard::evaluator::Decision;
 //!
 //! let mut collector = TraceCollector::new("git reset --hard");
 //! // ... pass &mut collector to evaluator ...
-//! let trace = collector.finish(EvaluationDecision::Deny);
+//! let trace = collector.finish(Decision::Deny);
 //!
 //! println!("Decision: {:?}", trace.decision);
 //! println!("Total time: {}us", trace.total_duration_us);
@@ -41,9 +41,10 @@
 //! ```
 
 use crate::allowlist::AllowlistLayer;
-use crate::evaluator::{EvaluationDecision, MatchSource};
-use crate::packs::Severity;
-use serde::Serialize;
+use crate::evaluator::{Decision, MatchSource};
+use crate::logging::{RedactionConfig, redact_command};
+use crate::packs::{DecisionMode, Severity};
+use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
 /// Current JSON schema version for explain output.
@@ -51,6 +52,9 @@ use std::time::Instant;
 /// v2 adds `matched_span`, `matched_text_preview`, and `explanation` in `match`.
 pub const EXPLAIN_JSON_SCHEMA_VERSION: u32 = 2;
 
+/// Schema version for `dcg explain --dump-trace` bundles.
+pub const TRACE_BUNDLE_SCHEMA_VERSION: u32 = 1;
+
 /// A complete trace of a command evaluation.
 ///
 /// Contains all information needed for `dcg explain` output formatting.
@@ -63,7 +67,7 @@ pub struct ExplainTrace {
     /// The sanitized command (after masking safe string arguments).
     pub sanitized_command: Option<String>,
     /// The final decision (Allow or Deny).
-    pub decision: EvaluationDecision,
+    pub decision: Decision,
     /// Whether evaluation was skipped due to time budget exhaustion.
     pub skipped_due_to_budget: bool,
     /// Total evaluation duration in microseconds.
@@ -74,6 +78,10 @@ pub struct ExplainTrace {
     pub match_info: Option<MatchInfo>,
     /// Allowlist override information (when a deny was overridden).
     pub allowlist_info: Option<AllowlistInfo>,
+    /// Why the command was allowed (when the effective outcome is allow).
+    pub allow_reason: Option<AllowReason>,
+    /// Mode the configured policy applies to the match (deny/warn/log).
+    pub effective_mode: Option<DecisionMode>,
     /// Summary of packs that were evaluated.
     pub pack_summary: Option<PackSummary>,
 }
@@ -171,10 +179,18 @@ pub enum TraceDetails {
     /// Final policy decision.
     PolicyDecision {
         /// The decision made.
-        decision: EvaluationDecision,
+        decision: Decision,
         /// Whether the decision was due to allowlist override.
         allowlisted: bool,
     },
+
+    /// `[evaluation] on_error` policy applied to the result.
+    ErrorPolicy {
+        /// Configured mode ("open" or "closed").
+        on_error: String,
+        /// Whether analysis was cut short (budget exceeded).
+        analysis_incomplete: bool,
+    },
 }
 
 /// Information about a pattern match (for denials or allowlist overrides).
@@ -213,6 +229,35 @@ pub struct AllowlistInfo {
     pub original_match: MatchInfo,
 }
 
+/// Why an allowed command was allowed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowReason {
+    /// No destructive pattern matched.
+    NoMatch,
+    /// A safe pattern cleared the command (rule ID, e.g. `core.git:checkout-new-branch`).
+    SafePattern(String),
+    /// A matched rule was overridden by an allowlist entry in this layer.
+    Allowlist(AllowlistLayer),
+    /// A rule matched but policy resolves it to warn or log.
+    BelowThreshold,
+    /// A rule matched but `[policy] observe_until` downgrades it to warn or log.
+    ReportMode,
+}
+
+impl AllowReason {
+    /// Stable label used in JSON output (`no_match`, `safe_pattern:<rule>`, `allowlist:<layer>`, ...).
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::NoMatch => "no_match".to_string(),
+            Self::SafePattern(rule_id) => format!("safe_pattern:{rule_id}"),
+            Self::Allowlist(layer) => format!("allowlist:{}", layer.label()),
+            Self::BelowThreshold => "below_threshold".to_string(),
+            Self::ReportMode => "report_mode".to_string(),
+        }
+    }
+}
+
 /// Summary of pack evaluation.
 #[derive(Debug, Clone)]
 pub struct PackSummary {
@@ -246,6 +291,10 @@ pub struct TraceCollector {
     match_info: Option<MatchInfo>,
     /// Allowlist information (set during evaluation).
     allowlist_info: Option<AllowlistInfo>,
+    /// Allow reason (set during evaluation).
+    allow_reason: Option<AllowReason>,
+    /// Effective policy mode (set during evaluation).
+    effective_mode: Option<DecisionMode>,
     /// Pack summary (set during evaluation).
     pack_summary: Option<PackSummary>,
     /// Whether evaluation skipped deeper analysis due to a budget overrun.
@@ -266,6 +315,8 @@ impl TraceCollector {
             sanitized_command: None,
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
             skipped_due_to_budget: false,
         }
@@ -316,6 +367,16 @@ impl TraceCollector {
         self.allowlist_info = Some(info);
     }
 
+    /// Set the reason an allowed command was allowed.
+    pub fn set_allow_reason(&mut self, reason: AllowReason) {
+        self.allow_reason = Some(reason);
+    }
+
+    /// Set the mode the configured policy applies to the match.
+    pub const fn set_effective_mode(&mut self, mode: Option<DecisionMode>) {
+        self.effective_mode = mode;
+    }
+
     /// Set pack summary.
     pub fn set_pack_summary(&mut self, summary: PackSummary) {
         self.pack_summary = Some(summary);
@@ -329,7 +390,7 @@ impl TraceCollector {
     /// Finish collection and produce the final trace.
     #[allow(clippy::cast_possible_truncation)] // Microseconds fit in u64
     #[must_use]
-    pub fn finish(self, decision: EvaluationDecision) -> ExplainTrace {
+    pub fn finish(self, decision: Decision) -> ExplainTrace {
         let total_duration_us = self.start_time.elapsed().as_micros() as u64;
         ExplainTrace {
             command: self.command,
@@ -341,6 +402,8 @@ impl TraceCollector {
             steps: self.steps,
             match_info: self.match_info,
             allowlist_info: self.allowlist_info,
+            allow_reason: self.allow_reason,
+            effective_mode: self.effective_mode,
             pack_summary: self.pack_summary,
         }
     }
@@ -359,6 +422,33 @@ impl ExplainTrace {
         self.allowlist_info.is_some()
     }
 
+    /// Redact every echoed copy of the command in place.
+    ///
+    /// Applies the `[logging.redaction]` mode even when logging redaction is
+    /// disabled; the caller has already decided the output should be redacted.
+    pub fn redact_commands(&mut self, redaction: &RedactionConfig) {
+        let redaction = RedactionConfig {
+            enabled: true,
+            ..redaction.clone()
+        };
+        let previews = self
+            .match_info
+            .iter_mut()
+            .chain(
+                self.allowlist_info
+                    .iter_mut()
+                    .map(|a| &mut a.original_match),
+            )
+            .filter_map(|info| info.matched_text_preview.as_mut());
+        let texts = std::iter::once(&mut self.command)
+            .chain(self.normalized_command.as_mut())
+            .chain(self.sanitized_command.as_mut())
+            .chain(previews);
+        for text in texts {
+            *text = redact_command(text, &redaction);
+        }
+    }
+
     /// Get the first match (either from denial or allowlist).
     #[must_use]
     pub fn first_match(&self) -> Option<&MatchInfo> {
@@ -385,8 +475,10 @@ impl ExplainTrace {
     pub fn format_compact(&self, max_command_len: Option<usize>) -> String {
         let max_len = max_command_len.unwrap_or(60);
         let decision_str = match self.decision {
-            EvaluationDecision::Allow => "ALLOW",
-            EvaluationDecision::Deny => "DENY",
+            Decision::Allow => "ALLOW",
+            Decision::Warn => "WARN",
+            Decision::Ask => "ASK",
+            Decision::Deny => "DENY",
         };
 
         let duration_str = format_duration(self.total_duration_us);
@@ -408,6 +500,75 @@ impl ExplainTrace {
         self.match_info.as_ref().map(|m| m.reason.as_str())
     }
 
+    /// Format the raw command next to the sanitized string patterns matched against.
+    ///
+    /// Masked regions (safe string arguments blanked out by
+    /// `sanitize_for_pattern_matching`) are marked with `^` under the raw command
+    /// and listed with their byte ranges. When normalization changed the length
+    /// (for example a redacted or rewritten command), the two cannot be aligned
+    /// byte for byte and only the lengths are reported. Returns `None` when no
+    /// sanitized command was recorded.
+    #[must_use]
+    pub fn format_normalization_diff(&self, use_color: bool) -> Option<String> {
+        use std::fmt::Write as _;
+
+        let sanitized = self.sanitized_command.as_deref()?;
+        let (bold, reset, yellow, cyan) = if use_color {
+            ("\x1b[1m", "\x1b[0m", "\x1b[33m", "\x1b[36m")
+        } else {
+            ("", "", "", "")
+        };
+
+        let mut out = String::with_capacity(256);
+        let _ = writeln!(
+            out,
+            "{bold}─── Normalization ─────────────────────────────────────────────────{reset}"
+        );
+        let _ = writeln!(out, "{cyan}Raw:{reset}        {}", self.command);
+        let _ = writeln!(out, "{cyan}Matched on:{reset} {sanitized}");
+
+        let Some(masked) = masked_ranges(&self.command, sanitized) else {
+            let _ = writeln!(
+                out,
+                "Lengths differ ({} bytes raw, {} bytes matched on); masked regions cannot be aligned.",
+                self.command.len(),
+                sanitized.len()
+            );
+            return Some(out);
+        };
+        if masked.is_empty() {
+            out.push_str("Nothing masked; patterns see the command as typed.\n");
+            return Some(out);
+        }
+
+        let markers: String = self
+            .command
+            .char_indices()
+            .map(|(idx, _)| {
+                if masked.iter().any(|r| r.contains(&idx)) {
+                    '^'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        let _ = writeln!(
+            out,
+            "{cyan}Masked:{reset}     {yellow}{}{reset}",
+            markers.trim_end()
+        );
+        for range in &masked {
+            let _ = writeln!(
+                out,
+                "  bytes {}..{} masked: {yellow}{}{reset}",
+                range.start,
+                range.end,
+                self.command.get(range.clone()).unwrap_or_default()
+            );
+        }
+        Some(out)
+    }
+
     /// Format the trace as human-readable pretty output.
     ///
     /// This format is optimized for answering:
@@ -444,10 +605,15 @@ impl ExplainTrace {
 
         // Decision with color
         let decision_str = match self.decision {
-            EvaluationDecision::Allow => format!("{green}{bold}ALLOW{reset}"),
-            EvaluationDecision::Deny => format!("{red}{bold}DENY{reset}"),
+            Decision::Allow => format!("{green}{bold}ALLOW{reset}"),
+            Decision::Warn => format!("{yellow}{bold}WARN{reset}"),
+            Decision::Ask => format!("{yellow}{bold}ASK{reset}"),
+            Decision::Deny => format!("{red}{bold}DENY{reset}"),
         };
         out.push_str(&format!("{bold}Decision:{reset} {decision_str}\n"));
+        if let Some(ref reason) = self.allow_reason {
+            out.push_str(&format!("{bold}Allowed:{reset}  {}\n", reason.label()));
+        }
         out.push_str(&format!(
             "{bold}Latency:{reset}  {}\n",
             format_duration(self.total_duration_us)
@@ -677,15 +843,13 @@ impl ExplainTrace {
             command: self.command.clone(),
             normalized_command: self.normalized_command.clone(),
             sanitized_command: self.sanitized_command.clone(),
-            decision: match self.decision {
-                EvaluationDecision::Allow => "allow".to_string(),
-                EvaluationDecision::Deny => "deny".to_string(),
-            },
+            decision: self.decision.as_str().to_string(),
             skipped_due_to_budget: self.skipped_due_to_budget.then_some(true),
             total_duration_us: self.total_duration_us,
             steps: self.steps.iter().map(TraceStep::to_json).collect(),
             match_info: self.match_info.as_ref().map(MatchInfo::to_json),
             allowlist: self.allowlist_info.as_ref().map(AllowlistInfo::to_json),
+            allow_reason: self.allow_reason.as_ref().map(AllowReason::label),
             pack_summary: self.pack_summary.as_ref().map(PackSummary::to_json),
             suggestions: if suggestions.is_empty() {
                 None
@@ -701,7 +865,7 @@ impl ExplainTrace {
 // ============================================================================
 
 /// Top-level JSON output structure for `dcg explain --format json`.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExplainJsonOutput {
     /// Schema version for forward compatibility.
     pub schema_version: u32,
@@ -728,6 +892,10 @@ pub struct ExplainJsonOutput {
     /// Allowlist override information.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allowlist: Option<JsonAllowlistInfo>,
+    /// Why the command was allowed (`no_match`, `safe_pattern:<rule>`, `allowlist:<layer>`,
+    /// `below_threshold`, or `report_mode`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_reason: Option<String>,
     /// Pack evaluation summary.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pack_summary: Option<JsonPackSummary>,
@@ -737,7 +905,7 @@ pub struct ExplainJsonOutput {
 }
 
 /// JSON representation of a trace step.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonTraceStep {
     /// Step name.
     pub name: String,
@@ -748,7 +916,7 @@ pub struct JsonTraceStep {
 }
 
 /// JSON representation of step details (tagged union).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum JsonTraceDetails {
     InputParsing {
@@ -793,11 +961,15 @@ pub enum JsonTraceDetails {
         decision: String,
         allowlisted: bool,
     },
+    ErrorPolicy {
+        on_error: String,
+        analysis_incomplete: bool,
+    },
 }
 
 /// JSON representation of match information.
 /// Schema v2 adds `matched_span`, `matched_text_preview`, and `explanation`.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonMatchInfo {
     /// Stable rule ID (e.g., "core.git:reset-hard").
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -827,7 +999,7 @@ pub struct JsonMatchInfo {
 }
 
 /// JSON representation of a byte span.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonSpan {
     /// Start byte offset.
     pub start: usize,
@@ -836,7 +1008,7 @@ pub struct JsonSpan {
 }
 
 /// JSON representation of allowlist override.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonAllowlistInfo {
     /// Layer that matched.
     pub layer: String,
@@ -847,7 +1019,7 @@ pub struct JsonAllowlistInfo {
 }
 
 /// JSON representation of pack evaluation summary.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonPackSummary {
     /// Total enabled packs.
     pub enabled_count: usize,
@@ -858,7 +1030,7 @@ pub struct JsonPackSummary {
 }
 
 /// JSON representation of a suggestion.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonSuggestion {
     /// Suggestion kind label.
     pub kind: String,
@@ -872,6 +1044,156 @@ pub struct JsonSuggestion {
     pub url: Option<String>,
 }
 
+// ============================================================================
+// Trace Bundle (bug-report attachment)
+// ============================================================================
+
+/// Complete diagnostic bundle written by `dcg explain --dump-trace <file>`.
+///
+/// Captures everything needed to triage a false positive/negative: the
+/// submitted and normalized command, classified spans, per-pack results, and
+/// the full explain trace. Command text is redacted the same way as the
+/// blocked-command log, since bundles are meant to be shared.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceBundle {
+    /// Bundle schema version.
+    pub schema_version: u32,
+    /// dcg version that produced the bundle.
+    pub dcg_version: String,
+    /// Command as given, redacted.
+    pub command: String,
+    /// Command after wrapper stripping and path normalization, redacted.
+    pub normalized_command: String,
+    /// Context-classified spans of the normalized command. Offsets refer to
+    /// the unredacted command; each span's text is redacted on its own.
+    pub spans: Vec<TraceSpan>,
+    /// Result of checking each enabled pack, in evaluation order.
+    pub packs: Vec<PackTraceResult>,
+    /// Decision: "allow" or "deny".
+    pub decision: String,
+    /// Full explain trace (same schema as `dcg explain --format json`).
+    pub trace: ExplainJsonOutput,
+}
+
+/// A classified span within the normalized command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceSpan {
+    /// Span kind (e.g. "executed", "data", "inline_code").
+    pub kind: String,
+    /// Start byte offset.
+    pub start: usize,
+    /// End byte offset.
+    pub end: usize,
+    /// Text covered by the span.
+    pub text: String,
+}
+
+/// Result of checking the normalized command against a single pack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackTraceResult {
+    /// Pack ID.
+    pub pack_id: String,
+    /// Whether any of the pack's keywords appear in the command.
+    pub keyword_matched: bool,
+    /// Whether a safe pattern matched.
+    pub safe_matched: bool,
+    /// Destructive pattern that matched (if any).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destructive_match: Option<String>,
+    /// Severity of the destructive match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+}
+
+impl TraceBundle {
+    /// Build a bundle from a finished trace and the ordered list of enabled packs.
+    ///
+    /// Spans and pack results are computed from the command as given; every
+    /// command string stored in the bundle then goes through `redaction`
+    /// (forced on, as with [`ExplainTrace::redact_commands`]).
+    #[must_use]
+    pub fn new(trace: &ExplainTrace, pack_ids: &[String], redaction: &RedactionConfig) -> Self {
+        let redaction = RedactionConfig {
+            enabled: true,
+            ..redaction.clone()
+        };
+        let stripped = crate::normalize::strip_wrapper_prefixes(&trace.command);
+        let normalized =
+            crate::normalize::normalize_command(stripped.normalized.as_ref()).into_owned();
+
+        let spans = crate::context::classify_command(&normalized)
+            .spans()
+            .iter()
+            .map(|span| TraceSpan {
+                kind: span.kind.label().to_string(),
+                start: span.byte_range.start,
+                end: span.byte_range.end,
+                text: redact_command(
+                    normalized.get(span.byte_range.clone()).unwrap_or_default(),
+                    &redaction,
+                ),
+            })
+            .collect();
+
+        let packs = pack_ids
+            .iter()
+            .filter_map(|id| crate::packs::REGISTRY.get(id).map(|pack| (id, pack)))
+            .map(|(id, pack)| {
+                let keyword_matched = pack.might_match(&normalized);
+                let safe_matched = keyword_matched && pack.matches_safe_command(&normalized);
+                let destructive = if keyword_matched && !safe_matched {
+                    pack.matches_destructive(&normalized)
+                } else {
+                    None
+                };
+                PackTraceResult {
+                    pack_id: id.clone(),
+                    keyword_matched,
+                    safe_matched,
+                    destructive_match: destructive
+                        .as_ref()
+                        .map(|m| m.name.unwrap_or("unnamed").to_string()),
+                    severity: destructive.map(|m| m.severity.label().to_string()),
+                }
+            })
+            .collect();
+
+        let mut trace = trace.clone();
+        trace.redact_commands(&redaction);
+        let trace = trace.to_json_output();
+        Self {
+            schema_version: TRACE_BUNDLE_SCHEMA_VERSION,
+            dcg_version: env!("CARGO_PKG_VERSION").to_string(),
+            command: trace.command.clone(),
+            normalized_command: redact_command(&normalized, &redaction),
+            spans,
+            packs,
+            decision: trace.decision.clone(),
+            trace,
+        }
+    }
+
+    /// Write the bundle as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or the file write fails.
+    pub fn write_to(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json + "\n")
+    }
+
+    /// Read a bundle previously written with [`TraceBundle::write_to`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid bundle.
+    pub fn read_from(path: &std::path::Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(std::io::Error::other)
+    }
+}
+
 // Conversion implementations
 impl TraceStep {
     fn to_json(&self) -> JsonTraceStep {
@@ -958,12 +1280,16 @@ impl TraceDetails {
                 decision,
                 allowlisted,
             } => JsonTraceDetails::PolicyDecision {
-                decision: match decision {
-                    EvaluationDecision::Allow => "allow".to_string(),
-                    EvaluationDecision::Deny => "deny".to_string(),
-                },
+                decision: decision.as_str().to_string(),
                 allowlisted: *allowlisted,
             },
+            Self::ErrorPolicy {
+                on_error,
+                analysis_incomplete,
+            } => JsonTraceDetails::ErrorPolicy {
+                on_error: on_error.clone(),
+                analysis_incomplete: *analysis_incomplete,
+            },
         }
     }
 }
@@ -1159,8 +1485,10 @@ fn format_step_details_summary(details: &TraceDetails) -> String {
             allowlisted,
         } => {
             let dec = match decision {
-                EvaluationDecision::Allow => "ALLOW",
-                EvaluationDecision::Deny => "DENY",
+                Decision::Allow => "ALLOW",
+                Decision::Warn => "WARN",
+                Decision::Ask => "ASK",
+                Decision::Deny => "DENY",
             };
             if *allowlisted {
                 format!("{dec} (allowlisted)")
@@ -1168,6 +1496,16 @@ fn format_step_details_summary(details: &TraceDetails) -> String {
                 dec.to_string()
             }
         }
+        TraceDetails::ErrorPolicy {
+            on_error,
+            analysis_incomplete,
+        } => {
+            if *analysis_incomplete {
+                format!("on_error={on_error} (analysis incomplete)")
+            } else {
+                format!("on_error={on_error}")
+            }
+        }
     }
 }
 
@@ -1201,6 +1539,30 @@ pub fn format_duration(us: u64) -> String {
     }
 }
 
+/// Byte ranges of `command` that were blanked out in `sanitized`.
+///
+/// Sanitization replaces masked bytes with spaces and keeps the length, so any
+/// differing byte was masked. Runs split only by whitespace are merged so a
+/// masked multi-word argument reports as one range. Returns `None` when the
+/// lengths differ (e.g., after redaction), since the bytes cannot be aligned.
+fn masked_ranges(command: &str, sanitized: &str) -> Option<Vec<std::ops::Range<usize>>> {
+    let (raw, clean) = (command.as_bytes(), sanitized.as_bytes());
+    if raw.len() != clean.len() {
+        return None;
+    }
+
+    let mut ranges: Vec<std::ops::Range<usize>> = Vec::new();
+    for idx in (0..raw.len()).filter(|&i| raw[i] != clean[i]) {
+        match ranges.last_mut() {
+            Some(last) if raw[last.end..idx].iter().all(u8::is_ascii_whitespace) => {
+                last.end = idx + 1;
+            }
+            _ => ranges.push(idx..idx + 1),
+        }
+    }
+    Some(ranges)
+}
+
 /// Truncate a string to at most `max_len` characters, ensuring UTF-8 safety.
 ///
 /// If truncation is needed, appends "..." and ensures the result is at most `max_len` chars.
@@ -1261,9 +1623,9 @@ mod tests {
             explanation: None,
         });
 
-        let trace = collector.finish(EvaluationDecision::Deny);
+        let trace = collector.finish(Decision::Deny);
 
-        assert_eq!(trace.decision, EvaluationDecision::Deny);
+        assert_eq!(trace.decision, Decision::Deny);
         assert_eq!(trace.command, "git reset --hard");
         assert!(trace.total_duration_us > 0);
         assert_eq!(trace.steps.len(), 1);
@@ -1286,9 +1648,9 @@ mod tests {
             },
         );
 
-        let trace = collector.finish(EvaluationDecision::Allow);
+        let trace = collector.finish(Decision::Allow);
 
-        assert_eq!(trace.decision, EvaluationDecision::Allow);
+        assert_eq!(trace.decision, Decision::Allow);
         assert!(trace.match_info.is_none());
         assert!(!trace.was_allowlisted());
     }
@@ -1316,9 +1678,9 @@ mod tests {
             original_match,
         });
 
-        let trace = collector.finish(EvaluationDecision::Allow);
+        let trace = collector.finish(Decision::Allow);
 
-        assert_eq!(trace.decision, EvaluationDecision::Allow);
+        assert_eq!(trace.decision, Decision::Allow);
         assert!(trace.was_allowlisted());
         assert!(trace.allowlist_info.is_some());
         assert_eq!(
@@ -1352,12 +1714,12 @@ mod tests {
             "step3",
             30,
             TraceDetails::PolicyDecision {
-                decision: EvaluationDecision::Allow,
+                decision: Decision::Allow,
                 allowlisted: false,
             },
         );
 
-        let trace = collector.finish(EvaluationDecision::Allow);
+        let trace = collector.finish(Decision::Allow);
 
         assert_eq!(trace.steps.len(), 3);
         assert_eq!(trace.steps[0].name, "step1");
@@ -1382,7 +1744,7 @@ mod tests {
             },
         );
 
-        let trace = collector.finish(EvaluationDecision::Allow);
+        let trace = collector.finish(Decision::Allow);
 
         assert!(trace.find_step("keyword_gating").is_some());
         assert!(trace.find_step("nonexistent").is_none());
@@ -1501,7 +1863,7 @@ mod tests {
             "test",
             94,
             TraceDetails::PolicyDecision {
-                decision: EvaluationDecision::Allow,
+                decision: Decision::Allow,
                 allowlisted: false,
             },
         );
@@ -1510,12 +1872,14 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 94,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1529,7 +1893,7 @@ mod tests {
             command: "git reset --hard".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 847,
             steps: vec![],
@@ -1546,6 +1910,8 @@ mod tests {
                 explanation: None,
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1564,12 +1930,14 @@ mod tests {
             command: long_cmd.to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 1200,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1584,7 +1952,7 @@ mod tests {
             command: "docker system prune -af".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 1_500,
             steps: vec![],
@@ -1601,6 +1969,8 @@ mod tests {
                 explanation: None,
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1621,12 +1991,14 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 94,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1651,7 +2023,7 @@ mod tests {
             command: "git reset --hard".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 847,
             steps: vec![],
@@ -1668,6 +2040,8 @@ mod tests {
                 explanation: None,
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1703,7 +2077,7 @@ mod tests {
             command: "sudo git reset --hard".to_string(),
             normalized_command: Some("git reset --hard".to_string()),
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 1200,
             steps: vec![],
@@ -1720,6 +2094,8 @@ mod tests {
                 explanation: None,
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1751,7 +2127,7 @@ mod tests {
             command: "git reset --hard".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 500,
             steps: vec![],
@@ -1761,6 +2137,8 @@ mod tests {
                 entry_reason: "Allowed for release automation".to_string(),
                 original_match,
             }),
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1775,18 +2153,55 @@ mod tests {
         assert!(pretty.contains("Overrode core.git:reset-hard"));
     }
 
+    #[test]
+    fn allow_reason_labels_are_stable_and_serialized() {
+        assert_eq!(AllowReason::NoMatch.label(), "no_match");
+        assert_eq!(
+            AllowReason::SafePattern("core.git:checkout-new-branch".to_string()).label(),
+            "safe_pattern:core.git:checkout-new-branch"
+        );
+        assert_eq!(
+            AllowReason::Allowlist(AllowlistLayer::User).label(),
+            "allowlist:user"
+        );
+        assert_eq!(AllowReason::BelowThreshold.label(), "below_threshold");
+        assert_eq!(AllowReason::ReportMode.label(), "report_mode");
+
+        let mut collector = TraceCollector::new("git checkout -b feature");
+        collector.set_allow_reason(AllowReason::SafePattern(
+            "core.git:checkout-new-branch".to_string(),
+        ));
+        let trace = collector.finish(Decision::Allow);
+        let json = trace.to_json_output();
+        assert_eq!(
+            json.allow_reason.as_deref(),
+            Some("safe_pattern:core.git:checkout-new-branch")
+        );
+        assert!(
+            trace
+                .format_pretty(false)
+                .contains("Allowed:  safe_pattern:core.git:checkout-new-branch")
+        );
+
+        let denied = TraceCollector::new("git reset --hard").finish(Decision::Deny);
+        let json = serde_json::to_value(denied.to_json_output()).unwrap();
+        assert!(json.get("allow_reason").is_none());
+    }
+
     #[test]
     fn format_pretty_with_pack_summary() {
         let trace = ExplainTrace {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 100,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: Some(PackSummary {
                 enabled_count: 5,
                 evaluated: vec!["core.git".to_string()],
@@ -1814,7 +2229,7 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 200,
             steps: vec![
@@ -1841,13 +2256,15 @@ mod tests {
                     name: "policy_decision",
                     duration_us: 10,
                     details: TraceDetails::PolicyDecision {
-                        decision: EvaluationDecision::Allow,
+                        decision: Decision::Allow,
                         allowlisted: false,
                     },
                 },
             ],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1869,7 +2286,7 @@ mod tests {
             command: "git reset --hard".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 847,
             steps: vec![],
@@ -1886,6 +2303,8 @@ mod tests {
                 explanation: None,
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -1984,7 +2403,7 @@ mod tests {
         );
 
         let policy_allow = TraceDetails::PolicyDecision {
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             allowlisted: true,
         };
         assert_eq!(
@@ -2003,12 +2422,14 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 94,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2025,12 +2446,14 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: true,
             total_duration_us: 10,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2044,7 +2467,7 @@ mod tests {
             command: "git reset --hard".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 847,
             steps: vec![],
@@ -2061,6 +2484,8 @@ mod tests {
                 explanation: None,
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2094,7 +2519,7 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 200,
             steps: vec![
@@ -2111,13 +2536,15 @@ mod tests {
                     name: "policy_decision",
                     duration_us: 10,
                     details: TraceDetails::PolicyDecision {
-                        decision: EvaluationDecision::Allow,
+                        decision: Decision::Allow,
                         allowlisted: false,
                     },
                 },
             ],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2152,7 +2579,7 @@ mod tests {
             command: "git reset --hard".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 500,
             steps: vec![],
@@ -2162,6 +2589,8 @@ mod tests {
                 entry_reason: "Allowed for release automation".to_string(),
                 original_match,
             }),
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2180,12 +2609,14 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 100,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: Some(PackSummary {
                 enabled_count: 5,
                 evaluated: vec!["core.git".to_string()],
@@ -2209,7 +2640,7 @@ mod tests {
             command: "git reset --hard".to_string(),
             normalized_command: Some("git reset --hard".to_string()),
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 847,
             steps: vec![TraceStep {
@@ -2234,6 +2665,8 @@ mod tests {
                 explanation: None,
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: Some(PackSummary {
                 enabled_count: 3,
                 evaluated: vec!["core.git".to_string()],
@@ -2267,12 +2700,14 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 100,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2533,7 +2968,7 @@ mod tests {
             command: "git reset --hard".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 100,
             steps: vec![],
@@ -2550,6 +2985,8 @@ mod tests {
                 explanation: Some("This is a detailed explanation.".to_string()),
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2570,7 +3007,7 @@ mod tests {
             command: "docker system prune -af".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Deny,
+            decision: Decision::Deny,
             skipped_due_to_budget: false,
             total_duration_us: 100,
             steps: vec![],
@@ -2587,6 +3024,8 @@ mod tests {
                 explanation: None, // No explicit explanation
             }),
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2616,12 +3055,14 @@ mod tests {
             command: "git status".to_string(),
             normalized_command: None,
             sanitized_command: None,
-            decision: EvaluationDecision::Allow,
+            decision: Decision::Allow,
             skipped_due_to_budget: false,
             total_duration_us: 100,
             steps: vec![],
             match_info: None,
             allowlist_info: None,
+            allow_reason: None,
+            effective_mode: None,
             pack_summary: None,
         };
 
@@ -2631,4 +3072,77 @@ mod tests {
         let version = parsed["schema_version"].as_u64();
         assert_eq!(version, Some(2), "Schema version should be 2");
     }
+
+    #[test]
+    fn trace_bundle_round_trips_with_spans() {
+        let mut collector = TraceCollector::new("git reset --hard");
+        collector.set_match(MatchInfo {
+            rule_id: Some("core.git:reset-hard".to_string()),
+            pack_id: Some("core.git".to_string()),
+            pattern_name: Some("reset-hard".to_string()),
+            severity: Some(Severity::Critical),
+            reason: "destroys uncommitted changes".to_string(),
+            source: MatchSource::Pack,
+            match_start: Some(0),
+            match_end: Some(16),
+            matched_text_preview: Some("git reset --hard".to_string()),
+            explanation: None,
+        });
+        let trace = collector.finish(Decision::Deny);
+
+        let bundle = TraceBundle::new(
+            &trace,
+            &["core.git".to_string()],
+            &RedactionConfig::default(),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.json");
+        bundle.write_to(&path).unwrap();
+
+        let loaded = TraceBundle::read_from(&path).unwrap();
+        assert_eq!(loaded, bundle);
+        assert_eq!(loaded.schema_version, TRACE_BUNDLE_SCHEMA_VERSION);
+        assert_eq!(loaded.decision, "deny");
+        assert!(!loaded.spans.is_empty());
+        assert!(loaded.spans.iter().any(|span| span.kind == "executed"));
+        assert_eq!(loaded.packs.len(), 1);
+        assert_eq!(
+            loaded.packs[0].destructive_match.as_deref(),
+            Some("reset-hard")
+        );
+    }
+
+    #[test]
+    fn trace_bundle_redacts_secrets_in_command() {
+        let command = "curl -H 'Authorization: Bearer abc123' https://x && API_TOKEN=hunter2 git reset --hard";
+        let mut collector = TraceCollector::new(command);
+        collector.set_normalized(command);
+        collector.set_match(MatchInfo {
+            rule_id: Some("core.git:reset-hard".to_string()),
+            pack_id: Some("core.git".to_string()),
+            pattern_name: Some("reset-hard".to_string()),
+            severity: Some(Severity::Critical),
+            reason: "destroys uncommitted changes".to_string(),
+            source: MatchSource::Pack,
+            match_start: None,
+            match_end: None,
+            matched_text_preview: Some("API_TOKEN=hunter2 git reset --hard".to_string()),
+            explanation: None,
+        });
+        let trace = collector.finish(Decision::Deny);
+
+        let bundle = TraceBundle::new(
+            &trace,
+            &["core.git".to_string()],
+            &RedactionConfig::default(),
+        );
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains("hunter2"), "secret leaked: {json}");
+        assert!(!json.contains("abc123"), "secret leaked: {json}");
+        // Analysis still ran on the command as given.
+        assert_eq!(
+            bundle.packs[0].destructive_match.as_deref(),
+            Some("reset-hard")
+        );
+    }
 }
@@ -170,13 +170,29 @@ fn test_exit_0_for_write_tool() {
         r#"{"tool_name":"Write","tool_input":{"file_path":"/tmp/test.txt","content":"hello"}}"#;
     let (stdout, _stderr, exit_code) = run_hook_mode_raw(input);
 
-    assert_eq!(exit_code, 0, "Write tool should exit 0 (skip)");
+    assert_eq!(exit_code, 0, "Write tool should exit 0");
     assert!(
         stdout.trim().is_empty(),
-        "Write tool should produce no output"
+        "Write to an unprotected path should produce no output"
     );
 }
 
+#[test]
+fn test_exit_0_with_deny_for_write_to_protected_path() {
+    let input =
+        r#"{"tool_name":"Write","tool_input":{"file_path":"/etc/passwd","content":"root::0:0"}}"#;
+    let (stdout, stderr, exit_code) = run_hook_mode_raw(input);
+
+    assert_eq!(
+        exit_code, 0,
+        "denied Write should exit 0 with JSON\nstderr: {stderr}"
+    );
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("deny JSON on stdout");
+    let output = &json["hookSpecificOutput"];
+    assert_eq!(output["permissionDecision"], "deny");
+    assert_eq!(output["ruleId"], "core.filesystem:write-protected-path");
+}
+
 // =============================================================================
 // Error Exit Codes
 // =============================================================================
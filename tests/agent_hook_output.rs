@@ -364,3 +364,49 @@ fn test_hook_output_remediation_safe_alternative() {
         }
     }
 }
+
+#[test]
+fn test_hook_reads_input_from_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let envelope = dir.path().join("envelope.json");
+    std::fs::write(
+        &envelope,
+        r#"{"tool_name":"Bash","tool_input":{"command":"git reset --hard"}}"#,
+    )
+    .unwrap();
+
+    // stdin is closed, so the decision can only come from --input-path.
+    let output = Command::new(dcg_binary())
+        .arg("--input-path")
+        .arg(&envelope)
+        .stdin(Stdio::null())
+        .output()
+        .expect("failed to run dcg");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("hook output should be valid JSON");
+    assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_hook_reads_input_from_fd() {
+    let mut child = Command::new(dcg_binary())
+        .args(["--input-fd", "0"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn dcg process");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(br#"{"tool_name":"Bash","tool_input":{"command":"git reset --hard"}}"#)
+        .expect("failed to write envelope");
+
+    let output = child.wait_with_output().expect("failed to wait for dcg");
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8_lossy(&output.stdout))
+        .expect("hook output should be valid JSON");
+    assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+}
@@ -108,6 +108,7 @@ fn run_dcg_hook(command: &str) -> HookRunOutput {
 
 mod explain_tests {
     use super::*;
+    use destructive_command_guard::exit_codes::{EXIT_DENIED, EXIT_SUCCESS, EXIT_WARNING};
 
     #[test]
     fn explain_safe_command_returns_allow_pretty() {
@@ -139,6 +140,70 @@ mod explain_tests {
         assert!(stdout.contains("core.git"), "should mention pack");
     }
 
+    #[test]
+    fn explain_exit_code_maps_decision() {
+        let output = run_dcg(&["explain", "--exit-code", "git reset --hard"]);
+        assert_eq!(
+            output.status.code(),
+            Some(EXIT_DENIED),
+            "--exit-code should return EXIT_DENIED for a denied command"
+        );
+
+        let output = run_dcg(&["explain", "--exit-code", "echo hello"]);
+        assert_eq!(output.status.code(), Some(EXIT_SUCCESS));
+
+        // Regraded to Medium, which warns: the code follows the evaluated decision
+        let output = run_dcg(&[
+            "explain",
+            "--exit-code",
+            "git push --force origin feature/x",
+        ]);
+        assert_eq!(output.status.code(), Some(EXIT_WARNING));
+    }
+
+    #[test]
+    fn explain_json_reports_allow_reason() {
+        let explain_json = |command: &str, xdg_config_dir: &std::path::Path| {
+            let output = Command::new(dcg_binary())
+                .env("HOME", xdg_config_dir.join("home"))
+                .env("XDG_CONFIG_HOME", xdg_config_dir)
+                .env("DCG_ALLOWLIST_SYSTEM_PATH", "")
+                .args(["explain", "--format", "json", command])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .output()
+                .expect("failed to execute dcg");
+            serde_json::from_str::<serde_json::Value>(&String::from_utf8_lossy(&output.stdout))
+                .expect("explain --format json should produce valid JSON")
+        };
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let json = explain_json("git checkout -b feature", temp.path());
+        assert_eq!(json["decision"], "allow");
+        assert_eq!(
+            json["allow_reason"],
+            "safe_pattern:core.git:checkout-new-branch"
+        );
+
+        let json = explain_json("git status", temp.path());
+        assert_eq!(json["allow_reason"], "no_match");
+
+        let user_dir = temp.path().join("dcg");
+        std::fs::create_dir_all(&user_dir).expect("user config dir");
+        std::fs::write(
+            user_dir.join("allowlist.toml"),
+            "[[allow]]\nrule = \"core.git:reset-hard\"\nreason = \"release automation\"\n",
+        )
+        .expect("write allowlist");
+        let json = explain_json("git reset --hard", temp.path());
+        assert_eq!(json["decision"], "allow");
+        assert_eq!(json["allow_reason"], "allowlist:user");
+        assert_eq!(
+            json["allowlist"]["original_match"]["rule_id"],
+            "core.git:reset-hard"
+        );
+    }
+
     #[test]
     fn explain_json_format_is_valid() {
         // Use git command since core.git is always enabled
@@ -456,6 +521,11 @@ mod allow_once_flow_tests {
 
         /// Run dcg in hook mode with JSON input.
         fn run_hook(&self, command: &str) -> HookRunOutput {
+            self.run_hook_with_env(command, &[])
+        }
+
+        /// Run dcg in hook mode with extra environment variables.
+        fn run_hook_with_env(&self, command: &str, extra_env: &[(&str, &str)]) -> HookRunOutput {
             let input = serde_json::json!({
                 "tool_name": "Bash",
                 "tool_input": {
@@ -475,6 +545,9 @@ mod allow_once_flow_tests {
                 .stdin(Stdio::piped())
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped());
+            for (key, value) in extra_env {
+                cmd.env(key, value);
+            }
 
             let mut child = cmd.spawn().expect("failed to spawn dcg hook mode");
 
@@ -630,6 +703,55 @@ mod allow_once_flow_tests {
         assert_is_allowed(&result3);
     }
 
+    #[test]
+    fn require_hmac_rejects_token_minted_with_env_secret() {
+        let env = FlowTestEnv::new();
+        let command = "git reset --hard";
+        let agent_secret = "secret-chosen-by-the-agent";
+        let hook_env = [
+            ("DCG_REQUIRE_HMAC", "1"),
+            ("DCG_ALLOW_ONCE_SECRET", agent_secret),
+        ];
+
+        let result = env.run_hook_with_env(command, &hook_env);
+        let stdout = assert_is_denial(&result);
+        let json: serde_json::Value =
+            serde_json::from_str(stdout.trim()).expect("expected JSON stdout");
+        let code = json["hookSpecificOutput"]["allowOnceCode"]
+            .as_str()
+            .expect("should have allowOnceCode");
+        let full_hash = json["hookSpecificOutput"]["allowOnceFullHash"]
+            .as_str()
+            .expect("should have allowOnceFullHash");
+
+        // An agent that can set DCG_ALLOW_ONCE_SECRET can compute this token;
+        // only the on-disk key may verify it.
+        let forged = destructive_command_guard::pending_exceptions::confirmation_token(
+            full_hash,
+            agent_secret,
+        );
+        let output = Command::new(dcg_binary())
+            .env_clear()
+            .env("HOME", &env.home_dir)
+            .env("XDG_CONFIG_HOME", &env.xdg_config_dir)
+            .env("DCG_ALLOWLIST_SYSTEM_PATH", "")
+            .env("DCG_PENDING_EXCEPTIONS_PATH", &env.pending_path)
+            .env("DCG_ALLOW_ONCE_PATH", &env.allow_once_path)
+            .envs(hook_env)
+            .current_dir(env.temp.path())
+            .args(["allow-once", code, "--token", &forged, "--yes"])
+            .output()
+            .expect("run dcg cli");
+        assert!(
+            !output.status.success(),
+            "forged token should be rejected\nstdout: {}\nstderr: {}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        assert_is_denial(&env.run_hook_with_env(command, &hook_env));
+    }
+
     #[test]
     fn block_emits_full_hash_in_hook_output() {
         let env = FlowTestEnv::new();
@@ -707,11 +829,35 @@ mod allow_once_flow_tests {
         let result2 = env.run_hook(command);
         assert_is_allowed(&result2);
 
-        // Step 4: Second run is blocked again (single-use consumed)
-        let result3 = env.run_hook(command);
+        // Step 4: Second run is blocked again (single-use consumed, no cooldown)
+        let result3 = env.run_hook_with_env(command, &[("DCG_ALLOW_ONCE_COOLDOWN_SECS", "0")]);
         assert_is_denial(&result3);
     }
 
+    #[test]
+    fn single_use_cooldown_allows_immediate_retry() {
+        let env = FlowTestEnv::new();
+        let command = "git reset --hard";
+
+        let result1 = env.run_hook(command);
+        let stdout1 = assert_is_denial(&result1);
+        let code = extract_code_from_denial(&stdout1).expect("should emit code");
+
+        let allow_output = env.run_cli(&["allow-once", &code, "--yes", "--single-use"]);
+        assert!(
+            allow_output.status.success(),
+            "allow-once --single-use should succeed\nstdout: {}\nstderr: {}",
+            String::from_utf8_lossy(&allow_output.stdout),
+            String::from_utf8_lossy(&allow_output.stderr)
+        );
+
+        // Approval consumes the entry; an immediate retry stays within the cooldown.
+        let result2 = env.run_hook(command);
+        assert_is_allowed(&result2);
+        let result3 = env.run_hook(command);
+        assert_is_allowed(&result3);
+    }
+
     #[test]
     fn allow_once_list_shows_pending_and_active_entries() {
         let env = FlowTestEnv::new();
@@ -1092,6 +1238,78 @@ mod scan_tests {
         );
     }
 
+    #[test]
+    fn scan_severity_at_least_filters_findings_not_summary() {
+        let mut file = tempfile::Builder::new().suffix(".sh").tempfile().unwrap();
+        writeln!(file, "git reset --hard").unwrap(); // critical
+        writeln!(file, "git branch -D topic").unwrap(); // medium
+        file.flush().unwrap();
+
+        let output = run_dcg(&[
+            "scan",
+            "--paths",
+            file.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--fail-on",
+            "none",
+            "--severity-at-least",
+            "high",
+        ]);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        let findings = json["findings"].as_array().unwrap();
+
+        assert_eq!(findings.len(), 1, "only the critical finding is shown");
+        assert_eq!(findings[0]["rule_id"], "core.git:reset-hard");
+        assert_eq!(findings[0]["rule_severity"], "critical");
+        assert_eq!(
+            json["summary"]["findings_total"], 2,
+            "summary still counts the hidden finding"
+        );
+    }
+
+    #[test]
+    fn scan_github_workflow_reports_run_step_with_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let workflows = dir.path().join(".github").join("workflows");
+        std::fs::create_dir_all(&workflows).unwrap();
+        let workflow = workflows.join("ci.yml");
+        std::fs::write(
+            &workflow,
+            "on: [push]\n\
+             jobs:\n\
+             \x20 cleanup:\n\
+             \x20   runs-on: ubuntu-latest\n\
+             \x20   steps:\n\
+             \x20     - uses: actions/checkout@v4\n\
+             \x20     - name: Wipe\n\
+             \x20       run: rm -rf /\n",
+        )
+        .unwrap();
+
+        let output = run_dcg(&[
+            "scan",
+            "--paths",
+            workflow.to_str().unwrap(),
+            "--format",
+            "json",
+            "--fail-on",
+            "none",
+        ]);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        let findings = json["findings"].as_array().unwrap();
+
+        assert_eq!(findings.len(), 1, "{stdout}");
+        assert_eq!(findings[0]["line"], 8);
+        assert_eq!(findings[0]["extractor_id"], "github_actions.steps.run");
+        assert_eq!(findings[0]["metadata"]["job"], "cleanup");
+        assert_eq!(findings[0]["metadata"]["step"], "Wipe");
+    }
+
     #[test]
     fn scan_empty_directory_succeeds() {
         let dir = tempfile::tempdir().unwrap();
@@ -1258,6 +1476,38 @@ mod config_tests {
         );
     }
 
+    #[test]
+    fn config_check_reports_unknown_pack_id() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let home_dir = temp.path().join("home");
+        let xdg_config_dir = temp.path().join("xdg_config");
+        std::fs::create_dir_all(&home_dir).expect("HOME dir");
+        std::fs::create_dir_all(&xdg_config_dir).expect("XDG_CONFIG_HOME dir");
+
+        let cfg_path = temp.path().join("explicit_config.toml");
+        std::fs::write(&cfg_path, "[packs]\nenabled = [\"containers.dockr\"]\n")
+            .expect("write config");
+
+        let output = Command::new(dcg_binary())
+            .env_clear()
+            .env("HOME", &home_dir)
+            .env("XDG_CONFIG_HOME", &xdg_config_dir)
+            .env("DCG_CONFIG", &cfg_path)
+            .current_dir(temp.path())
+            .args(["config", "--check"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .expect("run dcg config --check");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(output.status.code(), Some(3), "stdout:\n{stdout}");
+        assert!(
+            stdout.contains("packs.enabled") && stdout.contains("containers.dockr"),
+            "expected a diagnostic for packs.enabled\nstdout:\n{stdout}"
+        );
+    }
+
     #[test]
     fn doctor_reports_missing_dcg_config_override() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -1456,6 +1706,33 @@ mod packs_tests {
     }
 }
 
+// ============================================================================
+// DCG SELFTEST Tests
+// ============================================================================
+
+mod selftest_tests {
+    use super::*;
+
+    #[test]
+    fn selftest_regex_passes_for_builtin_packs() {
+        let output = run_dcg(&["selftest", "--regex", "--format", "json"]);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert_eq!(output.status.code(), Some(0), "{stdout}");
+        let json: serde_json::Value =
+            serde_json::from_str(&stdout).expect("selftest --format json should produce JSON");
+        assert_eq!(json["passed"], true);
+        assert!(json["patterns_checked"].as_u64().unwrap_or(0) > 0);
+    }
+
+    #[test]
+    fn selftest_requires_a_suite() {
+        let output = run_dcg(&["selftest"]);
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("--regex"));
+    }
+}
+
 // ============================================================================
 // DCG Hook Mode Tests (stdin JSON protocol)
 // ============================================================================
@@ -1468,6 +1745,336 @@ mod hook_mode_tests {
         AllowOnceEntry, AllowOnceScopeKind, PendingExceptionRecord,
     };
 
+    #[test]
+    fn safe_dir_from_hook_cwd_downgrades_deny_to_warn() {
+        let temp = tempfile::tempdir().unwrap();
+        let home_dir = temp.path().join("home");
+        let project = temp.path().join("project");
+        let scratch = temp.path().join("scratch").join("exp");
+        for dir in [&home_dir, &project, &scratch] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        let config_path = temp.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            format!(
+                "[safe_dirs]\npaths = [\"{}/**\"]\n",
+                temp.path().join("scratch").display()
+            ),
+        )
+        .unwrap();
+
+        let run = |cwd: &std::path::Path| {
+            let input = serde_json::json!({
+                "tool_name": "Bash",
+                "tool_input": { "command": "rm -rf ." },
+                "cwd": cwd,
+            });
+            let mut child = Command::new(dcg_binary())
+                .env_clear()
+                .env("HOME", &home_dir)
+                .env("XDG_CONFIG_HOME", temp.path().join("xdg"))
+                .env("DCG_ALLOWLIST_SYSTEM_PATH", "")
+                .env("DCG_PACKS", "core.filesystem")
+                .env("DCG_CONFIG", &config_path)
+                .current_dir(temp.path())
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("failed to spawn dcg hook mode");
+            serde_json::to_writer(child.stdin.as_mut().unwrap(), &input).unwrap();
+            child.wait_with_output().expect("failed to wait for dcg")
+        };
+
+        let denied = run(&project);
+        let stdout = String::from_utf8_lossy(&denied.stdout);
+        let json: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+        assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+
+        let warned = run(&scratch);
+        let stdout = String::from_utf8_lossy(&warned.stdout);
+        assert!(
+            stdout.trim().is_empty(),
+            "safe dir should not emit a deny\nstdout:\n{stdout}"
+        );
+        assert!(
+            !String::from_utf8_lossy(&warned.stderr).is_empty(),
+            "safe dir should still warn on stderr"
+        );
+
+        // `dcg explain` reaches the same verdict from the same directories.
+        let explain = |cwd: &std::path::Path| {
+            let output = Command::new(dcg_binary())
+                .env_clear()
+                .env("HOME", &home_dir)
+                .env("XDG_CONFIG_HOME", temp.path().join("xdg"))
+                .env("DCG_ALLOWLIST_SYSTEM_PATH", "")
+                .env("DCG_PACKS", "core.filesystem")
+                .env("DCG_CONFIG", &config_path)
+                .args(["explain", "--format", "json", "rm -rf ."])
+                .current_dir(cwd)
+                .output()
+                .expect("failed to run dcg explain");
+            let json: serde_json::Value =
+                serde_json::from_slice(&output.stdout).expect("explain JSON");
+            json["decision"].as_str().unwrap_or_default().to_string()
+        };
+        assert_eq!(explain(&project), "deny");
+        assert_eq!(explain(&scratch), "warn");
+    }
+
+    #[test]
+    fn terragrunt_destroy_is_denied_by_terraform_pack() {
+        let packs = std::ffi::OsStr::new("infrastructure.terraform");
+        for command in ["terragrunt destroy", "terragrunt run-all destroy"] {
+            let result = run_dcg_hook_with_env(command, &[("DCG_PACKS", packs)]);
+            let stdout = result.stdout_str();
+            let json: serde_json::Value = serde_json::from_str(stdout.trim())
+                .unwrap_or_else(|_| panic!("{command} should be denied\nstdout:\n{stdout}"));
+            assert_eq!(
+                json["hookSpecificOutput"]["permissionDecision"], "deny",
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn script_targets_resolve_in_hook_cwd() {
+        let temp = tempfile::tempdir().unwrap();
+        let home_dir = temp.path().join("home");
+        let project = temp.path().join("project");
+        for dir in [&home_dir, &project] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(project.join("Makefile"), "clean:\n\trm -rf /\n").unwrap();
+        let config_path = temp.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            "[evaluation]\nresolve_script_targets = true\n",
+        )
+        .unwrap();
+
+        let input = serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "make clean" },
+            "cwd": project,
+        });
+        // The process runs outside the project, so only the hook's cwd finds the Makefile.
+        let mut child = Command::new(dcg_binary())
+            .env_clear()
+            .env("HOME", &home_dir)
+            .env("XDG_CONFIG_HOME", temp.path().join("xdg"))
+            .env("DCG_ALLOWLIST_SYSTEM_PATH", "")
+            .env("DCG_PACKS", "core.filesystem")
+            .env("DCG_CONFIG", &config_path)
+            .current_dir(temp.path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to spawn dcg hook mode");
+        serde_json::to_writer(child.stdin.as_mut().unwrap(), &input).unwrap();
+        let output = child.wait_with_output().expect("failed to wait for dcg");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+        assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+    }
+
+    #[test]
+    fn powershell_log_keeps_submitted_command() {
+        let temp = tempfile::tempdir().unwrap();
+        let home_dir = temp.path().join("home");
+        std::fs::create_dir_all(&home_dir).unwrap();
+        let log_path = temp.path().join("blocked.log");
+        let config_path = temp.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            format!("[general]\nlog_file = {:?}\n", log_path.to_str().unwrap()),
+        )
+        .unwrap();
+
+        let input = serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": r"rm -r -fo C:\data" },
+        });
+        let mut child = Command::new(dcg_binary())
+            .env_clear()
+            .env("HOME", &home_dir)
+            .env("XDG_CONFIG_HOME", temp.path().join("xdg"))
+            .env("DCG_ALLOWLIST_SYSTEM_PATH", "")
+            .env("DCG_SHELL", "powershell")
+            .env("DCG_CONFIG", &config_path)
+            .current_dir(temp.path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to spawn dcg hook mode");
+        serde_json::to_writer(child.stdin.as_mut().unwrap(), &input).unwrap();
+        let output = child.wait_with_output().expect("failed to wait for dcg");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+        assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+
+        let log = std::fs::read_to_string(&log_path).expect("blocked command log");
+        assert!(log.contains(r"  Command: rm -r -fo C:\data"), "{log}");
+        assert!(
+            log.contains(r"  Evaluated as: Remove-Item -Recurse -Force C:\data"),
+            "{log}"
+        );
+    }
+
+    #[test]
+    fn no_history_suppresses_logging_when_history_enabled() {
+        let temp = tempfile::tempdir().unwrap();
+        let home_dir = temp.path().join("home");
+        std::fs::create_dir_all(&home_dir).unwrap();
+        let db_path = temp.path().join("history.db");
+
+        let run = |args: &[&str], env: &[(&str, &str)]| {
+            let input = serde_json::json!({
+                "tool_name": "Bash",
+                "tool_input": { "command": "git reset --hard" },
+            });
+            let mut child = Command::new(dcg_binary())
+                .env_clear()
+                .env("HOME", &home_dir)
+                .env("XDG_CONFIG_HOME", temp.path().join("xdg"))
+                .env("DCG_ALLOWLIST_SYSTEM_PATH", "")
+                .env("DCG_HISTORY_ENABLED", "true")
+                .env("DCG_HISTORY_DB", &db_path)
+                .envs(env.iter().copied())
+                .args(args)
+                .current_dir(temp.path())
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("failed to spawn dcg hook mode");
+            serde_json::to_writer(child.stdin.as_mut().unwrap(), &input).unwrap();
+            let output = child.wait_with_output().expect("failed to wait for dcg");
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let json: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+            assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+        };
+
+        // The history writer creates the database when it starts, so a
+        // missing file means nothing was logged.
+        run(&["--no-history"], &[]);
+        run(&[], &[("DCG_NO_HISTORY", "1")]);
+        assert!(
+            !db_path.exists(),
+            "--no-history/DCG_NO_HISTORY must not write history"
+        );
+
+        run(&[], &[]);
+        assert!(db_path.exists(), "history is written without the override");
+    }
+
+    #[test]
+    fn total_timeout_answers_with_on_error_fallback() {
+        // Thousands of segments take far longer than 1ms to evaluate, and the
+        // trailing reset is denied when evaluation is allowed to finish.
+        let slow: Vec<String> = (0..1700)
+            .map(|i| format!("git status && echo {i} | grep x"))
+            .collect();
+        let command = format!("{} ; git reset --hard", slow.join(" ; "));
+        let timeout = std::ffi::OsStr::new("1");
+
+        let start = std::time::Instant::now();
+        let open = run_dcg_hook_with_env(&command, &[("DCG_TOTAL_TIMEOUT_MS", timeout)]);
+        let elapsed = start.elapsed();
+        assert_eq!(open.output.status.code(), Some(0), "{}", open.stderr_str());
+        assert!(
+            open.stdout_str().trim().is_empty(),
+            "on_error = open allows a timed-out evaluation: {}",
+            open.stdout_str()
+        );
+        assert!(
+            elapsed < std::time::Duration::from_secs(5),
+            "timed-out evaluation took {elapsed:?}"
+        );
+
+        let closed = run_dcg_hook_with_env(
+            &command,
+            &[
+                ("DCG_TOTAL_TIMEOUT_MS", timeout),
+                ("DCG_ON_ERROR", std::ffi::OsStr::new("closed")),
+            ],
+        );
+        assert_eq!(closed.output.status.code(), Some(0));
+        let json: serde_json::Value =
+            serde_json::from_str(closed.stdout_str().trim()).expect("deny JSON");
+        assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+        let reason = json["hookSpecificOutput"]["permissionDecisionReason"]
+            .as_str()
+            .unwrap_or_default();
+        assert!(reason.contains("evaluation timed out"), "{reason}");
+
+        let full = run_dcg_hook(&command);
+        let json: serde_json::Value =
+            serde_json::from_str(full.stdout_str().trim()).expect("deny JSON");
+        assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+    }
+
+    #[test]
+    fn measure_records_latency_only_in_enabled_history() {
+        let temp = tempfile::tempdir().unwrap();
+        let home_dir = temp.path().join("home");
+        std::fs::create_dir_all(&home_dir).unwrap();
+        let db_path = temp.path().join("history.db");
+
+        let run = |history_enabled: &str| {
+            let input = serde_json::json!({
+                "tool_name": "Bash",
+                "tool_input": { "command": "git status" },
+            });
+            let mut child = Command::new(dcg_binary())
+                .env_clear()
+                .env("HOME", &home_dir)
+                .env("XDG_CONFIG_HOME", temp.path().join("xdg"))
+                .env("DCG_ALLOWLIST_SYSTEM_PATH", "")
+                .env("DCG_HISTORY_ENABLED", history_enabled)
+                .env("DCG_HISTORY_DB", &db_path)
+                .env("DCG_MEASURE", "1")
+                .current_dir(temp.path())
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("failed to spawn dcg hook mode");
+            serde_json::to_writer(child.stdin.as_mut().unwrap(), &input).unwrap();
+            let output = child.wait_with_output().expect("failed to wait for dcg");
+            assert!(output.status.success());
+            assert!(output.stdout.is_empty(), "allow emits no hook output");
+        };
+
+        // Measuring never overrides `[history] enabled = false`.
+        run("false");
+        assert!(
+            !db_path.exists(),
+            "DCG_MEASURE must not create a disabled history database"
+        );
+
+        run("true");
+        let db = destructive_command_guard::history::HistoryDb::open(Some(db_path))
+            .expect("open history");
+        assert_eq!(db.count_commands().expect("count history"), 1);
+        let entry = db
+            .get_command(1)
+            .expect("query history")
+            .expect("measured evaluation is recorded");
+        assert_eq!(entry.command, "git status");
+        assert_eq!(
+            entry.outcome,
+            destructive_command_guard::history::Outcome::Allow
+        );
+        assert!(entry.eval_duration_us > 0, "{entry:?}");
+    }
+
     fn assert_hook_denies(command: &str) {
         let result = run_dcg_hook(command);
         let stdout = result.stdout_str();
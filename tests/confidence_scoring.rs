@@ -16,7 +16,7 @@ fn mock_deny_result(
     match_span: Option<MatchSpan>,
 ) -> EvaluationResult {
     EvaluationResult {
-        decision: destructive_command_guard::evaluator::EvaluationDecision::Deny,
+        decision: destructive_command_guard::evaluator::Decision::Deny,
         pattern_info: Some(PatternMatch {
             pack_id: Some("core.git".to_string()),
             pattern_name: Some("reset-hard".to_string()),
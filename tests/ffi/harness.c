@@ -0,0 +1,88 @@
+/*
+ * C harness for the dcg C ABI (src/ffi.rs), driven by tests/ffi_c_harness.rs.
+ *
+ * The test binary links dcg statically and does not export its symbols, so
+ * the entry points are handed in as a table of function pointers. Everything
+ * else (types, status codes, out-pointers, ownership) is exercised exactly as
+ * an embedder would.
+ */
+#include <stddef.h>
+#include <string.h>
+
+typedef struct DcgEngine DcgEngine;
+
+typedef enum {
+    DCG_OK = 0,
+    DCG_NULL_ARGUMENT = 1,
+    DCG_INVALID_UTF8 = 2,
+    DCG_INVALID_CONFIG = 3,
+    DCG_INTERNAL_ERROR = 4,
+} DcgStatus;
+
+typedef struct {
+    DcgStatus (*engine_new)(const char *config_toml, DcgEngine **out_engine);
+    DcgStatus (*evaluate)(const DcgEngine *engine, const char *command, char **out_json);
+    void (*free_json)(char *json);
+    void (*engine_free)(DcgEngine *engine);
+} DcgApi;
+
+static int contains(const char *json, const char *needle) {
+    return json != NULL && strstr(json, needle) != NULL;
+}
+
+/* Returns 0 on success, or the number of the first failed check. */
+int dcg_harness_run(const DcgApi *api) {
+    DcgEngine *engine = (DcgEngine *)&engine;
+    char *json = NULL;
+    int ok;
+
+    if (api->engine_new("[packs\n", &engine) != DCG_INVALID_CONFIG || engine != NULL) {
+        return 1;
+    }
+    if (api->engine_new("[packs]\nenabled = [\"messaging.sqs_sns\"]\n", &engine) != DCG_OK
+        || engine == NULL) {
+        return 2;
+    }
+
+    if (api->evaluate(engine, "git reset --hard", &json) != DCG_OK) {
+        return 3;
+    }
+    ok = contains(json, "\"decision\":\"deny\"") && contains(json, "core.git:reset-hard");
+    api->free_json(json);
+    if (!ok) {
+        return 4;
+    }
+
+    if (api->evaluate(engine, "aws sqs purge-queue --queue-url q", &json) != DCG_OK) {
+        return 5;
+    }
+    ok = contains(json, "messaging.sqs_sns");
+    api->free_json(json);
+    if (!ok) {
+        return 6;
+    }
+
+    if (api->evaluate(engine, "git status", &json) != DCG_OK) {
+        return 7;
+    }
+    ok = contains(json, "\"decision\":\"allow\"");
+    api->free_json(json);
+    if (!ok) {
+        return 8;
+    }
+
+    if (api->evaluate(engine, NULL, &json) != DCG_NULL_ARGUMENT || json != NULL) {
+        return 9;
+    }
+    if (api->evaluate(engine, "\xff", &json) != DCG_INVALID_UTF8 || json != NULL) {
+        return 10;
+    }
+    if (api->evaluate(NULL, "ls", &json) != DCG_NULL_ARGUMENT) {
+        return 11;
+    }
+
+    api->free_json(NULL);
+    api->engine_free(engine);
+    api->engine_free(NULL);
+    return 0;
+}
@@ -0,0 +1,86 @@
+//! Drive the C ABI (`--features ffi`) from C code.
+//!
+//! Compiles `tests/ffi/harness.c` into a shared object with the system C
+//! compiler (`$CC`, default `cc`), loads it, and runs `dcg_harness_run` against
+//! the real `dcg_*` entry points. Skipped when no C compiler is available.
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test --features ffi --test ffi_c_harness
+//! ```
+#![cfg(all(unix, feature = "ffi"))]
+
+use destructive_command_guard::ffi::{
+    DcgEngine, DcgStatus, dcg_engine_free, dcg_engine_new, dcg_evaluate, dcg_free,
+};
+use std::ffi::{CString, c_char, c_int};
+use std::path::Path;
+use std::process::Command;
+
+/// Mirror of `DcgApi` in `harness.c`.
+#[repr(C)]
+struct DcgApi {
+    engine_new: unsafe extern "C" fn(*const c_char, *mut *mut DcgEngine) -> DcgStatus,
+    evaluate: unsafe extern "C" fn(*const DcgEngine, *const c_char, *mut *mut c_char) -> DcgStatus,
+    free_json: unsafe extern "C" fn(*mut c_char),
+    engine_free: unsafe extern "C" fn(*mut DcgEngine),
+}
+
+type HarnessRun = unsafe extern "C" fn(*const DcgApi) -> c_int;
+
+fn compile_harness(out: &Path) -> bool {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/ffi/harness.c");
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    match Command::new(&cc)
+        .args(["-shared", "-fPIC", "-Wall", "-Werror", "-o"])
+        .arg(out)
+        .arg(&source)
+        .status()
+    {
+        Ok(status) => {
+            assert!(
+                status.success(),
+                "{cc} failed to compile {}",
+                source.display()
+            );
+            true
+        }
+        Err(e) => {
+            eprintln!("skipping C harness: cannot run {cc}: {e}");
+            false
+        }
+    }
+}
+
+#[test]
+fn c_harness_round_trip() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let library = dir.path().join("libdcg_harness.so");
+    if !compile_harness(&library) {
+        return;
+    }
+
+    let path = CString::new(library.to_str().expect("utf8 path")).unwrap();
+    // SAFETY: `path` is a NUL-terminated path to the library built above.
+    let handle = unsafe { libc::dlopen(path.as_ptr(), libc::RTLD_NOW) };
+    assert!(!handle.is_null(), "dlopen failed");
+    // SAFETY: `handle` is live and the symbol name is NUL-terminated.
+    let symbol = unsafe { libc::dlsym(handle, c"dcg_harness_run".as_ptr()) };
+    assert!(!symbol.is_null(), "dcg_harness_run not found");
+    // SAFETY: `dcg_harness_run` has exactly this signature in harness.c.
+    let run: HarnessRun = unsafe { std::mem::transmute(symbol) };
+
+    let api = DcgApi {
+        engine_new: dcg_engine_new,
+        evaluate: dcg_evaluate,
+        free_json: dcg_free,
+        engine_free: dcg_engine_free,
+    };
+    // SAFETY: the table points at the real C ABI entry points.
+    let failed_check = unsafe { run(&raw const api) };
+    assert_eq!(failed_check, 0, "harness.c check {failed_check} failed");
+
+    // SAFETY: no harness code is running anymore.
+    unsafe { libc::dlclose(handle) };
+}
@@ -126,7 +126,12 @@ fn generate_category_doc(category: &str, packs: &[&Pack]) -> String {
         "system" => "System Packs",
         "safe" => "Safe Packs",
         "strict_git" => "Strict Git Packs",
+        "vcs" => "Version Control Packs",
         "package_managers" => "Package Manager Packs",
+        "ml_ops" => "ML Ops Packs",
+        "sql_migration" => "SQL Migration Packs",
+        "gpg_and_crypto" => "GPG and Disk Encryption Packs",
+        "windows" => "Windows Packs",
         _ => category,
     };
 
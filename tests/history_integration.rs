@@ -272,6 +272,52 @@ fn test_agent_type_tracking() {
     );
 }
 
+/// Test: Overview aggregates top denied rules, outcome ratios, dirs, and agents
+#[test]
+fn test_history_overview_on_standard_mix() {
+    init_test_logging();
+
+    let test_db = TestDb::with_standard_mix();
+    let since = Utc::now() - chrono::Duration::days(30);
+    let overview = test_db.db.compute_overview(since, 10).unwrap();
+
+    // 8 allow, 3 deny, 1 warn, 1 bypass
+    assert_eq!(overview.total_commands, 13);
+    assert_eq!(overview.outcomes.denied, 3);
+    assert_eq!(overview.outcomes.allowed, 8);
+    assert_eq!(overview.outcomes.warned, 1);
+    assert!((overview.ratios.deny - 3.0 / 13.0).abs() < 1e-9);
+    assert!((overview.ratios.allow - 8.0 / 13.0).abs() < 1e-9);
+    assert!((overview.ratios.warn - 1.0 / 13.0).abs() < 1e-9);
+
+    // Only denials count; ties are broken by rule id.
+    let rules: Vec<(&str, u64)> = overview
+        .top_denied_rules
+        .iter()
+        .map(|r| (r.rule_id.as_str(), r.count))
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            ("core.filesystem:rm-recursive-force", 1),
+            ("core.git:force-push", 1),
+            ("core.git:reset-hard", 1),
+        ]
+    );
+
+    assert_eq!(overview.busiest_dirs[0].path, "/data/projects/myapp");
+    assert_eq!(overview.busiest_dirs[0].command_count, 11);
+
+    let claude = &overview.agents[0];
+    assert_eq!(claude.name, "claude_code");
+    assert_eq!(claude.total, 11);
+    assert_eq!(claude.outcomes.denied, 3);
+
+    assert!(overview.latency.p50_us > 0);
+    assert!(overview.latency.p50_us <= overview.latency.p99_us);
+    assert!(overview.latency.p99_us <= overview.latency.max_us);
+}
+
 /// Test: Database file persistence
 #[test]
 fn test_database_persistence() {
@@ -435,6 +481,51 @@ fn test_history_writer_logs_allow() {
     assert_eq!(reader.count_commands().unwrap(), 1);
 }
 
+#[test]
+fn test_history_writer_skips_clean_allows_when_log_allows_false() {
+    init_test_logging();
+
+    let temp_dir = TempDir::new().expect("temp dir");
+    let db_path = temp_dir.path().join("history_writer_log_allows.db");
+
+    let config = HistoryConfig {
+        enabled: true,
+        redaction_mode: HistoryRedactionMode::None,
+        log_allows: false,
+        ..Default::default()
+    };
+    let writer = HistoryWriter::new(Some(db_path.clone()), &config);
+
+    writer.log(CommandEntry {
+        timestamp: Utc::now(),
+        agent_type: "claude_code".to_string(),
+        working_dir: "/tmp".to_string(),
+        command: "git status".to_string(),
+        outcome: Outcome::Allow,
+        ..Default::default()
+    });
+    writer.log(CommandEntry {
+        timestamp: Utc::now(),
+        agent_type: "claude_code".to_string(),
+        working_dir: "/tmp".to_string(),
+        command: "git reset --hard".to_string(),
+        outcome: Outcome::Deny,
+        pack_id: Some("core.git".to_string()),
+        pattern_name: Some("reset-hard".to_string()),
+        ..Default::default()
+    });
+    writer.flush_sync();
+
+    let reader = HistoryDb::open(Some(db_path)).expect("open reader");
+    assert_eq!(reader.count_commands().unwrap(), 1);
+    let stored: String = reader
+        .connection()
+        .query_row("SELECT command FROM commands LIMIT 1")
+        .map(|row| sv_to_string(&row.values()[0]))
+        .unwrap();
+    assert_eq!(stored, "git reset --hard");
+}
+
 #[test]
 fn test_history_writer_respects_disabled() {
     init_test_logging();
@@ -495,6 +586,44 @@ fn test_history_writer_full_redaction() {
     assert_eq!(stored, "[REDACTED]");
 }
 
+#[test]
+fn test_history_writer_redacts_normalized_command() {
+    init_test_logging();
+
+    let temp_dir = TempDir::new().expect("temp dir");
+    let db_path = temp_dir
+        .path()
+        .join("history_writer_normalized_redaction.db");
+
+    let config = HistoryConfig {
+        enabled: true,
+        redaction_mode: HistoryRedactionMode::Pattern,
+        ..Default::default()
+    };
+    let writer = HistoryWriter::new(Some(db_path.clone()), &config);
+
+    writer.log(CommandEntry {
+        timestamp: Utc::now(),
+        agent_type: "claude_code".to_string(),
+        working_dir: "/tmp".to_string(),
+        command: "Invoke-WebRequest -Uri $url -Headers @{Authorization='Bearer hunter2'}"
+            .to_string(),
+        normalized_command: Some("curl $url TOKEN=hunter2".to_string()),
+        outcome: Outcome::Deny,
+        ..Default::default()
+    });
+    writer.flush_sync();
+
+    let reader = HistoryDb::open(Some(db_path)).expect("open reader");
+    let stored: String = reader
+        .connection()
+        .query_row("SELECT normalized_command FROM commands LIMIT 1")
+        .map(|row| sv_to_string(&row.values()[0]))
+        .unwrap();
+    assert!(stored.contains("[REDACTED]"), "not redacted: {stored}");
+    assert!(!stored.contains("hunter2"), "secret leaked: {stored}");
+}
+
 #[test]
 fn test_history_writer_logs_deny_with_match_info() {
     init_test_logging();
@@ -537,6 +666,66 @@ fn test_history_writer_logs_deny_with_match_info() {
     assert_eq!(stored.2, "reset-hard");
 }
 
+#[test]
+fn test_history_writer_records_repo_and_branch() {
+    init_test_logging();
+
+    let temp_dir = TempDir::new().expect("temp dir");
+    let db_path = temp_dir.path().join("history_writer_provenance.db");
+    let repo = temp_dir.path().join("repo");
+    std::fs::create_dir_all(repo.join(".git")).unwrap();
+    std::fs::create_dir_all(repo.join("app")).unwrap();
+    std::fs::write(repo.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+    let outside = temp_dir.path().join("not-a-repo");
+    std::fs::create_dir_all(&outside).unwrap();
+
+    let config = HistoryConfig {
+        enabled: true,
+        redaction_mode: HistoryRedactionMode::None,
+        ..Default::default()
+    };
+    let writer = HistoryWriter::new(Some(db_path.clone()), &config);
+
+    for (dir, command) in [(repo.join("app"), "git reset --hard"), (outside, "ls")] {
+        writer.log(CommandEntry {
+            timestamp: Utc::now(),
+            agent_type: "claude_code".to_string(),
+            working_dir: dir.to_string_lossy().into_owned(),
+            command: command.to_string(),
+            outcome: Outcome::Allow,
+            ..Default::default()
+        });
+    }
+    writer.flush_sync();
+
+    let reader = HistoryDb::open(Some(db_path)).expect("open reader");
+    let rows = reader
+        .connection()
+        .query("SELECT command, repo_root, branch FROM commands ORDER BY command")
+        .unwrap();
+    let stored: Vec<(String, Option<String>, Option<String>)> = rows
+        .iter()
+        .map(|row| {
+            let vals = row.values();
+            (
+                sv_to_string(&vals[0]),
+                sv_to_opt_string(&vals[1]),
+                sv_to_opt_string(&vals[2]),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        stored[0],
+        (
+            "git reset --hard".to_string(),
+            Some(repo.to_string_lossy().into_owned()),
+            Some("main".to_string())
+        )
+    );
+    assert_eq!(stored[1], ("ls".to_string(), None, None));
+}
+
 #[test]
 fn test_history_writer_flushes_on_drop() {
     init_test_logging();
@@ -0,0 +1,195 @@
+//! Versioned contract tests for hook-mode stdout.
+//!
+//! The `PreToolUse` hook response is the surface agents integrate against, so
+//! these tests pin the exact bytes dcg writes to stdout for each decision:
+//!
+//! | Decision | stdout |
+//! |----------|--------|
+//! | allow    | nothing |
+//! | warn     | nothing (the warning goes to stderr) |
+//! | deny     | one JSON line, `permissionDecision: "deny"` with rule/pack/severity/remediation |
+//! | ask      | one JSON line, `permissionDecision: "ask"` |
+//!
+//! Golden files live in `tests/golden/hook_contract/<version>/`. Unlike
+//! `golden_json_tests.rs`, the comparison is byte-for-byte on the raw output,
+//! so key order, whitespace, and the trailing newline are part of the contract.
+//! Only the structure is pinned, though: free-text fields (the reason,
+//! explanation and safe alternative) are replaced with `<TEXT>` so rewording a
+//! rule does not break the contract, and the per-invocation allow-once code and
+//! hash are replaced with `<DYNAMIC>`.
+//!
+//! # Changing the contract
+//!
+//! Adding a field is a compatible change: regenerate the current version with
+//! ```bash
+//! UPDATE_GOLDEN=1 cargo test --test hook_contract
+//! ```
+//! The run fails after rewriting the files so a regeneration never passes
+//! silently; review the diff and rerun without `UPDATE_GOLDEN`.
+//! Renaming or removing a field, or changing a decision value, breaks agents.
+//! Bump `CONTRACT_VERSION` and keep the old directory for reference instead.
+
+mod e2e;
+
+use e2e::framework::{DcgOutput, E2ETestContext};
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Directory under `tests/golden/hook_contract/` holding the current contract.
+const CONTRACT_VERSION: &str = "v1";
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/hook_contract")
+        .join(CONTRACT_VERSION)
+        .join(name)
+}
+
+/// Free-text fields whose wording may change without breaking agents.
+const TEXT_FIELDS: &[&str] = &["permissionDecisionReason", "explanation", "safeAlternative"];
+
+/// Fields that differ on every invocation.
+const DYNAMIC_FIELDS: &[&str] = &["allowOnceCode", "allowOnceFullHash"];
+
+/// Replace free-text and per-invocation values with placeholders, in place in
+/// the raw output so every other byte is still compared.
+fn mask_unstable(stdout: &str) -> String {
+    fn mask_object(object: &Value, masked: &mut String) {
+        let Some(fields) = object.as_object() else {
+            return;
+        };
+        for (field, value) in fields {
+            if value.is_object() {
+                mask_object(value, masked);
+                continue;
+            }
+            let placeholder = if TEXT_FIELDS.contains(&field.as_str()) {
+                "<TEXT>"
+            } else if DYNAMIC_FIELDS.contains(&field.as_str()) {
+                "<DYNAMIC>"
+            } else {
+                continue;
+            };
+            *masked = masked.replace(
+                &format!("\"{field}\":{value}"),
+                &format!("\"{field}\":\"{placeholder}\""),
+            );
+        }
+    }
+
+    let Ok(json) = serde_json::from_str::<Value>(stdout) else {
+        return stdout.to_string();
+    };
+    let Some(output) = json.get("hookSpecificOutput") else {
+        return stdout.to_string();
+    };
+    let mut masked = stdout.to_string();
+    if let Some(code) = output.get("allowOnceCode").and_then(Value::as_str) {
+        masked = masked.replace(
+            &format!("dcg allow-once {code}\""),
+            "dcg allow-once <DYNAMIC>\"",
+        );
+    }
+    mask_object(output, &mut masked);
+    masked
+}
+
+/// Assert that hook stdout matches the golden file byte-for-byte.
+fn assert_contract(output: &DcgOutput, name: &str) {
+    let actual = mask_unstable(&output.stdout);
+    let path = golden_path(name);
+
+    if std::env::var("UPDATE_GOLDEN").is_ok() {
+        std::fs::create_dir_all(path.parent().expect("golden dir")).expect("create golden dir");
+        std::fs::write(&path, &actual).expect("write golden file");
+        panic!(
+            "rewrote {}; review the diff and rerun without UPDATE_GOLDEN",
+            path.display()
+        );
+    }
+
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("missing golden file {}: {e}", path.display()));
+    assert_eq!(
+        actual, expected,
+        "hook stdout for '{name}' changed; if intentional see the module docs\nstderr:\n{}",
+        output.stderr
+    );
+}
+
+fn hook_output(output: &DcgOutput) -> &Value {
+    output
+        .json
+        .as_ref()
+        .and_then(|json| json.get("hookSpecificOutput"))
+        .expect("hookSpecificOutput")
+}
+
+#[test]
+fn contract_allow_emits_nothing() {
+    let ctx = E2ETestContext::builder("contract_allow").build();
+    let output = ctx.run_dcg_hook("git status");
+
+    assert_eq!(output.exit_code, 0);
+    assert_contract(&output, "allow.stdout");
+}
+
+#[test]
+fn contract_warn_emits_nothing_on_stdout() {
+    // Medium severity warns by default.
+    let ctx = E2ETestContext::builder("contract_warn").build();
+    let output = ctx.run_dcg_hook("git branch -D feature");
+
+    assert_eq!(output.exit_code, 0);
+    assert!(
+        output.stderr.contains("dcg WARNING"),
+        "warning should go to stderr: {}",
+        output.stderr
+    );
+    assert_contract(&output, "warn.stdout");
+}
+
+#[test]
+fn contract_deny() {
+    let ctx = E2ETestContext::builder("contract_deny").build();
+    let output = ctx.run_dcg_hook("git reset --hard");
+
+    assert_contract(&output, "deny.stdout");
+
+    let hook = hook_output(&output);
+    assert_eq!(hook["hookEventName"], "PreToolUse");
+    assert_eq!(hook["permissionDecision"], "deny");
+    assert!(
+        hook["permissionDecisionReason"]
+            .as_str()
+            .is_some_and(|reason| !reason.is_empty())
+    );
+    assert_eq!(hook["ruleId"], "core.git:reset-hard");
+    assert_eq!(hook["packId"], "core.git");
+    assert_eq!(hook["severity"], "critical");
+    for field in ["allowOnceCommand", "explanation"] {
+        assert!(
+            hook["remediation"].get(field).is_some(),
+            "remediation.{field} missing"
+        );
+    }
+}
+
+#[test]
+fn contract_ask() {
+    let ctx = E2ETestContext::builder("contract_ask")
+        .with_env("DCG_ASK_ON_PRIVILEGED_UNKNOWN", "true")
+        .build();
+    let output = ctx.run_dcg_hook("sudo ./random-script.sh");
+
+    assert_contract(&output, "ask.stdout");
+
+    let hook = hook_output(&output);
+    assert_eq!(hook["hookEventName"], "PreToolUse");
+    assert_eq!(hook["permissionDecision"], "ask");
+    assert!(
+        hook["permissionDecisionReason"]
+            .as_str()
+            .is_some_and(|reason| reason.contains("./random-script.sh"))
+    );
+}
@@ -39,6 +39,8 @@ fn test_audit_backtracking_requirements() {
                 "rm-force-recursive-tmpdir",
                 "rm-recursive-force-tmpdir-brace",
                 "rm-force-recursive-tmpdir-brace",
+                "rm-rf-var-root",
+                "rm-r-var-root",
             ]),
         ),
         (
@@ -49,6 +51,16 @@ fn test_audit_backtracking_requirements() {
                 "checkout-ref-discard",
                 "restore-worktree",
                 "push-force-long",
+                "checkout-discard",
+                "checkout-discard-pathspec",
+                "checkout-dot",
+                "reset-hard",
+                "reset-hard-pathspec",
+                "checkout-ref-pathspec",
+                "checkout-path-like",
+                "worktree-prune",
+                "tag-delete",
+                "push-force-with-lease-overridden",
             ]),
         ),
         (
@@ -74,21 +86,38 @@ fn test_audit_backtracking_requirements() {
             HashSet::from(["compose-down-no-volumes"]),
         ),
         ("dns.generic", HashSet::from(["dns-dig-safe"])),
+        (
+            "storage.s3",
+            HashSet::from(["s3-sync-no-delete", "s3-rm-recursive", "s3-sync-delete"]),
+        ),
+        ("cloud.azure", HashSet::from(["group-delete-yes"])),
         ("database.mongodb", HashSet::from(["mongodump-no-drop"])),
         ("database.postgresql", HashSet::from(["pg-dump-no-clean"])),
         ("database.redis", HashSet::from(["shutdown"])),
         (
             "infrastructure.ansible",
-            HashSet::from(["playbook-all-hosts"]),
+            HashSet::from([
+                "ansible-check",
+                "ansible-ping",
+                "shell-rm-rf",
+                "file-absent-broad",
+                "user-remove",
+                "playbook-all-hosts",
+            ]),
         ),
         (
             "infrastructure.terraform",
-            HashSet::from(["terraform-plan"]),
+            HashSet::from(["terragrunt-plan"]),
         ),
         ("kubernetes.helm", HashSet::from(["uninstall", "rollback"])),
         (
             "kubernetes.kubectl",
-            HashSet::from(["delete-workload", "delete-pvc", "delete-pv"]),
+            HashSet::from([
+                "delete-workload",
+                "delete-pvc",
+                "delete-pv",
+                "delete-statefulset",
+            ]),
         ),
         (
             "kubernetes.kustomize",
@@ -108,7 +137,6 @@ fn test_audit_backtracking_requirements() {
         (
             "platform.github",
             HashSet::from([
-                // Safe patterns
                 "gh-repo-list-view",
                 "gh-gist-list-view",
                 "gh-release-list-view",
@@ -119,7 +147,6 @@ fn test_audit_backtracking_requirements() {
                 "gh-auth-status",
                 "gh-status",
                 "gh-api-explicit-get",
-                // Destructive patterns
                 "gh-repo-delete",
                 "gh-repo-archive",
                 "gh-gist-delete",
@@ -136,13 +163,79 @@ fn test_audit_backtracking_requirements() {
                 "gh-api-delete-hook",
                 "gh-api-delete-deploy-key",
                 "gh-api-delete-release",
+                "gh-pr-list-view",
             ]),
         ),
         (
             "system.disk",
-            HashSet::from(["fdisk-edit", "parted-modify"]),
+            HashSet::from([
+                "fdisk-edit",
+                "parted-modify",
+                "pvremove-force",
+                "lvremove-force",
+            ]),
+        ),
+        (
+            "system.permissions",
+            HashSet::from(["chmod-non-recursive", "chattr-recursive-immutable-root"]),
+        ),
+        ("database.mysql", HashSet::from(["mysqldump-no-drop"])),
+        ("sql_migration", HashSet::from(["django-migrate"])),
+        (
+            "gpg_and_crypto",
+            HashSet::from([
+                "gpg-list-keys",
+                "ssh-keygen-overwrite-key",
+                "openssl-overwrite-key",
+            ]),
+        ),
+        ("strict_git", HashSet::from(["repack-unreachable-now"])),
+        (
+            "system.mv",
+            HashSet::from(["mv-to-dev-null", "mv-onto-system-dir"]),
+        ),
+        (
+            "containers.podman",
+            HashSet::from([
+                "system-prune-all",
+                "image-prune-all",
+                "rm-all-force",
+                "rmi-all",
+            ]),
+        ),
+        ("cloud.aws", HashSet::from(["s3-sync-delete"])),
+        (
+            "windows.powershell",
+            HashSet::from([
+                "remove-item-drive-root",
+                "remove-item-recurse-force",
+                "clear-content",
+                "format-volume",
+            ]),
+        ),
+        (
+            "system.packages",
+            HashSet::from(["apt-simulate", "dnf-assumeno"]),
+        ),
+        (
+            "system.shell_config",
+            HashSet::from([
+                "authorized-keys-truncate",
+                "ssh-config-truncate",
+                "shell-rc-truncate",
+                "ssh-config-clobber",
+                "shell-rc-clobber",
+                "noclobber-override",
+            ]),
+        ),
+        (
+            "system.systemd",
+            HashSet::from([
+                "journalctl-unit-follow",
+                "systemctl-disable-now",
+                "systemctl-stop-critical",
+            ]),
         ),
-        ("system.permissions", HashSet::from(["chmod-non-recursive"])),
     ]);
 
     let registry = PackRegistry::new();
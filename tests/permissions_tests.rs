@@ -89,3 +89,41 @@ fn chmod_777_symbolic_bypass() {
     let output = run_hook(cmd);
     assert!(output.contains("deny"), "Bypass: '{cmd}' was allowed!");
 }
+
+#[test]
+fn chattr_recursive_immutable_removal_on_root() {
+    // Should BLOCK stripping immutability from the whole filesystem
+    for cmd in [
+        "chattr -R -i /",
+        "sudo chattr -i -R /etc",
+        "chattr -V -R -ia /usr/lib",
+        "chattr -Ri /",
+        "chattr -iR /usr",
+        "sudo chattr -VRai /etc",
+    ] {
+        let output = run_hook(cmd);
+        assert!(
+            output.contains("chattr-recursive-immutable-root"),
+            "Bypass: '{cmd}' was allowed! Output: {output}"
+        );
+    }
+}
+
+#[test]
+fn chattr_false_positives() {
+    // Should ALLOW setting immutability, single files, and project trees
+    for cmd in [
+        "chattr +i /etc/resolv.conf",
+        "chattr -R +i /etc",
+        "chattr -i ./build/output.log",
+        "chattr -R -i /home/user/project",
+        "chattr -Ri ./build && ls -R /",
+        "chattr -i ./build.log; chattr -R +i /etc",
+    ] {
+        let output = run_hook(cmd);
+        assert!(
+            !output.contains("deny"),
+            "False positive: '{cmd}' was blocked! Output: {output}"
+        );
+    }
+}
@@ -1,6 +1,19 @@
 #[cfg(test)]
 mod tests {
+    use destructive_command_guard::config::Config;
     use destructive_command_guard::context::{SpanKind, classify_command};
+    use destructive_command_guard::evaluator::{EvaluationResult, evaluate_command};
+    use destructive_command_guard::load_default_allowlists;
+    use destructive_command_guard::packs::REGISTRY;
+
+    fn evaluate(cmd: &str) -> EvaluationResult {
+        let config = Config::default();
+        let compiled_overrides = config.overrides.compile();
+        let allowlists = load_default_allowlists();
+        let enabled_packs = config.enabled_pack_ids();
+        let keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+        evaluate_command(cmd, &config, &keywords, &compiled_overrides, &allowlists)
+    }
 
     #[test]
     fn test_python_u_c_bypass() {
@@ -39,4 +52,30 @@ mod tests {
             "Failed to detect inline code with intervening flag"
         );
     }
+
+    #[test]
+    fn test_inline_interpreter_benign_code_allowed() {
+        for cmd in [
+            "python -c \"print(1)\"",
+            "node -e \"console.log(1)\"",
+            "ruby -e \"puts 1\"",
+            "perl -e 'print 1'",
+        ] {
+            assert!(evaluate(cmd).is_allowed(), "{cmd} should be allowed");
+        }
+    }
+
+    #[test]
+    fn test_inline_interpreter_destructive_calls_denied() {
+        for cmd in [
+            "python -c \"import shutil; shutil.rmtree('/')\"",
+            "python3 -c \"import os; os.system('git reset --hard')\"",
+            "node -e \"require('fs').rmSync('/', {recursive: true})\"",
+            "node -e \"require('child_process').exec('rm -rf /')\"",
+            "ruby -e \"require 'fileutils'; FileUtils.rm_rf('/')\"",
+            "perl -e 'system(\"rm -rf /\")'",
+        ] {
+            assert!(evaluate(cmd).is_denied(), "{cmd} should be denied");
+        }
+    }
 }
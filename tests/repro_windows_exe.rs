@@ -1,5 +1,5 @@
 use destructive_command_guard::config::Config;
-use destructive_command_guard::evaluator::{EvaluationDecision, MatchSource, evaluate_command};
+use destructive_command_guard::evaluator::{Decision, MatchSource, evaluate_command};
 use destructive_command_guard::load_default_allowlists;
 use destructive_command_guard::packs::REGISTRY;
 use std::collections::HashSet;
@@ -28,7 +28,7 @@ fn test_python_exe_bypass() {
 
     // This should be DENIED because it contains shutil.rmtree
     assert!(
-        result.decision != EvaluationDecision::Allow,
+        result.decision != Decision::Allow,
         "python.exe bypass detected! Command was allowed: {command}"
     );
 
@@ -63,7 +63,7 @@ fn test_python_versioned_exe_bypass() {
     );
 
     assert!(
-        result.decision != EvaluationDecision::Allow,
+        result.decision != Decision::Allow,
         "python3.11.exe bypass detected! Command was allowed: {command}"
     );
 
@@ -1,6 +1,6 @@
 use destructive_command_guard::packs::REGISTRY;
 use destructive_command_guard::{
-    config::Config, evaluator::evaluate_command, load_default_allowlists,
+    Decision, config::Config, evaluator::evaluate_command, load_default_allowlists,
 };
 
 #[test]
@@ -33,3 +33,59 @@ fn test_wrapper_bypasses() {
         );
     }
 }
+
+#[test]
+fn test_find_exec_and_xargs_git_payloads() {
+    let config = Config::default();
+    let compiled_overrides = config.overrides.compile();
+    let allowlists = load_default_allowlists();
+    let enabled_packs = config.enabled_pack_ids();
+    let keywords = REGISTRY.collect_enabled_keywords(&enabled_packs);
+
+    // The git payload of find -exec / xargs goes through the same pack
+    // evaluation as a bare git command, including global flags (-C, -c,
+    // --no-pager) between `git` and the subcommand.
+    let destructive_commands = [
+        (
+            "git branch | grep wip | xargs git branch -D",
+            "branch-force-delete",
+        ),
+        (
+            "git branch | xargs -n1 git -C repo branch -D",
+            "branch-force-delete",
+        ),
+        (
+            r"find . -name '*.lock' -exec git reset --hard \;",
+            "reset-hard",
+        ),
+        (
+            "find . -name .git -execdir git --no-pager reset --hard {} +",
+            "reset-hard",
+        ),
+        ("ls | xargs -I{} git -c core.x=y clean -fdx", "clean-force"),
+    ];
+    for (cmd, pattern) in destructive_commands {
+        let result = evaluate_command(cmd, &config, &keywords, &compiled_overrides, &allowlists);
+        // branch-force-delete is medium severity, so it is reported as Warn.
+        assert_ne!(
+            result.decision,
+            Decision::Allow,
+            "Command '{cmd}' should be blocked"
+        );
+        let info = result.pattern_info.expect("denied command has match info");
+        assert_eq!(info.pack_id.as_deref(), Some("core.git"), "{cmd}");
+        assert_eq!(info.pattern_name.as_deref(), Some(pattern), "{cmd}");
+    }
+
+    for cmd in [
+        "git branch --merged | xargs git branch -d",
+        r"find . -name '*.lock' -exec git status \;",
+    ] {
+        let result = evaluate_command(cmd, &config, &keywords, &compiled_overrides, &allowlists);
+        assert_eq!(
+            result.decision,
+            Decision::Allow,
+            "Command '{cmd}' should be allowed"
+        );
+    }
+}
@@ -16,6 +16,10 @@ use std::process::Command;
 
 /// Run dcg scan command and return output
 fn run_dcg_scan(args: &[&str]) -> std::process::Output {
+    run_dcg_scan_with_env(args, &[])
+}
+
+fn run_dcg_scan_with_env(args: &[&str], env: &[(&str, &str)]) -> std::process::Output {
     let dcg_bin = std::env::var("DCG_BIN")
         .map(std::path::PathBuf::from)
         .unwrap_or_else(|_| {
@@ -26,6 +30,7 @@ fn run_dcg_scan(args: &[&str]) -> std::process::Output {
         });
 
     Command::new(dcg_bin)
+        .envs(env.iter().copied())
         .args(["scan"])
         .args(args)
         .output()
@@ -501,6 +506,101 @@ fn scan_multi_format_repository() {
     );
 }
 
+#[test]
+fn scan_count_only_matches_full_output() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("deploy.sh"),
+        "#!/bin/bash\ngit push --force\nrm -rf /\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("Dockerfile"),
+        "FROM alpine\nRUN git reset --hard\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("skip.sh"), "git reset --hard\n").unwrap();
+
+    let path = dir.path().to_str().unwrap();
+    let full = run_dcg_scan(&[
+        "--paths",
+        path,
+        "--exclude",
+        "**/skip.sh",
+        "--format",
+        "json",
+    ]);
+    let full: serde_json::Value =
+        serde_json::from_str(&String::from_utf8_lossy(&full.stdout)).expect("valid JSON");
+
+    let counts = run_dcg_scan(&[
+        "--paths",
+        path,
+        "--exclude",
+        "**/skip.sh",
+        "--format",
+        "json",
+        "--count-only",
+    ]);
+    let counts: serde_json::Value =
+        serde_json::from_str(&String::from_utf8_lossy(&counts.stdout)).expect("valid JSON");
+
+    let findings = full["findings"].as_array().unwrap();
+    assert!(!findings.is_empty(), "fixture should produce findings");
+    assert!(counts.get("findings").is_none(), "no per-finding output");
+    assert_eq!(
+        counts["findings_total"].as_u64().unwrap(),
+        findings.len() as u64
+    );
+    assert_eq!(counts["severities"], full["summary"]["severities"]);
+    assert!(
+        findings
+            .iter()
+            .all(|f| !f["file"].as_str().unwrap().contains("skip.sh")),
+        "exclude is respected"
+    );
+}
+
+#[test]
+fn scan_dedupe_collapses_identical_findings_across_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.sh"), "docker system prune -af\n").unwrap();
+    std::fs::write(
+        dir.path().join("b.sh"),
+        "echo cleanup\ndocker system prune -af\n",
+    )
+    .unwrap();
+
+    let output = run_dcg_scan_with_env(
+        &[
+            "--paths",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--dedupe",
+        ],
+        &[("DCG_PACKS", "containers.docker")],
+    );
+    let report: serde_json::Value =
+        serde_json::from_str(&String::from_utf8_lossy(&output.stdout)).expect("valid JSON");
+
+    let prune: Vec<_> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|f| f["rule_id"] == "containers.docker:system-prune")
+        .collect();
+    assert_eq!(prune.len(), 1, "one deduped finding: {report}");
+    let occurrences = prune[0]["occurrences"].as_array().unwrap();
+    assert_eq!(occurrences.len(), 2);
+    assert!(occurrences[0]["file"].as_str().unwrap().ends_with("a.sh"));
+    assert_eq!(occurrences[0]["line"], 1);
+    assert!(occurrences[1]["file"].as_str().unwrap().ends_with("b.sh"));
+    assert_eq!(occurrences[1]["line"], 2);
+    assert_eq!(report["summary"]["findings_total"], 2);
+    assert_eq!(report["summary"]["findings_deduped"], 1);
+}
+
 // ============================================================================
 // Performance Test
 // ============================================================================